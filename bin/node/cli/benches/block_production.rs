@@ -67,7 +67,9 @@ fn new_node(tokio_handle: Handle) -> node_cli::service::NewFullBase {
 		keystore: KeystoreConfig::InMemory,
 		database: DatabaseSource::RocksDb { path: root.join("db"), cache_size: 128 },
 		trie_cache_maximum_size: Some(64 * 1024 * 1024),
+		flat_state_cache: false,
 		state_pruning: Some(PruningMode::ArchiveAll),
+		state_pruning_background: None,
 		blocks_pruning: BlocksPruning::KeepAll,
 		chain_spec: spec,
 		wasm_method: WasmExecutionMethod::Compiled {
@@ -93,6 +95,10 @@ fn new_node(tokio_handle: Handle) -> node_cli::service::NewFullBase {
 		tracing_receiver: Default::default(),
 		max_runtime_instances: 8,
 		runtime_cache_size: 2,
+		wasmtime_artifact_cache_size: None,
+		host_call_metrics: false,
+		rpc_fuel_limit: None,
+		rpc_runtime_api_fuel_limit: None,
 		announce_block: true,
 		data_path: base_path.path().into(),
 		base_path,
@@ -30,7 +30,7 @@ use sc_service::{
 	},
 	BasePath, Configuration, Role,
 };
-use sc_transaction_pool::PoolLimit;
+use sc_transaction_pool::{PoolLimit, PoolSenderLimit};
 use sc_transaction_pool_api::{TransactionPool as _, TransactionSource, TransactionStatus};
 use sp_core::{crypto::Pair, sr25519};
 use sp_keyring::Sr25519Keyring;
@@ -58,14 +58,22 @@ fn new_node(tokio_handle: Handle) -> node_cli::service::NewFullBase {
 		transaction_pool: TransactionPoolOptions {
 			ready: PoolLimit { count: 100_000, total_bytes: 100 * 1024 * 1024 },
 			future: PoolLimit { count: 100_000, total_bytes: 100 * 1024 * 1024 },
+			sender: PoolSenderLimit {
+				tag_prefix_len: 32,
+				limit: PoolLimit { count: 100_000, total_bytes: 100 * 1024 * 1024 },
+			},
 			reject_future_transactions: false,
 			ban_time: Duration::from_secs(30 * 60),
+			journal: None,
+			pool_type: Default::default(),
 		},
 		network: network_config,
 		keystore: KeystoreConfig::InMemory,
 		database: DatabaseSource::RocksDb { path: root.join("db"), cache_size: 128 },
 		trie_cache_maximum_size: Some(64 * 1024 * 1024),
+		flat_state_cache: false,
 		state_pruning: Some(PruningMode::ArchiveAll),
+		state_pruning_background: None,
 		blocks_pruning: BlocksPruning::KeepAll,
 		chain_spec: spec,
 		wasm_method: Default::default(),
@@ -89,6 +97,10 @@ fn new_node(tokio_handle: Handle) -> node_cli::service::NewFullBase {
 		tracing_receiver: Default::default(),
 		max_runtime_instances: 8,
 		runtime_cache_size: 2,
+		wasmtime_artifact_cache_size: None,
+		host_call_metrics: false,
+		rpc_fuel_limit: None,
+		rpc_runtime_api_fuel_limit: None,
 		announce_block: true,
 		data_path: base_path.path().into(),
 		base_path,
@@ -365,6 +365,8 @@ pub fn testnet_genesis(
 			..Default::default()
 		},
 		glutton: Default::default(),
+		safe_mode: Default::default(),
+		tx_pause: Default::default(),
 	}
 }
 
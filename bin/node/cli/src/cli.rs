@@ -90,9 +90,15 @@ pub enum Subcommand {
 	/// Export the state of a given block into a chain spec.
 	ExportState(sc_cli::ExportStateCmd),
 
+	/// Export the latest finalized state into a snapshot file.
+	ExportSnapshot(sc_cli::ExportSnapshotCmd),
+
 	/// Import blocks.
 	ImportBlocks(sc_cli::ImportBlocksCmd),
 
+	/// Initialize an empty database from a snapshot file.
+	ImportSnapshot(sc_cli::ImportSnapshotCmd),
+
 	/// Remove the whole chain.
 	PurgeChain(sc_cli::PurgeChainCmd),
 
@@ -101,4 +107,10 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Convert an archive database into one keeping the state of recent blocks only.
+	PruneState(sc_cli::PruneStateCmd),
+
+	/// Compile a runtime ahead of a runtime upgrade.
+	PrecompileRuntime(sc_cli::PrecompileRuntimeCmd),
 }
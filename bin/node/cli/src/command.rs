@@ -22,6 +22,7 @@ use crate::{
 	service::{new_partial, FullClient},
 	Cli, Subcommand,
 };
+use codec::Decode;
 use frame_benchmarking_cli::*;
 use kitchensink_runtime::{ExistentialDeposit, RuntimeApi};
 use node_executor::ExecutorDispatch;
@@ -202,6 +203,20 @@ pub fn run() -> Result<()> {
 				Ok((cmd.run(client, config.chain_spec), task_manager))
 			})
 		},
+		Some(Subcommand::ExportSnapshot(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.async_run(|config| {
+				let PartialComponents { client, backend, task_manager, other, .. } =
+					new_partial(&config)?;
+				let warp_sync = Arc::new(grandpa::warp_proof::NetworkProvider::new(
+					backend,
+					other.1 .1.shared_authority_set().clone(),
+					Vec::default(),
+				));
+				let genesis_authorities = genesis_grandpa_authorities(&*config.chain_spec)?;
+				Ok((cmd.run(client, warp_sync, genesis_authorities), task_manager))
+			})
+		},
 		Some(Subcommand::ImportBlocks(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
@@ -210,6 +225,23 @@ pub fn run() -> Result<()> {
 				Ok((cmd.run(client, import_queue), task_manager))
 			})
 		},
+		Some(Subcommand::ImportSnapshot(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.async_run(|mut config| {
+				// Keep the genesis state out of the database, the state of the snapshot is
+				// written in its place.
+				config.network.sync_mode = sc_service::config::SyncMode::Warp;
+				let PartialComponents {
+					client, backend, task_manager, import_queue, other, ..
+				} = new_partial(&config)?;
+				let warp_sync = Arc::new(grandpa::warp_proof::NetworkProvider::new(
+					backend.clone(),
+					other.1 .1.shared_authority_set().clone(),
+					Vec::default(),
+				));
+				Ok((cmd.run(client, backend, import_queue, warp_sync), task_manager))
+			})
+		},
 		Some(Subcommand::PurgeChain(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|config| cmd.run(config.database))
@@ -257,5 +289,29 @@ pub fn run() -> Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|config| cmd.run::<Block>(&config))
 		},
+		Some(Subcommand::PruneState(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| cmd.run::<Block>(&config))
+		},
+		Some(Subcommand::PrecompileRuntime(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| cmd.run(&config))
+		},
 	}
 }
+
+/// Read the GRANDPA authorities of the genesis block from the chain spec.
+///
+/// Unlike the runtime API, this does not need the genesis state, which pruned nodes discard.
+fn genesis_grandpa_authorities(
+	chain_spec: &dyn sc_service::ChainSpec,
+) -> sc_cli::Result<grandpa_primitives::AuthorityList> {
+	let storage = chain_spec.as_storage_builder().build_storage()?;
+	let authorities = storage
+		.top
+		.get(grandpa_primitives::GRANDPA_AUTHORITIES_KEY)
+		.ok_or("Genesis state has no GRANDPA authorities")?;
+	let authorities = grandpa_primitives::VersionedAuthorityList::decode(&mut &authorities[..])
+		.map_err(|e| format!("Invalid genesis GRANDPA authorities: {}", e))?;
+	Ok(authorities.into())
+}
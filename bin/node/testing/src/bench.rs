@@ -384,6 +384,8 @@ impl BenchDb {
 			state_pruning: Some(PruningMode::ArchiveAll),
 			source: database_type.into_settings(dir.into()),
 			blocks_pruning: sc_client_db::BlocksPruning::KeepAll,
+			background_pruning: None,
+			flat_state: false,
 		};
 		let task_executor = TaskExecutor::new();
 
@@ -107,5 +107,7 @@ pub fn config_endowed(code: Option<&[u8]>, extra_endowed: Vec<AccountId>) -> Run
 			trash_data_count: Default::default(),
 			..Default::default()
 		},
+		safe_mode: Default::default(),
+		tx_pause: Default::default(),
 	}
 }
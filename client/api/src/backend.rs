@@ -36,7 +36,7 @@ use sp_storage::{ChildInfo, StorageData, StorageKey};
 
 use crate::{blockchain::Backend as BlockchainBackend, UsageInfo};
 
-pub use sp_state_machine::{Backend as StateBackend, KeyValueStates};
+pub use sp_state_machine::{Backend as StateBackend, KeyValueStates, MerkleValue};
 
 /// Extracts the state backend type for the given backend.
 pub type StateBackendFor<B, Block> = <B as Backend<Block>>::State;
@@ -200,6 +200,14 @@ pub trait BlockImportOperation<Block: BlockT> {
 		state_version: StateVersion,
 	) -> sp_blockchain::Result<Block::Hash>;
 
+	/// Use the state with the given root, written with [`Backend::import_state_trie`], as the
+	/// state of the block.
+	///
+	/// The root is expected to be checked against the header of the block by the caller.
+	fn set_imported_state(&mut self, _root: Block::Hash) -> sp_blockchain::Result<()> {
+		Err(sp_blockchain::Error::Backend("Importing a state in parts is not supported".into()))
+	}
+
 	/// Set storage changes.
 	fn update_storage(
 		&mut self,
@@ -476,6 +484,21 @@ pub trait StorageProvider<Block: BlockT, B: Backend<Block>> {
 		child_info: &ChildInfo,
 		key: &StorageKey,
 	) -> sp_blockchain::Result<Option<Block::Hash>>;
+
+	/// Given a block's `Hash` and a key, return the closest merkle value.
+	fn closest_merkle_value(
+		&self,
+		hash: Block::Hash,
+		key: &StorageKey,
+	) -> sp_blockchain::Result<Option<MerkleValue<Block::Hash>>>;
+
+	/// Given a block's `Hash`, a key and a child storage key, return the closest merkle value.
+	fn child_closest_merkle_value(
+		&self,
+		hash: Block::Hash,
+		child_info: &ChildInfo,
+		key: &StorageKey,
+	) -> sp_blockchain::Result<Option<MerkleValue<Block::Hash>>>;
 }
 
 /// Client backend.
@@ -576,6 +599,21 @@ pub trait Backend<Block: BlockT>: AuxStore + Send + Sync {
 	/// Returns state backend with post-state of given block.
 	fn state_at(&self, hash: Block::Hash) -> sp_blockchain::Result<Self::State>;
 
+	/// Write a trie of a state straight to the database and return its root.
+	///
+	/// Used to import a state that doesn't fit in memory ahead of its block, one trie at a time:
+	/// the child tries first, then the top trie holding their roots. `key_values` must be sorted
+	/// by key. The block is then imported with
+	/// [`BlockImportOperation::set_imported_state`].
+	fn import_state_trie(
+		&self,
+		_child_info: Option<&ChildInfo>,
+		_state_version: StateVersion,
+		_key_values: &mut dyn Iterator<Item = (Vec<u8>, Vec<u8>)>,
+	) -> sp_blockchain::Result<Block::Hash> {
+		Err(sp_blockchain::Error::Backend("Importing a state in parts is not supported".into()))
+	}
+
 	/// Attempts to revert the chain by `n` blocks. If `revert_finalized` is set it will attempt to
 	/// revert past any finalized block, this is unsafe and can potentially leave the node in an
 	/// inconsistent state. All blocks higher than the best block are also reverted and not counting
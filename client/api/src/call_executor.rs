@@ -62,6 +62,19 @@ pub trait CallExecutor<B: BlockT>: RuntimeVersionOf {
 		context: CallContext,
 	) -> Result<Vec<u8>, sp_blockchain::Error>;
 
+	/// Execute a call on behalf of an RPC request on top of state in a block of given hash.
+	///
+	/// Same as [`Self::call`] with [`CallContext::Offchain`], but allows the executor to limit the
+	/// resources of calls coming from untrusted sources. No changes are made.
+	fn rpc_call(
+		&self,
+		at_hash: B::Hash,
+		method: &str,
+		call_data: &[u8],
+	) -> Result<Vec<u8>, sp_blockchain::Error> {
+		self.call(at_hash, method, call_data, CallContext::Offchain)
+	}
+
 	/// Execute a contextual call on top of state in a block of a given hash.
 	///
 	/// No changes are made.
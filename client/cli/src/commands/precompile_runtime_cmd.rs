@@ -0,0 +1,80 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	error,
+	params::{ImportParams, RuntimeParams, SharedParams},
+	CliConfiguration,
+};
+use clap::Parser;
+use log::info;
+use sc_service::{chain_ops::precompile_runtime, Configuration};
+use std::{fs, path::PathBuf};
+
+/// The `precompile-runtime` command used to compile a runtime ahead of a runtime upgrade.
+///
+/// The compiled runtime is stored in the on-disk cache of compiled runtimes of the node, which
+/// loads it from there once the runtime is enacted instead of compiling it.
+#[derive(Debug, Clone, Parser)]
+pub struct PrecompileRuntimeCmd {
+	/// Path to the runtime wasm blob, as it is going to be stored on chain.
+	#[arg()]
+	pub runtime: PathBuf,
+
+	/// The number of extra heap pages the runtime is going to be executed with on chain.
+	///
+	/// Defaults to the default of the node.
+	#[arg(long, value_name = "COUNT")]
+	pub heap_pages: Option<u64>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub import_params: ImportParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub runtime_params: RuntimeParams,
+}
+
+impl PrecompileRuntimeCmd {
+	/// Run the `precompile-runtime` command
+	pub fn run(&self, config: &Configuration) -> error::Result<()> {
+		let code = fs::read(&self.runtime)?;
+		let path = precompile_runtime(config, &code, self.heap_pages)?;
+		info!("Runtime {} compiled to {}", self.runtime.display(), path.display());
+		Ok(())
+	}
+}
+
+impl CliConfiguration for PrecompileRuntimeCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn import_params(&self) -> Option<&ImportParams> {
+		Some(&self.import_params)
+	}
+
+	fn wasmtime_artifact_cache_size(&self) -> error::Result<Option<u64>> {
+		Ok(self.runtime_params.wasmtime_artifact_cache_size())
+	}
+}
@@ -0,0 +1,83 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{CliConfiguration, DatabaseParams, Error, Result as CliResult, SharedParams};
+use clap::Parser;
+use log::info;
+use sp_runtime::traits::Block as BlockT;
+
+/// The `prune-state` command used to convert an archive database into a pruned one.
+///
+/// Only RocksDB databases can be converted. Nodes using ParityDB need to resync with the desired
+/// `--state-pruning` instead.
+#[derive(Debug, Clone, Parser)]
+pub struct PruneStateCmd {
+	/// Number of recent finalized blocks whose state is kept.
+	///
+	/// The database is opened in its stored archive mode, the state of older blocks is deleted
+	/// and the state pruning mode becomes the given number of blocks. Later runs must use a
+	/// numeric `--state-pruning` value.
+	#[arg(long, value_name = "COUNT", default_value_t = 256)]
+	pub blocks: u32,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl PruneStateCmd {
+	/// Run the `prune-state` command
+	pub fn run<B>(&self, config: &sc_service::Configuration) -> CliResult<()>
+	where
+		B: BlockT,
+	{
+		if matches!(config.database, sc_client_db::DatabaseSource::ParityDb { .. }) {
+			return Err(Error::Input(
+				"prune-state doesn't support ParityDB databases, whose state is reference \
+				 counted. Resync the node with the desired `--state-pruning` instead"
+					.into(),
+			))
+		}
+
+		let db_config = sc_client_db::DatabaseSettings {
+			trie_cache_maximum_size: config.trie_cache_maximum_size,
+			state_pruning: None,
+			source: config.database.clone(),
+			blocks_pruning: config.blocks_pruning,
+			background_pruning: None,
+			flat_state: false,
+		};
+		let deleted = sc_client_db::prune_archive_state::<B>(db_config, self.blocks)?;
+		info!("Pruned the database state: {} nodes deleted", deleted);
+		Ok(())
+	}
+}
+
+impl CliConfiguration for PruneStateCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
@@ -17,7 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-	arg_enums::RpcMethods,
+	arg_enums::{RpcMethods, WasmtimeInstantiationStrategy},
 	error::{Error, Result},
 	params::{
 		ImportParams, KeystoreParams, NetworkParams, OffchainWorkerParams, SharedParams,
@@ -30,11 +30,14 @@ use crate::{
 use clap::Parser;
 use regex::Regex;
 use sc_service::{
-	config::{BasePath, PrometheusConfig, TransactionPoolOptions},
+	config::{BasePath, PrometheusConfig, TransactionPoolJournalOptions, TransactionPoolOptions},
 	ChainSpec, Role,
 };
 use sc_telemetry::TelemetryEndpoints;
-use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::{
+	net::{IpAddr, Ipv4Addr, SocketAddr},
+	path::PathBuf,
+};
 
 /// The `run` command used to run a node.
 #[derive(Debug, Clone, Parser)]
@@ -220,6 +223,27 @@ impl RunCmd {
 			None
 		}
 	}
+
+	/// Returns the given `fuel_limit` of the `flag`, unless fuel metering isn't supported by the
+	/// instantiation strategy.
+	fn check_fuel_limit_supported(
+		&self,
+		flag: &str,
+		fuel_limit: Option<u64>,
+	) -> Result<Option<u64>> {
+		if fuel_limit.is_some() &&
+			matches!(
+				self.import_params.wasmtime_instantiation_strategy,
+				WasmtimeInstantiationStrategy::LegacyInstanceReuse
+			) {
+			return Err(Error::Input(format!(
+				"{} is not supported with the legacy-instance-reuse instantiation strategy",
+				flag,
+			)))
+		}
+
+		Ok(fuel_limit)
+	}
 }
 
 impl CliConfiguration for RunCmd {
@@ -369,6 +393,13 @@ impl CliConfiguration for RunCmd {
 		Ok(self.pool_config.transaction_pool(is_dev))
 	}
 
+	fn transaction_pool_journal(
+		&self,
+		config_dir: &PathBuf,
+	) -> Result<Option<TransactionPoolJournalOptions>> {
+		Ok(self.pool_config.journal(config_dir))
+	}
+
 	fn max_runtime_instances(&self) -> Result<Option<usize>> {
 		Ok(Some(self.runtime_params.max_runtime_instances))
 	}
@@ -377,6 +408,25 @@ impl CliConfiguration for RunCmd {
 		Ok(self.runtime_params.runtime_cache_size)
 	}
 
+	fn wasmtime_artifact_cache_size(&self) -> Result<Option<u64>> {
+		Ok(self.runtime_params.wasmtime_artifact_cache_size())
+	}
+
+	fn host_call_metrics(&self) -> Result<bool> {
+		Ok(self.runtime_params.host_call_metrics)
+	}
+
+	fn rpc_fuel_limit(&self) -> Result<Option<u64>> {
+		self.check_fuel_limit_supported("--rpc-fuel-limit", self.runtime_params.rpc_fuel_limit)
+	}
+
+	fn rpc_runtime_api_fuel_limit(&self) -> Result<Option<u64>> {
+		self.check_fuel_limit_supported(
+			"--rpc-runtime-api-fuel-limit",
+			self.runtime_params.rpc_runtime_api_fuel_limit,
+		)
+	}
+
 	fn base_path(&self) -> Result<Option<BasePath>> {
 		Ok(if self.tmp {
 			Some(BasePath::new_temp_dir()?)
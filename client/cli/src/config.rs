@@ -28,9 +28,10 @@ use sc_service::{
 	config::{
 		BasePath, Configuration, DatabaseSource, KeystoreConfig, NetworkConfiguration,
 		NodeKeyConfig, OffchainWorkerConfig, PrometheusConfig, PruningMode, Role, RpcMethods,
-		TelemetryEndpoints, TransactionPoolOptions, WasmExecutionMethod,
+		TelemetryEndpoints, TransactionPoolJournalOptions, TransactionPoolOptions,
+		WasmExecutionMethod,
 	},
-	BlocksPruning, ChainSpec, TracingReceiver,
+	BackgroundPruning, BlocksPruning, ChainSpec, TracingReceiver,
 };
 use sc_tracing::logging::LoggerBuilder;
 use std::{net::SocketAddr, path::PathBuf};
@@ -152,6 +153,16 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		Ok(Default::default())
 	}
 
+	/// Get the transaction pool journal options
+	///
+	/// By default the journal is disabled.
+	fn transaction_pool_journal(
+		&self,
+		_config_dir: &PathBuf,
+	) -> Result<Option<TransactionPoolJournalOptions>> {
+		Ok(None)
+	}
+
 	/// Get the network configuration
 	///
 	/// By default this is retrieved from `NetworkParams` if it is available otherwise it creates
@@ -241,6 +252,14 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		Ok(self.import_params().map(|x| x.trie_cache_maximum_size()).unwrap_or_default())
 	}
 
+	/// Whether to keep a flat in-memory copy of the finalized state.
+	///
+	/// By default this is retrieved from `ImportParams` if it is available. Otherwise its
+	/// `false`.
+	fn flat_state_cache(&self) -> Result<bool> {
+		Ok(self.import_params().map(|x| x.flat_state_cache).unwrap_or_default())
+	}
+
 	/// Get the state pruning mode.
 	///
 	/// By default this is retrieved from `PruningMode` if it is available. Otherwise its
@@ -251,6 +270,14 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			.unwrap_or_else(|| Ok(Default::default()))
 	}
 
+	/// Get the background state pruning settings.
+	///
+	/// By default this is retrieved from `PruningParams` if it is available. Otherwise the state
+	/// is pruned during block finalization.
+	fn state_pruning_background(&self) -> Result<Option<BackgroundPruning>> {
+		Ok(self.pruning_params().and_then(|x| x.state_pruning_background()))
+	}
+
 	/// Get the block pruning mode.
 	///
 	/// By default this is retrieved from `block_pruning` if it is available. Otherwise its
@@ -428,6 +455,35 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		Ok(2)
 	}
 
+	/// Get the maximum size in bytes of the on-disk cache of compiled runtimes.
+	///
+	/// By default this is `None`, which disables the cache.
+	fn wasmtime_artifact_cache_size(&self) -> Result<Option<u64>> {
+		Ok(Default::default())
+	}
+
+	/// Returns `Ok(true)` if the host functions called by the runtime should be profiled for
+	/// Prometheus.
+	///
+	/// By default this is `false`.
+	fn host_call_metrics(&self) -> Result<bool> {
+		Ok(Default::default())
+	}
+
+	/// Get the maximum amount of fuel of the runtime calls serving RPC requests.
+	///
+	/// By default this is `None`, which disables the limit.
+	fn rpc_fuel_limit(&self) -> Result<Option<u64>> {
+		Ok(Default::default())
+	}
+
+	/// Get the maximum amount of fuel of the runtime API calls made by RPC methods.
+	///
+	/// By default this is `None`, which disables the limit.
+	fn rpc_runtime_api_fuel_limit(&self) -> Result<Option<u64>> {
+		Ok(Default::default())
+	}
+
 	/// Activate or not the automatic announcing of blocks after import
 	///
 	/// By default this is `false`.
@@ -468,12 +524,16 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		let keystore = self.keystore_config(&config_dir)?;
 		let telemetry_endpoints = self.telemetry_endpoints(&chain_spec)?;
 		let runtime_cache_size = self.runtime_cache_size()?;
+		let mut transaction_pool = self.transaction_pool(is_dev)?;
+		if let Some(journal) = self.transaction_pool_journal(&config_dir)? {
+			transaction_pool.journal = Some(journal);
+		}
 
 		Ok(Configuration {
 			impl_name: C::impl_name(),
 			impl_version: C::impl_version(),
 			tokio_handle,
-			transaction_pool: self.transaction_pool(is_dev)?,
+			transaction_pool,
 			network: self.network_config(
 				&chain_spec,
 				is_dev,
@@ -488,7 +548,9 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			database: self.database_config(&config_dir, database_cache_size, database)?,
 			data_path: config_dir,
 			trie_cache_maximum_size: self.trie_cache_maximum_size()?,
+			flat_state_cache: self.flat_state_cache()?,
 			state_pruning: self.state_pruning()?,
+			state_pruning_background: self.state_pruning_background()?,
 			blocks_pruning: self.blocks_pruning()?,
 			wasm_method: self.wasm_method()?,
 			wasm_runtime_overrides: self.wasm_runtime_overrides(),
@@ -518,6 +580,10 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			base_path,
 			informant_output_format: Default::default(),
 			runtime_cache_size,
+			wasmtime_artifact_cache_size: self.wasmtime_artifact_cache_size()?,
+			host_call_metrics: self.host_call_metrics()?,
+			rpc_fuel_limit: self.rpc_fuel_limit()?,
+			rpc_runtime_api_fuel_limit: self.rpc_runtime_api_fuel_limit()?,
 		})
 	}
 
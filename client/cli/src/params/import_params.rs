@@ -78,6 +78,13 @@ pub struct ImportParams {
 	#[arg(long, value_name = "Bytes", default_value_t = 67108864)]
 	pub trie_cache_size: usize,
 
+	/// Keep a flat copy of the finalized state in the database.
+	///
+	/// Storage reads at recent blocks are answered from this copy instead of walking the trie,
+	/// at the cost of storing every value of the finalized state a second time.
+	#[arg(long)]
+	pub flat_state_cache: bool,
+
 	/// DEPRECATED
 	/// Switch to `--trie-cache-size`.
 	#[arg(long)]
@@ -18,7 +18,7 @@
 
 use crate::error;
 use clap::Args;
-use sc_service::{BlocksPruning, PruningMode};
+use sc_service::{BackgroundPruning, BlocksPruning, PruningMode};
 
 /// Parameters to define the pruning mode
 #[derive(Debug, Clone, Args)]
@@ -52,6 +52,16 @@ pub struct PruningParams {
 		default_value = "archive-canonical"
 	)]
 	pub blocks_pruning: DatabasePruningMode,
+
+	/// Prune the state on a background worker instead of during block finalization.
+	///
+	/// Has no effect when the state pruning mode is archive.
+	#[arg(long)]
+	pub state_pruning_background: bool,
+
+	/// Maximum number of blocks whose state is pruned per tick of the background worker.
+	#[arg(long, value_name = "COUNT", default_value_t = 32)]
+	pub state_pruning_blocks_per_tick: u32,
 }
 
 impl PruningParams {
@@ -64,6 +74,14 @@ impl PruningParams {
 	pub fn blocks_pruning(&self) -> error::Result<BlocksPruning> {
 		Ok(self.blocks_pruning.into())
 	}
+
+	/// Get the background state pruning settings from the parameters
+	pub fn state_pruning_background(&self) -> Option<BackgroundPruning> {
+		self.state_pruning_background.then(|| BackgroundPruning {
+			blocks_per_tick: self.state_pruning_blocks_per_tick,
+			..Default::default()
+		})
+	}
 }
 
 /// Specifies the pruning mode of the database.
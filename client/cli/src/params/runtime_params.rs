@@ -29,6 +29,52 @@ pub struct RuntimeParams {
 	/// Maximum number of different runtimes that can be cached.
 	#[arg(long, default_value_t = 2)]
 	pub runtime_cache_size: u8,
+
+	/// Maximum size in MiB of the on-disk cache of compiled runtimes.
+	///
+	/// Compiled runtimes are stored in the chain's data directory and reused across restarts.
+	/// The least recently used ones are removed once the cache exceeds this size.
+	/// `0` disables the cache.
+	#[arg(long, value_name = "MiB", default_value_t = 1024)]
+	pub wasmtime_artifact_cache_size: u64,
+
+	/// Profile the host functions called by the runtime and export the number of calls, the
+	/// bytes read and written and the time spent per host function to Prometheus.
+	///
+	/// This slows down the execution of the runtime.
+	#[arg(long)]
+	pub host_call_metrics: bool,
+
+	/// Maximum amount of fuel a runtime call serving an RPC request may consume, e.g. a
+	/// `state_call` or `chainHead_unstable_call` request.
+	///
+	/// The fuel is consumed deterministically by the executed instructions of the runtime. Calls
+	/// exceeding the limit are aborted with an error. These calls are executed by separately
+	/// compiled runtimes with fuel metering, all other runtime calls are not limited.
+	///
+	/// Not supported with `--wasm-instantiation-strategy legacy-instance-reuse`.
+	#[arg(long, value_name = "FUEL")]
+	pub rpc_fuel_limit: Option<u64>,
+
+	/// Maximum amount of fuel a runtime API call made by an RPC method may consume, e.g. the
+	/// `query_info` call of a `payment_queryInfo` request.
+	///
+	/// Works like `--rpc-fuel-limit`, but applies to the runtime API calls of the RPC methods
+	/// executing runtime code on behalf of their caller, each call having its own budget.
+	///
+	/// Not supported with `--wasm-instantiation-strategy legacy-instance-reuse`.
+	#[arg(long, value_name = "FUEL")]
+	pub rpc_runtime_api_fuel_limit: Option<u64>,
+}
+
+impl RuntimeParams {
+	/// Maximum size in bytes of the on-disk cache of compiled runtimes, `None` if it is disabled.
+	pub fn wasmtime_artifact_cache_size(&self) -> Option<u64> {
+		match self.wasmtime_artifact_cache_size {
+			0 => None,
+			size => Some(size.saturating_mul(1024 * 1024)),
+		}
+	}
 }
 
 fn parse_max_runtime_instances(s: &str) -> Result<usize, String> {
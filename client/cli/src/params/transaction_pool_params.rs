@@ -16,8 +16,13 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+use crate::arg_enums::TransactionPoolType;
 use clap::Args;
-use sc_service::config::TransactionPoolOptions;
+use sc_service::config::{TransactionPoolJournalOptions, TransactionPoolOptions};
+use std::path::PathBuf;
+
+/// Default file name of the transaction pool journal, inside the node's data directory.
+const DEFAULT_JOURNAL_FILE: &str = "txpool_journal";
 
 /// Parameters used to create the pool configuration.
 #[derive(Debug, Clone, Args)]
@@ -30,9 +35,39 @@ pub struct TransactionPoolParams {
 	#[arg(long, value_name = "COUNT", default_value_t = 20480)]
 	pub pool_kbytes: usize,
 
+	/// Maximum number of transactions of a single sender in the transaction pool.
+	#[arg(long, value_name = "COUNT", default_value_t = 1024)]
+	pub pool_limit_per_sender: usize,
+
+	/// Maximum number of kilobytes of all transactions of a single sender stored in the pool.
+	#[arg(long, value_name = "COUNT", default_value_t = 4096)]
+	pub pool_kbytes_per_sender: usize,
+
 	/// How long a transaction is banned for, if it is considered invalid. Defaults to 1800s.
 	#[arg(long, value_name = "SECONDS")]
 	pub tx_ban_seconds: Option<u64>,
+
+	/// Persist the pending transactions to disk, so they are re-validated and imported again
+	/// after a restart.
+	#[arg(long)]
+	pub pool_journal: bool,
+
+	/// Transactions which entered the pool earlier than this are not replayed from the journal.
+	#[arg(long, value_name = "SECONDS", default_value_t = 3600)]
+	pub pool_journal_max_age: u64,
+
+	/// The type of transaction pool to use.
+	///
+	/// The fork-aware pool keeps a separate view of the pool for every fork tip, so that blocks
+	/// can be authored on top of any of them.
+	#[arg(
+		long,
+		value_name = "TYPE",
+		value_enum,
+		ignore_case = true,
+		default_value_t = TransactionPoolType::SingleState,
+	)]
+	pub pool_type: TransactionPoolType,
 }
 
 impl TransactionPoolParams {
@@ -49,6 +84,10 @@ impl TransactionPoolParams {
 		opts.future.count = self.pool_limit / factor;
 		opts.future.total_bytes = self.pool_kbytes * 1024 / factor;
 
+		// single sender
+		opts.sender.limit.count = self.pool_limit_per_sender;
+		opts.sender.limit.total_bytes = self.pool_kbytes_per_sender * 1024;
+
 		opts.ban_time = if let Some(ban_seconds) = self.tx_ban_seconds {
 			std::time::Duration::from_secs(ban_seconds)
 		} else if is_dev {
@@ -57,6 +96,16 @@ impl TransactionPoolParams {
 			std::time::Duration::from_secs(30 * 60)
 		};
 
+		opts.pool_type = self.pool_type.into();
+
 		opts
 	}
+
+	/// Get the transaction pool journal options, if the journal is enabled.
+	pub fn journal(&self, config_dir: &PathBuf) -> Option<TransactionPoolJournalOptions> {
+		self.pool_journal.then(|| TransactionPoolJournalOptions {
+			path: config_dir.join(DEFAULT_JOURNAL_FILE),
+			max_age: std::time::Duration::from_secs(self.pool_journal_max_age),
+		})
+	}
 }
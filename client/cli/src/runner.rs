@@ -249,7 +249,9 @@ mod tests {
 				keystore: sc_service::config::KeystoreConfig::InMemory,
 				database: sc_client_db::DatabaseSource::ParityDb { path: root.clone() },
 				trie_cache_maximum_size: None,
+				flat_state_cache: false,
 				state_pruning: None,
+				state_pruning_background: None,
 				blocks_pruning: sc_client_db::BlocksPruning::KeepAll,
 				chain_spec: Box::new(GenericChainSpec::from_genesis(
 					"test",
@@ -289,6 +291,10 @@ mod tests {
 				data_path: root,
 				informant_output_format: Default::default(),
 				runtime_cache_size: 2,
+				wasmtime_artifact_cache_size: None,
+				host_call_metrics: false,
+				rpc_fuel_limit: None,
+				rpc_runtime_api_fuel_limit: None,
 			},
 			runtime,
 			Signals::dummy(),
@@ -133,6 +133,9 @@ pub struct ImportedState<B: BlockT> {
 	pub block: B::Hash,
 	/// State keys and values.
 	pub state: sp_state_machine::KeyValueStates,
+	/// Whether the tries of the state were already written to the database with
+	/// [`sc_client_api::backend::Backend::import_state_trie`], in which case `state` is empty.
+	pub written: bool,
 }
 
 impl<B: BlockT> std::fmt::Debug for ImportedState<B> {
@@ -19,11 +19,13 @@
 //! A manual sealing engine: the engine listens for rpc calls to seal blocks and create forks.
 //! This is suitable for a testing environment.
 
+use codec::Decode;
 use futures::prelude::*;
 use futures_timer::Delay;
 use prometheus_endpoint::Registry;
+use sc_block_builder::{BlockBuilderApi, BlockBuilderProvider};
 use sc_client_api::{
-	backend::{Backend as ClientBackend, Finalizer},
+	backend::{Backend as ClientBackend, Finalizer, StateBackendFor},
 	client::BlockchainEvents,
 };
 use sc_consensus::{
@@ -34,11 +36,15 @@ use sp_blockchain::HeaderBackend;
 use sp_consensus::{Environment, Proposer, SelectChain};
 use sp_core::traits::SpawnNamed;
 use sp_inherents::CreateInherentDataProviders;
-use sp_runtime::{traits::Block as BlockT, ConsensusEngineId};
+use sp_runtime::{
+	traits::{Block as BlockT, SaturatedConversion},
+	ConsensusEngineId,
+};
 use std::{marker::PhantomData, sync::Arc, time::Duration};
 
 mod error;
 mod finalize_block;
+mod revert_chain;
 mod seal_block;
 
 pub mod consensus;
@@ -48,11 +54,13 @@ pub use self::{
 	consensus::ConsensusDataProvider,
 	error::Error,
 	finalize_block::{finalize_block, FinalizeBlockParams},
+	revert_chain::{revert_chain, RevertChainParams},
 	rpc::{CreatedBlock, EngineCommand},
-	seal_block::{seal_block, SealBlockParams, MAX_PROPOSAL_DURATION},
+	seal_block::{seal_block, SealBlockParams, TimeTravel, MAX_PROPOSAL_DURATION},
 };
 use sc_transaction_pool_api::TransactionPool;
-use sp_api::{ProvideRuntimeApi, TransactionFor};
+use seal_block::try_seal_block;
+use sp_api::{ApiExt, ProvideRuntimeApi, TransactionFor};
 
 const LOG_TARGET: &str = "manual-seal";
 
@@ -88,7 +96,7 @@ where
 }
 
 /// Params required to start the instant sealing authorship task.
-pub struct ManualSealParams<B: BlockT, BI, E, C: ProvideRuntimeApi<B>, TP, SC, CS, CIDP, P> {
+pub struct ManualSealParams<B: BlockT, BI, E, C: ProvideRuntimeApi<B>, CB, TP, SC, CS, CIDP, P> {
 	/// Block import instance.
 	pub block_import: BI,
 
@@ -98,6 +106,9 @@ pub struct ManualSealParams<B: BlockT, BI, E, C: ProvideRuntimeApi<B>, TP, SC, C
 	/// Client instance
 	pub client: Arc<C>,
 
+	/// Backend of the client, required to revert the chain with [`EngineCommand::RevertTo`].
+	pub backend: Option<Arc<CB>>,
+
 	/// Shared reference to the transaction pool.
 	pub pool: Arc<TP>,
 
@@ -159,19 +170,25 @@ pub async fn run_manual_seal<B, BI, CB, E, C, TP, SC, CS, CIDP, P>(
 		mut block_import,
 		mut env,
 		client,
+		backend,
 		pool,
 		mut commands_stream,
 		select_chain,
 		consensus_data_provider,
 		create_inherent_data_providers,
-	}: ManualSealParams<B, BI, E, C, TP, SC, CS, CIDP, P>,
+	}: ManualSealParams<B, BI, E, C, CB, TP, SC, CS, CIDP, P>,
 ) where
 	B: BlockT + 'static,
 	BI: BlockImport<B, Error = sp_consensus::Error, Transaction = sp_api::TransactionFor<C, B>>
 		+ Send
 		+ Sync
 		+ 'static,
-	C: HeaderBackend<B> + Finalizer<B, CB> + ProvideRuntimeApi<B> + 'static,
+	C: HeaderBackend<B>
+		+ Finalizer<B, CB>
+		+ ProvideRuntimeApi<B>
+		+ BlockBuilderProvider<CB, B, C>
+		+ 'static,
+	C::Api: ApiExt<B, StateBackend = StateBackendFor<CB, B>> + BlockBuilderApi<B>,
 	CB: ClientBackend<B> + 'static,
 	E: Environment<B> + 'static,
 	E::Proposer: Proposer<B, Proof = P, Transaction = TransactionFor<C, B>>,
@@ -180,16 +197,21 @@ pub async fn run_manual_seal<B, BI, CB, E, C, TP, SC, CS, CIDP, P>(
 	TransactionFor<C, B>: 'static,
 	TP: TransactionPool<Block = B>,
 	CIDP: CreateInherentDataProviders<B, ()>,
-	P: Send + Sync + 'static,
+	P: Default + Send + Sync + 'static,
 {
+	let mut time_travel = TimeTravel::default();
+	let mut next_extrinsics = None;
+
 	while let Some(command) = commands_stream.next().await {
 		match command {
-			EngineCommand::SealNewBlock { create_empty, finalize, parent_hash, sender } => {
-				seal_block(SealBlockParams {
-					sender,
+			EngineCommand::SealNewBlock { create_empty, finalize, parent_hash, mut sender } => {
+				let result = try_seal_block(SealBlockParams {
+					sender: None,
 					parent_hash,
 					finalize,
 					create_empty,
+					extrinsics: next_extrinsics.clone(),
+					time_travel: &mut time_travel,
 					env: &mut env,
 					select_chain: &select_chain,
 					block_import: &mut block_import,
@@ -199,6 +221,43 @@ pub async fn run_manual_seal<B, BI, CB, E, C, TP, SC, CS, CIDP, P>(
 					create_inherent_data_providers: &create_inherent_data_providers,
 				})
 				.await;
+				if result.is_ok() {
+					next_extrinsics = None;
+				}
+				rpc::send_result(&mut sender, result);
+			},
+			EngineCommand::SealNewBlocks { count, finalize, mut parent_hash, mut sender } => {
+				let mut created = Vec::with_capacity(count as usize);
+				let mut result = Ok(());
+				for _ in 0..count {
+					match try_seal_block(SealBlockParams {
+						sender: None,
+						parent_hash,
+						finalize,
+						create_empty: true,
+						extrinsics: Some(next_extrinsics.take().unwrap_or_default()),
+						time_travel: &mut time_travel,
+						env: &mut env,
+						select_chain: &select_chain,
+						block_import: &mut block_import,
+						consensus_data_provider: consensus_data_provider.as_deref(),
+						pool: pool.clone(),
+						client: client.clone(),
+						create_inherent_data_providers: &create_inherent_data_providers,
+					})
+					.await
+					{
+						Ok(block) => {
+							parent_hash = Some(block.hash);
+							created.push(block);
+						},
+						Err(e) => {
+							result = Err(e);
+							break
+						},
+					}
+				}
+				rpc::send_result(&mut sender, result.map(|()| created));
 			},
 			EngineCommand::FinalizeBlock { hash, sender, justification } => {
 				let justification = justification.map(|j| (MANUAL_SEAL_ENGINE_ID, j));
@@ -211,6 +270,33 @@ pub async fn run_manual_seal<B, BI, CB, E, C, TP, SC, CS, CIDP, P>(
 				})
 				.await
 			},
+			EngineCommand::SetNextBlockTime { timestamp, slot, mut sender } => {
+				time_travel.set_next(timestamp, slot);
+				rpc::send_result(&mut sender, Ok(()));
+			},
+			EngineCommand::SetNextBlockExtrinsics { extrinsics, mut sender } => {
+				let result = extrinsics
+					.into_iter()
+					.map(|xt| B::Extrinsic::decode(&mut &xt[..]))
+					.collect::<Result<Vec<_>, _>>()
+					.map(|extrinsics| next_extrinsics = Some(extrinsics))
+					.map_err(|e| Error::StringError(format!("Invalid extrinsic: {}", e)));
+				rpc::send_result(&mut sender, result);
+			},
+			EngineCommand::RevertTo { number, mut sender } => match backend {
+				Some(ref backend) =>
+					revert_chain(RevertChainParams {
+						number: number.saturated_into(),
+						sender,
+						client: client.clone(),
+						backend: backend.clone(),
+					})
+					.await,
+				None => rpc::send_result(
+					&mut sender,
+					Err(Error::StringError("Reverting the chain is not supported".into())),
+				),
+			},
 		}
 	}
 }
@@ -234,7 +320,12 @@ pub async fn run_instant_seal<B, BI, CB, E, C, TP, SC, CIDP, P>(
 		+ Send
 		+ Sync
 		+ 'static,
-	C: HeaderBackend<B> + Finalizer<B, CB> + ProvideRuntimeApi<B> + 'static,
+	C: HeaderBackend<B>
+		+ Finalizer<B, CB>
+		+ ProvideRuntimeApi<B>
+		+ BlockBuilderProvider<CB, B, C>
+		+ 'static,
+	C::Api: ApiExt<B, StateBackend = StateBackendFor<CB, B>> + BlockBuilderApi<B>,
 	CB: ClientBackend<B> + 'static,
 	E: Environment<B> + 'static,
 	E::Proposer: Proposer<B, Proof = P, Transaction = TransactionFor<C, B>>,
@@ -242,7 +333,7 @@ pub async fn run_instant_seal<B, BI, CB, E, C, TP, SC, CIDP, P>(
 	TransactionFor<C, B>: 'static,
 	TP: TransactionPool<Block = B>,
 	CIDP: CreateInherentDataProviders<B, ()>,
-	P: Send + Sync + 'static,
+	P: Default + Send + Sync + 'static,
 {
 	// instant-seal creates blocks as soon as transactions are imported
 	// into the transaction pool.
@@ -257,6 +348,7 @@ pub async fn run_instant_seal<B, BI, CB, E, C, TP, SC, CIDP, P>(
 		block_import,
 		env,
 		client,
+		backend: None,
 		pool,
 		commands_stream,
 		select_chain,
@@ -288,7 +380,12 @@ pub async fn run_instant_seal_and_finalize<B, BI, CB, E, C, TP, SC, CIDP, P>(
 		+ Send
 		+ Sync
 		+ 'static,
-	C: HeaderBackend<B> + Finalizer<B, CB> + ProvideRuntimeApi<B> + 'static,
+	C: HeaderBackend<B>
+		+ Finalizer<B, CB>
+		+ ProvideRuntimeApi<B>
+		+ BlockBuilderProvider<CB, B, C>
+		+ 'static,
+	C::Api: ApiExt<B, StateBackend = StateBackendFor<CB, B>> + BlockBuilderApi<B>,
 	CB: ClientBackend<B> + 'static,
 	E: Environment<B> + 'static,
 	E::Proposer: Proposer<B, Proof = P, Transaction = TransactionFor<C, B>>,
@@ -296,7 +393,7 @@ pub async fn run_instant_seal_and_finalize<B, BI, CB, E, C, TP, SC, CIDP, P>(
 	TransactionFor<C, B>: 'static,
 	TP: TransactionPool<Block = B>,
 	CIDP: CreateInherentDataProviders<B, ()>,
-	P: Send + Sync + 'static,
+	P: Default + Send + Sync + 'static,
 {
 	// Creates and finalizes blocks as soon as transactions are imported
 	// into the transaction pool.
@@ -311,6 +408,7 @@ pub async fn run_instant_seal_and_finalize<B, BI, CB, E, C, TP, SC, CIDP, P>(
 		block_import,
 		env,
 		client,
+		backend: None,
 		pool,
 		commands_stream,
 		select_chain,
@@ -361,7 +459,9 @@ pub async fn run_delayed_finalize<B, CB, C, S>(
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use codec::Encode;
 	use sc_basic_authorship::ProposerFactory;
+	use sc_client_api::BlockBackend;
 	use sc_consensus::ImportedAux;
 	use sc_transaction_pool::{BasicPool, FullChainApi, Options, RevalidationType};
 	use sc_transaction_pool_api::{MaintainedTransactionPool, TransactionPool, TransactionSource};
@@ -448,6 +548,7 @@ mod tests {
 			block_import: client.clone(),
 			env,
 			client: client.clone(),
+			backend: None,
 			pool: pool.clone(),
 			commands_stream,
 			select_chain,
@@ -525,6 +626,7 @@ mod tests {
 			commands_stream,
 			env,
 			client: client.clone(),
+			backend: None,
 			pool: pool.clone(),
 			select_chain,
 			create_inherent_data_providers: |_, _| async { Ok(()) },
@@ -604,6 +706,7 @@ mod tests {
 			block_import: client.clone(),
 			env,
 			client: client.clone(),
+			backend: None,
 			pool: pool.clone(),
 			commands_stream,
 			select_chain,
@@ -689,6 +792,7 @@ mod tests {
 			block_import: client.clone(),
 			env,
 			client: client.clone(),
+			backend: None,
 			pool: pool.clone(),
 			commands_stream,
 			select_chain,
@@ -794,6 +898,7 @@ mod tests {
 			block_import: client.clone(),
 			env,
 			client: client.clone(),
+			backend: None,
 			pool: pool.clone(),
 			commands_stream,
 			select_chain,
@@ -820,4 +925,88 @@ mod tests {
 		let header = client.header(created_block.hash).unwrap().unwrap();
 		assert_eq!(header.number, 1);
 	}
+
+	#[tokio::test]
+	async fn manual_seal_injected_extrinsics_and_revert() {
+		let builder = TestClientBuilder::new();
+		let backend = builder.backend();
+		let (client, select_chain) = builder.build_with_longest_chain();
+		let client = Arc::new(client);
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let genesis_hash = client.info().genesis_hash;
+		let pool = Arc::new(BasicPool::with_revalidation_type(
+			Options::default(),
+			true.into(),
+			api(),
+			None,
+			RevalidationType::Full,
+			spawner.clone(),
+			0,
+			genesis_hash,
+			genesis_hash,
+		));
+		let env = ProposerFactory::new(spawner.clone(), client.clone(), pool.clone(), None, None);
+
+		let (mut sink, commands_stream) = futures::channel::mpsc::channel(1024);
+		let future = run_manual_seal(ManualSealParams {
+			block_import: client.clone(),
+			env,
+			client: client.clone(),
+			backend: Some(backend),
+			pool: pool.clone(),
+			commands_stream,
+			select_chain,
+			consensus_data_provider: None,
+			create_inherent_data_providers: |_, _| async { Ok(()) },
+		});
+		std::thread::spawn(|| {
+			let rt = tokio::runtime::Runtime::new().unwrap();
+			rt.block_on(future);
+		});
+
+		// the injected extrinsic lands in the first of the sealed blocks, bypassing the pool
+		let (tx, rx) = futures::channel::oneshot::channel();
+		sink.send(EngineCommand::SetNextBlockExtrinsics {
+			extrinsics: vec![uxt(Alice, 0).encode().into()],
+			sender: Some(tx),
+		})
+		.await
+		.unwrap();
+		rx.await.unwrap().unwrap();
+
+		let (tx, rx) = futures::channel::oneshot::channel();
+		sink.send(EngineCommand::SealNewBlocks {
+			count: 3,
+			finalize: false,
+			parent_hash: None,
+			sender: Some(tx),
+		})
+		.await
+		.unwrap();
+		let created_blocks = rx.await.unwrap().unwrap();
+		assert_eq!(created_blocks.len(), 3);
+		assert_eq!(client.info().best_number, 3);
+		assert_eq!(
+			client.block_body(created_blocks[0].hash).unwrap().unwrap(),
+			vec![uxt(Alice, 0)]
+		);
+		assert_eq!(client.block_body(created_blocks[1].hash).unwrap().unwrap(), vec![]);
+		assert_eq!(pool.status().ready, 0);
+
+		// revert back to the first sealed block
+		let (tx, rx) = futures::channel::oneshot::channel();
+		sink.send(EngineCommand::RevertTo { number: 1, sender: Some(tx) })
+			.await
+			.unwrap();
+		assert_eq!(rx.await.unwrap().unwrap(), created_blocks[0].hash);
+		assert_eq!(client.info().best_number, 1);
+
+		// blocks above the best block can't be reverted to
+		let (tx, rx) = futures::channel::oneshot::channel();
+		sink.send(EngineCommand::RevertTo { number: 2, sender: Some(tx) })
+			.await
+			.unwrap();
+		assert!(rx.await.unwrap().is_err());
+		assert_eq!(client.info().best_number, 1);
+	}
 }
@@ -0,0 +1,78 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Chain reverting utilities
+
+use crate::{rpc, Error, LOG_TARGET};
+use sc_client_api::backend::Backend as ClientBackend;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::{Block as BlockT, NumberFor, Saturating};
+use std::sync::Arc;
+
+/// params for reverting the chain.
+pub struct RevertChainParams<B: BlockT, C, CB> {
+	/// number of the new best block
+	pub number: NumberFor<B>,
+	/// sender to report the hash of the new best block to the rpc.
+	pub sender: rpc::Sender<<B as BlockT>::Hash>,
+	/// header backend
+	pub client: Arc<C>,
+	/// backend of the client, which actually reverts the blocks
+	pub backend: Arc<CB>,
+}
+
+/// reverts the best chain to the given block, finalized blocks are never reverted and blocks
+/// above the best block are rejected.
+pub async fn revert_chain<B, C, CB>(params: RevertChainParams<B, C, CB>)
+where
+	B: BlockT,
+	C: HeaderBackend<B>,
+	CB: ClientBackend<B>,
+{
+	let RevertChainParams { number, mut sender, client, backend } = params;
+
+	let info = client.info();
+	let result = if number < info.finalized_number {
+		Err(Error::StringError(format!(
+			"Can't revert to #{}, below the finalized block #{}",
+			number, info.finalized_number,
+		)))
+	} else if number > info.best_number {
+		Err(Error::StringError(format!(
+			"Can't revert to #{}, above the best block #{}",
+			number, info.best_number,
+		)))
+	} else {
+		backend
+			.revert(info.best_number.saturating_sub(number), false)
+			.map(|(reverted, _)| {
+				let best_hash = client.info().best_hash;
+				log::info!(
+					target: LOG_TARGET,
+					"⏪ Reverted {} blocks, new best block: #{} ({})",
+					reverted,
+					number,
+					best_hash,
+				);
+				best_hash
+			})
+			.map_err(Into::into)
+	};
+
+	rpc::send_result(&mut sender, result)
+}
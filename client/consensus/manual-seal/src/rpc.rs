@@ -29,8 +29,12 @@ use jsonrpsee::{
 };
 use sc_consensus::ImportedAux;
 use serde::{Deserialize, Serialize};
+use sp_core::Bytes;
 use sp_runtime::EncodedJustification;
 
+/// Maximum number of blocks that can be created by a single `engine_createBlocks` call.
+pub const MAX_CREATE_BLOCKS: u32 = 1024;
+
 /// Sender passed to the authorship task to report errors or successes.
 pub type Sender<T> = Option<oneshot::Sender<std::result::Result<T, Error>>>;
 
@@ -62,6 +66,50 @@ pub enum EngineCommand<Hash> {
 		/// finalization justification
 		justification: Option<EncodedJustification>,
 	},
+	/// Tells the engine to seal `count` blocks in a row, only containing the inherents and the
+	/// extrinsics set with [`EngineCommand::SetNextBlockExtrinsics`].
+	///
+	/// Every block is built on top of the previous one, so this can be used to build a fork
+	/// starting from `parent_hash`.
+	SealNewBlocks {
+		/// number of blocks to seal
+		count: u32,
+		/// instantly finalize these blocks?
+		finalize: bool,
+		/// specify the parent hash of the first block
+		parent_hash: Option<Hash>,
+		/// sender to report errors/success to the rpc.
+		sender: Sender<Vec<CreatedBlock<Hash>>>,
+	},
+	/// Tells the engine the timestamp and slot of the next sealed block.
+	///
+	/// The following blocks keep the same shift from the time provided by the inherent data
+	/// providers.
+	SetNextBlockTime {
+		/// timestamp of the next block, in milliseconds since the UNIX epoch
+		timestamp: Option<u64>,
+		/// BABE slot of the next block, AURA derives the slot from the timestamp
+		slot: Option<u64>,
+		/// sender to report errors/success to the rpc.
+		sender: Sender<()>,
+	},
+	/// Tells the engine to put the given extrinsics in the next sealed block, instead of the ones
+	/// from the transaction pool.
+	SetNextBlockExtrinsics {
+		/// SCALE encoded extrinsics
+		extrinsics: Vec<Bytes>,
+		/// sender to report errors/success to the rpc.
+		sender: Sender<()>,
+	},
+	/// Tells the engine to revert the best chain to the block with the given number
+	///
+	/// Finalized blocks can't be reverted.
+	RevertTo {
+		/// number of the new best block
+		number: u64,
+		/// sender to report the hash of the new best block to the rpc.
+		sender: Sender<Hash>,
+	},
 }
 
 /// RPC trait that provides methods for interacting with the manual-seal authorship task over rpc.
@@ -83,6 +131,32 @@ pub trait ManualSealApi<Hash> {
 		hash: Hash,
 		justification: Option<EncodedJustification>,
 	) -> RpcResult<bool>;
+
+	/// Instructs the manual-seal authorship task to create `count` blocks in a row, without
+	/// transactions from the pool. At most [`MAX_CREATE_BLOCKS`] blocks can be created at once.
+	#[method(name = "engine_createBlocks")]
+	async fn create_blocks(
+		&self,
+		count: u32,
+		finalize: bool,
+		parent_hash: Option<Hash>,
+	) -> RpcResult<Vec<CreatedBlock<Hash>>>;
+
+	/// Sets the timestamp, in milliseconds, and the BABE slot of the next created block
+	#[method(name = "engine_setNextBlockTime")]
+	async fn set_next_block_time(
+		&self,
+		timestamp: Option<u64>,
+		slot: Option<u64>,
+	) -> RpcResult<bool>;
+
+	/// Sets the SCALE encoded extrinsics of the next created block, bypassing the transaction pool
+	#[method(name = "engine_setNextBlockExtrinsics")]
+	async fn set_next_block_extrinsics(&self, extrinsics: Vec<Bytes>) -> RpcResult<bool>;
+
+	/// Reverts the best chain to the block with the given number, returning the new best hash
+	#[method(name = "engine_revertTo")]
+	async fn revert_to(&self, number: u64) -> RpcResult<Hash>;
 }
 
 /// A struct that implements the [`ManualSealApiServer`].
@@ -104,6 +178,22 @@ impl<Hash> ManualSeal<Hash> {
 	pub fn new(import_block_channel: mpsc::Sender<EngineCommand<Hash>>) -> Self {
 		Self { import_block_channel }
 	}
+
+	/// Sends the command to the authorship task and waits for its result.
+	async fn send<T>(
+		&self,
+		command: EngineCommand<Hash>,
+		receiver: oneshot::Receiver<std::result::Result<T, Error>>,
+	) -> RpcResult<T> {
+		let mut sink = self.import_block_channel.clone();
+		sink.send(command).await?;
+
+		match receiver.await {
+			Ok(Ok(rx)) => Ok(rx),
+			Ok(Err(e)) => Err(e.into()),
+			Err(e) => Err(JsonRpseeError::to_call_error(e)),
+		}
+	}
 }
 
 #[async_trait]
@@ -144,6 +234,48 @@ impl<Hash: Send + 'static> ManualSealApiServer<Hash> for ManualSeal<Hash> {
 		sink.send(command).await?;
 		receiver.await.map(|_| true).map_err(|e| JsonRpseeError::to_call_error(e))
 	}
+
+	async fn create_blocks(
+		&self,
+		count: u32,
+		finalize: bool,
+		parent_hash: Option<Hash>,
+	) -> RpcResult<Vec<CreatedBlock<Hash>>> {
+		if count > MAX_CREATE_BLOCKS {
+			return Err(Error::StringError(format!(
+				"Can't create {} blocks at once, the maximum is {}",
+				count, MAX_CREATE_BLOCKS,
+			))
+			.into())
+		}
+
+		let (sender, receiver) = oneshot::channel();
+		let command =
+			EngineCommand::SealNewBlocks { count, finalize, parent_hash, sender: Some(sender) };
+		self.send(command, receiver).await
+	}
+
+	async fn set_next_block_time(
+		&self,
+		timestamp: Option<u64>,
+		slot: Option<u64>,
+	) -> RpcResult<bool> {
+		let (sender, receiver) = oneshot::channel();
+		let command = EngineCommand::SetNextBlockTime { timestamp, slot, sender: Some(sender) };
+		self.send(command, receiver).await.map(|_| true)
+	}
+
+	async fn set_next_block_extrinsics(&self, extrinsics: Vec<Bytes>) -> RpcResult<bool> {
+		let (sender, receiver) = oneshot::channel();
+		let command = EngineCommand::SetNextBlockExtrinsics { extrinsics, sender: Some(sender) };
+		self.send(command, receiver).await.map(|_| true)
+	}
+
+	async fn revert_to(&self, number: u64) -> RpcResult<Hash> {
+		let (sender, receiver) = oneshot::channel();
+		let command = EngineCommand::RevertTo { number, sender: Some(sender) };
+		self.send(command, receiver).await
+	}
 }
 
 /// report any errors or successes encountered by the authorship task back
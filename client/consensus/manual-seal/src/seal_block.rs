@@ -20,18 +20,81 @@
 
 use crate::{rpc, ConsensusDataProvider, CreatedBlock, Error};
 use futures::prelude::*;
+use sc_block_builder::{BlockBuilderApi, BlockBuilderProvider};
+use sc_client_api::backend::{Backend as ClientBackend, StateBackendFor};
 use sc_consensus::{BlockImport, BlockImportParams, ForkChoiceStrategy, ImportResult, StateAction};
 use sc_transaction_pool_api::TransactionPool;
-use sp_api::{ProvideRuntimeApi, TransactionFor};
+use sp_api::{ApiExt, ProvideRuntimeApi, TransactionFor};
 use sp_blockchain::HeaderBackend;
 use sp_consensus::{self, BlockOrigin, Environment, Proposer, SelectChain};
-use sp_inherents::{CreateInherentDataProviders, InherentDataProvider};
+use sp_consensus_babe::inherents::BabeInherentData;
+use sp_consensus_slots::Slot;
+use sp_inherents::{CreateInherentDataProviders, InherentData, InherentDataProvider};
 use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use sp_timestamp::TimestampInherentData;
 use std::{sync::Arc, time::Duration};
 
 /// max duration for creating a proposal in secs
 pub const MAX_PROPOSAL_DURATION: u64 = 10;
 
+/// Shift of the time of the sealed blocks, set through `engine_setNextBlockTime`.
+///
+/// The next sealed block gets the requested timestamp and slot. The following blocks keep the same
+/// distance to the values produced by the inherent data providers, so the chain never travels back
+/// in time.
+#[derive(Debug, Default, Clone)]
+pub struct TimeTravel {
+	next_timestamp: Option<u64>,
+	next_slot: Option<u64>,
+	timestamp_offset: i128,
+	slot_offset: i128,
+}
+
+impl TimeTravel {
+	/// Requests the timestamp, in milliseconds, and the BABE slot of the next sealed block.
+	///
+	/// AURA derives the slot from the timestamp, so only the timestamp is needed there.
+	pub fn set_next(&mut self, timestamp: Option<u64>, slot: Option<u64>) {
+		self.next_timestamp = timestamp.or(self.next_timestamp);
+		self.next_slot = slot.or(self.next_slot);
+	}
+
+	/// Applies the shift to the inherent data, returning the state for the next block.
+	fn apply(&self, inherent_data: &mut InherentData) -> Result<Self, Error> {
+		fn shift(provided: u64, offset: i128) -> u64 {
+			(provided as i128 + offset).clamp(0, u64::MAX as i128) as u64
+		}
+
+		let mut next = Self { next_timestamp: None, next_slot: None, ..self.clone() };
+
+		let provided = inherent_data.timestamp_inherent_data()?.map(|t| t.as_millis());
+		if let Some(timestamp) = self
+			.next_timestamp
+			.or_else(|| provided.map(|p| shift(p, self.timestamp_offset)))
+		{
+			if let Some(provided) = provided {
+				next.timestamp_offset = timestamp as i128 - provided as i128;
+			}
+			inherent_data.replace_data(
+				sp_timestamp::INHERENT_IDENTIFIER,
+				&sp_timestamp::Timestamp::new(timestamp),
+			);
+		}
+
+		let provided = inherent_data.babe_inherent_data()?.map(|s| *s);
+		if let Some(slot) = self.next_slot.or_else(|| provided.map(|p| shift(p, self.slot_offset)))
+		{
+			if let Some(provided) = provided {
+				next.slot_offset = slot as i128 - provided as i128;
+			}
+			inherent_data
+				.replace_data(sp_consensus_babe::inherents::INHERENT_IDENTIFIER, &Slot::from(slot));
+		}
+
+		Ok(next)
+	}
+}
+
 /// params for sealing a new block
 pub struct SealBlockParams<'a, B: BlockT, BI, SC, C: ProvideRuntimeApi<B>, E, TP, CIDP, P> {
 	/// if true, empty blocks(without extrinsics) will be created.
@@ -41,6 +104,10 @@ pub struct SealBlockParams<'a, B: BlockT, BI, SC, C: ProvideRuntimeApi<B>, E, TP
 	pub finalize: bool,
 	/// specify the parent hash of the about-to-created block
 	pub parent_hash: Option<<B as BlockT>::Hash>,
+	/// extrinsics to put in the block instead of the ones from the transaction pool
+	pub extrinsics: Option<Vec<<B as BlockT>::Extrinsic>>,
+	/// shift of the block time, updated once the block is imported
+	pub time_travel: &'a mut TimeTravel,
 	/// sender to report errors/success to the rpc.
 	pub sender: rpc::Sender<CreatedBlock<<B as BlockT>::Hash>>,
 	/// transaction pool
@@ -61,65 +128,111 @@ pub struct SealBlockParams<'a, B: BlockT, BI, SC, C: ProvideRuntimeApi<B>, E, TP
 }
 
 /// seals a new block with the given params
-pub async fn seal_block<B, BI, SC, C, E, TP, CIDP, P>(
+pub async fn seal_block<B, BI, SC, C, CB, E, TP, CIDP, P>(
+	mut params: SealBlockParams<'_, B, BI, SC, C, E, TP, CIDP, P>,
+) where
+	B: BlockT,
+	BI: BlockImport<B, Error = sp_consensus::Error, Transaction = sp_api::TransactionFor<C, B>>
+		+ Send
+		+ Sync
+		+ 'static,
+	C: HeaderBackend<B> + ProvideRuntimeApi<B> + BlockBuilderProvider<CB, B, C>,
+	C::Api: ApiExt<B, StateBackend = StateBackendFor<CB, B>> + BlockBuilderApi<B>,
+	CB: ClientBackend<B>,
+	E: Environment<B>,
+	E::Proposer: Proposer<B, Proof = P, Transaction = TransactionFor<C, B>>,
+	TP: TransactionPool<Block = B>,
+	SC: SelectChain<B>,
+	TransactionFor<C, B>: 'static,
+	CIDP: CreateInherentDataProviders<B, ()>,
+	P: Default + Send + Sync + 'static,
+{
+	let mut sender = params.sender.take();
+	rpc::send_result(&mut sender, try_seal_block(params).await)
+}
+
+/// Seals a new block with the given params, returning the result instead of sending it to the
+/// `sender`.
+pub(crate) async fn try_seal_block<B, BI, SC, C, CB, E, TP, CIDP, P>(
 	SealBlockParams {
 		create_empty,
 		finalize,
 		pool,
 		parent_hash,
+		extrinsics,
+		time_travel,
 		client,
 		select_chain,
 		block_import,
 		env,
 		create_inherent_data_providers,
 		consensus_data_provider: digest_provider,
-		mut sender,
+		sender: _,
 	}: SealBlockParams<'_, B, BI, SC, C, E, TP, CIDP, P>,
-) where
+) -> Result<CreatedBlock<<B as BlockT>::Hash>, Error>
+where
 	B: BlockT,
 	BI: BlockImport<B, Error = sp_consensus::Error, Transaction = sp_api::TransactionFor<C, B>>
 		+ Send
 		+ Sync
 		+ 'static,
-	C: HeaderBackend<B> + ProvideRuntimeApi<B>,
+	C: HeaderBackend<B> + ProvideRuntimeApi<B> + BlockBuilderProvider<CB, B, C>,
+	C::Api: ApiExt<B, StateBackend = StateBackendFor<CB, B>> + BlockBuilderApi<B>,
+	CB: ClientBackend<B>,
 	E: Environment<B>,
 	E::Proposer: Proposer<B, Proof = P, Transaction = TransactionFor<C, B>>,
 	TP: TransactionPool<Block = B>,
 	SC: SelectChain<B>,
 	TransactionFor<C, B>: 'static,
 	CIDP: CreateInherentDataProviders<B, ()>,
-	P: Send + Sync + 'static,
+	P: Default + Send + Sync + 'static,
 {
-	let future = async {
-		if pool.status().ready == 0 && !create_empty {
-			return Err(Error::EmptyTransactionPool)
-		}
+	let is_empty = match extrinsics {
+		Some(ref extrinsics) => extrinsics.is_empty(),
+		None => pool.status().ready == 0,
+	};
+	if is_empty && !create_empty {
+		return Err(Error::EmptyTransactionPool)
+	}
 
-		// get the header to build this new block on.
-		// use the parent_hash supplied via `EngineCommand`
-		// or fetch the best_block.
-		let parent = match parent_hash {
-			Some(hash) =>
-				client.header(hash)?.ok_or_else(|| Error::BlockNotFound(format!("{}", hash)))?,
-			None => select_chain.best_chain().await?,
-		};
+	// get the header to build this new block on.
+	// use the parent_hash supplied via `EngineCommand`
+	// or fetch the best_block.
+	let parent = match parent_hash {
+		Some(hash) =>
+			client.header(hash)?.ok_or_else(|| Error::BlockNotFound(format!("{}", hash)))?,
+		None => select_chain.best_chain().await?,
+	};
 
-		let inherent_data_providers = create_inherent_data_providers
-			.create_inherent_data_providers(parent.hash(), ())
-			.await
-			.map_err(|e| Error::Other(e))?;
+	let inherent_data_providers = create_inherent_data_providers
+		.create_inherent_data_providers(parent.hash(), ())
+		.await
+		.map_err(|e| Error::Other(e))?;
 
-		let inherent_data = inherent_data_providers.create_inherent_data().await?;
+	let mut inherent_data = inherent_data_providers.create_inherent_data().await?;
+	let next_time_travel = time_travel.apply(&mut inherent_data)?;
 
-		let proposer = env.init(&parent).map_err(|err| Error::StringError(err.to_string())).await?;
-		let inherents_len = inherent_data.len();
+	let inherents_len = inherent_data.len();
 
-		let digest = if let Some(digest_provider) = digest_provider {
-			digest_provider.create_digest(&parent, &inherent_data)?
-		} else {
-			Default::default()
-		};
+	let digest = if let Some(digest_provider) = digest_provider {
+		digest_provider.create_digest(&parent, &inherent_data)?
+	} else {
+		Default::default()
+	};
 
+	let (block, storage_changes, proof) = if let Some(extrinsics) = extrinsics {
+		// build the block ourselves, so the transaction pool is bypassed.
+		let mut block_builder = client.new_block_at(parent.hash(), digest, false)?;
+		for inherent in block_builder.create_inherents(inherent_data.clone())? {
+			block_builder.push(inherent)?;
+		}
+		for extrinsic in extrinsics {
+			block_builder.push(extrinsic)?;
+		}
+		let built = block_builder.build()?;
+		(built.block, built.storage_changes, P::default())
+	} else {
+		let proposer = env.init(&parent).map_err(|err| Error::StringError(err.to_string())).await?;
 		let proposal = proposer
 			.propose(
 				inherent_data.clone(),
@@ -134,31 +247,31 @@ pub async fn seal_block<B, BI, SC, C, E, TP, CIDP, P>(
 			return Err(Error::EmptyTransactionPool)
 		}
 
-		let (header, body) = proposal.block.deconstruct();
-		let proof = proposal.proof;
-		let mut params = BlockImportParams::new(BlockOrigin::Own, header.clone());
-		params.body = Some(body);
-		params.finalized = finalize;
-		params.fork_choice = Some(ForkChoiceStrategy::LongestChain);
-		params.state_action = StateAction::ApplyChanges(sc_consensus::StorageChanges::Changes(
-			proposal.storage_changes,
-		));
-
-		if let Some(digest_provider) = digest_provider {
-			digest_provider.append_block_import(&parent, &mut params, &inherent_data, proof)?;
-		}
+		(proposal.block, proposal.storage_changes, proposal.proof)
+	};
 
-		// Make sure we return the same post-hash that will be calculated when importing the block
-		// This is important in case the digest_provider added any signature, seal, ect.
-		let mut post_header = header.clone();
-		post_header.digest_mut().logs.extend(params.post_digests.iter().cloned());
+	let (header, body) = block.deconstruct();
+	let mut params = BlockImportParams::new(BlockOrigin::Own, header.clone());
+	params.body = Some(body);
+	params.finalized = finalize;
+	params.fork_choice = Some(ForkChoiceStrategy::LongestChain);
+	params.state_action =
+		StateAction::ApplyChanges(sc_consensus::StorageChanges::Changes(storage_changes));
 
-		match block_import.import_block(params).await? {
-			ImportResult::Imported(aux) =>
-				Ok(CreatedBlock { hash: <B as BlockT>::Header::hash(&post_header), aux }),
-			other => Err(other.into()),
-		}
-	};
+	if let Some(digest_provider) = digest_provider {
+		digest_provider.append_block_import(&parent, &mut params, &inherent_data, proof)?;
+	}
+
+	// Make sure we return the same post-hash that will be calculated when importing the block
+	// This is important in case the digest_provider added any signature, seal, ect.
+	let mut post_header = header.clone();
+	post_header.digest_mut().logs.extend(params.post_digests.iter().cloned());
 
-	rpc::send_result(&mut sender, future.await)
+	match block_import.import_block(params).await? {
+		ImportResult::Imported(aux) => {
+			*time_travel = next_time_travel;
+			Ok(CreatedBlock { hash: <B as BlockT>::Header::hash(&post_header), aux })
+		},
+		other => Err(other.into()),
+	}
 }
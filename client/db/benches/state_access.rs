@@ -122,6 +122,8 @@ fn create_backend(config: BenchmarkConfig, temp_dir: &TempDir) -> Backend<Block>
 		state_pruning: Some(PruningMode::ArchiveAll),
 		source: DatabaseSource::ParityDb { path },
 		blocks_pruning: BlocksPruning::KeepAll,
+		background_pruning: None,
+		flat_state: false,
 	};
 
 	Backend::new(settings, 100).expect("Creates backend")
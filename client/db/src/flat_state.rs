@@ -0,0 +1,490 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Flat key-value copy of the finalized state.
+//!
+//! The values of the latest finalized state are stored in the [`columns::FLAT_STATE`] column,
+//! keyed by their storage key, and kept up to date by applying the storage changes of imported
+//! blocks once they are finalized. Changes of unfinalized blocks are kept as layers on top of the
+//! copy, both in memory and in the column, so that reads at blocks close to the head can be
+//! answered without walking the trie and nothing has to be read again after a restart.
+//!
+//! At most [`MAX_LAYERS`] unfinalized blocks are tracked. While finality stalls, the blocks
+//! imported past that limit are not tracked and reads at them use the trie. If the changes
+//! leading to a newly finalized block are unknown, for example after a state sync, a revert or a
+//! long finality stall, the column is filled again from the trie in the background.
+//!
+//! Every value is tagged with the epoch of the copy it was written for. Filling the column again
+//! starts a new epoch, so that values left over from the previous copy are ignored without having
+//! to find and delete them, which the database can't do efficiently.
+//!
+//! Reads at blocks that are not covered by the copy, `next_storage_key` lookups, which would need
+//! ordered iteration over the column, storage hashes and proofs keep using the trie.
+
+use crate::{columns, DbHash, DbStateBuilder, StorageDb};
+use codec::{Decode, Encode};
+use log::{debug, info, warn};
+use parking_lot::RwLock;
+use sp_core::storage::{well_known_keys, ChildInfo};
+use sp_database::{error::DatabaseError, Database, Transaction};
+use sp_runtime::traits::Block as BlockT;
+use sp_state_machine::{
+	Backend as StateBackend, ChildStorageCollection, IterArgs, StorageCollection, StorageKey,
+	StorageValue,
+};
+use std::{
+	collections::{BTreeMap, HashMap},
+	sync::{Arc, Weak},
+};
+
+const LOG_TARGET: &str = "db::flat";
+
+/// Maximum number of unfinalized blocks whose changes are tracked.
+const MAX_LAYERS: usize = 256;
+
+/// Number of values written at once while filling the column from the trie.
+const FILL_BATCH_SIZE: usize = 4096;
+
+/// Prefix of the keys of top trie values.
+const TOP_PREFIX: u8 = 0;
+/// Prefix of the keys of child trie values.
+const CHILD_PREFIX: u8 = 1;
+/// Prefix of the keys of the changes of unfinalized blocks.
+const LAYER_PREFIX: u8 = 2;
+/// Key of the [`Meta`] of the copy.
+const META_KEY: &[u8] = &[3];
+
+/// Storage changes, `None` meaning that the key was removed.
+type Changes = BTreeMap<StorageKey, Option<StorageValue>>;
+
+/// Storage changes of a single unfinalized block.
+#[derive(Encode, Decode)]
+struct Layer<Hash> {
+	parent: Hash,
+	number: u64,
+	top: Changes,
+	children: BTreeMap<StorageKey, Changes>,
+}
+
+/// Description of the copy stored in the column.
+#[derive(Encode, Decode, Default)]
+struct Meta<Hash> {
+	/// Epoch the values of the copy are tagged with.
+	epoch: u64,
+	/// Block the copy is at: `(hash, number)`.
+	at: Option<(Hash, u64)>,
+	/// Whether all of the values of the block were written.
+	complete: bool,
+	/// Unfinalized blocks whose changes are stored.
+	layers: Vec<Hash>,
+}
+
+/// Key of a value in the column.
+fn value_key(storage_key: Option<&[u8]>, key: &[u8]) -> Vec<u8> {
+	let mut db_key = Vec::with_capacity(key.len() + storage_key.map_or(1, |s| s.len() + 5));
+	match storage_key {
+		None => db_key.push(TOP_PREFIX),
+		Some(storage_key) => {
+			db_key.push(CHILD_PREFIX);
+			db_key.extend_from_slice(&(storage_key.len() as u32).to_be_bytes());
+			db_key.extend_from_slice(storage_key);
+		},
+	}
+	db_key.extend_from_slice(key);
+	db_key
+}
+
+/// Key of the changes of a block in the column.
+fn layer_key(hash: &impl AsRef<[u8]>) -> Vec<u8> {
+	let mut db_key = vec![LAYER_PREFIX];
+	db_key.extend_from_slice(hash.as_ref());
+	db_key
+}
+
+/// Tag a value with the epoch of the copy it is written for.
+fn encode_value(epoch: u64, value: &[u8]) -> Vec<u8> {
+	let mut encoded = Vec::with_capacity(value.len() + 8);
+	encoded.extend_from_slice(&epoch.to_le_bytes());
+	encoded.extend_from_slice(value);
+	encoded
+}
+
+/// Strip the epoch tag of a value, ignoring values left over from an older copy.
+fn decode_value(epoch: u64, mut encoded: Vec<u8>) -> Option<StorageValue> {
+	if encoded.len() < 8 || encoded[..8] != epoch.to_le_bytes() {
+		return None
+	}
+	encoded.drain(..8);
+	Some(encoded)
+}
+
+struct Inner<Block: BlockT> {
+	/// The copy stored in the column. Its `layers` are only filled when it is written.
+	meta: Meta<Block::Hash>,
+	layers: HashMap<Block::Hash, Layer<Block::Hash>>,
+	/// Latest finalized block with state: `(hash, number, state root)`.
+	finalized: Option<(Block::Hash, u64, Block::Hash)>,
+	/// Whether the copy is being filled from the trie.
+	filling: bool,
+}
+
+impl<Block: BlockT> Inner<Block> {
+	/// Block the copy is at, if it is complete.
+	fn base(&self) -> Option<(Block::Hash, u64)> {
+		self.meta.at.filter(|_| self.meta.complete)
+	}
+
+	/// Layers between the copy and `at`, newest first.
+	fn path(&self, at: &Block::Hash) -> Option<Vec<&Layer<Block::Hash>>> {
+		let (base, base_number) = self.base()?;
+		let mut layers = Vec::new();
+		let mut current = *at;
+		while current != base {
+			let layer = self.layers.get(&current).filter(|l| l.number > base_number)?;
+			current = layer.parent;
+			layers.push(layer);
+		}
+		Some(layers)
+	}
+
+	/// Drop the layers that can no longer be applied to the copy.
+	fn prune_layers(&mut self, transaction: &mut Transaction<DbHash>) {
+		let base = self.meta.at.map(|(_, number)| number);
+		let Some(base) = base.or(self.finalized.map(|(_, number, _)| number)) else { return };
+		let pruned: Vec<_> =
+			self.layers.iter().filter(|(_, l)| l.number <= base).map(|(h, _)| *h).collect();
+		for hash in pruned {
+			self.layers.remove(&hash);
+			transaction.remove(columns::FLAT_STATE, &layer_key(&hash));
+		}
+	}
+
+	fn write_meta(&self, transaction: &mut Transaction<DbHash>) {
+		let meta = Meta {
+			epoch: self.meta.epoch,
+			at: self.meta.at,
+			complete: self.meta.complete,
+			layers: self.layers.keys().copied().collect(),
+		};
+		transaction.set_from_vec(columns::FLAT_STATE, META_KEY, meta.encode());
+	}
+}
+
+/// Flat copy of the finalized state stored in the database, with the changes of unfinalized
+/// blocks on top.
+pub(crate) struct FlatState<Block: BlockT> {
+	db: Arc<dyn Database<DbHash>>,
+	storage: Weak<StorageDb<Block>>,
+	inner: RwLock<Inner<Block>>,
+}
+
+impl<Block: BlockT> FlatState<Block> {
+	/// Open the copy stored in the database, if any.
+	pub(crate) fn new(storage: &Arc<StorageDb<Block>>) -> Self {
+		let db = storage.db.clone();
+		let mut meta = db
+			.get(columns::FLAT_STATE, META_KEY)
+			.and_then(|encoded| Meta::<Block::Hash>::decode(&mut &encoded[..]).ok())
+			.unwrap_or_default();
+		let layers = std::mem::take(&mut meta.layers)
+			.into_iter()
+			.filter_map(|hash| {
+				let encoded = db.get(columns::FLAT_STATE, &layer_key(&hash))?;
+				Some((hash, Layer::decode(&mut &encoded[..]).ok()?))
+			})
+			.collect();
+		FlatState {
+			db,
+			storage: Arc::downgrade(storage),
+			inner: RwLock::new(Inner { meta, layers, finalized: None, filling: false }),
+		}
+	}
+
+	/// Whether the copy is complete.
+	#[cfg(test)]
+	pub(crate) fn is_ready(&self) -> bool {
+		self.inner.read().base().is_some()
+	}
+
+	/// Read a value at the given block.
+	///
+	/// Returns `None` if the block is not covered by the copy and the trie must be used.
+	pub(crate) fn storage(
+		&self,
+		at: &Block::Hash,
+		child: Option<&ChildInfo>,
+		key: &[u8],
+	) -> Option<Option<StorageValue>> {
+		if child.is_none() && well_known_keys::is_child_storage_key(key) {
+			// Child trie roots are only known to the trie.
+			return None
+		}
+		let inner = self.inner.read();
+		let layers = inner.path(at)?;
+		let storage_key = child.map(|c| c.storage_key());
+		for layer in layers {
+			let changes = match storage_key {
+				Some(storage_key) => layer.children.get(storage_key),
+				None => Some(&layer.top),
+			};
+			if let Some(value) = changes.and_then(|c| c.get(key)) {
+				return Some(value.clone())
+			}
+		}
+		let value = self.db.get(columns::FLAT_STATE, &value_key(storage_key, key));
+		Some(value.and_then(|value| decode_value(inner.meta.epoch, value)))
+	}
+
+	/// Record the storage changes of a newly imported block.
+	pub(crate) fn note_block(
+		&self,
+		hash: Block::Hash,
+		parent: Block::Hash,
+		number: u64,
+		top: StorageCollection,
+		children: ChildStorageCollection,
+	) {
+		let mut inner = self.inner.write();
+		if inner.finalized.map_or(false, |(_, finalized, _)| number <= finalized) {
+			return
+		}
+		if inner.layers.len() >= MAX_LAYERS {
+			debug!(target: LOG_TARGET, "Too many unfinalized blocks, skipping #{}", number);
+			return
+		}
+		let layer = Layer {
+			parent,
+			number,
+			top: top.into_iter().collect(),
+			children: children
+				.into_iter()
+				.map(|(storage_key, changes)| (storage_key, changes.into_iter().collect()))
+				.collect(),
+		};
+		let mut transaction = Transaction::new();
+		transaction.set_from_vec(columns::FLAT_STATE, &layer_key(&hash), layer.encode());
+		inner.layers.insert(hash, layer);
+		inner.write_meta(&mut transaction);
+		if let Err(e) = self.db.commit(transaction) {
+			warn!(target: LOG_TARGET, "Failed to store the changes of #{}: {}", number, e);
+			inner.layers.remove(&hash);
+		}
+	}
+
+	/// Move the copy to a newly finalized block.
+	///
+	/// The column is filled again from the trie in the background if the changes leading to the
+	/// block are unknown.
+	pub(crate) fn note_finalized(
+		self: &Arc<Self>,
+		hash: Block::Hash,
+		number: u64,
+		state_root: Block::Hash,
+	) {
+		let mut inner = self.inner.write();
+		if inner.finalized.map_or(false, |(finalized, _, _)| finalized == hash) {
+			return
+		}
+		inner.finalized = Some((hash, number, state_root));
+		if inner.filling {
+			// Applied once the copy is complete.
+			return
+		}
+		match self.advance(&mut inner, hash) {
+			Ok(true) => {},
+			Ok(false) => {
+				if inner.base().is_some() {
+					debug!(target: LOG_TARGET, "Missing changes up to #{} ({:?})", number, hash);
+				}
+				self.fill(&mut inner);
+			},
+			Err(e) => warn!(target: LOG_TARGET, "Failed to move flat state to #{}: {}", number, e),
+		}
+	}
+
+	/// Apply the changes of the blocks up to `hash` to the copy.
+	///
+	/// Returns `Ok(false)` if the changes of some block in between are unknown. If writing the
+	/// changes fails, the copy is dropped and filled again on the next finalized block.
+	fn advance(&self, inner: &mut Inner<Block>, hash: Block::Hash) -> Result<bool, DatabaseError> {
+		let Some(layers) = inner.path(&hash) else { return Ok(false) };
+		let Some(number) = layers.first().map(|layer| layer.number) else { return Ok(true) };
+
+		// Later blocks override the changes of earlier ones.
+		let mut values = BTreeMap::new();
+		for layer in layers.into_iter().rev() {
+			for (key, value) in &layer.top {
+				if !well_known_keys::is_child_storage_key(key) {
+					values.insert(value_key(None, key), value.as_ref());
+				}
+			}
+			for (storage_key, changes) in &layer.children {
+				for (key, value) in changes {
+					values.insert(value_key(Some(&storage_key[..]), key), value.as_ref());
+				}
+			}
+		}
+		let mut transaction = Transaction::new();
+		for (key, value) in values {
+			match value {
+				Some(value) => transaction.set_from_vec(
+					columns::FLAT_STATE,
+					&key,
+					encode_value(inner.meta.epoch, value),
+				),
+				None => transaction.remove(columns::FLAT_STATE, &key),
+			}
+		}
+
+		inner.meta.at = Some((hash, number));
+		inner.prune_layers(&mut transaction);
+		inner.write_meta(&mut transaction);
+		if let Err(e) = self.db.commit(transaction) {
+			inner.meta.at = None;
+			inner.meta.complete = false;
+			return Err(e)
+		}
+		Ok(true)
+	}
+
+	/// Start filling the column from the trie of the latest finalized block.
+	fn fill(self: &Arc<Self>, inner: &mut Inner<Block>) {
+		let Some((hash, number, state_root)) = inner.finalized else { return };
+		// Values written by an interrupted fill of the same block are still valid.
+		if inner.meta.complete || inner.meta.at != Some((hash, number)) {
+			inner.meta.epoch += 1;
+		}
+		inner.meta.at = Some((hash, number));
+		inner.meta.complete = false;
+		let mut transaction = Transaction::new();
+		inner.prune_layers(&mut transaction);
+		inner.write_meta(&mut transaction);
+		if let Err(e) = self.db.commit(transaction) {
+			warn!(target: LOG_TARGET, "Failed to start filling flat state: {}", e);
+			return
+		}
+
+		inner.filling = true;
+		let epoch = inner.meta.epoch;
+		let flat = self.clone();
+		let spawned = std::thread::Builder::new().name("flat-state".into()).spawn(move || {
+			let filled = flat.write_state(hash, number, state_root, epoch);
+			let mut inner = flat.inner.write();
+			inner.filling = false;
+			if !filled {
+				return
+			}
+			inner.meta.complete = true;
+			let mut transaction = Transaction::new();
+			inner.write_meta(&mut transaction);
+			if let Err(e) = flat.db.commit(transaction) {
+				warn!(target: LOG_TARGET, "Failed to complete flat state: {}", e);
+				inner.meta.complete = false;
+				return
+			}
+			info!(target: LOG_TARGET, "Flat state ready at #{}", number);
+
+			let Some((finalized, ..)) = inner.finalized else { return };
+			match flat.advance(&mut inner, finalized) {
+				Ok(true) => {},
+				Ok(false) => flat.fill(&mut inner),
+				Err(e) => warn!(target: LOG_TARGET, "Failed to move flat state: {}", e),
+			}
+		});
+		if let Err(e) = spawned {
+			warn!(target: LOG_TARGET, "Failed to spawn flat state worker: {}", e);
+			inner.filling = false;
+		}
+	}
+
+	/// Write all of the values of the given state to the column, tagged with `epoch`.
+	///
+	/// Returns `false` if the state is not available or the backend was dropped meanwhile.
+	fn write_state(
+		self: &Arc<Self>,
+		hash: Block::Hash,
+		number: u64,
+		state_root: Block::Hash,
+		epoch: u64,
+	) -> bool {
+		let Some(storage) = self.storage.upgrade() else { return false };
+		let hint = || {
+			sc_state_db::NodeDb::get(storage.as_ref(), state_root.as_ref())
+				.unwrap_or(None)
+				.is_some()
+		};
+		if storage.state_db.pin(&hash, number, hint).is_err() {
+			debug!(target: LOG_TARGET, "State of #{} ({:?}) is not available", number, hash);
+			return false
+		}
+		let result = self.write_pairs(&storage, state_root, epoch);
+		storage.state_db.unpin(&hash);
+		result.unwrap_or_else(|e| {
+			warn!(target: LOG_TARGET, "Failed to fill flat state at #{}: {}", number, e);
+			false
+		})
+	}
+
+	/// Write all pairs of the state, giving up with `Ok(false)` once the backend is dropped.
+	fn write_pairs(
+		self: &Arc<Self>,
+		storage: &Arc<StorageDb<Block>>,
+		state_root: Block::Hash,
+		epoch: u64,
+	) -> Result<bool, String> {
+		let state = DbStateBuilder::<Block>::new(storage.clone(), state_root).build();
+		let flush = |batch: &mut Transaction<DbHash>| -> Result<bool, String> {
+			self.db.commit(std::mem::take(batch)).map_err(|e| e.to_string())?;
+			// The thread is the last owner once the backend is gone.
+			Ok(Arc::strong_count(self) > 1)
+		};
+
+		let mut batch = Transaction::new();
+		let mut children = Vec::new();
+		for pair in state.pairs(IterArgs::default())? {
+			let (key, value) = pair?;
+			if well_known_keys::is_child_storage_key(&key) {
+				if let Some(storage_key) =
+					key.strip_prefix(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX)
+				{
+					children.push(storage_key.to_vec());
+				}
+				continue
+			}
+			let db_key = value_key(None, &key);
+			batch.set_from_vec(columns::FLAT_STATE, &db_key, encode_value(epoch, &value));
+			if batch.0.len() >= FILL_BATCH_SIZE && !flush(&mut batch)? {
+				return Ok(false)
+			}
+		}
+
+		for storage_key in children {
+			let mut args = IterArgs::default();
+			args.child_info = Some(ChildInfo::new_default(&storage_key));
+			for pair in state.pairs(args)? {
+				let (key, value) = pair?;
+				let db_key = value_key(Some(&storage_key[..]), &key);
+				batch.set_from_vec(columns::FLAT_STATE, &db_key, encode_value(epoch, &value));
+				if batch.0.len() >= FILL_BATCH_SIZE && !flush(&mut batch)? {
+					return Ok(false)
+				}
+			}
+		}
+		flush(&mut batch)
+	}
+}
@@ -33,9 +33,12 @@ pub mod offchain;
 pub mod bench;
 
 mod children;
+mod flat_state;
 mod parity_db;
 mod pinned_blocks_cache;
 mod record_stats_state;
+mod state_import;
+mod state_pruning;
 mod stats;
 #[cfg(any(feature = "rocksdb", test))]
 mod upgrade;
@@ -52,6 +55,7 @@ use std::{
 };
 
 use crate::{
+	flat_state::FlatState,
 	pinned_blocks_cache::PinnedBlocksCache,
 	record_stats_state::RecordStatsState,
 	stats::StateUsageStats,
@@ -90,13 +94,14 @@ use sp_state_machine::{
 	StateMachineStats, StorageCollection, StorageIterator, StorageKey, StorageValue,
 	UsageInfo as StateUsageInfo,
 };
-use sp_trie::{cache::SharedTrieCache, prefixed_key, MemoryDB, PrefixedMemoryDB};
+use sp_trie::{cache::SharedTrieCache, prefixed_key, MemoryDB, MerkleValue, PrefixedMemoryDB};
 
 // Re-export the Database trait so that one can pass an implementation of it.
 pub use sc_state_db::PruningMode;
 pub use sp_database::Database;
 
 pub use bench::BenchmarkingState;
+pub use state_pruning::{prune_archive_state, BackgroundPruning};
 
 const CACHE_HEADERS: usize = 8;
 
@@ -138,11 +143,23 @@ pub struct RefTrackingState<Block: BlockT> {
 	state: DbState<Block>,
 	storage: Arc<StorageDb<Block>>,
 	parent_hash: Option<Block::Hash>,
+	flat_state: Option<Arc<FlatState<Block>>>,
 }
 
 impl<B: BlockT> RefTrackingState<B> {
 	fn new(state: DbState<B>, storage: Arc<StorageDb<B>>, parent_hash: Option<B::Hash>) -> Self {
-		RefTrackingState { state, parent_hash, storage }
+		RefTrackingState { state, parent_hash, storage, flat_state: None }
+	}
+
+	/// Answer reads from the flat state when it covers this block.
+	fn with_flat_state(mut self, flat_state: Option<Arc<FlatState<B>>>) -> Self {
+		self.flat_state = flat_state;
+		self
+	}
+
+	fn flat_storage(&self, child_info: Option<&ChildInfo>, key: &[u8]) -> Option<Option<Vec<u8>>> {
+		let hash = self.parent_hash.as_ref()?;
+		self.flat_state.as_ref()?.storage(hash, child_info, key)
 	}
 }
 
@@ -192,7 +209,10 @@ impl<B: BlockT> StateBackend<HashingFor<B>> for RefTrackingState<B> {
 	type RawIter = RawIter<B>;
 
 	fn storage(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
-		self.state.storage(key)
+		match self.flat_storage(None, key) {
+			Some(value) => Ok(value),
+			None => self.state.storage(key),
+		}
 	}
 
 	fn storage_hash(&self, key: &[u8]) -> Result<Option<B::Hash>, Self::Error> {
@@ -204,7 +224,10 @@ impl<B: BlockT> StateBackend<HashingFor<B>> for RefTrackingState<B> {
 		child_info: &ChildInfo,
 		key: &[u8],
 	) -> Result<Option<Vec<u8>>, Self::Error> {
-		self.state.child_storage(child_info, key)
+		match self.flat_storage(Some(child_info), key) {
+			Some(value) => Ok(value),
+			None => self.state.child_storage(child_info, key),
+		}
 	}
 
 	fn child_storage_hash(
@@ -215,8 +238,26 @@ impl<B: BlockT> StateBackend<HashingFor<B>> for RefTrackingState<B> {
 		self.state.child_storage_hash(child_info, key)
 	}
 
+	fn closest_merkle_value(
+		&self,
+		key: &[u8],
+	) -> Result<Option<MerkleValue<B::Hash>>, Self::Error> {
+		self.state.closest_merkle_value(key)
+	}
+
+	fn child_closest_merkle_value(
+		&self,
+		child_info: &ChildInfo,
+		key: &[u8],
+	) -> Result<Option<MerkleValue<B::Hash>>, Self::Error> {
+		self.state.child_closest_merkle_value(child_info, key)
+	}
+
 	fn exists_storage(&self, key: &[u8]) -> Result<bool, Self::Error> {
-		self.state.exists_storage(key)
+		match self.flat_storage(None, key) {
+			Some(value) => Ok(value.is_some()),
+			None => self.state.exists_storage(key),
+		}
 	}
 
 	fn exists_child_storage(
@@ -224,7 +265,10 @@ impl<B: BlockT> StateBackend<HashingFor<B>> for RefTrackingState<B> {
 		child_info: &ChildInfo,
 		key: &[u8],
 	) -> Result<bool, Self::Error> {
-		self.state.exists_child_storage(child_info, key)
+		match self.flat_storage(Some(child_info), key) {
+			Some(value) => Ok(value.is_some()),
+			None => self.state.exists_child_storage(child_info, key),
+		}
 	}
 
 	fn next_storage_key(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
@@ -293,6 +337,13 @@ pub struct DatabaseSettings {
 	///
 	/// NOTE: only finalized blocks are subject for removal!
 	pub blocks_pruning: BlocksPruning,
+	/// Prune the state on a background worker instead of during canonicalization.
+	///
+	/// Ignored for archive state pruning modes.
+	pub background_pruning: Option<BackgroundPruning>,
+	/// Keep a flat copy of the finalized state in the database to answer storage reads at recent
+	/// blocks without walking the trie.
+	pub flat_state: bool,
 }
 
 /// Block pruning settings.
@@ -306,6 +357,16 @@ pub enum BlocksPruning {
 	Some(u32),
 }
 
+impl BlocksPruning {
+	/// True if this is an archive pruning mode (either KeepAll or KeepFinalized).
+	pub fn is_archive(&self) -> bool {
+		match *self {
+			BlocksPruning::KeepAll | BlocksPruning::KeepFinalized => true,
+			BlocksPruning::Some(_) => false,
+		}
+	}
+}
+
 /// Where to find the database..
 #[derive(Debug, Clone)]
 pub enum DatabaseSource {
@@ -409,6 +470,8 @@ pub(crate) mod columns {
 	/// Transactions
 	pub const TRANSACTION: u32 = 11;
 	pub const BODY_INDEX: u32 = 12;
+	/// Flat key-value copy of the finalized state, see the `flat_state` module.
+	pub const FLAT_STATE: u32 = 13;
 }
 
 struct PendingBlock<Block: BlockT> {
@@ -827,6 +890,8 @@ pub struct BlockImportOperation<Block: BlockT> {
 	finalized_blocks: Vec<(Block::Hash, Option<Justification>)>,
 	set_head: Option<Block::Hash>,
 	commit_state: bool,
+	/// The state was replaced as a whole rather than built from `storage_updates`.
+	reset_state: bool,
 	index_ops: Vec<IndexOperation>,
 }
 
@@ -880,6 +945,7 @@ impl<Block: BlockT> BlockImportOperation<Block> {
 		);
 
 		self.db_updates = transaction;
+		self.reset_state = true;
 		Ok(root)
 	}
 }
@@ -936,6 +1002,15 @@ impl<Block: BlockT> sc_client_api::backend::BlockImportOperation<Block>
 		Ok(root)
 	}
 
+	fn set_imported_state(&mut self, _root: Block::Hash) -> ClientResult<()> {
+		// The nodes are already in the database, the block only needs to be known to the state
+		// database.
+		self.db_updates = PrefixedMemoryDB::default();
+		self.commit_state = true;
+		self.reset_state = true;
+		Ok(())
+	}
+
 	fn insert_aux<I>(&mut self, ops: I) -> ClientResult<()>
 	where
 		I: IntoIterator<Item = (Vec<u8>, Option<Vec<u8>>)>,
@@ -1103,6 +1178,7 @@ pub struct Backend<Block: BlockT> {
 	state_usage: Arc<StateUsageStats>,
 	genesis_state: RwLock<Option<Arc<DbGenesisStorage<Block>>>>,
 	shared_trie_cache: Option<sp_trie::cache::SharedTrieCache<HashingFor<Block>>>,
+	flat_state: Option<Arc<FlatState<Block>>>,
 }
 
 impl<Block: BlockT> Backend<Block> {
@@ -1159,6 +1235,8 @@ impl<Block: BlockT> Backend<Block> {
 			state_pruning: Some(state_pruning),
 			source: DatabaseSource::Custom { db, require_create_flag: true },
 			blocks_pruning,
+			background_pruning: None,
+			flat_state: false,
 		};
 
 		Self::new(db_setting, canonicalization_delay).expect("failed to create test-db")
@@ -1212,8 +1290,17 @@ impl<Block: BlockT> Backend<Block> {
 
 		let offchain_storage = offchain::LocalStorage::new(db.clone());
 
+		let storage_db = Arc::new(storage_db);
+		if let Some(background_pruning) = config.background_pruning {
+			if !is_archive_pruning {
+				storage_db.state_db.set_background_pruning(true);
+				state_pruning::spawn_worker(Arc::downgrade(&storage_db), background_pruning)?;
+			}
+		}
+		let flat_state = config.flat_state.then(|| Arc::new(FlatState::new(&storage_db)));
+
 		let backend = Backend {
-			storage: Arc::new(storage_db),
+			storage: storage_db,
 			offchain_storage,
 			blockchain,
 			canonicalization_delay,
@@ -1226,6 +1313,7 @@ impl<Block: BlockT> Backend<Block> {
 			shared_trie_cache: config.trie_cache_maximum_size.map(|maximum_size| {
 				SharedTrieCache::new(sp_trie::cache::CacheSize::new(maximum_size))
 			}),
+			flat_state,
 		};
 
 		// Older DB versions have no last state key. Check if the state is available and set it.
@@ -1247,10 +1335,22 @@ impl<Block: BlockT> Backend<Block> {
 		}
 
 		db.commit(db_init_transaction)?;
+		backend.update_flat_state();
 
 		Ok(backend)
 	}
 
+	/// Move the flat state to the latest finalized block with state.
+	fn update_flat_state(&self) {
+		let Some(flat_state) = &self.flat_state else { return };
+		let Some((hash, number)) = self.blockchain.info().finalized_state else { return };
+		match self.blockchain.header_metadata(hash) {
+			Ok(header) =>
+				flat_state.note_finalized(hash, number.saturated_into(), header.state_root),
+			Err(e) => warn!(target: "db", "Failed to update flat state: {}", e),
+		}
+	}
+
 	/// Handle setting head within a transaction. `route_to` should be the last
 	/// block that existed in the database. `best_to` should be the best block
 	/// to be set.
@@ -1454,6 +1554,7 @@ impl<Block: BlockT> Backend<Block> {
 			last_finalized_num = *block_header.number();
 		}
 
+		let mut flat_block = None;
 		let imported = if let Some(pending_block) = operation.pending_block {
 			let hash = pending_block.header.hash();
 
@@ -1574,6 +1675,9 @@ impl<Block: BlockT> Backend<Block> {
 						sp_blockchain::Error::from_state_db(e)
 					})?;
 				apply_state_commit(&mut transaction, commit);
+				if !operation.reset_state {
+					flat_block = Some((hash, parent_hash, number_u64));
+				}
 				if number <= last_finalized_num {
 					// Canonicalize in the db when re-importing existing blocks with state.
 					let commit = self.storage.state_db.canonicalize_block(&hash).map_err(
@@ -1744,10 +1848,23 @@ impl<Block: BlockT> Backend<Block> {
 			cache_header(&mut self.blockchain.header_cache.lock(), hash, Some(header));
 		}
 
+		if let (Some(flat_state), Some((hash, parent_hash, number))) =
+			(&self.flat_state, flat_block)
+		{
+			flat_state.note_block(
+				hash,
+				parent_hash,
+				number,
+				operation.storage_updates,
+				operation.child_storage_updates,
+			);
+		}
+
 		for m in meta_updates {
 			self.blockchain.update_meta(m);
 		}
 		self.blockchain.update_block_gap(block_gap);
+		self.update_flat_state();
 
 		Ok(())
 	}
@@ -2053,6 +2170,7 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 			finalized_blocks: Vec::new(),
 			set_head: None,
 			commit_state: false,
+			reset_state: false,
 			index_ops: Default::default(),
 		})
 	}
@@ -2110,6 +2228,7 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 
 		self.storage.db.commit(transaction)?;
 		self.blockchain.update_meta(m);
+		self.update_flat_state();
 		Ok(())
 	}
 
@@ -2326,6 +2445,9 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 		};
 
 		revert_leaves()?;
+		if !reverted_finalized.is_empty() {
+			self.update_flat_state();
+		}
 
 		Ok((reverted, reverted_finalized))
 	}
@@ -2430,7 +2552,8 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 							self.shared_trie_cache.as_ref().map(|c| c.local_cache()),
 						)
 						.build();
-					let state = RefTrackingState::new(db_state, self.storage.clone(), Some(hash));
+					let state = RefTrackingState::new(db_state, self.storage.clone(), Some(hash))
+						.with_flat_state(self.flat_state.clone());
 					Ok(RecordStatsState::new(state, Some(hash), self.state_usage.clone()))
 				} else {
 					Err(sp_blockchain::Error::UnknownBlock(format!(
@@ -2443,6 +2566,15 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 		}
 	}
 
+	fn import_state_trie(
+		&self,
+		child_info: Option<&ChildInfo>,
+		state_version: StateVersion,
+		key_values: &mut dyn Iterator<Item = (Vec<u8>, Vec<u8>)>,
+	) -> ClientResult<Block::Hash> {
+		state_import::write_trie(&self.storage, child_info, state_version, key_values)
+	}
+
 	fn have_state_at(&self, hash: Block::Hash, number: NumberFor<Block>) -> bool {
 		if self.is_archive {
 			match self.blockchain.header_metadata(hash) {
@@ -2677,6 +2809,8 @@ pub(crate) mod tests {
 				state_pruning: Some(PruningMode::blocks_pruning(1)),
 				source: DatabaseSource::Custom { db: backing, require_create_flag: false },
 				blocks_pruning: BlocksPruning::KeepFinalized,
+				background_pruning: None,
+				flat_state: false,
 			},
 			0,
 		)
@@ -2687,6 +2821,59 @@ pub(crate) mod tests {
 		}
 	}
 
+	#[test]
+	fn prune_archive_state_keeps_recent_states() {
+		let backend = Backend::<Block>::new_test_with_tx_storage(BlocksPruning::KeepFinalized, 0);
+		let mut hashes = Vec::new();
+		let mut parent = Default::default();
+		for number in 0..6 {
+			parent = insert_header(&backend, number, parent, None, Default::default());
+			hashes.push(parent);
+		}
+		let state_roots: Vec<H256> = hashes
+			.iter()
+			.map(|hash| *backend.blockchain().header(*hash).unwrap().unwrap().state_root())
+			.collect();
+		let backing = backend.storage.db.clone();
+		drop(backend);
+
+		let settings = |state_pruning| DatabaseSettings {
+			trie_cache_maximum_size: None,
+			state_pruning,
+			source: DatabaseSource::Custom { db: backing.clone(), require_create_flag: false },
+			blocks_pruning: BlocksPruning::KeepFinalized,
+			background_pruning: None,
+			flat_state: false,
+		};
+		let has_state = |root: &H256| {
+			backing
+				.get(columns::STATE, &prefixed_key::<BlakeTwo256>(root, EMPTY_PREFIX))
+				.is_some()
+		};
+		assert!(prune_archive_state::<Block>(settings(None), 2).unwrap() > 0);
+		assert!(state_roots[..3].iter().all(|root| !has_state(root)));
+		assert!(state_roots[3..].iter().all(|root| has_state(root)));
+
+		let backend =
+			Backend::<Block>::new(settings(Some(PruningMode::blocks_pruning(2))), 0).unwrap();
+		assert!(backend.have_state_at(hashes[5], 5));
+		assert!(!backend.have_state_at(hashes[1], 1));
+		// every block stores its parent hash on top of the state of its parent
+		for number in 3..6 {
+			let state = backend.state_at(hashes[number]).unwrap();
+			for parent in &hashes[..number] {
+				assert_eq!(state.storage(parent.as_ref()).unwrap(), Some(parent.as_ref().to_vec()));
+			}
+		}
+		assert!(backend.state_at(hashes[2]).is_err());
+		assert!(backend.state_at(hashes[1]).is_err());
+
+		// the pruning window carries on from the converted states
+		insert_header(&backend, 6, hashes[5], None, Default::default());
+		assert!(!has_state(&state_roots[3]));
+		assert!(has_state(&state_roots[4]));
+	}
+
 	#[test]
 	fn set_state_data() {
 		set_state_data_inner(StateVersion::V0);
@@ -2769,6 +2956,142 @@ pub(crate) mod tests {
 		}
 	}
 
+	fn flat_state_backend(db: Arc<dyn Database<DbHash>>) -> Backend<Block> {
+		Backend::<Block>::new(
+			DatabaseSettings {
+				trie_cache_maximum_size: None,
+				state_pruning: Some(PruningMode::blocks_pruning(16)),
+				source: DatabaseSource::Custom { db, require_create_flag: false },
+				blocks_pruning: BlocksPruning::Some(16),
+				background_pruning: None,
+				flat_state: true,
+			},
+			0,
+		)
+		.unwrap()
+	}
+
+	fn import_flat_state_block(
+		backend: &Backend<Block>,
+		number: u64,
+		parent_hash: H256,
+		changes: Vec<(Vec<u8>, Option<Vec<u8>>)>,
+	) -> H256 {
+		let state_version = StateVersion::default();
+		let mut op = backend.begin_operation().unwrap();
+		backend.begin_state_operation(&mut op, parent_hash).unwrap();
+		let mut header = Header {
+			number,
+			parent_hash,
+			state_root: Default::default(),
+			digest: Default::default(),
+			extrinsics_root: Default::default(),
+		};
+		if number == 0 {
+			let top = changes.into_iter().map(|(k, v)| (k, v.unwrap())).collect();
+			header.state_root = op
+				.reset_storage(Storage { top, children_default: Default::default() }, state_version)
+				.unwrap();
+		} else {
+			let (root, overlay) = op.old_state.storage_root(
+				changes.iter().map(|(k, v)| (k.as_slice(), v.as_deref())),
+				state_version,
+			);
+			op.update_db_storage(overlay).unwrap();
+			op.update_storage(changes, Vec::new()).unwrap();
+			header.state_root = root;
+		}
+		op.set_block_data(header.clone(), Some(vec![]), None, None, NewBlockState::Best)
+			.unwrap();
+		backend.commit_operation(op).unwrap();
+		header.hash()
+	}
+
+	fn wait_for_flat_state(backend: &Backend<Block>) -> Arc<FlatState<Block>> {
+		let flat_state = backend.flat_state.clone().unwrap();
+		for _ in 0..100 {
+			if flat_state.is_ready() {
+				break
+			}
+			std::thread::sleep(std::time::Duration::from_millis(10));
+		}
+		assert!(flat_state.is_ready());
+		flat_state
+	}
+
+	#[test]
+	fn flat_state_follows_finalized_blocks() {
+		let db = sp_database::as_database(kvdb_memorydb::create(crate::utils::NUM_COLUMNS));
+		let backend = flat_state_backend(db);
+
+		let genesis = import_flat_state_block(
+			&backend,
+			0,
+			Default::default(),
+			vec![(vec![1, 2, 3], Some(vec![9, 9, 9])), (vec![1, 3, 5], Some(vec![2, 4, 6]))],
+		);
+		let flat_state = wait_for_flat_state(&backend);
+
+		let block1 = import_flat_state_block(
+			&backend,
+			1,
+			genesis,
+			vec![(vec![1, 3, 5], None), (vec![5, 5, 5], Some(vec![4, 5, 6]))],
+		);
+		let block2 =
+			import_flat_state_block(&backend, 2, block1, vec![(vec![1, 2, 3], Some(vec![0]))]);
+
+		let check = || {
+			let state = backend.state_at(block2).unwrap();
+			assert_eq!(state.storage(&[1, 2, 3]).unwrap(), Some(vec![0]));
+			assert_eq!(state.storage(&[1, 3, 5]).unwrap(), None);
+			assert!(state.exists_storage(&[5, 5, 5]).unwrap());
+			assert_eq!(state.next_storage_key(&[1, 2, 3]).unwrap(), Some(vec![5, 5, 5]));
+			assert_eq!(state.next_storage_key(&[5, 5, 5]).unwrap(), None);
+
+			let state = backend.state_at(genesis).unwrap();
+			assert_eq!(state.storage(&[1, 3, 5]).unwrap(), Some(vec![2, 4, 6]));
+			assert_eq!(state.next_storage_key(&[1, 2, 3]).unwrap(), Some(vec![1, 3, 5]));
+		};
+		check();
+		assert_eq!(flat_state.storage(&block2, None, &[1, 3, 5]), Some(None));
+
+		backend.finalize_block(block2, None).unwrap();
+		assert_eq!(flat_state.storage(&block2, None, &[1, 2, 3]), Some(Some(vec![0])));
+		assert_eq!(flat_state.storage(&block2, None, &[1, 3, 5]), Some(None));
+		assert_eq!(flat_state.storage(&genesis, None, &[1, 2, 3]), None);
+		check();
+	}
+
+	#[test]
+	fn flat_state_is_reused_after_restart() {
+		let db = sp_database::as_database(kvdb_memorydb::create(crate::utils::NUM_COLUMNS));
+		let (genesis, block1) = {
+			let backend = flat_state_backend(db.clone());
+			let genesis = import_flat_state_block(
+				&backend,
+				0,
+				Default::default(),
+				vec![(vec![1, 2, 3], Some(vec![9, 9, 9]))],
+			);
+			wait_for_flat_state(&backend);
+			let block1 =
+				import_flat_state_block(&backend, 1, genesis, vec![(vec![1, 2, 3], Some(vec![1]))]);
+			(genesis, block1)
+		};
+
+		// the copy and the changes of the unfinalized block are read back from the database
+		let backend = flat_state_backend(db);
+		let flat_state = backend.flat_state.clone().unwrap();
+		assert!(flat_state.is_ready());
+		assert_eq!(flat_state.storage(&genesis, None, &[1, 2, 3]), Some(Some(vec![9, 9, 9])));
+		assert_eq!(flat_state.storage(&block1, None, &[1, 2, 3]), Some(Some(vec![1])));
+
+		backend.finalize_block(block1, None).unwrap();
+		assert_eq!(flat_state.storage(&block1, None, &[1, 2, 3]), Some(Some(vec![1])));
+		assert_eq!(flat_state.storage(&genesis, None, &[1, 2, 3]), None);
+	}
+
 	#[test]
 	fn delete_only_when_negative_rc() {
 		sp_tracing::try_init_simple();
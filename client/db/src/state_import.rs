@@ -0,0 +1,140 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Import of states too large to be held in memory, one trie at a time.
+//!
+//! The trie nodes are built from the sorted key-value pairs as they come and written straight to
+//! the state column, so that only the path to the last pair is kept in memory. The nodes are
+//! not tracked by the state database until the block they belong to is imported, like the nodes
+//! of a canonicalized block.
+
+use crate::{columns, DbHash, StorageDb};
+use hash_db::{AsHashDB, HashDB, Hasher, Prefix};
+use sp_blockchain::{Error as ClientError, Result as ClientResult};
+use sp_core::storage::{ChildInfo, StateVersion};
+use sp_database::{error::DatabaseError, Transaction};
+use sp_runtime::traits::{Block as BlockT, HashingFor};
+use sp_state_machine::DBValue;
+use sp_trie::{prefixed_key, trie_visit, KeySpacedDBMut, LayoutV0, LayoutV1, TrieBuilder};
+
+/// Number of trie nodes written to the database at once.
+const WRITE_BATCH_SIZE: usize = 16 * 1024;
+
+/// Write-only node database committing the inserted nodes to the state column in batches.
+struct NodeWriter<'a, Block: BlockT> {
+	storage: &'a StorageDb<Block>,
+	transaction: Transaction<DbHash>,
+	pending: usize,
+	error: Option<DatabaseError>,
+}
+
+impl<'a, Block: BlockT> NodeWriter<'a, Block> {
+	fn flush(&mut self) {
+		let transaction = std::mem::take(&mut self.transaction);
+		self.pending = 0;
+		if self.error.is_none() {
+			if let Err(e) = self.storage.db.commit(transaction) {
+				self.error = Some(e);
+			}
+		}
+	}
+}
+
+impl<'a, Block: BlockT> HashDB<HashingFor<Block>, DBValue> for NodeWriter<'a, Block> {
+	fn get(&self, _key: &Block::Hash, _prefix: Prefix) -> Option<DBValue> {
+		None
+	}
+
+	fn contains(&self, _key: &Block::Hash, _prefix: Prefix) -> bool {
+		false
+	}
+
+	fn insert(&mut self, prefix: Prefix, value: &[u8]) -> Block::Hash {
+		let key = HashingFor::<Block>::hash(value);
+		self.emplace(key, prefix, value.to_vec());
+		key
+	}
+
+	fn emplace(&mut self, key: Block::Hash, prefix: Prefix, value: DBValue) {
+		let key = if self.storage.prefix_keys {
+			prefixed_key::<HashingFor<Block>>(&key, prefix)
+		} else {
+			key.as_ref().to_vec()
+		};
+		self.transaction.set_from_vec(columns::STATE, &key, value);
+		self.pending += 1;
+		if self.pending >= WRITE_BATCH_SIZE {
+			self.flush();
+		}
+	}
+
+	fn remove(&mut self, _key: &Block::Hash, _prefix: Prefix) {}
+}
+
+impl<'a, Block: BlockT> AsHashDB<HashingFor<Block>, DBValue> for NodeWriter<'a, Block> {
+	fn as_hash_db(&self) -> &dyn HashDB<HashingFor<Block>, DBValue> {
+		self
+	}
+
+	fn as_hash_db_mut<'b>(&'b mut self) -> &'b mut (dyn HashDB<HashingFor<Block>, DBValue> + 'b) {
+		self
+	}
+}
+
+/// Build the trie of the given sorted key-value pairs into the state column and return its
+/// root.
+pub(crate) fn write_trie<Block: BlockT>(
+	storage: &StorageDb<Block>,
+	child_info: Option<&ChildInfo>,
+	state_version: StateVersion,
+	key_values: &mut dyn Iterator<Item = (Vec<u8>, Vec<u8>)>,
+) -> ClientResult<Block::Hash> {
+	let mut writer =
+		NodeWriter { storage, transaction: Transaction::new(), pending: 0, error: None };
+	let root = match child_info {
+		Some(child_info) => {
+			let mut db = KeySpacedDBMut::new(&mut writer, child_info.keyspace());
+			visit::<Block, _>(&mut db, state_version, key_values)
+		},
+		None => visit::<Block, _>(&mut writer, state_version, key_values),
+	};
+	writer.flush();
+	if let Some(e) = writer.error {
+		return Err(ClientError::Backend(format!("Failed to write state: {}", e)))
+	}
+	root.ok_or_else(|| ClientError::Backend("Missing root of the imported trie".into()))
+}
+
+fn visit<Block: BlockT, DB: HashDB<HashingFor<Block>, DBValue>>(
+	db: &mut DB,
+	state_version: StateVersion,
+	key_values: &mut dyn Iterator<Item = (Vec<u8>, Vec<u8>)>,
+) -> Option<Block::Hash> {
+	match state_version {
+		StateVersion::V0 => {
+			let mut builder = TrieBuilder::<LayoutV0<HashingFor<Block>>, _>::new(db);
+			trie_visit(key_values, &mut builder);
+			builder.root
+		},
+		StateVersion::V1 => {
+			let mut builder = TrieBuilder::<LayoutV1<HashingFor<Block>>, _>::new(db);
+			trie_visit(key_values, &mut builder);
+			builder.root
+		},
+	}
+}
@@ -0,0 +1,449 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Background state pruning and conversion of archive databases into pruned ones.
+
+use crate::{
+	apply_state_commit, columns, Backend, DatabaseSettings, DbHash, StateMetaDb, StorageDb,
+};
+use hash_db::{HashDBRef, Prefix, EMPTY_PREFIX};
+use kvdb::{DBTransaction, KeyValueDB};
+use log::{debug, info, warn};
+use sc_client_api::backend::Backend as _;
+use sc_state_db::{LastCanonicalized, PruningMode};
+use sp_blockchain::{Error as ClientError, HeaderBackend, Result as ClientResult};
+use sp_core::storage::well_known_keys;
+use sp_database::{Database, Transaction};
+use sp_runtime::traits::{
+	Block as BlockT, HashingFor, Header as HeaderT, SaturatedConversion, Zero,
+};
+use sp_state_machine::DBValue;
+use sp_trie::{prefixed_key, KeySpacedDB, LayoutV1, Trie, TrieDBBuilder};
+use std::{
+	cell::{Cell, RefCell},
+	collections::{HashMap, HashSet},
+	path::Path,
+	sync::Weak,
+	time::Duration,
+};
+
+const LOG_TARGET: &str = "db::pruning";
+
+/// Number of deleted nodes written to the database at once when converting an archive database.
+const DELETE_BATCH_SIZE: usize = 64 * 1024;
+
+/// Number of kept nodes written to the temporary database at once when converting an archive
+/// database.
+const KEEP_BATCH_SIZE: usize = 64 * 1024;
+
+/// Name of the temporary database holding the nodes of the kept states, next to the database.
+const KEPT_DB_DIR: &str = "prune-state-tmp";
+
+/// Settings of the background state pruning worker.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BackgroundPruning {
+	/// Time between two pruning ticks.
+	pub interval: Duration,
+	/// Maximum number of blocks pruned per tick.
+	pub blocks_per_tick: u32,
+}
+
+impl Default for BackgroundPruning {
+	fn default() -> Self {
+		Self { interval: Duration::from_secs(1), blocks_per_tick: 32 }
+	}
+}
+
+/// Spawn the thread pruning the blocks that fell out of the pruning window.
+///
+/// The worker stops once the storage is dropped.
+pub(crate) fn spawn_worker<Block: BlockT>(
+	storage: Weak<StorageDb<Block>>,
+	settings: BackgroundPruning,
+) -> ClientResult<()> {
+	std::thread::Builder::new()
+		.name("state-pruning".into())
+		.spawn(move || loop {
+			std::thread::sleep(settings.interval);
+			let Some(storage) = storage.upgrade() else { break };
+			let result =
+				storage.state_db.prune_pending(settings.blocks_per_tick.into(), |commit| {
+					let mut transaction = Transaction::new();
+					apply_state_commit(&mut transaction, commit);
+					storage.db.commit(transaction)
+				});
+			match result {
+				Ok(0) => {},
+				Ok(pruned) => debug!(target: LOG_TARGET, "Pruned state of {} blocks", pruned),
+				Err(e) => {
+					warn!(target: LOG_TARGET, "Failed to prune state: {:?}", e);
+					if let Err(e) = storage.state_db.reset(StateMetaDb(storage.db.clone())) {
+						warn!(target: LOG_TARGET, "Failed to reset state db: {:?}", e);
+					}
+				},
+			}
+		})
+		.map(|_| ())
+		.map_err(|e| ClientError::Backend(format!("Failed to spawn state pruning worker: {}", e)))
+}
+
+/// The phase of the trie walk over the state column.
+#[derive(Clone, Copy)]
+enum Walk {
+	/// Collect the nodes of a state that is kept, with the state block number.
+	Keep(u64),
+	/// Collect the nodes of an older state for deletion.
+	Delete,
+}
+
+/// Walks the state tries, visiting each node at most once.
+///
+/// Visited nodes are skipped by reporting them as missing, which makes the trie iterator move
+/// on to the next sibling. The kept states are walked from the most recent one, so the first
+/// kept state visiting a node is the most recent one using it.
+struct StateWalker<'a, Block: BlockT> {
+	db: &'a dyn Database<DbHash>,
+	prefix_keys: bool,
+	walk: Cell<Walk>,
+	/// Number of the most recent kept state.
+	last_kept: u64,
+	/// Nodes of the kept states, written in batches from `kept_pending`.
+	kept: &'a dyn KeyValueDB,
+	/// Nodes of the kept states that are not yet written to `kept`.
+	kept_pending: RefCell<HashSet<Vec<u8>>>,
+	/// Number of nodes of the kept states.
+	kept_count: Cell<usize>,
+	/// Nodes of the kept states that are not used by the state of the given block, nor by any
+	/// more recent state.
+	obsolete: RefCell<HashMap<u64, Vec<Vec<u8>>>>,
+	/// Nodes of older states that are not used by any kept state.
+	deleted: RefCell<HashSet<Vec<u8>>>,
+	/// Number of nodes missing from the database.
+	missing: Cell<usize>,
+	/// Error of the temporary database, if any.
+	error: RefCell<Option<std::io::Error>>,
+	_phantom: std::marker::PhantomData<Block>,
+}
+
+impl<'a, Block: BlockT> StateWalker<'a, Block> {
+	fn new(
+		db: &'a dyn Database<DbHash>,
+		prefix_keys: bool,
+		kept: &'a dyn KeyValueDB,
+		last_kept: u64,
+	) -> Self {
+		StateWalker {
+			db,
+			prefix_keys,
+			walk: Cell::new(Walk::Delete),
+			last_kept,
+			kept,
+			kept_pending: Default::default(),
+			kept_count: Cell::new(0),
+			obsolete: Default::default(),
+			deleted: Default::default(),
+			missing: Cell::new(0),
+			error: Default::default(),
+			_phantom: Default::default(),
+		}
+	}
+
+	/// Check whether the node under `key` is used by a kept state.
+	///
+	/// Nodes are reported as kept if the temporary database fails, so they are never deleted.
+	fn is_kept(&self, key: &[u8]) -> bool {
+		if self.kept_pending.borrow().contains(key) {
+			return true
+		}
+		match self.kept.get(0, key) {
+			Ok(value) => value.is_some(),
+			Err(e) => {
+				self.error.borrow_mut().get_or_insert(e);
+				true
+			},
+		}
+	}
+
+	/// Record the node under `key` as used by the kept state of block `number`.
+	fn keep(&self, key: Vec<u8>, number: u64) {
+		if number < self.last_kept {
+			self.obsolete.borrow_mut().entry(number + 1).or_default().push(key.clone());
+		}
+		self.kept_count.set(self.kept_count.get() + 1);
+		let mut pending = self.kept_pending.borrow_mut();
+		pending.insert(key);
+		if pending.len() >= KEEP_BATCH_SIZE {
+			let mut transaction = DBTransaction::new();
+			for key in pending.drain() {
+				transaction.put(0, &key, &[]);
+			}
+			if let Err(e) = self.kept.write(transaction) {
+				self.error.borrow_mut().get_or_insert(e);
+			}
+		}
+	}
+
+	/// Returns the first error of the temporary database.
+	fn check_error(&self) -> ClientResult<()> {
+		match self.error.borrow_mut().take() {
+			Some(e) => Err(ClientError::Backend(format!(
+				"Failed to access the temporary database of the kept state nodes: {}",
+				e
+			))),
+			None => Ok(()),
+		}
+	}
+
+	fn db_key(&self, key: &Block::Hash, prefix: Prefix) -> Vec<u8> {
+		if self.prefix_keys {
+			prefixed_key::<HashingFor<Block>>(key, prefix)
+		} else {
+			key.as_ref().to_vec()
+		}
+	}
+
+	/// Check whether the root node of a state is in the database.
+	fn has_state(&self, root: &Block::Hash) -> bool {
+		self.db.contains(columns::STATE, &self.db_key(root, EMPTY_PREFIX))
+	}
+
+	/// Visit the top trie under `root` and all of its child tries.
+	fn walk(&self, root: &Block::Hash, walk: Walk) {
+		self.walk.set(walk);
+		let mut child_roots = Vec::new();
+		let trie = TrieDBBuilder::<LayoutV1<HashingFor<Block>>>::new(self, root).build();
+		if let Ok(iter) = trie.iter() {
+			// Errors are expected for the nodes that were visited before.
+			for (key, value) in iter.flatten() {
+				if let Some(storage_key) =
+					key.strip_prefix(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX)
+				{
+					let mut child_root = Block::Hash::default();
+					if value.len() == child_root.as_ref().len() {
+						child_root.as_mut().copy_from_slice(&value);
+						child_roots.push((storage_key.to_vec(), child_root));
+					}
+				}
+			}
+		}
+		for (keyspace, child_root) in child_roots {
+			let db = KeySpacedDB::new(self, &keyspace);
+			let trie = TrieDBBuilder::<LayoutV1<HashingFor<Block>>>::new(&db, &child_root).build();
+			let iter = trie.iter();
+			if let Ok(iter) = iter {
+				iter.for_each(drop);
+			}
+		}
+	}
+}
+
+impl<'a, Block: BlockT> HashDBRef<HashingFor<Block>, DBValue> for StateWalker<'a, Block> {
+	fn get(&self, key: &Block::Hash, prefix: Prefix) -> Option<DBValue> {
+		let key = self.db_key(key, prefix);
+		if self.deleted.borrow().contains(&key) || self.is_kept(&key) {
+			return None
+		}
+		let value = self.db.get(columns::STATE, &key);
+		match (value.is_some(), self.walk.get()) {
+			(false, _) => self.missing.set(self.missing.get() + 1),
+			(true, Walk::Keep(number)) => self.keep(key, number),
+			(true, Walk::Delete) => {
+				self.deleted.borrow_mut().insert(key);
+			},
+		}
+		value
+	}
+
+	fn contains(&self, key: &Block::Hash, prefix: Prefix) -> bool {
+		self.get(key, prefix).is_some()
+	}
+}
+
+/// Convert an archive database into a pruned one that keeps the state of the last `keep`
+/// canonical blocks. Returns the number of deleted state nodes.
+///
+/// The states of the kept blocks are walked first to find their nodes, then the states of all
+/// older canonical blocks are walked and the nodes not used by the kept states are deleted.
+/// Unfinalized blocks of an `ArchiveAll` database are reverted, since their state is not tracked
+/// by the canonicalization overlay. State of non-canonical blocks that were already discarded is
+/// not reachable and stays in the database.
+///
+/// The nodes of the kept states are stored in a temporary database next to the database while
+/// converting, so that they don't need to fit in memory.
+///
+/// Only databases without reference counting are supported, i.e. not ParityDB.
+///
+/// The deleted nodes are committed in batches while walking the older states, so an interrupted
+/// conversion leaves an archive database whose states older than the kept ones are partially
+/// deleted and can't be read anymore. Running the conversion again completes it, but the nodes
+/// only reachable through the nodes deleted by the interrupted run stay in the database.
+pub fn prune_archive_state<Block: BlockT>(
+	mut settings: DatabaseSettings,
+	keep: u32,
+) -> ClientResult<u64> {
+	settings.state_pruning = None;
+	settings.background_pruning = None;
+	let kept_db_path = settings.source.path().and_then(Path::parent).map(|p| p.join(KEPT_DB_DIR));
+	let backend = Backend::<Block>::new(settings, 0)?;
+	let db = backend.storage.db.clone();
+	if db.supports_ref_counting() {
+		return Err(ClientError::Backend(
+			"Converting the state of a database with reference counting, e.g. ParityDB, is not \
+			 supported. Resync the node with `--state-pruning` instead"
+				.into(),
+		))
+	}
+
+	let last_canonical = match backend.storage.state_db.pruning_mode() {
+		PruningMode::ArchiveAll => {
+			let info = backend.blockchain.info();
+			let unfinalized = info.best_number - info.finalized_number;
+			if !unfinalized.is_zero() {
+				info!(target: LOG_TARGET, "Reverting {} unfinalized blocks", unfinalized);
+				backend.revert(unfinalized, false)?;
+			}
+			backend.blockchain.info().finalized_number.saturated_into::<u64>()
+		},
+		PruningMode::ArchiveCanonical => match backend.storage.state_db.last_canonicalized() {
+			LastCanonicalized::Block(number) => number,
+			_ => return Err(ClientError::Backend("The database has no canonicalized block".into())),
+		},
+		PruningMode::Constrained(_) =>
+			return Err(ClientError::Backend("The database state is already pruned".into())),
+	};
+	let canonical_block = |number: u64| -> ClientResult<(Block::Hash, Block::Hash)> {
+		let hash = backend.blockchain.hash(number.saturated_into())?.ok_or_else(|| {
+			ClientError::UnknownBlock(format!("Missing canonical block #{}", number))
+		})?;
+		let header = backend.blockchain.expect_header(hash)?;
+		Ok((hash, *header.state_root()))
+	};
+
+	let kept_db = open_kept_db(kept_db_path.as_deref())?;
+	let walker =
+		StateWalker::<Block>::new(&*db, backend.storage.prefix_keys, &*kept_db, last_canonical);
+	let mut kept_blocks = Vec::new();
+	for number in (last_canonical.saturating_sub(keep.into())..=last_canonical).rev() {
+		let (hash, state_root) = canonical_block(number)?;
+		if number < last_canonical && !walker.has_state(&state_root) {
+			// Older states are not available, e.g. the node was warp synced.
+			break
+		}
+		walker.walk(&state_root, Walk::Keep(number));
+		walker.check_error()?;
+		if walker.missing.get() > 0 {
+			return Err(ClientError::StateDatabase(format!(
+				"State of block #{} ({:?}) is incomplete",
+				number, hash
+			)))
+		}
+		kept_blocks.push(hash);
+	}
+	let first_kept = last_canonical + 1 - kept_blocks.len() as u64;
+	info!(
+		target: LOG_TARGET,
+		"Keeping {} state nodes of blocks #{}..=#{}",
+		walker.kept_count.get(),
+		first_kept,
+		last_canonical,
+	);
+
+	let mut deleted = 0;
+	let mut flush = |walker: &StateWalker<Block>| -> ClientResult<()> {
+		let mut transaction = Transaction::new();
+		for key in walker.deleted.borrow_mut().drain() {
+			transaction.remove(columns::STATE, &key);
+			deleted += 1;
+		}
+		db.commit(transaction)?;
+		Ok(())
+	};
+	for number in (0..first_kept).rev() {
+		let (_, state_root) = canonical_block(number)?;
+		walker.walk(&state_root, Walk::Delete);
+		walker.check_error()?;
+		if walker.deleted.borrow().len() >= DELETE_BATCH_SIZE {
+			flush(&walker)?;
+			info!(target: LOG_TARGET, "Pruned state down to block #{}", number);
+		}
+	}
+	flush(&walker)?;
+
+	// The nodes last used by the state of block `n` are deleted once block `n + 1` is pruned.
+	let mut obsolete = walker.obsolete.take();
+	drop(walker);
+	drop(kept_db);
+	if let Some(path) = &kept_db_path {
+		remove_kept_db(path)?;
+	}
+	// The oldest kept state has no pruning journal record.
+	kept_blocks.pop();
+	let window = kept_blocks
+		.into_iter()
+		.rev()
+		.zip(first_kept + 1..)
+		.map(|(hash, number)| (hash, obsolete.remove(&number).unwrap_or_default()))
+		.collect();
+	let last_canonical_hash = canonical_block(last_canonical)?.0;
+	let commit = sc_state_db::convert_archive_to_constrained(
+		&StateMetaDb(db.clone()),
+		(last_canonical_hash, last_canonical),
+		window,
+	)
+	.map_err(ClientError::from_state_db)?;
+	let mut transaction = Transaction::new();
+	apply_state_commit(&mut transaction, commit);
+	db.commit(transaction)?;
+
+	info!(
+		target: LOG_TARGET,
+		"Converted the database to keep the state of {} blocks, {} nodes deleted",
+		last_canonical - first_kept,
+		deleted,
+	);
+	Ok(deleted)
+}
+
+/// Open an empty temporary database for the nodes of the kept states at `path`.
+///
+/// The database is kept in memory if there is no `path`.
+fn open_kept_db(path: Option<&Path>) -> ClientResult<Box<dyn KeyValueDB>> {
+	#[cfg(feature = "rocksdb")]
+	if let Some(path) = path {
+		// Leftover of an interrupted conversion.
+		remove_kept_db(path)?;
+		let config = kvdb_rocksdb::DatabaseConfig::with_columns(1);
+		let db = kvdb_rocksdb::Database::open(&config, path).map_err(|e| {
+			ClientError::Backend(format!("Failed to open {}: {}", path.display(), e))
+		})?;
+		return Ok(Box::new(db))
+	}
+	#[cfg(not(feature = "rocksdb"))]
+	let _ = path;
+
+	Ok(Box::new(kvdb_memorydb::create(1)))
+}
+
+/// Remove the temporary database at `path`, if any.
+fn remove_kept_db(path: &Path) -> ClientResult<()> {
+	match std::fs::remove_dir_all(path) {
+		Err(e) if e.kind() != std::io::ErrorKind::NotFound =>
+			Err(ClientError::Backend(format!("Failed to remove {}: {}", path.display(), e))),
+		_ => Ok(()),
+	}
+}
@@ -99,6 +99,9 @@ pub enum Error {
 
 	#[error("Output exceeds bounds of wasm memory")]
 	OutputExceedsBounds,
+
+	#[error("Execution aborted: fuel limit exhausted after consuming {0} units of fuel")]
+	OutOfFuel(u64),
 }
 
 impl From<&'static str> for Error {
@@ -111,6 +111,17 @@ pub trait WasmInstance: Send {
 		self.call(method.into(), data)
 	}
 
+	/// Limit the amount of fuel each of the following calls on this instance may consume.
+	///
+	/// `None` lifts the limit. Exceeding the limit aborts the call with [`Error::OutOfFuel`].
+	/// Returns an error if a limit is given but the instance doesn't support fuel metering.
+	fn set_fuel_limit(&mut self, fuel_limit: Option<u64>) -> Result<(), Error> {
+		match fuel_limit {
+			None => Ok(()),
+			Some(_) => Err(Error::Other("fuel metering is not supported".into())),
+		}
+	}
+
 	/// Get the value from a global with the given `name`.
 	///
 	/// This method is only suitable for getting immutable globals.
@@ -23,6 +23,7 @@ use crate::{
 };
 
 use std::{
+	collections::HashMap,
 	marker::PhantomData,
 	panic::{AssertUnwindSafe, UnwindSafe},
 	path::PathBuf,
@@ -37,6 +38,7 @@ use sc_executor_common::{
 	},
 };
 use sp_core::traits::{CallContext, CodeExecutor, Externalities, RuntimeCode};
+use sp_externalities::ExternalitiesExt;
 use sp_version::{GetNativeVersion, NativeVersion, RuntimeVersion};
 use sp_wasm_interface::{ExtendedHostFunctions, HostFunctions};
 
@@ -63,6 +65,8 @@ where
 	})
 }
 
+pub use sp_core::traits::{RpcCallExt, RpcCallKind};
+
 /// Delegate for dispatching a CodeExecutor call.
 ///
 /// By dispatching we mean that we execute a runtime function specified by it's name.
@@ -91,6 +95,8 @@ pub struct WasmExecutorBuilder<H> {
 	ignore_onchain_heap_pages: bool,
 	max_runtime_instances: usize,
 	cache_path: Option<PathBuf>,
+	artifact_cache: Option<(PathBuf, u64)>,
+	rpc_fuel_limits: HashMap<RpcCallKind, u64>,
 	allow_missing_host_functions: bool,
 	runtime_cache_size: u8,
 }
@@ -110,6 +116,8 @@ impl<H> WasmExecutorBuilder<H> {
 			runtime_cache_size: 4,
 			allow_missing_host_functions: false,
 			cache_path: None,
+			artifact_cache: None,
+			rpc_fuel_limits: HashMap::new(),
 		}
 	}
 
@@ -170,6 +178,38 @@ impl<H> WasmExecutorBuilder<H> {
 		self
 	}
 
+	/// Create the wasm executor with a persistent cache of compiled runtimes.
+	///
+	/// The compiled runtimes are stored in `dir`, keyed by the hash of the runtime code, and are
+	/// loaded from there instead of being compiled again, e.g. after a restart. The least
+	/// recently used runtimes are removed once the cache grows beyond `max_size` bytes.
+	///
+	/// By default there is no persistent cache of compiled runtimes.
+	pub fn with_artifact_cache(mut self, dir: impl Into<PathBuf>, max_size: u64) -> Self {
+		self.artifact_cache = Some((dir.into(), max_size));
+		self
+	}
+
+	/// Create the wasm executor with the given `fuel_limit` for runtime calls serving RPC
+	/// requests of the given `kind`, e.g. `state_call` for [`RpcCallKind::Call`].
+	///
+	/// Only calls executed with the [`RpcCallExt`] extension of the given `kind` registered are
+	/// limited, each call has its own budget of `fuel_limit`. The fuel is consumed
+	/// deterministically by the executed instructions and a call exceeding the limit is aborted
+	/// with [`Error::OutOfFuel`](crate::error::Error::OutOfFuel). These calls are always executed
+	/// in Wasm, by runtimes compiled with fuel metering that are cached separately from the ones
+	/// used for all other calls.
+	///
+	/// Fuel metering isn't supported by the
+	/// [`LegacyInstanceReuse`](crate::WasmtimeInstantiationStrategy::LegacyInstanceReuse)
+	/// instantiation strategy, the RPC calls fail to create their runtime when combining both.
+	///
+	/// By default there is no fuel limit.
+	pub fn with_rpc_fuel_limit(mut self, kind: RpcCallKind, fuel_limit: u64) -> Self {
+		self.rpc_fuel_limits.insert(kind, fuel_limit);
+		self
+	}
+
 	/// Create the wasm executor and allow/forbid missing host functions.
 	///
 	/// If missing host functions are forbidden, the instantiation of a wasm blob will fail
@@ -195,6 +235,20 @@ impl<H> WasmExecutorBuilder<H> {
 
 	/// Build the configured [`WasmExecutor`].
 	pub fn build(self) -> WasmExecutor<H> {
+		let artifact_cache = self.artifact_cache.map(|(dir, max_size)| {
+			Arc::new(sc_executor_wasmtime::ArtifactCache::new(dir, max_size))
+		});
+		let rpc_cache = (!self.rpc_fuel_limits.is_empty()).then(|| {
+			let cache = RuntimeCache::new(
+				self.max_runtime_instances,
+				self.cache_path.clone(),
+				self.runtime_cache_size,
+			)
+			.with_artifact_cache(artifact_cache.clone())
+			.with_fuel_metering(true);
+			(Arc::new(cache), self.rpc_fuel_limits)
+		});
+
 		WasmExecutor {
 			method: self.method,
 			default_offchain_heap_alloc_strategy: unwrap_heap_pages(
@@ -204,11 +258,15 @@ impl<H> WasmExecutorBuilder<H> {
 				self.onchain_heap_alloc_strategy,
 			),
 			ignore_onchain_heap_pages: self.ignore_onchain_heap_pages,
-			cache: Arc::new(RuntimeCache::new(
-				self.max_runtime_instances,
-				self.cache_path.clone(),
-				self.runtime_cache_size,
-			)),
+			cache: Arc::new(
+				RuntimeCache::new(
+					self.max_runtime_instances,
+					self.cache_path.clone(),
+					self.runtime_cache_size,
+				)
+				.with_artifact_cache(artifact_cache),
+			),
+			rpc_cache,
 			cache_path: self.cache_path,
 			allow_missing_host_functions: self.allow_missing_host_functions,
 			phantom: PhantomData,
@@ -229,6 +287,9 @@ pub struct WasmExecutor<H> {
 	ignore_onchain_heap_pages: bool,
 	/// WASM runtime cache.
 	cache: Arc<RuntimeCache>,
+	/// WASM runtime cache with fuel metering and the fuel limits per kind of calls serving RPC
+	/// requests.
+	rpc_cache: Option<(Arc<RuntimeCache>, HashMap<RpcCallKind, u64>)>,
 	/// The path to a directory which the executor can leverage for a file cache, e.g. put there
 	/// compiled artifacts.
 	cache_path: Option<PathBuf>,
@@ -245,6 +306,7 @@ impl<H> Clone for WasmExecutor<H> {
 			default_offchain_heap_alloc_strategy: self.default_offchain_heap_alloc_strategy,
 			ignore_onchain_heap_pages: self.ignore_onchain_heap_pages,
 			cache: self.cache.clone(),
+			rpc_cache: self.rpc_cache.clone(),
 			cache_path: self.cache_path.clone(),
 			allow_missing_host_functions: self.allow_missing_host_functions,
 			phantom: self.phantom,
@@ -296,6 +358,7 @@ where
 				cache_path.clone(),
 				runtime_cache_size,
 			)),
+			rpc_cache: None,
 			cache_path,
 			allow_missing_host_functions: false,
 			phantom: PhantomData,
@@ -341,7 +404,27 @@ where
 			AssertUnwindSafe<&mut dyn Externalities>,
 		) -> Result<Result<R>>,
 	{
-		match self.cache.with_instance::<H, _, _>(
+		self.with_instance_in(&self.cache, runtime_code, ext, heap_alloc_strategy, f)
+	}
+
+	/// Same as [`Self::with_instance`], but uses the runtimes of the given `cache`.
+	fn with_instance_in<R, F>(
+		&self,
+		cache: &RuntimeCache,
+		runtime_code: &RuntimeCode,
+		ext: &mut dyn Externalities,
+		heap_alloc_strategy: HeapAllocStrategy,
+		f: F,
+	) -> Result<R>
+	where
+		F: FnOnce(
+			AssertUnwindSafe<&dyn WasmModule>,
+			AssertUnwindSafe<&mut dyn WasmInstance>,
+			Option<&RuntimeVersion>,
+			AssertUnwindSafe<&mut dyn Externalities>,
+		) -> Result<Result<R>>,
+	{
+		match cache.with_instance::<H, _, _>(
 			runtime_code,
 			ext,
 			self.method,
@@ -359,6 +442,37 @@ where
 		}
 	}
 
+	/// Returns the runtime cache and fuel limit for the call, if it serves an RPC request and a
+	/// fuel limit is set for its kind.
+	fn rpc_cache_for(&self, ext: &mut dyn Externalities) -> Option<(&RuntimeCache, u64)> {
+		let (cache, fuel_limits) = self.rpc_cache.as_ref()?;
+		let mut ext = ext;
+		let kind = ext.extension::<RpcCallExt>()?.0;
+		fuel_limits.get(&kind).map(|fuel_limit| (&**cache, *fuel_limit))
+	}
+
+	/// Call `method` in Wasm with the runtimes of `cache`, consuming at most `fuel_limit`.
+	fn fuel_limited_call(
+		&self,
+		cache: &RuntimeCache,
+		fuel_limit: u64,
+		ext: &mut dyn Externalities,
+		runtime_code: &RuntimeCode,
+		method: &str,
+		data: &[u8],
+	) -> Result<Vec<u8>> {
+		self.with_instance_in(
+			cache,
+			runtime_code,
+			ext,
+			self.default_offchain_heap_alloc_strategy,
+			|_, mut instance, _onchain_version, mut ext| {
+				instance.set_fuel_limit(Some(fuel_limit))?;
+				with_externalities_safe(&mut **ext, move || instance.call_export(method, data))
+			},
+		)
+	}
+
 	/// Perform a call into the given runtime.
 	///
 	/// The runtime is passed as a [`RuntimeBlob`]. The runtime will be instantiated with the
@@ -515,6 +629,13 @@ where
 			CallContext::Onchain => on_chain_heap_alloc_strategy,
 		};
 
+		if let Some((cache, fuel_limit)) = self.rpc_cache_for(ext) {
+			return (
+				self.fuel_limited_call(cache, fuel_limit, ext, runtime_code, method, data),
+				false,
+			)
+		}
+
 		let result = self.with_instance(
 			runtime_code,
 			ext,
@@ -668,6 +789,13 @@ impl<D: NativeExecutionDispatch + 'static> CodeExecutor for NativeElseWasmExecut
 			CallContext::Onchain => on_chain_heap_alloc_strategy,
 		};
 
+		// RPC calls are metered, so they must not be executed natively.
+		if let Some((cache, fuel_limit)) = self.wasm.rpc_cache_for(ext) {
+			let result =
+				self.wasm.fuel_limited_call(cache, fuel_limit, ext, runtime_code, method, data);
+			return (result, false)
+		}
+
 		let mut used_native = false;
 		let result = self.wasm.with_instance(
 			runtime_code,
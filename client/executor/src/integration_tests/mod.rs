@@ -821,3 +821,48 @@ fn return_overflow(wasm_method: WasmExecutionMethod) {
 		error => panic!("unexpected error: {:?}", error),
 	}
 }
+
+#[test]
+fn rpc_fuel_limit_only_applies_to_rpc_calls_of_its_kind() {
+	use crate::{RpcCallExt, RpcCallKind};
+	use sp_core::traits::{CallContext, CodeExecutor, RuntimeCode, WrappedRuntimeCode};
+
+	let executor = crate::WasmExecutor::<HostFunctions>::builder()
+		.with_rpc_fuel_limit(RpcCallKind::Call, 1)
+		.build();
+	let code_fetcher = WrappedRuntimeCode(wasm_binary_unwrap().into());
+	let runtime_code = RuntimeCode { code_fetcher: &code_fetcher, hash: vec![1], heap_pages: None };
+
+	let mut ext = TestExternalities::default();
+	let (result, _) = executor.call(
+		&mut ext.ext(),
+		&runtime_code,
+		"test_empty_return",
+		&[],
+		false,
+		CallContext::Offchain,
+	);
+	assert!(result.is_ok());
+
+	ext.register_extension(RpcCallExt(RpcCallKind::RuntimeApi));
+	let (result, _) = executor.call(
+		&mut ext.ext(),
+		&runtime_code,
+		"test_empty_return",
+		&[],
+		false,
+		CallContext::Offchain,
+	);
+	assert!(result.is_ok());
+
+	ext.register_extension(RpcCallExt(RpcCallKind::Call));
+	let (result, _) = executor.call(
+		&mut ext.ext(),
+		&runtime_code,
+		"test_empty_return",
+		&[],
+		false,
+		CallContext::Offchain,
+	);
+	assert_matches!(result, Err(Error::OutOfFuel(_)));
+}
@@ -39,9 +39,10 @@ mod wasm_runtime;
 
 pub use self::{
 	executor::{
-		with_externalities_safe, NativeElseWasmExecutor, NativeExecutionDispatch, WasmExecutor,
+		with_externalities_safe, NativeElseWasmExecutor, NativeExecutionDispatch, RpcCallExt,
+		RpcCallKind, WasmExecutor,
 	},
-	wasm_runtime::{read_embedded_version, WasmExecutionMethod},
+	wasm_runtime::{precompile_wasm_runtime, read_embedded_version, WasmExecutionMethod},
 };
 pub use codec::Codec;
 #[doc(hidden)]
@@ -54,7 +55,11 @@ pub use sc_executor_common::{
 	error,
 	wasm_runtime::{HeapAllocStrategy, DEFAULT_HEAP_ALLOC_PAGES, DEFAULT_HEAP_ALLOC_STRATEGY},
 };
-pub use sc_executor_wasmtime::InstantiationStrategy as WasmtimeInstantiationStrategy;
+pub use sc_executor_wasmtime::{
+	profile_host_calls, set_host_call_observer, ArtifactCache as WasmtimeArtifactCache,
+	HostCallObserver, HostFunctionStats, InstantiationStrategy as WasmtimeInstantiationStrategy,
+	RuntimeCallProfile,
+};
 
 /// Extracts the runtime version of a given runtime code.
 pub trait RuntimeVersionOf {
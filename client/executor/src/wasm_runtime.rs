@@ -29,6 +29,7 @@ use sc_executor_common::{
 	runtime_blob::RuntimeBlob,
 	wasm_runtime::{HeapAllocStrategy, WasmInstance, WasmModule},
 };
+use sc_executor_wasmtime::ArtifactCache;
 use schnellru::{ByLength, LruMap};
 use sp_core::traits::{Externalities, FetchRuntimeCode, RuntimeCode};
 use sp_version::RuntimeVersion;
@@ -167,6 +168,10 @@ pub struct RuntimeCache {
 	/// The size of the instances cache for each runtime.
 	max_runtime_instances: usize,
 	cache_path: Option<PathBuf>,
+	/// The persistent cache of compiled runtimes, if any.
+	artifact_cache: Option<Arc<ArtifactCache>>,
+	/// Whether the runtimes are compiled with fuel metering.
+	fuel_metering: bool,
 }
 
 impl RuntimeCache {
@@ -186,7 +191,29 @@ impl RuntimeCache {
 		runtime_cache_size: u8,
 	) -> RuntimeCache {
 		let cap = ByLength::new(runtime_cache_size.max(1) as u32);
-		RuntimeCache { runtimes: Mutex::new(LruMap::new(cap)), max_runtime_instances, cache_path }
+		RuntimeCache {
+			runtimes: Mutex::new(LruMap::new(cap)),
+			max_runtime_instances,
+			cache_path,
+			artifact_cache: None,
+			fuel_metering: false,
+		}
+	}
+
+	/// Use the given persistent cache of compiled runtimes.
+	///
+	/// Runtimes are then loaded from `artifact_cache` when possible instead of being compiled,
+	/// and newly compiled runtimes are stored in it.
+	pub fn with_artifact_cache(mut self, artifact_cache: Option<Arc<ArtifactCache>>) -> Self {
+		self.artifact_cache = artifact_cache;
+		self
+	}
+
+	/// Compile the runtimes with fuel metering, which allows limiting the fuel of the calls
+	/// through [`WasmInstance::set_fuel_limit`].
+	pub fn with_fuel_metering(mut self, fuel_metering: bool) -> Self {
+		self.fuel_metering = fuel_metering;
+		self
 	}
 
 	/// Prepares a WASM module instance and executes given function for it.
@@ -249,12 +276,15 @@ impl RuntimeCache {
 
 			let result = create_versioned_wasm_runtime::<H>(
 				&code,
+				code_hash,
 				ext,
 				wasm_method,
 				heap_alloc_strategy,
 				allow_missing_func_imports,
 				self.max_runtime_instances,
 				self.cache_path.as_deref(),
+				self.artifact_cache.as_deref(),
+				self.fuel_metering,
 			);
 
 			match result {
@@ -297,27 +327,61 @@ pub fn create_wasm_runtime_with_code<H>(
 where
 	H: HostFunctions,
 {
+	let config = wasmtime_config(
+		wasm_method,
+		heap_alloc_strategy,
+		allow_missing_func_imports,
+		cache_path,
+		false,
+	);
+
+	sc_executor_wasmtime::create_runtime::<H>(blob, config)
+		.map(|runtime| -> Box<dyn WasmModule> { Box::new(runtime) })
+}
+
+/// Compile the runtime `code` and store the result in the given `artifact_cache`.
+///
+/// The compiled runtime is stored under the hash the node uses for `code` when it is stored
+/// on chain, so it will be picked up by an executor using the same `artifact_cache`,
+/// `wasm_method` and `heap_alloc_strategy` once the runtime is enacted. This allows compiling a
+/// runtime ahead of a runtime upgrade. Returns the path of the compiled runtime.
+pub fn precompile_wasm_runtime(
+	artifact_cache: &ArtifactCache,
+	code: &[u8],
+	wasm_method: WasmExecutionMethod,
+	heap_alloc_strategy: HeapAllocStrategy,
+) -> Result<PathBuf, WasmError> {
+	let code_hash = sp_core::blake2_256(code);
+	let blob = RuntimeBlob::uncompress_if_needed(code)?;
+	let config = wasmtime_config(wasm_method, heap_alloc_strategy, false, None, false);
+
+	artifact_cache.precompile(&code_hash, blob, &config.semantics)
+}
+
+fn wasmtime_config(
+	wasm_method: WasmExecutionMethod,
+	heap_alloc_strategy: HeapAllocStrategy,
+	allow_missing_func_imports: bool,
+	cache_path: Option<&Path>,
+	fuel_metering: bool,
+) -> sc_executor_wasmtime::Config {
 	match wasm_method {
-		WasmExecutionMethod::Compiled { instantiation_strategy } =>
-			sc_executor_wasmtime::create_runtime::<H>(
-				blob,
-				sc_executor_wasmtime::Config {
-					allow_missing_func_imports,
-					cache_path: cache_path.map(ToOwned::to_owned),
-					semantics: sc_executor_wasmtime::Semantics {
-						heap_alloc_strategy,
-						instantiation_strategy,
-						deterministic_stack_limit: None,
-						canonicalize_nans: false,
-						parallel_compilation: true,
-						wasm_multi_value: false,
-						wasm_bulk_memory: false,
-						wasm_reference_types: false,
-						wasm_simd: false,
-					},
-				},
-			)
-			.map(|runtime| -> Box<dyn WasmModule> { Box::new(runtime) }),
+		WasmExecutionMethod::Compiled { instantiation_strategy } => sc_executor_wasmtime::Config {
+			allow_missing_func_imports,
+			cache_path: cache_path.map(ToOwned::to_owned),
+			semantics: sc_executor_wasmtime::Semantics {
+				heap_alloc_strategy,
+				instantiation_strategy,
+				deterministic_stack_limit: None,
+				canonicalize_nans: false,
+				parallel_compilation: true,
+				wasm_multi_value: false,
+				wasm_bulk_memory: false,
+				wasm_reference_types: false,
+				wasm_simd: false,
+				fuel_metering,
+			},
+		},
 	}
 }
 
@@ -379,12 +443,15 @@ pub fn read_embedded_version(blob: &RuntimeBlob) -> Result<Option<RuntimeVersion
 
 fn create_versioned_wasm_runtime<H>(
 	code: &[u8],
+	code_hash: &[u8],
 	ext: &mut dyn Externalities,
 	wasm_method: WasmExecutionMethod,
 	heap_alloc_strategy: HeapAllocStrategy,
 	allow_missing_func_imports: bool,
 	max_instances: usize,
 	cache_path: Option<&Path>,
+	artifact_cache: Option<&ArtifactCache>,
+	fuel_metering: bool,
 ) -> Result<VersionedRuntime, WasmError>
 where
 	H: HostFunctions,
@@ -398,13 +465,18 @@ where
 	// runtime.
 	let mut version = read_embedded_version(&blob)?;
 
-	let runtime = create_wasm_runtime_with_code::<H>(
+	let config = wasmtime_config(
 		wasm_method,
 		heap_alloc_strategy,
-		blob,
 		allow_missing_func_imports,
 		cache_path,
-	)?;
+		fuel_metering,
+	);
+	let runtime: Box<dyn WasmModule> = match artifact_cache {
+		Some(artifact_cache) =>
+			Box::new(artifact_cache.create_runtime::<H>(code_hash, blob, config)?),
+		None => Box::new(sc_executor_wasmtime::create_runtime::<H>(blob, config)?),
+	};
 
 	// If the runtime blob doesn't embed the runtime version then use the legacy version query
 	// mechanism: call the runtime.
@@ -0,0 +1,339 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! A persistent on-disk cache of precompiled runtime artifacts.
+//!
+//! Compiling a runtime takes a noticeable amount of time, which is paid on every node restart and
+//! on every runtime upgrade. The [`ArtifactCache`] keeps the artifacts produced by
+//! [`prepare_runtime_artifact`] in a directory, keyed by the hash of the runtime code and by a
+//! fingerprint of everything else the compiled code depends on: the version of wasmtime and the
+//! settings of the engine compiling it, including the target, and the rest of the [`Semantics`]
+//! used for compilation. The fingerprint uses a stable hash function, so it doesn't change
+//! between builds of the node.
+//!
+//! Every artifact is accompanied by a checksum file which is verified before the artifact is
+//! handed to wasmtime, so a truncated or otherwise corrupted artifact is never loaded. The
+//! checksum file is rewritten whenever the artifact is used and its modification time is used to
+//! evict the least recently used artifacts once the cache grows beyond its size limit.
+//!
+//! Artifacts are never modified in place. New artifacts are written to a temporary file which is
+//! then atomically renamed, and evicted artifacts are unlinked, so any runtime which has already
+//! mapped an artifact keeps working.
+
+use crate::{
+	create_runtime, create_runtime_from_artifact, create_runtime_from_artifact_bytes,
+	prepare_runtime_artifact, runtime::hash_engine_settings, Config, InstantiationStrategy,
+	Semantics, WasmtimeRuntime,
+};
+
+use sc_executor_common::{error::WasmError, runtime_blob::RuntimeBlob};
+use sp_core_hashing::blake2_256;
+use sp_wasm_interface::HostFunctions;
+use std::{
+	fs,
+	hash::{Hash, Hasher},
+	io,
+	path::{Path, PathBuf},
+	sync::Mutex,
+	time::SystemTime,
+};
+use twox_hash::XxHash64;
+
+const LOG_TARGET: &str = "wasmtime-artifact-cache";
+
+/// Extension of the files holding the compiled artifacts.
+const ARTIFACT_EXTENSION: &str = "artifact";
+
+/// Extension of the files holding the checksums of the compiled artifacts.
+const CHECKSUM_EXTENSION: &str = "checksum";
+
+/// A directory of precompiled runtime artifacts with a maximum total size.
+///
+/// The directory is created lazily, when the first artifact is stored.
+pub struct ArtifactCache {
+	dir: PathBuf,
+	max_size: u64,
+	/// Serializes the writes and the eviction done by this process.
+	lock: Mutex<()>,
+}
+
+impl ArtifactCache {
+	/// Create a new cache in `dir` which holds at most `max_size` bytes of artifacts.
+	///
+	/// The artifact which was stored last is always kept, even if it alone exceeds `max_size`.
+	pub fn new(dir: impl Into<PathBuf>, max_size: u64) -> Self {
+		Self { dir: dir.into(), max_size, lock: Mutex::new(()) }
+	}
+
+	/// The directory the artifacts are stored in.
+	pub fn dir(&self) -> &Path {
+		&self.dir
+	}
+
+	/// The path of the artifact compiled from the code with the given hash using `semantics`.
+	///
+	/// The file at the returned path doesn't necessarily exist.
+	pub fn artifact_path(
+		&self,
+		code_hash: &[u8],
+		semantics: &Semantics,
+	) -> Result<PathBuf, WasmError> {
+		let name = format!("{}-{:016x}", hex(code_hash), fingerprint(semantics)?);
+
+		Ok(self.dir.join(name).with_extension(ARTIFACT_EXTENSION))
+	}
+
+	/// Create a runtime from the code with the given hash.
+	///
+	/// If a valid artifact for the code is found in the cache it is used, otherwise the `blob` is
+	/// compiled and the resulting artifact is stored in the cache. Failing to access the cache is
+	/// not an error, the runtime is created from the `blob` in that case.
+	///
+	/// The legacy instance reuse strategy doesn't support precompiled artifacts, so the cache is
+	/// bypassed when it is used.
+	pub fn create_runtime<H>(
+		&self,
+		code_hash: &[u8],
+		blob: RuntimeBlob,
+		config: Config,
+	) -> Result<WasmtimeRuntime, WasmError>
+	where
+		H: HostFunctions,
+	{
+		if config.semantics.instantiation_strategy == InstantiationStrategy::LegacyInstanceReuse {
+			return create_runtime::<H>(blob, config)
+		}
+
+		let path = self.artifact_path(code_hash, &config.semantics)?;
+		if self.validate(&path) {
+			// SAFETY: The artifact was produced by `prepare_runtime_artifact`, its checksum was
+			//         just verified and the cache never modifies artifacts in place.
+			match unsafe { create_runtime_from_artifact::<H>(&path, config.clone()) } {
+				Ok(runtime) => {
+					log::debug!(target: LOG_TARGET, "Loaded runtime artifact {}", path.display());
+					return Ok(runtime)
+				},
+				Err(error) => {
+					log::warn!(
+						target: LOG_TARGET,
+						"Failed to load runtime artifact {}, recompiling: {}",
+						path.display(),
+						error,
+					);
+					self.remove(&path);
+				},
+			}
+		}
+
+		let artifact = prepare_runtime_artifact(blob, &config.semantics)?;
+		match self.store(&path, &artifact) {
+			// SAFETY: The artifact was just produced by `prepare_runtime_artifact` and the cache
+			//         never modifies artifacts in place.
+			Ok(()) => unsafe { create_runtime_from_artifact::<H>(&path, config) },
+			Err(error) => {
+				log::warn!(
+					target: LOG_TARGET,
+					"Failed to store runtime artifact {}: {}",
+					path.display(),
+					error,
+				);
+				// SAFETY: The artifact was just produced by `prepare_runtime_artifact`.
+				unsafe { create_runtime_from_artifact_bytes::<H>(&artifact, config) }
+			},
+		}
+	}
+
+	/// Compile the `blob` with the given `semantics` and store the artifact in the cache, unless a
+	/// valid artifact for the code is already present.
+	///
+	/// This allows paying the compilation cost of a runtime ahead of time, e.g. before a runtime
+	/// upgrade is enacted. Returns the path of the artifact.
+	pub fn precompile(
+		&self,
+		code_hash: &[u8],
+		blob: RuntimeBlob,
+		semantics: &Semantics,
+	) -> Result<PathBuf, WasmError> {
+		let path = self.artifact_path(code_hash, semantics)?;
+		if !self.validate(&path) {
+			let artifact = prepare_runtime_artifact(blob, semantics)?;
+			self.store(&path, &artifact).map_err(|error| {
+				WasmError::Other(format!(
+					"cannot store runtime artifact {}: {}",
+					path.display(),
+					error
+				))
+			})?;
+		}
+
+		Ok(path)
+	}
+
+	/// Check that the artifact at `path` exists and matches its checksum.
+	///
+	/// A valid artifact is marked as recently used, an invalid one is removed.
+	fn validate(&self, path: &Path) -> bool {
+		let checksum_path = path.with_extension(CHECKSUM_EXTENSION);
+		let expected = match fs::read_to_string(&checksum_path) {
+			Ok(checksum) => checksum,
+			Err(error) if error.kind() == io::ErrorKind::NotFound => return false,
+			Err(error) => {
+				log::warn!(
+					target: LOG_TARGET,
+					"Failed to read {}: {}",
+					checksum_path.display(),
+					error,
+				);
+				return false
+			},
+		};
+
+		let valid = fs::read(path).map_or(false, |artifact| checksum(&artifact) == expected);
+		if valid {
+			// Bump the modification time of the checksum file, which is what the eviction uses.
+			// The file is replaced atomically so that a concurrent reader never sees it truncated,
+			// the lock guards the temporary file shared by the threads of this process.
+			let _lock = self.lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+			let _ = write_atomically(&checksum_path, expected.as_bytes());
+		} else {
+			log::warn!(target: LOG_TARGET, "Runtime artifact {} is corrupted", path.display());
+			self.remove(path);
+		}
+
+		valid
+	}
+
+	/// Atomically write the `artifact` and its checksum to `path` and evict old artifacts.
+	fn store(&self, path: &Path, artifact: &[u8]) -> io::Result<()> {
+		let _lock = self.lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+		fs::create_dir_all(&self.dir)?;
+
+		// The checksum is written first, a checksum without a matching artifact is harmless.
+		let checksum_path = path.with_extension(CHECKSUM_EXTENSION);
+		write_atomically(&checksum_path, checksum(artifact).as_bytes())?;
+		write_atomically(path, artifact)?;
+
+		self.evict(path);
+
+		Ok(())
+	}
+
+	/// Remove the least recently used artifacts until the cache fits into its size limit.
+	///
+	/// The artifact at `keep` is never removed.
+	fn evict(&self, keep: &Path) {
+		let entries = match fs::read_dir(&self.dir) {
+			Ok(entries) => entries,
+			Err(error) => {
+				log::warn!(
+					target: LOG_TARGET,
+					"Failed to list {}: {}",
+					self.dir.display(),
+					error,
+				);
+				return
+			},
+		};
+
+		let mut total_size = 0;
+		let mut artifacts = Vec::new();
+		for entry in entries.flatten() {
+			let path = entry.path();
+			if path.extension().map_or(true, |extension| extension != ARTIFACT_EXTENSION) {
+				continue
+			}
+
+			let Ok(metadata) = entry.metadata() else { continue };
+			let last_used = fs::metadata(path.with_extension(CHECKSUM_EXTENSION))
+				.and_then(|metadata| metadata.modified())
+				.unwrap_or(SystemTime::UNIX_EPOCH);
+
+			total_size += metadata.len();
+			if path != keep {
+				artifacts.push((last_used, metadata.len(), path));
+			}
+		}
+
+		artifacts.sort_unstable();
+		for (_, size, path) in artifacts {
+			if total_size <= self.max_size {
+				break
+			}
+
+			log::debug!(target: LOG_TARGET, "Evicting runtime artifact {}", path.display());
+			self.remove(&path);
+			total_size = total_size.saturating_sub(size);
+		}
+	}
+
+	/// Remove the artifact at `path` together with its checksum.
+	fn remove(&self, path: &Path) {
+		for path in [path.with_extension(CHECKSUM_EXTENSION), path.to_path_buf()] {
+			if let Err(error) = fs::remove_file(&path) {
+				if error.kind() != io::ErrorKind::NotFound {
+					log::warn!(
+						target: LOG_TARGET,
+						"Failed to remove {}: {}",
+						path.display(),
+						error,
+					);
+				}
+			}
+		}
+	}
+}
+
+/// A fingerprint of everything besides the runtime code which affects the compiled artifact.
+fn fingerprint(semantics: &Semantics) -> Result<u64, WasmError> {
+	let mut hasher = XxHash64::with_seed(0);
+	hash_engine_settings(semantics, &mut hasher)?;
+	semantics.instantiation_strategy.hash(&mut hasher);
+	semantics
+		.deterministic_stack_limit
+		.as_ref()
+		.map(|limit| (limit.logical_max, limit.native_stack_max))
+		.hash(&mut hasher);
+	semantics.canonicalize_nans.hash(&mut hasher);
+	semantics.heap_alloc_strategy.hash(&mut hasher);
+	semantics.wasm_multi_value.hash(&mut hasher);
+	semantics.wasm_bulk_memory.hash(&mut hasher);
+	semantics.wasm_reference_types.hash(&mut hasher);
+	semantics.wasm_simd.hash(&mut hasher);
+	semantics.fuel_metering.hash(&mut hasher);
+	Ok(hasher.finish())
+}
+
+/// The checksum of an artifact as stored in its checksum file.
+fn checksum(artifact: &[u8]) -> String {
+	format!("{} {}", hex(&blake2_256(artifact)), artifact.len())
+}
+
+fn hex(bytes: &[u8]) -> String {
+	bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Write `data` to a temporary file next to `path` and rename it to `path`.
+fn write_atomically(path: &Path, data: &[u8]) -> io::Result<()> {
+	let tmp_path = path.with_extension(format!("tmp-{}", std::process::id()));
+	fs::write(&tmp_path, data)?;
+	fs::rename(&tmp_path, path).map_err(|error| {
+		let _ = fs::remove_file(&tmp_path);
+		error
+	})
+}
@@ -19,12 +19,15 @@
 //! This module defines `HostState` and `HostContext` structs which provide logic and state
 //! required for execution of host.
 
+use std::{cell::Cell, collections::BTreeMap, time::Duration};
 use wasmtime::Caller;
 
 use sc_allocator::{AllocationStats, FreeingBumpHeapAllocator};
 use sp_wasm_interface::{Pointer, WordSize};
 
-use crate::{instance_wrapper::MemoryWrapper, runtime::StoreData, util};
+use crate::{
+	instance_wrapper::MemoryWrapper, profiling::HostFunctionStats, runtime::StoreData, util,
+};
 
 /// The state required to construct a HostContext context. The context only lasts for one host
 /// call, whereas the state is maintained for the duration of a Wasm runtime call, which may make
@@ -37,12 +40,29 @@ pub struct HostState {
 	/// once.
 	allocator: Option<FreeingBumpHeapAllocator>,
 	panic_message: Option<String>,
+	/// The statistics of the host functions called so far, if profiling is enabled.
+	host_calls: Option<BTreeMap<&'static str, HostFunctionStats>>,
 }
 
 impl HostState {
 	/// Constructs a new `HostState`.
 	pub fn new(allocator: FreeingBumpHeapAllocator) -> Self {
-		HostState { allocator: Some(allocator), panic_message: None }
+		HostState { allocator: Some(allocator), panic_message: None, host_calls: None }
+	}
+
+	/// Start recording the host functions called by the runtime.
+	pub(crate) fn enable_profiling(&mut self) {
+		self.host_calls = Some(BTreeMap::new());
+	}
+
+	/// Whether the host functions called by the runtime are recorded.
+	pub(crate) fn is_profiling(&self) -> bool {
+		self.host_calls.is_some()
+	}
+
+	/// Takes the statistics of the called host functions out of the host state.
+	pub(crate) fn take_host_calls(&mut self) -> Option<BTreeMap<&'static str, HostFunctionStats>> {
+		self.host_calls.take()
 	}
 
 	/// Takes the error message out of the host state, leaving a `None` in its place.
@@ -61,10 +81,31 @@ impl HostState {
 /// runtime. The `HostContext` exists only for the lifetime of the call and borrows state from
 /// a longer-living `HostState`.
 pub(crate) struct HostContext<'a> {
-	pub(crate) caller: Caller<'a, StoreData>,
+	caller: Caller<'a, StoreData>,
+	/// The number of bytes read from the memory of the runtime during the call.
+	bytes_read: Cell<u64>,
+	/// The number of bytes written to the memory of the runtime during the call.
+	bytes_written: u64,
 }
 
 impl<'a> HostContext<'a> {
+	pub(crate) fn new(caller: Caller<'a, StoreData>) -> Self {
+		HostContext { caller, bytes_read: Cell::new(0), bytes_written: 0 }
+	}
+
+	/// Record a call to the host function `fn_name` which took `time`, if profiling is enabled.
+	pub(crate) fn record_host_call(&mut self, fn_name: &'static str, time: Duration) {
+		let (bytes_read, bytes_written) = (self.bytes_read.get(), self.bytes_written);
+		if let Some(host_calls) = self.host_state_mut().host_calls.as_mut() {
+			host_calls.entry(fn_name).or_default().merge(&HostFunctionStats {
+				calls: 1,
+				bytes_read,
+				bytes_written,
+				time,
+			});
+		}
+	}
+
 	fn host_state_mut(&mut self) -> &mut HostState {
 		self.caller
 			.data_mut()
@@ -79,10 +120,12 @@ impl<'a> sp_wasm_interface::FunctionContext for HostContext<'a> {
 		address: Pointer<u8>,
 		dest: &mut [u8],
 	) -> sp_wasm_interface::Result<()> {
+		self.bytes_read.set(self.bytes_read.get() + dest.len() as u64);
 		util::read_memory_into(&self.caller, address, dest).map_err(|e| e.to_string())
 	}
 
 	fn write_memory(&mut self, address: Pointer<u8>, data: &[u8]) -> sp_wasm_interface::Result<()> {
+		self.bytes_written += data.len() as u64;
 		util::write_memory_from(&mut self.caller, address, data).map_err(|e| e.to_string())
 	}
 
@@ -16,10 +16,13 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{host::HostContext, runtime::StoreData};
+use crate::{
+	host::{HostContext, HostState},
+	runtime::StoreData,
+};
 use sc_executor_common::error::WasmError;
 use sp_wasm_interface::{FunctionContext, HostFunctions};
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Instant};
 use wasmtime::{ExternType, FuncType, ImportType, Linker, Module};
 
 /// Goes over all imports of a module and prepares the given linker for instantiation of the module.
@@ -101,7 +104,25 @@ impl<'a, 'b> sp_wasm_interface::HostFunctionRegistry for Registry<'a, 'b> {
 		caller: wasmtime::Caller<Self::State>,
 		callback: impl FnOnce(&mut dyn FunctionContext) -> R,
 	) -> R {
-		callback(&mut HostContext { caller })
+		callback(&mut HostContext::new(caller))
+	}
+
+	fn with_named_function_context<R>(
+		caller: wasmtime::Caller<Self::State>,
+		fn_name: &'static str,
+		callback: impl FnOnce(&mut dyn FunctionContext) -> R,
+	) -> R {
+		let profiling = caller.data().host_state.as_ref().map_or(false, HostState::is_profiling);
+		let mut context = HostContext::new(caller);
+		if !profiling {
+			return callback(&mut context)
+		}
+
+		let started = Instant::now();
+		let result = callback(&mut context);
+		context.record_host_call(fn_name, started.elapsed());
+
+		result
 	}
 
 	fn register_static<Params, Results>(
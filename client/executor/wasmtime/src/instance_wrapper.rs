@@ -83,6 +83,8 @@ impl EntryPoint {
 
 			if let Some(message) = host_state.take_panic_message() {
 				Error::AbortedDueToPanic(MessageWithBacktrace { message, backtrace })
+			} else if trap.downcast_ref::<wasmtime::Trap>() == Some(&wasmtime::Trap::OutOfFuel) {
+				Error::OutOfFuel(store.fuel_consumed().unwrap_or_default())
 			} else {
 				let message = trap.root_cause().to_string();
 				Error::AbortedDueToTrap(MessageWithBacktrace { message, backtrace })
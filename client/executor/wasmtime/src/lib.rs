@@ -27,15 +27,22 @@
 //! | `"jitdump"` | jitdump profiling       |
 //! | other value | No profiling (warning)  |
 
+mod artifact_cache;
 mod host;
 mod imports;
 mod instance_wrapper;
+mod profiling;
 mod runtime;
 mod util;
 
 #[cfg(test)]
 mod tests;
 
+pub use artifact_cache::ArtifactCache;
+pub use profiling::{
+	profile_host_calls, set_host_call_observer, HostCallObserver, HostFunctionStats,
+	RuntimeCallProfile,
+};
 pub use runtime::{
 	create_runtime, create_runtime_from_artifact, create_runtime_from_artifact_bytes,
 	prepare_runtime_artifact, Config, DeterministicStackLimit, InstantiationStrategy, Semantics,
@@ -0,0 +1,144 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Opt-in profiling of the host functions called by the runtime.
+//!
+//! While profiling is enabled, every host function call made by the runtime is recorded together
+//! with the time it took and the number of bytes it read from and wrote to the memory of the
+//! runtime. The records are aggregated per host function into a [`RuntimeCallProfile`] for every
+//! runtime call.
+//!
+//! Profiling is enabled either process-wide, by setting an observer with
+//! [`set_host_call_observer`], or for the runtime calls made on the current thread within the
+//! closure passed to [`profile_host_calls`]. Otherwise it costs nothing but a check at the start
+//! of every runtime call.
+
+use std::{
+	cell::RefCell,
+	collections::BTreeMap,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		RwLock,
+	},
+	time::Duration,
+};
+
+/// The statistics of the calls to a single host function.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HostFunctionStats {
+	/// The number of calls.
+	pub calls: u64,
+	/// The number of bytes read from the memory of the runtime.
+	pub bytes_read: u64,
+	/// The number of bytes written to the memory of the runtime.
+	pub bytes_written: u64,
+	/// The wall time spent in the host function.
+	pub time: Duration,
+}
+
+impl HostFunctionStats {
+	/// Add the statistics in `other` to `self`.
+	pub fn merge(&mut self, other: &Self) {
+		self.calls += other.calls;
+		self.bytes_read += other.bytes_read;
+		self.bytes_written += other.bytes_written;
+		self.time += other.time;
+	}
+}
+
+/// The host functions called by a single runtime call.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuntimeCallProfile {
+	/// The runtime method which was called, e.g. `Core_execute_block`.
+	pub method: String,
+	/// The statistics of every host function called, by name.
+	pub host_functions: BTreeMap<&'static str, HostFunctionStats>,
+}
+
+/// A process-wide observer of the profiled runtime calls.
+pub type HostCallObserver = Box<dyn Fn(&RuntimeCallProfile) + Send + Sync>;
+
+static OBSERVER: RwLock<Option<HostCallObserver>> = RwLock::new(None);
+
+/// Whether [`OBSERVER`] is set, to avoid taking the lock when profiling is disabled.
+static HAS_OBSERVER: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+	/// The profiles collected by [`profile_host_calls`] on this thread.
+	static CAPTURED: RefCell<Option<Vec<RuntimeCallProfile>>> = RefCell::new(None);
+}
+
+/// Enable profiling of all runtime calls and pass the profile of every call to `observer`.
+///
+/// The observer is called on the thread which executed the runtime call, right after it has
+/// finished. Passing `None` disables the process-wide profiling again.
+pub fn set_host_call_observer(observer: Option<HostCallObserver>) {
+	let mut current = OBSERVER.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+	HAS_OBSERVER.store(observer.is_some(), Ordering::Release);
+	*current = observer;
+}
+
+/// Call `f` and return the profiles of the runtime calls it made on the current thread.
+///
+/// The runtime calls made by `f` are also passed to the observer set with
+/// [`set_host_call_observer`], if any, and collected by enclosing calls of this function.
+pub fn profile_host_calls<R>(f: impl FnOnce() -> R) -> (R, Vec<RuntimeCallProfile>) {
+	/// Restores the collection of an enclosing call, even if `f` panics.
+	struct Guard(Option<Vec<RuntimeCallProfile>>);
+
+	impl Drop for Guard {
+		fn drop(&mut self) {
+			CAPTURED.with(|captured| *captured.borrow_mut() = self.0.take());
+		}
+	}
+
+	let guard = Guard(CAPTURED.with(|captured| captured.borrow_mut().replace(Vec::new())));
+	let result = f();
+	let profiles = CAPTURED.with(|captured| captured.borrow_mut().take()).unwrap_or_default();
+	drop(guard);
+
+	CAPTURED.with(|captured| {
+		if let Some(outer) = captured.borrow_mut().as_mut() {
+			outer.extend(profiles.iter().cloned());
+		}
+	});
+
+	(result, profiles)
+}
+
+/// Whether the runtime calls made now on the current thread should be profiled.
+pub(crate) fn is_enabled() -> bool {
+	HAS_OBSERVER.load(Ordering::Acquire) || CAPTURED.with(|captured| captured.borrow().is_some())
+}
+
+/// Hand the profile of a finished runtime call to the observer and the enclosing
+/// [`profile_host_calls`].
+pub(crate) fn report(profile: RuntimeCallProfile) {
+	if HAS_OBSERVER.load(Ordering::Acquire) {
+		let observer = OBSERVER.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+		if let Some(observer) = observer.as_ref() {
+			observer(&profile);
+		}
+	}
+
+	CAPTURED.with(|captured| {
+		if let Some(captured) = captured.borrow_mut().as_mut() {
+			captured.push(profile);
+		}
+	});
+}
@@ -21,6 +21,7 @@
 use crate::{
 	host::HostState,
 	instance_wrapper::{EntryPoint, InstanceWrapper, MemoryWrapper},
+	profiling::{self, RuntimeCallProfile},
 	util::{self, replace_strategy_if_broken},
 };
 
@@ -125,6 +126,7 @@ pub struct WasmtimeRuntime {
 	engine: wasmtime::Engine,
 	instance_pre: Arc<wasmtime::InstancePre<StoreData>>,
 	instantiation_strategy: InternalInstantiationStrategy,
+	fuel_metering: bool,
 }
 
 impl WasmModule for WasmtimeRuntime {
@@ -156,7 +158,11 @@ impl WasmModule for WasmtimeRuntime {
 			}),
 		};
 
-		Ok(Box::new(WasmtimeInstance { strategy }))
+		Ok(Box::new(WasmtimeInstance {
+			strategy,
+			fuel_metering: self.fuel_metering,
+			fuel_limit: None,
+		}))
 	}
 }
 
@@ -164,6 +170,8 @@ impl WasmModule for WasmtimeRuntime {
 /// to execute the compiled code.
 pub struct WasmtimeInstance {
 	strategy: Strategy,
+	fuel_metering: bool,
+	fuel_limit: Option<u64>,
 }
 
 impl WasmtimeInstance {
@@ -173,6 +181,10 @@ impl WasmtimeInstance {
 		data: &[u8],
 		allocation_stats: &mut Option<AllocationStats>,
 	) -> Result<Vec<u8>> {
+		let profiled_method = profiling::is_enabled().then(|| invoke_method_name(&method));
+		// Every call gets a fresh store, so the fuel only has to be added once per call.
+		let fuel = self.fuel_metering.then(|| self.fuel_limit.unwrap_or(u64::MAX));
+
 		match &mut self.strategy {
 			Strategy::LegacyInstanceReuse {
 				ref mut instance_wrapper,
@@ -192,8 +204,14 @@ impl WasmtimeInstance {
 				globals_snapshot.apply(&mut InstanceGlobals { instance: instance_wrapper });
 				let allocator = FreeingBumpHeapAllocator::new(*heap_base);
 
-				let result =
-					perform_call(data, instance_wrapper, entrypoint, allocator, allocation_stats);
+				let result = perform_call(
+					data,
+					instance_wrapper,
+					entrypoint,
+					allocator,
+					allocation_stats,
+					profiled_method,
+				);
 
 				// Signal to the OS that we are done with the linear memory and that it can be
 				// reclaimed.
@@ -203,11 +221,24 @@ impl WasmtimeInstance {
 			},
 			Strategy::RecreateInstance(ref mut instance_creator) => {
 				let mut instance_wrapper = instance_creator.instantiate()?;
+				if let Some(fuel) = fuel {
+					instance_wrapper
+						.store_mut()
+						.add_fuel(fuel)
+						.map_err(|e| Error::Other(format!("failed to add fuel: {:#}", e)))?;
+				}
 				let heap_base = instance_wrapper.extract_heap_base()?;
 				let entrypoint = instance_wrapper.resolve_entrypoint(method)?;
 
 				let allocator = FreeingBumpHeapAllocator::new(heap_base);
-				perform_call(data, &mut instance_wrapper, entrypoint, allocator, allocation_stats)
+				perform_call(
+					data,
+					&mut instance_wrapper,
+					entrypoint,
+					allocator,
+					allocation_stats,
+					profiled_method,
+				)
 			},
 		}
 	}
@@ -224,6 +255,15 @@ impl WasmInstance for WasmtimeInstance {
 		(result, allocation_stats)
 	}
 
+	fn set_fuel_limit(&mut self, fuel_limit: Option<u64>) -> Result<()> {
+		if fuel_limit.is_some() && !self.fuel_metering {
+			return Err(Error::Other("fuel metering is not enabled for this runtime".into()))
+		}
+
+		self.fuel_limit = fuel_limit;
+		Ok(())
+	}
+
 	fn get_global_const(&mut self, name: &str) -> Result<Option<Value>> {
 		match &mut self.strategy {
 			Strategy::LegacyInstanceReuse { instance_wrapper, .. } =>
@@ -323,6 +363,8 @@ fn common_config(semantics: &Semantics) -> std::result::Result<wasmtime::Config,
 
 	config.parallel_compilation(semantics.parallel_compilation);
 
+	config.consume_fuel(semantics.fuel_metering);
+
 	// Be clear and specific about the extensions we support. If an update brings new features
 	// they should be introduced here as well.
 	config.wasm_reference_types(semantics.wasm_reference_types);
@@ -516,6 +558,15 @@ pub struct Semantics {
 
 	/// Enables WASM Fixed-Width SIMD proposal
 	pub wasm_simd: bool,
+
+	/// Enables deterministic fuel metering of the executed code.
+	///
+	/// When enabled, every call consumes fuel for each executed instruction and traps with
+	/// [`Error::OutOfFuel`] once the limit set through [`WasmInstance::set_fuel_limit`] is
+	/// exhausted. Calls without a limit are not restricted, but still pay for the metering.
+	///
+	/// Not supported by the [`InstantiationStrategy::LegacyInstanceReuse`] strategy.
+	pub fuel_metering: bool,
 }
 
 #[derive(Clone)]
@@ -633,6 +684,15 @@ where
 {
 	replace_strategy_if_broken(&mut config.semantics.instantiation_strategy);
 
+	if config.semantics.fuel_metering &&
+		config.semantics.instantiation_strategy == InstantiationStrategy::LegacyInstanceReuse
+	{
+		return Err(WasmError::Other(
+			"the legacy instance reuse instantiation strategy is incompatible with fuel metering"
+				.into(),
+		))
+	}
+
 	let mut wasmtime_config = common_config(&config.semantics)?;
 	if let Some(ref cache_path) = config.cache_path {
 		if let Err(reason) = setup_wasmtime_caching(cache_path, &mut wasmtime_config) {
@@ -719,7 +779,12 @@ where
 		.instantiate_pre(&module)
 		.map_err(|e| WasmError::Other(format!("cannot preinstantiate module: {:#}", e)))?;
 
-	Ok(WasmtimeRuntime { engine, instance_pre: Arc::new(instance_pre), instantiation_strategy })
+	Ok(WasmtimeRuntime {
+		engine,
+		instance_pre: Arc::new(instance_pre),
+		instantiation_strategy,
+		fuel_metering: config.semantics.fuel_metering,
+	})
 }
 
 fn prepare_blob_for_compilation(
@@ -766,16 +831,37 @@ pub fn prepare_runtime_artifact(
 		.map_err(|e| WasmError::Other(format!("cannot precompile module: {:#}", e)))
 }
 
+/// Feed the settings of the engine which compiles runtimes using `semantics` to the `hasher`.
+///
+/// The settings include the version of wasmtime and everything else it checks before loading an
+/// artifact produced by [`prepare_runtime_artifact`].
+pub(crate) fn hash_engine_settings(
+	semantics: &Semantics,
+	hasher: &mut impl std::hash::Hasher,
+) -> std::result::Result<(), WasmError> {
+	let mut semantics = semantics.clone();
+	replace_strategy_if_broken(&mut semantics.instantiation_strategy);
+
+	let engine = Engine::new(&common_config(&semantics)?)
+		.map_err(|e| WasmError::Other(format!("cannot create the engine: {:#}", e)))?;
+	std::hash::Hash::hash(&engine.precompile_compatibility_hash(), hasher);
+	Ok(())
+}
+
 fn perform_call(
 	data: &[u8],
 	instance_wrapper: &mut InstanceWrapper,
 	entrypoint: EntryPoint,
 	mut allocator: FreeingBumpHeapAllocator,
 	allocation_stats: &mut Option<AllocationStats>,
+	profiled_method: Option<String>,
 ) -> Result<Vec<u8>> {
 	let (data_ptr, data_len) = inject_input_data(instance_wrapper, &mut allocator, data)?;
 
-	let host_state = HostState::new(allocator);
+	let mut host_state = HostState::new(allocator);
+	if profiled_method.is_some() {
+		host_state.enable_profiling();
+	}
 
 	// Set the host state before calling into wasm.
 	instance_wrapper.store_mut().data_mut().host_state = Some(host_state);
@@ -785,17 +871,31 @@ fn perform_call(
 		.map(unpack_ptr_and_len);
 
 	// Reset the host state
-	let host_state = instance_wrapper.store_mut().data_mut().host_state.take().expect(
+	let mut host_state = instance_wrapper.store_mut().data_mut().host_state.take().expect(
 		"the host state is always set before calling into WASM so it can't be None here; qed",
 	);
 	*allocation_stats = Some(host_state.allocation_stats());
 
+	if let (Some(method), Some(host_functions)) = (profiled_method, host_state.take_host_calls()) {
+		profiling::report(RuntimeCallProfile { method, host_functions });
+	}
+
 	let (output_ptr, output_len) = ret?;
 	let output = extract_output_data(instance_wrapper, output_ptr, output_len)?;
 
 	Ok(output)
 }
 
+/// The name of the called runtime method as it appears in the profiles of the runtime calls.
+fn invoke_method_name(method: &InvokeMethod) -> String {
+	match method {
+		InvokeMethod::Export(name) => (*name).to_owned(),
+		InvokeMethod::Table(func) => format!("table[{}]", func),
+		InvokeMethod::TableWithWrapper { dispatcher_ref, func } =>
+			format!("table[{}]({})", dispatcher_ref, func),
+	}
+}
+
 fn inject_input_data(
 	instance: &mut InstanceWrapper,
 	allocator: &mut FreeingBumpHeapAllocator,
@@ -83,6 +83,7 @@ struct RuntimeBuilder {
 	deterministic_stack: bool,
 	heap_pages: HeapAllocStrategy,
 	precompile_runtime: bool,
+	fuel_metering: bool,
 	tmpdir: Option<tempfile::TempDir>,
 }
 
@@ -95,6 +96,7 @@ impl RuntimeBuilder {
 			deterministic_stack: false,
 			heap_pages: DEFAULT_HEAP_ALLOC_STRATEGY,
 			precompile_runtime: false,
+			fuel_metering: false,
 			tmpdir: None,
 		}
 	}
@@ -124,6 +126,11 @@ impl RuntimeBuilder {
 		self
 	}
 
+	fn fuel_metering(mut self, fuel_metering: bool) -> Self {
+		self.fuel_metering = fuel_metering;
+		self
+	}
+
 	fn build(&mut self) -> impl WasmModule + '_ {
 		let blob = {
 			let wasm: Vec<u8>;
@@ -159,6 +166,7 @@ impl RuntimeBuilder {
 				wasm_bulk_memory: false,
 				wasm_reference_types: false,
 				wasm_simd: false,
+				fuel_metering: self.fuel_metering,
 			},
 		};
 
@@ -316,6 +324,53 @@ fn test_stack_depth_reaching(instantiation_strategy: InstantiationStrategy) {
 	}
 }
 
+test_wasm_execution!(@no_legacy_instance_reuse test_fuel_metering);
+fn test_fuel_metering(instantiation_strategy: InstantiationStrategy) {
+	const WAT: &str = r#"
+		(module
+		  (memory $0 32)
+		  (export "memory" (memory $0))
+		  (global (export "__heap_base") i32 (i32.const 0))
+
+		  (func (export "main")
+		    (param i32 i32) (result i64)
+		    (i64.const 0)
+		  )
+
+		  (func (export "infinite_loop")
+		    (param i32 i32) (result i64)
+		    (loop $l (br $l))
+		    (i64.const 0)
+		  )
+		)
+	"#;
+
+	let mut builder = RuntimeBuilder::new(instantiation_strategy)
+		.use_wat(WAT.to_string())
+		.fuel_metering(true);
+	let runtime = builder.build();
+	let mut instance = runtime.new_instance().expect("failed to instantiate a runtime");
+
+	instance.set_fuel_limit(Some(10_000)).unwrap();
+	for _ in 0..2 {
+		// The fuel is replenished for every call.
+		match instance.call_export("infinite_loop", &[]).unwrap_err() {
+			Error::OutOfFuel(consumed) => assert!(consumed >= 10_000),
+			error => panic!("unexpected error: {:?}", error),
+		}
+		instance.call_export("main", &[]).unwrap();
+	}
+
+	instance.set_fuel_limit(None).unwrap();
+	instance.call_export("main", &[]).unwrap();
+
+	let mut builder = RuntimeBuilder::new(instantiation_strategy).use_wat(WAT.to_string());
+	let runtime = builder.build();
+	let mut instance = runtime.new_instance().expect("failed to instantiate a runtime");
+	assert!(instance.set_fuel_limit(Some(10_000)).is_err());
+	instance.set_fuel_limit(None).unwrap();
+}
+
 test_wasm_execution!(test_max_memory_pages_imported_memory_without_precompilation);
 fn test_max_memory_pages_imported_memory_without_precompilation(
 	instantiation_strategy: InstantiationStrategy,
@@ -482,6 +537,7 @@ fn test_instances_without_reuse_are_not_leaked() {
 				wasm_bulk_memory: false,
 				wasm_reference_types: false,
 				wasm_simd: false,
+				fuel_metering: false,
 			},
 		},
 	)
@@ -529,3 +585,106 @@ fn test_rustix_version_matches_with_wasmtime() {
 		);
 	}
 }
+
+#[test]
+fn test_artifact_cache() {
+	const CODE: &str = r#"
+		(module
+			(memory $0 1 1)
+			(export "memory" (memory $0))
+			(global (export "__heap_base") i32 (i32.const 0))
+			(func (export "main") (param i32 i32) (result i64)
+				(i64.const 0)
+			)
+		)
+	"#;
+
+	let blob = || RuntimeBlob::uncompress_if_needed(&wat::parse_str(CODE).unwrap()).unwrap();
+	let config = crate::Config {
+		allow_missing_func_imports: true,
+		cache_path: None,
+		semantics: crate::Semantics {
+			instantiation_strategy: InstantiationStrategy::RecreateInstance,
+			deterministic_stack_limit: None,
+			canonicalize_nans: false,
+			parallel_compilation: true,
+			heap_alloc_strategy: DEFAULT_HEAP_ALLOC_STRATEGY,
+			wasm_multi_value: false,
+			wasm_bulk_memory: false,
+			wasm_reference_types: false,
+			wasm_simd: false,
+			fuel_metering: false,
+		},
+	};
+	let call = |runtime: crate::WasmtimeRuntime| {
+		runtime.new_instance().unwrap().call_export("main", &[]).unwrap();
+	};
+
+	let dir = tempfile::tempdir().unwrap();
+	let cache = crate::ArtifactCache::new(dir.path(), u64::MAX);
+
+	// The first runtime is compiled and stored in the cache.
+	call(cache.create_runtime::<HostFunctions>(b"code", blob(), config.clone()).unwrap());
+	let path = cache.artifact_path(b"code", &config.semantics).unwrap();
+	let artifact = std::fs::read(&path).unwrap();
+
+	// The second one is loaded from the cache.
+	call(cache.create_runtime::<HostFunctions>(b"code", blob(), config.clone()).unwrap());
+	assert_eq!(cache.precompile(b"code", blob(), &config.semantics).unwrap(), path);
+
+	// A different configuration results in a different artifact.
+	let mut other_semantics = config.semantics.clone();
+	other_semantics.canonicalize_nans = true;
+	assert_ne!(cache.artifact_path(b"code", &other_semantics).unwrap(), path);
+
+	// A corrupted artifact is detected and replaced.
+	std::fs::write(&path, &artifact[..artifact.len() / 2]).unwrap();
+	call(cache.create_runtime::<HostFunctions>(b"code", blob(), config.clone()).unwrap());
+	assert_eq!(std::fs::read(&path).unwrap(), artifact);
+
+	// Storing an artifact evicts the older ones once the cache is full.
+	let cache = crate::ArtifactCache::new(dir.path(), artifact.len() as u64);
+	let other_path = cache.precompile(b"other", blob(), &config.semantics).unwrap();
+	assert!(other_path.exists());
+	assert!(!path.exists());
+}
+
+#[test]
+fn test_host_call_profiling() {
+	let mut builder = RuntimeBuilder::new(InstantiationStrategy::RecreateInstance).use_wat(
+		r#"
+		(module
+			(import "env" "ext_hashing_twox_64_version_1"
+				(func $twox_64 (param i64) (result i32)))
+			(memory $0 1)
+			(export "memory" (memory $0))
+			(global (export "__heap_base") i32 (i32.const 1024))
+			(data (i32.const 0) "hello")
+			(func (export "main") (param i32 i32) (result i64)
+				;; Hash the 5 bytes at offset 0 twice.
+				(drop (call $twox_64 (i64.const 0x500000000)))
+				(drop (call $twox_64 (i64.const 0x500000000)))
+				(i64.const 0)
+			)
+		)
+		"#
+		.to_string(),
+	);
+	let runtime = builder.build();
+	let mut instance = runtime.new_instance().unwrap();
+
+	// Runtime calls outside of `profile_host_calls` are not recorded.
+	instance.call_export("main", &[]).unwrap();
+
+	let ((), profiles) = crate::profile_host_calls(|| {
+		instance.call_export("main", &[]).unwrap();
+	});
+
+	assert_eq!(profiles.len(), 1);
+	assert_eq!(profiles[0].method, "main");
+
+	let stats = profiles[0].host_functions["ext_hashing_twox_64_version_1"];
+	assert_eq!(stats.calls, 2);
+	assert_eq!(stats.bytes_read, 10);
+	assert_eq!(stats.bytes_written, 16);
+}
@@ -34,6 +34,7 @@ use sp_api::{ApiExt, NumberFor, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_core::{
 	offchain::{storage::OffchainDb, OffchainDbExt, OffchainStorage},
+	traits::{RpcCallExt, RpcCallKind},
 	Bytes,
 };
 use sp_mmr_primitives::{Error as MmrError, Proof};
@@ -157,7 +158,8 @@ where
 		let block_hash = at.unwrap_or_else(||
 			// If the block hash is not supplied assume the best block.
 			self.client.info().best_hash);
-		let api = self.client.runtime_api();
+		let mut api = self.client.runtime_api();
+		api.register_extension(RpcCallExt(RpcCallKind::RuntimeApi));
 		let mmr_root = api
 			.mmr_root(block_hash)
 			.map_err(runtime_error_into_rpc_error)?
@@ -177,6 +179,7 @@ where
 			self.client.info().best_hash);
 
 		api.register_extension(OffchainDbExt::new(self.offchain_db.clone()));
+		api.register_extension(RpcCallExt(RpcCallKind::RuntimeApi));
 
 		let (leaves, proof) = api
 			.generate_proof(block_hash, block_numbers, best_known_block_number)
@@ -196,6 +199,7 @@ where
 			.map_err(|e| CallError::InvalidParams(anyhow::Error::new(e)))?;
 
 		api.register_extension(OffchainDbExt::new(self.offchain_db.clone()));
+		api.register_extension(RpcCallExt(RpcCallKind::RuntimeApi));
 
 		api.verify_proof(proof.block_hash, leaves, decoded_proof)
 			.map_err(runtime_error_into_rpc_error)?
@@ -209,7 +213,8 @@ where
 		mmr_root: MmrHash,
 		proof: LeavesProof<<Block as BlockT>::Hash>,
 	) -> RpcResult<bool> {
-		let api = self.client.runtime_api();
+		let mut api = self.client.runtime_api();
+		api.register_extension(RpcCallExt(RpcCallKind::RuntimeApi));
 
 		let leaves = Decode::decode(&mut &proof.leaves.0[..])
 			.map_err(|e| CallError::InvalidParams(anyhow::Error::new(e)))?;
@@ -225,6 +225,7 @@ where
 				ImportedState {
 					block: self.target_block,
 					state: std::mem::take(&mut self.state).into(),
+					written: false,
 				},
 				self.target_body.clone(),
 				self.target_justifications.clone(),
@@ -289,4 +289,13 @@ pub trait StateApi<Hash> {
 		storage_keys: Option<String>,
 		methods: Option<String>,
 	) -> RpcResult<sp_rpc::tracing::TraceBlockResponse>;
+
+	/// Re-execute the given block and report the host functions called by every runtime call
+	/// made, with the number of calls, the bytes read from and written to the runtime memory
+	/// and the wall time spent per host function.
+	///
+	/// Only runtime calls executed by the wasm executor are profiled, so the node should run
+	/// with `--execution=wasm` for the report to be complete.
+	#[method(name = "state_profileBlockHostCalls", blocking)]
+	fn profile_block_host_calls(&self, block: Hash) -> RpcResult<sp_rpc::tracing::BlockHostCalls>;
 }
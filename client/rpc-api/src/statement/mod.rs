@@ -19,10 +19,23 @@
 //! Substrate Statement Store RPC API.
 
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
 use sp_core::Bytes;
 
 pub mod error;
 
+/// Filter over the topics of the statements reported by `statement_subscribe`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TopicFilter {
+	/// Report statements that include all of the given topics.
+	///
+	/// An empty list reports all statements.
+	MatchAll(Vec<[u8; 32]>),
+	/// Report statements that include at least one of the given topics.
+	MatchAny(Vec<[u8; 32]>),
+}
+
 /// Substrate statement RPC API
 #[rpc(client, server)]
 pub trait StatementApi {
@@ -57,4 +70,19 @@ pub trait StatementApi {
 	/// Remove a statement from the store.
 	#[method(name = "statement_remove")]
 	fn remove(&self, statement_hash: [u8; 32]) -> RpcResult<()>;
+
+	/// Subscribe to new statements accepted by the store, SCALE-encoded.
+	///
+	/// Only statements that match the `topic_filter` and whose decryption key is identified
+	/// as `dest` are reported. Statements without a `DecryptionKey` field are reported if
+	/// `dest` is not provided.
+	///
+	/// This method is unsafe. Subscriptions that don't keep up with the statements sent to them
+	/// are closed.
+	#[subscription(
+		name = "statement_subscribe" => "statement_statement",
+		unsubscribe = "statement_unsubscribe",
+		item = Bytes,
+	)]
+	fn subscribe_statement(&self, topic_filter: TopicFilter, dest: Option<[u8; 32]>);
 }
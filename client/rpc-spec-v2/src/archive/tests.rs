@@ -0,0 +1,604 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	archive::{
+		event::{ArchiveStorageMethodOk, ArchiveStorageResult, PaginatedStorageQuery},
+		MethodResult,
+	},
+	chain_head::{
+		event::{StorageQueryType, StorageResult, StorageResultType},
+		hex_string,
+	},
+};
+
+use super::{archive::Archive, *};
+
+use assert_matches::assert_matches;
+use codec::{Decode, Encode};
+use jsonrpsee::{
+	core::error::Error,
+	rpc_params,
+	types::{error::CallError, EmptyServerParams as EmptyParams},
+	RpcModule,
+};
+use sc_block_builder::BlockBuilderProvider;
+use sc_client_api::ChildInfo;
+use sp_blockchain::HeaderBackend;
+use sp_consensus::BlockOrigin;
+use sp_core::{Blake2Hasher, Hasher};
+use sp_runtime::{
+	traits::{Block as BlockT, Header as HeaderT},
+	SaturatedConversion,
+};
+use std::sync::Arc;
+use substrate_test_runtime::Transfer;
+use substrate_test_runtime_client::{
+	prelude::*, runtime, Backend, BlockBuilderExt, Client, ClientBlockImportExt,
+};
+
+const CHAIN_GENESIS: [u8; 32] = [0; 32];
+const INVALID_HASH: [u8; 32] = [1; 32];
+const MAX_DESCENDANT_RESPONSES: usize = 3;
+const MAX_QUERIED_ITEMS: usize = 4;
+const KEY: &[u8] = b":mock";
+const CHILD_STORAGE_KEY: &[u8] = b"child";
+const CHILD_VALUE: &[u8] = b"child value";
+
+type Header = substrate_test_runtime_client::runtime::Header;
+type Block = substrate_test_runtime_client::runtime::Block;
+
+fn setup_api(
+	max_descendant_responses: usize,
+	max_queried_items: usize,
+) -> (Arc<Client<Backend>>, RpcModule<Archive<Backend, Block, Client<Backend>>>) {
+	let child_info = ChildInfo::new_default(CHILD_STORAGE_KEY);
+	let builder = TestClientBuilder::new().add_extra_child_storage(
+		&child_info,
+		KEY.to_vec(),
+		CHILD_VALUE.to_vec(),
+	);
+	let backend = builder.backend();
+	let client = Arc::new(builder.build());
+
+	let api = Archive::new(
+		client.clone(),
+		backend,
+		CHAIN_GENESIS,
+		max_descendant_responses,
+		max_queried_items,
+	)
+	.into_rpc();
+
+	(client, api)
+}
+
+#[tokio::test]
+async fn archive_genesis() {
+	let (_client, api) = setup_api(MAX_DESCENDANT_RESPONSES, MAX_QUERIED_ITEMS);
+
+	let genesis: String =
+		api.call("archive_unstable_genesisHash", EmptyParams::new()).await.unwrap();
+	assert_eq!(genesis, hex_string(&CHAIN_GENESIS));
+}
+
+#[tokio::test]
+async fn archive_body() {
+	let (mut client, api) = setup_api(MAX_DESCENDANT_RESPONSES, MAX_QUERIED_ITEMS);
+
+	// Invalid block hash.
+	let invalid_hash = hex_string(&INVALID_HASH);
+	let extrinsics: Option<Vec<String>> =
+		api.call("archive_unstable_body", [invalid_hash]).await.unwrap();
+	assert!(extrinsics.is_none());
+
+	// Import a new block with an extrinsic.
+	let mut builder = client.new_block(Default::default()).unwrap();
+	builder
+		.push_transfer(runtime::Transfer {
+			from: AccountKeyring::Alice.into(),
+			to: AccountKeyring::Ferdie.into(),
+			amount: 42,
+			nonce: 0,
+		})
+		.unwrap();
+	let block = builder.build().unwrap().block;
+	let block_hash = format!("{:?}", block.header.hash());
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	let expected_tx = hex_string(&block.extrinsics[0].encode());
+
+	let body: Vec<String> = api.call("archive_unstable_body", [block_hash]).await.unwrap();
+	assert_eq!(vec![expected_tx], body);
+}
+
+#[tokio::test]
+async fn archive_header() {
+	let (mut client, api) = setup_api(MAX_DESCENDANT_RESPONSES, MAX_QUERIED_ITEMS);
+
+	// Invalid block hash.
+	let invalid_hash = hex_string(&INVALID_HASH);
+	let header: Option<String> = api.call("archive_unstable_header", [invalid_hash]).await.unwrap();
+	assert!(header.is_none());
+
+	let block = client.new_block(Default::default()).unwrap().build().unwrap().block;
+	let block_hash = format!("{:?}", block.header.hash());
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	let header: String = api.call("archive_unstable_header", [block_hash]).await.unwrap();
+	let bytes = array_bytes::hex2bytes(&header).unwrap();
+	let header: Header = Decode::decode(&mut &bytes[..]).unwrap();
+	assert_eq!(header, block.header);
+}
+
+#[tokio::test]
+async fn archive_finalized_height() {
+	let (client, api) = setup_api(MAX_DESCENDANT_RESPONSES, MAX_QUERIED_ITEMS);
+
+	let client_height: u32 = client.info().finalized_number.saturated_into();
+
+	let height: u32 =
+		api.call("archive_unstable_finalizedHeight", EmptyParams::new()).await.unwrap();
+
+	assert_eq!(client_height, height);
+}
+
+#[tokio::test]
+async fn archive_hash_by_height() {
+	let (mut client, api) = setup_api(MAX_DESCENDANT_RESPONSES, MAX_QUERIED_ITEMS);
+
+	// Genesis height.
+	let hashes: Vec<String> = api.call("archive_unstable_hashByHeight", [0]).await.unwrap();
+	assert_eq!(hashes, vec![format!("{:?}", client.genesis_hash())]);
+
+	// Block tree:
+	// finalized  -> block 1 -> block 2 -> block 3
+	//                       -> block 2' -> block 3'
+	let finalized_hash = client.info().finalized_hash;
+	let block_1 = client.new_block(Default::default()).unwrap().build().unwrap().block;
+	let block_1_hash = block_1.header.hash();
+	client.import(BlockOrigin::Own, block_1.clone()).await.unwrap();
+
+	let block_2 = client
+		.new_block_at(block_1_hash, Default::default(), false)
+		.unwrap()
+		.build()
+		.unwrap()
+		.block;
+	let block_2_hash = block_2.header.hash();
+	client.import(BlockOrigin::Own, block_2.clone()).await.unwrap();
+	let block_3 = client
+		.new_block_at(block_2_hash, Default::default(), false)
+		.unwrap()
+		.build()
+		.unwrap()
+		.block;
+	let block_3_hash = block_3.header.hash();
+	client.import(BlockOrigin::Own, block_3.clone()).await.unwrap();
+
+	// Import block 2' on a fork of block 1.
+	let mut block_builder = client.new_block_at(block_1_hash, Default::default(), false).unwrap();
+	// This push is required as otherwise block 2' has the same hash as block 2.
+	block_builder
+		.push_transfer(Transfer {
+			from: AccountKeyring::Alice.into(),
+			to: AccountKeyring::Ferdie.into(),
+			amount: 41,
+			nonce: 0,
+		})
+		.unwrap();
+	let block_2_f = block_builder.build().unwrap().block;
+	let block_2_f_hash = block_2_f.header.hash();
+	client.import(BlockOrigin::Own, block_2_f.clone()).await.unwrap();
+
+	let block_3_f = client
+		.new_block_at(block_2_f_hash, Default::default(), false)
+		.unwrap()
+		.build()
+		.unwrap()
+		.block;
+	let block_3_f_hash = block_3_f.header.hash();
+	client.import(BlockOrigin::Own, block_3_f.clone()).await.unwrap();
+
+	// Check finalized height.
+	let hashes: Vec<String> = api.call("archive_unstable_hashByHeight", [0]).await.unwrap();
+	assert_eq!(hashes, vec![format!("{:?}", finalized_hash)]);
+
+	// Block 1 is on the canonical chain, but it is not finalized.
+	let hashes: Vec<String> = api.call("archive_unstable_hashByHeight", [1]).await.unwrap();
+	assert_eq!(hashes, vec![format!("{:?}", block_1_hash)]);
+
+	// Block 2 and block 2' are on different forks.
+	let mut hashes: Vec<String> = api.call("archive_unstable_hashByHeight", [2]).await.unwrap();
+	let mut expected = vec![format!("{:?}", block_2_hash), format!("{:?}", block_2_f_hash)];
+	hashes.sort();
+	expected.sort();
+	assert_eq!(hashes, expected);
+
+	// Block 3 and block 3' are the leaves of the chain.
+	let mut hashes: Vec<String> = api.call("archive_unstable_hashByHeight", [3]).await.unwrap();
+	let mut expected = vec![format!("{:?}", block_3_hash), format!("{:?}", block_3_f_hash)];
+	hashes.sort();
+	expected.sort();
+	assert_eq!(hashes, expected);
+
+	// Height past the leaves.
+	let hashes: Vec<String> = api.call("archive_unstable_hashByHeight", [4]).await.unwrap();
+	assert!(hashes.is_empty());
+}
+
+#[tokio::test]
+async fn archive_call() {
+	let (mut client, api) = setup_api(MAX_DESCENDANT_RESPONSES, MAX_QUERIED_ITEMS);
+	let invalid_hash = hex_string(&INVALID_HASH);
+
+	// Invalid parameter (non-hex).
+	let result: Result<MethodResult, _> = api
+		.call("archive_unstable_call", [&invalid_hash, "BabeApi_current_epoch", "0x00X"])
+		.await;
+	assert_matches!(result, Err(Error::Call(CallError::Custom(ref err))) if err.code() == 3001);
+
+	// Pass an invalid parameters that cannot be decode.
+	let result: Result<MethodResult, _> = api
+		.call(
+			"archive_unstable_call",
+			// 0x0 is invalid.
+			[&invalid_hash, "BabeApi_current_epoch", "0x0"],
+		)
+		.await;
+	assert_matches!(result, Err(Error::Call(CallError::Custom(ref err))) if err.code() == 3001);
+
+	// Invalid hash.
+	let result: MethodResult = api
+		.call("archive_unstable_call", [&invalid_hash, "BabeApi_current_epoch", "0x00"])
+		.await
+		.unwrap();
+	assert_matches!(result, MethodResult::Err(_));
+
+	let block_1 = client.new_block(Default::default()).unwrap().build().unwrap().block;
+	let block_1_hash = block_1.header.hash();
+	client.import(BlockOrigin::Own, block_1.clone()).await.unwrap();
+
+	// Valid call.
+	let alice_id = AccountKeyring::Alice.to_account_id();
+	// Hex encoded scale encoded bytes representing the call parameters.
+	let call_parameters = hex_string(&alice_id.encode());
+	let result: MethodResult = api
+		.call(
+			"archive_unstable_call",
+			[&format!("{:?}", block_1_hash), "AccountNonceApi_account_nonce", &call_parameters],
+		)
+		.await
+		.unwrap();
+	let expected = MethodResult::ok("0x0000000000000000");
+	assert_eq!(result, expected);
+}
+
+#[tokio::test]
+async fn archive_storage_hashes_values() {
+	let (mut client, api) = setup_api(MAX_DESCENDANT_RESPONSES, MAX_QUERIED_ITEMS);
+	let genesis_hash = format!("{:?}", client.genesis_hash());
+
+	let key = hex_string(&b":A");
+	let items: Vec<PaginatedStorageQuery<String>> = vec![
+		PaginatedStorageQuery {
+			key: key.clone(),
+			query_type: StorageQueryType::DescendantsHashes,
+			pagination_start_key: None,
+		},
+		PaginatedStorageQuery {
+			key: key.clone(),
+			query_type: StorageQueryType::DescendantsValues,
+			pagination_start_key: None,
+		},
+		PaginatedStorageQuery {
+			key: key.clone(),
+			query_type: StorageQueryType::Hash,
+			pagination_start_key: None,
+		},
+		PaginatedStorageQuery {
+			key: key.clone(),
+			query_type: StorageQueryType::Value,
+			pagination_start_key: None,
+		},
+	];
+
+	let result: ArchiveStorageResult = api
+		.call("archive_unstable_storage", rpc_params![&genesis_hash, items.clone()])
+		.await
+		.unwrap();
+
+	match result {
+		ArchiveStorageResult::Ok(ArchiveStorageMethodOk { result, discarded_items }) => {
+			// Key has not been imported yet.
+			assert_eq!(result.len(), 0);
+			assert_eq!(discarded_items, 0);
+		},
+		_ => panic!("Unexpected result"),
+	};
+
+	// Import a block with the given key value pair.
+	let mut builder = client.new_block(Default::default()).unwrap();
+	builder.push_storage_change(b":A".to_vec(), Some(b"B".to_vec())).unwrap();
+	builder.push_storage_change(b":AA".to_vec(), Some(b"BB".to_vec())).unwrap();
+	let block = builder.build().unwrap().block;
+	let block_hash = format!("{:?}", block.header.hash());
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	let expected_hash = format!("{:?}", Blake2Hasher::hash(&b"B"));
+	let expected_value = hex_string(&b"B");
+	let expected_hash_aa = format!("{:?}", Blake2Hasher::hash(&b"BB"));
+	let expected_value_aa = hex_string(&b"BB");
+	let key_aa = hex_string(&b":AA");
+
+	let result: ArchiveStorageResult = api
+		.call("archive_unstable_storage", rpc_params![&block_hash, items])
+		.await
+		.unwrap();
+
+	match result {
+		ArchiveStorageResult::Ok(ArchiveStorageMethodOk { result, discarded_items }) => {
+			assert_eq!(result.len(), 6);
+			assert_eq!(discarded_items, 0);
+
+			assert_eq!(result[0].key, key);
+			assert_eq!(result[0].result, StorageResultType::Hash(expected_hash.clone()));
+			assert_eq!(result[1].key, key_aa);
+			assert_eq!(result[1].result, StorageResultType::Hash(expected_hash_aa));
+
+			assert_eq!(result[2].key, key);
+			assert_eq!(result[2].result, StorageResultType::Value(expected_value.clone()));
+			assert_eq!(result[3].key, key_aa);
+			assert_eq!(result[3].result, StorageResultType::Value(expected_value_aa));
+
+			assert_eq!(result[4].key, key);
+			assert_eq!(result[4].result, StorageResultType::Hash(expected_hash));
+			assert_eq!(result[5].key, key);
+			assert_eq!(result[5].result, StorageResultType::Value(expected_value));
+		},
+		_ => panic!("Unexpected result"),
+	};
+}
+
+#[tokio::test]
+async fn archive_storage_closest_merkle_value() {
+	let (mut client, api) = setup_api(MAX_DESCENDANT_RESPONSES, MAX_QUERIED_ITEMS);
+
+	let mut builder = client.new_block(Default::default()).unwrap();
+	builder.push_storage_change(b":AAAA".to_vec(), Some(vec![1; 64])).unwrap();
+	builder.push_storage_change(b":AAAB".to_vec(), Some(vec![2; 64])).unwrap();
+	let block = builder.build().unwrap().block;
+	let block_hash = format!("{:?}", block.header.hash());
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	let query = |key: &[u8]| PaginatedStorageQuery {
+		key: hex_string(&key),
+		query_type: StorageQueryType::ClosestDescendantMerkleValue,
+		pagination_start_key: None,
+	};
+
+	let result: ArchiveStorageResult = api
+		.call(
+			"archive_unstable_storage",
+			rpc_params![&block_hash, vec![query(b":AAAA"), query(b":AAA"), query(b":ZZZ")]],
+		)
+		.await
+		.unwrap();
+
+	let ArchiveStorageResult::Ok(ArchiveStorageMethodOk { result, discarded_items }) = result
+	else {
+		panic!("Unexpected result")
+	};
+	assert_eq!(discarded_items, 0);
+	// The key `:ZZZ` has no descendants.
+	assert_eq!(result.len(), 2);
+
+	let merkle_value = |item: &StorageResult<String>| match &item.result {
+		StorageResultType::ClosestDescendantMerkleValue(value) => value.clone(),
+		_ => panic!("Unexpected result type"),
+	};
+	// The branch node of `:AAA` is the parent of the `:AAAA` leaf and must differ.
+	assert_eq!(result[0].key, hex_string(&b":AAAA"));
+	assert_eq!(result[1].key, hex_string(&b":AAA"));
+	assert_ne!(merkle_value(&result[0]), merkle_value(&result[1]));
+
+	// Changing the value of `:AAAB` changes the merkle value of the closest descendant
+	// of `:AAA`, but not the one of `:AAAA`.
+	let mut builder = client.new_block(Default::default()).unwrap();
+	builder.push_storage_change(b":AAAB".to_vec(), Some(vec![3; 64])).unwrap();
+	let block = builder.build().unwrap().block;
+	let new_block_hash = format!("{:?}", block.header.hash());
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	let new_result: ArchiveStorageResult = api
+		.call(
+			"archive_unstable_storage",
+			rpc_params![&new_block_hash, vec![query(b":AAAA"), query(b":AAA")]],
+		)
+		.await
+		.unwrap();
+	let ArchiveStorageResult::Ok(ArchiveStorageMethodOk { result: new_result, .. }) = new_result
+	else {
+		panic!("Unexpected result")
+	};
+	assert_eq!(merkle_value(&result[0]), merkle_value(&new_result[0]));
+	assert_ne!(merkle_value(&result[1]), merkle_value(&new_result[1]));
+}
+
+#[tokio::test]
+async fn archive_storage_paginate_iterations() {
+	// 1 iteration allowed before pagination kicks in.
+	let (mut client, api) = setup_api(1, MAX_QUERIED_ITEMS);
+
+	// Import a new block with storage changes.
+	let mut builder = client.new_block(Default::default()).unwrap();
+	builder.push_storage_change(b":m".to_vec(), Some(b"a".to_vec())).unwrap();
+	builder.push_storage_change(b":mo".to_vec(), Some(b"ab".to_vec())).unwrap();
+	builder.push_storage_change(b":moc".to_vec(), Some(b"abc".to_vec())).unwrap();
+	let block = builder.build().unwrap().block;
+	let block_hash = format!("{:?}", block.header.hash());
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	let mut pagination_start_key = None;
+	let mut values = Vec::new();
+	for _ in 0..4 {
+		let result: ArchiveStorageResult = api
+			.call(
+				"archive_unstable_storage",
+				rpc_params![
+					&block_hash,
+					vec![PaginatedStorageQuery {
+						key: hex_string(&b":m"),
+						query_type: StorageQueryType::DescendantsValues,
+						pagination_start_key: pagination_start_key.clone(),
+					}]
+				],
+			)
+			.await
+			.unwrap();
+
+		let ArchiveStorageResult::Ok(ArchiveStorageMethodOk { mut result, .. }) = result else {
+			panic!("Unexpected result")
+		};
+		let Some(item) = result.pop() else { break };
+		assert!(result.is_empty());
+
+		pagination_start_key = Some(item.key.clone());
+		values.push(item);
+	}
+
+	assert_eq!(
+		values,
+		vec![
+			StorageResult {
+				key: hex_string(&b":m"),
+				result: StorageResultType::Value(hex_string(&b"a"))
+			},
+			StorageResult {
+				key: hex_string(&b":mo"),
+				result: StorageResultType::Value(hex_string(&b"ab"))
+			},
+			StorageResult {
+				key: hex_string(&b":moc"),
+				result: StorageResultType::Value(hex_string(&b"abc"))
+			},
+		]
+	);
+}
+
+#[tokio::test]
+async fn archive_storage_discarded_items() {
+	// One query at a time
+	let (mut client, api) = setup_api(MAX_DESCENDANT_RESPONSES, 1);
+
+	// Import a new block with storage changes.
+	let mut builder = client.new_block(Default::default()).unwrap();
+	builder.push_storage_change(b":m".to_vec(), Some(b"a".to_vec())).unwrap();
+	let block = builder.build().unwrap().block;
+	let block_hash = format!("{:?}", block.header.hash());
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	// Valid call with storage at the key.
+	let result: ArchiveStorageResult = api
+		.call(
+			"archive_unstable_storage",
+			rpc_params![
+				&block_hash,
+				vec![
+					PaginatedStorageQuery {
+						key: hex_string(&b":m"),
+						query_type: StorageQueryType::Value,
+						pagination_start_key: None,
+					},
+					PaginatedStorageQuery {
+						key: hex_string(&b":m"),
+						query_type: StorageQueryType::Hash,
+						pagination_start_key: None,
+					},
+					PaginatedStorageQuery {
+						key: hex_string(&b":m"),
+						query_type: StorageQueryType::Hash,
+						pagination_start_key: None,
+					}
+				]
+			],
+		)
+		.await
+		.unwrap();
+	match result {
+		ArchiveStorageResult::Ok(ArchiveStorageMethodOk { result, discarded_items }) => {
+			assert_eq!(result.len(), 1);
+			assert_eq!(discarded_items, 2);
+
+			assert_eq!(result[0].key, hex_string(&b":m"));
+			assert_eq!(result[0].result, StorageResultType::Value(hex_string(&b"a")));
+		},
+		_ => panic!("Unexpected result"),
+	};
+}
+
+#[tokio::test]
+async fn archive_storage_child_trie() {
+	let (client, api) = setup_api(MAX_DESCENDANT_RESPONSES, MAX_QUERIED_ITEMS);
+	let genesis_hash = format!("{:?}", client.genesis_hash());
+
+	// Child value set in `setup_api`.
+	let result: ArchiveStorageResult = api
+		.call(
+			"archive_unstable_storage",
+			rpc_params![
+				&genesis_hash,
+				vec![PaginatedStorageQuery {
+					key: hex_string(&KEY),
+					query_type: StorageQueryType::Value,
+					pagination_start_key: None,
+				}],
+				hex_string(&CHILD_STORAGE_KEY)
+			],
+		)
+		.await
+		.unwrap();
+	match result {
+		ArchiveStorageResult::Ok(ArchiveStorageMethodOk { result, discarded_items }) => {
+			assert_eq!(result.len(), 1);
+			assert_eq!(discarded_items, 0);
+			assert_eq!(result[0].key, hex_string(&KEY));
+			assert_eq!(result[0].result, StorageResultType::Value(hex_string(&CHILD_VALUE)));
+		},
+		_ => panic!("Unexpected result"),
+	};
+
+	// Pagination start key is not allowed for plain value queries.
+	let err = api
+		.call::<_, serde_json::Value>(
+			"archive_unstable_storage",
+			rpc_params![
+				&genesis_hash,
+				vec![PaginatedStorageQuery {
+					key: hex_string(&KEY),
+					query_type: StorageQueryType::Value,
+					pagination_start_key: Some(hex_string(&KEY)),
+				}]
+			],
+		)
+		.await
+		.unwrap_err();
+	assert_matches!(err,
+		Error::Call(CallError::Custom(ref err)) if err.code() == 3001 && err.message().contains("Invalid parameter")
+	);
+}
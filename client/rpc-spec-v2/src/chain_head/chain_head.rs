@@ -43,13 +43,13 @@ use sc_client_api::{
 };
 use sp_api::CallApiAt;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
-use sp_core::{traits::CallContext, Bytes};
+use sp_core::Bytes;
 use sp_runtime::traits::Block as BlockT;
 use std::{marker::PhantomData, sync::Arc, time::Duration};
 
 use super::{
-	chain_head_storage::ChainHeadStorage,
-	event::{ChainHeadStorageEvent, StorageQuery, StorageQueryType},
+	chain_head_storage::{ChainHeadStorage, StorageOperation},
+	event::{ChainHeadStorageEvent, StorageQuery},
 };
 
 pub(crate) const LOG_TARGET: &str = "rpc-spec-v2";
@@ -300,20 +300,12 @@ where
 		let items = items
 			.into_iter()
 			.map(|query| {
-				if query.query_type == StorageQueryType::ClosestDescendantMerkleValue {
-					// Note: remove this once all types are implemented.
-					let _ = sink.reject(ChainHeadRpcError::InvalidParam(
-						"Storage query type not supported".into(),
-					));
-					return Err(SubscriptionEmptyError)
-				}
-
 				Ok(StorageQuery {
 					key: StorageKey(parse_hex_param(&mut sink, query.key)?),
 					query_type: query.query_type,
 				})
 			})
-			.collect::<Result<Vec<_>, _>>()?;
+			.collect::<Result<Vec<_>, SubscriptionEmptyError>>()?;
 
 		let child_trie = child_trie
 			.map(|child_trie| parse_hex_param(&mut sink, child_trie))
@@ -343,12 +335,17 @@ where
 			},
 		};
 
+		// The ID of the storage subscription identifies the operation
+		// for the `chainHead_continue` method.
+		let operation_id = self.accept_subscription(&mut sink)?;
+		let operation = StorageOperation { subscriptions, follow_subscription, operation_id };
+
 		let storage_client = ChainHeadStorage::<Client, Block, BE>::new(client);
 
 		let fut = async move {
 			let _block_guard = block_guard;
 
-			storage_client.generate_events(sink, hash, items, child_trie);
+			storage_client.generate_events(sink, hash, items, child_trie, operation).await;
 		};
 
 		self.executor.spawn("substrate-rpc-subscription", Some("rpc"), fut.boxed());
@@ -400,7 +397,7 @@ where
 
 			let res = client
 				.executor()
-				.call(hash, &function, &call_parameters, CallContext::Offchain)
+				.rpc_call(hash, &function, &call_parameters)
 				.map(|result| {
 					let result = hex_string(&result);
 					ChainHeadEvent::Done(ChainHeadResult { result })
@@ -434,4 +431,19 @@ where
 			Err(_) => Err(ChainHeadRpcError::InvalidBlock.into()),
 		}
 	}
+
+	fn chain_head_unstable_continue(
+		&self,
+		follow_subscription: String,
+		operation_id: String,
+	) -> RpcResult<()> {
+		match self.subscriptions.continue_operation(&follow_subscription, &operation_id) {
+			Ok(()) => Ok(()),
+			Err(SubscriptionManagementError::SubscriptionAbsent) => {
+				// Invalid invalid subscription ID.
+				Ok(())
+			},
+			Err(_) => Err(ChainHeadRpcError::InvalidContinue.into()),
+		}
+	}
 }
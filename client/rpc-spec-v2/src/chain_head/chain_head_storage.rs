@@ -18,47 +18,50 @@
 
 //! Implementation of the `chainHead_storage` method.
 
-use std::{marker::PhantomData, sync::Arc};
+use std::sync::Arc;
 
+use futures::{pin_mut, select, FutureExt};
 use jsonrpsee::SubscriptionSink;
 use sc_client_api::{Backend, ChildInfo, StorageKey, StorageProvider};
 use sp_api::BlockT;
 use sp_core::storage::well_known_keys;
 
+use crate::common::storage::{IterQueryType, QueryIter, Storage};
+
 use super::{
-	event::{
-		ChainHeadStorageEvent, ItemsEvent, StorageQuery, StorageQueryType, StorageResult,
-		StorageResultType,
-	},
-	hex_string, ErrorEvent,
+	event::{ChainHeadStorageEvent, ItemsEvent, StorageQuery, StorageQueryType},
+	subscription::SubscriptionManagement,
+	ErrorEvent,
 };
 
 /// The maximum number of items the `chainHead_storage` can return
 /// before paginations is required.
 const MAX_ITER_ITEMS: usize = 10;
 
-/// The query type of an interation.
-enum IterQueryType {
-	/// Iterating over (key, value) pairs.
-	Value,
-	/// Iterating over (key, hash) pairs.
-	Hash,
-}
-
 /// Generates the events of the `chainHead_storage` method.
 pub struct ChainHeadStorage<Client, Block, BE> {
-	/// Substrate client.
-	client: Arc<Client>,
-	_phantom: PhantomData<(Block, BE)>,
+	/// Storage client.
+	client: Storage<Client, Block, BE>,
 }
 
 impl<Client, Block, BE> ChainHeadStorage<Client, Block, BE> {
 	/// Constructs a new [`ChainHeadStorage`].
 	pub fn new(client: Arc<Client>) -> Self {
-		Self { client, _phantom: PhantomData }
+		Self { client: Storage::new(client) }
 	}
 }
 
+/// A `chainHead_storage` operation that may need the `chainHead_continue`
+/// method to make progress.
+pub struct StorageOperation<Block: BlockT, BE: Backend<Block>> {
+	/// Keep track of the pinned blocks and operations of each subscription.
+	pub subscriptions: Arc<SubscriptionManagement<Block, BE>>,
+	/// The `chainHead_follow` subscription ID.
+	pub follow_subscription: String,
+	/// The operation ID, which is the ID of the `chainHead_storage` subscription.
+	pub operation_id: String,
+}
+
 /// Checks if the provided key (main or child key) is valid
 /// for queries.
 ///
@@ -69,11 +72,10 @@ fn is_key_queryable(key: &[u8]) -> bool {
 		!well_known_keys::is_child_storage_key(key)
 }
 
-/// The result of making a query call.
-type QueryResult = Result<Option<StorageResult<String>>, ChainHeadStorageEvent<String>>;
-
-/// The result of iterating over keys.
-type QueryIterResult = Result<Vec<StorageResult<String>>, ChainHeadStorageEvent<String>>;
+/// Convert a storage query error into the `chainHead_storage` error event.
+fn error_event(error: String) -> ChainHeadStorageEvent<String> {
+	ChainHeadStorageEvent::Error(ErrorEvent { error })
+}
 
 impl<Client, Block, BE> ChainHeadStorage<Client, Block, BE>
 where
@@ -81,100 +83,18 @@ where
 	BE: Backend<Block> + 'static,
 	Client: StorageProvider<Block, BE> + 'static,
 {
-	/// Fetch the value from storage.
-	fn query_storage_value(
-		&self,
-		hash: Block::Hash,
-		key: &StorageKey,
-		child_key: Option<&ChildInfo>,
-	) -> QueryResult {
-		let result = if let Some(child_key) = child_key {
-			self.client.child_storage(hash, child_key, key)
-		} else {
-			self.client.storage(hash, key)
-		};
-
-		result
-			.map(|opt| {
-				QueryResult::Ok(opt.map(|storage_data| StorageResult::<String> {
-					key: hex_string(&key.0),
-					result: StorageResultType::Value(hex_string(&storage_data.0)),
-				}))
-			})
-			.unwrap_or_else(|err| {
-				QueryResult::Err(ChainHeadStorageEvent::<String>::Error(ErrorEvent {
-					error: err.to_string(),
-				}))
-			})
-	}
-
-	/// Fetch the hash of a value from storage.
-	fn query_storage_hash(
-		&self,
-		hash: Block::Hash,
-		key: &StorageKey,
-		child_key: Option<&ChildInfo>,
-	) -> QueryResult {
-		let result = if let Some(child_key) = child_key {
-			self.client.child_storage_hash(hash, child_key, key)
-		} else {
-			self.client.storage_hash(hash, key)
-		};
-
-		result
-			.map(|opt| {
-				QueryResult::Ok(opt.map(|storage_data| StorageResult::<String> {
-					key: hex_string(&key.0),
-					result: StorageResultType::Hash(hex_string(&storage_data.as_ref())),
-				}))
-			})
-			.unwrap_or_else(|err| {
-				QueryResult::Err(ChainHeadStorageEvent::<String>::Error(ErrorEvent {
-					error: err.to_string(),
-				}))
-			})
-	}
-
-	/// Handle iterating over (key, value) or (key, hash) pairs.
-	fn query_storage_iter(
-		&self,
-		hash: Block::Hash,
-		key: &StorageKey,
-		child_key: Option<&ChildInfo>,
-		ty: IterQueryType,
-	) -> QueryIterResult {
-		let keys_iter = if let Some(child_key) = child_key {
-			self.client.child_storage_keys(hash, child_key.to_owned(), Some(key), None)
-		} else {
-			self.client.storage_keys(hash, Some(key), None)
-		}
-		.map_err(|err| {
-			ChainHeadStorageEvent::<String>::Error(ErrorEvent { error: err.to_string() })
-		})?;
-
-		let mut ret = Vec::with_capacity(MAX_ITER_ITEMS);
-		let mut keys_iter = keys_iter.take(MAX_ITER_ITEMS);
-		while let Some(key) = keys_iter.next() {
-			let result = match ty {
-				IterQueryType::Value => self.query_storage_value(hash, &key, child_key),
-				IterQueryType::Hash => self.query_storage_hash(hash, &key, child_key),
-			}?;
-
-			if let Some(result) = result {
-				ret.push(result);
-			}
-		}
-
-		QueryIterResult::Ok(ret)
-	}
-
 	/// Generate the block events for the `chainHead_storage` method.
-	pub fn generate_events(
+	///
+	/// Descendant queries are iterated in pages of at most [`MAX_ITER_ITEMS`] items.
+	/// If more items are available after a page, the `WaitForContinue` event is generated
+	/// and the iteration resumes once the user calls the `chainHead_continue` method.
+	pub async fn generate_events(
 		&self,
 		mut sink: SubscriptionSink,
 		hash: Block::Hash,
 		items: Vec<StorageQuery<StorageKey>>,
 		child_key: Option<ChildInfo>,
+		operation: StorageOperation<Block, BE>,
 	) {
 		if let Some(child_key) = child_key.as_ref() {
 			if !is_key_queryable(child_key.storage_key()) {
@@ -184,62 +104,112 @@ where
 		}
 
 		let mut storage_results = Vec::with_capacity(items.len());
+		let mut iter_operations = Vec::new();
 		for item in items {
 			if !is_key_queryable(&item.key.0) {
 				continue
 			}
 
-			match item.query_type {
-				StorageQueryType::Value => {
-					match self.query_storage_value(hash, &item.key, child_key.as_ref()) {
-						Ok(Some(value)) => storage_results.push(value),
-						Ok(None) => continue,
-						Err(err) => {
-							let _ = sink.send(&err);
-							return
-						},
-					}
-				},
+			let result = match item.query_type {
+				StorageQueryType::Value =>
+					self.client.query_value(hash, &item.key, child_key.as_ref()),
 				StorageQueryType::Hash =>
-					match self.query_storage_hash(hash, &item.key, child_key.as_ref()) {
-						Ok(Some(value)) => storage_results.push(value),
-						Ok(None) => continue,
-						Err(err) => {
-							let _ = sink.send(&err);
-							return
-						},
-					},
-				StorageQueryType::DescendantsValues => match self.query_storage_iter(
-					hash,
-					&item.key,
-					child_key.as_ref(),
-					IterQueryType::Value,
-				) {
-					Ok(values) => storage_results.extend(values),
-					Err(err) => {
-						let _ = sink.send(&err);
-						return
-					},
+					self.client.query_hash(hash, &item.key, child_key.as_ref()),
+				StorageQueryType::ClosestDescendantMerkleValue =>
+					self.client.query_merkle_value(hash, &item.key, child_key.as_ref()),
+				StorageQueryType::DescendantsValues => {
+					iter_operations.push(QueryIter {
+						query_key: item.key,
+						pagination_start_key: None,
+						ty: IterQueryType::Value,
+					});
+					continue
 				},
-				StorageQueryType::DescendantsHashes => match self.query_storage_iter(
+				StorageQueryType::DescendantsHashes => {
+					iter_operations.push(QueryIter {
+						query_key: item.key,
+						pagination_start_key: None,
+						ty: IterQueryType::Hash,
+					});
+					continue
+				},
+			};
+
+			match result {
+				Ok(Some(value)) => storage_results.push(value),
+				Ok(None) => continue,
+				Err(error) => {
+					let _ = sink.send(&error_event(error));
+					return
+				},
+			}
+		}
+
+		loop {
+			// Advance each descendant query by one page.
+			let mut pending_operations = Vec::new();
+			for query in iter_operations.drain(..) {
+				match self.client.query_iter_pagination(
+					query,
 					hash,
-					&item.key,
 					child_key.as_ref(),
-					IterQueryType::Hash,
+					MAX_ITER_ITEMS,
 				) {
-					Ok(values) => storage_results.extend(values),
-					Err(err) => {
-						let _ = sink.send(&err);
+					Ok((items, maybe_next_query)) => {
+						storage_results.extend(items);
+						pending_operations.extend(maybe_next_query);
+					},
+					Err(error) => {
+						let _ = sink.send(&error_event(error));
 						return
 					},
-				},
-				_ => continue,
+				}
+			}
+
+			if !storage_results.is_empty() {
+				let items = std::mem::take(&mut storage_results);
+				let _ = sink.send(&ChainHeadStorageEvent::Items(ItemsEvent { items }));
+			}
+
+			if pending_operations.is_empty() {
+				break
+			}
+
+			// The operation must be registered before the `WaitForContinue` event is
+			// generated, to not miss the `chainHead_continue` call of the user.
+			let Ok(rx_continue) = operation
+				.subscriptions
+				.wait_for_continue(&operation.follow_subscription, operation.operation_id.clone())
+			else {
+				// The follow subscription is no longer active.
+				return
 			};
-		}
 
-		if !storage_results.is_empty() {
-			let event = ChainHeadStorageEvent::Items(ItemsEvent { items: storage_results });
-			let _ = sink.send(&event);
+			let _ = sink.send(&ChainHeadStorageEvent::<String>::WaitForContinue);
+
+			let continued = {
+				let sink_closed = sink.closed().fuse();
+				pin_mut!(sink_closed);
+				let mut rx_continue = rx_continue;
+				select! {
+					// Fails if the follow subscription ended before the user called
+					// `chainHead_continue`.
+					result = rx_continue => result.is_ok(),
+					_ = sink_closed => {
+						// The user stopped the `chainHead_storage` subscription.
+						operation.subscriptions.stop_operation(
+							&operation.follow_subscription,
+							&operation.operation_id,
+						);
+						false
+					},
+				}
+			};
+			if !continued {
+				return
+			}
+
+			iter_operations = pending_operations;
 		}
 
 		let _ = sink.send(&ChainHeadStorageEvent::<String>::Done);
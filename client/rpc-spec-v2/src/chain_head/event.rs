@@ -268,6 +268,13 @@ pub enum StorageQueryType {
 	DescendantsHashes,
 }
 
+impl StorageQueryType {
+	/// Returns `true` if the query iterates over the descendants of the provided key.
+	pub fn is_descendant_query(&self) -> bool {
+		matches!(self, Self::DescendantsValues | Self::DescendantsHashes)
+	}
+}
+
 /// The storage result.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -298,7 +305,8 @@ pub enum StorageResultType<T> {
 pub enum ChainHeadStorageEvent<T> {
 	/// The request produced multiple result items.
 	Items(ItemsEvent<T>),
-	/// The request produced multiple result items.
+	/// The request requires the `chainHead_continue` method
+	/// to be called before producing more result items.
 	WaitForContinue,
 	/// The request completed successfully and all the results were provided.
 	Done,
@@ -127,6 +127,10 @@ struct SubscriptionState<Block: BlockT> {
 	/// - extra space: an extra BTreeMap<Instant, Hash> to older hashes by oldest insertion
 	/// - extra time: O(log(N)) for insert/remove/find each `pin` block time per subscriptions
 	blocks: HashMap<Block::Hash, BlockState>,
+	/// Operations waiting for the `chainHead_continue` method to make progress.
+	///
+	/// Maps the operation ID to the sender that resumes the operation.
+	continue_operations: HashMap<String, oneshot::Sender<()>>,
 }
 
 impl<Block: BlockT> SubscriptionState<Block> {
@@ -318,6 +322,7 @@ impl<Block: BlockT, BE: Backend<Block>> SubscriptionsInner<Block, BE> {
 				with_runtime,
 				tx_stop: Some(tx_stop),
 				blocks: Default::default(),
+				continue_operations: Default::default(),
 			};
 			entry.insert(state);
 			Some(rx_stop)
@@ -501,6 +506,53 @@ impl<Block: BlockT, BE: Backend<Block>> SubscriptionsInner<Block, BE> {
 
 		BlockGuard::new(hash, sub.with_runtime, self.backend.clone())
 	}
+
+	/// Mark the operation as waiting for the `chainHead_continue` method.
+	///
+	/// Returns the receiver that is triggered when the user calls `chainHead_continue`
+	/// for the given operation. The receiver is cancelled if the subscription is removed.
+	pub fn wait_for_continue(
+		&mut self,
+		sub_id: &str,
+		operation_id: String,
+	) -> Result<oneshot::Receiver<()>, SubscriptionManagementError> {
+		let Some(sub) = self.subs.get_mut(sub_id) else {
+			return Err(SubscriptionManagementError::SubscriptionAbsent)
+		};
+
+		let (tx_continue, rx_continue) = oneshot::channel();
+		sub.continue_operations.insert(operation_id, tx_continue);
+		Ok(rx_continue)
+	}
+
+	/// Resume the operation that is waiting for the `chainHead_continue` method.
+	///
+	/// Returns an error if the operation did not previously call
+	/// [`Self::wait_for_continue`] or the subscription ID is invalid.
+	pub fn continue_operation(
+		&mut self,
+		sub_id: &str,
+		operation_id: &str,
+	) -> Result<(), SubscriptionManagementError> {
+		let Some(sub) = self.subs.get_mut(sub_id) else {
+			return Err(SubscriptionManagementError::SubscriptionAbsent)
+		};
+
+		let Some(tx_continue) = sub.continue_operations.remove(operation_id) else {
+			return Err(SubscriptionManagementError::OperationAbsent)
+		};
+
+		// The operation might have been stopped in the meantime.
+		let _ = tx_continue.send(());
+		Ok(())
+	}
+
+	/// Stop waiting for the `chainHead_continue` method, releasing the operation.
+	pub fn stop_operation(&mut self, sub_id: &str, operation_id: &str) {
+		if let Some(sub) = self.subs.get_mut(sub_id) {
+			sub.continue_operations.remove(operation_id);
+		}
+	}
 }
 
 #[cfg(test)]
@@ -608,6 +660,7 @@ mod tests {
 			with_runtime: false,
 			tx_stop: None,
 			blocks: Default::default(),
+			continue_operations: Default::default(),
 		};
 
 		let hash = H256::random();
@@ -633,6 +686,7 @@ mod tests {
 			with_runtime: false,
 			tx_stop: None,
 			blocks: Default::default(),
+			continue_operations: Default::default(),
 		};
 
 		let hash = H256::random();
@@ -934,4 +988,48 @@ mod tests {
 		let res = rx_stop.try_recv().unwrap();
 		assert!(res.is_some());
 	}
+
+	#[test]
+	fn subscription_continue_operation() {
+		let builder = TestClientBuilder::new();
+		let backend = builder.backend();
+		let mut subs = SubscriptionsInner::new(10, Duration::from_secs(10), backend);
+
+		let id = "abc".to_string();
+		let operation_id = "op".to_string();
+
+		// Subscription not inserted.
+		let err = subs.wait_for_continue(&id, operation_id.clone()).unwrap_err();
+		assert_eq!(err, SubscriptionManagementError::SubscriptionAbsent);
+		let err = subs.continue_operation(&id, &operation_id).unwrap_err();
+		assert_eq!(err, SubscriptionManagementError::SubscriptionAbsent);
+
+		let _stop = subs.insert_subscription(id.clone(), true).unwrap();
+
+		// The operation is not waiting for continue.
+		let err = subs.continue_operation(&id, &operation_id).unwrap_err();
+		assert_eq!(err, SubscriptionManagementError::OperationAbsent);
+
+		let mut rx_continue = subs.wait_for_continue(&id, operation_id.clone()).unwrap();
+		assert!(rx_continue.try_recv().unwrap().is_none());
+
+		subs.continue_operation(&id, &operation_id).unwrap();
+		assert!(rx_continue.try_recv().unwrap().is_some());
+
+		// The operation can be continued only once per wait.
+		let err = subs.continue_operation(&id, &operation_id).unwrap_err();
+		assert_eq!(err, SubscriptionManagementError::OperationAbsent);
+
+		// Stopping the operation releases it.
+		let mut rx_continue = subs.wait_for_continue(&id, operation_id.clone()).unwrap();
+		subs.stop_operation(&id, &operation_id);
+		assert!(rx_continue.try_recv().is_err());
+		let err = subs.continue_operation(&id, &operation_id).unwrap_err();
+		assert_eq!(err, SubscriptionManagementError::OperationAbsent);
+
+		// Removing the subscription cancels the waiting operations.
+		let mut rx_continue = subs.wait_for_continue(&id, operation_id.clone()).unwrap();
+		subs.remove_subscription(&id);
+		assert!(rx_continue.try_recv().is_err());
+	}
 }
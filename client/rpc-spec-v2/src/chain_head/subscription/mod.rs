@@ -122,4 +122,38 @@ impl<Block: BlockT, BE: Backend<Block>> SubscriptionManagement<Block, BE> {
 		let mut inner = self.inner.write();
 		inner.lock_block(sub_id, hash)
 	}
+
+	/// Mark the operation as waiting for the `chainHead_continue` method.
+	///
+	/// Returns a receiver that is triggered once the user calls `chainHead_continue`
+	/// for the operation, or an error if the subscription ID is invalid.
+	pub fn wait_for_continue(
+		&self,
+		sub_id: &str,
+		operation_id: String,
+	) -> Result<oneshot::Receiver<()>, SubscriptionManagementError> {
+		let mut inner = self.inner.write();
+		inner.wait_for_continue(sub_id, operation_id)
+	}
+
+	/// Resume the operation that is waiting for the `chainHead_continue` method.
+	///
+	/// Returns an error if the operation is not waiting for continue or
+	/// the subscription ID is invalid.
+	pub fn continue_operation(
+		&self,
+		sub_id: &str,
+		operation_id: &str,
+	) -> Result<(), SubscriptionManagementError> {
+		let mut inner = self.inner.write();
+		inner.continue_operation(sub_id, operation_id)
+	}
+
+	/// Stop waiting for the `chainHead_continue` method, releasing the operation.
+	///
+	/// Called when the user stops the operation before continuing it.
+	pub fn stop_operation(&self, sub_id: &str, operation_id: &str) {
+		let mut inner = self.inner.write();
+		inner.stop_operation(sub_id, operation_id)
+	}
 }
@@ -825,6 +825,180 @@ async fn get_storage_value() {
 	assert_matches!(event, ChainHeadStorageEvent::Done);
 }
 
+#[tokio::test]
+async fn get_storage_closest_merkle_value() {
+	let (mut client, api, mut block_sub, sub_id, _) = setup_api().await;
+	let key = hex_string(&KEY);
+
+	// Import a new block with storage changes.
+	let mut builder = client.new_block(Default::default()).unwrap();
+	builder.push_storage_change(KEY.to_vec(), Some(VALUE.to_vec())).unwrap();
+	let block = builder.build().unwrap().block;
+	let block_hash = format!("{:?}", block.header.hash());
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	// Ensure the imported block is propagated and pinned for this subscription.
+	assert_matches!(
+		get_next_event::<FollowEvent<String>>(&mut block_sub).await,
+		FollowEvent::NewBlock(_)
+	);
+	assert_matches!(
+		get_next_event::<FollowEvent<String>>(&mut block_sub).await,
+		FollowEvent::BestBlockChanged(_)
+	);
+
+	let mut sub = api
+		.subscribe(
+			"chainHead_unstable_storage",
+			rpc_params![
+				&sub_id,
+				&block_hash,
+				vec![StorageQuery {
+					key: key.clone(),
+					query_type: StorageQueryType::ClosestDescendantMerkleValue
+				}]
+			],
+		)
+		.await
+		.unwrap();
+	let event: ChainHeadStorageEvent<String> = get_next_event(&mut sub).await;
+	let merkle_value = match event {
+		ChainHeadStorageEvent::Items(res) if res.items.len() == 1 && res.items[0].key == key =>
+			match &res.items[0].result {
+				StorageResultType::ClosestDescendantMerkleValue(value) => value.clone(),
+				result => panic!("Unexpected result {:?}", result),
+			},
+		event => panic!("Unexpected event {:?}", event),
+	};
+	let event: ChainHeadStorageEvent<String> = get_next_event(&mut sub).await;
+	assert_matches!(event, ChainHeadStorageEvent::Done);
+
+	// Changing the value changes the merkle value of the key.
+	let mut builder = client.new_block(Default::default()).unwrap();
+	builder.push_storage_change(KEY.to_vec(), Some(CHILD_VALUE.to_vec())).unwrap();
+	let block = builder.build().unwrap().block;
+	let block_hash = format!("{:?}", block.header.hash());
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	assert_matches!(
+		get_next_event::<FollowEvent<String>>(&mut block_sub).await,
+		FollowEvent::NewBlock(_)
+	);
+	assert_matches!(
+		get_next_event::<FollowEvent<String>>(&mut block_sub).await,
+		FollowEvent::BestBlockChanged(_)
+	);
+
+	let mut sub = api
+		.subscribe(
+			"chainHead_unstable_storage",
+			rpc_params![
+				&sub_id,
+				&block_hash,
+				vec![StorageQuery {
+					key: key.clone(),
+					query_type: StorageQueryType::ClosestDescendantMerkleValue
+				}]
+			],
+		)
+		.await
+		.unwrap();
+	let event: ChainHeadStorageEvent<String> = get_next_event(&mut sub).await;
+	assert_matches!(event, ChainHeadStorageEvent::<String>::Items(res) if res.items.len() == 1 &&
+		res.items[0].key == key &&
+		res.items[0].result != StorageResultType::ClosestDescendantMerkleValue(merkle_value));
+	let event: ChainHeadStorageEvent<String> = get_next_event(&mut sub).await;
+	assert_matches!(event, ChainHeadStorageEvent::Done);
+}
+
+#[tokio::test]
+async fn get_storage_iter_pagination() {
+	let (mut client, api, mut block_sub, sub_id, _) = setup_api().await;
+	let key = hex_string(&KEY);
+
+	// Import a new block with 21 keys that are descendants of `KEY`.
+	let mut builder = client.new_block(Default::default()).unwrap();
+	builder.push_storage_change(KEY.to_vec(), Some(VALUE.to_vec())).unwrap();
+	for index in 0..20u8 {
+		let key = [KEY, &[index]].concat();
+		builder.push_storage_change(key, Some(VALUE.to_vec())).unwrap();
+	}
+	let block = builder.build().unwrap().block;
+	let block_hash = format!("{:?}", block.header.hash());
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	// Ensure the imported block is propagated and pinned for this subscription.
+	assert_matches!(
+		get_next_event::<FollowEvent<String>>(&mut block_sub).await,
+		FollowEvent::NewBlock(_)
+	);
+	assert_matches!(
+		get_next_event::<FollowEvent<String>>(&mut block_sub).await,
+		FollowEvent::BestBlockChanged(_)
+	);
+
+	let mut sub = api
+		.subscribe(
+			"chainHead_unstable_storage",
+			rpc_params![
+				&sub_id,
+				&block_hash,
+				vec![StorageQuery {
+					key: key.clone(),
+					query_type: StorageQueryType::DescendantsValues
+				}]
+			],
+		)
+		.await
+		.unwrap();
+	let operation_id = serde_json::to_string(&sub.subscription_id()).unwrap();
+
+	// Unknown operations cannot be continued.
+	let err = api
+		.call::<_, serde_json::Value>(
+			"chainHead_unstable_continue",
+			[&sub_id, &"invalid_operation_id".to_string()],
+		)
+		.await
+		.unwrap_err();
+	assert_matches!(err,
+		Error::Call(CallError::Custom(ref err)) if err.code() == 2005
+	);
+
+	let expected_value = hex_string(&VALUE);
+	let mut keys = Vec::new();
+	for expected_items in [10, 10, 1] {
+		let event: ChainHeadStorageEvent<String> = get_next_event(&mut sub).await;
+		let items = match event {
+			ChainHeadStorageEvent::Items(res) => res.items,
+			event => panic!("Unexpected event {:?}", event),
+		};
+		assert_eq!(items.len(), expected_items);
+		assert!(items
+			.iter()
+			.all(|item| item.result == StorageResultType::Value(expected_value.clone())));
+		keys.extend(items.into_iter().map(|item| item.key));
+
+		if expected_items == 1 {
+			break
+		}
+
+		let event: ChainHeadStorageEvent<String> = get_next_event(&mut sub).await;
+		assert_matches!(event, ChainHeadStorageEvent::WaitForContinue);
+
+		let _res: () =
+			api.call("chainHead_unstable_continue", [&sub_id, &operation_id]).await.unwrap();
+	}
+	let event: ChainHeadStorageEvent<String> = get_next_event(&mut sub).await;
+	assert_matches!(event, ChainHeadStorageEvent::Done);
+
+	// All the descendant keys are reported exactly once.
+	keys.sort();
+	keys.dedup();
+	assert_eq!(keys.len(), 21);
+	assert!(keys.contains(&key));
+}
+
 #[tokio::test]
 async fn get_storage_wrong_key() {
 	let (mut _client, api, mut _block_sub, sub_id, block) = setup_api().await;
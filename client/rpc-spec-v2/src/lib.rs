@@ -23,6 +23,9 @@
 #![warn(missing_docs)]
 #![deny(unused_crate_dependencies)]
 
+mod common;
+
+pub mod archive;
 pub mod chain_head;
 pub mod chain_spec;
 pub mod transaction;
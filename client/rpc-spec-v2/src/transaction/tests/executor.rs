@@ -0,0 +1,57 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use futures::future::BoxFuture;
+use parking_lot::Mutex;
+use sp_core::traits::SpawnNamed;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+
+/// Executor spawning the tasks on the runtime of the test and keeping their handles, so that the
+/// tests can wait for a broadcast to end.
+#[derive(Clone, Default)]
+pub struct TaskExecutor {
+	tasks: Arc<Mutex<Vec<JoinHandle<()>>>>,
+}
+
+impl TaskExecutor {
+	/// Take the handle of the last spawned task.
+	pub fn take_last_task(&self) -> JoinHandle<()> {
+		self.tasks.lock().pop().expect("a task was spawned; qed")
+	}
+}
+
+impl SpawnNamed for TaskExecutor {
+	fn spawn_blocking(
+		&self,
+		name: &'static str,
+		group: Option<&'static str>,
+		future: BoxFuture<'static, ()>,
+	) {
+		self.spawn(name, group, future)
+	}
+
+	fn spawn(
+		&self,
+		_name: &'static str,
+		_group: Option<&'static str>,
+		future: BoxFuture<'static, ()>,
+	) {
+		self.tasks.lock().push(tokio::spawn(future));
+	}
+}
@@ -0,0 +1,154 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use futures::{channel::oneshot, FutureExt, StreamExt};
+use sc_transaction_pool::Transaction;
+use sc_transaction_pool_api::{
+	error::Error, ImportNotificationStream, PoolFuture, PoolStatus, ReadyTransactions,
+	TransactionFor, TransactionPool, TransactionSource, TransactionStatus,
+	TransactionStatusStreamFor, TxHash,
+};
+use sc_utils::mpsc::{tracing_unbounded, TracingUnboundedReceiver, TracingUnboundedSender};
+use sp_runtime::{generic::BlockId, traits::NumberFor};
+use std::{collections::HashMap, future::Future, pin::Pin, sync::Arc};
+use substrate_test_runtime::{Block, Extrinsic, Hash};
+
+type SubmitResult = Result<Pin<Box<TransactionStatusStreamFor<MockTransactionPool>>>, Error>;
+
+/// A transaction submitted to the [`MockTransactionPool`], waiting for the test to decide the
+/// outcome of the submission.
+pub struct Submission {
+	/// The block the transaction was submitted at.
+	pub at: Hash,
+	/// The submitted transaction.
+	pub xt: Extrinsic,
+	response: oneshot::Sender<SubmitResult>,
+}
+
+impl Submission {
+	/// Accept the transaction and return the sender of its events.
+	pub fn accept(self) -> TracingUnboundedSender<TransactionStatus<Hash, Hash>> {
+		let (events, stream) = tracing_unbounded("mpsc_mock_pool_events", 1024);
+		let _ = self.response.send(Ok(stream.boxed()));
+		events
+	}
+
+	/// Reject the transaction with the given error.
+	pub fn reject(self, error: Error) {
+		let _ = self.response.send(Err(error));
+	}
+}
+
+/// Transaction pool handing the watched transactions over to the test.
+///
+/// Only [`TransactionPool::submit_and_watch`] is supported.
+pub struct MockTransactionPool {
+	submissions: TracingUnboundedSender<Submission>,
+}
+
+impl MockTransactionPool {
+	/// Create a new pool and the stream of the transactions submitted to it.
+	pub fn new() -> (Self, TracingUnboundedReceiver<Submission>) {
+		let (submissions, stream) = tracing_unbounded("mpsc_mock_pool_submissions", 1024);
+		(Self { submissions }, stream)
+	}
+}
+
+impl TransactionPool for MockTransactionPool {
+	type Block = Block;
+	type Hash = Hash;
+	type InPoolTransaction = Transaction<Hash, Extrinsic>;
+	type Error = Error;
+
+	fn submit_at(
+		&self,
+		_at: &BlockId<Self::Block>,
+		_source: TransactionSource,
+		_xts: Vec<TransactionFor<Self>>,
+	) -> PoolFuture<Vec<Result<TxHash<Self>, Self::Error>>, Self::Error> {
+		unimplemented!()
+	}
+
+	fn submit_one(
+		&self,
+		_at: &BlockId<Self::Block>,
+		_source: TransactionSource,
+		_xt: TransactionFor<Self>,
+	) -> PoolFuture<TxHash<Self>, Self::Error> {
+		unimplemented!()
+	}
+
+	fn submit_and_watch(
+		&self,
+		at: &BlockId<Self::Block>,
+		_source: TransactionSource,
+		xt: TransactionFor<Self>,
+	) -> PoolFuture<Pin<Box<TransactionStatusStreamFor<Self>>>, Self::Error> {
+		let at = match at {
+			BlockId::Hash(hash) => *hash,
+			BlockId::Number(_) => panic!("Transactions are broadcast at block hashes"),
+		};
+		let (response, result) = oneshot::channel();
+		self.submissions
+			.unbounded_send(Submission { at, xt, response })
+			.expect("The test holds the stream of submissions; qed");
+
+		async move { result.await.expect("The test answers every submission; qed") }.boxed()
+	}
+
+	fn ready_at(
+		&self,
+		_at: NumberFor<Self::Block>,
+	) -> Pin<
+		Box<
+			dyn Future<
+					Output = Box<dyn ReadyTransactions<Item = Arc<Self::InPoolTransaction>> + Send>,
+				> + Send,
+		>,
+	> {
+		unimplemented!()
+	}
+
+	fn ready(&self) -> Box<dyn ReadyTransactions<Item = Arc<Self::InPoolTransaction>> + Send> {
+		unimplemented!()
+	}
+
+	fn remove_invalid(&self, _hashes: &[TxHash<Self>]) -> Vec<Arc<Self::InPoolTransaction>> {
+		unimplemented!()
+	}
+
+	fn status(&self) -> PoolStatus {
+		unimplemented!()
+	}
+
+	fn import_notification_stream(&self) -> ImportNotificationStream<TxHash<Self>> {
+		unimplemented!()
+	}
+
+	fn on_broadcasted(&self, _propagations: HashMap<TxHash<Self>, Vec<String>>) {
+		unimplemented!()
+	}
+
+	fn hash_of(&self, _xt: &TransactionFor<Self>) -> TxHash<Self> {
+		unimplemented!()
+	}
+
+	fn ready_transaction(&self, _hash: &TxHash<Self>) -> Option<Arc<Self::InPoolTransaction>> {
+		unimplemented!()
+	}
+}
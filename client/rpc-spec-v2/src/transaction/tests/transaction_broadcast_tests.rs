@@ -0,0 +1,172 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+	executor::TaskExecutor,
+	mock_pool::{MockTransactionPool, Submission},
+};
+use crate::transaction::{TransactionBroadcast, TransactionBroadcastApiServer};
+use assert_matches::assert_matches;
+use codec::Encode;
+use futures::{FutureExt, StreamExt};
+use jsonrpsee::{core::error::Error, types::error::CallError, RpcModule};
+use sc_block_builder::BlockBuilderProvider;
+use sc_transaction_pool_api::{error::Error as PoolError, TransactionStatus};
+use sc_utils::mpsc::TracingUnboundedReceiver;
+use sp_blockchain::HeaderBackend;
+use sp_consensus::BlockOrigin;
+use sp_runtime::traits::Block as BlockT;
+use std::{sync::Arc, time::Duration};
+use substrate_test_runtime::{Extrinsic, ExtrinsicBuilder, Hash};
+use substrate_test_runtime_client::{Backend, Client, ClientBlockImportExt};
+
+type Api = RpcModule<TransactionBroadcast<MockTransactionPool, Client<Backend>>>;
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+
+fn setup_api() -> (Arc<Client<Backend>>, Api, TracingUnboundedReceiver<Submission>, TaskExecutor) {
+	let client = Arc::new(substrate_test_runtime_client::new());
+	let (pool, submissions) = MockTransactionPool::new();
+	let executor = TaskExecutor::default();
+
+	let api = TransactionBroadcast::new(client.clone(), Arc::new(pool), Arc::new(executor.clone()))
+		.into_rpc();
+
+	(client, api, submissions, executor)
+}
+
+/// Broadcast a transaction and return the transaction with the ID of the operation.
+async fn broadcast(api: &Api) -> (Extrinsic, String) {
+	let xt = ExtrinsicBuilder::new_include_data(vec![1, 2, 3]).build();
+	let bytes = array_bytes::bytes2hex("0x", &xt.encode());
+
+	let id: Option<String> = api.call("transaction_unstable_broadcast", [bytes]).await.unwrap();
+	(xt, id.unwrap())
+}
+
+async fn next_submission(submissions: &mut TracingUnboundedReceiver<Submission>) -> Submission {
+	tokio::time::timeout(TIMEOUT, submissions.next()).await.unwrap().unwrap()
+}
+
+/// Import a new best block and return its hash.
+async fn import_block(client: &mut Arc<Client<Backend>>) -> Hash {
+	let block = client.new_block(Default::default()).unwrap().build().unwrap().block;
+	let hash = block.hash();
+	client.import(BlockOrigin::Own, block).await.unwrap();
+	hash
+}
+
+#[tokio::test]
+async fn broadcast_resubmits_at_new_best_blocks() {
+	let (mut client, api, mut submissions, _executor) = setup_api();
+	let (xt, id) = broadcast(&api).await;
+
+	// The transaction is first submitted at the best block.
+	let submission = next_submission(&mut submissions).await;
+	assert_eq!(submission.at, client.info().best_hash);
+	assert_eq!(submission.xt, xt);
+
+	// The transaction is dropped from the pool after entering it.
+	let events = submission.accept();
+	events.unbounded_send(TransactionStatus::Ready).unwrap();
+	events.unbounded_send(TransactionStatus::Dropped).unwrap();
+
+	// Nothing happens until the next best block.
+	assert!(submissions.next().now_or_never().is_none());
+	let block_hash = import_block(&mut client).await;
+	let submission = next_submission(&mut submissions).await;
+	assert_eq!(submission.at, block_hash);
+	assert_eq!(submission.xt, xt);
+
+	// The pool can't take the transaction in.
+	submission.reject(PoolError::ImmediatelyDropped);
+
+	let block_hash = import_block(&mut client).await;
+	let submission = next_submission(&mut submissions).await;
+	assert_eq!(submission.at, block_hash);
+	assert_eq!(submission.xt, xt);
+
+	let _: () = api.call("transaction_unstable_stop", [&id]).await.unwrap();
+}
+
+#[tokio::test]
+async fn broadcast_stops_with_valid_id() {
+	let (mut client, api, mut submissions, executor) = setup_api();
+	let (_xt, id) = broadcast(&api).await;
+
+	let events = next_submission(&mut submissions).await.accept();
+	events.unbounded_send(TransactionStatus::Ready).unwrap();
+
+	let _: () = api.call("transaction_unstable_stop", [&id]).await.unwrap();
+
+	// The broadcast ends and stops watching the transaction.
+	tokio::time::timeout(TIMEOUT, executor.take_last_task()).await.unwrap().unwrap();
+	assert!(events.is_closed());
+
+	// The transaction is not submitted again at new blocks.
+	import_block(&mut client).await;
+	assert!(submissions.next().now_or_never().is_none());
+
+	// The operation ID can't be stopped twice.
+	let err = api.call::<_, ()>("transaction_unstable_stop", [&id]).await.unwrap_err();
+	assert_matches!(
+		err,
+		Error::Call(CallError::Custom(ref err)) if err.code() == -32602 && err.message() == "Invalid operation id"
+	);
+}
+
+#[tokio::test]
+async fn broadcast_stop_with_invalid_id() {
+	let (_client, api, _submissions, _executor) = setup_api();
+
+	let err = api
+		.call::<_, ()>("transaction_unstable_stop", ["invalid_operation"])
+		.await
+		.unwrap_err();
+	assert_matches!(
+		err,
+		Error::Call(CallError::Custom(ref err)) if err.code() == -32602 && err.message() == "Invalid operation id"
+	);
+}
+
+#[tokio::test]
+async fn broadcast_ends_after_inclusion() {
+	let (mut client, api, mut submissions, executor) = setup_api();
+	let (_xt, id) = broadcast(&api).await;
+
+	let events = next_submission(&mut submissions).await.accept();
+	let block_hash = import_block(&mut client).await;
+	events.unbounded_send(TransactionStatus::Ready).unwrap();
+	events.unbounded_send(TransactionStatus::InBlock((block_hash, 0))).unwrap();
+	events.unbounded_send(TransactionStatus::Finalized((block_hash, 0))).unwrap();
+
+	// The broadcast ends once the transaction is finalized.
+	tokio::time::timeout(TIMEOUT, executor.take_last_task()).await.unwrap().unwrap();
+	assert!(events.is_closed());
+
+	// The transaction is not submitted again at new blocks.
+	import_block(&mut client).await;
+	assert!(submissions.next().now_or_never().is_none());
+
+	// The operation ID is released once the broadcast ends.
+	let err = api.call::<_, ()>("transaction_unstable_stop", [&id]).await.unwrap_err();
+	assert_matches!(
+		err,
+		Error::Call(CallError::Custom(ref err)) if err.code() == -32602 && err.message() == "Invalid operation id"
+	);
+}
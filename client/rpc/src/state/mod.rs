@@ -157,6 +157,12 @@ where
 		methods: Option<String>,
 	) -> Result<sp_rpc::tracing::TraceBlockResponse, Error>;
 
+	/// Profile the host calls made while executing block
+	fn profile_block_host_calls(
+		&self,
+		block: Block::Hash,
+	) -> Result<sp_rpc::tracing::BlockHostCalls, Error>;
+
 	/// New runtime version subscription
 	fn subscribe_runtime_version(&self, sink: SubscriptionSink);
 
@@ -325,6 +331,17 @@ where
 			.map_err(Into::into)
 	}
 
+	/// Re-execute the given block and capture the host functions called by the runtime.
+	///
+	/// Note: requires the node to run with `--rpc-methods=Unsafe`.
+	fn profile_block_host_calls(
+		&self,
+		block: Block::Hash,
+	) -> RpcResult<sp_rpc::tracing::BlockHostCalls> {
+		self.deny_unsafe.check_if_safe()?;
+		self.backend.profile_block_host_calls(block).map_err(Into::into)
+	}
+
 	fn subscribe_runtime_version(&self, sink: SubscriptionSink) -> SubscriptionResult {
 		self.backend.subscribe_runtime_version(sink);
 		Ok(())
@@ -46,7 +46,6 @@ use sp_core::{
 	storage::{
 		ChildInfo, ChildType, PrefixedStorageKey, StorageChangeSet, StorageData, StorageKey,
 	},
-	traits::CallContext,
 	Bytes,
 };
 use sp_runtime::traits::Block as BlockT;
@@ -196,10 +195,7 @@ where
 	) -> std::result::Result<Bytes, Error> {
 		self.block_or_best(block)
 			.and_then(|block| {
-				self.client
-					.executor()
-					.call(block, &method, &call_data, CallContext::Offchain)
-					.map(Into::into)
+				self.client.executor().rpc_call(block, &method, &call_data).map(Into::into)
 			})
 			.map_err(client_err)
 	}
@@ -474,6 +470,15 @@ where
 		.trace_block()
 		.map_err(|e| invalid_block::<Block>(block, None, e.to_string()))
 	}
+
+	fn profile_block_host_calls(
+		&self,
+		block: Block::Hash,
+	) -> std::result::Result<sp_rpc::tracing::BlockHostCalls, Error> {
+		sc_tracing::block::BlockExecutor::new(self.client.clone(), block, None, None, None)
+			.profile_host_calls()
+			.map_err(|e| invalid_block::<Block>(block, None, e.to_string()))
+	}
 }
 
 impl<BE, Block, Client> ChildStateBackend<Block, Client> for FullState<BE, Block, Client>
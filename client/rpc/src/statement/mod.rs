@@ -18,18 +18,28 @@
 
 //! Substrate statement store API.
 
+use crate::SubscriptionTaskExecutor;
 use codec::{Decode, Encode};
-use jsonrpsee::core::{async_trait, RpcResult};
+use futures::{FutureExt, StreamExt};
+use jsonrpsee::{
+	core::{async_trait, Error as JsonRpseeError, RpcResult},
+	types::SubscriptionResult,
+	SubscriptionSink,
+};
 /// Re-export the API for backward compatibility.
-pub use sc_rpc_api::statement::{error::Error, StatementApiServer};
+pub use sc_rpc_api::statement::{error::Error, StatementApiServer, TopicFilter};
 use sc_rpc_api::DenyUnsafe;
 use sp_core::Bytes;
 use sp_statement_store::{StatementSource, SubmitResult};
 use std::sync::Arc;
 
+#[cfg(test)]
+mod tests;
+
 /// Statement store API
 pub struct StatementStore {
 	store: Arc<dyn sp_statement_store::StatementStore>,
+	executor: SubscriptionTaskExecutor,
 	deny_unsafe: DenyUnsafe,
 }
 
@@ -37,9 +47,18 @@ impl StatementStore {
 	/// Create new instance of Offchain API.
 	pub fn new(
 		store: Arc<dyn sp_statement_store::StatementStore>,
+		executor: SubscriptionTaskExecutor,
 		deny_unsafe: DenyUnsafe,
 	) -> Self {
-		StatementStore { store, deny_unsafe }
+		StatementStore { store, executor, deny_unsafe }
+	}
+}
+
+/// Convert the RPC topic filter into the filter of the statement store.
+fn into_store_filter(filter: TopicFilter) -> sp_statement_store::TopicFilter {
+	match filter {
+		TopicFilter::MatchAll(topics) => sp_statement_store::TopicFilter::MatchAll(topics),
+		TopicFilter::MatchAny(topics) => sp_statement_store::TopicFilter::MatchAny(topics),
 	}
 }
 
@@ -105,4 +124,32 @@ impl StatementApiServer for StatementStore {
 	fn remove(&self, hash: [u8; 32]) -> RpcResult<()> {
 		Ok(self.store.remove(&hash).map_err(|e| Error::StatementStore(e.to_string()))?)
 	}
+
+	fn subscribe_statement(
+		&self,
+		mut sink: SubscriptionSink,
+		topic_filter: TopicFilter,
+		dest: Option<[u8; 32]>,
+	) -> SubscriptionResult {
+		if let Err(err) = self.deny_unsafe.check_if_safe() {
+			let _ = sink.reject(JsonRpseeError::from(err));
+			return Ok(())
+		}
+
+		let stream = match self.store.subscribe(into_store_filter(topic_filter), dest) {
+			Ok(stream) => stream,
+			Err(e) => {
+				let _ = sink.reject(JsonRpseeError::from(Error::StatementStore(e.to_string())));
+				return Ok(())
+			},
+		};
+
+		let stream = stream.map(|statement| Bytes::from(statement.encode()));
+		let fut = async move {
+			sink.pipe_from_stream(stream).await;
+		};
+
+		self.executor.spawn("substrate-rpc-subscription", Some("rpc"), fut.boxed());
+		Ok(())
+	}
 }
@@ -0,0 +1,145 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::testing::{test_executor, timeout_secs};
+use assert_matches::assert_matches;
+use futures::channel::mpsc;
+use jsonrpsee::{rpc_params, types::error::CallError};
+use parking_lot::Mutex;
+use sp_statement_store::{Hash, Statement, Topic};
+
+/// Statement store that reports the statements sent through the channel
+/// to the first subscriber.
+struct TestStore {
+	stream: Mutex<Option<sp_statement_store::StatementStream>>,
+	subscriptions: Mutex<Vec<(sp_statement_store::TopicFilter, Option<[u8; 32]>)>>,
+}
+
+impl TestStore {
+	fn new() -> (Arc<Self>, mpsc::UnboundedSender<Statement>) {
+		let (tx, rx) = mpsc::unbounded();
+		let store =
+			TestStore { stream: Mutex::new(Some(Box::pin(rx))), subscriptions: Default::default() };
+		(Arc::new(store), tx)
+	}
+}
+
+impl sp_statement_store::StatementStore for TestStore {
+	fn statements(&self) -> sp_statement_store::Result<Vec<(Hash, Statement)>> {
+		unimplemented!()
+	}
+
+	fn statement(&self, _hash: &Hash) -> sp_statement_store::Result<Option<Statement>> {
+		unimplemented!()
+	}
+
+	fn broadcasts(&self, _match_all_topics: &[Topic]) -> sp_statement_store::Result<Vec<Vec<u8>>> {
+		unimplemented!()
+	}
+
+	fn posted(
+		&self,
+		_match_all_topics: &[Topic],
+		_dest: [u8; 32],
+	) -> sp_statement_store::Result<Vec<Vec<u8>>> {
+		unimplemented!()
+	}
+
+	fn posted_clear(
+		&self,
+		_match_all_topics: &[Topic],
+		_dest: [u8; 32],
+	) -> sp_statement_store::Result<Vec<Vec<u8>>> {
+		unimplemented!()
+	}
+
+	fn submit(&self, _statement: Statement, _source: StatementSource) -> SubmitResult {
+		unimplemented!()
+	}
+
+	fn remove(&self, _hash: &Hash) -> sp_statement_store::Result<()> {
+		unimplemented!()
+	}
+
+	fn subscribe(
+		&self,
+		topic_filter: sp_statement_store::TopicFilter,
+		dest: Option<[u8; 32]>,
+	) -> sp_statement_store::Result<sp_statement_store::StatementStream> {
+		self.subscriptions.lock().push((topic_filter, dest));
+		self.stream
+			.lock()
+			.take()
+			.ok_or_else(|| sp_statement_store::Error::Db("Already subscribed".into()))
+	}
+}
+
+#[tokio::test]
+async fn subscribe_reports_statements() {
+	let (store, tx) = TestStore::new();
+	let api = StatementStore::new(store.clone(), test_executor(), DenyUnsafe::No).into_rpc();
+
+	let topic = [1u8; 32];
+	let mut sub = api
+		.subscribe(
+			"statement_subscribe",
+			rpc_params![TopicFilter::MatchAny(vec![topic]), Some([2u8; 32])],
+		)
+		.await
+		.unwrap();
+	assert_eq!(
+		*store.subscriptions.lock(),
+		vec![(sp_statement_store::TopicFilter::MatchAny(vec![topic]), Some([2u8; 32]))]
+	);
+
+	let mut statement = Statement::new();
+	statement.set_topic(0, topic);
+	statement.set_plain_data(vec![1, 2, 3]);
+	tx.unbounded_send(statement.clone()).unwrap();
+
+	let (encoded, _) = timeout_secs(10, sub.next::<Bytes>()).await.unwrap().unwrap().unwrap();
+	assert_eq!(Statement::decode(&mut &encoded[..]).unwrap(), statement);
+
+	// The store rejects the second subscription.
+	let err = api
+		.subscribe(
+			"statement_subscribe",
+			rpc_params![TopicFilter::MatchAll(vec![]), None::<[u8; 32]>],
+		)
+		.await;
+	assert_matches!(
+		err,
+		Err(JsonRpseeError::Call(CallError::Custom(err))) if err.code() == 7001
+	);
+}
+
+#[tokio::test]
+async fn subscribe_is_unsafe() {
+	let (store, _tx) = TestStore::new();
+	let api = StatementStore::new(store.clone(), test_executor(), DenyUnsafe::Yes).into_rpc();
+
+	let err = api
+		.subscribe(
+			"statement_subscribe",
+			rpc_params![TopicFilter::MatchAll(vec![]), None::<[u8; 32]>],
+		)
+		.await;
+	assert_matches!(err, Err(JsonRpseeError::Call(CallError::Custom(err))) if err.code() == -32601);
+	assert!(store.subscriptions.lock().is_empty());
+}
@@ -0,0 +1,47 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{config::Configuration, error::Error};
+use sc_executor::{
+	precompile_wasm_runtime, HeapAllocStrategy, WasmtimeArtifactCache, DEFAULT_HEAP_ALLOC_STRATEGY,
+};
+use std::path::PathBuf;
+
+/// Compile the runtime `code` and store it in the on-disk cache of compiled runtimes.
+///
+/// `heap_pages` is the number of extra heap pages the runtime is going to be executed with on
+/// chain. If it is `None` the default of the node is assumed. Once the runtime is enacted, the
+/// node loads the compiled runtime from the cache instead of compiling it. Returns the path of
+/// the compiled runtime.
+pub fn precompile_runtime(
+	config: &Configuration,
+	code: &[u8],
+	heap_pages: Option<u64>,
+) -> Result<PathBuf, Error> {
+	let max_size = config
+		.wasmtime_artifact_cache_size
+		.ok_or_else(|| Error::Other("The cache of compiled runtimes is disabled".into()))?;
+	let artifact_cache = WasmtimeArtifactCache::new(config.wasmtime_artifact_cache_dir(), max_size);
+
+	let heap_alloc_strategy = heap_pages
+		.or(config.default_heap_pages)
+		.map_or(DEFAULT_HEAP_ALLOC_STRATEGY, |p| HeapAllocStrategy::Static { extra_pages: p as _ });
+
+	precompile_wasm_runtime(&artifact_cache, code, config.wasm_method, heap_alloc_strategy)
+		.map_err(|e| Error::Other(format!("Failed to precompile the runtime: {}", e)))
+}
@@ -0,0 +1,520 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::error::Error;
+use codec::{Decode, DecodeAll, Encode, IoReader as CodecIoReader};
+use futures::{future, prelude::*};
+use log::info;
+use sc_client_api::{BlockBackend, HeaderBackend, StorageProvider};
+use sc_consensus::{
+	import_queue::{BlockImportError, BlockImportStatus, ImportQueue, IncomingBlock, Link},
+	ImportedState,
+};
+use sc_network_common::sync::warp::{
+	AuthorityList, EncodedProof, VerificationResult, WarpSyncProvider,
+};
+use sp_api::CallApiAt;
+use sp_consensus::BlockOrigin;
+use sp_core::storage::{well_known_keys, ChildInfo, StateVersion, StorageKey};
+use sp_runtime::{
+	traits::{Block as BlockT, Header as HeaderT, NumberFor},
+	Justifications,
+};
+use sp_state_machine::KeyValueStates;
+use std::{
+	collections::BTreeMap,
+	io::{Read, Write},
+	pin::Pin,
+	sync::Arc,
+	task::Poll,
+};
+
+/// Bytes every snapshot file starts with.
+const SNAPSHOT_MAGIC: [u8; 8] = *b"SUBSNAP\0";
+
+/// Version of the snapshot file format.
+const SNAPSHOT_VERSION: u32 = 1;
+
+/// Size of the key-value pairs after which a chunk is written out.
+const MAX_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Maximum size of an encoded chunk, large enough for a chunk holding a single runtime code.
+const MAX_ENCODED_CHUNK_SIZE: usize = 64 * 1024 * 1024;
+
+/// Block the snapshot was taken at, with the proof of its finality.
+#[derive(Encode, Decode)]
+struct SnapshotHeader<B: BlockT> {
+	genesis_hash: B::Hash,
+	header: B::Header,
+	justifications: Option<Justifications>,
+	/// Warp sync proofs from genesis up to `header`, as returned by the warp sync provider.
+	warp_proof: Vec<Vec<u8>>,
+	/// Version of the tries of the state.
+	state_version: StateVersion,
+}
+
+/// A batch of key-value pairs of the snapshot state.
+///
+/// Every chunk is preceded by its encoded length as a little endian `u32`. The chunks of the
+/// child tries come first, so that the state can be imported one trie at a time, followed by the
+/// chunks of the top trie and `End`.
+#[derive(Encode, Decode)]
+enum SnapshotChunk {
+	/// Pairs of the top trie, including the child trie roots.
+	Top(Vec<(Vec<u8>, Vec<u8>)>),
+	/// Pairs of a default child trie.
+	Child { prefixed_storage_key: Vec<u8>, root: Vec<u8>, entries: Vec<(Vec<u8>, Vec<u8>)> },
+	/// No more chunks follow.
+	End,
+}
+
+fn decode_error(e: codec::Error) -> Error {
+	Error::Other(format!("Invalid snapshot: {}", e))
+}
+
+/// Accumulates key-value pairs and writes them out in chunks.
+struct ChunkWriter<W> {
+	output: W,
+	entries: Vec<(Vec<u8>, Vec<u8>)>,
+	size: usize,
+	keys: u64,
+}
+
+impl<W: Write> ChunkWriter<W> {
+	fn push(
+		&mut self,
+		key: Vec<u8>,
+		value: Vec<u8>,
+		chunk: impl Fn(Vec<(Vec<u8>, Vec<u8>)>) -> SnapshotChunk,
+	) -> Result<(), Error> {
+		if self.size + key.len() + value.len() > MAX_CHUNK_SIZE {
+			self.flush(&chunk)?;
+		}
+		self.size += key.len() + value.len();
+		self.keys += 1;
+		self.entries.push((key, value));
+		Ok(())
+	}
+
+	fn flush(
+		&mut self,
+		chunk: impl Fn(Vec<(Vec<u8>, Vec<u8>)>) -> SnapshotChunk,
+	) -> Result<(), Error> {
+		if !self.entries.is_empty() {
+			self.size = 0;
+			self.write(chunk(std::mem::take(&mut self.entries)))?;
+		}
+		Ok(())
+	}
+
+	fn write(&mut self, chunk: SnapshotChunk) -> Result<(), Error> {
+		let encoded = chunk.encode();
+		if encoded.len() > MAX_ENCODED_CHUNK_SIZE {
+			return Err(Error::Other(format!(
+				"Storage entry of {} bytes is too large for a snapshot",
+				encoded.len()
+			)))
+		}
+		self.output.write_all(&(encoded.len() as u32).encode())?;
+		self.output.write_all(&encoded)?;
+		Ok(())
+	}
+}
+
+/// Export the state of the latest block with a finality proof into a snapshot file.
+///
+/// The finality of the block is proven with warp sync proofs starting from the given genesis
+/// authorities. Returns the header of the exported block.
+pub fn export_snapshot<B, BA, C>(
+	client: Arc<C>,
+	warp_sync: Arc<dyn WarpSyncProvider<B>>,
+	genesis_authorities: AuthorityList,
+	mut output: impl Write,
+) -> Result<B::Header, Error>
+where
+	B: BlockT,
+	BA: sc_client_api::backend::Backend<B>,
+	C: HeaderBackend<B> + BlockBackend<B> + StorageProvider<B, BA> + CallApiAt<B>,
+{
+	let genesis_hash = client.info().genesis_hash;
+	let mut warp_proof = Vec::new();
+	let (mut set_id, mut authorities, mut start) = (0, genesis_authorities, genesis_hash);
+	let header = loop {
+		let proof = warp_sync
+			.generate(start)
+			.map_err(|e| Error::Other(format!("Failed to generate warp proof: {}", e)))?;
+		let result = warp_sync
+			.verify(&proof, set_id, std::mem::take(&mut authorities))
+			.map_err(|e| Error::Other(format!("Failed to verify warp proof: {}", e)))?;
+		warp_proof.push(proof.0);
+		match result {
+			VerificationResult::Partial(next_set_id, next_authorities, hash) => {
+				if hash == start {
+					return Err(Error::Other("Warp proof does not make progress".into()))
+				}
+				set_id = next_set_id;
+				authorities = next_authorities;
+				start = hash;
+			},
+			VerificationResult::Complete(_, _, header) => break header,
+		}
+	};
+
+	let hash = header.hash();
+	info!("Exporting snapshot at #{} ({})", header.number(), hash);
+	let justifications = client.justifications(hash)?;
+	let state_version = client
+		.runtime_version_at(hash)
+		.map_err(sp_blockchain::Error::from)?
+		.state_version();
+	output.write_all(&SNAPSHOT_MAGIC)?;
+	output.write_all(&SNAPSHOT_VERSION.encode())?;
+	output.write_all(
+		&SnapshotHeader::<B> {
+			genesis_hash,
+			header: header.clone(),
+			justifications,
+			warp_proof,
+			state_version,
+		}
+		.encode(),
+	)?;
+
+	let mut writer = ChunkWriter { output, entries: Vec::new(), size: 0, keys: 0 };
+	let child_prefix = StorageKey(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX.to_vec());
+	for (prefixed_storage_key, root) in client.storage_pairs(hash, Some(&child_prefix), None)? {
+		let child_info = ChildInfo::new_default(
+			&prefixed_storage_key.0[well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX.len()..],
+		);
+		let chunk = |entries| SnapshotChunk::Child {
+			prefixed_storage_key: prefixed_storage_key.0.clone(),
+			root: root.0.clone(),
+			entries,
+		};
+		for key in client.child_storage_keys(hash, child_info.clone(), None, None)? {
+			if let Some(value) = client.child_storage(hash, &child_info, &key)? {
+				writer.push(key.0, value.0, chunk)?;
+			}
+		}
+		writer.flush(chunk)?;
+	}
+
+	for (key, value) in client.storage_pairs(hash, None, None)? {
+		writer.push(key.0, value.0, SnapshotChunk::Top)?;
+	}
+	writer.flush(SnapshotChunk::Top)?;
+	writer.write(SnapshotChunk::End)?;
+	writer.output.flush()?;
+
+	info!("🎉 Exported {} keys", writer.keys);
+	Ok(header)
+}
+
+/// Reads the chunks of a snapshot one at a time.
+struct ChunkReader<R> {
+	input: R,
+	/// Chunk read ahead to find the end of a trie.
+	next: Option<SnapshotChunk>,
+	/// Error hit while reading the pairs of a trie.
+	error: Option<Error>,
+	/// Roots of the child tries found in the top trie.
+	child_roots: BTreeMap<Vec<u8>, Vec<u8>>,
+	keys: u64,
+}
+
+impl<R: Read> ChunkReader<R> {
+	fn read(&mut self) -> Result<SnapshotChunk, Error> {
+		if let Some(chunk) = self.next.take() {
+			return Ok(chunk)
+		}
+		let mut len = [0u8; 4];
+		self.input.read_exact(&mut len)?;
+		let len = u32::from_le_bytes(len) as usize;
+		if len > MAX_ENCODED_CHUNK_SIZE {
+			return Err(Error::Other(format!(
+				"Snapshot chunk of {} bytes exceeds the limit of {} bytes",
+				len, MAX_ENCODED_CHUNK_SIZE
+			)))
+		}
+		let mut encoded = vec![0; len];
+		self.input.read_exact(&mut encoded)?;
+		SnapshotChunk::decode_all(&mut &encoded[..]).map_err(decode_error)
+	}
+
+	fn peek(&mut self) -> Result<&SnapshotChunk, Error> {
+		let chunk = self.read()?;
+		Ok(self.next.insert(chunk))
+	}
+
+	/// Take the pairs of the next chunk if it belongs to the given child trie, or to the top trie
+	/// if `child` is `None`.
+	fn next_entries(&mut self, child: Option<&[u8]>) -> Option<Vec<(Vec<u8>, Vec<u8>)>> {
+		match self.peek() {
+			Ok(SnapshotChunk::Top(_)) if child.is_none() => {},
+			Ok(SnapshotChunk::Child { prefixed_storage_key, .. })
+				if Some(&prefixed_storage_key[..]) == child => {},
+			Ok(_) => return None,
+			Err(e) => {
+				self.error = Some(e);
+				return None
+			},
+		}
+		match self.next.take() {
+			Some(SnapshotChunk::Top(entries)) | Some(SnapshotChunk::Child { entries, .. }) =>
+				Some(entries),
+			_ => None,
+		}
+	}
+}
+
+/// Key-value pairs of a single trie, read from consecutive chunks.
+struct TriePairs<'a, R> {
+	reader: &'a mut ChunkReader<R>,
+	/// Prefixed storage key of the child trie, `None` for the top trie.
+	child: Option<Vec<u8>>,
+	entries: std::vec::IntoIter<(Vec<u8>, Vec<u8>)>,
+}
+
+impl<'a, R: Read> Iterator for TriePairs<'a, R> {
+	type Item = (Vec<u8>, Vec<u8>);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			if let Some((key, value)) = self.entries.next() {
+				self.reader.keys += 1;
+				if self.child.is_none() && well_known_keys::is_default_child_storage_key(&key) {
+					self.reader.child_roots.insert(key.clone(), value.clone());
+				}
+				return Some((key, value))
+			}
+			self.entries = self.reader.next_entries(self.child.as_deref())?.into_iter();
+		}
+	}
+}
+
+/// Read the header of a snapshot file and check the finality proof of its block.
+fn read_snapshot<B, C, R>(
+	client: &C,
+	warp_sync: &dyn WarpSyncProvider<B>,
+	input: R,
+) -> Result<(SnapshotHeader<B>, ChunkReader<R>), Error>
+where
+	B: BlockT,
+	C: HeaderBackend<B>,
+	R: Read,
+{
+	let mut reader = CodecIoReader(input);
+	let mut magic = [0u8; 8];
+	reader.0.read_exact(&mut magic)?;
+	if magic != SNAPSHOT_MAGIC {
+		return Err(Error::Other("Not a snapshot file".into()))
+	}
+	let version = u32::decode(&mut reader).map_err(decode_error)?;
+	if version != SNAPSHOT_VERSION {
+		return Err(Error::Other(format!("Unsupported snapshot version {}", version)))
+	}
+	let snapshot = SnapshotHeader::<B>::decode(&mut reader).map_err(decode_error)?;
+
+	let info = client.info();
+	if snapshot.genesis_hash != info.genesis_hash {
+		return Err(Error::Other("Snapshot belongs to a different chain".into()))
+	}
+	if info.best_hash != info.genesis_hash {
+		return Err(Error::Other("Snapshots can only be imported into an empty database".into()))
+	}
+
+	let hash = snapshot.header.hash();
+	let (mut set_id, mut authorities) = (0, warp_sync.current_authorities());
+	let mut proven = false;
+	for proof in &snapshot.warp_proof {
+		if proven {
+			return Err(Error::Other("Warp proof continues past its target".into()))
+		}
+		let result = warp_sync
+			.verify(&EncodedProof(proof.clone()), set_id, std::mem::take(&mut authorities))
+			.map_err(|e| Error::Other(format!("Invalid warp proof: {}", e)))?;
+		match result {
+			VerificationResult::Partial(next_set_id, next_authorities, _) => {
+				set_id = next_set_id;
+				authorities = next_authorities;
+			},
+			VerificationResult::Complete(_, _, header) if header.hash() == hash => proven = true,
+			VerificationResult::Complete(..) =>
+				return Err(Error::Other("Warp proof does not match the snapshot block".into())),
+		}
+	}
+	if !proven {
+		return Err(Error::Other("Warp proof does not reach the snapshot block".into()))
+	}
+
+	let chunks = ChunkReader {
+		input: reader.0,
+		next: None,
+		error: None,
+		child_roots: BTreeMap::new(),
+		keys: 0,
+	};
+	Ok((snapshot, chunks))
+}
+
+/// Write the state of a snapshot to the database trie by trie, checking it against the state
+/// root of its block.
+fn import_state<B, BA, R>(
+	backend: &BA,
+	state_version: StateVersion,
+	state_root: &B::Hash,
+	reader: &mut ChunkReader<R>,
+) -> Result<(), Error>
+where
+	B: BlockT,
+	BA: sc_client_api::backend::Backend<B>,
+	R: Read,
+{
+	let mut children = BTreeMap::new();
+	while let SnapshotChunk::Child { prefixed_storage_key, root, .. } = reader.peek()? {
+		let (prefixed_storage_key, root) = (prefixed_storage_key.clone(), root.clone());
+		if children.contains_key(&prefixed_storage_key) {
+			return Err(Error::Other("Child trie is split in the snapshot".into()))
+		}
+		let child_info = match prefixed_storage_key
+			.strip_prefix(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX)
+		{
+			Some(storage_key) => ChildInfo::new_default(storage_key),
+			None => return Err(Error::Other("Invalid child storage key in the snapshot".into())),
+		};
+		let mut pairs = TriePairs {
+			reader: &mut *reader,
+			child: Some(prefixed_storage_key.clone()),
+			entries: Vec::new().into_iter(),
+		};
+		let imported = backend.import_state_trie(Some(&child_info), state_version, &mut pairs)?;
+		if let Some(e) = reader.error.take() {
+			return Err(e)
+		}
+		if imported.as_ref() != &root[..] {
+			return Err(Error::Other("Child trie root mismatch in the snapshot".into()))
+		}
+		children.insert(prefixed_storage_key, root);
+	}
+
+	let mut pairs =
+		TriePairs { reader: &mut *reader, child: None, entries: Vec::new().into_iter() };
+	let imported = backend.import_state_trie(None, state_version, &mut pairs)?;
+	if let Some(e) = reader.error.take() {
+		return Err(e)
+	}
+	if imported != *state_root {
+		return Err(Error::Other("State root mismatch in the snapshot".into()))
+	}
+	if !matches!(reader.read()?, SnapshotChunk::End) {
+		return Err(Error::Other("Unexpected chunk after the top trie of the snapshot".into()))
+	}
+	if reader.child_roots != children {
+		return Err(Error::Other("Child tries of the snapshot do not match its state".into()))
+	}
+	Ok(())
+}
+
+/// Import a snapshot file into an empty database.
+///
+/// The state is streamed into the database trie by trie, then the block of the snapshot is
+/// imported as finalized on top of it, so that the node can start syncing from it. The genesis
+/// state must not have been committed to the database.
+pub fn import_snapshot<B, BA, IQ, C>(
+	client: Arc<C>,
+	backend: Arc<BA>,
+	mut import_queue: IQ,
+	warp_sync: Arc<dyn WarpSyncProvider<B>>,
+	input: impl Read,
+) -> Pin<Box<dyn Future<Output = Result<(), Error>> + Send>>
+where
+	C: HeaderBackend<B> + Send + Sync + 'static,
+	B: BlockT,
+	BA: sc_client_api::backend::Backend<B>,
+	IQ: ImportQueue<B> + 'static,
+{
+	struct WaitLink {
+		imported: bool,
+		error: Option<String>,
+	}
+
+	impl<B: BlockT> Link<B> for WaitLink {
+		fn blocks_processed(
+			&mut self,
+			_imported: usize,
+			_count: usize,
+			results: Vec<(Result<BlockImportStatus<NumberFor<B>>, BlockImportError>, B::Hash)>,
+		) {
+			for (result, hash) in results {
+				match result {
+					Ok(_) => self.imported = true,
+					Err(e) => self.error = Some(format!("Failed to import {:?}: {}", hash, e)),
+				}
+			}
+		}
+	}
+
+	let (snapshot, mut reader) = match read_snapshot(&*client, &*warp_sync, input) {
+		Ok(snapshot) => snapshot,
+		Err(e) => return future::ready(Err(e)).boxed(),
+	};
+	let hash = snapshot.header.hash();
+	info!("Importing snapshot at #{} ({})", snapshot.header.number(), hash);
+	if let Err(e) = import_state::<B, _, _>(
+		&*backend,
+		snapshot.state_version,
+		snapshot.header.state_root(),
+		&mut reader,
+	) {
+		return future::ready(Err(e)).boxed()
+	}
+	info!("Imported {} keys", reader.keys);
+
+	let block = IncomingBlock {
+		hash,
+		header: Some(snapshot.header),
+		body: None,
+		indexed_body: None,
+		justifications: snapshot.justifications,
+		origin: None,
+		allow_missing_state: true,
+		skip_execution: true,
+		import_existing: true,
+		state: Some(ImportedState {
+			block: hash,
+			state: KeyValueStates(Vec::new()),
+			written: true,
+		}),
+	};
+	import_queue.service_ref().import_blocks(BlockOrigin::File, vec![block]);
+
+	let mut link = WaitLink { imported: false, error: None };
+	future::poll_fn(move |cx| {
+		import_queue.poll_actions(cx, &mut link);
+		if let Some(e) = link.error.take() {
+			return Poll::Ready(Err(Error::Other(e)))
+		}
+		if link.imported {
+			let info = client.info();
+			info!("🎉 Imported snapshot. Finalized: #{}", info.finalized_number);
+			return Poll::Ready(Ok(()))
+		}
+		Poll::Pending
+	})
+	.boxed()
+}
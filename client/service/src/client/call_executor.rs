@@ -20,7 +20,7 @@ use super::{client::ClientConfig, wasm_override::WasmOverride, wasm_substitutes:
 use sc_client_api::{
 	backend, call_executor::CallExecutor, execution_extensions::ExecutionExtensions, HeaderBackend,
 };
-use sc_executor::{RuntimeVersion, RuntimeVersionOf};
+use sc_executor::{RpcCallExt, RpcCallKind, RuntimeVersion, RuntimeVersionOf};
 use sp_api::{ProofRecorder, StorageTransactionCache};
 use sp_core::traits::{CallContext, CodeExecutor, RuntimeCode};
 use sp_externalities::Extensions;
@@ -126,6 +126,45 @@ where
 			.runtime_version(&mut ext, code)
 			.map_err(|e| sp_blockchain::Error::VersionInvalid(e.to_string()))
 	}
+
+	/// Execute a call with the given `extensions` registered in addition to the ones of the
+	/// execution extensions.
+	fn call_with_extensions(
+		&self,
+		at_hash: Block::Hash,
+		method: &str,
+		call_data: &[u8],
+		context: CallContext,
+		additional_extensions: Extensions,
+	) -> sp_blockchain::Result<Vec<u8>> {
+		let mut changes = OverlayedChanges::default();
+		let at_number =
+			self.backend.blockchain().expect_block_number_from_id(&BlockId::Hash(at_hash))?;
+		let state = self.backend.state_at(at_hash)?;
+
+		let state_runtime_code = sp_state_machine::backend::BackendRuntimeCode::new(&state);
+		let runtime_code =
+			state_runtime_code.runtime_code().map_err(sp_blockchain::Error::RuntimeCode)?;
+
+		let runtime_code = self.check_override(runtime_code, &state, at_hash)?.0;
+
+		let mut extensions = self.execution_extensions.extensions(at_hash, at_number);
+		extensions.merge(additional_extensions);
+
+		let mut sm = StateMachine::new(
+			&state,
+			&mut changes,
+			&self.executor,
+			method,
+			call_data,
+			&mut extensions,
+			&runtime_code,
+			context,
+		)
+		.set_parent_hash(at_hash);
+
+		sm.execute().map_err(Into::into)
+	}
 }
 
 impl<Block: BlockT, B, E> Clone for LocalCallExecutor<Block, B, E>
@@ -164,32 +203,18 @@ where
 		call_data: &[u8],
 		context: CallContext,
 	) -> sp_blockchain::Result<Vec<u8>> {
-		let mut changes = OverlayedChanges::default();
-		let at_number =
-			self.backend.blockchain().expect_block_number_from_id(&BlockId::Hash(at_hash))?;
-		let state = self.backend.state_at(at_hash)?;
-
-		let state_runtime_code = sp_state_machine::backend::BackendRuntimeCode::new(&state);
-		let runtime_code =
-			state_runtime_code.runtime_code().map_err(sp_blockchain::Error::RuntimeCode)?;
-
-		let runtime_code = self.check_override(runtime_code, &state, at_hash)?.0;
-
-		let mut extensions = self.execution_extensions.extensions(at_hash, at_number);
-
-		let mut sm = StateMachine::new(
-			&state,
-			&mut changes,
-			&self.executor,
-			method,
-			call_data,
-			&mut extensions,
-			&runtime_code,
-			context,
-		)
-		.set_parent_hash(at_hash);
+		self.call_with_extensions(at_hash, method, call_data, context, Extensions::new())
+	}
 
-		sm.execute().map_err(Into::into)
+	fn rpc_call(
+		&self,
+		at_hash: Block::Hash,
+		method: &str,
+		call_data: &[u8],
+	) -> sp_blockchain::Result<Vec<u8>> {
+		let mut extensions = Extensions::new();
+		extensions.register(RpcCallExt(RpcCallKind::Call));
+		self.call_with_extensions(at_hash, method, call_data, CallContext::Offchain, extensions)
 	}
 
 	fn contextual_call(
@@ -18,7 +18,7 @@
 
 //! Service configuration.
 
-pub use sc_client_db::{BlocksPruning, Database, DatabaseSource, PruningMode};
+pub use sc_client_db::{BackgroundPruning, BlocksPruning, Database, DatabaseSource, PruningMode};
 pub use sc_executor::{WasmExecutionMethod, WasmtimeInstantiationStrategy};
 pub use sc_network::{
 	config::{
@@ -34,7 +34,10 @@ pub use sc_network::{
 use prometheus_endpoint::Registry;
 use sc_chain_spec::ChainSpec;
 pub use sc_telemetry::TelemetryEndpoints;
-pub use sc_transaction_pool::Options as TransactionPoolOptions;
+pub use sc_transaction_pool::{
+	JournalOptions as TransactionPoolJournalOptions, Options as TransactionPoolOptions,
+	TransactionPoolType,
+};
 use sp_core::crypto::SecretString;
 use std::{
 	io, iter,
@@ -66,8 +69,12 @@ pub struct Configuration {
 	///
 	/// If `None` is given the cache is disabled.
 	pub trie_cache_maximum_size: Option<usize>,
+	/// Keep a flat copy of the finalized state in the database to speed up storage reads.
+	pub flat_state_cache: bool,
 	/// State pruning settings.
 	pub state_pruning: Option<PruningMode>,
+	/// Prune the state on a background worker instead of during block finalization.
+	pub state_pruning_background: Option<BackgroundPruning>,
 	/// Number of blocks to keep in the db.
 	///
 	/// NOTE: only finalized blocks are subject for removal!
@@ -137,6 +144,23 @@ pub struct Configuration {
 	pub informant_output_format: sc_informant::OutputFormat,
 	/// Maximum number of different runtime versions that can be cached.
 	pub runtime_cache_size: u8,
+	/// Maximum size in bytes of the on-disk cache of compiled runtimes.
+	///
+	/// `None` disables the cache. See [`Self::wasmtime_artifact_cache_dir`] for its location.
+	pub wasmtime_artifact_cache_size: Option<u64>,
+	/// Profile the host functions called by the runtime and export their statistics to
+	/// Prometheus.
+	pub host_call_metrics: bool,
+	/// Maximum amount of fuel a runtime call serving an RPC request, e.g. `state_call`, may
+	/// consume.
+	///
+	/// `None` disables the limit. Other runtime calls are never limited.
+	pub rpc_fuel_limit: Option<u64>,
+	/// Maximum amount of fuel a runtime API call made by an RPC method, e.g.
+	/// `payment_queryInfo`, may consume.
+	///
+	/// `None` disables the limit.
+	pub rpc_runtime_api_fuel_limit: Option<u64>,
 }
 
 /// Type for tasks spawned by the executor.
@@ -241,8 +265,15 @@ impl Configuration {
 			state_pruning: self.state_pruning.clone(),
 			source: self.database.clone(),
 			blocks_pruning: self.blocks_pruning,
+			background_pruning: self.state_pruning_background,
+			flat_state: self.flat_state_cache,
 		}
 	}
+
+	/// Returns the directory of the on-disk cache of compiled runtimes.
+	pub fn wasmtime_artifact_cache_dir(&self) -> PathBuf {
+		self.data_path.join("wasmtime-artifacts")
+	}
 }
 
 /// Available RPC methods.
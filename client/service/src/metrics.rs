@@ -20,8 +20,11 @@ use std::time::SystemTime;
 
 use crate::config::Configuration;
 use futures_timer::Delay;
-use prometheus_endpoint::{register, Gauge, GaugeVec, Opts, PrometheusError, Registry, U64};
+use prometheus_endpoint::{
+	register, CounterVec, Gauge, GaugeVec, Opts, PrometheusError, Registry, F64, U64,
+};
 use sc_client_api::{ClientInfo, UsageProvider};
+use sc_executor::RuntimeCallProfile;
 use sc_network::{config::Role, NetworkStatus, NetworkStatusProvider};
 use sc_network_common::sync::{SyncStatus, SyncStatusProvider};
 use sc_telemetry::{telemetry, TelemetryHandle, SUBSTRATE_INFO};
@@ -296,3 +299,61 @@ impl MetricsService {
 		}
 	}
 }
+
+/// Profile the host functions called by all runtime calls and export their statistics to
+/// Prometheus.
+pub(crate) fn register_host_call_metrics(registry: &Registry) -> Result<(), PrometheusError> {
+	let labels = &["method", "function"];
+	let calls = register(
+		CounterVec::<U64>::new(
+			Opts::new(
+				"substrate_executor_host_calls_total",
+				"Number of host function calls made by the runtime",
+			),
+			labels,
+		)?,
+		registry,
+	)?;
+	let read_bytes = register(
+		CounterVec::<U64>::new(
+			Opts::new(
+				"substrate_executor_host_call_read_bytes_total",
+				"Number of bytes host functions read from the runtime memory",
+			),
+			labels,
+		)?,
+		registry,
+	)?;
+	let written_bytes = register(
+		CounterVec::<U64>::new(
+			Opts::new(
+				"substrate_executor_host_call_written_bytes_total",
+				"Number of bytes host functions wrote to the runtime memory",
+			),
+			labels,
+		)?,
+		registry,
+	)?;
+	let seconds = register(
+		CounterVec::<F64>::new(
+			Opts::new(
+				"substrate_executor_host_call_seconds_total",
+				"Wall time spent in host functions called by the runtime",
+			),
+			labels,
+		)?,
+		registry,
+	)?;
+
+	sc_executor::set_host_call_observer(Some(Box::new(move |profile: &RuntimeCallProfile| {
+		for (function, stats) in &profile.host_functions {
+			let labels = [profile.method.as_str(), function];
+			calls.with_label_values(&labels).inc_by(stats.calls);
+			read_bytes.with_label_values(&labels).inc_by(stats.bytes_read);
+			written_bytes.with_label_values(&labels).inc_by(stats.bytes_written);
+			seconds.with_label_values(&labels).inc_by(stats.time.as_secs_f64());
+		}
+	})));
+
+	Ok(())
+}
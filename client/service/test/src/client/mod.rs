@@ -55,6 +55,7 @@ use substrate_test_runtime_client::{
 };
 
 mod db;
+mod snapshot;
 
 const TEST_ENGINE_ID: ConsensusEngineId = *b"TEST";
 
@@ -1264,6 +1265,8 @@ fn doesnt_import_blocks_that_revert_finality() {
 				trie_cache_maximum_size: Some(1 << 20),
 				state_pruning: Some(PruningMode::ArchiveAll),
 				blocks_pruning: BlocksPruning::KeepAll,
+				background_pruning: None,
+				flat_state: false,
 				source: DatabaseSource::RocksDb { path: tmp.path().into(), cache_size: 1024 },
 			},
 			u64::MAX,
@@ -1518,6 +1521,8 @@ fn returns_status_for_pruned_blocks() {
 				trie_cache_maximum_size: Some(1 << 20),
 				state_pruning: Some(PruningMode::blocks_pruning(1)),
 				blocks_pruning: BlocksPruning::KeepFinalized,
+				background_pruning: None,
+				flat_state: false,
 				source: DatabaseSource::RocksDb { path: tmp.path().into(), cache_size: 1024 },
 			},
 			u64::MAX,
@@ -0,0 +1,223 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use futures::executor::block_on;
+use parity_scale_codec::{Decode, Encode};
+use sc_block_builder::BlockBuilderProvider;
+use sc_client_api::{HeaderBackend, StorageProvider};
+use sc_consensus::{BasicQueue, BlockImportParams, ForkChoiceStrategy, Verifier};
+use sc_network_common::sync::warp::{
+	AuthorityList, EncodedProof, SetId, VerificationResult, WarpSyncProvider,
+};
+use sc_service::{
+	chain_ops::{export_snapshot, import_snapshot},
+	Error,
+};
+use sp_consensus::BlockOrigin;
+use sp_core::testing::TaskExecutor;
+use sp_runtime::{traits::Header as HeaderT, Justifications, StateVersion};
+use sp_state_machine::Backend as _;
+use sp_storage::{ChildInfo, StorageKey};
+use std::sync::Arc;
+use substrate_test_runtime_client::{
+	runtime::{Block, Header},
+	Backend, ClientBlockImportExt, DefaultTestClientBuilderExt, ExecutorDispatch, TestClient,
+	TestClientBuilder, TestClientBuilderExt,
+};
+
+/// Proves the finality of a fixed header in a single step.
+struct TestWarpSync(Header);
+
+impl WarpSyncProvider<Block> for TestWarpSync {
+	fn generate(
+		&self,
+		_start: <Block as sp_runtime::traits::Block>::Hash,
+	) -> Result<EncodedProof, Box<dyn std::error::Error + Send + Sync>> {
+		Ok(EncodedProof(self.0.encode()))
+	}
+
+	fn verify(
+		&self,
+		proof: &EncodedProof,
+		set_id: SetId,
+		authorities: AuthorityList,
+	) -> Result<VerificationResult<Block>, Box<dyn std::error::Error + Send + Sync>> {
+		let header = Header::decode(&mut &proof.0[..])?;
+		Ok(VerificationResult::Complete(set_id, authorities, header))
+	}
+
+	fn current_authorities(&self) -> AuthorityList {
+		Vec::new()
+	}
+}
+
+/// Imports every block as finalized.
+struct FinalizingVerifier;
+
+#[async_trait::async_trait]
+impl Verifier<Block> for FinalizingVerifier {
+	async fn verify(
+		&mut self,
+		mut block: BlockImportParams<Block, ()>,
+	) -> Result<BlockImportParams<Block, ()>, String> {
+		block.finalized = true;
+		block.fork_choice = Some(ForkChoiceStrategy::LongestChain);
+		Ok(block)
+	}
+}
+
+fn child_info() -> ChildInfo {
+	ChildInfo::new_default(b"snapshot_child")
+}
+
+fn client_builder() -> TestClientBuilder<ExecutorDispatch, Backend> {
+	TestClientBuilder::new()
+		.add_extra_storage(b":snapshot_key".to_vec(), b"top_value".to_vec())
+		.add_extra_child_storage(&child_info(), b"child_key".to_vec(), b"child_value".to_vec())
+}
+
+/// Client with a few blocks on top of genesis and a snapshot of its best block.
+fn exported_snapshot() -> (Arc<TestClient>, Header, Vec<u8>) {
+	let mut client = client_builder().build();
+	for _ in 0..3 {
+		let block = client.new_block(Default::default()).unwrap().build().unwrap().block;
+		block_on(client.import_as_final(BlockOrigin::Own, block)).unwrap();
+	}
+	let client = Arc::new(client);
+	let header = client.header(client.info().best_hash).unwrap().unwrap();
+
+	let mut snapshot = Vec::new();
+	let exported = export_snapshot::<_, Backend, _>(
+		client.clone(),
+		Arc::new(TestWarpSync(header.clone())),
+		Vec::new(),
+		&mut snapshot,
+	)
+	.unwrap();
+	assert_eq!(exported, header);
+	(client, header, snapshot)
+}
+
+/// Import a snapshot into a fresh client without genesis state.
+fn import(header: Header, snapshot: &[u8]) -> (Arc<TestClient>, Result<(), Error>) {
+	let (client, backend) = client_builder().set_no_genesis().build_with_backend();
+	let client = Arc::new(client);
+	let import_queue = BasicQueue::new(
+		FinalizingVerifier,
+		Box::new(client.clone()),
+		None,
+		&TaskExecutor::new(),
+		None,
+	);
+	let result = block_on(import_snapshot(
+		client.clone(),
+		backend,
+		import_queue,
+		Arc::new(TestWarpSync(header)),
+		snapshot,
+	));
+	(client, result)
+}
+
+/// Start of a snapshot of `header` up to its first chunk.
+fn snapshot_header(client: &TestClient, header: &Header) -> Vec<u8> {
+	let mut snapshot = b"SUBSNAP\0".to_vec();
+	snapshot.extend(1u32.encode());
+	snapshot.extend(
+		(
+			client.info().genesis_hash,
+			header.clone(),
+			None::<Justifications>,
+			vec![header.encode()],
+			StateVersion::V1,
+		)
+			.encode(),
+	);
+	snapshot
+}
+
+fn assert_other_error(result: Result<(), Error>, message: &str) {
+	match result {
+		Err(Error::Other(e)) => assert!(e.contains(message), "unexpected error: {}", e),
+		other => panic!("expected an error containing {:?}, got {:?}", message, other),
+	}
+}
+
+#[test]
+fn snapshot_round_trip_restores_the_state() {
+	let (source, header, snapshot) = exported_snapshot();
+	let hash = header.hash();
+
+	let (client, result) = import(header.clone(), &snapshot);
+	result.unwrap();
+
+	let info = client.info();
+	assert_eq!(info.finalized_hash, hash);
+	assert_eq!(info.best_hash, hash);
+
+	let state = client.state_at(hash).unwrap();
+	let state_version = client.runtime_version_at(hash).unwrap().state_version();
+	assert_eq!(state.storage_root(std::iter::empty(), state_version).0, *header.state_root());
+	assert_eq!(
+		client.storage_pairs(hash, None, None).unwrap().collect::<Vec<_>>(),
+		source.storage_pairs(hash, None, None).unwrap().collect::<Vec<_>>(),
+	);
+	assert_eq!(
+		client.storage(hash, &StorageKey(b":snapshot_key".to_vec())).unwrap().unwrap().0,
+		b"top_value".to_vec(),
+	);
+	assert_eq!(
+		client
+			.child_storage(hash, &child_info(), &StorageKey(b"child_key".to_vec()))
+			.unwrap()
+			.unwrap()
+			.0,
+		b"child_value".to_vec(),
+	);
+}
+
+#[test]
+fn snapshot_with_bad_magic_is_rejected() {
+	let (_, header, mut snapshot) = exported_snapshot();
+	snapshot[0] = b'X';
+
+	let (client, result) = import(header, &snapshot);
+	assert_other_error(result, "Not a snapshot file");
+	assert_eq!(client.info().best_number, 0);
+}
+
+#[test]
+fn snapshot_with_bad_version_is_rejected() {
+	let (_, header, mut snapshot) = exported_snapshot();
+	snapshot[8..12].copy_from_slice(&2u32.encode());
+
+	let (client, result) = import(header, &snapshot);
+	assert_other_error(result, "Unsupported snapshot version 2");
+	assert_eq!(client.info().best_number, 0);
+}
+
+#[test]
+fn snapshot_with_oversized_chunk_is_rejected() {
+	let (source, header, _) = exported_snapshot();
+	let mut snapshot = snapshot_header(&source, &header);
+	snapshot.extend(u32::MAX.encode());
+
+	let (client, result) = import(header, &snapshot);
+	assert_other_error(result, "exceeds the limit");
+	assert_eq!(client.info().best_number, 0);
+}
@@ -240,7 +240,9 @@ fn node_config<
 		keystore: KeystoreConfig::Path { path: root.join("key"), password: None },
 		database: DatabaseSource::RocksDb { path: root.join("db"), cache_size: 128 },
 		trie_cache_maximum_size: Some(16 * 1024 * 1024),
+		flat_state_cache: false,
 		state_pruning: Default::default(),
+		state_pruning_background: None,
 		blocks_pruning: BlocksPruning::KeepFinalized,
 		chain_spec: Box::new((*spec).clone()),
 		wasm_method: Default::default(),
@@ -269,6 +271,10 @@ fn node_config<
 		data_path: root,
 		informant_output_format: Default::default(),
 		runtime_cache_size: 2,
+		wasmtime_artifact_cache_size: None,
+		host_call_metrics: false,
+		rpc_fuel_limit: None,
+		rpc_runtime_api_fuel_limit: None,
 	}
 }
 
@@ -39,18 +39,25 @@
 //!
 //! # Pruning.
 //! See `RefWindow` for pruning algorithm details. `StateDb` prunes on each canonicalization until
-//! pruning constraints are satisfied.
+//! pruning constraints are satisfied. With background pruning enabled canonicalization only grows
+//! the pruning window and the blocks that fall out of it are pruned in batches by
+//! `StateDb::prune_pending`.
+//!
+//! # Archive conversion.
+//! An archive database keeps no pruning journal. `convert_archive_to_constrained` builds the
+//! metadata that turns such a database into a constrained one, given the nodes that become
+//! obsolete for each block of the new pruning window.
 
 mod noncanonical;
 mod pruning;
 #[cfg(test)]
 mod test;
 
-use codec::Codec;
+use codec::{Codec, Encode};
 use log::trace;
-use noncanonical::NonCanonicalOverlay;
+use noncanonical::{NonCanonicalOverlay, LAST_CANONICAL};
 use parking_lot::RwLock;
-use pruning::{HaveBlock, RefWindow};
+use pruning::{HaveBlock, RefWindow, LAST_PRUNED};
 use std::{
 	collections::{hash_map::Entry, HashMap},
 	fmt,
@@ -305,6 +312,7 @@ pub struct StateDbSync<BlockHash: Hash, Key: Hash, D: MetaDb> {
 	pruning: Option<RefWindow<BlockHash, Key, D>>,
 	pinned: HashMap<BlockHash, u32>,
 	ref_counting: bool,
+	background_pruning: bool,
 }
 
 impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
@@ -322,7 +330,14 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
 			PruningMode::ArchiveAll | PruningMode::ArchiveCanonical => None,
 		};
 
-		Ok(StateDbSync { mode, non_canonical, pruning, pinned: Default::default(), ref_counting })
+		Ok(StateDbSync {
+			mode,
+			non_canonical,
+			pruning,
+			pinned: Default::default(),
+			ref_counting,
+			background_pruning: false,
+		})
 	}
 
 	fn insert_block(
@@ -361,7 +376,9 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
 		if let Some(ref mut pruning) = self.pruning {
 			pruning.note_canonical(hash, number, &mut commit)?;
 		}
-		self.prune(&mut commit)?;
+		if !self.background_pruning {
+			self.prune(&mut commit, None)?;
+		}
 		Ok(commit)
 	}
 
@@ -407,12 +424,21 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
 		}
 	}
 
-	fn prune(&mut self, commit: &mut CommitSet<Key>) -> Result<(), Error<D::Error>> {
+	/// Prune blocks until the pruning constraints are satisfied, but no more than `limit` blocks
+	/// if given. Returns the number of pruned blocks.
+	fn prune(
+		&mut self,
+		commit: &mut CommitSet<Key>,
+		limit: Option<u64>,
+	) -> Result<u64, Error<D::Error>> {
+		let mut pruned = 0;
 		if let (&mut Some(ref mut pruning), PruningMode::Constrained(constraints)) =
 			(&mut self.pruning, &self.mode)
 		{
 			loop {
-				if pruning.window_size() <= constraints.max_blocks.unwrap_or(0) as u64 {
+				if pruning.window_size() <= constraints.max_blocks.unwrap_or(0) as u64 ||
+					limit.map_or(false, |limit| pruned >= limit)
+				{
 					break
 				}
 
@@ -431,9 +457,10 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDbSync<BlockHash, Key, D> {
 					Err(Error::StateDb(StateDbError::BlockUnavailable)) => break,
 					res => res?,
 				}
+				pruned += 1;
 			}
 		}
-		Ok(())
+		Ok(pruned)
 	}
 
 	/// Revert all non-canonical blocks with the best block number.
@@ -595,6 +622,33 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDb<BlockHash, Key, D> {
 		self.db.write().canonicalize_block(hash)
 	}
 
+	/// Enable or disable background pruning.
+	///
+	/// When enabled, `canonicalize_block` only adds the block to the pruning window and the
+	/// blocks that fall out of the window are kept until `prune_pending` is called.
+	pub fn set_background_pruning(&self, enabled: bool) {
+		self.db.write().background_pruning = enabled;
+	}
+
+	/// Prune at most `max_blocks` blocks that fell out of the pruning window and pass the
+	/// resulting commit to `apply`. Returns the number of pruned blocks.
+	///
+	/// The state database stays locked until `apply` returns, so the pruning commit reaches the
+	/// backing database before any commit produced afterwards. If `apply` fails, the in-memory
+	/// state is ahead of the database and `reset` must be called.
+	pub fn prune_pending<F>(&self, max_blocks: u64, apply: F) -> Result<u64, Error<D::Error>>
+	where
+		F: FnOnce(CommitSet<Key>) -> Result<(), D::Error>,
+	{
+		let mut state_db = self.db.write();
+		let mut commit = CommitSet::default();
+		let pruned = state_db.prune(&mut commit, Some(max_blocks))?;
+		if pruned > 0 {
+			apply(commit).map_err(Error::Db)?;
+		}
+		Ok(pruned)
+	}
+
 	/// Prevents pruning of specified block and its descendants.
 	/// `hint` used for further checking if the given block exists
 	pub fn pin<F>(&self, hash: &BlockHash, number: u64, hint: F) -> Result<(), PinError>
@@ -655,7 +709,9 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> StateDb<BlockHash, Key, D> {
 	/// Reset in-memory changes to the last disk-backed state.
 	pub fn reset(&self, db: D) -> Result<(), Error<D::Error>> {
 		let mut state_db = self.db.write();
+		let background_pruning = state_db.background_pruning;
 		*state_db = StateDbSync::new(state_db.mode.clone(), state_db.ref_counting, db)?;
+		state_db.background_pruning = background_pruning;
 		Ok(())
 	}
 }
@@ -702,18 +758,71 @@ fn choose_pruning_mode(
 	}
 }
 
+/// Build the commit that converts an archive state database into a constrained one.
+///
+/// `last_canonical` becomes the last canonicalized block. `window` lists the blocks of the new
+/// pruning window, oldest first and ending with `last_canonical`, each with the nodes to delete
+/// once the block is pruned. States older than the first block of the window are not tracked
+/// and must be removed by the caller.
+pub fn convert_archive_to_constrained<BlockHash: Hash, Key: Hash, D: MetaDb>(
+	db: &D,
+	last_canonical: (BlockHash, u64),
+	window: Vec<(BlockHash, Vec<Key>)>,
+) -> Result<CommitSet<Key>, Error<D::Error>> {
+	match fetch_stored_pruning_mode(db)? {
+		Some(mode) if mode.is_archive() => {},
+		stored =>
+			return Err(StateDbError::Metadata(format!(
+				"Expected an archive database, found {:?}",
+				stored
+			))
+			.into()),
+	}
+	let (last_hash, last_number) = last_canonical;
+	if window.len() as u64 > last_number ||
+		window.last().map_or(false, |(hash, _)| *hash != last_hash)
+	{
+		return Err(StateDbError::InvalidBlockNumber.into())
+	}
+
+	let mut commit = CommitSet::default();
+	let last_pruned = last_number - window.len() as u64;
+	for (number, (hash, deleted)) in (last_pruned + 1..).zip(window) {
+		pruning::journal_block(number, hash, deleted, &mut commit);
+	}
+	commit.meta.inserted.push((
+		to_meta_key(PRUNING_MODE, &()),
+		PruningMode::Constrained(Default::default()).id().to_owned(),
+	));
+	commit
+		.meta
+		.inserted
+		.push((to_meta_key(LAST_CANONICAL, &()), (last_hash, last_number).encode()));
+	commit.meta.inserted.push((to_meta_key(LAST_PRUNED, &()), last_pruned.encode()));
+	Ok(commit)
+}
+
 #[cfg(test)]
 mod tests {
 	use crate::{
-		test::{make_changeset, make_db, TestDb},
+		convert_archive_to_constrained,
+		test::{make_changeset, make_commit, make_db, TestDb},
 		Constraints, Error, IsPruned, PruningMode, StateDb, StateDbError,
 	};
 	use sp_core::H256;
 
 	fn make_test_db(settings: PruningMode) -> (TestDb, StateDb<H256, H256, TestDb>) {
+		make_test_db_with_background_pruning(settings, false)
+	}
+
+	fn make_test_db_with_background_pruning(
+		settings: PruningMode,
+		background_pruning: bool,
+	) -> (TestDb, StateDb<H256, H256, TestDb>) {
 		let mut db = make_db(&[91, 921, 922, 93, 94]);
 		let (state_db_init, state_db) =
 			StateDb::open(db.clone(), Some(settings), false, true).unwrap();
+		state_db.set_background_pruning(background_pruning);
 		db.commit(&state_db_init);
 
 		db.commit(
@@ -847,6 +956,80 @@ mod tests {
 		assert!(db.data_eq(&make_db(&[1, 21, 3, 921, 922, 93, 94])));
 	}
 
+	#[test]
+	fn background_pruning_is_deferred() {
+		let (mut db, sdb) = make_test_db_with_background_pruning(
+			PruningMode::Constrained(Constraints { max_blocks: Some(0) }),
+			true,
+		);
+		assert!(db.data_eq(&make_db(&[1, 21, 3, 91, 921, 922, 93, 94])));
+		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(1), 1), IsPruned::NotPruned);
+
+		let pruned = sdb
+			.prune_pending(2, |commit| {
+				db.commit(&commit);
+				Ok(())
+			})
+			.unwrap();
+		assert_eq!(pruned, 2);
+		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(1), 1), IsPruned::Pruned);
+		assert_ne!(sdb.is_pruned(&H256::from_low_u64_be(3), 3), IsPruned::Pruned);
+		assert!(db.data_eq(&make_db(&[21, 3, 922, 93, 94])));
+
+		let pruned = sdb
+			.prune_pending(2, |commit| {
+				db.commit(&commit);
+				Ok(())
+			})
+			.unwrap();
+		assert_eq!(pruned, 1);
+		assert!(db.data_eq(&make_db(&[21, 3, 922, 94])));
+
+		// nothing left to prune, the commit is not applied
+		assert_eq!(sdb.prune_pending(2, |_| panic!("nothing to apply")).unwrap(), 0);
+	}
+
+	#[test]
+	fn converts_archive_to_constrained() {
+		let (mut db, _) = make_test_db(PruningMode::ArchiveCanonical);
+		// keep the state of block 3 and delete the older ones
+		db.commit(&make_commit(&[], &[91, 921, 1]));
+		db.commit(
+			&convert_archive_to_constrained(
+				&db,
+				(H256::from_low_u64_be(3), 3),
+				vec![(H256::from_low_u64_be(3), vec![H256::from_low_u64_be(93)])],
+			)
+			.unwrap(),
+		);
+
+		let (_, sdb) = StateDb::<H256, H256, TestDb>::open(
+			db.clone(),
+			Some(PruningMode::Constrained(Constraints { max_blocks: Some(1) })),
+			false,
+			false,
+		)
+		.unwrap();
+		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(21), 2), IsPruned::Pruned);
+		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(3), 3), IsPruned::NotPruned);
+
+		db.commit(&sdb.canonicalize_block(&H256::from_low_u64_be(4)).unwrap());
+		assert!(db.data_eq(&make_db(&[21, 3, 4, 922, 94])));
+		assert_eq!(sdb.is_pruned(&H256::from_low_u64_be(3), 3), IsPruned::Pruned);
+		assert_ne!(sdb.is_pruned(&H256::from_low_u64_be(4), 4), IsPruned::Pruned);
+	}
+
+	#[test]
+	fn only_converts_archive() {
+		let (db, _) = make_test_db(PruningMode::Constrained(Constraints { max_blocks: Some(1) }));
+		assert!(convert_archive_to_constrained::<H256, H256, _>(
+			&db,
+			(H256::from_low_u64_be(3), 3),
+			vec![]
+		)
+		.is_err());
+	}
+
 	#[test]
 	fn detects_incompatible_mode() {
 		let mut db = make_db(&[]);
@@ -425,6 +425,19 @@ impl<BlockHash: Hash, Key: Hash, D: MetaDb> RefWindow<BlockHash, Key, D> {
 	}
 }
 
+/// Add a pruning journal record for a block that enters the window without being noted as
+/// canonical, e.g. when an archive database is converted. `deleted` are the nodes removed once
+/// the block is pruned.
+pub(crate) fn journal_block<BlockHash: Hash, Key: Hash>(
+	number: u64,
+	hash: BlockHash,
+	deleted: Vec<Key>,
+	commit: &mut CommitSet<Key>,
+) {
+	let journal_record = JournalRecord { hash, inserted: Vec::new(), deleted };
+	commit.meta.inserted.push((to_journal_key(number), journal_record.encode()));
+}
+
 #[cfg(test)]
 mod tests {
 	use super::{to_journal_key, DeathRowQueue, HaveBlock, JournalRecord, RefWindow, LAST_PRUNED};
@@ -52,7 +52,7 @@ mod metrics;
 pub use sp_statement_store::{Error, StatementStore, MAX_TOPICS};
 
 use metrics::MetricsLink as PrometheusMetrics;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use prometheus_endpoint::Registry as PrometheusRegistry;
 use sc_keystore::LocalKeystore;
 use sp_api::ProvideRuntimeApi;
@@ -64,7 +64,7 @@ use sp_statement_store::{
 		InvalidStatement, StatementSource, StatementStoreExt, ValidStatement, ValidateStatement,
 	},
 	AccountId, BlockHash, Channel, DecryptionKey, Hash, NetworkPriority, Proof, Result, Statement,
-	SubmitResult, Topic,
+	StatementStream, SubmitResult, Topic, TopicFilter,
 };
 use std::{
 	collections::{BTreeMap, HashMap, HashSet},
@@ -80,6 +80,9 @@ const DEFAULT_PURGE_AFTER_SEC: u64 = 2 * 24 * 60 * 60; //48h
 const DEFAULT_MAX_TOTAL_STATEMENTS: usize = 8192;
 const DEFAULT_MAX_TOTAL_SIZE: usize = 64 * 1024 * 1024;
 
+const MAX_SUBSCRIBERS: usize = 128;
+const SUBSCRIPTION_BUFFER_SIZE: usize = 1024;
+
 const MAINTENANCE_PERIOD: std::time::Duration = std::time::Duration::from_secs(30);
 
 mod col {
@@ -161,6 +164,19 @@ struct Index {
 	total_size: usize,
 }
 
+/// A subscriber to the statements accepted by the store.
+struct Subscriber {
+	topic_filter: TopicFilter,
+	dest: Option<DecryptionKey>,
+	sender: futures::channel::mpsc::Sender<Statement>,
+}
+
+impl Subscriber {
+	fn matches(&self, statement: &Statement) -> bool {
+		self.dest == statement.decryption_key() && self.topic_filter.matches(statement)
+	}
+}
+
 struct ClientWrapper<Block, Client> {
 	client: Arc<Client>,
 	_block: std::marker::PhantomData<Block>,
@@ -206,6 +222,7 @@ pub struct Store {
 	// Used for testing
 	time_override: Option<u64>,
 	metrics: PrometheusMetrics,
+	subscribers: Mutex<Vec<Subscriber>>,
 }
 
 enum IndexQuery {
@@ -573,6 +590,7 @@ impl Store {
 			keystore,
 			time_override: None,
 			metrics: PrometheusMetrics::new(prometheus),
+			subscribers: Default::default(),
 		};
 		store.populate()?;
 		Ok(store)
@@ -689,6 +707,28 @@ impl Store {
 		);
 	}
 
+	/// Send a newly accepted statement to the matching subscribers.
+	///
+	/// Subscribers that are no longer listening or that don't keep up with the statements sent
+	/// to them are removed.
+	fn notify_subscribers(&self, statement: &Statement) {
+		self.subscribers.lock().retain_mut(|subscriber| {
+			if subscriber.matches(statement) {
+				match subscriber.sender.try_send(statement.clone()) {
+					Ok(()) => true,
+					Err(e) => {
+						if e.is_full() {
+							log::debug!(target: LOG_TARGET, "Dropping slow statement subscriber");
+						}
+						false
+					},
+				}
+			} else {
+				!subscriber.sender.is_closed()
+			}
+		});
+	}
+
 	fn timestamp(&self) -> u64 {
 		self.time_override.unwrap_or_else(|| {
 			std::time::SystemTime::now()
@@ -897,6 +937,7 @@ impl StatementStore for Store {
 			}
 		} // Release index lock
 		self.metrics.report(|metrics| metrics.submitted_statements.inc());
+		self.notify_subscribers(&statement);
 		let network_priority = NetworkPriority::High;
 		log::trace!(target: LOG_TARGET, "Statement submitted: {:?}", HexDisplay::from(&hash));
 		SubmitResult::New(network_priority)
@@ -925,17 +966,38 @@ impl StatementStore for Store {
 		}
 		Ok(())
 	}
+
+	/// Subscribe to the statements accepted by the store after this call.
+	///
+	/// At most `MAX_SUBSCRIBERS` subscriptions are served at the same time. A subscription is
+	/// closed if more than `SUBSCRIPTION_BUFFER_SIZE` statements are waiting to be received.
+	fn subscribe(
+		&self,
+		topic_filter: TopicFilter,
+		dest: Option<DecryptionKey>,
+	) -> Result<StatementStream> {
+		let mut subscribers = self.subscribers.lock();
+		subscribers.retain(|subscriber| !subscriber.sender.is_closed());
+		if subscribers.len() >= MAX_SUBSCRIBERS {
+			return Err(Error::TooManySubscribers)
+		}
+
+		let (sender, receiver) = futures::channel::mpsc::channel(SUBSCRIPTION_BUFFER_SIZE);
+		subscribers.push(Subscriber { topic_filter, dest, sender });
+		Ok(Box::pin(receiver))
+	}
 }
 
 #[cfg(test)]
 mod tests {
 	use crate::Store;
+	use futures::{FutureExt, StreamExt};
 	use sc_keystore::Keystore;
 	use sp_core::Pair;
 	use sp_statement_store::{
 		runtime_api::{InvalidStatement, ValidStatement, ValidateStatement},
 		AccountId, Channel, DecryptionKey, NetworkPriority, Proof, SignatureVerificationResult,
-		Statement, StatementSource, StatementStore, SubmitResult, Topic,
+		Statement, StatementSource, StatementStore, SubmitResult, Topic, TopicFilter,
 	};
 
 	type Extrinsic = sp_runtime::OpaqueExtrinsic;
@@ -1293,4 +1355,92 @@ mod tests {
 		let posted_clear = store.posted_clear(&[], public.into()).unwrap();
 		assert_eq!(posted_clear, vec![plain]);
 	}
+
+	#[test]
+	fn subscribe_reports_matching_statements() {
+		let (store, _temp) = test_store();
+		let mut all = store.subscribe(TopicFilter::MatchAll(vec![]), None).unwrap();
+		let mut match_all =
+			store.subscribe(TopicFilter::MatchAll(vec![topic(0), topic(1)]), None).unwrap();
+		let mut match_any =
+			store.subscribe(TopicFilter::MatchAny(vec![topic(1), topic(2)]), None).unwrap();
+		let mut posted = store
+			.subscribe(TopicFilter::MatchAll(vec![topic(0)]), Some(dec_key(2)))
+			.unwrap();
+
+		let statement0 = signed_statement(0);
+		let statement1 = signed_statement_with_topics(1, &[topic(0)], None);
+		let statement2 = signed_statement_with_topics(2, &[topic(0), topic(1)], Some(dec_key(2)));
+		let statement3 = signed_statement_with_topics(3, &[topic(0), topic(1), topic(2)], None);
+		let statement4 = signed_statement_with_topics(4, &[topic(2)], None);
+		for s in [&statement0, &statement1, &statement2, &statement3, &statement4] {
+			assert_eq!(
+				store.submit(s.clone(), StatementSource::Network),
+				SubmitResult::New(NetworkPriority::High)
+			);
+		}
+		// Known statements are not reported again.
+		assert_eq!(store.submit(statement0.clone(), StatementSource::Network), SubmitResult::Known);
+
+		let collect = |stream: &mut sp_statement_store::StatementStream| {
+			let mut result = Vec::new();
+			while let Some(Some(statement)) = stream.next().now_or_never() {
+				result.push(statement);
+			}
+			result
+		};
+		assert_eq!(
+			collect(&mut all),
+			vec![statement0.clone(), statement1.clone(), statement3.clone(), statement4.clone()]
+		);
+		assert_eq!(collect(&mut match_all), vec![statement3.clone()]);
+		assert_eq!(collect(&mut match_any), vec![statement3.clone(), statement4.clone()]);
+		assert_eq!(collect(&mut posted), vec![statement2.clone()]);
+
+		// Dropped subscriptions are removed from the store.
+		drop((all, match_all, match_any));
+		let statement5 = signed_statement_with_topics(5, &[topic(0)], Some(dec_key(2)));
+		store.submit(statement5.clone(), StatementSource::Network);
+		assert_eq!(store.subscribers.lock().len(), 1);
+		assert_eq!(collect(&mut posted), vec![statement5]);
+	}
+
+	#[test]
+	fn subscribers_are_limited() {
+		let (store, _temp) = test_store();
+		let mut subscriptions = (0..crate::MAX_SUBSCRIBERS)
+			.map(|_| store.subscribe(TopicFilter::MatchAll(vec![]), None).unwrap())
+			.collect::<Vec<_>>();
+		assert!(matches!(
+			store.subscribe(TopicFilter::MatchAll(vec![]), None),
+			Err(crate::Error::TooManySubscribers)
+		));
+
+		// Closed subscriptions make room for new ones.
+		subscriptions.pop();
+		assert!(store.subscribe(TopicFilter::MatchAll(vec![]), None).is_ok());
+	}
+
+	#[test]
+	fn slow_subscribers_are_dropped() {
+		let (store, _temp) = test_store();
+		let mut slow = store.subscribe(TopicFilter::MatchAll(vec![]), None).unwrap();
+		// The channel has room for one message per sender in addition to the buffer.
+		let capacity = crate::SUBSCRIPTION_BUFFER_SIZE + 1;
+		for i in 0..capacity {
+			store.notify_subscribers(&signed_statement(i as u8));
+		}
+		assert_eq!(store.subscribers.lock().len(), 1);
+
+		store.notify_subscribers(&signed_statement(0));
+		assert!(store.subscribers.lock().is_empty());
+
+		// The buffered statements are still received before the subscription ends.
+		let mut received = 0;
+		while let Some(Some(_)) = slow.next().now_or_never() {
+			received += 1;
+		}
+		assert_eq!(received, capacity);
+		assert_eq!(slow.next().now_or_never(), Some(None));
+	}
 }
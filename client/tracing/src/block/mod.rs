@@ -37,7 +37,9 @@ use sc_client_api::BlockBackend;
 use sp_api::{Core, Encode, Metadata, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_core::hexdisplay::HexDisplay;
-use sp_rpc::tracing::{BlockTrace, Span, TraceBlockResponse};
+use sp_rpc::tracing::{
+	BlockHostCalls, BlockTrace, HostFunctionCalls, RuntimeCallHostCalls, Span, TraceBlockResponse,
+};
 use sp_runtime::{
 	generic::BlockId,
 	traits::{Block as BlockT, Header},
@@ -194,23 +196,7 @@ where
 	/// prefixes in `Self::storage_keys`.
 	pub fn trace_block(&self) -> TraceBlockResult<TraceBlockResponse> {
 		tracing::debug!(target: "state_tracing", "Tracing block: {}", self.block);
-		// Prepare the block
-		let mut header = self
-			.client
-			.header(self.block)
-			.map_err(Error::InvalidBlockId)?
-			.ok_or_else(|| Error::MissingBlockComponent("Header not found".to_string()))?;
-		let extrinsics = self
-			.client
-			.block_body(self.block)
-			.map_err(Error::InvalidBlockId)?
-			.ok_or_else(|| Error::MissingBlockComponent("Extrinsics not found".to_string()))?;
-		tracing::debug!(target: "state_tracing", "Found {} extrinsics", extrinsics.len());
-		let parent_hash = *header.parent_hash();
-		// Remove all `Seal`s as they are added by the consensus engines after building the block.
-		// On import they are normally removed by the consensus engine.
-		header.digest_mut().logs.retain(|d| d.as_seal().is_none());
-		let block = Block::new(header, extrinsics);
+		let (parent_hash, block) = self.prepare_block()?;
 
 		let targets = if let Some(t) = &self.targets { t } else { DEFAULT_TARGETS };
 		let block_subscriber = BlockSubscriber::new(targets);
@@ -277,6 +263,65 @@ where
 			events,
 		}))
 	}
+
+	/// Execute block and record the host functions called by every runtime call.
+	///
+	/// Only runtime calls executed by the wasm executor are recorded.
+	pub fn profile_host_calls(&self) -> TraceBlockResult<BlockHostCalls> {
+		tracing::debug!(target: "state_tracing", "Profiling host calls of block: {}", self.block);
+		let (parent_hash, block) = self.prepare_block()?;
+
+		let (result, profiles) = sc_executor::profile_host_calls(|| {
+			self.client.runtime_api().execute_block(parent_hash, block)
+		});
+		if let Err(e) = result {
+			return Err(Error::Dispatch(format!("Failed to execute block: {}", e)))
+		}
+
+		let runtime_calls = profiles
+			.into_iter()
+			.map(|profile| RuntimeCallHostCalls {
+				method: profile.method,
+				host_functions: profile
+					.host_functions
+					.into_iter()
+					.map(|(name, stats)| HostFunctionCalls {
+						name: name.to_string(),
+						calls: stats.calls,
+						bytes_read: stats.bytes_read,
+						bytes_written: stats.bytes_written,
+						time_ns: stats.time.as_nanos().try_into().unwrap_or(u64::MAX),
+					})
+					.collect(),
+			})
+			.collect();
+
+		Ok(BlockHostCalls {
+			block_hash: block_id_as_string(BlockId::<Block>::Hash(self.block)),
+			parent_hash: block_id_as_string(BlockId::<Block>::Hash(parent_hash)),
+			runtime_calls,
+		})
+	}
+
+	/// Fetch the block to execute, returning it together with the hash of its parent.
+	fn prepare_block(&self) -> TraceBlockResult<(Block::Hash, Block)> {
+		let mut header = self
+			.client
+			.header(self.block)
+			.map_err(Error::InvalidBlockId)?
+			.ok_or_else(|| Error::MissingBlockComponent("Header not found".to_string()))?;
+		let extrinsics = self
+			.client
+			.block_body(self.block)
+			.map_err(Error::InvalidBlockId)?
+			.ok_or_else(|| Error::MissingBlockComponent("Extrinsics not found".to_string()))?;
+		tracing::debug!(target: "state_tracing", "Found {} extrinsics", extrinsics.len());
+		let parent_hash = *header.parent_hash();
+		// Remove all `Seal`s as they are added by the consensus engines after building the block.
+		// On import they are normally removed by the consensus engine.
+		header.digest_mut().logs.retain(|d| d.as_seal().is_none());
+		Ok((parent_hash, Block::new(header, extrinsics)))
+	}
 }
 
 fn event_values_filter(event: &TraceEvent, filter_kind: &str, values: &str) -> bool {
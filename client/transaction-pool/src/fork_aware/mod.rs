@@ -0,0 +1,569 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Fork-aware transaction pool.
+//!
+//! Unlike [`BasicPool`](crate::BasicPool), which keeps a single ready/future graph following the
+//! best block, this pool keeps a separate *view* for every active fork tip. Each view is a
+//! [`graph::Pool`] holding the transactions validated against the state of its block.
+//!
+//! Incoming transactions are validated against all of the views and accepted if any of them
+//! considers them valid. When a new block is imported, a view for it is built by re-validating
+//! all of the known transactions (except the ones included on its fork) and the view of its
+//! parent is dropped. Views for blocks that never became the best block, e.g. when a block is
+//! authored on top of another fork, are built on demand from the view of their nearest ancestor.
+//! On finalization the views on the forks that can no longer be finalized are dropped as well.
+//!
+//! Statuses reported by the views are merged into a single stream per watched transaction, see
+//! the `multi_view_listener` module for details.
+
+mod multi_view_listener;
+mod view;
+mod view_store;
+
+use self::view_store::ViewStore;
+use crate::{
+	api::FullChainApi,
+	enactment_state::{EnactmentAction, EnactmentState},
+	error,
+	graph::{self, ChainApi, ExtrinsicHash, IsValidator},
+	journal::Journal,
+	metrics::MetricsLink as PrometheusMetrics,
+	PolledIterator, ReadyIteratorFor, ReadyPoll, LOG_TARGET,
+};
+use async_trait::async_trait;
+use futures::{channel::mpsc::channel, prelude::*};
+use parking_lot::Mutex;
+use prometheus_endpoint::Registry as PrometheusRegistry;
+use sc_transaction_pool_api::{
+	error::Error as TxPoolError, ChainEvent, ImportNotificationStream, MaintainedTransactionPool,
+	PoolFuture, PoolStatus, TransactionFor, TransactionPool, TransactionSource,
+	TransactionStatusStreamFor, TxHash,
+};
+use sp_blockchain::{HashAndNumber, TreeRoute};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, Extrinsic, NumberFor},
+};
+use std::{collections::HashMap, pin::Pin, sync::Arc};
+
+/// A fork-aware transaction pool for a full node.
+pub type FullForkAwarePool<Block, Client> = ForkAwarePool<FullChainApi<Client, Block>, Block>;
+
+/// Transaction pool keeping a separate view for every active fork tip.
+pub struct ForkAwarePool<PoolApi, Block>
+where
+	Block: BlockT,
+	PoolApi: graph::ChainApi<Block = Block>,
+{
+	api: Arc<PoolApi>,
+	view_store: Arc<ViewStore<PoolApi>>,
+	ready_poll: Arc<Mutex<ReadyPoll<ReadyIteratorFor<PoolApi>, Block>>>,
+	metrics: PrometheusMetrics,
+	enactment_state: Arc<Mutex<EnactmentState<Block>>>,
+	journal: Option<Journal<PoolApi>>,
+}
+
+impl<PoolApi, Block> ForkAwarePool<PoolApi, Block>
+where
+	Block: BlockT,
+	PoolApi: graph::ChainApi<Block = Block> + 'static,
+{
+	/// Create new fork-aware transaction pool with provided api, for tests.
+	pub fn new_test(
+		pool_api: Arc<PoolApi>,
+		best_block_hash: Block::Hash,
+		finalized_hash: Block::Hash,
+	) -> Self {
+		let best_block_number = pool_api
+			.block_id_to_number(&BlockId::Hash(best_block_hash))
+			.ok()
+			.flatten()
+			.unwrap_or_default();
+		Self::new(
+			Default::default(),
+			true.into(),
+			pool_api,
+			None,
+			HashAndNumber { hash: best_block_hash, number: best_block_number },
+			finalized_hash,
+		)
+	}
+
+	/// Create new fork-aware transaction pool with provided api.
+	///
+	/// The initial view is built at the given best block.
+	pub fn new(
+		options: graph::Options,
+		is_validator: IsValidator,
+		pool_api: Arc<PoolApi>,
+		prometheus: Option<&PrometheusRegistry>,
+		best_block: HashAndNumber<Block>,
+		finalized_hash: Block::Hash,
+	) -> Self {
+		Self {
+			view_store: Arc::new(ViewStore::new(
+				pool_api.clone(),
+				options,
+				is_validator,
+				best_block.clone(),
+			)),
+			api: pool_api,
+			ready_poll: Arc::new(Mutex::new(ReadyPoll::new(best_block.number))),
+			metrics: PrometheusMetrics::new(prometheus),
+			enactment_state: Arc::new(Mutex::new(EnactmentState::new(
+				best_block.hash,
+				finalized_hash,
+			))),
+			journal: None,
+		}
+	}
+
+	/// Get access to the underlying api
+	pub fn api(&self) -> &PoolApi {
+		&self.api
+	}
+
+	/// Returns the hashes of the blocks the pool currently keeps a view for.
+	pub fn views(&self) -> Vec<Block::Hash> {
+		self.view_store.views().iter().map(|view| view.at.hash).collect()
+	}
+
+	/// Returns the status of the view kept for the given block, if there is one.
+	pub fn view_status(&self, at: &Block::Hash) -> Option<PoolStatus> {
+		self.view_store.view_at(at).map(|view| view.pool.validated_pool().status())
+	}
+
+	/// Returns the hashes of the extrinsics of the given block.
+	async fn block_extrinsics(
+		&self,
+		at: Block::Hash,
+	) -> Vec<(ExtrinsicHash<PoolApi>, graph::ExtrinsicFor<PoolApi>)> {
+		self.api
+			.block_body(at)
+			.await
+			.unwrap_or_else(|e| {
+				log::warn!(target: LOG_TARGET, "Failed to fetch block body: {}", e);
+				None
+			})
+			.unwrap_or_default()
+			.into_iter()
+			.map(|tx| (self.api.hash_and_length(&tx).0, tx))
+			.collect()
+	}
+
+	/// Handles enactment and retraction of blocks.
+	///
+	/// Watchers are notified about the retracted and enacted blocks, transactions from the
+	/// retracted blocks are kept for re-validation, and a view for the new best block is built.
+	async fn handle_enactment(&self, tree_route: TreeRoute<Block>) {
+		log::trace!(target: LOG_TARGET, "handle_enactment tree_route: {tree_route:?}");
+
+		let at = match tree_route.last() {
+			Some(at) => at.clone(),
+			None => {
+				log::warn!(
+					target: LOG_TARGET,
+					"Skipping ChainEvent - no last block in tree route {:?}",
+					tree_route,
+				);
+				return
+			},
+		};
+
+		for retracted in tree_route.retracted() {
+			self.view_store.note_retracted(&retracted.hash);
+
+			let resubmitted = self.view_store.insert_retracted(
+				self.block_extrinsics(retracted.hash)
+					.await
+					.into_iter()
+					.map(|(_, tx)| tx)
+					.filter(|tx| tx.is_signed().unwrap_or(true)),
+			);
+			self.metrics.report(|metrics| {
+				metrics.block_transactions_resubmitted.inc_by(resubmitted as u64)
+			});
+		}
+
+		for enacted in tree_route.enacted() {
+			let hashes = self
+				.block_extrinsics(enacted.hash)
+				.await
+				.into_iter()
+				.map(|(hash, _)| hash)
+				.collect();
+			let pruned = self.view_store.note_included(enacted.clone(), hashes);
+			self.metrics
+				.report(|metrics| metrics.block_transactions_pruned.inc_by(pruned as u64));
+		}
+
+		let view = match self.view_store.view_at(&at.hash) {
+			Some(view) => view,
+			None => self.view_store.build_view(at.clone()).await,
+		};
+		self.view_store.insert_view(view.clone(), true);
+
+		// views of the blocks the new best block was built on top of are not fork tips anymore
+		self.view_store.remove_view(&tree_route.common_block().hash);
+		for enacted in tree_route.enacted().iter().filter(|enacted| enacted.hash != at.hash) {
+			self.view_store.remove_view(&enacted.hash);
+		}
+
+		self.ready_poll
+			.lock()
+			.trigger(at.number, move || Box::new(view.pool.validated_pool().ready()));
+	}
+
+	/// Notifies the watchers about the finalized blocks and drops the views of the forks that
+	/// can't be finalized anymore.
+	fn handle_finalized(&self, hash: Block::Hash, tree_route: &[Block::Hash]) {
+		let finalized = tree_route.iter().copied().chain(std::iter::once(hash)).collect::<Vec<_>>();
+		self.view_store.note_finalized(&finalized);
+	}
+}
+
+impl<PoolApi, Block> TransactionPool for ForkAwarePool<PoolApi, Block>
+where
+	Block: BlockT,
+	PoolApi: 'static + graph::ChainApi<Block = Block>,
+{
+	type Block = PoolApi::Block;
+	type Hash = graph::ExtrinsicHash<PoolApi>;
+	type InPoolTransaction = graph::base_pool::Transaction<TxHash<Self>, TransactionFor<Self>>;
+	type Error = PoolApi::Error;
+
+	/// Validates the transactions against all of the views.
+	///
+	/// The `at` block is ignored, transactions are accepted if they are valid in any of the views.
+	fn submit_at(
+		&self,
+		_at: &BlockId<Self::Block>,
+		source: TransactionSource,
+		xts: Vec<TransactionFor<Self>>,
+	) -> PoolFuture<Vec<Result<TxHash<Self>, Self::Error>>, Self::Error> {
+		let view_store = self.view_store.clone();
+
+		self.metrics
+			.report(|metrics| metrics.submitted_transactions.inc_by(xts.len() as u64));
+
+		async move { view_store.submit(source, xts).await }.boxed()
+	}
+
+	fn submit_one(
+		&self,
+		_at: &BlockId<Self::Block>,
+		source: TransactionSource,
+		xt: TransactionFor<Self>,
+	) -> PoolFuture<TxHash<Self>, Self::Error> {
+		let view_store = self.view_store.clone();
+
+		self.metrics.report(|metrics| metrics.submitted_transactions.inc());
+
+		async move {
+			let res = view_store.submit(source, vec![xt]).await?.pop();
+			res.expect("One extrinsic passed; one result returned; qed")
+		}
+		.boxed()
+	}
+
+	fn submit_and_watch(
+		&self,
+		_at: &BlockId<Self::Block>,
+		source: TransactionSource,
+		xt: TransactionFor<Self>,
+	) -> PoolFuture<Pin<Box<TransactionStatusStreamFor<Self>>>, Self::Error> {
+		let view_store = self.view_store.clone();
+
+		self.metrics.report(|metrics| metrics.submitted_transactions.inc());
+
+		async move { view_store.submit_and_watch(source, xt).await }.boxed()
+	}
+
+	fn remove_invalid(&self, hashes: &[TxHash<Self>]) -> Vec<Arc<Self::InPoolTransaction>> {
+		let removed = self.view_store.remove_invalid(hashes);
+		self.metrics
+			.report(|metrics| metrics.validations_invalid.inc_by(removed.len() as u64));
+		removed
+	}
+
+	fn status(&self) -> PoolStatus {
+		self.view_store.most_recent_view().pool.validated_pool().status()
+	}
+
+	fn import_notification_stream(&self) -> ImportNotificationStream<TxHash<Self>> {
+		const CHANNEL_BUFFER_SIZE: usize = 1024;
+
+		let (sink, stream) = channel(CHANNEL_BUFFER_SIZE);
+		self.view_store.add_import_notification_sink(sink);
+		stream
+	}
+
+	fn hash_of(&self, xt: &TransactionFor<Self>) -> TxHash<Self> {
+		self.api.hash_and_length(xt).0
+	}
+
+	fn on_broadcasted(&self, propagations: HashMap<TxHash<Self>, Vec<String>>) {
+		for view in self.view_store.views() {
+			view.pool.validated_pool().on_broadcasted(propagations.clone());
+		}
+	}
+
+	fn ready_transaction(&self, hash: &TxHash<Self>) -> Option<Arc<Self::InPoolTransaction>> {
+		self.view_store.most_recent_view().pool.validated_pool().ready_by_hash(hash)
+	}
+
+	fn ready_at(&self, at: NumberFor<Self::Block>) -> PolledIterator<PoolApi> {
+		if self.view_store.mempool_len() == 0 {
+			return async { Box::new(std::iter::empty()) as Box<_> }.boxed()
+		}
+
+		if self.ready_poll.lock().updated_at() >= at {
+			log::trace!(target: LOG_TARGET, "Transaction pool already processed block  #{}", at);
+			let iterator: ReadyIteratorFor<PoolApi> = self.ready();
+			return async move { iterator }.boxed()
+		}
+
+		self.ready_poll
+			.lock()
+			.add(at)
+			.map(|received| {
+				received.unwrap_or_else(|e| {
+					log::warn!("Error receiving pending set: {:?}", e);
+					Box::new(std::iter::empty())
+				})
+			})
+			.boxed()
+	}
+
+	/// Returns the ready set of the view built for the given block.
+	///
+	/// If the block is not tracked, a view is built for it from the view of its nearest ancestor.
+	fn ready_at_block(
+		&self,
+		at: Block::Hash,
+		number: NumberFor<Self::Block>,
+	) -> PolledIterator<PoolApi> {
+		if let Some(view) = self.view_store.view_at(&at) {
+			let iterator: ReadyIteratorFor<PoolApi> = Box::new(view.pool.validated_pool().ready());
+			return async move { iterator }.boxed()
+		}
+
+		let view_store = self.view_store.clone();
+		async move {
+			let view = view_store.build_view_on_demand(HashAndNumber { hash: at, number }).await;
+			Box::new(view.pool.validated_pool().ready()) as ReadyIteratorFor<PoolApi>
+		}
+		.boxed()
+	}
+
+	fn ready(&self) -> ReadyIteratorFor<PoolApi> {
+		Box::new(self.view_store.most_recent_view().pool.validated_pool().ready())
+	}
+}
+
+impl<Block, Client> FullForkAwarePool<Block, Client>
+where
+	Block: BlockT,
+	Client: sp_api::ProvideRuntimeApi<Block>
+		+ sc_client_api::BlockBackend<Block>
+		+ sc_client_api::blockchain::HeaderBackend<Block>
+		+ sp_runtime::traits::BlockIdTo<Block>
+		+ sc_client_api::ExecutorProvider<Block>
+		+ sc_client_api::UsageProvider<Block>
+		+ sp_blockchain::HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ Send
+		+ Sync
+		+ 'static,
+	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>,
+{
+	/// Create new fork-aware transaction pool for a full node with the provided api.
+	pub fn new_full(
+		options: graph::Options,
+		is_validator: IsValidator,
+		prometheus: Option<&PrometheusRegistry>,
+		spawner: impl sp_core::traits::SpawnEssentialNamed,
+		client: Arc<Client>,
+	) -> Arc<Self> {
+		let pool_api = Arc::new(FullChainApi::new(client.clone(), prometheus, &spawner));
+		let info = client.usage_info().chain;
+
+		let journal = options.journal.clone().map(|options| {
+			let (journal, writer) = Journal::open(options, &*pool_api);
+			spawner.spawn_essential_blocking("txpool-journal", Some("transaction-pool"), writer);
+			journal
+		});
+		let mut pool = Self::new(
+			options,
+			is_validator,
+			pool_api,
+			prometheus,
+			HashAndNumber { hash: info.best_hash, number: info.best_number },
+			info.finalized_hash,
+		);
+		pool.journal = journal;
+
+		Arc::new(pool)
+	}
+}
+
+impl<Block, Client> sc_transaction_pool_api::LocalTransactionPool
+	for ForkAwarePool<FullChainApi<Client, Block>, Block>
+where
+	Block: BlockT,
+	Client: sp_api::ProvideRuntimeApi<Block>
+		+ sc_client_api::BlockBackend<Block>
+		+ sc_client_api::blockchain::HeaderBackend<Block>
+		+ sp_runtime::traits::BlockIdTo<Block>
+		+ sp_blockchain::HeaderMetadata<Block, Error = sp_blockchain::Error>,
+	Client: Send + Sync + 'static,
+	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>,
+{
+	type Block = Block;
+	type Hash = graph::ExtrinsicHash<FullChainApi<Client, Block>>;
+	type Error = <FullChainApi<Client, Block> as graph::ChainApi>::Error;
+
+	/// Validates the transaction against all of the views, the `at` block is ignored.
+	///
+	/// The transaction is kept if any of the views accepts it, but the result reported is the
+	/// one of the most recent view.
+	fn submit_local(
+		&self,
+		_at: Block::Hash,
+		xt: sc_transaction_pool_api::LocalTransactionFor<Self>,
+	) -> Result<Self::Hash, Self::Error> {
+		use sp_runtime::{
+			traits::SaturatedConversion, transaction_validity::TransactionValidityError,
+		};
+
+		let (hash, bytes) = self.api.hash_and_length(&xt);
+
+		let mut result = None;
+		let mut accepted = None;
+		for view in self.view_store.views() {
+			let submit = || -> Result<Self::Hash, Self::Error> {
+				let validity = self
+					.api
+					.validate_transaction_blocking(
+						&BlockId::hash(view.at.hash),
+						TransactionSource::Local,
+						xt.clone(),
+					)?
+					.map_err(|e| {
+						Self::Error::Pool(match e {
+							TransactionValidityError::Invalid(i) =>
+								TxPoolError::InvalidTransaction(i),
+							TransactionValidityError::Unknown(u) =>
+								TxPoolError::UnknownTransaction(u),
+						})
+					})?;
+
+				let validated = graph::ValidatedTransaction::valid_at(
+					view.at.number.saturated_into::<u64>(),
+					hash,
+					TransactionSource::Local,
+					xt.clone(),
+					bytes,
+					validity,
+				);
+
+				view.pool.validated_pool().submit(vec![validated]).remove(0)
+			};
+
+			// `views` returns the most recent view last, so its result is the one kept.
+			let view_result = submit();
+			if let Ok(hash) = &view_result {
+				accepted = Some(*hash);
+			}
+			result = Some(view_result);
+		}
+
+		if let Some(hash) = accepted {
+			self.view_store.insert_tx(hash, TransactionSource::Local, xt);
+		}
+		result.unwrap_or_else(|| Err(error::Error::from(TxPoolError::ImmediatelyDropped)))
+	}
+}
+
+#[async_trait]
+impl<PoolApi, Block> MaintainedTransactionPool for ForkAwarePool<PoolApi, Block>
+where
+	Block: BlockT,
+	PoolApi: 'static + graph::ChainApi<Block = Block>,
+{
+	async fn maintain(&self, event: ChainEvent<Self::Block>) {
+		let compute_tree_route = |from, to| -> Result<TreeRoute<Block>, String> {
+			self.api.tree_route(from, to).map_err(|e| {
+				format!("Error occurred while computing tree_route from {from:?} to {to:?}: {e}")
+			})
+		};
+		let block_id_to_number =
+			|hash| self.api.block_id_to_number(&BlockId::Hash(hash)).map_err(|e| format!("{}", e));
+
+		let result =
+			self.enactment_state
+				.lock()
+				.update(&event, &compute_tree_route, &block_id_to_number);
+		let is_new_best = matches!(result, Ok(EnactmentAction::HandleEnactment(_)));
+
+		match result {
+			Err(msg) => {
+				log::debug!(target: LOG_TARGET, "{msg}");
+				self.enactment_state.lock().force_update(&event);
+			},
+			Ok(EnactmentAction::Skip) => return,
+			Ok(EnactmentAction::HandleFinalization) => {},
+			Ok(EnactmentAction::HandleEnactment(tree_route)) => {
+				self.handle_enactment(tree_route).await;
+			},
+		};
+
+		if let ChainEvent::Finalized { hash, tree_route } = event {
+			self.handle_finalized(hash, &tree_route);
+		}
+
+		let purged = self.view_store.purge();
+		log::debug!(
+			target: LOG_TARGET,
+			"Maintained the pool: {} views, {} transactions dropped, status: {:?}",
+			self.view_store.views().len(),
+			purged,
+			self.status(),
+		);
+
+		if let Some(ref journal) = self.journal {
+			let view_store = &self.view_store;
+			journal.replay(move |source, xts| view_store.submit(source, xts)).await;
+			if is_new_best {
+				journal.on_best_block(|| view_store.pooled_transactions());
+			}
+		}
+	}
+}
+
+impl<PoolApi, Block> Drop for ForkAwarePool<PoolApi, Block>
+where
+	Block: BlockT,
+	PoolApi: graph::ChainApi<Block = Block>,
+{
+	fn drop(&mut self) {
+		if let Some(ref journal) = self.journal {
+			journal.flush(|| self.view_store.pooled_transactions());
+		}
+	}
+}
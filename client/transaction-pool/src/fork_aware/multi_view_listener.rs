@@ -0,0 +1,274 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Aggregation of the per-view transaction status streams.
+//!
+//! Every view of the fork-aware pool keeps its own listener and emits its own
+//! [`TransactionStatus`] events. The external watcher returned to the RPC layer merges
+//! them into a single stream: `Ready`, `Future` and `Broadcast` are reported once, no matter
+//! how many views produced them, terminal per-view statuses are only reported when the
+//! transaction is gone from all of the views, and block related statuses (`InBlock`,
+//! `Retracted`, `Finalized`) are reported by the pool itself.
+
+use crate::{
+	graph::{BlockHash, ChainApi, ExtrinsicHash},
+	LOG_TARGET,
+};
+use futures::{future::Either, stream::SelectAll, StreamExt};
+use parking_lot::Mutex;
+use sc_transaction_pool_api::{TransactionStatus, TransactionStatusStream, TxIndex};
+use sc_utils::mpsc::{tracing_unbounded, TracingUnboundedReceiver, TracingUnboundedSender};
+use std::{collections::HashMap, pin::Pin};
+
+/// Per-view status stream of a single transaction.
+pub(crate) type ViewStatusStream<C> =
+	Pin<Box<TransactionStatusStream<ExtrinsicHash<C>, BlockHash<C>>>>;
+
+/// Commands sent from the pool to the task driving an external watcher.
+enum ControllerCommand<C: ChainApi> {
+	/// A new view started watching the transaction.
+	AddView(BlockHash<C>, ViewStatusStream<C>),
+	/// The transaction was included in a block.
+	InBlock(BlockHash<C>, TxIndex),
+	/// The block including the transaction was retracted.
+	Retracted(BlockHash<C>),
+	/// The block including the transaction was finalized.
+	Finalized(BlockHash<C>, TxIndex),
+	/// The transaction was found invalid when building a new view.
+	InvalidInView,
+	/// The transaction is not part of any view anymore.
+	Dropped,
+}
+
+type Controller<C> = TracingUnboundedSender<ControllerCommand<C>>;
+
+/// State of a single external watcher.
+struct ExternalWatcherContext<C: ChainApi> {
+	tx_hash: ExtrinsicHash<C>,
+	command_receiver: TracingUnboundedReceiver<ControllerCommand<C>>,
+	views: SelectAll<ViewStatusStream<C>>,
+	ready_reported: bool,
+	future_reported: bool,
+	broadcast_reported: bool,
+	/// The last terminal status reported by any of the views.
+	terminal_status: Option<TransactionStatus<ExtrinsicHash<C>, BlockHash<C>>>,
+	terminated: bool,
+}
+
+impl<C: ChainApi> ExternalWatcherContext<C> {
+	/// Decides if the status reported by a view should be forwarded to the external watcher.
+	fn handle_view_status(
+		&mut self,
+		status: TransactionStatus<ExtrinsicHash<C>, BlockHash<C>>,
+	) -> Option<TransactionStatus<ExtrinsicHash<C>, BlockHash<C>>> {
+		match status {
+			TransactionStatus::Ready if !self.ready_reported => {
+				self.ready_reported = true;
+				Some(status)
+			},
+			TransactionStatus::Future if !self.ready_reported && !self.future_reported => {
+				self.future_reported = true;
+				Some(status)
+			},
+			TransactionStatus::Broadcast(_) if !self.broadcast_reported => {
+				self.broadcast_reported = true;
+				Some(status)
+			},
+			TransactionStatus::Usurped(_) |
+			TransactionStatus::Dropped |
+			TransactionStatus::Invalid |
+			TransactionStatus::FinalityTimeout(_) => {
+				self.terminal_status = Some(status);
+				None
+			},
+			_ => None,
+		}
+	}
+
+	/// Handles the command sent by the pool, returns the status to be reported (if any).
+	fn handle_command(
+		&mut self,
+		command: ControllerCommand<C>,
+	) -> Option<TransactionStatus<ExtrinsicHash<C>, BlockHash<C>>> {
+		match command {
+			ControllerCommand::AddView(view, stream) => {
+				log::trace!(target: LOG_TARGET, "[{:?}] added view {:?}", self.tx_hash, view);
+				self.views.push(stream);
+				None
+			},
+			ControllerCommand::InBlock(block, index) =>
+				Some(TransactionStatus::InBlock((block, index))),
+			ControllerCommand::Retracted(block) => {
+				// the transaction may become ready again in views built on top of other forks
+				self.ready_reported = false;
+				self.future_reported = false;
+				Some(TransactionStatus::Retracted(block))
+			},
+			ControllerCommand::Finalized(block, index) => {
+				self.terminated = true;
+				Some(TransactionStatus::Finalized((block, index)))
+			},
+			ControllerCommand::InvalidInView => {
+				self.terminal_status = Some(TransactionStatus::Invalid);
+				None
+			},
+			ControllerCommand::Dropped => {
+				self.terminated = true;
+				Some(self.terminal_status.take().unwrap_or(TransactionStatus::Dropped))
+			},
+		}
+	}
+}
+
+/// Keeps the controllers of all external watchers.
+pub(crate) struct MultiViewListener<C: ChainApi> {
+	controllers: Mutex<HashMap<ExtrinsicHash<C>, Controller<C>>>,
+}
+
+impl<C: ChainApi> Default for MultiViewListener<C> {
+	fn default() -> Self {
+		Self { controllers: Default::default() }
+	}
+}
+
+impl<C> MultiViewListener<C>
+where
+	C: ChainApi + 'static,
+{
+	/// Creates an external watcher for the given transaction.
+	///
+	/// Returns `None` if the transaction is already watched.
+	pub(crate) fn create_external_watcher_for_tx(
+		&self,
+		tx_hash: ExtrinsicHash<C>,
+	) -> Option<ViewStatusStream<C>> {
+		let mut controllers = self.controllers.lock();
+		if controllers.contains_key(&tx_hash) {
+			return None
+		}
+
+		let (sender, command_receiver) = tracing_unbounded("mpsc_txpool_multi_view_watcher", 1000);
+		controllers.insert(tx_hash, sender);
+
+		let context = ExternalWatcherContext {
+			tx_hash,
+			command_receiver,
+			views: SelectAll::new(),
+			ready_reported: false,
+			future_reported: false,
+			broadcast_reported: false,
+			terminal_status: None,
+			terminated: false,
+		};
+
+		let stream = futures::stream::unfold(context, |mut ctx| async move {
+			if ctx.terminated {
+				return None
+			}
+
+			loop {
+				// statuses already reported by the views take precedence over the commands sent
+				// by the pool, so the last terminal status is known when the transaction is dropped
+				let event = futures::select_biased! {
+					status = ctx.views.next() => Either::Right(status),
+					command = ctx.command_receiver.next() => Either::Left(command),
+				};
+
+				let status = match event {
+					Either::Left(Some(command)) => ctx.handle_command(command),
+					Either::Left(None) => return None,
+					Either::Right(Some(status)) => ctx.handle_view_status(status),
+					Either::Right(None) => continue,
+				};
+
+				if let Some(status) = status {
+					return Some((status, ctx))
+				}
+			}
+		});
+
+		Some(stream.boxed())
+	}
+
+	/// Returns true if the transaction is watched by an external watcher.
+	pub(crate) fn is_watched(&self, tx_hash: &ExtrinsicHash<C>) -> bool {
+		self.controllers.lock().contains_key(tx_hash)
+	}
+
+	/// Adds the status stream of the given view to the external watcher of the transaction.
+	pub(crate) fn add_view_watcher_for_tx(
+		&self,
+		tx_hash: ExtrinsicHash<C>,
+		view: BlockHash<C>,
+		stream: ViewStatusStream<C>,
+	) {
+		self.send(tx_hash, ControllerCommand::AddView(view, stream), false);
+	}
+
+	/// Notifies the external watcher that the transaction was included in the block.
+	pub(crate) fn transaction_in_block(
+		&self,
+		tx_hash: ExtrinsicHash<C>,
+		block: BlockHash<C>,
+		index: TxIndex,
+	) {
+		self.send(tx_hash, ControllerCommand::InBlock(block, index), false);
+	}
+
+	/// Notifies the external watcher that the block including the transaction was retracted.
+	pub(crate) fn transaction_retracted(&self, tx_hash: ExtrinsicHash<C>, block: BlockHash<C>) {
+		self.send(tx_hash, ControllerCommand::Retracted(block), false);
+	}
+
+	/// Notifies the external watcher that the block including the transaction was finalized.
+	///
+	/// This is a final event, the watcher is removed.
+	pub(crate) fn transaction_finalized(
+		&self,
+		tx_hash: ExtrinsicHash<C>,
+		block: BlockHash<C>,
+		index: TxIndex,
+	) {
+		self.send(tx_hash, ControllerCommand::Finalized(block, index), true);
+	}
+
+	/// Records that the transaction was rejected as invalid by a newly built view.
+	///
+	/// The status is only reported if the transaction is dropped from all of the views.
+	pub(crate) fn transaction_invalid_in_view(&self, tx_hash: ExtrinsicHash<C>) {
+		self.send(tx_hash, ControllerCommand::InvalidInView, false);
+	}
+
+	/// Notifies the external watcher that the transaction is no longer part of any view.
+	///
+	/// The last terminal status reported by the views (or `Dropped`) is sent and the watcher is
+	/// removed.
+	pub(crate) fn transaction_dropped(&self, tx_hash: ExtrinsicHash<C>) {
+		self.send(tx_hash, ControllerCommand::Dropped, true);
+	}
+
+	fn send(&self, tx_hash: ExtrinsicHash<C>, command: ControllerCommand<C>, is_final: bool) {
+		let mut controllers = self.controllers.lock();
+		if let Some(controller) = controllers.get(&tx_hash) {
+			if controller.unbounded_send(command).is_err() || is_final {
+				log::trace!(target: LOG_TARGET, "[{:?}] removing external watcher", tx_hash);
+				controllers.remove(&tx_hash);
+			}
+		}
+	}
+}
@@ -0,0 +1,76 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! A view of the transaction pool at a single fork tip.
+
+use crate::graph::{self, watcher::Watcher, ChainApi, ExtrinsicFor, ExtrinsicHash, IsValidator};
+use sp_blockchain::HashAndNumber;
+use sp_runtime::{generic::BlockId, transaction_validity::TransactionSource};
+use std::sync::Arc;
+
+/// Ready and future transactions validated against the state of a single block.
+pub(crate) struct View<C: ChainApi> {
+	/// The block this view was built for.
+	pub(crate) at: HashAndNumber<C::Block>,
+	/// Transactions valid at `at`.
+	pub(crate) pool: graph::Pool<C>,
+}
+
+impl<C: ChainApi> View<C> {
+	/// Creates an empty view at the given block.
+	pub(crate) fn new(
+		api: Arc<C>,
+		at: HashAndNumber<C::Block>,
+		options: graph::Options,
+		is_validator: IsValidator,
+	) -> Self {
+		Self { at, pool: graph::Pool::new(options, is_validator, api) }
+	}
+
+	/// Validates the transactions against the view's block and imports the valid ones.
+	pub(crate) async fn submit_many(
+		&self,
+		source: TransactionSource,
+		xts: impl IntoIterator<Item = ExtrinsicFor<C>>,
+	) -> Result<Vec<Result<ExtrinsicHash<C>, C::Error>>, C::Error> {
+		self.pool.submit_at(&BlockId::Hash(self.at.hash), source, xts).await
+	}
+
+	/// Imports the transactions without checking if they were banned by this view.
+	pub(crate) async fn resubmit_many(
+		&self,
+		source: TransactionSource,
+		xts: impl IntoIterator<Item = ExtrinsicFor<C>>,
+	) -> Result<Vec<Result<ExtrinsicHash<C>, C::Error>>, C::Error> {
+		self.pool.resubmit_at(&BlockId::Hash(self.at.hash), source, xts).await
+	}
+
+	/// Validates and imports a single transaction, returning the view's status watcher.
+	pub(crate) async fn submit_and_watch(
+		&self,
+		source: TransactionSource,
+		xt: ExtrinsicFor<C>,
+	) -> Result<Watcher<ExtrinsicHash<C>, ExtrinsicHash<C>>, C::Error> {
+		self.pool.submit_and_watch(&BlockId::Hash(self.at.hash), source, xt).await
+	}
+
+	/// Returns true if the transaction is in the ready or future queue of this view.
+	pub(crate) fn contains(&self, hash: &ExtrinsicHash<C>) -> bool {
+		self.pool.validated_pool().check_is_known(hash, true).is_err()
+	}
+}
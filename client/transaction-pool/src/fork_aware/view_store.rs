@@ -0,0 +1,571 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Storage of the views and of the transactions shared between them.
+
+use super::{
+	multi_view_listener::{MultiViewListener, ViewStatusStream},
+	view::View,
+};
+use crate::{
+	graph::{self, BlockHash, ChainApi, ExtrinsicFor, ExtrinsicHash, IsValidator, TransactionFor},
+	journal::PooledTransaction,
+	LOG_TARGET,
+};
+use futures::{channel::mpsc::Sender, future, StreamExt};
+use parking_lot::{Mutex, RwLock};
+use sc_transaction_pool_api::error::{Error as TxPoolError, IntoPoolError};
+use sp_blockchain::HashAndNumber;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Header as HeaderT, NumberFor},
+	transaction_validity::TransactionSource,
+};
+use std::{
+	collections::{hash_map::Entry, HashMap, HashSet},
+	sync::Arc,
+};
+
+/// A transaction known to the pool, kept independently of the views.
+struct TxInMemPool<C: ChainApi> {
+	source: TransactionSource,
+	tx: ExtrinsicFor<C>,
+}
+
+/// Extrinsics of a block seen by the pool.
+struct IncludedTxs<C: ChainApi> {
+	number: NumberFor<C::Block>,
+	hashes: Vec<ExtrinsicHash<C>>,
+}
+
+/// Views at the active fork tips, together with all the transactions they were built from.
+///
+/// A transaction stays in the store as long as it is part of at least one view, or it is
+/// included in a block that was not finalized yet.
+pub(crate) struct ViewStore<C: ChainApi> {
+	api: Arc<C>,
+	options: graph::Options,
+	is_validator: IsValidator,
+	pub(crate) listener: MultiViewListener<C>,
+	mempool: RwLock<HashMap<ExtrinsicHash<C>, TxInMemPool<C>>>,
+	views: RwLock<HashMap<BlockHash<C>, Arc<View<C>>>>,
+	most_recent_view: RwLock<Arc<View<C>>>,
+	included: RwLock<HashMap<BlockHash<C>, IncludedTxs<C>>>,
+	import_notification_sinks: Mutex<Vec<Sender<ExtrinsicHash<C>>>>,
+}
+
+// Not bound by `'static`, so the pool can flush its journal when dropped.
+impl<C: ChainApi> ViewStore<C> {
+	/// Returns the transactions kept in any of the views, each of them once.
+	pub(crate) fn pooled_transactions(&self) -> Vec<PooledTransaction<C>> {
+		let mut seen = HashSet::new();
+		self.views
+			.read()
+			.values()
+			.flat_map(|view| view.pool.validated_pool().all_transactions())
+			.filter(|(hash, _, _)| seen.insert(*hash))
+			.collect()
+	}
+}
+
+impl<C> ViewStore<C>
+where
+	C: ChainApi + 'static,
+{
+	/// Creates a new store with an empty view at the given block.
+	pub(crate) fn new(
+		api: Arc<C>,
+		options: graph::Options,
+		is_validator: IsValidator,
+		at: HashAndNumber<C::Block>,
+	) -> Self {
+		let view =
+			Arc::new(View::new(api.clone(), at.clone(), options.clone(), is_validator.clone()));
+		Self {
+			api,
+			options,
+			is_validator,
+			listener: Default::default(),
+			mempool: Default::default(),
+			views: RwLock::new(HashMap::from([(at.hash, view.clone())])),
+			most_recent_view: RwLock::new(view),
+			included: Default::default(),
+			import_notification_sinks: Default::default(),
+		}
+	}
+
+	/// Returns the view built for the most recent best block.
+	pub(crate) fn most_recent_view(&self) -> Arc<View<C>> {
+		self.most_recent_view.read().clone()
+	}
+
+	/// Returns the view built for the given block, if it is tracked.
+	pub(crate) fn view_at(&self, at: &BlockHash<C>) -> Option<Arc<View<C>>> {
+		self.views.read().get(at).cloned()
+	}
+
+	/// Returns all the views, the most recent one being the last.
+	pub(crate) fn views(&self) -> Vec<Arc<View<C>>> {
+		let most_recent = self.most_recent_view.read().at.hash;
+		let mut views = self.views.read().values().cloned().collect::<Vec<_>>();
+		views.sort_by_key(|view| (view.at.hash == most_recent, view.at.number));
+		views
+	}
+
+	/// Returns the number of transactions kept in the store.
+	pub(crate) fn mempool_len(&self) -> usize {
+		self.mempool.read().len()
+	}
+
+	/// Returns true if the transaction is ready in any of the views.
+	fn is_ready(&self, hash: &ExtrinsicHash<C>) -> bool {
+		self.views
+			.read()
+			.values()
+			.any(|view| view.pool.validated_pool().ready_by_hash(hash).is_some())
+	}
+
+	/// Stores the transaction, notifying the import listeners if it became ready.
+	pub(crate) fn insert_tx(
+		&self,
+		hash: ExtrinsicHash<C>,
+		source: TransactionSource,
+		tx: ExtrinsicFor<C>,
+	) {
+		let inserted = match self.mempool.write().entry(hash) {
+			Entry::Occupied(_) => false,
+			Entry::Vacant(entry) => {
+				entry.insert(TxInMemPool { source, tx });
+				true
+			},
+		};
+
+		if !inserted || !self.is_ready(&hash) {
+			return
+		}
+
+		let sinks = &mut self.import_notification_sinks.lock();
+		sinks.retain_mut(|sink| match sink.try_send(hash) {
+			Ok(()) => true,
+			Err(e) =>
+				if e.is_full() {
+					log::warn!(
+						target: LOG_TARGET,
+						"[{:?}] Trying to notify an import but the channel is full",
+						hash,
+					);
+					true
+				} else {
+					false
+				},
+		});
+	}
+
+	/// Registers a new import notification sink.
+	pub(crate) fn add_import_notification_sink(&self, sink: Sender<ExtrinsicHash<C>>) {
+		self.import_notification_sinks.lock().push(sink);
+	}
+
+	/// Stores transactions coming from retracted blocks, so they are validated by the next views.
+	pub(crate) fn insert_retracted(&self, xts: impl IntoIterator<Item = ExtrinsicFor<C>>) -> usize {
+		let mut mempool = self.mempool.write();
+		let mut inserted = 0;
+		for tx in xts {
+			let hash = self.api.hash_and_length(&tx).0;
+			mempool.entry(hash).or_insert_with(|| {
+				inserted += 1;
+				TxInMemPool { source: TransactionSource::External, tx }
+			});
+		}
+		inserted
+	}
+
+	/// Validates the transactions against every view.
+	///
+	/// A transaction is accepted if at least one of the views accepts it, otherwise the error
+	/// reported by the most recent view is returned.
+	pub(crate) async fn submit(
+		&self,
+		source: TransactionSource,
+		xts: Vec<ExtrinsicFor<C>>,
+	) -> Result<Vec<Result<ExtrinsicHash<C>, C::Error>>, C::Error> {
+		let views = self.views();
+		let results =
+			future::join_all(views.iter().map(|view| view.submit_many(source, xts.clone()))).await;
+
+		let mut aggregated = xts.iter().map(|_| None).collect::<Vec<_>>();
+		let mut last_error = None;
+		for result in results {
+			match result {
+				Ok(results) =>
+					for (slot, result) in aggregated.iter_mut().zip(results) {
+						if !matches!(slot, Some(Ok(_))) {
+							*slot = Some(result);
+						}
+					},
+				Err(e) => last_error = Some(e),
+			}
+		}
+
+		let results = match (aggregated.into_iter().collect::<Option<Vec<_>>>(), last_error) {
+			(Some(results), _) => results,
+			(None, Some(e)) => return Err(e),
+			(None, None) => return Ok(Vec::new()),
+		};
+
+		for (tx, result) in xts.into_iter().zip(results.iter()) {
+			if let Ok(hash) = result {
+				self.insert_tx(*hash, source, tx);
+			}
+		}
+
+		Ok(results)
+	}
+
+	/// Validates the transaction against every view and starts watching its status.
+	pub(crate) async fn submit_and_watch(
+		&self,
+		source: TransactionSource,
+		xt: ExtrinsicFor<C>,
+	) -> Result<ViewStatusStream<C>, C::Error> {
+		let hash = self.api.hash_and_length(&xt).0;
+		if self.mempool.read().contains_key(&hash) {
+			return Err(TxPoolError::AlreadyImported(Box::new(hash)).into())
+		}
+
+		let views = self.views();
+		let results = future::join_all(views.iter().map(|view| {
+			let xt = xt.clone();
+			async move { (view.at.hash, view.submit_and_watch(source, xt).await) }
+		}))
+		.await;
+
+		let mut watchers = Vec::new();
+		let mut last_error = None;
+		for (view, result) in results {
+			match result {
+				Ok(watcher) => watchers.push((view, watcher)),
+				Err(e) => last_error = Some(e),
+			}
+		}
+
+		if watchers.is_empty() {
+			return Err(last_error.unwrap_or_else(|| TxPoolError::ImmediatelyDropped.into()))
+		}
+
+		let external_watcher = self
+			.listener
+			.create_external_watcher_for_tx(hash)
+			.ok_or_else(|| TxPoolError::AlreadyImported(Box::new(hash)))?;
+		for (view, watcher) in watchers {
+			self.listener.add_view_watcher_for_tx(hash, view, watcher.into_stream().boxed());
+		}
+		self.insert_tx(hash, source, xt);
+
+		Ok(external_watcher)
+	}
+
+	/// Removes the transactions (and the transactions depending on them) from all of the views.
+	pub(crate) fn remove_invalid(&self, hashes: &[ExtrinsicHash<C>]) -> Vec<TransactionFor<C>> {
+		let mut seen = HashSet::new();
+		let removed = self
+			.views
+			.read()
+			.values()
+			.flat_map(|view| view.pool.validated_pool().remove_invalid(hashes))
+			.filter(|tx| seen.insert(tx.hash))
+			.collect::<Vec<_>>();
+
+		let mut mempool = self.mempool.write();
+		for tx in &removed {
+			mempool.remove(&tx.hash);
+			self.listener.transaction_invalid_in_view(tx.hash);
+			self.listener.transaction_dropped(tx.hash);
+		}
+
+		removed
+	}
+
+	/// Builds the view for the given block from the stored transactions.
+	///
+	/// Transactions included in the block or in any of its not yet finalized ancestors are
+	/// skipped. The view is not inserted into the store.
+	pub(crate) async fn build_view(&self, at: HashAndNumber<C::Block>) -> Arc<View<C>> {
+		let included = self.included_on_route(at.hash);
+		self.build_view_with(at, |hash| !included.contains(hash)).await
+	}
+
+	/// Builds the view for a block that is not tracked, e.g. a block on a fork that never became
+	/// the best block, and inserts it into the store.
+	///
+	/// The view starts from the transactions of the view of the nearest tracked ancestor, except
+	/// the ones included in the blocks between that ancestor and `at`. If none of the views is an
+	/// ancestor of `at`, the view is built from all of the stored transactions.
+	pub(crate) async fn build_view_on_demand(&self, at: HashAndNumber<C::Block>) -> Arc<View<C>> {
+		let ancestor = self
+			.views()
+			.into_iter()
+			.filter_map(|view| {
+				let tree_route = self.api.tree_route(view.at.hash, at.hash).ok()?;
+				tree_route.retracted().is_empty().then(|| (view, tree_route.enacted().to_vec()))
+			})
+			.min_by_key(|(_, enacted)| enacted.len());
+
+		let view = match ancestor {
+			Some((ancestor, enacted)) => {
+				let mut included = HashSet::new();
+				for block in enacted {
+					let body = self.api.block_body(block.hash).await.unwrap_or_else(|e| {
+						log::warn!(target: LOG_TARGET, "Failed to fetch block body: {}", e);
+						None
+					});
+					included.extend(
+						body.unwrap_or_default().iter().map(|tx| self.api.hash_and_length(tx).0),
+					);
+				}
+				log::debug!(
+					target: LOG_TARGET,
+					"Building view at {:?} from the view at {:?}",
+					at.hash,
+					ancestor.at.hash,
+				);
+				self.build_view_with(at, |hash| {
+					!included.contains(hash) && ancestor.contains(hash)
+				})
+				.await
+			},
+			None => self.build_view(at).await,
+		};
+
+		// the view might have been built concurrently, e.g. by the maintenance task
+		let mut views = self.views.write();
+		views.entry(view.at.hash).or_insert(view).clone()
+	}
+
+	/// Builds the view for the given block from the stored transactions passing `filter`.
+	async fn build_view_with(
+		&self,
+		at: HashAndNumber<C::Block>,
+		filter: impl Fn(&ExtrinsicHash<C>) -> bool,
+	) -> Arc<View<C>> {
+		let view = Arc::new(View::new(
+			self.api.clone(),
+			at.clone(),
+			self.options.clone(),
+			self.is_validator.clone(),
+		));
+
+		let (watched, unwatched): (Vec<_>, Vec<_>) = self
+			.mempool
+			.read()
+			.iter()
+			.filter(|(hash, _)| filter(*hash))
+			.map(|(hash, tx)| (*hash, tx.source, tx.tx.clone()))
+			.partition(|(hash, _, _)| self.listener.is_watched(hash));
+
+		for source in
+			[TransactionSource::InBlock, TransactionSource::Local, TransactionSource::External]
+		{
+			let xts = unwatched
+				.iter()
+				.filter(|(_, tx_source, _)| *tx_source == source)
+				.map(|(_, _, tx)| tx.clone())
+				.collect::<Vec<_>>();
+			if xts.is_empty() {
+				continue
+			}
+
+			if let Err(e) = view.resubmit_many(source, xts).await {
+				log::debug!(
+					target: LOG_TARGET,
+					"[{:?}] Error re-submitting transactions to the new view: {}",
+					at.hash,
+					e,
+				);
+			}
+		}
+
+		for (hash, source, tx) in watched {
+			match view.submit_and_watch(source, tx).await {
+				Ok(watcher) => self.listener.add_view_watcher_for_tx(
+					hash,
+					at.hash,
+					watcher.into_stream().boxed(),
+				),
+				Err(e) =>
+					if let Ok(TxPoolError::InvalidTransaction(_)) = e.into_pool_error() {
+						self.listener.transaction_invalid_in_view(hash);
+					},
+			}
+		}
+
+		log::debug!(
+			target: LOG_TARGET,
+			"Built view at {:?}: {:?}",
+			at.hash,
+			view.pool.validated_pool().status(),
+		);
+
+		view
+	}
+
+	/// Inserts the view into the store, optionally making it the most recent one.
+	pub(crate) fn insert_view(&self, view: Arc<View<C>>, most_recent: bool) {
+		if most_recent {
+			*self.most_recent_view.write() = view.clone();
+		}
+		self.views.write().insert(view.at.hash, view);
+	}
+
+	/// Removes the view built for the given block, unless it is the most recent one.
+	pub(crate) fn remove_view(&self, at: &BlockHash<C>) {
+		if self.most_recent_view.read().at.hash != *at {
+			self.views.write().remove(at);
+		}
+	}
+
+	/// Records the extrinsics of the block and notifies the watchers about the inclusion.
+	///
+	/// Returns the number of stored transactions included in the block.
+	pub(crate) fn note_included(
+		&self,
+		at: HashAndNumber<C::Block>,
+		hashes: Vec<ExtrinsicHash<C>>,
+	) -> usize {
+		let mempool = self.mempool.read();
+		let mut included = 0;
+		for (index, hash) in hashes.iter().enumerate() {
+			if mempool.contains_key(hash) {
+				included += 1;
+				self.listener.transaction_in_block(*hash, at.hash, index);
+			}
+		}
+		self.included.write().insert(at.hash, IncludedTxs { number: at.number, hashes });
+		included
+	}
+
+	/// Notifies the watchers that the block including their transactions was retracted.
+	pub(crate) fn note_retracted(&self, at: &BlockHash<C>) {
+		if let Some(included) = self.included.read().get(at) {
+			for hash in &included.hashes {
+				self.listener.transaction_retracted(*hash, *at);
+			}
+		}
+	}
+
+	/// Handles finalization of the given blocks, the last one being the finalized tip.
+	///
+	/// Transactions included in the finalized blocks are removed, as well as the views and the
+	/// inclusion records of the forks that can't be finalized anymore.
+	pub(crate) fn note_finalized(&self, finalized: &[BlockHash<C>]) {
+		let tip = match finalized.last() {
+			Some(tip) => *tip,
+			None => return,
+		};
+
+		{
+			let mut included = self.included.write();
+			let mut mempool = self.mempool.write();
+			for block in finalized {
+				if let Some(txs) = included.remove(block) {
+					for (index, hash) in txs.hashes.into_iter().enumerate() {
+						if mempool.remove(&hash).is_some() {
+							self.listener.transaction_finalized(hash, *block, index);
+						}
+					}
+				}
+			}
+
+			let number = match self.api.block_id_to_number(&BlockId::Hash(tip)) {
+				Ok(Some(number)) => number,
+				_ => return,
+			};
+			included.retain(|_, txs| txs.number > number);
+		}
+
+		let most_recent = self.most_recent_view.read().at.hash;
+		self.views.write().retain(|hash, view| {
+			if *hash == tip || *hash == most_recent {
+				return true
+			}
+			match self.api.tree_route(tip, *hash) {
+				Ok(tree_route) => tree_route.retracted().is_empty(),
+				Err(e) => {
+					log::debug!(target: LOG_TARGET, "Dropping view {:?}: {}", view.at.hash, e);
+					false
+				},
+			}
+		});
+	}
+
+	/// Removes the transactions which are neither part of any view nor included in any of the
+	/// tracked blocks.
+	///
+	/// Returns the number of removed transactions.
+	pub(crate) fn purge(&self) -> usize {
+		let views = self.views.read().values().cloned().collect::<Vec<_>>();
+		let included = self
+			.included
+			.read()
+			.values()
+			.flat_map(|txs| txs.hashes.iter().copied())
+			.collect::<HashSet<_>>();
+
+		let mut mempool = self.mempool.write();
+		let purged = mempool
+			.keys()
+			.filter(|hash| {
+				!included.contains(*hash) && !views.iter().any(|view| view.contains(hash))
+			})
+			.copied()
+			.collect::<Vec<_>>();
+
+		for hash in &purged {
+			log::trace!(target: LOG_TARGET, "[{:?}] Not part of any view, dropping", hash);
+			mempool.remove(hash);
+			self.listener.transaction_dropped(*hash);
+		}
+
+		purged.len()
+	}
+
+	/// Collects the extrinsics included in the given block and its ancestors that are still
+	/// tracked.
+	fn included_on_route(&self, at: BlockHash<C>) -> HashSet<ExtrinsicHash<C>> {
+		let included = self.included.read();
+		let lowest = match included.values().map(|txs| txs.number).min() {
+			Some(number) => number,
+			None => return Default::default(),
+		};
+
+		let mut result = HashSet::new();
+		let mut current = at;
+		loop {
+			if let Some(txs) = included.get(&current) {
+				result.extend(txs.hashes.iter().copied());
+			}
+
+			match self.api.block_header(current) {
+				Ok(Some(header)) if *header.number() > lowest => current = *header.parent_hash(),
+				_ => break,
+			}
+		}
+
+		result
+	}
+}
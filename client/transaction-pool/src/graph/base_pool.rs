@@ -20,7 +20,12 @@
 //!
 //! For a more full-featured pool, have a look at the `pool` module.
 
-use std::{cmp::Ordering, collections::HashSet, fmt, hash, sync::Arc};
+use std::{
+	cmp::Reverse,
+	collections::{BTreeSet, HashMap, HashSet},
+	fmt, hash,
+	sync::Arc,
+};
 
 use crate::LOG_TARGET;
 use log::{debug, trace, warn};
@@ -146,6 +151,17 @@ impl<Hash, Extrinsic> InPoolTransaction for Transaction<Hash, Extrinsic> {
 	}
 }
 
+impl<Hash, Extrinsic> Transaction<Hash, Extrinsic> {
+	/// Returns the sender of the transaction.
+	///
+	/// The sender is identified by the first `prefix_len` bytes of the first tag provided by the
+	/// transaction, e.g. the account part of the `(account, nonce)` tag. Transactions which don't
+	/// provide any tags have no sender.
+	pub fn sender(&self, prefix_len: usize) -> Option<&[u8]> {
+		self.provides.first().map(|tag| &tag[..prefix_len.min(tag.len())])
+	}
+}
+
 impl<Hash: Clone, Extrinsic: Clone> Transaction<Hash, Extrinsic> {
 	/// Explicit transaction clone.
 	///
@@ -198,6 +214,10 @@ where
 /// Store last pruned tags for given number of invocations.
 const RECENTLY_PRUNED_TAGS: usize = 2;
 
+/// Number of leading bytes of the first provided tag identifying the sender by default, i.e. the
+/// size of the account id in the `(account, nonce)` tag.
+pub const DEFAULT_SENDER_TAG_PREFIX_LEN: usize = 32;
+
 /// Transaction pool.
 ///
 /// Builds a dependency graph for all transactions in the pool and returns
@@ -219,6 +239,12 @@ pub struct BasePool<Hash: hash::Hash + Eq, Ex> {
 	/// transactions to future in case they were just stuck in verification.
 	recently_pruned: [HashSet<Tag>; RECENTLY_PRUNED_TAGS],
 	recently_pruned_index: usize,
+	/// Ready transactions of every sender.
+	ready_senders: SenderIndex<Hash>,
+	/// Future transactions of every sender.
+	future_senders: SenderIndex<Hash>,
+	/// Senders which got new transactions since the limits were last enforced.
+	grown_senders: HashSet<Tag>,
 }
 
 impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> Default for BasePool<Hash, Ex> {
@@ -230,12 +256,24 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> Default for Bas
 impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash, Ex> {
 	/// Create new pool given reject_future_transactions flag.
 	pub fn new(reject_future_transactions: bool) -> Self {
+		Self::with_sender_tag_prefix_len(reject_future_transactions, DEFAULT_SENDER_TAG_PREFIX_LEN)
+	}
+
+	/// Create new pool given reject_future_transactions flag, identifying the sender of a
+	/// transaction by the first `sender_tag_prefix_len` bytes of its first provided tag.
+	pub fn with_sender_tag_prefix_len(
+		reject_future_transactions: bool,
+		sender_tag_prefix_len: usize,
+	) -> Self {
 		Self {
 			reject_future_transactions,
 			future: Default::default(),
 			ready: Default::default(),
 			recently_pruned: Default::default(),
 			recently_pruned_index: 0,
+			ready_senders: SenderIndex::new(sender_tag_prefix_len),
+			future_senders: SenderIndex::new(sender_tag_prefix_len),
+			grown_senders: Default::default(),
 		}
 	}
 
@@ -272,6 +310,10 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 			return Err(error::Error::AlreadyImported(Box::new(tx.hash)))
 		}
 
+		if let Some(sender) = tx.sender(self.ready_senders.tag_prefix_len) {
+			self.grown_senders.insert(sender.to_vec());
+		}
+
 		let tx = WaitingTransaction::new(tx, self.ready.provided_tags(), &self.recently_pruned);
 		trace!(target: LOG_TARGET, "[{:?}] {:?}", tx.transaction.hash, tx);
 		debug!(
@@ -288,6 +330,7 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 			}
 
 			let hash = tx.transaction.hash.clone();
+			self.future_senders.insert(&tx.transaction);
 			self.future.import(tx);
 			return Ok(Imported::Future { hash })
 		}
@@ -313,12 +356,17 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		// take first transaction from the list
 		while let Some(tx) = to_import.pop() {
 			// find transactions in Future that it unlocks
-			to_import.append(&mut self.future.satisfy_tags(&tx.transaction.provides));
+			let mut unlocked = self.future.satisfy_tags(&tx.transaction.provides);
+			unlocked.iter().for_each(|tx| self.future_senders.remove(&tx.transaction));
+			to_import.append(&mut unlocked);
 
 			// import this transaction
 			let current_hash = tx.transaction.hash.clone();
+			let transaction = tx.transaction.clone();
 			match self.ready.import(tx) {
 				Ok(mut replaced) => {
+					self.ready_senders.insert(&transaction);
+					replaced.iter().for_each(|tx| self.ready_senders.remove(tx));
 					if !first {
 						promoted.push(current_hash);
 					}
@@ -346,7 +394,9 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		if removed.iter().any(|tx| tx.hash == hash) {
 			// We still need to remove all transactions that we promoted
 			// since they depend on each other and will never get to the best iterator.
-			self.ready.remove_subtree(&promoted);
+			for tx in self.ready.remove_subtree(&promoted) {
+				self.ready_senders.remove(&tx);
+			}
 
 			debug!(target: LOG_TARGET, "[{:?}] Cycle detected, bailing.", hash);
 			return Err(error::Error::CycleDetected)
@@ -385,62 +435,124 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 	///
 	/// Removes and returns worst transactions from the queues and all transactions that depend on
 	/// them. Technically the worst transaction should be evaluated by computing the entire pending
-	/// set. We use a simplified approach to remove transactions with the lowest priority first or
-	/// those that occupy the pool for the longest time in case priority is the same.
+	/// set. We use a simplified approach:
+	/// - senders exceeding the `sender` limit lose their future transactions first, then the ready
+	///   ones with the lowest priority, most recent first, so only the tail of their queue is
+	///   dropped,
+	/// - when a queue is full, the transactions of the senders with the most transactions in that
+	///   queue are evicted first. Among those we remove transactions with the lowest priority first
+	///   or those that occupy the pool for the longest time in case priority is the same.
+	///
+	/// Only the senders which got new transactions since the last call are checked against the
+	/// `sender` limit, and the function returns early if no limit is exceeded.
 	pub fn enforce_limits(
 		&mut self,
 		ready: &Limit,
 		future: &Limit,
-	) -> Vec<Arc<Transaction<Hash, Ex>>> {
-		let mut removed = vec![];
+		sender: &Limit,
+	) -> Evicted<Hash, Ex> {
+		let mut evicted = Evicted::default();
+
+		for grown in std::mem::take(&mut self.grown_senders) {
+			loop {
+				let (ready_count, ready_bytes) = self.ready_senders.usage(&grown);
+				let (future_count, future_bytes) = self.future_senders.usage(&grown);
+				if !sender.is_exceeded(ready_count + future_count, ready_bytes + future_bytes) {
+					break
+				}
 
-		while ready.is_exceeded(self.ready.len(), self.ready.bytes()) {
-			// find the worst transaction
-			let worst = self.ready.fold::<TransactionRef<Hash, Ex>, _>(|worst, current| {
-				let transaction = &current.transaction;
-				worst
-					.map(|worst| {
-						// Here we don't use `TransactionRef`'s ordering implementation because
-						// while it prefers priority like need here, it also prefers older
-						// transactions for inclusion purposes and limit enforcement needs to prefer
-						// newer transactions instead and drop the older ones.
-						match worst.transaction.priority.cmp(&transaction.transaction.priority) {
-							Ordering::Less => worst,
-							Ordering::Equal =>
-								if worst.insertion_id > transaction.insertion_id {
-									transaction.clone()
-								} else {
-									worst
-								},
-							Ordering::Greater => transaction.clone(),
-						}
+				let future = self.future_senders.hashes(&grown);
+				let ready = self.ready_senders.hashes(&grown);
+				let worst = self.worst_future(Some(&future), |tx| tx.imported_at).or_else(|| {
+					self.worst_ready(Some(&ready), |tx| {
+						(Reverse(tx.transaction.priority), tx.insertion_id)
 					})
-					.or_else(|| Some(transaction.clone()))
-			});
+				});
+
+				if let Some(worst) = worst {
+					evicted.sender_limit.extend(self.remove_subtree(&[worst]));
+				} else {
+					break
+				}
+			}
+		}
+
+		while ready.is_exceeded(self.ready.len(), self.ready.bytes()) {
+			// Here we don't use `TransactionRef`'s ordering implementation because while it prefers
+			// priority like need here, it also prefers older transactions for inclusion purposes
+			// and limit enforcement needs to prefer newer transactions instead and drop the older
+			// ones.
+			let key = |tx: &TransactionRef<Hash, Ex>| {
+				(Reverse(tx.transaction.priority), Reverse(tx.insertion_id))
+			};
+			// if every sender has a single transaction in the queue, all of them are candidates
+			let heaviest = self.ready_senders.heaviest();
+			let worst = self.worst_ready(heaviest.as_deref(), key);
 
 			if let Some(worst) = worst {
-				removed.append(&mut self.remove_subtree(&[worst.transaction.hash.clone()]))
+				evicted.ready_limit.extend(self.remove_subtree(&[worst]));
 			} else {
 				break
 			}
 		}
 
 		while future.is_exceeded(self.future.len(), self.future.bytes()) {
-			// find the worst transaction
-			let worst = self.future.fold(|worst, current| match worst {
-				None => Some(current.clone()),
-				Some(ref tx) if tx.imported_at > current.imported_at => Some(current.clone()),
-				other => other,
-			});
+			let key = |tx: &WaitingTransaction<Hash, Ex>| Reverse(tx.imported_at);
+			let heaviest = self.future_senders.heaviest();
+			let worst = self.worst_future(heaviest.as_deref(), key);
 
 			if let Some(worst) = worst {
-				removed.append(&mut self.remove_subtree(&[worst.transaction.hash.clone()]))
+				evicted.future_limit.extend(self.remove_subtree(&[worst]));
 			} else {
 				break
 			}
 		}
 
-		removed
+		evicted
+	}
+
+	/// Returns the hash of the ready transaction with the greatest eviction `key`, among the
+	/// `candidates` or, if `None`, in the whole queue.
+	fn worst_ready<K: Ord>(
+		&self,
+		candidates: Option<&[Hash]>,
+		key: impl Fn(&TransactionRef<Hash, Ex>) -> K,
+	) -> Option<Hash> {
+		let worst = match candidates {
+			Some(hashes) => hashes
+				.iter()
+				.filter_map(|hash| self.ready.ref_by_hash(hash))
+				.max_by_key(|tx| key(tx)),
+			None => self.ready.fold(|worst, current| match worst {
+				Some(worst) if key(&worst) >= key(&current.transaction) => Some(worst),
+				_ => Some(current.transaction.clone()),
+			}),
+		};
+		worst.map(|tx| tx.transaction.hash.clone())
+	}
+
+	/// Returns the hash of the future transaction with the greatest eviction `key`, among the
+	/// `candidates` or, if `None`, in the whole queue.
+	fn worst_future<K: Ord>(
+		&self,
+		candidates: Option<&[Hash]>,
+		key: impl Fn(&WaitingTransaction<Hash, Ex>) -> K,
+	) -> Option<Hash> {
+		match candidates {
+			Some(hashes) => hashes
+				.iter()
+				.filter_map(|hash| self.future.get(hash))
+				.max_by_key(|tx| key(tx))
+				.map(|tx| tx.transaction.hash.clone()),
+			None => self
+				.future
+				.fold(|worst, current| match worst {
+					Some((worst_key, worst)) if worst_key >= key(current) =>
+						Some((worst_key, worst)),
+					_ => Some((key(current), current.transaction.hash.clone())),
+				})
+				.map(|(_, worst)| worst),
+		}
 	}
 
 	/// Removes all transactions represented by the hashes and all other transactions
@@ -453,12 +565,16 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 	/// and you don't want them to be stored in the pool use `prune_tags` method.
 	pub fn remove_subtree(&mut self, hashes: &[Hash]) -> Vec<Arc<Transaction<Hash, Ex>>> {
 		let mut removed = self.ready.remove_subtree(hashes);
-		removed.extend(self.future.remove(hashes));
+		removed.iter().for_each(|tx| self.ready_senders.remove(tx));
+		let future = self.future.remove(hashes);
+		future.iter().for_each(|tx| self.future_senders.remove(tx));
+		removed.extend(future);
 		removed
 	}
 
 	/// Removes and returns all transactions from the future queue.
 	pub fn clear_future(&mut self) -> Vec<Arc<Transaction<Hash, Ex>>> {
+		self.future_senders = SenderIndex::new(self.future_senders.tag_prefix_len);
 		self.future.clear()
 	}
 
@@ -477,9 +593,13 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 
 		for tag in tags {
 			// make sure to promote any future transactions that could be unlocked
-			to_import.append(&mut self.future.satisfy_tags(std::iter::once(&tag)));
+			let mut unlocked = self.future.satisfy_tags(std::iter::once(&tag));
+			unlocked.iter().for_each(|tx| self.future_senders.remove(&tx.transaction));
+			to_import.append(&mut unlocked);
 			// and actually prune transactions in ready queue
-			pruned.append(&mut self.ready.prune_tags(tag.clone()));
+			let mut ready_pruned = self.ready.prune_tags(tag.clone());
+			ready_pruned.iter().for_each(|tx| self.ready_senders.remove(tx));
+			pruned.append(&mut ready_pruned);
 			// store the tags for next submission
 			recently_pruned.insert(tag);
 		}
@@ -514,6 +634,126 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 	}
 }
 
+/// Transactions evicted while enforcing the pool limits, by reason.
+#[derive(Debug)]
+pub struct Evicted<Hash, Ex> {
+	/// Transactions evicted because their sender exceeded the per-sender limit.
+	pub sender_limit: Vec<Arc<Transaction<Hash, Ex>>>,
+	/// Transactions evicted because the ready queue was full.
+	pub ready_limit: Vec<Arc<Transaction<Hash, Ex>>>,
+	/// Transactions evicted because the future queue was full.
+	pub future_limit: Vec<Arc<Transaction<Hash, Ex>>>,
+}
+
+impl<Hash, Ex> Default for Evicted<Hash, Ex> {
+	fn default() -> Self {
+		Self { sender_limit: vec![], ready_limit: vec![], future_limit: vec![] }
+	}
+}
+
+impl<Hash, Ex> Evicted<Hash, Ex> {
+	/// Returns all the evicted transactions.
+	pub fn all(&self) -> impl Iterator<Item = &Arc<Transaction<Hash, Ex>>> {
+		self.sender_limit.iter().chain(&self.ready_limit).chain(&self.future_limit)
+	}
+}
+
+/// Transactions of every sender in a queue.
+///
+/// Kept up to date on every insertion and removal, so the limits can be enforced without going
+/// through the whole queue.
+#[derive(Debug)]
+struct SenderIndex<Hash> {
+	/// Number of leading bytes of the first provided tag which identify the sender.
+	tag_prefix_len: usize,
+	/// Hashes and total size of the transactions of every sender.
+	senders: HashMap<Tag, (HashSet<Hash>, usize)>,
+	/// Senders ordered by the number of their transactions.
+	by_count: BTreeSet<(usize, Tag)>,
+}
+
+impl<Hash: hash::Hash + Eq + Clone> SenderIndex<Hash> {
+	fn new(tag_prefix_len: usize) -> Self {
+		Self { tag_prefix_len, senders: Default::default(), by_count: Default::default() }
+	}
+
+	fn insert<Ex>(&mut self, tx: &Transaction<Hash, Ex>) {
+		let sender = match tx.sender(self.tag_prefix_len) {
+			Some(sender) => sender,
+			None => return,
+		};
+		let (hashes, bytes) = self.senders.entry(sender.to_vec()).or_default();
+		if hashes.insert(tx.hash.clone()) {
+			*bytes += tx.bytes;
+			self.by_count.remove(&(hashes.len() - 1, sender.to_vec()));
+			self.by_count.insert((hashes.len(), sender.to_vec()));
+		}
+	}
+
+	fn remove<Ex>(&mut self, tx: &Transaction<Hash, Ex>) {
+		let sender = match tx.sender(self.tag_prefix_len) {
+			Some(sender) => sender,
+			None => return,
+		};
+		let (hashes, bytes) = match self.senders.get_mut(sender) {
+			Some(entry) => entry,
+			None => return,
+		};
+		if !hashes.remove(&tx.hash) {
+			return
+		}
+
+		*bytes = bytes.saturating_sub(tx.bytes);
+		self.by_count.remove(&(hashes.len() + 1, sender.to_vec()));
+		if hashes.is_empty() {
+			self.senders.remove(sender);
+		} else {
+			self.by_count.insert((hashes.len(), sender.to_vec()));
+		}
+	}
+
+	/// Number and total size of the transactions of the sender.
+	fn usage(&self, sender: &[u8]) -> (usize, usize) {
+		self.senders
+			.get(sender)
+			.map_or((0, 0), |(hashes, bytes)| (hashes.len(), *bytes))
+	}
+
+	/// Hashes of the transactions of the sender.
+	fn hashes(&self, sender: &[u8]) -> Vec<Hash> {
+		self.senders
+			.get(sender)
+			.map_or_else(Vec::new, |(hashes, _)| hashes.iter().cloned().collect())
+	}
+
+	/// Hashes of the transactions of the senders with the most transactions.
+	///
+	/// Returns `None` if every sender has a single transaction, since transactions without a
+	/// sender count as a sender on their own.
+	fn heaviest(&self) -> Option<Vec<Hash>> {
+		let max = match self.by_count.last() {
+			Some((count, _)) if *count > 1 => *count,
+			_ => return None,
+		};
+		Some(
+			self.by_count
+				.range((max, Tag::new())..)
+				.flat_map(|(_, sender)| self.hashes(sender))
+				.collect(),
+		)
+	}
+}
+
+/// Limits of the transactions coming from a single sender.
+#[derive(Debug, Clone)]
+pub struct SenderLimit {
+	/// Number of leading bytes of the first tag provided by a transaction which identify its
+	/// sender.
+	pub tag_prefix_len: usize,
+	/// Maximal number and size of the ready and future transactions of a single sender.
+	pub limit: Limit,
+}
+
 /// Queue limits
 #[derive(Debug, Clone)]
 pub struct Limit {
@@ -1043,4 +1283,108 @@ source: TransactionSource::External, requires: [03, 02], provides: [04], data: [
 		assert_eq!(pool.reject_future_transactions, true);
 		assert_eq!(pool.future.len(), 1);
 	}
+
+	#[test]
+	fn should_enforce_sender_limit() {
+		// given
+		let mut pool = BasePool::with_sender_tag_prefix_len(false, 1);
+		let sender = Limit { count: 2, total_bytes: 1000 };
+		let unlimited = Limit { count: 100, total_bytes: 1000 };
+		pool.import(Transaction { hash: 1, provides: vec![vec![1, 0]], ..DEFAULT_TX.clone() })
+			.unwrap();
+		pool.import(Transaction {
+			hash: 2,
+			requires: vec![vec![1, 0]],
+			provides: vec![vec![1, 1]],
+			..DEFAULT_TX.clone()
+		})
+		.unwrap();
+		pool.import(Transaction {
+			hash: 3,
+			requires: vec![vec![1, 1]],
+			provides: vec![vec![1, 2]],
+			..DEFAULT_TX.clone()
+		})
+		.unwrap();
+		pool.import(Transaction { hash: 4, provides: vec![vec![2, 0]], ..DEFAULT_TX.clone() })
+			.unwrap();
+
+		// when
+		let evicted = pool.enforce_limits(&unlimited, &unlimited, &sender);
+
+		// then
+		assert_eq!(evicted.sender_limit.iter().map(|tx| tx.hash).collect::<Vec<_>>(), vec![3]);
+		assert!(evicted.ready_limit.is_empty());
+		assert_eq!(pool.ready.len(), 3);
+		assert!(pool.ready_by_hash(&3).is_none());
+	}
+
+	#[test]
+	fn should_evict_from_heaviest_sender_first() {
+		// given
+		let mut pool = BasePool::with_sender_tag_prefix_len(false, 1);
+		let sender = Limit { count: 100, total_bytes: 1000 };
+		for (hash, nonce) in [(1, 0), (2, 1), (3, 2)] {
+			pool.import(Transaction {
+				hash,
+				priority: 10,
+				provides: vec![vec![1, nonce]],
+				..DEFAULT_TX.clone()
+			})
+			.unwrap();
+		}
+		pool.import(Transaction {
+			hash: 4,
+			priority: 1,
+			provides: vec![vec![2, 0]],
+			..DEFAULT_TX.clone()
+		})
+		.unwrap();
+
+		// when
+		let evicted = pool.enforce_limits(
+			&Limit { count: 3, total_bytes: 1000 },
+			&Limit { count: 100, total_bytes: 1000 },
+			&sender,
+		);
+
+		// then
+		assert_eq!(evicted.ready_limit.iter().map(|tx| tx.hash).collect::<Vec<_>>(), vec![1]);
+		assert_eq!(pool.ready.len(), 3);
+		assert!(pool.ready_by_hash(&4).is_some());
+	}
+
+	#[test]
+	fn should_keep_sender_usage_up_to_date() {
+		// given
+		let mut pool = BasePool::with_sender_tag_prefix_len(false, 1);
+		let sender = Limit { count: 2, total_bytes: 1000 };
+		let unlimited = Limit { count: 100, total_bytes: 1000 };
+		pool.import(Transaction {
+			hash: 1,
+			requires: vec![vec![1, 0]],
+			provides: vec![vec![1, 1]],
+			..DEFAULT_TX.clone()
+		})
+		.unwrap();
+		pool.import(Transaction { hash: 2, provides: vec![vec![1, 0]], ..DEFAULT_TX.clone() })
+			.unwrap();
+		assert!(pool.enforce_limits(&unlimited, &unlimited, &sender).sender_limit.is_empty());
+
+		// when
+		pool.prune_tags(vec![vec![1, 0]]);
+		pool.import(Transaction {
+			hash: 3,
+			requires: vec![vec![1, 1]],
+			provides: vec![vec![1, 2]],
+			..DEFAULT_TX.clone()
+		})
+		.unwrap();
+		let evicted = pool.enforce_limits(&unlimited, &unlimited, &sender);
+
+		// then
+		assert!(evicted.sender_limit.is_empty());
+		assert_eq!(pool.ready_senders.usage(&[1]), (2, 2));
+		assert_eq!(pool.future_senders.usage(&[1]), (0, 0));
+	}
 }
@@ -18,7 +18,7 @@
 
 use std::{collections::HashMap, sync::Arc, time::Duration};
 
-use crate::LOG_TARGET;
+use crate::{journal::JournalOptions, metrics::MetricsLink, TransactionPoolType, LOG_TARGET};
 use futures::{channel::mpsc::Receiver, Future};
 use sc_transaction_pool_api::error;
 use sp_blockchain::TreeRoute;
@@ -115,10 +115,18 @@ pub struct Options {
 	pub ready: base::Limit,
 	/// Future queue limits.
 	pub future: base::Limit,
+	/// Limits of the transactions of a single sender, across both queues.
+	pub sender: base::SenderLimit,
 	/// Reject future transactions.
 	pub reject_future_transactions: bool,
 	/// How long the extrinsic is banned for.
 	pub ban_time: Duration,
+	/// Journal persisting the pool across restarts, disabled if `None`.
+	pub journal: Option<JournalOptions>,
+	/// Implementation of the pool used by a full node, see [`FullTransactionPool`].
+	///
+	/// [`FullTransactionPool`]: crate::FullTransactionPool
+	pub pool_type: TransactionPoolType,
 }
 
 impl Default for Options {
@@ -126,8 +134,14 @@ impl Default for Options {
 		Self {
 			ready: base::Limit { count: 8192, total_bytes: 20 * 1024 * 1024 },
 			future: base::Limit { count: 512, total_bytes: 1 * 1024 * 1024 },
+			sender: base::SenderLimit {
+				tag_prefix_len: base::DEFAULT_SENDER_TAG_PREFIX_LEN,
+				limit: base::Limit { count: 1024, total_bytes: 4 * 1024 * 1024 },
+			},
 			reject_future_transactions: false,
 			ban_time: Duration::from_secs(60 * 30),
+			journal: None,
+			pool_type: TransactionPoolType::default(),
 		}
 	}
 }
@@ -151,6 +165,23 @@ impl<B: ChainApi> Pool<B> {
 		Self { validated_pool: Arc::new(ValidatedPool::new(options, is_validator, api)) }
 	}
 
+	/// Create a new transaction pool reporting to the given metrics.
+	pub(crate) fn with_metrics(
+		options: Options,
+		is_validator: IsValidator,
+		api: Arc<B>,
+		metrics: MetricsLink,
+	) -> Self {
+		Self {
+			validated_pool: Arc::new(ValidatedPool::with_metrics(
+				options,
+				is_validator,
+				api,
+				metrics,
+			)),
+		}
+	}
+
 	/// Imports a bunch of unverified extrinsics to the pool
 	pub async fn submit_at(
 		&self,
@@ -234,7 +234,7 @@ impl<Hash: hash::Hash + Member + Serialize, Ex> ReadyTransactions<Hash, Ex> {
 
 	/// Fold a list of ready transactions to compute a single value.
 	pub fn fold<R, F: FnMut(Option<R>, &ReadyTx<Hash, Ex>) -> Option<R>>(
-		&mut self,
+		&self,
 		f: F,
 	) -> Option<R> {
 		self.ready.read().values().fold(None, f)
@@ -250,6 +250,11 @@ impl<Hash: hash::Hash + Member + Serialize, Ex> ReadyTransactions<Hash, Ex> {
 		self.by_hashes(&[hash.clone()]).into_iter().next().unwrap_or(None)
 	}
 
+	/// Retrieve transaction by hash, together with its insertion id.
+	pub fn ref_by_hash(&self, hash: &Hash) -> Option<TransactionRef<Hash, Ex>> {
+		self.ready.read().get(hash).map(|x| x.transaction.clone())
+	}
+
 	/// Retrieve transactions by hash
 	pub fn by_hashes(&self, hashes: &[Hash]) -> Vec<Option<Arc<Transaction<Hash, Ex>>>> {
 		let ready = self.ready.read();
@@ -0,0 +1,480 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! On-disk journal of the pool transactions.
+//!
+//! The journal keeps a snapshot of the ready and future transactions, so they can be replayed
+//! after the node restarts. Replayed transactions are validated again, like any other incoming
+//! transaction, and the ones older than the configured maximum age are discarded.
+//!
+//! The snapshot is taken at most once every [`PERSIST_INTERVAL`] best blocks, and only if the
+//! set of pooled transactions changed since the previous one. Writing it to disk happens on a
+//! separate blocking task, so the pool maintenance is never stalled by the file system.
+
+use crate::{
+	graph::{ChainApi, ExtrinsicFor, ExtrinsicHash},
+	LOG_TARGET,
+};
+use codec::{Decode, Encode};
+use futures::{
+	channel::mpsc::{channel, Receiver, Sender},
+	future::BoxFuture,
+	Future, FutureExt, StreamExt,
+};
+use parking_lot::Mutex;
+use sp_runtime::transaction_validity::TransactionSource;
+use std::{
+	collections::HashMap,
+	fs, io,
+	path::PathBuf,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc,
+	},
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Number of best blocks between two snapshots of the pool.
+const PERSIST_INTERVAL: u32 = 10;
+
+/// A transaction kept in the pool, as stored in the journal.
+pub(crate) type PooledTransaction<B> = (ExtrinsicHash<B>, TransactionSource, ExtrinsicFor<B>);
+
+/// Result of submitting the replayed transactions to the pool.
+type SubmitResult<B> =
+	Result<Vec<Result<ExtrinsicHash<B>, <B as ChainApi>::Error>>, <B as ChainApi>::Error>;
+
+/// Configuration of the transaction pool journal.
+#[derive(Debug, Clone)]
+pub struct JournalOptions {
+	/// Path of the journal file.
+	pub path: PathBuf,
+	/// Transactions older than this are not replayed.
+	pub max_age: Duration,
+}
+
+/// A single transaction stored in the journal.
+#[derive(Encode, Decode)]
+struct JournalEntry {
+	/// Source of the transaction.
+	source: TransactionSource,
+	/// Seconds since the UNIX epoch at which the transaction entered the pool.
+	inserted_at: u64,
+	/// Encoded extrinsic.
+	data: Vec<u8>,
+}
+
+fn now() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map(|d| d.as_secs())
+		.unwrap_or_default()
+}
+
+/// The journal file, shared by the [`Journal`] and its background writer.
+struct JournalFile {
+	path: PathBuf,
+	/// Generation of the last snapshot written to the file.
+	written: Mutex<u64>,
+}
+
+impl JournalFile {
+	/// Writes the snapshot, unless a newer one was written already.
+	fn write(&self, generation: u64, bytes: &[u8]) {
+		let mut written = self.written.lock();
+		if generation <= *written {
+			return
+		}
+
+		if let Err(e) = self.write_atomically(bytes) {
+			log::warn!(
+				target: LOG_TARGET,
+				"Failed to write transaction pool journal {}: {}",
+				self.path.display(),
+				e,
+			);
+		}
+		*written = generation;
+	}
+
+	fn write_atomically(&self, bytes: &[u8]) -> io::Result<()> {
+		if let Some(dir) = self.path.parent() {
+			fs::create_dir_all(dir)?;
+		}
+		// write to a temporary file first, so a crash never leaves a truncated journal behind
+		let tmp_path = self.path.with_extension("tmp");
+		fs::write(&tmp_path, bytes)?;
+		fs::rename(&tmp_path, &self.path)
+	}
+}
+
+/// Writes the snapshots sent by the [`Journal`] to disk, until the journal is dropped.
+async fn writer_task(file: Arc<JournalFile>, mut snapshots: Receiver<(u64, Vec<u8>)>) {
+	while let Some((generation, bytes)) = snapshots.next().await {
+		file.write(generation, &bytes);
+	}
+}
+
+/// Journal of the transactions kept in the pool.
+pub(crate) struct Journal<B: ChainApi> {
+	file: Arc<JournalFile>,
+	/// Insertion times of the journaled transactions.
+	inserted_at: Mutex<HashMap<ExtrinsicHash<B>, u64>>,
+	/// Transactions loaded from disk, waiting to be replayed.
+	pending_replay: Mutex<Option<Vec<(TransactionSource, ExtrinsicFor<B>)>>>,
+	/// Number of best blocks since the last snapshot.
+	blocks_since_snapshot: Mutex<u32>,
+	/// Generation of the last snapshot taken.
+	generation: AtomicU64,
+	/// Sends the snapshots to the background writer.
+	writer: Sender<(u64, Vec<u8>)>,
+}
+
+impl<B: ChainApi> Journal<B> {
+	/// Opens the journal, loading the transactions that are young enough to be replayed.
+	///
+	/// Returns the journal and the background task writing its snapshots to disk.
+	pub(crate) fn open(options: JournalOptions, api: &B) -> (Self, BoxFuture<'static, ()>) {
+		let entries = match fs::read(&options.path) {
+			Ok(bytes) => Vec::<JournalEntry>::decode(&mut &bytes[..]).unwrap_or_else(|e| {
+				log::warn!(
+					target: LOG_TARGET,
+					"Failed to decode transaction pool journal {}: {}",
+					options.path.display(),
+					e,
+				);
+				Vec::new()
+			}),
+			Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+			Err(e) => {
+				log::warn!(
+					target: LOG_TARGET,
+					"Failed to read transaction pool journal {}: {}",
+					options.path.display(),
+					e,
+				);
+				Vec::new()
+			},
+		};
+
+		let oldest = now().saturating_sub(options.max_age.as_secs());
+		let total = entries.len();
+		let mut inserted_at = HashMap::new();
+		let mut pending_replay = Vec::new();
+		for entry in entries.into_iter().filter(|entry| entry.inserted_at >= oldest) {
+			match ExtrinsicFor::<B>::decode(&mut &entry.data[..]) {
+				Ok(xt) => {
+					inserted_at.insert(api.hash_and_length(&xt).0, entry.inserted_at);
+					pending_replay.push((entry.source, xt));
+				},
+				Err(e) => log::debug!(target: LOG_TARGET, "Skipping journal entry: {}", e),
+			}
+		}
+
+		log::info!(
+			target: LOG_TARGET,
+			"Loaded {} of {} transactions from the pool journal",
+			pending_replay.len(),
+			total,
+		);
+
+		// a single pending snapshot is enough, a newer one is taken on a later block anyway
+		let file = Arc::new(JournalFile { path: options.path, written: Mutex::new(0) });
+		let (writer, snapshots) = channel(0);
+		let background = writer_task(file.clone(), snapshots).boxed();
+		let journal = Self {
+			file,
+			inserted_at: Mutex::new(inserted_at),
+			pending_replay: Mutex::new(Some(pending_replay)),
+			blocks_since_snapshot: Mutex::new(0),
+			generation: AtomicU64::new(0),
+			writer,
+		};
+
+		(journal, background)
+	}
+
+	/// Re-validates the loaded transactions and imports the valid ones, by passing them to
+	/// `submit` grouped by their source.
+	///
+	/// Only the first call does anything.
+	pub(crate) async fn replay<F, Fut>(&self, mut submit: F)
+	where
+		F: FnMut(TransactionSource, Vec<ExtrinsicFor<B>>) -> Fut,
+		Fut: Future<Output = SubmitResult<B>>,
+	{
+		let pending = match self.pending_replay.lock().take() {
+			Some(pending) if !pending.is_empty() => pending,
+			_ => return,
+		};
+
+		let mut imported = 0;
+		for source in
+			[TransactionSource::InBlock, TransactionSource::Local, TransactionSource::External]
+		{
+			let xts = pending
+				.iter()
+				.filter(|(tx_source, _)| *tx_source == source)
+				.map(|(_, xt)| xt.clone())
+				.collect::<Vec<_>>();
+			if xts.is_empty() {
+				continue
+			}
+
+			match submit(source, xts).await {
+				Ok(results) => imported += results.iter().filter(|r| r.is_ok()).count(),
+				Err(e) =>
+					log::warn!(target: LOG_TARGET, "Failed to replay the pool journal: {}", e),
+			}
+		}
+
+		log::info!(
+			target: LOG_TARGET,
+			"Replayed {} of {} transactions from the pool journal",
+			imported,
+			pending.len(),
+		);
+	}
+
+	/// Notifies the journal about a new best block.
+	///
+	/// Every [`PERSIST_INTERVAL`] blocks, sends a snapshot of the pool to the background writer if
+	/// the pool changed since the previous one. `transactions` returns all of the transactions
+	/// kept in the pool.
+	pub(crate) fn on_best_block(&self, transactions: impl FnOnce() -> Vec<PooledTransaction<B>>) {
+		{
+			let mut blocks = self.blocks_since_snapshot.lock();
+			*blocks += 1;
+			if *blocks < PERSIST_INTERVAL {
+				return
+			}
+		}
+
+		let mut writer = self.writer.clone();
+		let sent = self.snapshot(transactions, |snapshot| match writer.try_send(snapshot) {
+			Ok(()) => true,
+			Err(e) => {
+				log::debug!(
+					target: LOG_TARGET,
+					"Transaction pool journal writer is busy, skipping snapshot: {}",
+					e,
+				);
+				false
+			},
+		});
+		if sent {
+			*self.blocks_since_snapshot.lock() = 0;
+		}
+	}
+
+	/// Writes the current content of the pool to disk right away, if it changed since the last
+	/// snapshot.
+	///
+	/// Used when the pool shuts down, so the changes since the last snapshot are not lost.
+	pub(crate) fn flush(&self, transactions: impl FnOnce() -> Vec<PooledTransaction<B>>) {
+		self.snapshot(transactions, |(generation, bytes)| {
+			self.file.write(generation, &bytes);
+			true
+		});
+	}
+
+	/// Takes a snapshot of the pool and passes the encoded journal, with its generation, to
+	/// `store`.
+	///
+	/// Does nothing until the loaded transactions are replayed, so the journal is not overwritten
+	/// by an empty pool, or if the set of pooled transactions did not change since the last stored
+	/// snapshot. Returns `true` if the pool is up to date on disk, or will be once `store` is done.
+	fn snapshot(
+		&self,
+		transactions: impl FnOnce() -> Vec<PooledTransaction<B>>,
+		store: impl FnOnce((u64, Vec<u8>)) -> bool,
+	) -> bool {
+		if self.pending_replay.lock().as_ref().map_or(false, |pending| !pending.is_empty()) {
+			return false
+		}
+
+		let transactions = transactions();
+		let mut inserted_at = self.inserted_at.lock();
+		if transactions.len() == inserted_at.len() &&
+			transactions.iter().all(|(hash, _, _)| inserted_at.contains_key(hash))
+		{
+			return true
+		}
+
+		let now = now();
+		let mut journaled = HashMap::with_capacity(transactions.len());
+		let entries = transactions
+			.into_iter()
+			.map(|(hash, source, xt)| {
+				let inserted_at = inserted_at.get(&hash).copied().unwrap_or(now);
+				journaled.insert(hash, inserted_at);
+				JournalEntry { source, inserted_at, data: xt.encode() }
+			})
+			.collect::<Vec<_>>();
+
+		let generation = self.generation.fetch_add(1, Ordering::Relaxed) + 1;
+		let stored = store((generation, entries.encode()));
+		if stored {
+			*inserted_at = journaled;
+		}
+		stored
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		graph::Pool,
+		tests::{pool, uxt, TestApi},
+	};
+	use futures::executor::block_on;
+	use sp_runtime::generic::BlockId;
+	use substrate_test_runtime::{AccountId, Transfer, H256};
+	use substrate_test_runtime_client::AccountKeyring::Alice;
+
+	fn transfer(nonce: u64) -> ExtrinsicFor<TestApi> {
+		uxt(Transfer {
+			from: Alice.into(),
+			to: AccountId::from_h256(H256::from_low_u64_be(2)),
+			amount: 5,
+			nonce,
+		})
+	}
+
+	fn options(dir: &tempfile::TempDir, max_age: Duration) -> JournalOptions {
+		JournalOptions { path: dir.path().join("journal"), max_age }
+	}
+
+	fn open(dir: &tempfile::TempDir) -> Journal<TestApi> {
+		Journal::open(options(dir, Duration::from_secs(60)), &TestApi::default()).0
+	}
+
+	fn transactions(pool: &Pool<TestApi>) -> impl FnOnce() -> Vec<PooledTransaction<TestApi>> + '_ {
+		|| pool.validated_pool().all_transactions()
+	}
+
+	fn replay(journal: &Journal<TestApi>, pool: &Pool<TestApi>) {
+		let at = &BlockId::Number(0);
+		block_on(journal.replay(move |source, xts| pool.resubmit_at(at, source, xts)));
+	}
+
+	#[test]
+	fn should_replay_persisted_transactions() {
+		// given
+		let dir = tempfile::tempdir().unwrap();
+		let pool = pool();
+		let journal = open(&dir);
+		block_on(pool.submit_one(&BlockId::Number(0), TransactionSource::External, transfer(0)))
+			.unwrap();
+		block_on(pool.submit_one(&BlockId::Number(0), TransactionSource::Local, transfer(1)))
+			.unwrap();
+		journal.flush(transactions(&pool));
+
+		// when
+		let restarted = pool();
+		let journal = open(&dir);
+		replay(&journal, &restarted);
+
+		// then
+		assert_eq!(restarted.validated_pool().status().ready, 2);
+	}
+
+	#[test]
+	fn should_not_replay_expired_transactions() {
+		// given
+		let dir = tempfile::tempdir().unwrap();
+		let entry = JournalEntry {
+			source: TransactionSource::External,
+			inserted_at: now() - 120,
+			data: transfer(0).encode(),
+		};
+		fs::write(dir.path().join("journal"), vec![entry].encode()).unwrap();
+
+		// when
+		let pool = pool();
+		let journal = open(&dir);
+		replay(&journal, &pool);
+
+		// then
+		assert_eq!(pool.validated_pool().status().ready, 0);
+	}
+
+	#[test]
+	fn should_not_overwrite_journal_before_replay() {
+		// given
+		let dir = tempfile::tempdir().unwrap();
+		let pool = pool();
+		block_on(pool.submit_one(&BlockId::Number(0), TransactionSource::External, transfer(0)))
+			.unwrap();
+		open(&dir).flush(transactions(&pool));
+
+		// when
+		let journal = open(&dir);
+		journal.flush(transactions(&pool()));
+
+		// then
+		let restarted = pool();
+		replay(&journal, &restarted);
+		assert_eq!(restarted.validated_pool().status().ready, 1);
+	}
+
+	#[test]
+	fn should_not_write_unchanged_pool() {
+		// given
+		let dir = tempfile::tempdir().unwrap();
+		let pool = pool();
+		let journal = open(&dir);
+		block_on(pool.submit_one(&BlockId::Number(0), TransactionSource::External, transfer(0)))
+			.unwrap();
+		journal.flush(transactions(&pool));
+		fs::remove_file(dir.path().join("journal")).unwrap();
+
+		// when
+		journal.flush(transactions(&pool));
+
+		// then
+		assert!(!dir.path().join("journal").exists());
+	}
+
+	#[test]
+	fn should_persist_in_background_every_interval() {
+		// given
+		let dir = tempfile::tempdir().unwrap();
+		let pool = pool();
+		let (journal, background) =
+			Journal::open(options(&dir, Duration::from_secs(60)), &TestApi::default());
+		block_on(pool.submit_one(&BlockId::Number(0), TransactionSource::External, transfer(0)))
+			.unwrap();
+
+		// when
+		for _ in 1..PERSIST_INTERVAL {
+			journal.on_best_block(transactions(&pool));
+		}
+		assert_eq!(*journal.blocks_since_snapshot.lock(), PERSIST_INTERVAL - 1);
+		journal.on_best_block(transactions(&pool));
+		assert_eq!(*journal.blocks_since_snapshot.lock(), 0);
+		drop(journal);
+		block_on(background);
+
+		// then
+		let restarted = pool();
+		replay(&open(&dir), &restarted);
+		assert_eq!(restarted.validated_pool().status().ready, 1);
+	}
+}
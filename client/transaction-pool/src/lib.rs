@@ -23,9 +23,12 @@
 #![warn(unused_extern_crates)]
 
 mod api;
+mod builder;
 mod enactment_state;
 pub mod error;
+mod fork_aware;
 mod graph;
+mod journal;
 mod metrics;
 mod revalidation;
 #[cfg(test)]
@@ -33,15 +36,19 @@ mod tests;
 
 pub use crate::api::FullChainApi;
 use async_trait::async_trait;
+pub use builder::{FullTransactionPool, TransactionPoolType};
 use enactment_state::{EnactmentAction, EnactmentState};
+pub use fork_aware::{ForkAwarePool, FullForkAwarePool};
 use futures::{
 	channel::oneshot,
 	future::{self, ready},
 	prelude::*,
 };
 pub use graph::{
-	base_pool::Limit as PoolLimit, ChainApi, Options, Pool, Transaction, ValidatedTransaction,
+	base_pool::{Limit as PoolLimit, SenderLimit as PoolSenderLimit},
+	ChainApi, Options, Pool, Transaction, ValidatedTransaction,
 };
+pub use journal::JournalOptions;
 use parking_lot::Mutex;
 use std::{
 	collections::{HashMap, HashSet},
@@ -50,6 +57,7 @@ use std::{
 };
 
 use graph::{ExtrinsicHash, IsValidator};
+use journal::Journal;
 use sc_transaction_pool_api::{
 	error::Error as TxPoolError, ChainEvent, ImportNotificationStream, MaintainedTransactionPool,
 	PoolFuture, PoolStatus, ReadyTransactions, TransactionFor, TransactionPool, TransactionSource,
@@ -93,6 +101,7 @@ where
 	ready_poll: Arc<Mutex<ReadyPoll<ReadyIteratorFor<PoolApi>, Block>>>,
 	metrics: PrometheusMetrics,
 	enactment_state: Arc<Mutex<EnactmentState<Block>>>,
+	journal: Option<Journal<PoolApi>>,
 }
 
 struct ReadyPoll<T, Block: BlockT> {
@@ -180,6 +189,7 @@ where
 					best_block_hash,
 					finalized_hash,
 				))),
+				journal: None,
 			},
 			background_task,
 		)
@@ -198,7 +208,18 @@ where
 		best_block_hash: Block::Hash,
 		finalized_hash: Block::Hash,
 	) -> Self {
-		let pool = Arc::new(graph::Pool::new(options, is_validator, pool_api.clone()));
+		let journal = options.journal.clone().map(|options| {
+			let (journal, writer) = Journal::open(options, &*pool_api);
+			spawner.spawn_essential_blocking("txpool-journal", Some("transaction-pool"), writer);
+			journal
+		});
+		let metrics = PrometheusMetrics::new(prometheus);
+		let pool = Arc::new(graph::Pool::with_metrics(
+			options,
+			is_validator,
+			pool_api.clone(),
+			metrics.clone(),
+		));
 		let (revalidation_queue, background_task) = match revalidation_type {
 			RevalidationType::Light =>
 				(revalidation::RevalidationQueue::new(pool_api.clone(), pool.clone()), None),
@@ -223,11 +244,12 @@ where
 				RevalidationType::Full => RevalidationStrategy::Always,
 			})),
 			ready_poll: Arc::new(Mutex::new(ReadyPoll::new(best_block_number))),
-			metrics: PrometheusMetrics::new(prometheus),
+			metrics,
 			enactment_state: Arc::new(Mutex::new(EnactmentState::new(
 				best_block_hash,
 				finalized_hash,
 			))),
+			journal,
 		}
 	}
 
@@ -725,6 +747,8 @@ where
 	PoolApi: 'static + graph::ChainApi<Block = Block>,
 {
 	async fn maintain(&self, event: ChainEvent<Self::Block>) {
+		let block_hash = event.hash();
+		let is_new_best = matches!(event, ChainEvent::NewBestBlock { .. });
 		let prev_finalized_block = self.enactment_state.lock().recent_finalized_block();
 		let compute_tree_route = |from, to| -> Result<TreeRoute<Block>, String> {
 			match self.api.tree_route(from, to) {
@@ -772,6 +796,26 @@ where
 				}
 			}
 		}
+
+		if let Some(ref journal) = self.journal {
+			let (pool, at) = (&self.pool, &BlockId::Hash(block_hash));
+			journal.replay(move |source, xts| pool.resubmit_at(at, source, xts)).await;
+			if is_new_best {
+				journal.on_best_block(|| pool.validated_pool().all_transactions());
+			}
+		}
+	}
+}
+
+impl<PoolApi, Block> Drop for BasicPool<PoolApi, Block>
+where
+	Block: BlockT,
+	PoolApi: graph::ChainApi<Block = Block>,
+{
+	fn drop(&mut self) {
+		if let Some(ref journal) = self.journal {
+			journal.flush(|| self.pool.validated_pool().all_transactions());
+		}
 	}
 }
 
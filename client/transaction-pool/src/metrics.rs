@@ -20,7 +20,7 @@
 
 use std::sync::Arc;
 
-use prometheus_endpoint::{register, Counter, PrometheusError, Registry, U64};
+use prometheus_endpoint::{register, Counter, CounterVec, Opts, PrometheusError, Registry, U64};
 
 #[derive(Clone, Default)]
 pub struct MetricsLink(Arc<Option<Metrics>>);
@@ -49,6 +49,7 @@ pub struct Metrics {
 	pub validations_invalid: Counter<U64>,
 	pub block_transactions_pruned: Counter<U64>,
 	pub block_transactions_resubmitted: Counter<U64>,
+	pub evicted_transactions: CounterVec<U64>,
 }
 
 impl Metrics {
@@ -82,6 +83,16 @@ impl Metrics {
 				)?,
 				registry,
 			)?,
+			evicted_transactions: register(
+				CounterVec::new(
+					Opts::new(
+						"substrate_sub_txpool_evicted_transactions",
+						"Total number of transactions evicted from the pool to enforce its limits",
+					),
+					&["reason"],
+				)?,
+				registry,
+			)?,
 		})
 	}
 }
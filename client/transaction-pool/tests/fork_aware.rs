@@ -0,0 +1,236 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tests for the fork-aware transaction pool.
+
+use futures::executor::{block_on, block_on_stream};
+use sc_transaction_pool::ForkAwarePool;
+use sc_transaction_pool_api::{
+	ChainEvent, MaintainedTransactionPool, TransactionPool, TransactionStatus,
+};
+use sp_runtime::{generic::BlockId, transaction_validity::TransactionSource};
+use std::sync::Arc;
+use substrate_test_runtime_client::{
+	runtime::{Block, Hash, Header, TransferData},
+	AccountKeyring::*,
+};
+use substrate_test_runtime_transaction_pool::{uxt, TestApi};
+
+const SOURCE: TransactionSource = TransactionSource::External;
+
+fn genesis_hash(api: &TestApi) -> Hash {
+	api.chain()
+		.read()
+		.block_by_number
+		.get(&0)
+		.map(|blocks| blocks[0].0.header.hash())
+		.expect("there is block 0. qed")
+}
+
+fn pool() -> (ForkAwarePool<TestApi, Block>, Arc<TestApi>) {
+	let api = Arc::new(TestApi::with_alice_nonce(209));
+	let genesis = genesis_hash(&api);
+	(ForkAwarePool::new_test(api.clone(), genesis, genesis), api)
+}
+
+fn new_best_block_event(
+	pool: &ForkAwarePool<TestApi, Block>,
+	from: Option<Hash>,
+	to: &Header,
+) -> ChainEvent<Block> {
+	ChainEvent::NewBestBlock {
+		hash: to.hash(),
+		tree_route: from.map(|from| {
+			Arc::new(pool.api().tree_route(from, to.parent_hash).expect("Tree route exists"))
+		}),
+	}
+}
+
+fn finalized_block_event(hash: Hash) -> ChainEvent<Block> {
+	ChainEvent::Finalized { hash, tree_route: Arc::from(vec![]) }
+}
+
+fn ready_nonces(pool: &ForkAwarePool<TestApi, Block>, at: &Header) -> Vec<u64> {
+	block_on(pool.ready_at_block(at.hash(), at.number))
+		.map(|tx| TransferData::try_from(&tx.data).unwrap().nonce)
+		.collect()
+}
+
+#[test]
+fn submission_should_work() {
+	let (pool, _api) = pool();
+	block_on(pool.submit_one(&BlockId::number(0), SOURCE, uxt(Alice, 209))).unwrap();
+	block_on(pool.submit_one(&BlockId::number(0), SOURCE, uxt(Alice, 210))).unwrap();
+
+	assert_eq!(pool.status().ready, 2);
+	assert_eq!(pool.views().len(), 1);
+}
+
+#[test]
+fn invalid_transaction_should_be_rejected() {
+	let (pool, api) = pool();
+	let xt = uxt(Alice, 209);
+	api.add_invalid(&xt);
+
+	assert!(block_on(pool.submit_one(&BlockId::number(0), SOURCE, xt)).is_err());
+	assert_eq!(pool.status().ready, 0);
+}
+
+#[test]
+fn view_should_be_built_for_new_best_block() {
+	let (pool, api) = pool();
+	block_on(pool.submit_one(&BlockId::number(0), SOURCE, uxt(Alice, 209))).unwrap();
+	block_on(pool.submit_one(&BlockId::number(0), SOURCE, uxt(Alice, 210))).unwrap();
+
+	let header = api.push_block(1, vec![uxt(Alice, 209)], true);
+	api.increment_nonce(Alice.into());
+	block_on(pool.maintain(new_best_block_event(&pool, None, &header)));
+
+	assert_eq!(pool.views(), vec![header.hash()]);
+	assert_eq!(ready_nonces(&pool, &header), vec![210]);
+}
+
+#[test]
+fn views_should_be_kept_for_every_fork_tip() {
+	let (pool, api) = pool();
+	block_on(pool.submit_one(&BlockId::number(0), SOURCE, uxt(Alice, 209))).unwrap();
+
+	let a1 = api.push_block(1, vec![uxt(Alice, 209)], true);
+	block_on(pool.maintain(new_best_block_event(&pool, None, &a1)));
+
+	let b1 = api.push_block_with_parent(genesis_hash(&api), vec![], true);
+	block_on(pool.maintain(new_best_block_event(&pool, Some(a1.hash()), &b1)));
+
+	let mut views = pool.views();
+	views.sort();
+	let mut expected = vec![a1.hash(), b1.hash()];
+	expected.sort();
+	assert_eq!(views, expected);
+
+	// the transaction is included on the `a` fork only
+	assert_eq!(ready_nonces(&pool, &a1), Vec::<u64>::new());
+	assert_eq!(ready_nonces(&pool, &b1), vec![209]);
+	assert_eq!(pool.status().ready, 1);
+}
+
+#[test]
+fn finalization_should_drop_views_of_other_forks() {
+	let (pool, api) = pool();
+
+	let a1 = api.push_block(1, vec![], true);
+	block_on(pool.maintain(new_best_block_event(&pool, None, &a1)));
+	let b1 = api.push_block_with_parent(genesis_hash(&api), vec![], true);
+	block_on(pool.maintain(new_best_block_event(&pool, Some(a1.hash()), &b1)));
+	assert_eq!(pool.views().len(), 2);
+
+	block_on(pool.maintain(finalized_block_event(b1.hash())));
+
+	assert_eq!(pool.views(), vec![b1.hash()]);
+}
+
+#[test]
+fn ready_at_block_should_build_view_for_untracked_block() {
+	let (pool, api) = pool();
+	block_on(pool.submit_one(&BlockId::number(0), SOURCE, uxt(Alice, 209))).unwrap();
+
+	let header = api.push_block(1, vec![], true);
+
+	assert_eq!(ready_nonces(&pool, &header), vec![209]);
+	assert!(pool.views().contains(&header.hash()));
+}
+
+#[test]
+fn ready_at_block_should_build_view_from_nearest_ancestor() {
+	let (pool, api) = pool();
+	block_on(pool.submit_one(&BlockId::number(0), SOURCE, uxt(Alice, 209))).unwrap();
+
+	let a1 = api.push_block(1, vec![], true);
+	block_on(pool.maintain(new_best_block_event(&pool, None, &a1)));
+
+	// `a2` includes the transaction but never becomes the best block of the pool, `b2` is only
+	// there so the test api validates transactions at height 2
+	let a2 = api.push_block_with_parent(a1.hash(), vec![uxt(Alice, 209)], false);
+	let _b2 = api.push_block_with_parent(a1.hash(), vec![], true);
+
+	assert_eq!(ready_nonces(&pool, &a2), Vec::<u64>::new());
+	assert_eq!(ready_nonces(&pool, &a1), vec![209]);
+	assert_eq!(pool.view_status(&a2.hash()).map(|status| status.ready), Some(0));
+}
+
+#[test]
+fn watcher_should_report_inclusion_and_finalization() {
+	let (pool, api) = pool();
+	let xt = uxt(Alice, 209);
+	let watcher = block_on(pool.submit_and_watch(&BlockId::number(0), SOURCE, xt.clone())).unwrap();
+
+	let header = api.push_block(1, vec![xt], true);
+	block_on(pool.maintain(new_best_block_event(&pool, None, &header)));
+	block_on(pool.maintain(finalized_block_event(header.hash())));
+
+	assert_eq!(
+		block_on_stream(watcher).collect::<Vec<_>>(),
+		vec![
+			TransactionStatus::Ready,
+			TransactionStatus::InBlock((header.hash(), 0)),
+			TransactionStatus::Finalized((header.hash(), 0)),
+		],
+	);
+}
+
+#[test]
+fn watcher_should_report_retraction() {
+	let (pool, api) = pool();
+	let xt = uxt(Alice, 209);
+	let watcher = block_on(pool.submit_and_watch(&BlockId::number(0), SOURCE, xt.clone())).unwrap();
+
+	let a1 = api.push_block(1, vec![xt.clone()], true);
+	block_on(pool.maintain(new_best_block_event(&pool, None, &a1)));
+
+	let b1 = api.push_block_with_parent(genesis_hash(&api), vec![], true);
+	let b2 = api.push_block_with_parent(b1.hash(), vec![xt], true);
+	block_on(pool.maintain(new_best_block_event(&pool, Some(a1.hash()), &b2)));
+	block_on(pool.maintain(finalized_block_event(b2.hash())));
+
+	assert_eq!(
+		block_on_stream(watcher).collect::<Vec<_>>(),
+		vec![
+			TransactionStatus::Ready,
+			TransactionStatus::InBlock((a1.hash(), 0)),
+			TransactionStatus::Retracted(a1.hash()),
+			TransactionStatus::InBlock((b2.hash(), 0)),
+			TransactionStatus::Finalized((b2.hash(), 0)),
+		],
+	);
+}
+
+#[test]
+fn watcher_should_report_invalid_when_dropped_from_all_views() {
+	let (pool, api) = pool();
+	let xt = uxt(Alice, 209);
+	let watcher = block_on(pool.submit_and_watch(&BlockId::number(0), SOURCE, xt.clone())).unwrap();
+
+	api.add_invalid(&xt);
+	let header = api.push_block(1, vec![], true);
+	block_on(pool.maintain(new_best_block_event(&pool, None, &header)));
+
+	assert_eq!(pool.status().ready, 0);
+	assert_eq!(
+		block_on_stream(watcher).collect::<Vec<_>>(),
+		vec![TransactionStatus::Ready, TransactionStatus::Invalid],
+	);
+}
@@ -18,7 +18,10 @@
 //! Test utilities
 
 pub use sp_core::H256;
-use sp_runtime::traits::Hash;
+use sp_runtime::{
+	testing::{TestSignature, UintAuthorityId},
+	traits::Hash,
+};
 pub use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup},
 	BuildStorage,
@@ -51,6 +54,7 @@ impl frame_system::Config for Test {
 	type BlockLength = ();
 	type RuntimeOrigin = RuntimeOrigin;
 	type RuntimeCall = RuntimeCall;
+	type RuntimeTask = RuntimeTask;
 	type Nonce = u64;
 	type Hash = H256;
 	type Hashing = BlakeTwo256;
@@ -143,9 +147,25 @@ impl pallet_identity::Config for Test {
 	type Slashed = ();
 	type RegistrarOrigin = EnsureOneOrRoot;
 	type ForceOrigin = EnsureTwoOrRoot;
+	type OffchainSignature = TestSignature;
+	type SigningPublicKey = UintAuthorityId;
+	type UsernameAuthorityOrigin = EnsureOneOrRoot;
+	type PendingUsernameExpiration = frame_support::traits::ConstU64<100>;
+	type MaxSuffixLength = frame_support::traits::ConstU32<7>;
+	type MaxUsernameLength = frame_support::traits::ConstU32<32>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = Self;
 	type WeightInfo = ();
 }
 
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_identity::BenchmarkHelper<UintAuthorityId, TestSignature> for Test {
+	fn sign_message(message: &[u8]) -> (UintAuthorityId, TestSignature) {
+		let public = UintAuthorityId(42);
+		(public.clone(), TestSignature(public.0, message.to_vec()))
+	}
+}
+
 pub struct AllianceIdentityVerifier;
 impl IdentityVerifier<AccountId> for AllianceIdentityVerifier {
 	fn has_identity(who: &AccountId, fields: u64) -> bool {
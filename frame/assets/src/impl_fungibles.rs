@@ -121,7 +121,13 @@ impl<T: Config<I>, I: 'static> fungibles::Balanced<<T as SystemConfig>::AccountI
 }
 
 impl<T: Config<I>, I: 'static> fungibles::Unbalanced<T::AccountId> for Pallet<T, I> {
-	fn handle_raw_dust(_: Self::AssetId, _: Self::Balance) {}
+	fn handle_raw_dust(asset: Self::AssetId, amount: Self::Balance) {
+		Asset::<T, I>::mutate_exists(asset, |maybe_asset| {
+			if let Some(ref mut asset) = maybe_asset {
+				asset.supply.saturating_reduce(amount);
+			}
+		});
+	}
 	fn handle_dust(_: fungibles::Dust<T::AccountId, Self>) {
 		defensive!("`decrease_balance` and `increase_balance` have non-default impls; nothing else calls this; qed");
 	}
@@ -152,7 +158,14 @@ impl<T: Config<I>, I: 'static> fungibles::Unbalanced<T::AccountId> for Pallet<T,
 			keep_alive: preservation != Expendable,
 			best_effort: precision == BestEffort,
 		};
-		Self::decrease_balance(asset, who, amount, f, |_, _| Ok(()))
+		let actual = Self::decrease_balance(asset.clone(), who, amount, f, |_, _| Ok(()))?;
+		// If the account got reaped, `actual` includes the dust it left behind. Nothing else will
+		// account for it, so it is destroyed here and only the requested reduction is reported.
+		let dust = actual.saturating_sub(amount);
+		if !dust.is_zero() {
+			Self::handle_raw_dust(asset, dust);
+		}
+		Ok(actual.saturating_sub(dust))
 	}
 	fn increase_balance(
 		asset: T::AssetId,
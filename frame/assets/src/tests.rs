@@ -17,6 +17,8 @@
 
 //! Tests for Assets pallet.
 
+mod fungibles_conformance_tests;
+
 use super::*;
 use crate::{mock::*, Error};
 use frame_support::{
@@ -0,0 +1,132 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use frame_support::traits::fungibles::conformance_tests;
+use paste::paste;
+
+macro_rules! run_tests {
+	($path:path, $min_balance:expr, $($name:ident),*) => {
+		$(
+			paste! {
+				#[test]
+				fn [< $name _min_balance_ $min_balance >]() {
+					new_test_ext().execute_with(|| {
+						let asset_id = 1;
+						assert_ok!(Assets::force_create(
+							RuntimeOrigin::root(),
+							asset_id,
+							1,
+							true,
+							$min_balance
+						));
+						$path::$name::<
+							Assets,
+							<Test as frame_system::Config>::AccountId,
+						>(asset_id, None);
+					});
+				}
+			}
+		)*
+	};
+}
+
+macro_rules! run_inspect_mutate_tests {
+	($min_balance:expr) => {
+		run_tests!(
+			conformance_tests::inspect_mutate,
+			$min_balance,
+			mint_into_success,
+			mint_into_overflow,
+			mint_into_below_minimum,
+			burn_from_exact_success,
+			burn_from_best_effort_success,
+			burn_from_exact_insufficient_funds,
+			restore_success,
+			restore_overflow,
+			restore_below_minimum,
+			shelve_success,
+			shelve_insufficient_funds,
+			transfer_success,
+			transfer_expendable_all,
+			transfer_expendable_dust,
+			transfer_protect_preserve,
+			set_balance_mint_success,
+			set_balance_burn_success,
+			can_deposit_success,
+			can_deposit_below_minimum,
+			can_deposit_overflow,
+			can_withdraw_success,
+			can_withdraw_reduced_to_zero,
+			can_withdraw_balance_low,
+			reducible_balance_expendable,
+			reducible_balance_protect_preserve
+		);
+	};
+}
+
+run_inspect_mutate_tests!(1);
+run_inspect_mutate_tests!(2);
+run_inspect_mutate_tests!(5);
+run_inspect_mutate_tests!(1000);
+
+macro_rules! run_unbalanced_tests {
+	($min_balance:expr) => {
+		run_tests!(
+			conformance_tests::unbalanced,
+			$min_balance,
+			set_total_issuance_success,
+			increase_balance_exact_success,
+			increase_balance_overflow,
+			increase_balance_below_minimum,
+			decrease_balance_exact_success,
+			decrease_balance_insufficient_funds,
+			decrease_balance_preserve,
+			decrease_balance_expendable_dust,
+			deactivate_and_reactivate_success
+		);
+	};
+}
+
+run_unbalanced_tests!(1);
+run_unbalanced_tests!(2);
+run_unbalanced_tests!(5);
+run_unbalanced_tests!(1000);
+
+macro_rules! run_balanced_tests {
+	($min_balance:expr) => {
+		run_tests!(
+			conformance_tests::balanced,
+			$min_balance,
+			issue_and_resolve_credit,
+			issue_and_resolve_credit_below_minimum,
+			rescind_and_settle_debt,
+			rescind_and_settle_debt_insufficient_funds,
+			pair_success,
+			deposit_success,
+			deposit_below_minimum,
+			withdraw_success,
+			withdraw_insufficient_funds,
+			withdraw_expendable_dust
+		);
+	};
+}
+
+run_balanced_tests!(1);
+run_balanced_tests!(2);
+run_balanced_tests!(5);
+run_balanced_tests!(1000);
@@ -20,8 +20,7 @@ use frame_support::traits::fungible::{conformance_tests, Inspect, Mutate};
 use paste::paste;
 
 macro_rules! run_tests {
-    ($path:path, $ext_deposit:expr, $($name:ident),*) => {
-		$(
+	(@single $path:path, $ext_deposit:expr, [$($arg:expr),*], $name:ident) => {
 			paste! {
 				#[test]
 				fn [< $name _existential_deposit_ $ext_deposit _dust_trap_on >]() {
@@ -32,7 +31,7 @@ macro_rules! run_tests {
 						$path::$name::<
 							Balances,
 							<Test as frame_system::Config>::AccountId,
-						>(Some(trap_account));
+						>($($arg,)* Some(trap_account));
 					});
 				}
 
@@ -43,10 +42,19 @@ macro_rules! run_tests {
 						$path::$name::<
 							Balances,
 							<Test as frame_system::Config>::AccountId,
-						>(None);
+						>($($arg,)* None);
 					});
 				}
 			}
+	};
+	($path:path, $ext_deposit:expr, $($name:ident),*) => {
+		$(
+			run_tests!(@single $path, $ext_deposit, [], $name);
+		)*
+	};
+	($path:path, $ext_deposit:expr, $args:tt, $($name:ident),*) => {
+		$(
+			run_tests!(@single $path, $ext_deposit, $args, $name);
 		)*
 	};
 	($path:path, $ext_deposit:expr) => {
@@ -86,3 +94,96 @@ run_tests!(conformance_tests::inspect_mutate, 1);
 run_tests!(conformance_tests::inspect_mutate, 2);
 run_tests!(conformance_tests::inspect_mutate, 5);
 run_tests!(conformance_tests::inspect_mutate, 1000);
+
+macro_rules! run_unbalanced_tests {
+	($ext_deposit:expr) => {
+		run_tests!(
+			conformance_tests::unbalanced,
+			$ext_deposit,
+			write_balance_success,
+			write_balance_below_minimum,
+			set_total_issuance_success,
+			increase_balance_exact_success,
+			increase_balance_overflow,
+			increase_balance_below_minimum,
+			decrease_balance_exact_success,
+			decrease_balance_insufficient_funds,
+			decrease_balance_preserve,
+			decrease_balance_expendable_dust,
+			deactivate_and_reactivate_success
+		);
+	};
+}
+
+run_unbalanced_tests!(1);
+run_unbalanced_tests!(2);
+run_unbalanced_tests!(5);
+run_unbalanced_tests!(1000);
+
+macro_rules! run_balanced_tests {
+	($ext_deposit:expr) => {
+		run_tests!(
+			conformance_tests::balanced,
+			$ext_deposit,
+			issue_and_resolve_credit,
+			issue_and_resolve_credit_below_minimum,
+			rescind_and_settle_debt,
+			rescind_and_settle_debt_insufficient_funds,
+			pair_success,
+			deposit_success,
+			deposit_below_minimum,
+			withdraw_success,
+			withdraw_insufficient_funds,
+			withdraw_expendable_dust
+		);
+	};
+}
+
+run_balanced_tests!(1);
+run_balanced_tests!(2);
+run_balanced_tests!(5);
+run_balanced_tests!(1000);
+
+macro_rules! run_mutate_hold_tests {
+	($ext_deposit:expr) => {
+		run_tests!(
+			conformance_tests::mutate_hold,
+			$ext_deposit,
+			[TestId::Foo],
+			hold_success,
+			hold_insufficient_funds,
+			hold_dust,
+			release_exact_success,
+			release_more_than_held,
+			burn_held_success,
+			transfer_on_hold_to_hold,
+			transfer_on_hold_to_free,
+			transfer_on_hold_below_minimum,
+			transfer_and_hold_success
+		);
+	};
+}
+
+run_mutate_hold_tests!(1);
+run_mutate_hold_tests!(2);
+run_mutate_hold_tests!(5);
+run_mutate_hold_tests!(1000);
+
+macro_rules! run_mutate_freeze_tests {
+	($ext_deposit:expr) => {
+		run_tests!(
+			conformance_tests::mutate_freeze,
+			$ext_deposit,
+			[TestId::Foo],
+			set_freeze_success,
+			extend_freeze_success,
+			thaw_success,
+			freeze_restricts_withdrawals
+		);
+	};
+}
+
+run_mutate_freeze_tests!(1);
+run_mutate_freeze_tests!(2);
+run_mutate_freeze_tests!(5);
+run_mutate_freeze_tests!(1000);
@@ -0,0 +1,220 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use frame_support::traits::{
+	fungible::{Inspect, Mutate},
+	fungibles::{self, conformance_tests},
+	tokens::{
+		DepositConsequence, Fortitude, Precision, Preservation, Provenance, WithdrawConsequence,
+	},
+};
+use paste::paste;
+
+/// Identifier of the only asset of [`NativeAsset`].
+const NATIVE: u32 = 0;
+
+/// Exposes `Balances` as a `fungibles` implementation with a single asset, so that the
+/// `fungibles` hold and freeze suites run against the holds and freezes of the pallet.
+struct NativeAsset;
+
+impl fungibles::Inspect<u64> for NativeAsset {
+	type AssetId = u32;
+	type Balance = u64;
+
+	fn total_issuance(_asset: u32) -> u64 {
+		Balances::total_issuance()
+	}
+	fn active_issuance(_asset: u32) -> u64 {
+		Balances::active_issuance()
+	}
+	fn minimum_balance(_asset: u32) -> u64 {
+		Balances::minimum_balance()
+	}
+	fn total_balance(_asset: u32, who: &u64) -> u64 {
+		Balances::total_balance(who)
+	}
+	fn balance(_asset: u32, who: &u64) -> u64 {
+		Balances::balance(who)
+	}
+	fn reducible_balance(
+		_asset: u32,
+		who: &u64,
+		preservation: Preservation,
+		force: Fortitude,
+	) -> u64 {
+		Balances::reducible_balance(who, preservation, force)
+	}
+	fn can_deposit(
+		_asset: u32,
+		who: &u64,
+		amount: u64,
+		provenance: Provenance,
+	) -> DepositConsequence {
+		Balances::can_deposit(who, amount, provenance)
+	}
+	fn can_withdraw(_asset: u32, who: &u64, amount: u64) -> WithdrawConsequence<u64> {
+		Balances::can_withdraw(who, amount)
+	}
+	fn asset_exists(asset: u32) -> bool {
+		asset == NATIVE
+	}
+}
+
+impl fungibles::Unbalanced<u64> for NativeAsset {
+	fn handle_dust(dust: fungibles::Dust<u64, Self>) {
+		<Balances as fungible::Unbalanced<_>>::handle_dust(fungible::Dust(dust.1))
+	}
+	fn write_balance(_asset: u32, who: &u64, amount: u64) -> Result<Option<u64>, DispatchError> {
+		<Balances as fungible::Unbalanced<_>>::write_balance(who, amount)
+	}
+	fn set_total_issuance(_asset: u32, amount: u64) {
+		<Balances as fungible::Unbalanced<_>>::set_total_issuance(amount)
+	}
+	fn deactivate(_asset: u32, amount: u64) {
+		<Balances as fungible::Unbalanced<_>>::deactivate(amount)
+	}
+	fn reactivate(_asset: u32, amount: u64) {
+		<Balances as fungible::Unbalanced<_>>::reactivate(amount)
+	}
+}
+
+impl fungibles::Mutate<u64> for NativeAsset {}
+
+impl fungibles::InspectHold<u64> for NativeAsset {
+	type Reason = TestId;
+
+	fn total_balance_on_hold(_asset: u32, who: &u64) -> u64 {
+		<Balances as fungible::InspectHold<_>>::total_balance_on_hold(who)
+	}
+	fn reducible_total_balance_on_hold(_asset: u32, who: &u64, force: Fortitude) -> u64 {
+		<Balances as fungible::InspectHold<_>>::reducible_total_balance_on_hold(who, force)
+	}
+	fn balance_on_hold(_asset: u32, reason: &TestId, who: &u64) -> u64 {
+		<Balances as fungible::InspectHold<_>>::balance_on_hold(reason, who)
+	}
+	fn hold_available(_asset: u32, reason: &TestId, who: &u64) -> bool {
+		<Balances as fungible::InspectHold<_>>::hold_available(reason, who)
+	}
+}
+
+impl fungibles::UnbalancedHold<u64> for NativeAsset {
+	fn set_balance_on_hold(_asset: u32, reason: &TestId, who: &u64, amount: u64) -> DispatchResult {
+		<Balances as fungible::UnbalancedHold<_>>::set_balance_on_hold(reason, who, amount)
+	}
+}
+
+impl fungibles::MutateHold<u64> for NativeAsset {}
+
+impl fungibles::InspectFreeze<u64> for NativeAsset {
+	type Id = TestId;
+
+	fn balance_frozen(_asset: u32, id: &TestId, who: &u64) -> u64 {
+		<Balances as fungible::InspectFreeze<_>>::balance_frozen(id, who)
+	}
+	fn can_freeze(_asset: u32, id: &TestId, who: &u64) -> bool {
+		<Balances as fungible::InspectFreeze<_>>::can_freeze(id, who)
+	}
+}
+
+impl fungibles::MutateFreeze<u64> for NativeAsset {
+	fn set_freeze(_asset: u32, id: &TestId, who: &u64, amount: u64) -> DispatchResult {
+		<Balances as fungible::MutateFreeze<_>>::set_freeze(id, who, amount)
+	}
+	fn extend_freeze(_asset: u32, id: &TestId, who: &u64, amount: u64) -> DispatchResult {
+		<Balances as fungible::MutateFreeze<_>>::extend_freeze(id, who, amount)
+	}
+	fn thaw(_asset: u32, id: &TestId, who: &u64) -> DispatchResult {
+		<Balances as fungible::MutateFreeze<_>>::thaw(id, who)
+	}
+}
+
+macro_rules! run_tests {
+	($path:path, $ext_deposit:expr, $arg:expr, $($name:ident),*) => {
+		$(
+			paste! {
+				#[test]
+				fn [< $name _existential_deposit_ $ext_deposit _dust_trap_on >]() {
+					let trap_account = <Test as frame_system::Config>::AccountId::from(65174286u64);
+					let builder = ExtBuilder::default().existential_deposit($ext_deposit).dust_trap(trap_account);
+					builder.build_and_execute_with(|| {
+						Balances::set_balance(&trap_account, Balances::minimum_balance());
+						$path::$name::<
+							NativeAsset,
+							<Test as frame_system::Config>::AccountId,
+						>(NATIVE, $arg, Some(trap_account));
+					});
+				}
+
+				#[test]
+				fn [< $name _existential_deposit_ $ext_deposit _dust_trap_off >]() {
+					let builder = ExtBuilder::default().existential_deposit($ext_deposit);
+					builder.build_and_execute_with(|| {
+						$path::$name::<
+							NativeAsset,
+							<Test as frame_system::Config>::AccountId,
+						>(NATIVE, $arg, None);
+					});
+				}
+			}
+		)*
+	};
+}
+
+macro_rules! run_mutate_hold_tests {
+	($ext_deposit:expr) => {
+		run_tests!(
+			conformance_tests::mutate_hold,
+			$ext_deposit,
+			TestId::Foo,
+			hold_success,
+			hold_insufficient_funds,
+			hold_dust,
+			release_exact_success,
+			release_more_than_held,
+			burn_held_success,
+			transfer_on_hold_to_hold,
+			transfer_on_hold_to_free,
+			transfer_on_hold_below_minimum,
+			transfer_and_hold_success
+		);
+	};
+}
+
+run_mutate_hold_tests!(1);
+run_mutate_hold_tests!(2);
+run_mutate_hold_tests!(5);
+run_mutate_hold_tests!(1000);
+
+macro_rules! run_mutate_freeze_tests {
+	($ext_deposit:expr) => {
+		run_tests!(
+			conformance_tests::mutate_freeze,
+			$ext_deposit,
+			TestId::Foo,
+			set_freeze_success,
+			extend_freeze_success,
+			thaw_success,
+			freeze_restricts_withdrawals
+		);
+	};
+}
+
+run_mutate_freeze_tests!(1);
+run_mutate_freeze_tests!(2);
+run_mutate_freeze_tests!(5);
+run_mutate_freeze_tests!(1000);
@@ -46,6 +46,7 @@ use std::collections::BTreeSet;
 mod currency_tests;
 mod dispatchable_tests;
 mod fungible_conformance_tests;
+mod fungibles_conformance_tests;
 mod fungible_tests;
 mod reentrancy_tests;
 
@@ -94,6 +95,7 @@ impl frame_system::Config for Test {
 	type RuntimeOrigin = RuntimeOrigin;
 	type Nonce = u64;
 	type RuntimeCall = RuntimeCall;
+	type RuntimeTask = RuntimeTask;
 	type Hash = H256;
 	type Hashing = ::sp_runtime::traits::BlakeTwo256;
 	type AccountId = u64;
@@ -131,6 +131,7 @@ pub mod tests {
 		type BaseCallFilter = frame_support::traits::Everything;
 		type RuntimeOrigin = RuntimeOrigin;
 		type RuntimeCall = RuntimeCall;
+		type RuntimeTask = RuntimeTask;
 		type RuntimeEvent = RuntimeEvent;
 		type PalletInfo = PalletInfo;
 		type OnSetCode = ();
@@ -38,3 +38,6 @@
 //!
 //! - [**`pallet-example-split`**](./split): A simple example of a FRAME pallet demonstrating the
 //!   ability to split sections across multiple files.
+//!
+//! - [**`pallet-example-tasks`**](./tasks): A simple example of a FRAME pallet demonstrating how to
+//!   declare and submit tasks.
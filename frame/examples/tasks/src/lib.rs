@@ -0,0 +1,106 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Tasks Example Pallet
+//!
+//! **This pallet serves as an example and is not meant to be used in production.**
+//!
+//! A FRAME pallet demonstrating how to declare permissionless background work with
+//! `#[pallet::tasks]`.
+//!
+//! Numbers are queued in [`Numbers`] through [`Call::queue_number`]. Each queued number is a
+//! candidate for the `add_number_into_total` task, which removes it from the queue and adds it to
+//! [`Total`]. Anyone can run a valid task through `frame_system::Call::do_task`, and the offchain
+//! worker of this pallet submits all valid tasks as unsigned transactions.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+
+#[frame_support::pallet(dev_mode)]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use frame_system::{
+		offchain::{submit_valid_tasks, SendTransactionTypes},
+		pallet_prelude::*,
+	};
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config + SendTransactionTypes<frame_system::Call<Self>>
+	{
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The total would overflow.
+		Overflow,
+	}
+
+	/// Numbers waiting to be added into the total.
+	#[pallet::storage]
+	pub type Numbers<T: Config> = StorageMap<_, Twox64Concat, u32, u32, OptionQuery>;
+
+	/// The sum of all numbers processed so far.
+	#[pallet::storage]
+	pub type Total<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Queue `number` under `key`, to be added into the total by a task.
+		#[pallet::call_index(0)]
+		pub fn queue_number(origin: OriginFor<T>, key: u32, number: u32) -> DispatchResult {
+			ensure_signed(origin)?;
+			Numbers::<T>::insert(key, number);
+			Ok(())
+		}
+	}
+
+	#[pallet::tasks]
+	impl<T: Config> Pallet<T> {
+		/// Add the number queued under `key` into the total.
+		#[pallet::task_index(0)]
+		#[pallet::task_condition(|key| Numbers::<T>::contains_key(key))]
+		#[pallet::task_condition_weight(T::DbWeight::get().reads(1))]
+		#[pallet::task_list(Numbers::<T>::iter_keys())]
+		#[pallet::task_weight(T::DbWeight::get().reads_writes(2, 2))]
+		pub fn add_number_into_total(key: u32) -> DispatchResult {
+			let number = Numbers::<T>::take(key).ok_or(DispatchError::Other("not queued"))?;
+			Total::<T>::try_mutate(|total| {
+				*total = total.checked_add(number).ok_or(Error::<T>::Overflow)?;
+				Ok(())
+			})
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn offchain_worker(_block_number: BlockNumberFor<T>) {
+			let submitted = submit_valid_tasks::<T>();
+			log::debug!(target: "runtime::example-tasks", "submitted {} tasks", submitted);
+		}
+	}
+}
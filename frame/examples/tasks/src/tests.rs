@@ -0,0 +1,155 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{mock::*, Numbers, Total};
+use codec::{Decode, Encode};
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::GetDispatchInfo,
+	traits::{Get, Hooks, Task},
+	weights::constants::RocksDbWeight,
+};
+use sp_core::offchain::{testing, OffchainWorkerExt, TransactionPoolExt};
+use sp_runtime::{
+	traits::ValidateUnsigned,
+	transaction_validity::{InvalidTransaction, TransactionSource},
+	DispatchError,
+};
+
+type PalletTask = crate::Task<Runtime>;
+
+fn add_number_task(key: u32) -> RuntimeTask {
+	PalletTask::add_number_into_total { key }.into()
+}
+
+#[test]
+fn tasks_are_enumerated_from_storage() {
+	new_test_ext().execute_with(|| {
+		assert!(<RuntimeTask as Task>::iter().next().is_none());
+
+		assert_ok!(TasksExample::queue_number(RuntimeOrigin::signed(1), 1, 10));
+		assert_ok!(TasksExample::queue_number(RuntimeOrigin::signed(1), 2, 20));
+
+		let mut tasks = <RuntimeTask as Task>::iter().collect::<Vec<_>>();
+		tasks.sort_by_key(|task| task.encode());
+		assert_eq!(tasks, vec![add_number_task(1), add_number_task(2)]);
+		assert!(tasks.iter().all(|task| task.is_valid()));
+		assert!(!add_number_task(3).is_valid());
+		assert_eq!(add_number_task(3).task_index(), 0);
+	});
+}
+
+#[test]
+fn do_task_runs_valid_task() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TasksExample::queue_number(RuntimeOrigin::signed(1), 1, 10));
+
+		assert_ok!(System::do_task(RuntimeOrigin::signed(2), add_number_task(1)));
+
+		assert_eq!(Total::<Runtime>::get(), 10);
+		assert!(!Numbers::<Runtime>::contains_key(1));
+		System::assert_last_event(
+			frame_system::Event::TaskCompleted { task: add_number_task(1) }.into(),
+		);
+
+		// The task is not valid anymore once run.
+		assert_noop!(
+			System::do_task(RuntimeOrigin::signed(2), add_number_task(1)),
+			frame_system::Error::<Runtime>::InvalidTask,
+		);
+	});
+}
+
+#[test]
+fn do_task_charges_condition_and_task_weight() {
+	let task = add_number_task(1);
+	let call = frame_system::Call::<Runtime>::do_task { task: task.clone() };
+
+	assert_eq!(
+		call.get_dispatch_info().weight,
+		RocksDbWeight::get().reads(1) + RocksDbWeight::get().reads_writes(2, 2),
+	);
+	assert_eq!(call.get_dispatch_info().weight, task.is_valid_weight() + task.weight());
+}
+
+#[test]
+fn failed_task_is_reverted() {
+	new_test_ext().execute_with(|| {
+		Total::<Runtime>::put(u32::MAX);
+		assert_ok!(TasksExample::queue_number(RuntimeOrigin::signed(1), 1, 10));
+
+		assert_ok!(System::do_task(RuntimeOrigin::none(), add_number_task(1)));
+
+		// The number was not removed from the queue.
+		assert_eq!(Numbers::<Runtime>::get(1), Some(10));
+		assert_eq!(Total::<Runtime>::get(), u32::MAX);
+		System::assert_last_event(
+			frame_system::Event::TaskFailed {
+				task: add_number_task(1),
+				err: DispatchError::from(crate::Error::<Runtime>::Overflow),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn validate_unsigned_checks_task_validity() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TasksExample::queue_number(RuntimeOrigin::signed(1), 1, 10));
+
+		let call = frame_system::Call::do_task { task: add_number_task(1) };
+		assert_ok!(System::validate_unsigned(TransactionSource::External, &call));
+
+		let call = frame_system::Call::do_task { task: add_number_task(2) };
+		assert_eq!(
+			System::validate_unsigned(TransactionSource::External, &call),
+			Err(InvalidTransaction::Stale.into()),
+		);
+
+		let call = frame_system::Call::remark { remark: vec![] };
+		assert_eq!(
+			System::validate_unsigned(TransactionSource::External, &call),
+			Err(InvalidTransaction::Call.into()),
+		);
+	});
+}
+
+#[test]
+fn offchain_worker_submits_valid_tasks() {
+	let (offchain, _offchain_state) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+
+	let mut ext = new_test_ext();
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+
+	ext.execute_with(|| {
+		assert_ok!(TasksExample::queue_number(RuntimeOrigin::signed(1), 1, 10));
+
+		TasksExample::offchain_worker(1);
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature, None);
+		assert_eq!(
+			tx.call,
+			RuntimeCall::System(frame_system::Call::do_task { task: add_number_task(1) }),
+		);
+	});
+}
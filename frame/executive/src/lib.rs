@@ -141,6 +141,11 @@ use sp_std::{marker::PhantomData, prelude::*};
 #[cfg(feature = "try-runtime")]
 use sp_runtime::TryRuntimeError;
 
+/// The maximal number of multi-block migration steps that `try_runtime_upgrade` simulates before
+/// it considers the migrations to be stuck.
+#[cfg(feature = "try-runtime")]
+const MAX_TRY_RUNTIME_MBM_STEPS: u32 = 100_000;
+
 #[allow(dead_code)]
 const LOG_TARGET: &str = "runtime::executive";
 
@@ -159,6 +164,9 @@ pub type OriginOf<E, C> = <CallOf<E, C> as Dispatchable>::RuntimeOrigin;
 ///   used to call hooks e.g. `on_initialize`.
 /// - `OnRuntimeUpgrade`: Custom logic that should be called after a runtime upgrade. Modules are
 ///   already called by `AllPalletsWithSystem`. It will be called before all modules will be called.
+/// - `MultiStepMigrator`: Drives the multi-block migrations of the runtime, see
+///   [`frame_support::migrations::MultiStepMigrator`]. While migrations are ongoing, only mandatory
+///   extrinsics are applied and `on_idle` is replaced by a migration step.
 pub struct Executive<
 	System,
 	Block,
@@ -166,6 +174,7 @@ pub struct Executive<
 	UnsignedValidator,
 	AllPalletsWithSystem,
 	OnRuntimeUpgrade = (),
+	MultiStepMigrator = (),
 >(
 	PhantomData<(
 		System,
@@ -174,6 +183,7 @@ pub struct Executive<
 		UnsignedValidator,
 		AllPalletsWithSystem,
 		OnRuntimeUpgrade,
+		MultiStepMigrator,
 	)>,
 );
 
@@ -191,8 +201,17 @@ impl<
 			+ OnFinalize<BlockNumberFor<System>>
 			+ OffchainWorker<BlockNumberFor<System>>,
 		COnRuntimeUpgrade: OnRuntimeUpgrade,
+		MultiStepMigrator: frame_support::migrations::MultiStepMigrator,
 	> ExecuteBlock<Block>
-	for Executive<System, Block, Context, UnsignedValidator, AllPalletsWithSystem, COnRuntimeUpgrade>
+	for Executive<
+		System,
+		Block,
+		Context,
+		UnsignedValidator,
+		AllPalletsWithSystem,
+		COnRuntimeUpgrade,
+		MultiStepMigrator,
+	>
 where
 	Block::Extrinsic: Checkable<Context> + Codec,
 	CheckedOf<Block::Extrinsic, Context>: Applyable + GetDispatchInfo,
@@ -209,6 +228,7 @@ where
 			UnsignedValidator,
 			AllPalletsWithSystem,
 			COnRuntimeUpgrade,
+			MultiStepMigrator,
 		>::execute_block(block);
 	}
 }
@@ -229,7 +249,17 @@ impl<
 			+ OffchainWorker<BlockNumberFor<System>>
 			+ frame_support::traits::TryState<BlockNumberFor<System>>,
 		COnRuntimeUpgrade: OnRuntimeUpgrade,
-	> Executive<System, Block, Context, UnsignedValidator, AllPalletsWithSystem, COnRuntimeUpgrade>
+		MultiStepMigrator: frame_support::migrations::MultiStepMigrator,
+	>
+	Executive<
+		System,
+		Block,
+		Context,
+		UnsignedValidator,
+		AllPalletsWithSystem,
+		COnRuntimeUpgrade,
+		MultiStepMigrator,
+	>
 where
 	Block::Extrinsic: Checkable<Context> + Codec,
 	CheckedOf<Block::Extrinsic, Context>: Applyable + GetDispatchInfo,
@@ -277,9 +307,12 @@ where
 			} else {
 				uxt.unchecked_into_checked_i_know_what_i_am_doing(&Default::default())
 			}?;
+			let dispatch_info = xt.get_dispatch_info();
+			if dispatch_info.class != DispatchClass::Mandatory && MultiStepMigrator::ongoing() {
+				return Err(InvalidTransaction::ExhaustsResources.into())
+			}
 			<frame_system::Pallet<System>>::note_extrinsic(encoded);
 
-			let dispatch_info = xt.get_dispatch_info();
 			let r = Applyable::apply::<UnsignedValidator>(xt, &dispatch_info, encoded_len)?;
 
 			<frame_system::Pallet<System>>::note_applied_extrinsic(&r, dispatch_info);
@@ -351,6 +384,10 @@ where
 	///
 	/// Runs the try-state code both before and after the migration function if `checks` is set to
 	/// `true`. Also, if set to `true`, it runs the `pre_upgrade` and `post_upgrade` hooks.
+	///
+	/// Afterwards, all multi-block migrations are stepped until they completed. An error is
+	/// returned if they fail to complete within a bounded number of steps, e.g. because they
+	/// got stuck.
 	pub fn try_runtime_upgrade(
 		checks: frame_try_runtime::UpgradeCheckSelect,
 	) -> Result<Weight, TryRuntimeError> {
@@ -364,11 +401,43 @@ where
 			)?;
 		}
 
-		let weight =
+		let mut weight =
 			<(COnRuntimeUpgrade, AllPalletsWithSystem) as OnRuntimeUpgrade>::try_on_runtime_upgrade(
 				checks.pre_and_post(),
 			)?;
 
+		// Simulate the multi-block migrations by stepping them until they are done. Every step
+		// runs in its own block, such that the `max_steps` of the migrations are enforced. The
+		// block number is restored afterwards.
+		let block_number = frame_system::Pallet::<System>::block_number();
+		let mut steps = 0u32;
+		while MultiStepMigrator::ongoing() {
+			if steps >= MAX_TRY_RUNTIME_MBM_STEPS {
+				frame_support::log::error!(
+					target: LOG_TARGET,
+					"try-runtime: multi-block migrations did not complete after {} steps",
+					steps,
+				);
+				frame_system::Pallet::<System>::set_block_number(block_number);
+				return Err("Multi-block migrations did not complete".into())
+			}
+
+			weight = weight.saturating_add(MultiStepMigrator::step());
+			steps += 1;
+			frame_system::Pallet::<System>::set_block_number(
+				frame_system::Pallet::<System>::block_number() + One::one(),
+			);
+		}
+		frame_system::Pallet::<System>::set_block_number(block_number);
+
+		if steps > 0 {
+			frame_support::log::info!(
+				target: LOG_TARGET,
+				"try-runtime: multi-block migrations completed after {} steps",
+				steps,
+			);
+		}
+
 		if checks.try_state() {
 			let _guard = frame_support::StorageNoopGuard::default();
 			<AllPalletsWithSystem as frame_support::traits::TryState<
@@ -397,7 +466,17 @@ impl<
 			+ OnFinalize<BlockNumberFor<System>>
 			+ OffchainWorker<BlockNumberFor<System>>,
 		COnRuntimeUpgrade: OnRuntimeUpgrade,
-	> Executive<System, Block, Context, UnsignedValidator, AllPalletsWithSystem, COnRuntimeUpgrade>
+		MultiStepMigrator: frame_support::migrations::MultiStepMigrator,
+	>
+	Executive<
+		System,
+		Block,
+		Context,
+		UnsignedValidator,
+		AllPalletsWithSystem,
+		COnRuntimeUpgrade,
+		MultiStepMigrator,
+	>
 where
 	Block::Extrinsic: Checkable<Context> + Codec,
 	CheckedOf<Block::Extrinsic, Context>: Applyable + GetDispatchInfo,
@@ -547,7 +626,14 @@ where
 		let max_weight = <System::BlockWeights as frame_support::traits::Get<_>>::get().max_block;
 		let remaining_weight = max_weight.saturating_sub(weight.total());
 
-		if remaining_weight.all_gt(Weight::zero()) {
+		if MultiStepMigrator::ongoing() {
+			// Ongoing multi-block migrations take precedence over `on_idle`.
+			let used_weight = MultiStepMigrator::step();
+			<frame_system::Pallet<System>>::register_extra_weight_unchecked(
+				used_weight,
+				DispatchClass::Mandatory,
+			);
+		} else if remaining_weight.all_gt(Weight::zero()) {
 			let used_weight = <AllPalletsWithSystem as OnIdle<BlockNumberFor<System>>>::on_idle(
 				block_number,
 				remaining_weight,
@@ -573,6 +659,13 @@ where
 				ext=?sp_core::hexdisplay::HexDisplay::from(&encoded)));
 		// Verify that the signature is good.
 		let xt = uxt.check(&Default::default())?;
+		let dispatch_info = xt.get_dispatch_info();
+
+		// Only inherents are applied while multi-block migrations are ongoing. The transaction
+		// stays valid and will be included once the migrations completed.
+		if dispatch_info.class != DispatchClass::Mandatory && MultiStepMigrator::ongoing() {
+			return Err(InvalidTransaction::ExhaustsResources.into())
+		}
 
 		// We don't need to make sure to `note_extrinsic` only after we know it's going to be
 		// executed to prevent it from leaking in storage since at this point, it will either
@@ -582,7 +675,6 @@ where
 		// AUDIT: Under no circumstances may this function panic from here onwards.
 
 		// Decode parameters and dispatch
-		let dispatch_info = xt.get_dispatch_info();
 		let r = Applyable::apply::<UnsignedValidator>(xt, &dispatch_info, encoded_len)?;
 
 		// Mandatory(inherents) are not allowed to fail.
@@ -870,6 +962,7 @@ mod tests {
 		type RuntimeOrigin = RuntimeOrigin;
 		type Nonce = u64;
 		type RuntimeCall = RuntimeCall;
+		type RuntimeTask = RuntimeTask;
 		type Hash = sp_core::H256;
 		type Hashing = BlakeTwo256;
 		type AccountId = u64;
@@ -952,6 +1045,23 @@ mod tests {
 		}
 	}
 
+	// Contains the number of remaining steps of the mocked multi-block migrations.
+	const MBM_STEPS_KEY: &[u8] = b":test:mbm_steps:";
+
+	/// Multi-block migrations that are ongoing as long as there are remaining steps.
+	struct MockedMbms;
+	impl frame_support::migrations::MultiStepMigrator for MockedMbms {
+		fn ongoing() -> bool {
+			frame_support::storage::unhashed::get_or_default::<u32>(MBM_STEPS_KEY) > 0
+		}
+
+		fn step() -> Weight {
+			let remaining = frame_support::storage::unhashed::get_or_default::<u32>(MBM_STEPS_KEY);
+			frame_support::storage::unhashed::put(MBM_STEPS_KEY, &remaining.saturating_sub(1));
+			Weight::from_parts(100, 0)
+		}
+	}
+
 	type Executive = super::Executive<
 		Runtime,
 		Block<TestXt>,
@@ -959,6 +1069,7 @@ mod tests {
 		Runtime,
 		AllPalletsWithSystem,
 		CustomOnRuntimeUpgrade,
+		MockedMbms,
 	>;
 
 	fn extra(nonce: u64, fee: Balance) -> SignedExtra {
@@ -1640,4 +1751,70 @@ mod tests {
 			);
 		})
 	}
+
+	#[test]
+	fn extrinsics_are_paused_while_mbms_ongoing() {
+		let xt1 = TestXt::new(RuntimeCall::Custom(custom::Call::inherent_call {}), None);
+		let xt2 = TestXt::new(call_transfer(33, 0), sign_extra(1, 0, 0));
+
+		new_test_ext(1).execute_with(|| {
+			frame_support::storage::unhashed::put(MBM_STEPS_KEY, &2u32);
+
+			for n in 1..=2 {
+				Executive::initialize_block(&Header::new(
+					n,
+					H256::default(),
+					H256::default(),
+					[69u8; 32].into(),
+					Digest::default(),
+				));
+
+				// Inherents are still applied, but transactions are rejected.
+				Executive::apply_extrinsic(xt1.clone()).unwrap().unwrap();
+				assert_eq!(
+					Executive::apply_extrinsic(xt2.clone()),
+					Err(InvalidTransaction::ExhaustsResources.into())
+				);
+
+				// Each block makes one step of progress.
+				Executive::finalize_block();
+				assert_eq!(
+					frame_support::storage::unhashed::get::<u32>(MBM_STEPS_KEY),
+					Some(2 - n as u32)
+				);
+			}
+
+			// Transactions are applied again once the migrations completed.
+			Executive::initialize_block(&Header::new(
+				3,
+				H256::default(),
+				H256::default(),
+				[69u8; 32].into(),
+				Digest::default(),
+			));
+			Executive::apply_extrinsic(xt2).unwrap().unwrap();
+		});
+	}
+
+	#[test]
+	fn mbms_replace_on_idle() {
+		new_test_ext(1).execute_with(|| {
+			frame_support::storage::unhashed::put(MBM_STEPS_KEY, &1u32);
+			Executive::initialize_block(&Header::new(
+				1,
+				H256::default(),
+				H256::default(),
+				[69u8; 32].into(),
+				Digest::default(),
+			));
+			let before = System::block_weight().get(DispatchClass::Mandatory).ref_time();
+
+			Executive::idle_and_finalize_hook(1);
+
+			// Only the step was executed, not `on_idle` of the custom pallet.
+			let after = System::block_weight().get(DispatchClass::Mandatory).ref_time();
+			assert_eq!(after - before, 100);
+			assert!(!<MockedMbms as frame_support::migrations::MultiStepMigrator>::ongoing());
+		});
+	}
 }
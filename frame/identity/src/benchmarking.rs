@@ -28,7 +28,7 @@ use frame_support::{
 	traits::{EnsureOrigin, Get},
 };
 use frame_system::RawOrigin;
-use sp_runtime::traits::Bounded;
+use sp_runtime::traits::{Bounded, One};
 
 const SEED: u32 = 0;
 
@@ -36,6 +36,10 @@ fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
 	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
 }
 
+fn assert_has_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+	frame_system::Pallet::<T>::assert_has_event(generic_event.into());
+}
+
 // Adds `r` registrars to the Identity Pallet. These registrars will have set fees and fields.
 fn add_registrars<T: Config>(r: u32) -> Result<(), &'static str> {
 	for i in 0..r {
@@ -46,14 +50,16 @@ fn add_registrars<T: Config>(r: u32) -> Result<(), &'static str> {
 			.expect("RegistrarOrigin has no successful origin required for the benchmark");
 		Identity::<T>::add_registrar(registrar_origin, registrar_lookup)?;
 		Identity::<T>::set_fee(RawOrigin::Signed(registrar.clone()).into(), i, 10u32.into())?;
-		let fields =
-			IdentityFields(
-				IdentityField::Display |
-					IdentityField::Legal | IdentityField::Web |
-					IdentityField::Riot | IdentityField::Email |
-					IdentityField::PgpFingerprint |
-					IdentityField::Image | IdentityField::Twitter,
-			);
+		let fields = IdentityFields(
+			IdentityField::Display |
+				IdentityField::Legal |
+				IdentityField::Web |
+				IdentityField::Riot |
+				IdentityField::Email |
+				IdentityField::PgpFingerprint |
+				IdentityField::Image |
+				IdentityField::Twitter,
+		);
 		Identity::<T>::set_fields(RawOrigin::Signed(registrar.clone()).into(), i, fields)?;
 	}
 
@@ -118,6 +124,37 @@ fn create_identity_info<T: Config>(num_fields: u32) -> IdentityInfo<T::MaxAdditi
 	}
 }
 
+// The suffix used by the benchmarked username authority.
+fn bench_suffix<T: Config>() -> Vec<u8> {
+	vec![b'a'; T::MaxSuffixLength::get() as usize]
+}
+
+// A username that, together with the longest possible suffix, fits `MaxUsernameLength`.
+fn bench_username() -> Vec<u8> {
+	b"benchmarkuser".to_vec()
+}
+
+// The username with the benchmark suffix appended.
+fn full_username<T: Config>(username: &[u8]) -> Username<T> {
+	let mut full = username.to_vec();
+	full.push(b'.');
+	full.extend(bench_suffix::<T>());
+	full.try_into().expect("the benchmark username fits the configured bounds")
+}
+
+// Make `authority` a username authority with some allocation left.
+fn make_username_authority<T: Config>(authority: &T::AccountId) -> Result<(), BenchmarkError> {
+	let origin = T::UsernameAuthorityOrigin::try_successful_origin()
+		.map_err(|_| BenchmarkError::Weightless)?;
+	Identity::<T>::add_username_authority(
+		origin,
+		T::Lookup::unlookup(authority.clone()),
+		bench_suffix::<T>(),
+		10,
+	)?;
+	Ok(())
+}
+
 benchmarks! {
 	add_registrar {
 		let r in 1 .. T::MaxRegistrars::get() - 1 => add_registrars::<T>(r)?;
@@ -448,5 +485,108 @@ benchmarks! {
 		ensure!(!SuperOf::<T>::contains_key(&caller), "Sub not removed");
 	}
 
+	add_username_authority {
+		let origin =
+			T::UsernameAuthorityOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		let authority: T::AccountId = account("authority", 0, SEED);
+		let authority_lookup = T::Lookup::unlookup(authority.clone());
+		let suffix = bench_suffix::<T>();
+		let allocation = 10;
+	}: _<T::RuntimeOrigin>(origin, authority_lookup, suffix, allocation)
+	verify {
+		assert_last_event::<T>(Event::<T>::AuthorityAdded { authority }.into());
+	}
+
+	remove_username_authority {
+		let origin =
+			T::UsernameAuthorityOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		let authority: T::AccountId = account("authority", 0, SEED);
+		let authority_lookup = T::Lookup::unlookup(authority.clone());
+		Identity::<T>::add_username_authority(origin.clone(), authority_lookup.clone(), bench_suffix::<T>(), 10)?;
+	}: _<T::RuntimeOrigin>(origin, authority_lookup)
+	verify {
+		assert_last_event::<T>(Event::<T>::AuthorityRemoved { authority }.into());
+	}
+
+	set_username_for {
+		// Set up a username authority.
+		let authority: T::AccountId = account("authority", 0, SEED);
+		make_username_authority::<T>(&authority)?;
+
+		// Worst case: the username is set immediately, so the signature has to be verified.
+		let username = bench_username();
+		let full_username = full_username::<T>(&username);
+		let (public, signature) = T::BenchmarkHelper::sign_message(&full_username[..]);
+		let who: T::AccountId = public.into_account();
+		let who_lookup = T::Lookup::unlookup(who.clone());
+	}: _(RawOrigin::Signed(authority), who_lookup, username, Some(signature))
+	verify {
+		assert_has_event::<T>(Event::<T>::UsernameSet { who: who.clone(), username: full_username.clone() }.into());
+		assert_last_event::<T>(Event::<T>::PrimaryUsernameSet { who, username: full_username }.into());
+	}
+
+	accept_username {
+		let caller: T::AccountId = whitelisted_caller();
+		let authority: T::AccountId = account("authority", 0, SEED);
+		make_username_authority::<T>(&authority)?;
+
+		let username = bench_username();
+		let full_username = full_username::<T>(&username);
+		Identity::<T>::set_username_for(
+			RawOrigin::Signed(authority).into(),
+			T::Lookup::unlookup(caller.clone()),
+			username,
+			None,
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), full_username.clone())
+	verify {
+		assert_last_event::<T>(Event::<T>::PrimaryUsernameSet { who: caller, username: full_username }.into());
+	}
+
+	remove_expired_approval {
+		let caller: T::AccountId = whitelisted_caller();
+		let authority: T::AccountId = account("authority", 0, SEED);
+		make_username_authority::<T>(&authority)?;
+
+		let username = bench_username();
+		let full_username = full_username::<T>(&username);
+		Identity::<T>::set_username_for(
+			RawOrigin::Signed(authority).into(),
+			T::Lookup::unlookup(caller.clone()),
+			username,
+			None,
+		)?;
+
+		let expiration = T::PendingUsernameExpiration::get() + frame_system::Pallet::<T>::block_number();
+		frame_system::Pallet::<T>::set_block_number(expiration + One::one());
+	}: _(RawOrigin::Signed(caller.clone()), full_username)
+	verify {
+		assert_last_event::<T>(Event::<T>::PreapprovalExpired { whose: caller }.into());
+	}
+
+	set_primary_username {
+		let caller: T::AccountId = whitelisted_caller();
+		let authority: T::AccountId = account("authority", 0, SEED);
+		make_username_authority::<T>(&authority)?;
+
+		// Give the caller two usernames so the second one can be made primary.
+		let mut second_username = Default::default();
+		for username in [bench_username(), b"secondusername".to_vec()] {
+			second_username = full_username::<T>(&username);
+			Identity::<T>::set_username_for(
+				RawOrigin::Signed(authority.clone()).into(),
+				T::Lookup::unlookup(caller.clone()),
+				username,
+				None,
+			)?;
+			Identity::<T>::accept_username(RawOrigin::Signed(caller.clone()).into(), second_username.clone())?;
+		}
+	}: _(RawOrigin::Signed(caller.clone()), second_username.clone())
+	verify {
+		assert_last_event::<T>(Event::<T>::PrimaryUsernameSet { who: caller, username: second_username }.into());
+	}
+
 	impl_benchmark_test_suite!(Identity, crate::tests::new_test_ext(), crate::tests::Test);
 }
@@ -40,6 +40,22 @@
 //! The number of registrars should be limited, and the deposit made sufficiently large, to ensure
 //! no state-bloat attack is viable.
 //!
+//! ### Usernames
+//!
+//! The pallet provides functionality for username authorities to issue usernames. The first
+//! username an account receives becomes its primary username. An account can hold multiple
+//! usernames, but only one of them is the primary one, and it can be looked up in both directions:
+//! from the account to its primary username and from any username to the account that owns it.
+//!
+//! Username authorities are added by `T::UsernameAuthorityOrigin` together with a suffix and an
+//! allocation of usernames they may grant. Every username granted by an authority ends with
+//! `.suffix`, so two authorities can never grant the same username.
+//!
+//! Users can either pre-sign a username and hand the signature to the authority, in which case the
+//! username is set immediately, or the authority can grant a username without a signature, in
+//! which case the user has `T::PendingUsernameExpiration` blocks to accept it. Expired grants can
+//! be removed by anyone.
+//!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
@@ -63,9 +79,19 @@
 //! * `set_fields` - Set the fields that a registrar cares about in their judgements.
 //! * `provide_judgement` - Provide a judgement to an identity.
 //!
+//! #### For general users with usernames
+//! * `accept_username` - Accept a username granted by an authority.
+//! * `set_primary_username` - Set one of the sender's usernames as their primary username.
+//! * `remove_expired_approval` - Remove a username grant that was not accepted in time.
+//!
+//! #### For username authorities
+//! * `set_username_for` - Grant a username to an account, optionally with a pre-signed approval.
+//!
 //! #### For super-users
 //! * `add_registrar` - Add a new registrar to the system.
 //! * `kill_identity` - Forcibly remove the associated identity; the deposit is lost.
+//! * `add_username_authority` - Add an account with the ability to grant usernames.
+//! * `remove_username_authority` - Remove an account with the ability to grant usernames.
 //!
 //! [`Call`]: ./enum.Call.html
 //! [`Config`]: ./trait.Config.html
@@ -78,15 +104,21 @@ mod tests;
 mod types;
 pub mod weights;
 
-use frame_support::traits::{BalanceStatus, Currency, OnUnbalanced, ReservableCurrency};
-use sp_runtime::traits::{AppendZerosInput, Hash, Saturating, StaticLookup, Zero};
+use frame_support::{
+	ensure,
+	traits::{BalanceStatus, Currency, Get, OnUnbalanced, ReservableCurrency},
+	BoundedVec,
+};
+use sp_runtime::traits::{
+	AppendZerosInput, Hash, IdentifyAccount, Saturating, StaticLookup, Verify, Zero,
+};
 use sp_std::prelude::*;
 pub use weights::WeightInfo;
 
 pub use pallet::*;
 pub use types::{
-	Data, IdentityField, IdentityFields, IdentityInfo, Judgement, RegistrarIndex, RegistrarInfo,
-	Registration,
+	Allocation, AuthorityProperties, Data, IdentityField, IdentityFields, IdentityInfo, Judgement,
+	RegistrarIndex, RegistrarInfo, Registration,
 };
 
 type BalanceOf<T> =
@@ -95,6 +127,11 @@ type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
 	<T as frame_system::Config>::AccountId,
 >>::NegativeImbalance;
 type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
+/// The suffix of usernames granted by an authority.
+pub type Suffix<T> = BoundedVec<u8, <T as Config>::MaxSuffixLength>;
+/// A full username, including its suffix.
+pub type Username<T> = BoundedVec<u8, <T as Config>::MaxUsernameLength>;
+type AuthorityPropertiesOf<T> = AuthorityProperties<Suffix<T>>;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -102,6 +139,21 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
+	#[cfg(feature = "runtime-benchmarks")]
+	pub trait BenchmarkHelper<Public, Signature> {
+		/// Sign `message` with a freshly generated key, returning the public key and signature.
+		fn sign_message(message: &[u8]) -> (Public, Signature);
+	}
+	#[cfg(feature = "runtime-benchmarks")]
+	impl BenchmarkHelper<sp_runtime::MultiSigner, sp_runtime::MultiSignature> for () {
+		fn sign_message(message: &[u8]) -> (sp_runtime::MultiSigner, sp_runtime::MultiSignature) {
+			let public = sp_io::crypto::sr25519_generate(0.into(), None);
+			let signature = sp_io::crypto::sr25519_sign(0.into(), &public, message)
+				.expect("key was generated above; qed");
+			(public.into(), signature.into())
+		}
+	}
+
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		/// The overarching event type.
@@ -147,6 +199,33 @@ pub mod pallet {
 		/// The origin which may add or remove registrars. Root can always do this.
 		type RegistrarOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
+		/// Off-chain signature type used to pre-approve usernames.
+		///
+		/// Can verify whether a `Self::SigningPublicKey` created a signature.
+		type OffchainSignature: Verify<Signer = Self::SigningPublicKey> + Parameter;
+
+		/// Public key that corresponds to an on-chain `Self::AccountId`.
+		type SigningPublicKey: IdentifyAccount<AccountId = Self::AccountId>;
+
+		/// The origin which may add or remove username authorities. Root can always do this.
+		type UsernameAuthorityOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The number of blocks within which a username grant must be accepted.
+		#[pallet::constant]
+		type PendingUsernameExpiration: Get<BlockNumberFor<Self>>;
+
+		/// The maximum length of a suffix.
+		#[pallet::constant]
+		type MaxSuffixLength: Get<u32>;
+
+		/// The maximum length of a username, including its suffix and the `.` delimiter.
+		#[pallet::constant]
+		type MaxUsernameLength: Get<u32>;
+
+		/// A set of helper functions for benchmarking.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::SigningPublicKey, Self::OffchainSignature>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -201,6 +280,56 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// A map of the accounts who are authorized to grant usernames.
+	///
+	/// TWOX-NOTE: OK ― `AccountId` is a secure hash.
+	#[pallet::storage]
+	#[pallet::getter(fn authority)]
+	pub(super) type UsernameAuthorities<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, AuthorityPropertiesOf<T>, OptionQuery>;
+
+	/// Reverse lookup from a suffix to the username authority that uses it.
+	///
+	/// Every suffix belongs to at most one authority, so usernames granted by different
+	/// authorities can never collide.
+	#[pallet::storage]
+	pub(super) type AuthorityOfSuffix<T: Config> =
+		StorageMap<_, Blake2_128Concat, Suffix<T>, T::AccountId, OptionQuery>;
+
+	/// Reverse lookup from `username` to the `AccountId` that has registered it.
+	///
+	/// Multiple usernames may map to the same `AccountId`, but only one of them is the primary
+	/// username of that account, see [`UsernameOf`].
+	#[pallet::storage]
+	#[pallet::getter(fn account_of_username)]
+	pub(super) type AccountOfUsername<T: Config> =
+		StorageMap<_, Blake2_128Concat, Username<T>, T::AccountId, OptionQuery>;
+
+	/// The primary username of an account.
+	///
+	/// TWOX-NOTE: OK ― `AccountId` is a secure hash.
+	#[pallet::storage]
+	#[pallet::getter(fn username)]
+	pub(super) type UsernameOf<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, Username<T>, OptionQuery>;
+
+	/// Usernames that an authority has granted, but that the account controller has not confirmed
+	/// that they want it. Used primarily in cases where the `AccountId` cannot provide a signature
+	/// because they are a pure proxy, multisig, etc. In order to confirm it, they should call
+	/// [`Call::accept_username`].
+	///
+	/// First tuple item is the account and second is the block number after which the grant
+	/// expires.
+	#[pallet::storage]
+	#[pallet::getter(fn preapproved_usernames)]
+	pub(super) type PendingUsernames<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		Username<T>,
+		(T::AccountId, BlockNumberFor<T>),
+		OptionQuery,
+	>;
+
 	#[pallet::error]
 	pub enum Error<T> {
 		/// Too many subs-accounts.
@@ -239,6 +368,26 @@ pub mod pallet {
 		JudgementForDifferentIdentity,
 		/// Error that occurs when there is an issue paying for judgement.
 		JudgementPaymentFailed,
+		/// The provided suffix is too long or contains invalid characters.
+		InvalidSuffix,
+		/// The sender does not have permission to issue a username.
+		NotUsernameAuthority,
+		/// The authority cannot allocate any more usernames.
+		NoAllocation,
+		/// The signature on a username was not valid.
+		InvalidSignature,
+		/// The username does not meet the requirements.
+		InvalidUsername,
+		/// The username is already taken.
+		UsernameTaken,
+		/// The requested username does not exist.
+		NoUsername,
+		/// The username cannot be forcefully removed because it can still be accepted.
+		NotExpired,
+		/// The suffix is already used by another authority.
+		SuffixTaken,
+		/// The username grant has expired and can no longer be accepted.
+		UsernameExpired,
 	}
 
 	#[pallet::event]
@@ -265,6 +414,18 @@ pub mod pallet {
 		/// A sub-identity was cleared, and the given deposit repatriated from the
 		/// main identity account to the sub-identity account.
 		SubIdentityRevoked { sub: T::AccountId, main: T::AccountId, deposit: BalanceOf<T> },
+		/// A username authority was added.
+		AuthorityAdded { authority: T::AccountId },
+		/// A username authority was removed.
+		AuthorityRemoved { authority: T::AccountId },
+		/// A username was set for `who`.
+		UsernameSet { who: T::AccountId, username: Username<T> },
+		/// A username was queued, but `who` must accept it prior to `expiration`.
+		UsernameQueued { who: T::AccountId, username: Username<T>, expiration: BlockNumberFor<T> },
+		/// A queued username passed its expiration without being claimed and was removed.
+		PreapprovalExpired { whose: T::AccountId },
+		/// A username was set as a primary and can be looked up from `who`.
+		PrimaryUsernameSet { who: T::AccountId, username: Username<T> },
 	}
 
 	#[pallet::call]
@@ -959,6 +1120,187 @@ pub mod pallet {
 			});
 			Ok(())
 		}
+
+		/// Add an `AccountId` with permission to grant usernames with a given `suffix` appended.
+		///
+		/// The dispatch origin for this call must be `T::UsernameAuthorityOrigin`.
+		///
+		/// - `authority`: the account that may grant usernames.
+		/// - `suffix`: the suffix appended to every username it grants. Must consist of lowercase
+		///   alphanumeric characters only.
+		/// - `allocation`: the number of usernames the authority may grant.
+		///
+		/// If the account is already an authority, its suffix and allocation are replaced. A
+		/// suffix can only be used by one authority at a time.
+		///
+		/// Emits `AuthorityAdded` if successful.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::add_username_authority())]
+		pub fn add_username_authority(
+			origin: OriginFor<T>,
+			authority: AccountIdLookupOf<T>,
+			suffix: Vec<u8>,
+			allocation: Allocation,
+		) -> DispatchResult {
+			T::UsernameAuthorityOrigin::ensure_origin(origin)?;
+			let authority = T::Lookup::lookup(authority)?;
+			Self::validate_username_part(&suffix).map_err(|_| Error::<T>::InvalidSuffix)?;
+			let suffix = Suffix::<T>::try_from(suffix).map_err(|_| Error::<T>::InvalidSuffix)?;
+			ensure!(
+				AuthorityOfSuffix::<T>::get(&suffix).map_or(true, |owner| owner == authority),
+				Error::<T>::SuffixTaken
+			);
+			if let Some(previous) = UsernameAuthorities::<T>::get(&authority) {
+				AuthorityOfSuffix::<T>::remove(&previous.suffix);
+			}
+			AuthorityOfSuffix::<T>::insert(&suffix, &authority);
+			UsernameAuthorities::<T>::insert(
+				&authority,
+				AuthorityPropertiesOf::<T> { suffix, allocation },
+			);
+			Self::deposit_event(Event::AuthorityAdded { authority });
+			Ok(())
+		}
+
+		/// Remove `authority` from the username authorities.
+		///
+		/// The dispatch origin for this call must be `T::UsernameAuthorityOrigin`. Usernames
+		/// already granted by the authority are not affected.
+		///
+		/// Emits `AuthorityRemoved` if successful.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::remove_username_authority())]
+		pub fn remove_username_authority(
+			origin: OriginFor<T>,
+			authority: AccountIdLookupOf<T>,
+		) -> DispatchResult {
+			T::UsernameAuthorityOrigin::ensure_origin(origin)?;
+			let authority = T::Lookup::lookup(authority)?;
+			let properties = UsernameAuthorities::<T>::take(&authority)
+				.ok_or(Error::<T>::NotUsernameAuthority)?;
+			AuthorityOfSuffix::<T>::remove(&properties.suffix);
+			Self::deposit_event(Event::AuthorityRemoved { authority });
+			Ok(())
+		}
+
+		/// Grant `username` to the account `who`, consuming one of the sender's allocations.
+		///
+		/// The dispatch origin for this call must be _Signed_ by a username authority. The
+		/// authority's suffix is appended to `username`, which must otherwise consist of lowercase
+		/// alphanumeric characters only.
+		///
+		/// - `who`: the account receiving the username.
+		/// - `username`: the username without suffix.
+		/// - `signature`: a signature by `who` over the full username, including the suffix. If
+		///   provided, the username is set immediately. Otherwise it is queued and `who` must call
+		///   `accept_username` within `T::PendingUsernameExpiration` blocks.
+		///
+		/// Emits `UsernameSet` if a valid signature was provided and `UsernameQueued` otherwise.
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::set_username_for())]
+		pub fn set_username_for(
+			origin: OriginFor<T>,
+			who: AccountIdLookupOf<T>,
+			username: Vec<u8>,
+			signature: Option<T::OffchainSignature>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			let suffix = UsernameAuthorities::<T>::try_mutate(
+				&sender,
+				|maybe_authority| -> Result<Suffix<T>, DispatchError> {
+					let properties =
+						maybe_authority.as_mut().ok_or(Error::<T>::NotUsernameAuthority)?;
+					ensure!(properties.allocation > 0, Error::<T>::NoAllocation);
+					properties.allocation.saturating_dec();
+					Ok(properties.suffix.clone())
+				},
+			)?;
+
+			Self::validate_username_part(&username)?;
+			let mut full_username = username;
+			full_username.push(b'.');
+			full_username.extend(suffix.iter());
+			let full_username =
+				Username::<T>::try_from(full_username).map_err(|_| Error::<T>::InvalidUsername)?;
+
+			ensure!(
+				!AccountOfUsername::<T>::contains_key(&full_username),
+				Error::<T>::UsernameTaken
+			);
+			ensure!(
+				!PendingUsernames::<T>::contains_key(&full_username),
+				Error::<T>::UsernameTaken
+			);
+
+			match signature {
+				Some(signature) => {
+					Self::validate_signature(&full_username[..], &signature, &who)?;
+					Self::insert_username(&who, full_username);
+				},
+				None => Self::queue_acceptance(&who, full_username),
+			}
+			Ok(())
+		}
+
+		/// Accept a username that an authority has granted.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the account the username was
+		/// granted to, and the grant must not have expired.
+		///
+		/// Emits `UsernameSet` if successful.
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::accept_username())]
+		pub fn accept_username(origin: OriginFor<T>, username: Username<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let (approved_for, expiration) =
+				PendingUsernames::<T>::take(&username).ok_or(Error::<T>::NoUsername)?;
+			ensure!(approved_for == who, Error::<T>::InvalidUsername);
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(now <= expiration, Error::<T>::UsernameExpired);
+			Self::insert_username(&who, username);
+			Ok(())
+		}
+
+		/// Remove a username grant that has expired without being accepted.
+		///
+		/// The dispatch origin for this call must be _Signed_. The call is free if the grant has
+		/// expired.
+		///
+		/// Emits `PreapprovalExpired` if successful.
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::remove_expired_approval())]
+		pub fn remove_expired_approval(
+			origin: OriginFor<T>,
+			username: Username<T>,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let (who, expiration) =
+				PendingUsernames::<T>::get(&username).ok_or(Error::<T>::NoUsername)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(now > expiration, Error::<T>::NotExpired);
+			PendingUsernames::<T>::remove(&username);
+			Self::deposit_event(Event::PreapprovalExpired { whose: who });
+			Ok(Pays::No.into())
+		}
+
+		/// Set one of the sender's usernames as their primary username.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the account owning `username`.
+		///
+		/// Emits `PrimaryUsernameSet` if successful.
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::set_primary_username())]
+		pub fn set_primary_username(origin: OriginFor<T>, username: Username<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let account_of_username =
+				AccountOfUsername::<T>::get(&username).ok_or(Error::<T>::NoUsername)?;
+			ensure!(who == account_of_username, Error::<T>::InvalidUsername);
+			UsernameOf::<T>::insert(&who, username.clone());
+			Self::deposit_event(Event::PrimaryUsernameSet { who, username });
+			Ok(())
+		}
 	}
 }
 
@@ -972,6 +1314,64 @@ impl<T: Config> Pallet<T> {
 			.collect()
 	}
 
+	/// Get the account owning `username`, if any.
+	pub fn lookup_username(username: &[u8]) -> Option<T::AccountId> {
+		let username = Username::<T>::try_from(username.to_vec()).ok()?;
+		AccountOfUsername::<T>::get(username)
+	}
+
+	/// Ensure that `part` of a username or suffix is non-empty and only consists of lowercase
+	/// alphanumeric characters.
+	fn validate_username_part(part: &[u8]) -> sp_runtime::DispatchResult {
+		ensure!(
+			!part.is_empty() && part.iter().all(|c| c.is_ascii_digit() || c.is_ascii_lowercase()),
+			Error::<T>::InvalidUsername
+		);
+		Ok(())
+	}
+
+	/// Check that `signature` is a signature by `signer` over `data`.
+	fn validate_signature(
+		data: &[u8],
+		signature: &T::OffchainSignature,
+		signer: &T::AccountId,
+	) -> sp_runtime::DispatchResult {
+		if signature.verify(data, signer) {
+			return Ok(())
+		}
+
+		// NOTE: for security reasons modern UIs implicitly wrap the data requested to sign into
+		// <Bytes></Bytes>, that's why we support both wrapped and raw versions.
+		let prefix = b"<Bytes>";
+		let suffix = b"</Bytes>";
+		let mut wrapped: Vec<u8> = Vec::with_capacity(data.len() + prefix.len() + suffix.len());
+		wrapped.extend(prefix);
+		wrapped.extend(data);
+		wrapped.extend(suffix);
+
+		ensure!(signature.verify(&wrapped[..], signer), Error::<T>::InvalidSignature);
+		Ok(())
+	}
+
+	/// Register `username` for `who`, making it the primary username if `who` has none yet.
+	fn insert_username(who: &T::AccountId, username: Username<T>) {
+		AccountOfUsername::<T>::insert(&username, who);
+		Self::deposit_event(Event::UsernameSet { who: who.clone(), username: username.clone() });
+
+		if !UsernameOf::<T>::contains_key(who) {
+			UsernameOf::<T>::insert(who, &username);
+			Self::deposit_event(Event::PrimaryUsernameSet { who: who.clone(), username });
+		}
+	}
+
+	/// Queue `username` until `who` accepts it or it expires.
+	fn queue_acceptance(who: &T::AccountId, username: Username<T>) {
+		let now = frame_system::Pallet::<T>::block_number();
+		let expiration = now.saturating_add(T::PendingUsernameExpiration::get());
+		PendingUsernames::<T>::insert(&username, (who.clone(), expiration));
+		Self::deposit_event(Event::UsernameQueued { who: who.clone(), username, expiration });
+	}
+
 	/// Check if the account has corresponding identity information by the identity field.
 	pub fn has_identity(who: &T::AccountId, fields: u64) -> bool {
 		IdentityOf::<T>::get(who)
@@ -22,13 +22,16 @@ use crate as pallet_identity;
 
 use codec::{Decode, Encode};
 use frame_support::{
-	assert_noop, assert_ok, ord_parameter_types, parameter_types,
+	assert_noop, assert_ok,
+	dispatch::Pays,
+	ord_parameter_types, parameter_types,
 	traits::{ConstU32, ConstU64, EitherOfDiverse},
 	BoundedVec,
 };
 use frame_system::{EnsureRoot, EnsureSignedBy};
 use sp_core::H256;
 use sp_runtime::{
+	testing::{TestSignature, UintAuthorityId},
 	traits::{BadOrigin, BlakeTwo256, IdentityLookup},
 	BuildStorage,
 };
@@ -52,6 +55,7 @@ impl frame_system::Config for Test {
 	type Nonce = u64;
 	type Hash = H256;
 	type RuntimeCall = RuntimeCall;
+	type RuntimeTask = RuntimeTask;
 	type Hashing = BlakeTwo256;
 	type AccountId = u64;
 	type Lookup = IdentityLookup<Self::AccountId>;
@@ -109,9 +113,25 @@ impl pallet_identity::Config for Test {
 	type MaxRegistrars = MaxRegistrars;
 	type RegistrarOrigin = EnsureOneOrRoot;
 	type ForceOrigin = EnsureTwoOrRoot;
+	type OffchainSignature = TestSignature;
+	type SigningPublicKey = UintAuthorityId;
+	type UsernameAuthorityOrigin = EnsureOneOrRoot;
+	type PendingUsernameExpiration = ConstU64<100>;
+	type MaxSuffixLength = ConstU32<7>;
+	type MaxUsernameLength = ConstU32<32>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = Self;
 	type WeightInfo = ();
 }
 
+#[cfg(feature = "runtime-benchmarks")]
+impl BenchmarkHelper<UintAuthorityId, TestSignature> for Test {
+	fn sign_message(message: &[u8]) -> (UintAuthorityId, TestSignature) {
+		let public = UintAuthorityId(42);
+		(public.clone(), TestSignature(public.0, message.to_vec()))
+	}
+}
+
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
 	pallet_balances::GenesisConfig::<Test> {
@@ -619,3 +639,423 @@ fn test_has_identity() {
 		));
 	});
 }
+
+fn username_of(user: &[u8], suffix: &[u8]) -> Username<Test> {
+	let mut full = user.to_vec();
+	full.push(b'.');
+	full.extend_from_slice(suffix);
+	full.try_into().unwrap()
+}
+
+fn signature_of(who: u64, username: &Username<Test>) -> TestSignature {
+	TestSignature(who, username.to_vec())
+}
+
+#[test]
+fn adding_and_removing_authorities_should_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			Identity::add_username_authority(RuntimeOrigin::signed(10), 20, b"test".to_vec(), 10),
+			BadOrigin
+		);
+		assert_noop!(
+			Identity::add_username_authority(RuntimeOrigin::root(), 20, b"Test".to_vec(), 10),
+			Error::<Test>::InvalidSuffix
+		);
+		assert_noop!(
+			Identity::add_username_authority(
+				RuntimeOrigin::root(),
+				20,
+				b"toolong".to_vec().repeat(2),
+				10
+			),
+			Error::<Test>::InvalidSuffix
+		);
+		assert_noop!(
+			Identity::add_username_authority(RuntimeOrigin::root(), 20, vec![], 10),
+			Error::<Test>::InvalidSuffix
+		);
+
+		assert_ok!(Identity::add_username_authority(
+			RuntimeOrigin::signed(1),
+			20,
+			b"test".to_vec(),
+			10
+		));
+		System::assert_last_event(Event::AuthorityAdded { authority: 20 }.into());
+		assert_eq!(
+			Identity::authority(20),
+			Some(AuthorityProperties {
+				suffix: b"test".to_vec().try_into().unwrap(),
+				allocation: 10
+			})
+		);
+
+		// re-adding updates the properties
+		assert_ok!(Identity::add_username_authority(RuntimeOrigin::root(), 20, b"dot".to_vec(), 5));
+		assert_eq!(
+			Identity::authority(20),
+			Some(AuthorityProperties {
+				suffix: b"dot".to_vec().try_into().unwrap(),
+				allocation: 5
+			})
+		);
+
+		assert_noop!(Identity::remove_username_authority(RuntimeOrigin::signed(10), 20), BadOrigin);
+		assert_noop!(
+			Identity::remove_username_authority(RuntimeOrigin::root(), 30),
+			Error::<Test>::NotUsernameAuthority
+		);
+		assert_ok!(Identity::remove_username_authority(RuntimeOrigin::root(), 20));
+		System::assert_last_event(Event::AuthorityRemoved { authority: 20 }.into());
+		assert_eq!(Identity::authority(20), None);
+	});
+}
+
+#[test]
+fn authority_suffixes_should_be_unique() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Identity::add_username_authority(
+			RuntimeOrigin::root(),
+			20,
+			b"test".to_vec(),
+			10
+		));
+		assert_noop!(
+			Identity::add_username_authority(RuntimeOrigin::root(), 30, b"test".to_vec(), 10),
+			Error::<Test>::SuffixTaken
+		);
+
+		// changing the suffix releases the previous one
+		assert_ok!(Identity::add_username_authority(
+			RuntimeOrigin::root(),
+			20,
+			b"dot".to_vec(),
+			10
+		));
+		assert_noop!(
+			Identity::add_username_authority(RuntimeOrigin::root(), 30, b"dot".to_vec(), 10),
+			Error::<Test>::SuffixTaken
+		);
+		assert_ok!(Identity::add_username_authority(
+			RuntimeOrigin::root(),
+			30,
+			b"test".to_vec(),
+			10
+		));
+
+		// removing an authority releases its suffix
+		assert_ok!(Identity::remove_username_authority(RuntimeOrigin::root(), 20));
+		assert_ok!(Identity::add_username_authority(
+			RuntimeOrigin::root(),
+			40,
+			b"dot".to_vec(),
+			10
+		));
+	});
+}
+
+#[test]
+fn setting_username_with_signature_should_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Identity::add_username_authority(
+			RuntimeOrigin::root(),
+			20,
+			b"test".to_vec(),
+			2
+		));
+
+		let username = username_of(b"alice", b"test");
+		assert_ok!(Identity::set_username_for(
+			RuntimeOrigin::signed(20),
+			10,
+			b"alice".to_vec(),
+			Some(signature_of(10, &username)),
+		));
+		System::assert_has_event(Event::UsernameSet { who: 10, username: username.clone() }.into());
+		System::assert_last_event(
+			Event::PrimaryUsernameSet { who: 10, username: username.clone() }.into(),
+		);
+
+		// the first username becomes the primary one and can be looked up both ways
+		assert_eq!(Identity::username(10), Some(username.clone()));
+		assert_eq!(Identity::account_of_username(&username), Some(10));
+		assert_eq!(Identity::lookup_username(b"alice.test"), Some(10));
+		assert_eq!(Identity::authority(20).unwrap().allocation, 1);
+
+		// signatures wrapped in <Bytes> are accepted as well
+		let second = username_of(b"alice2", b"test");
+		let mut wrapped = b"<Bytes>".to_vec();
+		wrapped.extend_from_slice(&second);
+		wrapped.extend_from_slice(b"</Bytes>");
+		assert_ok!(Identity::set_username_for(
+			RuntimeOrigin::signed(20),
+			10,
+			b"alice2".to_vec(),
+			Some(TestSignature(10, wrapped)),
+		));
+		System::assert_last_event(Event::UsernameSet { who: 10, username: second.clone() }.into());
+
+		// a second username does not replace the primary one
+		assert_eq!(Identity::username(10), Some(username));
+		assert_eq!(Identity::account_of_username(&second), Some(10));
+		assert_eq!(Identity::authority(20).unwrap().allocation, 0);
+	});
+}
+
+#[test]
+fn setting_username_should_fail_on_invalid_input() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Identity::set_username_for(RuntimeOrigin::signed(20), 10, b"alice".to_vec(), None),
+			Error::<Test>::NotUsernameAuthority
+		);
+		assert_ok!(Identity::add_username_authority(
+			RuntimeOrigin::root(),
+			20,
+			b"test".to_vec(),
+			1
+		));
+
+		// characters must be lowercase alphanumeric
+		for invalid in [&b"Alice"[..], b"al.ice", b"al ice", b""] {
+			assert_noop!(
+				Identity::set_username_for(RuntimeOrigin::signed(20), 10, invalid.to_vec(), None),
+				Error::<Test>::InvalidUsername
+			);
+		}
+		// the full username, including the suffix, must fit `MaxUsernameLength`
+		assert_noop!(
+			Identity::set_username_for(RuntimeOrigin::signed(20), 10, vec![b'a'; 28], None),
+			Error::<Test>::InvalidUsername
+		);
+		assert_ok!(Identity::set_username_for(
+			RuntimeOrigin::signed(20),
+			10,
+			vec![b'a'; 27],
+			Some(signature_of(10, &username_of(&[b'a'; 27], b"test"))),
+		));
+
+		// signature by a different account
+		assert_ok!(Identity::add_username_authority(
+			RuntimeOrigin::root(),
+			20,
+			b"test".to_vec(),
+			5
+		));
+		let username = username_of(b"bob", b"test");
+		assert_noop!(
+			Identity::set_username_for(
+				RuntimeOrigin::signed(20),
+				30,
+				b"bob".to_vec(),
+				Some(signature_of(10, &username)),
+			),
+			Error::<Test>::InvalidSignature
+		);
+
+		// usernames are unique, whether accepted or pending
+		assert_noop!(
+			Identity::set_username_for(RuntimeOrigin::signed(20), 30, vec![b'a'; 27], None),
+			Error::<Test>::UsernameTaken
+		);
+		assert_ok!(Identity::set_username_for(
+			RuntimeOrigin::signed(20),
+			30,
+			b"bob".to_vec(),
+			None
+		));
+		assert_noop!(
+			Identity::set_username_for(
+				RuntimeOrigin::signed(20),
+				10,
+				b"bob".to_vec(),
+				Some(signature_of(10, &username)),
+			),
+			Error::<Test>::UsernameTaken
+		);
+
+		// allocation is exhausted
+		assert_ok!(Identity::add_username_authority(
+			RuntimeOrigin::root(),
+			20,
+			b"test".to_vec(),
+			0
+		));
+		assert_noop!(
+			Identity::set_username_for(RuntimeOrigin::signed(20), 10, b"carol".to_vec(), None),
+			Error::<Test>::NoAllocation
+		);
+	});
+}
+
+#[test]
+fn accepting_pending_username_should_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Identity::add_username_authority(
+			RuntimeOrigin::root(),
+			20,
+			b"test".to_vec(),
+			5
+		));
+
+		let username = username_of(b"alice", b"test");
+		assert_ok!(Identity::set_username_for(
+			RuntimeOrigin::signed(20),
+			10,
+			b"alice".to_vec(),
+			None
+		));
+		System::assert_last_event(
+			Event::UsernameQueued { who: 10, username: username.clone(), expiration: 101 }.into(),
+		);
+		assert_eq!(Identity::preapproved_usernames(&username), Some((10, 101)));
+		assert_eq!(Identity::account_of_username(&username), None);
+		assert_eq!(Identity::authority(20).unwrap().allocation, 4);
+
+		assert_noop!(
+			Identity::accept_username(RuntimeOrigin::signed(30), username.clone()),
+			Error::<Test>::InvalidUsername
+		);
+		assert_noop!(
+			Identity::accept_username(RuntimeOrigin::signed(10), username_of(b"bob", b"test")),
+			Error::<Test>::NoUsername
+		);
+
+		assert_ok!(Identity::accept_username(RuntimeOrigin::signed(10), username.clone()));
+		System::assert_has_event(Event::UsernameSet { who: 10, username: username.clone() }.into());
+		assert_eq!(Identity::preapproved_usernames(&username), None);
+		assert_eq!(Identity::account_of_username(&username), Some(10));
+		assert_eq!(Identity::username(10), Some(username.clone()));
+
+		assert_noop!(
+			Identity::accept_username(RuntimeOrigin::signed(10), username),
+			Error::<Test>::NoUsername
+		);
+	});
+}
+
+#[test]
+fn accepting_expired_username_should_fail() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Identity::add_username_authority(
+			RuntimeOrigin::root(),
+			20,
+			b"test".to_vec(),
+			5
+		));
+
+		let username = username_of(b"alice", b"test");
+		assert_ok!(Identity::set_username_for(
+			RuntimeOrigin::signed(20),
+			10,
+			b"alice".to_vec(),
+			None
+		));
+
+		System::set_block_number(102);
+		assert_noop!(
+			Identity::accept_username(RuntimeOrigin::signed(10), username.clone()),
+			Error::<Test>::UsernameExpired
+		);
+		assert_eq!(Identity::preapproved_usernames(&username), Some((10, 101)));
+		assert_eq!(Identity::account_of_username(&username), None);
+
+		// the grant can still be accepted in its last block
+		System::set_block_number(101);
+		assert_ok!(Identity::accept_username(RuntimeOrigin::signed(10), username.clone()));
+		assert_eq!(Identity::account_of_username(&username), Some(10));
+	});
+}
+
+#[test]
+fn removing_expired_approval_should_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Identity::add_username_authority(
+			RuntimeOrigin::root(),
+			20,
+			b"test".to_vec(),
+			5
+		));
+
+		let username = username_of(b"alice", b"test");
+		assert_ok!(Identity::set_username_for(
+			RuntimeOrigin::signed(20),
+			10,
+			b"alice".to_vec(),
+			None
+		));
+
+		assert_noop!(
+			Identity::remove_expired_approval(RuntimeOrigin::signed(30), username.clone()),
+			Error::<Test>::NotExpired
+		);
+		System::set_block_number(101);
+		assert_noop!(
+			Identity::remove_expired_approval(RuntimeOrigin::signed(30), username.clone()),
+			Error::<Test>::NotExpired
+		);
+
+		System::set_block_number(102);
+		let info =
+			Identity::remove_expired_approval(RuntimeOrigin::signed(30), username.clone()).unwrap();
+		assert_eq!(info.pays_fee, Pays::No);
+		System::assert_last_event(Event::PreapprovalExpired { whose: 10 }.into());
+		assert_eq!(Identity::preapproved_usernames(&username), None);
+
+		assert_noop!(
+			Identity::remove_expired_approval(RuntimeOrigin::signed(30), username.clone()),
+			Error::<Test>::NoUsername
+		);
+		assert_noop!(
+			Identity::accept_username(RuntimeOrigin::signed(10), username),
+			Error::<Test>::NoUsername
+		);
+	});
+}
+
+#[test]
+fn setting_primary_username_should_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Identity::add_username_authority(
+			RuntimeOrigin::root(),
+			20,
+			b"test".to_vec(),
+			5
+		));
+
+		let first = username_of(b"alice", b"test");
+		let second = username_of(b"alice2", b"test");
+		for (user, username) in [(&b"alice"[..], &first), (b"alice2", &second)] {
+			assert_ok!(Identity::set_username_for(
+				RuntimeOrigin::signed(20),
+				10,
+				user.to_vec(),
+				Some(signature_of(10, username)),
+			));
+		}
+		assert_eq!(Identity::username(10), Some(first.clone()));
+
+		assert_noop!(
+			Identity::set_primary_username(RuntimeOrigin::signed(30), second.clone()),
+			Error::<Test>::InvalidUsername
+		);
+		assert_noop!(
+			Identity::set_primary_username(RuntimeOrigin::signed(10), username_of(b"bob", b"test")),
+			Error::<Test>::NoUsername
+		);
+
+		assert_ok!(Identity::set_primary_username(RuntimeOrigin::signed(10), second.clone()));
+		System::assert_last_event(
+			Event::PrimaryUsernameSet { who: 10, username: second.clone() }.into(),
+		);
+		assert_eq!(Identity::username(10), Some(second));
+		assert_eq!(Identity::account_of_username(&first), Some(10));
+	});
+}
@@ -433,6 +433,20 @@ pub struct RegistrarInfo<
 	pub fields: IdentityFields,
 }
 
+/// The number of usernames that an authority may grant.
+pub type Allocation = u32;
+
+/// Properties of a username authority.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct AuthorityProperties<Suffix> {
+	/// The suffix appended to every username granted by this authority, e.g. `dot` in
+	/// `alice.dot`.
+	pub suffix: Suffix,
+
+	/// The number of usernames that this authority may still grant.
+	pub allocation: Allocation,
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -68,6 +68,12 @@ pub trait WeightInfo {
 	fn rename_sub(s: u32, ) -> Weight;
 	fn remove_sub(s: u32, ) -> Weight;
 	fn quit_sub(s: u32, ) -> Weight;
+	fn add_username_authority() -> Weight;
+	fn remove_username_authority() -> Weight;
+	fn set_username_for() -> Weight;
+	fn accept_username() -> Weight;
+	fn remove_expired_approval() -> Weight;
+	fn set_primary_username() -> Weight;
 }
 
 /// Weights for pallet_identity using the Substrate node and recommended hardware.
@@ -363,6 +369,83 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(3_u64))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
+	/// Storage: Identity UsernameAuthorities (r:0 w:1)
+	/// Proof: Identity UsernameAuthorities (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	fn add_username_authority() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 9_451_000 picoseconds.
+		Weight::from_parts(9_801_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Identity UsernameAuthorities (r:1 w:1)
+	/// Proof: Identity UsernameAuthorities (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	fn remove_username_authority() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `79`
+		//  Estimated: `3517`
+		// Minimum execution time: 13_112_000 picoseconds.
+		Weight::from_parts(13_579_000, 3517)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Identity UsernameAuthorities (r:1 w:1)
+	/// Proof: Identity UsernameAuthorities (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: Identity AccountOfUsername (r:1 w:1)
+	/// Proof: Identity AccountOfUsername (max_values: None, max_size: Some(81), added: 2556, mode: MaxEncodedLen)
+	/// Storage: Identity PendingUsernames (r:1 w:0)
+	/// Proof: Identity PendingUsernames (max_values: None, max_size: Some(85), added: 2560, mode: MaxEncodedLen)
+	/// Storage: Identity UsernameOf (r:1 w:1)
+	/// Proof: Identity UsernameOf (max_values: None, max_size: Some(73), added: 2548, mode: MaxEncodedLen)
+	fn set_username_for() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `79`
+		//  Estimated: `3546`
+		// Minimum execution time: 64_738_000 picoseconds.
+		Weight::from_parts(66_220_000, 3546)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: Identity PendingUsernames (r:1 w:1)
+	/// Proof: Identity PendingUsernames (max_values: None, max_size: Some(85), added: 2560, mode: MaxEncodedLen)
+	/// Storage: Identity AccountOfUsername (r:0 w:1)
+	/// Proof: Identity AccountOfUsername (max_values: None, max_size: Some(81), added: 2556, mode: MaxEncodedLen)
+	/// Storage: Identity UsernameOf (r:1 w:1)
+	/// Proof: Identity UsernameOf (max_values: None, max_size: Some(73), added: 2548, mode: MaxEncodedLen)
+	fn accept_username() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `106`
+		//  Estimated: `3550`
+		// Minimum execution time: 19_882_000 picoseconds.
+		Weight::from_parts(20_506_000, 3550)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: Identity PendingUsernames (r:1 w:1)
+	/// Proof: Identity PendingUsernames (max_values: None, max_size: Some(85), added: 2560, mode: MaxEncodedLen)
+	fn remove_expired_approval() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `106`
+		//  Estimated: `3550`
+		// Minimum execution time: 12_954_000 picoseconds.
+		Weight::from_parts(13_411_000, 3550)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Identity AccountOfUsername (r:1 w:0)
+	/// Proof: Identity AccountOfUsername (max_values: None, max_size: Some(81), added: 2556, mode: MaxEncodedLen)
+	/// Storage: Identity UsernameOf (r:0 w:1)
+	/// Proof: Identity UsernameOf (max_values: None, max_size: Some(73), added: 2548, mode: MaxEncodedLen)
+	fn set_primary_username() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `184`
+		//  Estimated: `3546`
+		// Minimum execution time: 16_301_000 picoseconds.
+		Weight::from_parts(16_958_000, 3546)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -657,4 +740,81 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(3_u64))
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
+	/// Storage: Identity UsernameAuthorities (r:0 w:1)
+	/// Proof: Identity UsernameAuthorities (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	fn add_username_authority() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 9_451_000 picoseconds.
+		Weight::from_parts(9_801_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Identity UsernameAuthorities (r:1 w:1)
+	/// Proof: Identity UsernameAuthorities (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	fn remove_username_authority() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `79`
+		//  Estimated: `3517`
+		// Minimum execution time: 13_112_000 picoseconds.
+		Weight::from_parts(13_579_000, 3517)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Identity UsernameAuthorities (r:1 w:1)
+	/// Proof: Identity UsernameAuthorities (max_values: None, max_size: Some(52), added: 2527, mode: MaxEncodedLen)
+	/// Storage: Identity AccountOfUsername (r:1 w:1)
+	/// Proof: Identity AccountOfUsername (max_values: None, max_size: Some(81), added: 2556, mode: MaxEncodedLen)
+	/// Storage: Identity PendingUsernames (r:1 w:0)
+	/// Proof: Identity PendingUsernames (max_values: None, max_size: Some(85), added: 2560, mode: MaxEncodedLen)
+	/// Storage: Identity UsernameOf (r:1 w:1)
+	/// Proof: Identity UsernameOf (max_values: None, max_size: Some(73), added: 2548, mode: MaxEncodedLen)
+	fn set_username_for() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `79`
+		//  Estimated: `3546`
+		// Minimum execution time: 64_738_000 picoseconds.
+		Weight::from_parts(66_220_000, 3546)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: Identity PendingUsernames (r:1 w:1)
+	/// Proof: Identity PendingUsernames (max_values: None, max_size: Some(85), added: 2560, mode: MaxEncodedLen)
+	/// Storage: Identity AccountOfUsername (r:0 w:1)
+	/// Proof: Identity AccountOfUsername (max_values: None, max_size: Some(81), added: 2556, mode: MaxEncodedLen)
+	/// Storage: Identity UsernameOf (r:1 w:1)
+	/// Proof: Identity UsernameOf (max_values: None, max_size: Some(73), added: 2548, mode: MaxEncodedLen)
+	fn accept_username() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `106`
+		//  Estimated: `3550`
+		// Minimum execution time: 19_882_000 picoseconds.
+		Weight::from_parts(20_506_000, 3550)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: Identity PendingUsernames (r:1 w:1)
+	/// Proof: Identity PendingUsernames (max_values: None, max_size: Some(85), added: 2560, mode: MaxEncodedLen)
+	fn remove_expired_approval() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `106`
+		//  Estimated: `3550`
+		// Minimum execution time: 12_954_000 picoseconds.
+		Weight::from_parts(13_411_000, 3550)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Identity AccountOfUsername (r:1 w:0)
+	/// Proof: Identity AccountOfUsername (max_values: None, max_size: Some(81), added: 2556, mode: MaxEncodedLen)
+	/// Storage: Identity UsernameOf (r:0 w:1)
+	/// Proof: Identity UsernameOf (max_values: None, max_size: Some(73), added: 2548, mode: MaxEncodedLen)
+	fn set_primary_username() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `184`
+		//  Estimated: `3546`
+		// Minimum execution time: 16_301_000 picoseconds.
+		Weight::from_parts(16_958_000, 3546)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
@@ -0,0 +1,182 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test helpers for internal and external usage.
+
+#![allow(missing_docs)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	migrations::*,
+	storage::{transactional::with_transaction, unhashed},
+	weights::{Weight, WeightMeter},
+};
+use sp_core::ConstU32;
+use sp_runtime::{BoundedVec, DispatchError, TransactionOutcome};
+use sp_std::vec::Vec;
+
+/// Opaque identifier of a migration.
+pub type MockedIdentifier = BoundedVec<u8, ConstU32<64>>;
+
+/// How a mocked migration should behave.
+#[derive(Debug, Clone, Copy, Encode, Decode)]
+pub enum MockedMigrationKind {
+	/// Succeed after its number of steps elapsed.
+	SucceedAfter,
+	/// Fail after its number of steps elapsed.
+	FailAfter,
+	/// Never terminate.
+	TimeoutAfter,
+	/// Cause an [`SteppedMigrationError::InsufficientWeight`] error after its number of steps
+	/// elapsed.
+	HighWeightAfter(Weight),
+}
+use MockedMigrationKind::*; // C style
+
+/// Creates a migration identifier with a specific `kind` and `steps`.
+pub fn mocked_id(kind: MockedMigrationKind, steps: u32) -> MockedIdentifier {
+	(b"MockedMigration", kind, steps)
+		.encode()
+		.try_into()
+		.expect("Mocked identifier must fit into its bound")
+}
+
+/// The key under which [`MigrationsStorage`] persists its migrations.
+const MIGRATIONS_KEY: &[u8] = b":mocked_migrations:";
+
+/// A configurable list of mocked migrations.
+///
+/// The migrations are kept in unhashed storage so that they can be set up from tests as well as
+/// from benchmarks. Each mocked migration uses a `u32` step counter as its cursor.
+pub struct MigrationsStorage;
+
+impl MigrationsStorage {
+	/// Set the migrations that should be executed.
+	pub fn set(migrations: Vec<(MockedMigrationKind, u32)>) {
+		unhashed::put(MIGRATIONS_KEY, &migrations);
+	}
+
+	/// The migrations that are currently configured.
+	pub fn get() -> Vec<(MockedMigrationKind, u32)> {
+		unhashed::get_or_default(MIGRATIONS_KEY)
+	}
+
+	fn nth(n: u32) -> Option<(MockedMigrationKind, u32)> {
+		Self::get().get(n as usize).copied()
+	}
+}
+
+impl SteppedMigrations for MigrationsStorage {
+	fn len() -> u32 {
+		Self::get().len() as u32
+	}
+
+	fn nth_id(n: u32) -> Option<Vec<u8>> {
+		let (kind, steps) = Self::nth(n)?;
+		Some(mocked_id(kind, steps).encode())
+	}
+
+	fn nth_max_steps(n: u32) -> Option<Option<u32>> {
+		let (kind, steps) = Self::nth(n)?;
+		Some(matches!(kind, TimeoutAfter).then_some(steps))
+	}
+
+	fn nth_step(
+		n: u32,
+		cursor: Option<Vec<u8>>,
+		_meter: &mut WeightMeter,
+	) -> Option<Result<Option<Vec<u8>>, SteppedMigrationError>> {
+		let (kind, steps) = Self::nth(n)?;
+
+		let count: u32 = match cursor {
+			Some(cursor) => match Decode::decode(&mut &cursor[..]) {
+				Ok(count) => count,
+				Err(_) => return Some(Err(SteppedMigrationError::InvalidCursor)),
+			},
+			None => 0,
+		};
+		log::debug!("MockedMigration: Step {}", count);
+
+		if count != steps || matches!(kind, TimeoutAfter) {
+			return Some(Ok(Some(count.saturating_add(1).encode())))
+		}
+
+		Some(match kind {
+			SucceedAfter => {
+				log::debug!("MockedMigration: Succeeded after {} steps", count);
+				Ok(None)
+			},
+			HighWeightAfter(required) => {
+				log::debug!("MockedMigration: Not enough weight after {} steps", count);
+				Err(SteppedMigrationError::InsufficientWeight { required })
+			},
+			FailAfter => {
+				log::debug!("MockedMigration: Failed after {} steps", count);
+				Err(SteppedMigrationError::Failed)
+			},
+			TimeoutAfter => unreachable!(),
+		})
+	}
+
+	fn nth_transactional_step(
+		n: u32,
+		cursor: Option<Vec<u8>>,
+		meter: &mut WeightMeter,
+	) -> Option<Result<Option<Vec<u8>>, SteppedMigrationError>> {
+		with_transaction(|| {
+			let result = Self::nth_step(n, cursor, meter);
+			if matches!(result, Some(Err(_))) {
+				TransactionOutcome::Rollback(Ok(result))
+			} else {
+				TransactionOutcome::Commit(Ok(result))
+			}
+		})
+		.unwrap_or_else(|_: DispatchError| Some(Err(SteppedMigrationError::Failed)))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn nth_pre_upgrade(n: u32) -> Option<Result<Vec<u8>, sp_runtime::TryRuntimeError>> {
+		Self::nth(n).map(|_| Ok(Vec::new()))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn nth_post_upgrade(
+		n: u32,
+		_state: Vec<u8>,
+	) -> Option<Result<(), sp_runtime::TryRuntimeError>> {
+		Self::nth(n).map(|_| Ok(()))
+	}
+
+	fn cursor_max_encoded_len() -> usize {
+		u32::max_encoded_len()
+	}
+
+	fn identifier_max_encoded_len() -> usize {
+		MockedIdentifier::max_encoded_len()
+	}
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl crate::MockedMigrations for MigrationsStorage {
+	fn set_fail_after(steps: u32) {
+		Self::set(sp_std::vec![(FailAfter, steps)]);
+	}
+
+	fn set_success_after(steps: u32) {
+		Self::set(sp_std::vec![(SucceedAfter, steps)]);
+	}
+}
@@ -0,0 +1,300 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the multi-block migrations pallet.
+
+#![cfg(test)]
+
+use crate::{
+	mock::*,
+	mock_helpers::{MockedMigrationKind::*, *},
+	Cursor, Event, FailedMigrationHandling, HistoricCleanupSelector, MigrationCursor,
+};
+use frame_support::{assert_noop, assert_ok, migrations::MultiStepMigrator, weights::Weight};
+use frame_system::RawOrigin;
+
+#[test]
+fn simple_works() {
+	test_closure(|| {
+		// Add two migrations, one succeeding after zero and one after one step.
+		MigrationsStorage::set(vec![(SucceedAfter, 0), (SucceedAfter, 1)]);
+
+		System::set_block_number(1);
+		upgrade();
+		assert!(<Migrations as MultiStepMigrator>::ongoing());
+
+		run_to_block(10);
+
+		// Check that the executed migrations are recorded in `Historical`.
+		assert_eq!(historic(), vec![mocked_id(SucceedAfter, 0), mocked_id(SucceedAfter, 1)]);
+		assert!(!<Migrations as MultiStepMigrator>::ongoing());
+
+		// Check that we got all events.
+		assert_events(vec![
+			Event::UpgradeStarted { migrations: 2 },
+			Event::MigrationCompleted { index: 0, took: 1 },
+			Event::MigrationAdvanced { index: 1, took: 0 },
+			Event::MigrationCompleted { index: 1, took: 1 },
+			Event::UpgradeCompleted,
+		]);
+		assert_eq!(UpgradesStarted::get(), 1);
+		assert_eq!(UpgradesCompleted::get(), 1);
+	});
+}
+
+#[test]
+fn failing_migration_sets_cursor_to_stuck() {
+	test_closure(|| {
+		FailedUpgradeResponse::set(FailedMigrationHandling::KeepStuck);
+		MigrationsStorage::set(vec![(FailAfter, 2)]);
+
+		System::set_block_number(1);
+		upgrade();
+		run_to_block(10);
+
+		// Failed migrations are not recorded in `Historical`.
+		assert!(historic().is_empty());
+		assert_events(vec![
+			Event::UpgradeStarted { migrations: 1 },
+			Event::MigrationAdvanced { index: 0, took: 1 },
+			Event::MigrationAdvanced { index: 0, took: 2 },
+			Event::MigrationFailed { index: 0, took: 3 },
+			Event::UpgradeFailed,
+		]);
+
+		// The cursor is stuck and transactions stay paused.
+		assert_eq!(Cursor::<Test>::get(), Some(MigrationCursor::Stuck));
+		assert!(<Migrations as MultiStepMigrator>::ongoing());
+		assert_eq!(UpgradesStarted::get(), 1);
+		assert_eq!(UpgradesCompleted::get(), 0);
+	});
+}
+
+#[test]
+fn failing_migration_force_unstuck_works() {
+	test_closure(|| {
+		FailedUpgradeResponse::set(FailedMigrationHandling::ForceUnstuck);
+		MigrationsStorage::set(vec![(FailAfter, 2)]);
+
+		System::set_block_number(1);
+		upgrade();
+		run_to_block(10);
+
+		assert!(historic().is_empty());
+		assert_events(vec![
+			Event::UpgradeStarted { migrations: 1 },
+			Event::MigrationAdvanced { index: 0, took: 1 },
+			Event::MigrationAdvanced { index: 0, took: 2 },
+			Event::MigrationFailed { index: 0, took: 3 },
+			Event::UpgradeFailed,
+		]);
+
+		// The cursor is reset and transactions are processed again.
+		assert!(Cursor::<Test>::get().is_none());
+		assert!(!<Migrations as MultiStepMigrator>::ongoing());
+	});
+}
+
+/// A migration that reports insufficient weight is retried once in the next block.
+#[test]
+fn high_weight_migration_retries_once() {
+	test_closure(|| {
+		MigrationsStorage::set(vec![
+			(SucceedAfter, 0),
+			(HighWeightAfter(Weight::from_parts(1, 1)), 0),
+		]);
+
+		System::set_block_number(1);
+		upgrade();
+		run_to_block(10);
+
+		assert_eq!(historic(), vec![mocked_id(SucceedAfter, 0)]);
+		assert_events(vec![
+			Event::UpgradeStarted { migrations: 2 },
+			Event::MigrationCompleted { index: 0, took: 1 },
+			// Retried in the next block, but that also fails:
+			Event::MigrationFailed { index: 1, took: 1 },
+			Event::UpgradeFailed,
+		]);
+		assert_eq!(Cursor::<Test>::get(), Some(MigrationCursor::Stuck));
+	});
+}
+
+/// A migration that requires more than the max service weight fails immediately.
+#[test]
+fn high_weight_migration_fails_immediately() {
+	test_closure(|| {
+		MigrationsStorage::set(vec![(HighWeightAfter(Weight::MAX), 0)]);
+
+		System::set_block_number(1);
+		upgrade();
+		run_to_block(10);
+
+		assert!(historic().is_empty());
+		assert_events(vec![
+			Event::UpgradeStarted { migrations: 1 },
+			Event::MigrationFailed { index: 0, took: 1 },
+			Event::UpgradeFailed,
+		]);
+	});
+}
+
+#[test]
+fn timeout_migration_fails() {
+	test_closure(|| {
+		MigrationsStorage::set(vec![(TimeoutAfter, 3)]);
+
+		System::set_block_number(1);
+		upgrade();
+		run_to_block(10);
+
+		assert!(historic().is_empty());
+		assert_events(vec![
+			Event::UpgradeStarted { migrations: 1 },
+			Event::MigrationAdvanced { index: 0, took: 1 },
+			Event::MigrationAdvanced { index: 0, took: 2 },
+			Event::MigrationAdvanced { index: 0, took: 3 },
+			Event::MigrationAdvanced { index: 0, took: 4 },
+			Event::MigrationFailed { index: 0, took: 4 },
+			Event::UpgradeFailed,
+		]);
+		assert_eq!(Cursor::<Test>::get(), Some(MigrationCursor::Stuck));
+	});
+}
+
+#[test]
+fn historic_migrations_are_skipped() {
+	test_closure(|| {
+		MigrationsStorage::set(vec![(SucceedAfter, 0), (SucceedAfter, 1)]);
+
+		System::set_block_number(1);
+		upgrade();
+		run_to_block(10);
+		assert_eq!(historic(), vec![mocked_id(SucceedAfter, 0), mocked_id(SucceedAfter, 1)]);
+		System::reset_events();
+
+		// The next upgrade contains the old migrations and a new one.
+		MigrationsStorage::set(vec![(SucceedAfter, 0), (SucceedAfter, 1), (SucceedAfter, 2)]);
+		upgrade();
+		run_to_block(20);
+
+		assert_eq!(
+			historic(),
+			vec![
+				mocked_id(SucceedAfter, 0),
+				mocked_id(SucceedAfter, 1),
+				mocked_id(SucceedAfter, 2)
+			]
+		);
+		assert_events(vec![
+			Event::UpgradeStarted { migrations: 3 },
+			Event::MigrationSkipped { index: 0 },
+			Event::MigrationSkipped { index: 1 },
+			Event::MigrationAdvanced { index: 2, took: 0 },
+			Event::MigrationAdvanced { index: 2, took: 1 },
+			Event::MigrationCompleted { index: 2, took: 2 },
+			Event::UpgradeCompleted,
+		]);
+		assert_eq!(UpgradesStarted::get(), 2);
+		assert_eq!(UpgradesCompleted::get(), 2);
+	});
+}
+
+/// A runtime upgrade while migrations are still ongoing fails the upgrade.
+#[test]
+fn upgrade_interrupting_ongoing_migrations_fails() {
+	test_closure(|| {
+		MigrationsStorage::set(vec![(SucceedAfter, 5)]);
+
+		System::set_block_number(1);
+		upgrade();
+		run_to_block(3);
+		assert!(<Migrations as MultiStepMigrator>::ongoing());
+		System::reset_events();
+
+		upgrade();
+
+		assert_events(vec![Event::UpgradeFailed]);
+		assert_eq!(Cursor::<Test>::get(), Some(MigrationCursor::Stuck));
+		assert!(historic().is_empty());
+	});
+}
+
+#[test]
+fn force_onboard_mbms_works() {
+	test_closure(|| {
+		MigrationsStorage::set(vec![(SucceedAfter, 0)]);
+
+		assert_ok!(Migrations::force_onboard_mbms(RawOrigin::Root.into()));
+		assert_events(vec![Event::UpgradeStarted { migrations: 1 }]);
+
+		// Cannot onboard again while ongoing.
+		assert_noop!(
+			Migrations::force_onboard_mbms(RawOrigin::Root.into()),
+			crate::Error::<Test>::Ongoing
+		);
+		assert_noop!(
+			Migrations::force_onboard_mbms(RawOrigin::Signed(1).into()),
+			sp_runtime::DispatchError::BadOrigin
+		);
+
+		run_to_block(3);
+		assert_eq!(historic(), vec![mocked_id(SucceedAfter, 0)]);
+	});
+}
+
+#[test]
+fn force_set_cursor_works() {
+	test_closure(|| {
+		assert_ok!(Migrations::force_set_cursor(
+			RawOrigin::Root.into(),
+			Some(MigrationCursor::Stuck)
+		));
+		assert!(<Migrations as MultiStepMigrator>::ongoing());
+
+		assert_ok!(Migrations::force_set_cursor(RawOrigin::Root.into(), None));
+		assert!(!<Migrations as MultiStepMigrator>::ongoing());
+	});
+}
+
+#[test]
+fn clear_historic_works() {
+	test_closure(|| {
+		MigrationsStorage::set(vec![(SucceedAfter, 0), (SucceedAfter, 1), (SucceedAfter, 2)]);
+
+		System::set_block_number(1);
+		upgrade();
+		run_to_block(10);
+		assert_eq!(historic().len(), 3);
+		System::reset_events();
+
+		let id = mocked_id(SucceedAfter, 0);
+		assert_ok!(Migrations::clear_historic(
+			RawOrigin::Root.into(),
+			HistoricCleanupSelector::Specific(vec![codec::Encode::encode(&id).try_into().unwrap()])
+		));
+		assert_eq!(historic(), vec![mocked_id(SucceedAfter, 1), mocked_id(SucceedAfter, 2)]);
+		assert_events(vec![Event::HistoricCleared { next_cursor: None }]);
+
+		assert_ok!(Migrations::clear_historic(
+			RawOrigin::Root.into(),
+			HistoricCleanupSelector::Wildcard { limit: 10, previous_cursor: None }
+		));
+		assert!(historic().is_empty());
+		assert_events(vec![Event::HistoricCleared { next_cursor: None }]);
+	});
+}
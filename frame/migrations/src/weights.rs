@@ -0,0 +1,325 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! Autogenerated weights for pallet_migrations
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2023-07-12, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! WORST CASE MAP SIZE: `1000000`
+//! HOSTNAME: `runner-e8ezs4ez-project-145-concurrent-0`, CPU: `Intel(R) Xeon(R) CPU @ 2.60GHz`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+
+// Executed Command:
+// ./target/production/substrate
+// benchmark
+// pallet
+// --chain=dev
+// --steps=50
+// --repeat=20
+// --pallet=pallet_migrations
+// --no-storage-info
+// --no-median-slopes
+// --no-min-squares
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --output=./frame/migrations/src/weights.rs
+// --header=./HEADER-APACHE2
+// --template=./.maintain/frame-weight-template.hbs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_migrations.
+pub trait WeightInfo {
+	fn onboard_new_mbms() -> Weight;
+	fn progress_mbms_none() -> Weight;
+	fn exec_migration_completed() -> Weight;
+	fn exec_migration_skipped_historic() -> Weight;
+	fn exec_migration_advance() -> Weight;
+	fn exec_migration_complete() -> Weight;
+	fn exec_migration_fail() -> Weight;
+	fn force_set_cursor() -> Weight;
+	fn force_onboard_mbms() -> Weight;
+	fn clear_historic(n: u32, ) -> Weight;
+}
+
+/// Weights for pallet_migrations using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: MultiBlockMigrations Cursor (r:1 w:1)
+	/// Proof: MultiBlockMigrations Cursor (max_values: Some(1), max_size: Some(65550), added: 66045, mode: MaxEncodedLen)
+	/// Storage: unknown `0x583359fe0e84d953a9dd84e8addb08a5` (r:1 w:0)
+	/// Proof Skipped: unknown `0x583359fe0e84d953a9dd84e8addb08a5` (r:1 w:0)
+	fn onboard_new_mbms() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `109`
+		//  Estimated: `67035`
+		// Minimum execution time: 7_932_000 picoseconds.
+		Weight::from_parts(8_326_000, 67035)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MultiBlockMigrations Cursor (r:1 w:0)
+	/// Proof: MultiBlockMigrations Cursor (max_values: Some(1), max_size: Some(65550), added: 66045, mode: MaxEncodedLen)
+	fn progress_mbms_none() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `42`
+		//  Estimated: `67035`
+		// Minimum execution time: 2_557_000 picoseconds.
+		Weight::from_parts(2_721_000, 67035)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	/// Storage: unknown `0x583359fe0e84d953a9dd84e8addb08a5` (r:1 w:0)
+	/// Proof Skipped: unknown `0x583359fe0e84d953a9dd84e8addb08a5` (r:1 w:0)
+	/// Storage: MultiBlockMigrations Cursor (r:0 w:1)
+	/// Proof: MultiBlockMigrations Cursor (max_values: Some(1), max_size: Some(65550), added: 66045, mode: MaxEncodedLen)
+	fn exec_migration_completed() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `96`
+		//  Estimated: `3561`
+		// Minimum execution time: 5_953_000 picoseconds.
+		Weight::from_parts(6_214_000, 3561)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: unknown `0x583359fe0e84d953a9dd84e8addb08a5` (r:1 w:0)
+	/// Proof Skipped: unknown `0x583359fe0e84d953a9dd84e8addb08a5` (r:1 w:0)
+	/// Storage: MultiBlockMigrations Historic (r:1 w:0)
+	/// Proof: MultiBlockMigrations Historic (max_values: None, max_size: Some(266), added: 2741, mode: MaxEncodedLen)
+	fn exec_migration_skipped_historic() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `154`
+		//  Estimated: `3731`
+		// Minimum execution time: 11_202_000 picoseconds.
+		Weight::from_parts(11_635_000, 3731)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+	}
+	/// Storage: unknown `0x583359fe0e84d953a9dd84e8addb08a5` (r:1 w:0)
+	/// Proof Skipped: unknown `0x583359fe0e84d953a9dd84e8addb08a5` (r:1 w:0)
+	/// Storage: MultiBlockMigrations Historic (r:1 w:0)
+	/// Proof: MultiBlockMigrations Historic (max_values: None, max_size: Some(266), added: 2741, mode: MaxEncodedLen)
+	fn exec_migration_advance() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `96`
+		//  Estimated: `3731`
+		// Minimum execution time: 10_681_000 picoseconds.
+		Weight::from_parts(11_002_000, 3731)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+	}
+	/// Storage: unknown `0x583359fe0e84d953a9dd84e8addb08a5` (r:1 w:0)
+	/// Proof Skipped: unknown `0x583359fe0e84d953a9dd84e8addb08a5` (r:1 w:0)
+	/// Storage: MultiBlockMigrations Historic (r:1 w:1)
+	/// Proof: MultiBlockMigrations Historic (max_values: None, max_size: Some(266), added: 2741, mode: MaxEncodedLen)
+	fn exec_migration_complete() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `96`
+		//  Estimated: `3731`
+		// Minimum execution time: 12_174_000 picoseconds.
+		Weight::from_parts(12_591_000, 3731)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: unknown `0x583359fe0e84d953a9dd84e8addb08a5` (r:1 w:0)
+	/// Proof Skipped: unknown `0x583359fe0e84d953a9dd84e8addb08a5` (r:1 w:0)
+	/// Storage: MultiBlockMigrations Historic (r:1 w:0)
+	/// Proof: MultiBlockMigrations Historic (max_values: None, max_size: Some(266), added: 2741, mode: MaxEncodedLen)
+	/// Storage: MultiBlockMigrations Cursor (r:0 w:1)
+	/// Proof: MultiBlockMigrations Cursor (max_values: Some(1), max_size: Some(65550), added: 66045, mode: MaxEncodedLen)
+	fn exec_migration_fail() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `96`
+		//  Estimated: `3731`
+		// Minimum execution time: 12_812_000 picoseconds.
+		Weight::from_parts(13_244_000, 3731)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MultiBlockMigrations Cursor (r:0 w:1)
+	/// Proof: MultiBlockMigrations Cursor (max_values: Some(1), max_size: Some(65550), added: 66045, mode: MaxEncodedLen)
+	fn force_set_cursor() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 2_384_000 picoseconds.
+		Weight::from_parts(2_519_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MultiBlockMigrations Cursor (r:1 w:1)
+	/// Proof: MultiBlockMigrations Cursor (max_values: Some(1), max_size: Some(65550), added: 66045, mode: MaxEncodedLen)
+	/// Storage: unknown `0x583359fe0e84d953a9dd84e8addb08a5` (r:1 w:0)
+	/// Proof Skipped: unknown `0x583359fe0e84d953a9dd84e8addb08a5` (r:1 w:0)
+	fn force_onboard_mbms() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `109`
+		//  Estimated: `67035`
+		// Minimum execution time: 9_611_000 picoseconds.
+		Weight::from_parts(9_972_000, 67035)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: MultiBlockMigrations Historic (r:0 w:10)
+	/// Proof: MultiBlockMigrations Historic (max_values: None, max_size: Some(266), added: 2741, mode: MaxEncodedLen)
+	/// The range of component `n` is `[0, 1000]`.
+	fn clear_historic(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1122 + n * (271 ±0)`
+		//  Estimated: `3834 + n * (2740 ±0)`
+		// Minimum execution time: 16_203_000 picoseconds.
+		Weight::from_parts(16_592_000, 3834)
+			// Standard Error: 3_091
+			.saturating_add(Weight::from_parts(1_396_912, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2740).saturating_mul(n.into()))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: MultiBlockMigrations Cursor (r:1 w:1)
+	/// Proof: MultiBlockMigrations Cursor (max_values: Some(1), max_size: Some(65550), added: 66045, mode: MaxEncodedLen)
+	/// Storage: unknown `0x583359fe0e84d953a9dd84e8addb08a5` (r:1 w:0)
+	/// Proof Skipped: unknown `0x583359fe0e84d953a9dd84e8addb08a5` (r:1 w:0)
+	fn onboard_new_mbms() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `109`
+		//  Estimated: `67035`
+		// Minimum execution time: 7_932_000 picoseconds.
+		Weight::from_parts(8_326_000, 67035)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MultiBlockMigrations Cursor (r:1 w:0)
+	/// Proof: MultiBlockMigrations Cursor (max_values: Some(1), max_size: Some(65550), added: 66045, mode: MaxEncodedLen)
+	fn progress_mbms_none() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `42`
+		//  Estimated: `67035`
+		// Minimum execution time: 2_557_000 picoseconds.
+		Weight::from_parts(2_721_000, 67035)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+	/// Storage: unknown `0x583359fe0e84d953a9dd84e8addb08a5` (r:1 w:0)
+	/// Proof Skipped: unknown `0x583359fe0e84d953a9dd84e8addb08a5` (r:1 w:0)
+	/// Storage: MultiBlockMigrations Cursor (r:0 w:1)
+	/// Proof: MultiBlockMigrations Cursor (max_values: Some(1), max_size: Some(65550), added: 66045, mode: MaxEncodedLen)
+	fn exec_migration_completed() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `96`
+		//  Estimated: `3561`
+		// Minimum execution time: 5_953_000 picoseconds.
+		Weight::from_parts(6_214_000, 3561)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: unknown `0x583359fe0e84d953a9dd84e8addb08a5` (r:1 w:0)
+	/// Proof Skipped: unknown `0x583359fe0e84d953a9dd84e8addb08a5` (r:1 w:0)
+	/// Storage: MultiBlockMigrations Historic (r:1 w:0)
+	/// Proof: MultiBlockMigrations Historic (max_values: None, max_size: Some(266), added: 2741, mode: MaxEncodedLen)
+	fn exec_migration_skipped_historic() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `154`
+		//  Estimated: `3731`
+		// Minimum execution time: 11_202_000 picoseconds.
+		Weight::from_parts(11_635_000, 3731)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+	}
+	/// Storage: unknown `0x583359fe0e84d953a9dd84e8addb08a5` (r:1 w:0)
+	/// Proof Skipped: unknown `0x583359fe0e84d953a9dd84e8addb08a5` (r:1 w:0)
+	/// Storage: MultiBlockMigrations Historic (r:1 w:0)
+	/// Proof: MultiBlockMigrations Historic (max_values: None, max_size: Some(266), added: 2741, mode: MaxEncodedLen)
+	fn exec_migration_advance() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `96`
+		//  Estimated: `3731`
+		// Minimum execution time: 10_681_000 picoseconds.
+		Weight::from_parts(11_002_000, 3731)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+	}
+	/// Storage: unknown `0x583359fe0e84d953a9dd84e8addb08a5` (r:1 w:0)
+	/// Proof Skipped: unknown `0x583359fe0e84d953a9dd84e8addb08a5` (r:1 w:0)
+	/// Storage: MultiBlockMigrations Historic (r:1 w:1)
+	/// Proof: MultiBlockMigrations Historic (max_values: None, max_size: Some(266), added: 2741, mode: MaxEncodedLen)
+	fn exec_migration_complete() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `96`
+		//  Estimated: `3731`
+		// Minimum execution time: 12_174_000 picoseconds.
+		Weight::from_parts(12_591_000, 3731)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: unknown `0x583359fe0e84d953a9dd84e8addb08a5` (r:1 w:0)
+	/// Proof Skipped: unknown `0x583359fe0e84d953a9dd84e8addb08a5` (r:1 w:0)
+	/// Storage: MultiBlockMigrations Historic (r:1 w:0)
+	/// Proof: MultiBlockMigrations Historic (max_values: None, max_size: Some(266), added: 2741, mode: MaxEncodedLen)
+	/// Storage: MultiBlockMigrations Cursor (r:0 w:1)
+	/// Proof: MultiBlockMigrations Cursor (max_values: Some(1), max_size: Some(65550), added: 66045, mode: MaxEncodedLen)
+	fn exec_migration_fail() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `96`
+		//  Estimated: `3731`
+		// Minimum execution time: 12_812_000 picoseconds.
+		Weight::from_parts(13_244_000, 3731)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MultiBlockMigrations Cursor (r:0 w:1)
+	/// Proof: MultiBlockMigrations Cursor (max_values: Some(1), max_size: Some(65550), added: 66045, mode: MaxEncodedLen)
+	fn force_set_cursor() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 2_384_000 picoseconds.
+		Weight::from_parts(2_519_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MultiBlockMigrations Cursor (r:1 w:1)
+	/// Proof: MultiBlockMigrations Cursor (max_values: Some(1), max_size: Some(65550), added: 66045, mode: MaxEncodedLen)
+	/// Storage: unknown `0x583359fe0e84d953a9dd84e8addb08a5` (r:1 w:0)
+	/// Proof Skipped: unknown `0x583359fe0e84d953a9dd84e8addb08a5` (r:1 w:0)
+	fn force_onboard_mbms() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `109`
+		//  Estimated: `67035`
+		// Minimum execution time: 9_611_000 picoseconds.
+		Weight::from_parts(9_972_000, 67035)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: MultiBlockMigrations Historic (r:0 w:10)
+	/// Proof: MultiBlockMigrations Historic (max_values: None, max_size: Some(266), added: 2741, mode: MaxEncodedLen)
+	/// The range of component `n` is `[0, 1000]`.
+	fn clear_historic(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1122 + n * (271 ±0)`
+		//  Estimated: `3834 + n * (2740 ±0)`
+		// Minimum execution time: 16_203_000 picoseconds.
+		Weight::from_parts(16_592_000, 3834)
+			// Standard Error: 3_091
+			.saturating_add(Weight::from_parts(1_396_912, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2740).saturating_mul(n.into()))
+	}
+}
@@ -678,9 +678,13 @@ benchmarks_instance_pallet! {
 		let (item, ..) = mint_item::<T, I>(0);
 		let buyer: T::AccountId = account("buyer", 0, SEED);
 		let buyer_lookup = T::Lookup::unlookup(buyer.clone());
-		let price = ItemPrice::<T, I>::from(0u32);
-		let origin = SystemOrigin::Signed(seller.clone()).into();
-		Nfts::<T, I>::set_price(origin, collection, item, Some(price), Some(buyer_lookup))?;
+		// The royalty creates the account of its recipient.
+		let price = T::Currency::minimum_balance().saturating_mul(100u32.into());
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let recipient_lookup = T::Lookup::unlookup(recipient);
+		let origin = SystemOrigin::Signed(seller.clone());
+		Nfts::<T, I>::set_collection_royalty(origin.clone().into(), collection, recipient_lookup, Permill::from_percent(10))?;
+		Nfts::<T, I>::set_price(origin.into(), collection, item, Some(price), Some(buyer_lookup))?;
 		T::Currency::make_free_balance_be(&buyer, DepositBalanceOf::<T, I>::max_value());
 	}: _(SystemOrigin::Signed(buyer.clone()), collection, item, price)
 	verify {
@@ -765,14 +769,18 @@ benchmarks_instance_pallet! {
 		let (collection, caller, _) = create_collection::<T, I>();
 		let (item1, ..) = mint_item::<T, I>(0);
 		let (item2, ..) = mint_item::<T, I>(1);
-		let price = ItemPrice::<T, I>::from(0u32);
+		// The royalty creates the account of its recipient.
+		let price = T::Currency::minimum_balance().saturating_mul(100u32.into());
 		let price_direction = PriceDirection::Receive;
 		let price_with_direction = PriceWithDirection { amount: price, direction: price_direction };
 		let duration = T::MaxDeadlineDuration::get();
 		let target: T::AccountId = account("target", 0, SEED);
 		let target_lookup = T::Lookup::unlookup(target.clone());
-		T::Currency::make_free_balance_be(&target, T::Currency::minimum_balance());
+		T::Currency::make_free_balance_be(&target, DepositBalanceOf::<T, I>::max_value());
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let recipient_lookup = T::Lookup::unlookup(recipient);
 		let origin = SystemOrigin::Signed(caller.clone());
+		Nfts::<T, I>::set_collection_royalty(origin.clone().into(), collection, recipient_lookup, Permill::from_percent(10))?;
 		frame_system::Pallet::<T>::set_block_number(One::one());
 		Nfts::<T, I>::transfer(origin.clone().into(), collection, item2, target_lookup)?;
 		Nfts::<T, I>::create_swap(
@@ -892,5 +900,49 @@ benchmarks_instance_pallet! {
 		);
 	}
 
+	set_collection_royalty {
+		let (collection, caller, _) = create_collection::<T, I>();
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let recipient_lookup = T::Lookup::unlookup(recipient.clone());
+		let percentage = Permill::from_percent(10);
+	}: _(SystemOrigin::Signed(caller), collection, recipient_lookup, percentage)
+	verify {
+		assert_last_event::<T, I>(Event::CollectionRoyaltySet { collection, recipient, percentage }.into());
+	}
+
+	clear_collection_royalty {
+		let (collection, caller, _) = create_collection::<T, I>();
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let recipient_lookup = T::Lookup::unlookup(recipient);
+		let origin = SystemOrigin::Signed(caller.clone());
+		Nfts::<T, I>::set_collection_royalty(origin.into(), collection, recipient_lookup, Permill::from_percent(10))?;
+	}: _(SystemOrigin::Signed(caller), collection)
+	verify {
+		assert_last_event::<T, I>(Event::CollectionRoyaltyCleared { collection }.into());
+	}
+
+	set_item_royalty {
+		let (collection, caller, _) = create_collection::<T, I>();
+		let (item, ..) = mint_item::<T, I>(0);
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let recipient_lookup = T::Lookup::unlookup(recipient.clone());
+		let percentage = Permill::from_percent(10);
+	}: _(SystemOrigin::Signed(caller), collection, item, recipient_lookup, percentage)
+	verify {
+		assert_last_event::<T, I>(Event::ItemRoyaltySet { collection, item, recipient, percentage }.into());
+	}
+
+	clear_item_royalty {
+		let (collection, caller, _) = create_collection::<T, I>();
+		let (item, ..) = mint_item::<T, I>(0);
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let recipient_lookup = T::Lookup::unlookup(recipient);
+		let origin = SystemOrigin::Signed(caller.clone());
+		Nfts::<T, I>::set_item_royalty(origin.into(), collection, item, recipient_lookup, Permill::from_percent(10))?;
+	}: _(SystemOrigin::Signed(caller), collection, item)
+	verify {
+		assert_last_event::<T, I>(Event::ItemRoyaltyCleared { collection, item }.into());
+	}
+
 	impl_benchmark_test_suite!(Nfts, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -0,0 +1,142 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Currency, ExistenceRequirement::KeepAlive},
+};
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	pub(crate) fn do_set_collection_royalty(
+		maybe_check_owner: Option<T::AccountId>,
+		collection: T::CollectionId,
+		recipient: T::AccountId,
+		percentage: Permill,
+	) -> DispatchResult {
+		Self::ensure_royalties_unlocked(maybe_check_owner, &collection)?;
+		ensure!(
+			ItemPriceOf::<T, I>::iter_prefix_values(&collection).next().is_none() &&
+				PendingSwapOf::<T, I>::iter_prefix_values(&collection).next().is_none(),
+			Error::<T, I>::ItemOnSale
+		);
+
+		CollectionRoyaltyOf::<T, I>::insert(
+			&collection,
+			RoyaltyInfo { recipient: recipient.clone(), percentage },
+		);
+		Self::deposit_event(Event::CollectionRoyaltySet { collection, recipient, percentage });
+		Ok(())
+	}
+
+	pub(crate) fn do_clear_collection_royalty(
+		maybe_check_owner: Option<T::AccountId>,
+		collection: T::CollectionId,
+	) -> DispatchResult {
+		Self::ensure_royalties_unlocked(maybe_check_owner, &collection)?;
+
+		CollectionRoyaltyOf::<T, I>::take(&collection).ok_or(Error::<T, I>::RoyaltyNotFound)?;
+		Self::deposit_event(Event::CollectionRoyaltyCleared { collection });
+		Ok(())
+	}
+
+	pub(crate) fn do_set_item_royalty(
+		maybe_check_owner: Option<T::AccountId>,
+		collection: T::CollectionId,
+		item: T::ItemId,
+		recipient: T::AccountId,
+		percentage: Permill,
+	) -> DispatchResult {
+		Self::ensure_royalties_unlocked(maybe_check_owner, &collection)?;
+		ensure!(Item::<T, I>::contains_key(&collection, &item), Error::<T, I>::UnknownItem);
+		ensure!(
+			!ItemPriceOf::<T, I>::contains_key(&collection, &item) &&
+				!PendingSwapOf::<T, I>::contains_key(&collection, &item),
+			Error::<T, I>::ItemOnSale
+		);
+
+		ItemRoyaltyOf::<T, I>::insert(
+			&collection,
+			&item,
+			RoyaltyInfo { recipient: recipient.clone(), percentage },
+		);
+		Self::deposit_event(Event::ItemRoyaltySet { collection, item, recipient, percentage });
+		Ok(())
+	}
+
+	pub(crate) fn do_clear_item_royalty(
+		maybe_check_owner: Option<T::AccountId>,
+		collection: T::CollectionId,
+		item: T::ItemId,
+	) -> DispatchResult {
+		Self::ensure_royalties_unlocked(maybe_check_owner, &collection)?;
+
+		ItemRoyaltyOf::<T, I>::take(&collection, &item).ok_or(Error::<T, I>::RoyaltyNotFound)?;
+		Self::deposit_event(Event::ItemRoyaltyCleared { collection, item });
+		Ok(())
+	}
+
+	/// Returns the royalty that applies to sales of `item`: the item's own royalty if it has one,
+	/// otherwise the royalty of its collection.
+	pub fn royalty_of(collection: &T::CollectionId, item: &T::ItemId) -> Option<RoyaltyInfoOf<T>> {
+		ItemRoyaltyOf::<T, I>::get(collection, item)
+			.or_else(|| CollectionRoyaltyOf::<T, I>::get(collection))
+	}
+
+	/// Transfer `price` from `payer` to `seller` for the sale of `item`, paying the item's
+	/// royalty, if any, out of it to the royalty recipient.
+	///
+	/// A royalty below the existential deposit is left to the `seller` if the recipient's account
+	/// doesn't exist, as it couldn't be created by the transfer.
+	pub(crate) fn pay_with_royalty(
+		collection: T::CollectionId,
+		item: T::ItemId,
+		payer: &T::AccountId,
+		seller: &T::AccountId,
+		price: ItemPrice<T, I>,
+	) -> DispatchResult {
+		let mut remainder = price;
+		if let Some(RoyaltyInfo { recipient, percentage }) = Self::royalty_of(&collection, &item) {
+			let amount = percentage.mul_floor(price);
+			let can_receive = amount >= T::Currency::minimum_balance() ||
+				!T::Currency::total_balance(&recipient).is_zero();
+			if &recipient != seller && !amount.is_zero() && can_receive {
+				T::Currency::transfer(payer, &recipient, amount, KeepAlive)?;
+				remainder = remainder.saturating_sub(amount);
+				Self::deposit_event(Event::RoyaltyPaid { collection, item, recipient, amount });
+			}
+		}
+		T::Currency::transfer(payer, seller, remainder, KeepAlive)
+	}
+
+	fn ensure_royalties_unlocked(
+		maybe_check_owner: Option<T::AccountId>,
+		collection: &T::CollectionId,
+	) -> DispatchResult {
+		let details =
+			Collection::<T, I>::get(collection).ok_or(Error::<T, I>::UnknownCollection)?;
+		if let Some(check_owner) = &maybe_check_owner {
+			ensure!(check_owner == &details.owner, Error::<T, I>::NoPermission);
+		}
+
+		let collection_config = Self::get_collection_config(collection)?;
+		ensure!(
+			collection_config.is_setting_enabled(CollectionSetting::UnlockedRoyalties),
+			Error::<T, I>::LockedCollectionRoyalties
+		);
+		Ok(())
+	}
+}
@@ -147,6 +147,15 @@ impl<T: Config<I>, I: 'static> InspectRole<<T as SystemConfig>::AccountId> for P
 	}
 }
 
+impl<T: Config<I>, I: 'static> InspectRoyalty<<T as SystemConfig>::AccountId> for Pallet<T, I> {
+	fn royalty(
+		collection: &Self::CollectionId,
+		item: &Self::ItemId,
+	) -> Option<(<T as SystemConfig>::AccountId, Permill)> {
+		Self::royalty_of(collection, item).map(|royalty| (royalty.recipient, royalty.percentage))
+	}
+}
+
 impl<T: Config<I>, I: 'static> Create<<T as SystemConfig>::AccountId, CollectionConfigFor<T, I>>
 	for Pallet<T, I>
 {
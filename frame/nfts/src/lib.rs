@@ -52,7 +52,7 @@ use frame_support::traits::{
 use frame_system::Config as SystemConfig;
 use sp_runtime::{
 	traits::{IdentifyAccount, Saturating, StaticLookup, Verify, Zero},
-	RuntimeDebug,
+	Permill, RuntimeDebug,
 };
 use sp_std::prelude::*;
 
@@ -72,7 +72,7 @@ pub mod pallet {
 	use frame_system::pallet_prelude::*;
 
 	/// The current storage version.
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
@@ -373,6 +373,23 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// The royalty paid on sales of the items of a collection.
+	#[pallet::storage]
+	pub type CollectionRoyaltyOf<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::CollectionId, RoyaltyInfoOf<T>, OptionQuery>;
+
+	/// The royalty paid on sales of an item. Takes precedence over the collection's royalty.
+	#[pallet::storage]
+	pub type ItemRoyaltyOf<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		RoyaltyInfoOf<T>,
+		OptionQuery,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config<I>, I: 'static = ()> {
@@ -553,6 +570,30 @@ pub mod pallet {
 			attribute: PalletAttributes<T::CollectionId>,
 			value: BoundedVec<u8, T::ValueLimit>,
 		},
+		/// A royalty has been set for a `collection`.
+		CollectionRoyaltySet {
+			collection: T::CollectionId,
+			recipient: T::AccountId,
+			percentage: Permill,
+		},
+		/// The royalty has been cleared for a `collection`.
+		CollectionRoyaltyCleared { collection: T::CollectionId },
+		/// A royalty has been set for an `item` of the `collection`.
+		ItemRoyaltySet {
+			collection: T::CollectionId,
+			item: T::ItemId,
+			recipient: T::AccountId,
+			percentage: Permill,
+		},
+		/// The royalty has been cleared for an `item` of the `collection`.
+		ItemRoyaltyCleared { collection: T::CollectionId, item: T::ItemId },
+		/// A royalty was paid out of the sale price of an `item`.
+		RoyaltyPaid {
+			collection: T::CollectionId,
+			item: T::ItemId,
+			recipient: T::AccountId,
+			amount: ItemPrice<T, I>,
+		},
 	}
 
 	#[pallet::error]
@@ -647,6 +688,13 @@ pub mod pallet {
 		CollectionNotEmpty,
 		/// The witness data should be provided.
 		WitnessRequired,
+		/// Collection's royalties are locked.
+		LockedCollectionRoyalties,
+		/// The provided royalty can't be found.
+		RoyaltyNotFound,
+		/// The royalty can't be set while the item, or an item of the collection, is for sale or
+		/// offered in a swap.
+		ItemOnSale,
 	}
 
 	#[pallet::call]
@@ -1889,6 +1937,114 @@ pub mod pallet {
 			Self::validate_signature(&Encode::encode(&data), &signature, &signer)?;
 			Self::do_set_attributes_pre_signed(origin, data, signer)
 		}
+
+		/// Set the royalty paid on every sale of the items of a collection.
+		///
+		/// Origin must be either `ForceOrigin` or `Signed` and the sender should be the Owner of
+		/// the `collection`.
+		///
+		/// The royalty is deducted from the price paid when an item is bought with `buy_item` or
+		/// sold as part of a `claim_swap`, unless the item has a royalty of its own. It can't be
+		/// set while any item of the collection is for sale or offered in a swap.
+		///
+		/// - `collection`: The identifier of the collection.
+		/// - `recipient`: The account to receive the royalty.
+		/// - `percentage`: The share of the sale price paid to the `recipient`.
+		///
+		/// Emits `CollectionRoyaltySet`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::call_index(39)]
+		#[pallet::weight(T::WeightInfo::set_collection_royalty())]
+		pub fn set_collection_royalty(
+			origin: OriginFor<T>,
+			collection: T::CollectionId,
+			recipient: AccountIdLookupOf<T>,
+			percentage: Permill,
+		) -> DispatchResult {
+			let maybe_check_owner = T::ForceOrigin::try_origin(origin)
+				.map(|_| None)
+				.or_else(|origin| ensure_signed(origin).map(Some).map_err(DispatchError::from))?;
+			let recipient = T::Lookup::lookup(recipient)?;
+			Self::do_set_collection_royalty(maybe_check_owner, collection, recipient, percentage)
+		}
+
+		/// Clear the royalty of a collection.
+		///
+		/// Origin must be either `ForceOrigin` or `Signed` and the sender should be the Owner of
+		/// the `collection`.
+		///
+		/// - `collection`: The identifier of the collection whose royalty to clear.
+		///
+		/// Emits `CollectionRoyaltyCleared`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::call_index(40)]
+		#[pallet::weight(T::WeightInfo::clear_collection_royalty())]
+		pub fn clear_collection_royalty(
+			origin: OriginFor<T>,
+			collection: T::CollectionId,
+		) -> DispatchResult {
+			let maybe_check_owner = T::ForceOrigin::try_origin(origin)
+				.map(|_| None)
+				.or_else(|origin| ensure_signed(origin).map(Some).map_err(DispatchError::from))?;
+			Self::do_clear_collection_royalty(maybe_check_owner, collection)
+		}
+
+		/// Set the royalty paid on every sale of an item, overriding the collection's royalty.
+		///
+		/// Origin must be either `ForceOrigin` or `Signed` and the sender should be the Owner of
+		/// the `collection`.
+		///
+		/// The royalty can't be set while the item is for sale or offered in a swap.
+		///
+		/// - `collection`: The identifier of the collection.
+		/// - `item`: The identifier of the item.
+		/// - `recipient`: The account to receive the royalty.
+		/// - `percentage`: The share of the sale price paid to the `recipient`.
+		///
+		/// Emits `ItemRoyaltySet`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::call_index(41)]
+		#[pallet::weight(T::WeightInfo::set_item_royalty())]
+		pub fn set_item_royalty(
+			origin: OriginFor<T>,
+			collection: T::CollectionId,
+			item: T::ItemId,
+			recipient: AccountIdLookupOf<T>,
+			percentage: Permill,
+		) -> DispatchResult {
+			let maybe_check_owner = T::ForceOrigin::try_origin(origin)
+				.map(|_| None)
+				.or_else(|origin| ensure_signed(origin).map(Some).map_err(DispatchError::from))?;
+			let recipient = T::Lookup::lookup(recipient)?;
+			Self::do_set_item_royalty(maybe_check_owner, collection, item, recipient, percentage)
+		}
+
+		/// Clear the royalty of an item, so the collection's royalty applies again.
+		///
+		/// Origin must be either `ForceOrigin` or `Signed` and the sender should be the Owner of
+		/// the `collection`.
+		///
+		/// - `collection`: The identifier of the collection.
+		/// - `item`: The identifier of the item whose royalty to clear.
+		///
+		/// Emits `ItemRoyaltyCleared`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::call_index(42)]
+		#[pallet::weight(T::WeightInfo::clear_item_royalty())]
+		pub fn clear_item_royalty(
+			origin: OriginFor<T>,
+			collection: T::CollectionId,
+			item: T::ItemId,
+		) -> DispatchResult {
+			let maybe_check_owner = T::ForceOrigin::try_origin(origin)
+				.map(|_| None)
+				.or_else(|origin| ensure_signed(origin).map(Some).map_err(DispatchError::from))?;
+			Self::do_clear_item_royalty(maybe_check_owner, collection, item)
+		}
 	}
 }
 
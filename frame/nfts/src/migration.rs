@@ -118,3 +118,84 @@ pub mod v1 {
 		}
 	}
 }
+
+pub mod v2 {
+	use frame_support::{pallet_prelude::*, weights::Weight};
+
+	use super::*;
+
+	/// Locks the royalties of all existing collections.
+	///
+	/// The royalties are unlocked by default for new collections, so without this migration the
+	/// owners of existing collections, including those that locked all of their settings, could
+	/// start charging royalties on the sales of their items.
+	pub struct MigrateToV2<T, I = ()>(sp_std::marker::PhantomData<(T, I)>);
+	impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for MigrateToV2<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			let current_version = Pallet::<T, I>::current_storage_version();
+			let onchain_version = Pallet::<T, I>::on_chain_storage_version();
+
+			log::info!(
+				target: LOG_TARGET,
+				"Running migration with current storage version {:?} / onchain {:?}",
+				current_version,
+				onchain_version
+			);
+
+			if onchain_version == 1 && current_version == 2 {
+				let mut translated = 0u64;
+				CollectionConfigOf::<T, I>::translate::<CollectionConfigFor<T, I>, _>(
+					|_, mut config| {
+						config.disable_setting(CollectionSetting::UnlockedRoyalties);
+						translated.saturating_inc();
+						Some(config)
+					},
+				);
+
+				current_version.put::<Pallet<T, I>>();
+
+				log::info!(
+					target: LOG_TARGET,
+					"Upgraded {} records, storage to version {:?}",
+					translated,
+					current_version
+				);
+				T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+			} else {
+				log::info!(
+					target: LOG_TARGET,
+					"Migration did not execute. This probably should be removed"
+				);
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			let current_version = Pallet::<T, I>::current_storage_version();
+			let onchain_version = Pallet::<T, I>::on_chain_storage_version();
+			ensure!(onchain_version == 1 && current_version == 2, "migration from version 1 to 2.");
+			let prev_count = CollectionConfigOf::<T, I>::iter().count();
+			Ok((prev_count as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(prev_count: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let prev_count: u32 = Decode::decode(&mut prev_count.as_slice()).expect(
+				"the state parameter should be something that was generated by pre_upgrade",
+			);
+			let post_count = CollectionConfigOf::<T, I>::iter().count() as u32;
+			ensure!(
+				prev_count == post_count,
+				"the records count before and after the migration should be the same"
+			);
+			let all_locked = CollectionConfigOf::<T, I>::iter_values()
+				.all(|config| config.has_disabled_setting(CollectionSetting::UnlockedRoyalties));
+			ensure!(all_locked, "the royalties of all collections should be locked");
+
+			ensure!(Pallet::<T, I>::on_chain_storage_version() == 2, "wrong storage version");
+
+			Ok(())
+		}
+	}
+}
@@ -23,7 +23,7 @@ use frame_support::{
 	assert_noop, assert_ok,
 	dispatch::Dispatchable,
 	traits::{
-		tokens::nonfungibles_v2::{Create, Destroy, Mutate},
+		tokens::nonfungibles_v2::{Create, Destroy, InspectRoyalty, Mutate},
 		Currency, Get,
 	},
 };
@@ -3716,3 +3716,592 @@ fn basic_create_collection_with_id_should_work() {
 		);
 	});
 }
+
+#[test]
+fn set_royalties_should_work() {
+	new_test_ext().execute_with(|| {
+		let user_1 = account(1);
+		let user_2 = account(2);
+		let recipient = account(3);
+		let collection_id = 0;
+		let item_id = 1;
+
+		Balances::make_free_balance_be(&user_1, 100);
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			user_2.clone(),
+			None
+		));
+
+		// only the collection owner or the force origin can set royalties
+		assert_noop!(
+			Nfts::set_collection_royalty(
+				RuntimeOrigin::signed(user_2.clone()),
+				collection_id,
+				recipient.clone(),
+				Permill::from_percent(10),
+			),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			Nfts::set_item_royalty(
+				RuntimeOrigin::signed(user_2.clone()),
+				collection_id,
+				item_id,
+				user_2.clone(),
+				Permill::from_percent(10),
+			),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			Nfts::set_collection_royalty(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id + 1,
+				recipient.clone(),
+				Permill::from_percent(10),
+			),
+			Error::<Test>::UnknownCollection
+		);
+		assert_noop!(
+			Nfts::set_item_royalty(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				item_id + 1,
+				recipient.clone(),
+				Permill::from_percent(10),
+			),
+			Error::<Test>::UnknownItem
+		);
+		assert_noop!(
+			Nfts::clear_collection_royalty(RuntimeOrigin::signed(user_1.clone()), collection_id),
+			Error::<Test>::RoyaltyNotFound
+		);
+
+		assert_ok!(Nfts::set_collection_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			recipient.clone(),
+			Permill::from_percent(10),
+		));
+		assert!(events().contains(&Event::<Test>::CollectionRoyaltySet {
+			collection: collection_id,
+			recipient: recipient.clone(),
+			percentage: Permill::from_percent(10),
+		}));
+		assert_eq!(
+			<Nfts as InspectRoyalty<AccountIdOf<Test>>>::royalty(&collection_id, &item_id),
+			Some((recipient.clone(), Permill::from_percent(10)))
+		);
+		assert_eq!(
+			<Nfts as InspectRoyalty<AccountIdOf<Test>>>::royalty_for(
+				&collection_id,
+				&item_id,
+				99u64
+			),
+			Some((recipient.clone(), 9))
+		);
+
+		// the item's royalty takes precedence over the collection's one
+		assert_ok!(Nfts::set_item_royalty(
+			RuntimeOrigin::root(),
+			collection_id,
+			item_id,
+			user_1.clone(),
+			Permill::from_percent(5),
+		));
+		assert!(events().contains(&Event::<Test>::ItemRoyaltySet {
+			collection: collection_id,
+			item: item_id,
+			recipient: user_1.clone(),
+			percentage: Permill::from_percent(5),
+		}));
+		assert_eq!(
+			<Nfts as InspectRoyalty<AccountIdOf<Test>>>::royalty(&collection_id, &item_id),
+			Some((user_1.clone(), Permill::from_percent(5)))
+		);
+
+		assert_ok!(Nfts::clear_item_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id
+		));
+		assert!(events().contains(&Event::<Test>::ItemRoyaltyCleared {
+			collection: collection_id,
+			item: item_id,
+		}));
+		assert_eq!(
+			<Nfts as InspectRoyalty<AccountIdOf<Test>>>::royalty(&collection_id, &item_id),
+			Some((recipient.clone(), Permill::from_percent(10)))
+		);
+
+		// royalties can't be changed once locked
+		assert_ok!(Nfts::lock_collection(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			CollectionSettings::from_disabled(CollectionSetting::UnlockedRoyalties.into())
+		));
+		assert_noop!(
+			Nfts::clear_collection_royalty(RuntimeOrigin::root(), collection_id),
+			Error::<Test>::LockedCollectionRoyalties
+		);
+		assert_noop!(
+			Nfts::set_item_royalty(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				item_id,
+				user_1.clone(),
+				Permill::from_percent(5),
+			),
+			Error::<Test>::LockedCollectionRoyalties
+		);
+	});
+}
+
+#[test]
+fn royalties_are_removed_with_items_and_collections() {
+	new_test_ext().execute_with(|| {
+		let user_1 = account(1);
+		let recipient = account(2);
+		let collection_id = 0;
+		let item_id = 1;
+
+		Balances::make_free_balance_be(&user_1, 100);
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			user_1.clone(),
+			None
+		));
+		assert_ok!(Nfts::set_collection_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			recipient.clone(),
+			Permill::from_percent(10),
+		));
+		assert_ok!(Nfts::set_item_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			recipient.clone(),
+			Permill::from_percent(20),
+		));
+
+		assert_ok!(Nfts::burn(RuntimeOrigin::signed(user_1.clone()), collection_id, item_id));
+		assert!(!ItemRoyaltyOf::<Test>::contains_key(collection_id, item_id));
+
+		let witness = Nfts::get_destroy_witness(&collection_id).unwrap();
+		assert_ok!(Nfts::destroy(RuntimeOrigin::signed(user_1.clone()), collection_id, witness));
+		assert!(!CollectionRoyaltyOf::<Test>::contains_key(collection_id));
+	});
+}
+
+#[test]
+fn buy_item_should_pay_royalties() {
+	new_test_ext().execute_with(|| {
+		let user_1 = account(1);
+		let user_2 = account(2);
+		let user_3 = account(3);
+		let recipient_1 = account(4);
+		let recipient_2 = account(5);
+		let collection_id = 0;
+		let item_1 = 1;
+		let item_2 = 2;
+		let price = 50;
+		let initial_balance = 100;
+
+		for who in [&user_1, &user_2, &recipient_1, &recipient_2] {
+			Balances::make_free_balance_be(who, initial_balance);
+		}
+		Balances::make_free_balance_be(&user_3, 2 * initial_balance);
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+		for item in [item_1, item_2] {
+			assert_ok!(Nfts::mint(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				item,
+				user_2.clone(),
+				None
+			));
+		}
+		assert_ok!(Nfts::set_collection_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			recipient_1.clone(),
+			Permill::from_percent(10),
+		));
+		assert_ok!(Nfts::set_item_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_2,
+			recipient_2.clone(),
+			Permill::from_percent(30),
+		));
+		for item in [item_1, item_2] {
+			assert_ok!(Nfts::set_price(
+				RuntimeOrigin::signed(user_2.clone()),
+				collection_id,
+				item,
+				Some(price),
+				None,
+			));
+		}
+
+		// the collection's royalty is deducted from the price
+		assert_ok!(Nfts::buy_item(
+			RuntimeOrigin::signed(user_3.clone()),
+			collection_id,
+			item_1,
+			price
+		));
+		assert_eq!(Balances::total_balance(&user_3), 2 * initial_balance - price);
+		assert_eq!(Balances::total_balance(&recipient_1), initial_balance + 5);
+		assert_eq!(Balances::total_balance(&user_2), initial_balance + 45);
+		assert!(events().contains(&Event::<Test>::RoyaltyPaid {
+			collection: collection_id,
+			item: item_1,
+			recipient: recipient_1.clone(),
+			amount: 5,
+		}));
+
+		// the item's royalty overrides the collection's one
+		assert_ok!(Nfts::buy_item(
+			RuntimeOrigin::signed(user_3.clone()),
+			collection_id,
+			item_2,
+			price
+		));
+		assert_eq!(Balances::total_balance(&recipient_1), initial_balance + 5);
+		assert_eq!(Balances::total_balance(&recipient_2), initial_balance + 15);
+		assert_eq!(Balances::total_balance(&user_2), initial_balance + 45 + 35);
+
+		// no royalty is paid when the seller is the royalty recipient
+		assert_ok!(Nfts::set_collection_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			user_3.clone(),
+			Permill::from_percent(10),
+		));
+		assert_ok!(Nfts::set_price(
+			RuntimeOrigin::signed(user_3.clone()),
+			collection_id,
+			item_1,
+			Some(price),
+			None,
+		));
+		events();
+		assert_ok!(Nfts::buy_item(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_1,
+			price
+		));
+		assert_eq!(Balances::total_balance(&user_3), 2 * initial_balance - price);
+		assert!(!events().iter().any(|e| matches!(e, Event::<Test>::RoyaltyPaid { .. })));
+	});
+}
+
+#[test]
+fn claim_swap_should_pay_royalties() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let user_1 = account(1);
+		let user_2 = account(2);
+		let recipient = account(3);
+		let collection_id = 0;
+		let item_1 = 1;
+		let item_2 = 2;
+		let price = 100;
+		let initial_balance = 1000;
+
+		for who in [&user_1, &user_2, &recipient] {
+			Balances::make_free_balance_be(who, initial_balance);
+		}
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_1,
+			user_1.clone(),
+			None,
+		));
+		assert_ok!(Nfts::force_mint(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_2,
+			user_2.clone(),
+			default_item_config(),
+		));
+		assert_ok!(Nfts::set_item_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_1,
+			recipient.clone(),
+			Permill::from_percent(10),
+		));
+
+		// `user_2` pays for `item_1`, so its royalty is deducted
+		let price_with_direction =
+			PriceWithDirection { amount: price, direction: PriceDirection::Receive };
+		assert_ok!(Nfts::create_swap(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_1,
+			collection_id,
+			Some(item_2),
+			Some(price_with_direction.clone()),
+			2,
+		));
+		assert_ok!(Nfts::claim_swap(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_2,
+			collection_id,
+			item_1,
+			Some(price_with_direction),
+		));
+		assert_eq!(Balances::total_balance(&user_2), initial_balance - price);
+		assert_eq!(Balances::total_balance(&recipient), initial_balance + 10);
+		assert_eq!(Balances::total_balance(&user_1), initial_balance + 90);
+		assert!(events().contains(&Event::<Test>::RoyaltyPaid {
+			collection: collection_id,
+			item: item_1,
+			recipient: recipient.clone(),
+			amount: 10,
+		}));
+
+		// `user_2` now owns `item_1` and pays to get `item_2` back, so `item_1`'s owner is the
+		// buyer and `item_2` has no royalty
+		let price_with_direction =
+			PriceWithDirection { amount: price, direction: PriceDirection::Send };
+		assert_ok!(Nfts::create_swap(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_1,
+			collection_id,
+			Some(item_2),
+			Some(price_with_direction.clone()),
+			2,
+		));
+		assert_ok!(Nfts::claim_swap(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_2,
+			collection_id,
+			item_1,
+			Some(price_with_direction),
+		));
+		assert_eq!(Balances::total_balance(&user_2), initial_balance - 2 * price);
+		assert_eq!(Balances::total_balance(&user_1), initial_balance + 90 + price);
+		assert_eq!(Balances::total_balance(&recipient), initial_balance + 10);
+	});
+}
+
+#[test]
+fn royalties_cant_be_set_while_on_sale() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let user_1 = account(1);
+		let user_2 = account(2);
+		let recipient = account(3);
+		let collection_id = 0;
+		let item_1 = 1;
+		let item_2 = 2;
+
+		Balances::make_free_balance_be(&user_1, 100);
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+		for item in [item_1, item_2] {
+			assert_ok!(Nfts::mint(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				item,
+				user_2.clone(),
+				None
+			));
+		}
+		assert_ok!(Nfts::set_collection_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			recipient.clone(),
+			Permill::from_percent(10),
+		));
+
+		// listed items can't have their royalty raised
+		assert_ok!(Nfts::set_price(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_1,
+			Some(50),
+			None,
+		));
+		assert_noop!(
+			Nfts::set_item_royalty(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				item_1,
+				recipient.clone(),
+				Permill::from_percent(100),
+			),
+			Error::<Test>::ItemOnSale
+		);
+		assert_noop!(
+			Nfts::set_collection_royalty(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				recipient.clone(),
+				Permill::from_percent(100),
+			),
+			Error::<Test>::ItemOnSale
+		);
+		// the royalty of items that aren't on sale can be changed
+		assert_ok!(Nfts::set_item_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_2,
+			recipient.clone(),
+			Permill::from_percent(20),
+		));
+		// and royalties can always be cleared
+		assert_ok!(Nfts::clear_collection_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id
+		));
+		assert_ok!(Nfts::set_price(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_1,
+			None,
+			None,
+		));
+
+		// items offered in a swap can't have their royalty raised either
+		assert_ok!(Nfts::create_swap(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_2,
+			collection_id,
+			None,
+			Some(PriceWithDirection { amount: 50, direction: PriceDirection::Receive }),
+			2,
+		));
+		assert_noop!(
+			Nfts::set_item_royalty(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				item_2,
+				recipient.clone(),
+				Permill::from_percent(100),
+			),
+			Error::<Test>::ItemOnSale
+		);
+		assert_noop!(
+			Nfts::set_collection_royalty(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				recipient.clone(),
+				Permill::from_percent(100),
+			),
+			Error::<Test>::ItemOnSale
+		);
+	});
+}
+
+#[test]
+fn royalties_below_existential_deposit_are_left_to_the_seller() {
+	new_test_ext().execute_with(|| {
+		ExistentialDeposit::set(10);
+		let user_1 = account(1);
+		let user_2 = account(2);
+		let recipient = account(3);
+		let collection_id = 0;
+		let item_id = 1;
+		let price = 50;
+		let initial_balance = 100;
+
+		for who in [&user_1, &user_2] {
+			Balances::make_free_balance_be(who, initial_balance);
+		}
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			user_1.clone(),
+			None
+		));
+		assert_ok!(Nfts::set_collection_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			recipient.clone(),
+			Permill::from_percent(10),
+		));
+		assert_ok!(Nfts::set_price(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			Some(price),
+			None,
+		));
+
+		// the royalty of 5 can't create the recipient's account, so the sale goes through
+		// without it
+		events();
+		assert_ok!(Nfts::buy_item(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_id,
+			price
+		));
+		assert_eq!(Balances::total_balance(&recipient), 0);
+		assert_eq!(Balances::total_balance(&user_1), initial_balance + price);
+		assert!(!events().iter().any(|e| matches!(e, Event::<Test>::RoyaltyPaid { .. })));
+
+		// once the recipient's account exists, the royalty is paid
+		Balances::make_free_balance_be(&recipient, initial_balance);
+		assert_ok!(Nfts::set_price(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_id,
+			Some(price),
+			None,
+		));
+		assert_ok!(Nfts::buy_item(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			price
+		));
+		assert_eq!(Balances::total_balance(&recipient), initial_balance + 5);
+		assert_eq!(Balances::total_balance(&user_2), initial_balance - price + 45);
+	});
+}
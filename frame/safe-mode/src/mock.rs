@@ -0,0 +1,188 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test utilities for the safe-mode pallet.
+
+use super::*;
+use crate as pallet_safe_mode;
+
+use frame_support::{
+	parameter_types,
+	traits::{ConstU32, ConstU64},
+};
+use frame_system::{EnsureRoot, EnsureWithSuccess};
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage,
+};
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = SafeMode;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeTask = RuntimeTask;
+	type Nonce = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type Balance = u64;
+	type DustRemoval = ();
+	type RuntimeEvent = RuntimeEvent;
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type MaxHolds = ConstU32<10>;
+}
+
+impl pallet_utility::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type PalletsOrigin = OriginCaller;
+	type WeightInfo = ();
+}
+
+/// Allows every call except those of `pallet-balances`.
+pub struct WhitelistedCalls;
+impl Contains<RuntimeCall> for WhitelistedCalls {
+	fn contains(call: &RuntimeCall) -> bool {
+		!matches!(call, RuntimeCall::Balances(_))
+	}
+}
+
+parameter_types! {
+	pub const EnterDuration: u64 = 7;
+	pub const ExtendDuration: u64 = 30;
+	pub const ForceEnterDuration: u64 = 13;
+	pub const ForceExtendDuration: u64 = 17;
+
+	pub static EnterDepositAmount: Option<u64> = Some(100);
+	pub static ExtendDepositAmount: Option<u64> = Some(100);
+	pub static ReleaseDelay: Option<u64> = Some(20);
+
+	pub static Notifications: Vec<bool> = vec![];
+}
+
+/// Records all enter (`true`) and exit (`false`) notifications.
+pub struct RecordNotifications;
+impl SafeModeNotify for RecordNotifications {
+	fn entered() {
+		Notifications::mutate(|n| n.push(true));
+	}
+
+	fn exited() {
+		Notifications::mutate(|n| n.push(false));
+	}
+}
+
+impl Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type WhitelistedCalls = WhitelistedCalls;
+	type EnterDuration = EnterDuration;
+	type EnterDepositAmount = EnterDepositAmount;
+	type ExtendDuration = ExtendDuration;
+	type ExtendDepositAmount = ExtendDepositAmount;
+	type ForceEnterOrigin = EnsureWithSuccess<EnsureRoot<u64>, u64, ForceEnterDuration>;
+	type ForceExtendOrigin = EnsureWithSuccess<EnsureRoot<u64>, u64, ForceExtendDuration>;
+	type ForceExitOrigin = EnsureRoot<Self::AccountId>;
+	type ForceDepositOrigin = EnsureRoot<Self::AccountId>;
+	type Notify = RecordNotifications;
+	type ReleaseDelay = ReleaseDelay;
+	type WeightInfo = ();
+}
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		Utility: pallet_utility,
+		SafeMode: pallet_safe_mode,
+	}
+);
+
+pub const ALICE: u64 = 0;
+pub const BOB: u64 = 1;
+pub const CHARLIE: u64 = 2;
+
+pub const BAL_ACC0: u64 = 1234;
+pub const BAL_ACC1: u64 = 5678;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(ALICE, BAL_ACC0), (BOB, BAL_ACC1), (CHARLIE, BAL_ACC0)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	pallet_safe_mode::GenesisConfig::<Test> { entered_until: None, _config: Default::default() }
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+	});
+	ext
+}
+
+pub fn next_block() {
+	SafeMode::on_finalize(System::block_number());
+	Balances::on_finalize(System::block_number());
+	System::on_finalize(System::block_number());
+	System::set_block_number(System::block_number() + 1);
+	System::on_initialize(System::block_number());
+	Balances::on_initialize(System::block_number());
+	SafeMode::on_initialize(System::block_number());
+}
+
+pub fn run_to(n: u64) {
+	while System::block_number() < n {
+		next_block();
+	}
+}
@@ -0,0 +1,410 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test suite for the safe-mode pallet.
+
+#![cfg(test)]
+
+use super::*;
+use crate as pallet_safe_mode;
+use crate::mock::{RuntimeCall, *};
+
+use frame_support::{assert_err, assert_noop, assert_ok, dispatch::Dispatchable};
+use sp_runtime::{BuildStorage, TransactionOutcome};
+
+fn transfer_call() -> RuntimeCall {
+	RuntimeCall::Balances(pallet_balances::Call::transfer_keep_alive { dest: BOB, value: 1 })
+}
+
+fn remark_call() -> RuntimeCall {
+	RuntimeCall::System(frame_system::Call::remark { remark: vec![] })
+}
+
+#[test]
+fn fails_to_filter_calls_to_safe_mode_pallet() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SafeMode::enter(RuntimeOrigin::signed(ALICE)));
+		let activated_at_block = System::block_number();
+
+		assert_err!(
+			transfer_call().dispatch(RuntimeOrigin::signed(ALICE)),
+			frame_system::Error::<Test>::CallFiltered
+		);
+
+		next_block();
+		assert_ok!(SafeMode::extend(RuntimeOrigin::signed(BOB)));
+		assert_ok!(SafeMode::force_extend(RuntimeOrigin::root()));
+		assert_err!(
+			transfer_call().dispatch(RuntimeOrigin::signed(ALICE)),
+			frame_system::Error::<Test>::CallFiltered
+		);
+		assert_ok!(SafeMode::force_exit(RuntimeOrigin::root()));
+		assert_ok!(SafeMode::force_release_deposit(
+			RuntimeOrigin::root(),
+			BOB,
+			activated_at_block + 1
+		));
+
+		next_block();
+		assert_ok!(SafeMode::enter(RuntimeOrigin::signed(ALICE)));
+		assert_err!(
+			transfer_call().dispatch(RuntimeOrigin::signed(ALICE)),
+			frame_system::Error::<Test>::CallFiltered
+		);
+		assert_ok!(SafeMode::force_exit(RuntimeOrigin::root()));
+		assert_ok!(SafeMode::force_slash_deposit(
+			RuntimeOrigin::root(),
+			ALICE,
+			activated_at_block + 2
+		));
+	});
+}
+
+#[test]
+fn fails_to_enter_or_extend_with_insufficient_balance() {
+	new_test_ext().execute_with(|| {
+		// An account that holds less than the required deposit.
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), 4, 50));
+		assert_noop!(
+			SafeMode::enter(RuntimeOrigin::signed(4)),
+			sp_runtime::TokenError::FundsUnavailable
+		);
+
+		assert_ok!(SafeMode::force_enter(RuntimeOrigin::root()));
+		assert_noop!(
+			SafeMode::extend(RuntimeOrigin::signed(4)),
+			sp_runtime::TokenError::FundsUnavailable
+		);
+	});
+}
+
+#[test]
+fn fails_to_extend_if_not_activated() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(EnteredUntil::<Test>::get(), None);
+		assert_noop!(SafeMode::extend(RuntimeOrigin::signed(ALICE)), Error::<Test>::Exited);
+		assert_noop!(SafeMode::force_extend(RuntimeOrigin::root()), Error::<Test>::Exited);
+	});
+}
+
+#[test]
+fn fails_to_force_release_deposits_with_wrong_block() {
+	new_test_ext().execute_with(|| {
+		let activated_at_block = System::block_number();
+		assert_ok!(SafeMode::enter(RuntimeOrigin::signed(ALICE)));
+		run_to(mock::EnterDuration::get() + activated_at_block + 1);
+
+		assert_err!(
+			SafeMode::force_release_deposit(RuntimeOrigin::root(), ALICE, activated_at_block + 1),
+			Error::<Test>::NoDeposit
+		);
+
+		assert_err!(
+			SafeMode::force_slash_deposit(RuntimeOrigin::root(), ALICE, activated_at_block + 1),
+			Error::<Test>::NoDeposit
+		);
+	});
+}
+
+#[test]
+fn fails_to_release_deposits_too_early() {
+	new_test_ext().execute_with(|| {
+		let activated_at_block = System::block_number();
+		assert_ok!(SafeMode::enter(RuntimeOrigin::signed(ALICE)));
+		assert_err!(
+			SafeMode::release_deposit(RuntimeOrigin::signed(BOB), ALICE, activated_at_block),
+			Error::<Test>::Entered
+		);
+		assert_ok!(SafeMode::force_exit(RuntimeOrigin::root()));
+		assert_err!(
+			SafeMode::release_deposit(RuntimeOrigin::signed(BOB), ALICE, activated_at_block),
+			Error::<Test>::CannotReleaseYet
+		);
+
+		run_to(activated_at_block + mock::ReleaseDelay::get().unwrap() + 1);
+		assert_ok!(SafeMode::release_deposit(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			activated_at_block
+		));
+		assert_eq!(Balances::free_balance(&ALICE), BAL_ACC0);
+	});
+}
+
+#[test]
+fn fails_to_release_deposits_if_not_configured() {
+	new_test_ext().execute_with(|| {
+		let activated_at_block = System::block_number();
+		assert_ok!(SafeMode::enter(RuntimeOrigin::signed(ALICE)));
+		assert_ok!(SafeMode::force_exit(RuntimeOrigin::root()));
+
+		mock::ReleaseDelay::set(None);
+		run_to(activated_at_block + 100);
+		assert_noop!(
+			SafeMode::release_deposit(RuntimeOrigin::signed(BOB), ALICE, activated_at_block),
+			Error::<Test>::NotConfigured
+		);
+		// Can still be released by force.
+		assert_ok!(SafeMode::force_release_deposit(
+			RuntimeOrigin::root(),
+			ALICE,
+			activated_at_block
+		));
+		assert_eq!(Balances::free_balance(&ALICE), BAL_ACC0);
+	});
+}
+
+#[test]
+fn fails_to_enter_or_extend_if_not_configured() {
+	new_test_ext().execute_with(|| {
+		mock::EnterDepositAmount::set(None);
+		assert_noop!(SafeMode::enter(RuntimeOrigin::signed(ALICE)), Error::<Test>::NotConfigured);
+
+		assert_ok!(SafeMode::force_enter(RuntimeOrigin::root()));
+		mock::ExtendDepositAmount::set(None);
+		assert_noop!(SafeMode::extend(RuntimeOrigin::signed(ALICE)), Error::<Test>::NotConfigured);
+	});
+}
+
+#[test]
+fn fails_to_enter_twice() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SafeMode::enter(RuntimeOrigin::signed(ALICE)));
+		assert_noop!(SafeMode::enter(RuntimeOrigin::signed(BOB)), Error::<Test>::Entered);
+		assert_noop!(SafeMode::force_enter(RuntimeOrigin::root()), Error::<Test>::Entered);
+	});
+}
+
+#[test]
+fn fails_to_exit_if_not_entered() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(SafeMode::force_exit(RuntimeOrigin::root()), Error::<Test>::Exited);
+	});
+}
+
+#[test]
+fn force_calls_require_the_configured_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(SafeMode::force_enter(RuntimeOrigin::signed(ALICE)), DispatchError::BadOrigin);
+		assert_ok!(SafeMode::enter(RuntimeOrigin::signed(ALICE)));
+		let block = System::block_number();
+
+		assert_noop!(
+			SafeMode::force_extend(RuntimeOrigin::signed(ALICE)),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(SafeMode::force_exit(RuntimeOrigin::signed(ALICE)), DispatchError::BadOrigin);
+		assert_noop!(
+			SafeMode::force_slash_deposit(RuntimeOrigin::signed(ALICE), ALICE, block),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			SafeMode::force_release_deposit(RuntimeOrigin::signed(ALICE), ALICE, block),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn can_automatically_deactivate_after_timeout() {
+	new_test_ext().execute_with(|| {
+		let activated_at_block = System::block_number();
+		assert_ok!(SafeMode::force_enter(RuntimeOrigin::root()));
+		let until = activated_at_block + mock::ForceEnterDuration::get();
+		System::assert_last_event(Event::Entered { until }.into());
+
+		run_to(until);
+		assert!(SafeMode::is_entered());
+		next_block();
+		assert!(!SafeMode::is_entered());
+		System::assert_last_event(Event::Exited { reason: ExitReason::Timeout }.into());
+		assert_eq!(Notifications::get(), vec![true, false]);
+	});
+}
+
+#[test]
+fn can_filter_balance_calls_when_activated() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(transfer_call().dispatch(RuntimeOrigin::signed(ALICE)));
+		assert_ok!(SafeMode::enter(RuntimeOrigin::signed(ALICE)));
+
+		assert_err!(
+			transfer_call().dispatch(RuntimeOrigin::signed(ALICE)),
+			frame_system::Error::<Test>::CallFiltered
+		);
+		// Whitelisted calls are still allowed.
+		assert_ok!(remark_call().dispatch(RuntimeOrigin::signed(ALICE)));
+		assert!(!<SafeMode as Contains<RuntimeCall>>::contains(&transfer_call()));
+		assert!(<SafeMode as Contains<RuntimeCall>>::contains(&remark_call()));
+	});
+}
+
+#[test]
+fn can_filter_balance_in_batch_when_activated() {
+	new_test_ext().execute_with(|| {
+		let batch_call =
+			RuntimeCall::Utility(pallet_utility::Call::batch { calls: vec![transfer_call()] });
+
+		assert_ok!(batch_call.clone().dispatch(RuntimeOrigin::signed(ALICE)));
+
+		assert_ok!(SafeMode::force_enter(RuntimeOrigin::root()));
+
+		assert_ok!(batch_call.dispatch(RuntimeOrigin::signed(ALICE)));
+		System::assert_last_event(
+			pallet_utility::Event::BatchInterrupted {
+				index: 0,
+				error: frame_system::Error::<Test>::CallFiltered.into(),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn can_activate_and_extend_with_deposits() {
+	new_test_ext().execute_with(|| {
+		let activated_at_block = System::block_number();
+		let enter_deposit = mock::EnterDepositAmount::get().unwrap();
+		let extend_deposit = mock::ExtendDepositAmount::get().unwrap();
+
+		assert_ok!(SafeMode::enter(RuntimeOrigin::signed(ALICE)));
+		System::assert_has_event(
+			Event::DepositPlaced { account: ALICE, amount: enter_deposit }.into(),
+		);
+		assert_eq!(
+			EnteredUntil::<Test>::get().unwrap(),
+			activated_at_block + mock::EnterDuration::get()
+		);
+
+		next_block();
+		assert_ok!(SafeMode::extend(RuntimeOrigin::signed(BOB)));
+		assert_eq!(
+			EnteredUntil::<Test>::get().unwrap(),
+			activated_at_block + mock::EnterDuration::get() + mock::ExtendDuration::get()
+		);
+		assert_eq!(Deposits::<Test>::get(ALICE, activated_at_block), Some(enter_deposit));
+		assert_eq!(Deposits::<Test>::get(BOB, activated_at_block + 1), Some(extend_deposit));
+		assert_eq!(Balances::free_balance(&ALICE), BAL_ACC0 - enter_deposit);
+		assert_eq!(Balances::free_balance(&BOB), BAL_ACC1 - extend_deposit);
+	});
+}
+
+#[test]
+fn extend_twice_in_the_same_block_accumulates_deposits() {
+	new_test_ext().execute_with(|| {
+		let block = System::block_number();
+		let extend_deposit = mock::ExtendDepositAmount::get().unwrap();
+
+		assert_ok!(SafeMode::force_enter(RuntimeOrigin::root()));
+		assert_ok!(SafeMode::extend(RuntimeOrigin::signed(BOB)));
+		assert_ok!(SafeMode::extend(RuntimeOrigin::signed(BOB)));
+
+		assert_eq!(Deposits::<Test>::get(BOB, block), Some(2 * extend_deposit));
+		assert_eq!(Balances::free_balance(&BOB), BAL_ACC1 - 2 * extend_deposit);
+	});
+}
+
+#[test]
+fn can_force_slash_deposit() {
+	new_test_ext().execute_with(|| {
+		let block = System::block_number();
+		let enter_deposit = mock::EnterDepositAmount::get().unwrap();
+		let issuance = Balances::total_issuance();
+
+		assert_ok!(SafeMode::enter(RuntimeOrigin::signed(ALICE)));
+		// Slashing also works while safe-mode is still entered.
+		assert_ok!(SafeMode::force_slash_deposit(RuntimeOrigin::root(), ALICE, block));
+		System::assert_last_event(
+			Event::DepositSlashed { account: ALICE, amount: enter_deposit }.into(),
+		);
+
+		assert_eq!(Balances::free_balance(&ALICE), BAL_ACC0 - enter_deposit);
+		assert_eq!(Balances::total_issuance(), issuance - enter_deposit);
+		assert!(!Deposits::<Test>::contains_key(ALICE, block));
+	});
+}
+
+#[test]
+fn can_force_release_deposit_while_entered() {
+	new_test_ext().execute_with(|| {
+		let block = System::block_number();
+		let enter_deposit = mock::EnterDepositAmount::get().unwrap();
+
+		assert_ok!(SafeMode::enter(RuntimeOrigin::signed(ALICE)));
+		assert_ok!(SafeMode::force_release_deposit(RuntimeOrigin::root(), ALICE, block));
+		System::assert_last_event(
+			Event::DepositReleased { account: ALICE, amount: enter_deposit }.into(),
+		);
+		assert_eq!(Balances::free_balance(&ALICE), BAL_ACC0);
+		assert!(SafeMode::is_entered());
+	});
+}
+
+#[test]
+fn safe_mode_trait_works() {
+	use frame_support::traits::SafeMode as SafeModeTrait;
+
+	new_test_ext().execute_with(|| {
+		assert!(!<SafeMode as SafeModeTrait>::is_entered());
+		assert_eq!(<SafeMode as SafeModeTrait>::remaining(), None);
+		assert_eq!(<SafeMode as SafeModeTrait>::extend(1), Err(SafeModeError::AlreadyExited));
+		assert_eq!(<SafeMode as SafeModeTrait>::exit(), Err(SafeModeError::AlreadyExited));
+
+		assert_ok!(<SafeMode as SafeModeTrait>::enter(5));
+		assert_eq!(<SafeMode as SafeModeTrait>::enter(5), Err(SafeModeError::AlreadyEntered));
+		assert_eq!(<SafeMode as SafeModeTrait>::remaining(), Some(5));
+		assert_ok!(<SafeMode as SafeModeTrait>::extend(2));
+		next_block();
+		assert_eq!(<SafeMode as SafeModeTrait>::remaining(), Some(6));
+
+		assert_ok!(<SafeMode as SafeModeTrait>::exit());
+		assert!(!<SafeMode as SafeModeTrait>::is_entered());
+	});
+}
+
+#[test]
+fn genesis_config_can_enter_safe_mode() {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_safe_mode::GenesisConfig::<Test> { entered_until: Some(5), _config: Default::default() }
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+	sp_io::TestExternalities::new(t).execute_with(|| {
+		System::set_block_number(1);
+		assert!(SafeMode::is_entered());
+		assert!(!<SafeMode as Contains<RuntimeCall>>::contains(&transfer_call()));
+
+		run_to(6);
+		assert!(!SafeMode::is_entered());
+	});
+}
+
+#[test]
+fn deposits_are_not_touched_by_a_failed_enter() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SafeMode::force_enter(RuntimeOrigin::root()));
+
+		frame_support::storage::with_transaction(|| {
+			assert_noop!(SafeMode::enter(RuntimeOrigin::signed(ALICE)), Error::<Test>::Entered);
+			TransactionOutcome::Rollback(Ok::<(), DispatchError>(()))
+		})
+		.unwrap();
+
+		assert_eq!(Balances::free_balance(&ALICE), BAL_ACC0);
+		assert_eq!(Deposits::<Test>::iter().count(), 0);
+	});
+}
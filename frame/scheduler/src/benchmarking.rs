@@ -306,5 +306,91 @@ benchmarks! {
 		);
 	}
 
+	schedule_retry {
+		let s in 1 .. T::MaxScheduledPerBlock::get();
+		let when = BLOCK_NUMBER.into();
+
+		fill_schedule::<T>(when, s)?;
+		let name = u32_to_name(s - 1);
+		let address = Lookup::<T>::get(name).unwrap();
+		let period: BlockNumberFor<T> = 1u32.into();
+		let retry_config = RetryConfig { total_retries: 10, remaining: 10, period };
+		Retries::<T>::insert(address, retry_config);
+		let (when, index) = address;
+		let task = Agenda::<T>::get(when)[index as usize].clone().unwrap();
+		// fill the agenda the retry is placed in, leaving room for a single task.
+		let wake = when.saturating_add(period);
+		let items = (0..s - 1).map(|_| Some(task.as_retry())).collect::<Vec<_>>();
+		Agenda::<T>::insert(wake, BoundedVec::truncate_from(items));
+		let mut weight_counter = WeightMeter::max_limit();
+	}: {
+		Scheduler::<T>::schedule_retry(&mut weight_counter, when, when, index, &task, retry_config);
+	} verify {
+		assert_eq!(
+			Retries::<T>::get((wake, s - 1)),
+			Some(RetryConfig { total_retries: 10, remaining: 9, period })
+		);
+	}
+
+	set_retry {
+		let s = T::MaxScheduledPerBlock::get();
+		let when = BLOCK_NUMBER.into();
+
+		fill_schedule::<T>(when, s)?;
+		let name = u32_to_name(s - 1);
+		let address = Lookup::<T>::get(name).unwrap();
+		let period: BlockNumberFor<T> = 1u32.into();
+	}: _(RawOrigin::Root, address, 10, period)
+	verify {
+		assert_eq!(
+			Retries::<T>::get(address),
+			Some(RetryConfig { total_retries: 10, remaining: 10, period })
+		);
+	}
+
+	set_retry_named {
+		let s = T::MaxScheduledPerBlock::get();
+		let when = BLOCK_NUMBER.into();
+
+		fill_schedule::<T>(when, s)?;
+		let name = u32_to_name(s - 1);
+		let address = Lookup::<T>::get(name).unwrap();
+		let period: BlockNumberFor<T> = 1u32.into();
+	}: _(RawOrigin::Root, name, 10, period)
+	verify {
+		assert_eq!(
+			Retries::<T>::get(address),
+			Some(RetryConfig { total_retries: 10, remaining: 10, period })
+		);
+	}
+
+	cancel_retry {
+		let s = T::MaxScheduledPerBlock::get();
+		let when = BLOCK_NUMBER.into();
+
+		fill_schedule::<T>(when, s)?;
+		let name = u32_to_name(s - 1);
+		let address = Lookup::<T>::get(name).unwrap();
+		let period: BlockNumberFor<T> = 1u32.into();
+		assert!(Scheduler::<T>::set_retry(RawOrigin::Root.into(), address, 10, period).is_ok());
+	}: _(RawOrigin::Root, address)
+	verify {
+		assert!(!Retries::<T>::contains_key(address));
+	}
+
+	cancel_retry_named {
+		let s = T::MaxScheduledPerBlock::get();
+		let when = BLOCK_NUMBER.into();
+
+		fill_schedule::<T>(when, s)?;
+		let name = u32_to_name(s - 1);
+		let address = Lookup::<T>::get(name).unwrap();
+		let period: BlockNumberFor<T> = 1u32.into();
+		assert!(Scheduler::<T>::set_retry_named(RawOrigin::Root.into(), name, 10, period).is_ok());
+	}: _(RawOrigin::Root, name)
+	verify {
+		assert!(!Retries::<T>::contains_key(address));
+	}
+
 	impl_benchmark_test_suite!(Scheduler, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -40,9 +40,12 @@ pub mod logger {
 	use super::{OriginCaller, OriginTrait};
 	use frame_support::{pallet_prelude::*, parameter_types};
 	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::UniqueSaturatedInto;
 
 	parameter_types! {
 		static Log: Vec<(OriginCaller, u32)> = Vec::new();
+		/// The range of blocks, inclusive, in which `timed_log` succeeds.
+		pub static Threshold: (u32, u32) = (0, u32::MAX);
 	}
 	pub fn log() -> Vec<(OriginCaller, u32)> {
 		Log::get().clone()
@@ -89,6 +92,19 @@ pub mod logger {
 			});
 			Ok(())
 		}
+
+		#[pallet::call_index(2)]
+		#[pallet::weight(*weight)]
+		pub fn timed_log(origin: OriginFor<T>, i: u32, weight: Weight) -> DispatchResult {
+			let now: u32 = frame_system::Pallet::<T>::block_number().unique_saturated_into();
+			let (start, end) = Threshold::get();
+			ensure!(now >= start && now <= end, DispatchError::Other("outside of the threshold"));
+			Self::deposit_event(Event::Logged(i, weight));
+			Log::mutate(|log| {
+				log.push((origin.caller().clone(), i));
+			});
+			Ok(())
+		}
 	}
 }
 
@@ -125,6 +141,7 @@ impl system::Config for Test {
 	type DbWeight = RocksDbWeight;
 	type RuntimeOrigin = RuntimeOrigin;
 	type RuntimeCall = RuntimeCall;
+	type RuntimeTask = RuntimeTask;
 	type Nonce = u64;
 	type Hash = H256;
 	type Hashing = BlakeTwo256;
@@ -197,6 +214,21 @@ impl WeightInfo for TestWeightInfo {
 	fn cancel_named(_s: u32) -> Weight {
 		Weight::from_parts(50, 0)
 	}
+	fn schedule_retry(_s: u32) -> Weight {
+		Weight::from_parts(100000, 0)
+	}
+	fn set_retry() -> Weight {
+		Weight::from_parts(50, 0)
+	}
+	fn set_retry_named() -> Weight {
+		Weight::from_parts(50, 0)
+	}
+	fn cancel_retry() -> Weight {
+		Weight::from_parts(50, 0)
+	}
+	fn cancel_retry_named() -> Weight {
+		Weight::from_parts(50, 0)
+	}
 }
 parameter_types! {
 	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) *
@@ -1851,3 +1851,409 @@ fn reschedule_named_last_task_removes_agenda() {
 		assert!(Agenda::<Test>::get(when).len() == 0);
 	});
 }
+
+#[test]
+fn retry_scheduling_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// task fails until block 8 is reached
+		logger::Threshold::set((8, 100));
+		// task 42 at #4
+		assert_ok!(Scheduler::do_schedule(
+			DispatchTime::At(4),
+			None,
+			127,
+			root(),
+			Preimage::bound(RuntimeCall::Logger(logger::Call::timed_log {
+				i: 42,
+				weight: Weight::from_parts(10, 0)
+			}))
+			.unwrap()
+		));
+		assert!(Agenda::<Test>::get(4)[0].is_some());
+		// retry 10 times every 3 blocks
+		assert_ok!(Scheduler::set_retry(RuntimeOrigin::root(), (4, 0), 10, 3));
+		assert_eq!(Retries::<Test>::iter().count(), 1);
+		System::assert_last_event(
+			Event::<Test>::RetrySet { task: (4, 0), id: None, period: 3, retries: 10 }.into(),
+		);
+		run_to_block(3);
+		assert!(logger::log().is_empty());
+		// task should be retried in block 7
+		run_to_block(4);
+		assert!(Agenda::<Test>::get(4).is_empty());
+		assert!(Agenda::<Test>::get(7)[0].is_some());
+		assert_eq!(
+			Retries::<Test>::get((7, 0)),
+			Some(RetryConfig { total_retries: 10, remaining: 9, period: 3 })
+		);
+		assert!(logger::log().is_empty());
+		run_to_block(6);
+		assert!(Agenda::<Test>::get(7)[0].is_some());
+		assert!(logger::log().is_empty());
+		// task still fails, should be retried in block 10
+		run_to_block(7);
+		assert!(Agenda::<Test>::get(7).is_empty());
+		assert!(Agenda::<Test>::get(10)[0].is_some());
+		assert!(logger::log().is_empty());
+		run_to_block(9);
+		assert!(logger::log().is_empty());
+		assert!(Agenda::<Test>::get(10)[0].is_some());
+		// finally it should succeed
+		run_to_block(10);
+		assert_eq!(logger::log(), vec![(root(), 42u32)]);
+		assert!(Agenda::<Test>::get(10).is_empty());
+		assert_eq!(Retries::<Test>::iter().count(), 0);
+		run_to_block(100);
+		assert_eq!(logger::log(), vec![(root(), 42u32)]);
+	});
+}
+
+#[test]
+fn named_retry_scheduling_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// task fails until block 8 is reached
+		logger::Threshold::set((8, 100));
+		// task 42 at #4
+		assert_ok!(Scheduler::do_schedule_named(
+			[1u8; 32],
+			DispatchTime::At(4),
+			None,
+			127,
+			root(),
+			Preimage::bound(RuntimeCall::Logger(logger::Call::timed_log {
+				i: 42,
+				weight: Weight::from_parts(10, 0)
+			}))
+			.unwrap()
+		));
+		// retry 10 times every 3 blocks
+		assert_ok!(Scheduler::set_retry_named(RuntimeOrigin::root(), [1u8; 32], 10, 3));
+		System::assert_last_event(
+			Event::<Test>::RetrySet { task: (4, 0), id: Some([1u8; 32]), period: 3, retries: 10 }
+				.into(),
+		);
+		assert_eq!(
+			Retries::<Test>::get((4, 0)),
+			Some(RetryConfig { total_retries: 10, remaining: 10, period: 3 })
+		);
+		// the retries are unnamed clones of the task
+		run_to_block(4);
+		assert!(Lookup::<Test>::get([1u8; 32]).is_none());
+		assert!(Agenda::<Test>::get(7)[0].as_ref().unwrap().maybe_id.is_none());
+		assert!(logger::log().is_empty());
+		run_to_block(7);
+		assert!(logger::log().is_empty());
+		run_to_block(10);
+		assert_eq!(logger::log(), vec![(root(), 42u32)]);
+		assert_eq!(Retries::<Test>::iter().count(), 0);
+		run_to_block(100);
+		assert_eq!(logger::log(), vec![(root(), 42u32)]);
+	});
+}
+
+#[test]
+fn retry_scheduling_expires() {
+	new_test_ext().execute_with(|| {
+		// task will fail if we're past block 3
+		logger::Threshold::set((1, 3));
+		// task 42 at #4
+		assert_ok!(Scheduler::do_schedule(
+			DispatchTime::At(4),
+			None,
+			127,
+			root(),
+			Preimage::bound(RuntimeCall::Logger(logger::Call::timed_log {
+				i: 42,
+				weight: Weight::from_parts(10, 0)
+			}))
+			.unwrap()
+		));
+		// retry 3 times every block
+		assert_ok!(Scheduler::set_retry(RuntimeOrigin::root(), (4, 0), 3, 1));
+		run_to_block(4);
+		assert_eq!(
+			Retries::<Test>::get((5, 0)),
+			Some(RetryConfig { total_retries: 3, remaining: 2, period: 1 })
+		);
+		run_to_block(6);
+		assert_eq!(
+			Retries::<Test>::get((7, 0)),
+			Some(RetryConfig { total_retries: 3, remaining: 0, period: 1 })
+		);
+		// the last retry fails and is not rescheduled
+		run_to_block(7);
+		System::assert_has_event(Event::<Test>::RetryFailed { task: (7, 0), id: None }.into());
+		assert!(Agenda::<Test>::get(8).is_empty());
+		assert_eq!(Retries::<Test>::iter().count(), 0);
+		run_to_block(100);
+		assert!(logger::log().is_empty());
+	});
+}
+
+#[test]
+fn retry_periodic_task_works() {
+	new_test_ext().execute_with(|| {
+		// task fails at block 4 only
+		logger::Threshold::set((5, 100));
+		// periodic task 42 at #4, #7 and #10
+		assert_ok!(Scheduler::do_schedule(
+			DispatchTime::At(4),
+			Some((3, 3)),
+			127,
+			root(),
+			Preimage::bound(RuntimeCall::Logger(logger::Call::timed_log {
+				i: 42,
+				weight: Weight::from_parts(10, 0)
+			}))
+			.unwrap()
+		));
+		// retry 10 times every block
+		assert_ok!(Scheduler::set_retry(RuntimeOrigin::root(), (4, 0), 10, 1));
+		run_to_block(4);
+		assert!(logger::log().is_empty());
+		// both the retry and the next periodic run are scheduled
+		let retry = Agenda::<Test>::get(5)[0].clone().unwrap();
+		assert!(retry.maybe_periodic.is_none());
+		assert_eq!(
+			Retries::<Test>::get((5, 0)),
+			Some(RetryConfig { total_retries: 10, remaining: 9, period: 1 })
+		);
+		assert!(Agenda::<Test>::get(7)[0].as_ref().unwrap().maybe_periodic.is_some());
+		// the next periodic run keeps the original retry configuration
+		assert_eq!(
+			Retries::<Test>::get((7, 0)),
+			Some(RetryConfig { total_retries: 10, remaining: 10, period: 1 })
+		);
+		run_to_block(5);
+		assert_eq!(logger::log(), vec![(root(), 42u32)]);
+		assert!(Agenda::<Test>::get(6).is_empty());
+		run_to_block(10);
+		assert_eq!(logger::log(), vec![(root(), 42u32), (root(), 42u32), (root(), 42u32)]);
+		assert_eq!(Retries::<Test>::iter().count(), 0);
+		run_to_block(100);
+		assert_eq!(logger::log().len(), 3);
+	});
+}
+
+#[test]
+fn retry_fails_when_agenda_is_full() {
+	new_test_ext().execute_with(|| {
+		// task fails at block 4 only
+		logger::Threshold::set((5, 100));
+		assert_ok!(Scheduler::do_schedule(
+			DispatchTime::At(4),
+			None,
+			127,
+			root(),
+			Preimage::bound(RuntimeCall::Logger(logger::Call::timed_log {
+				i: 42,
+				weight: Weight::from_parts(10, 0)
+			}))
+			.unwrap()
+		));
+		assert_ok!(Scheduler::set_retry(RuntimeOrigin::root(), (4, 0), 10, 1));
+		// fill the agenda of block 5
+		let max: u32 = <Test as Config>::MaxScheduledPerBlock::get();
+		for i in 0..max {
+			assert_ok!(Scheduler::do_schedule(
+				DispatchTime::At(5),
+				None,
+				127,
+				root(),
+				Preimage::bound(RuntimeCall::Logger(LoggerCall::log {
+					i,
+					weight: Weight::from_parts(10, 0)
+				}))
+				.unwrap()
+			));
+		}
+		run_to_block(4);
+		System::assert_has_event(Event::<Test>::RetryFailed { task: (4, 0), id: None }.into());
+		assert_eq!(Retries::<Test>::iter().count(), 0);
+		run_to_block(100);
+		assert_eq!(logger::log().len(), max as usize);
+		assert!(logger::log().iter().all(|(_, i)| *i != 42));
+	});
+}
+
+#[test]
+fn retry_config_is_removed_when_preimage_is_unavailable() {
+	new_test_ext().execute_with(|| {
+		let call =
+			RuntimeCall::Logger(LoggerCall::log { i: 42, weight: Weight::from_parts(10, 0) });
+		let hash = <Test as frame_system::Config>::Hashing::hash_of(&call);
+		let len = call.using_encoded(|x| x.len()) as u32;
+		// the preimage is never noted
+		assert_ok!(Scheduler::do_schedule(
+			DispatchTime::At(4),
+			None,
+			127,
+			root(),
+			Bounded::Lookup { hash, len },
+		));
+		assert_ok!(Scheduler::set_retry(RuntimeOrigin::root(), (4, 0), 10, 1));
+		assert_eq!(Retries::<Test>::iter().count(), 1);
+
+		run_to_block(4);
+		assert!(logger::log().is_empty());
+		assert_eq!(Retries::<Test>::iter().count(), 0);
+		run_to_block(100);
+		assert!(logger::log().is_empty());
+	});
+}
+
+#[test]
+fn retry_config_is_removed_when_task_is_permanently_overweight() {
+	let max_weight: Weight = <Test as Config>::MaximumWeight::get();
+	new_test_ext().execute_with(|| {
+		let call = RuntimeCall::Logger(LoggerCall::log { i: 42, weight: max_weight });
+		assert_ok!(Scheduler::do_schedule(
+			DispatchTime::At(4),
+			None,
+			127,
+			root(),
+			Preimage::bound(call).unwrap(),
+		));
+		assert_ok!(Scheduler::set_retry(RuntimeOrigin::root(), (4, 0), 10, 1));
+		assert_eq!(Retries::<Test>::iter().count(), 1);
+
+		run_to_block(4);
+		System::assert_has_event(
+			Event::<Test>::PermanentlyOverweight { task: (4, 0), id: None }.into(),
+		);
+		assert_eq!(Retries::<Test>::iter().count(), 0);
+		assert!(Agenda::<Test>::get(5).is_empty());
+		assert!(logger::log().is_empty());
+	});
+}
+
+#[test]
+fn set_retry_checks_task_and_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Scheduler::set_retry(RuntimeOrigin::root(), (4, 0), 10, 1),
+			Error::<Test>::NotFound
+		);
+		assert_noop!(
+			Scheduler::set_retry_named(RuntimeOrigin::root(), [1u8; 32], 10, 1),
+			Error::<Test>::NotFound
+		);
+		assert_ok!(Scheduler::do_schedule_named(
+			[1u8; 32],
+			DispatchTime::At(4),
+			None,
+			127,
+			root(),
+			Preimage::bound(RuntimeCall::Logger(logger::Call::timed_log {
+				i: 42,
+				weight: Weight::from_parts(10, 0)
+			}))
+			.unwrap()
+		));
+		// a signed origin has lower privileges than the root origin of the task
+		assert_noop!(Scheduler::set_retry(RuntimeOrigin::signed(1), (4, 0), 10, 1), BadOrigin);
+		assert_noop!(
+			Scheduler::set_retry_named(RuntimeOrigin::signed(1), [1u8; 32], 10, 1),
+			BadOrigin
+		);
+		assert_noop!(Scheduler::set_retry(RuntimeOrigin::signed(2), (4, 0), 10, 1), BadOrigin);
+		assert_ok!(Scheduler::set_retry(RuntimeOrigin::root(), (4, 0), 10, 1));
+		assert_noop!(Scheduler::cancel_retry(RuntimeOrigin::signed(1), (4, 0)), BadOrigin);
+		assert_noop!(
+			Scheduler::cancel_retry(RuntimeOrigin::root(), (4, 1)),
+			Error::<Test>::NotFound
+		);
+	});
+}
+
+#[test]
+fn cancel_retry_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// task fails until block 8 is reached
+		logger::Threshold::set((8, 100));
+		assert_ok!(Scheduler::do_schedule(
+			DispatchTime::At(4),
+			None,
+			127,
+			root(),
+			Preimage::bound(RuntimeCall::Logger(logger::Call::timed_log {
+				i: 42,
+				weight: Weight::from_parts(10, 0)
+			}))
+			.unwrap()
+		));
+		assert_ok!(Scheduler::do_schedule_named(
+			[1u8; 32],
+			DispatchTime::At(4),
+			None,
+			127,
+			root(),
+			Preimage::bound(RuntimeCall::Logger(logger::Call::timed_log {
+				i: 43,
+				weight: Weight::from_parts(10, 0)
+			}))
+			.unwrap()
+		));
+		assert_ok!(Scheduler::set_retry(RuntimeOrigin::root(), (4, 0), 10, 3));
+		assert_ok!(Scheduler::set_retry_named(RuntimeOrigin::root(), [1u8; 32], 10, 3));
+		assert_eq!(Retries::<Test>::iter().count(), 2);
+
+		assert_ok!(Scheduler::cancel_retry(RuntimeOrigin::root(), (4, 0)));
+		System::assert_last_event(Event::<Test>::RetryCancelled { task: (4, 0), id: None }.into());
+		assert_ok!(Scheduler::cancel_retry_named(RuntimeOrigin::root(), [1u8; 32]));
+		System::assert_last_event(
+			Event::<Test>::RetryCancelled { task: (4, 1), id: Some([1u8; 32]) }.into(),
+		);
+		assert_eq!(Retries::<Test>::iter().count(), 0);
+
+		// both tasks fail and are not retried
+		run_to_block(4);
+		assert!(Agenda::<Test>::get(7).is_empty());
+		run_to_block(100);
+		assert!(logger::log().is_empty());
+	});
+}
+
+#[test]
+fn cancel_and_reschedule_handle_retry_config() {
+	new_test_ext().execute_with(|| {
+		let call = RuntimeCall::Logger(logger::Call::timed_log {
+			i: 42,
+			weight: Weight::from_parts(10, 0),
+		});
+		assert_ok!(Scheduler::do_schedule(
+			DispatchTime::At(4),
+			None,
+			127,
+			root(),
+			Preimage::bound(call.clone()).unwrap()
+		));
+		assert_ok!(Scheduler::do_schedule_named(
+			[1u8; 32],
+			DispatchTime::At(4),
+			None,
+			127,
+			root(),
+			Preimage::bound(call).unwrap()
+		));
+		assert_ok!(Scheduler::set_retry(RuntimeOrigin::root(), (4, 0), 10, 3));
+		assert_ok!(Scheduler::set_retry(RuntimeOrigin::root(), (4, 1), 10, 3));
+
+		// the retry configuration follows a rescheduled task
+		assert_eq!(Scheduler::do_reschedule((4, 0), DispatchTime::At(6)).unwrap(), (6, 0));
+		assert!(Retries::<Test>::get((4, 0)).is_none());
+		assert!(Retries::<Test>::get((6, 0)).is_some());
+		assert_eq!(Scheduler::do_reschedule_named([1u8; 32], DispatchTime::At(6)).unwrap(), (6, 1));
+		assert!(Retries::<Test>::get((4, 1)).is_none());
+		assert!(Retries::<Test>::get((6, 1)).is_some());
+
+		// and is removed when the task is canceled
+		assert_ok!(Scheduler::do_cancel(None, (6, 0)));
+		assert!(Retries::<Test>::get((6, 0)).is_none());
+		assert_ok!(Scheduler::do_cancel_named(None, [1u8; 32]));
+		assert_eq!(Retries::<Test>::iter().count(), 0);
+	});
+}
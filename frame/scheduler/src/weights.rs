@@ -64,6 +64,11 @@ pub trait WeightInfo {
 	fn cancel(s: u32, ) -> Weight;
 	fn schedule_named(s: u32, ) -> Weight;
 	fn cancel_named(s: u32, ) -> Weight;
+	fn schedule_retry(s: u32, ) -> Weight;
+	fn set_retry() -> Weight;
+	fn set_retry_named() -> Weight;
+	fn cancel_retry() -> Weight;
+	fn cancel_retry_named() -> Weight;
 }
 
 /// Weights for pallet_scheduler using the Substrate node and recommended hardware.
@@ -94,12 +99,16 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: Scheduler Retries (r:1 w:1)
+	/// Proof: Scheduler Retries (max_values: None, max_size: Some(30), added: 2505, mode: MaxEncodedLen)
 	fn service_task_base() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
-		//  Estimated: `0`
+		//  Estimated: `3495`
 		// Minimum execution time: 5_250_000 picoseconds.
-		Weight::from_parts(5_549_000, 0)
+		Weight::from_parts(5_549_000, 3495)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
 	/// Storage: Preimage PreimageFor (r:1 w:1)
 	/// Proof: Preimage PreimageFor (max_values: None, max_size: Some(4194344), added: 4196819, mode: Measured)
@@ -167,6 +176,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: Scheduler Agenda (max_values: None, max_size: Some(107022), added: 109497, mode: MaxEncodedLen)
 	/// Storage: Scheduler Lookup (r:0 w:1)
 	/// Proof: Scheduler Lookup (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: Scheduler Retries (r:0 w:1)
+	/// Proof: Scheduler Retries (max_values: None, max_size: Some(30), added: 2505, mode: MaxEncodedLen)
 	/// The range of component `s` is `[1, 512]`.
 	fn cancel(s: u32, ) -> Weight {
 		// Proof Size summary in bytes:
@@ -177,7 +188,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			// Standard Error: 1_344
 			.saturating_add(Weight::from_parts(545_863, 0).saturating_mul(s.into()))
 			.saturating_add(T::DbWeight::get().reads(1_u64))
-			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
 	/// Storage: Scheduler Lookup (r:1 w:1)
 	/// Proof: Scheduler Lookup (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
@@ -199,6 +210,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: Scheduler Lookup (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
 	/// Storage: Scheduler Agenda (r:1 w:1)
 	/// Proof: Scheduler Agenda (max_values: None, max_size: Some(107022), added: 109497, mode: MaxEncodedLen)
+	/// Storage: Scheduler Retries (r:0 w:1)
+	/// Proof: Scheduler Retries (max_values: None, max_size: Some(30), added: 2505, mode: MaxEncodedLen)
 	/// The range of component `s` is `[1, 512]`.
 	fn cancel_named(s: u32, ) -> Weight {
 		// Proof Size summary in bytes:
@@ -209,8 +222,80 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			// Standard Error: 1_084
 			.saturating_add(Weight::from_parts(557_691, 0).saturating_mul(s.into()))
 			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: Scheduler Agenda (r:1 w:1)
+	/// Proof: Scheduler Agenda (max_values: None, max_size: Some(107022), added: 109497, mode: MaxEncodedLen)
+	/// Storage: Scheduler Retries (r:0 w:1)
+	/// Proof: Scheduler Retries (max_values: None, max_size: Some(30), added: 2505, mode: MaxEncodedLen)
+	/// The range of component `s` is `[1, 512]`.
+	fn schedule_retry(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `159 + s * (177 ±0)`
+		//  Estimated: `110487`
+		// Minimum execution time: 13_092_000 picoseconds.
+		Weight::from_parts(15_328_457, 110487)
+			// Standard Error: 1_011
+			.saturating_add(Weight::from_parts(371_530, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
+	/// Storage: Scheduler Agenda (r:1 w:0)
+	/// Proof: Scheduler Agenda (max_values: None, max_size: Some(107022), added: 109497, mode: MaxEncodedLen)
+	/// Storage: Scheduler Retries (r:0 w:1)
+	/// Proof: Scheduler Retries (max_values: None, max_size: Some(30), added: 2505, mode: MaxEncodedLen)
+	fn set_retry() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `90705`
+		//  Estimated: `110487`
+		// Minimum execution time: 80_641_000 picoseconds.
+		Weight::from_parts(83_047_000, 110487)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Scheduler Lookup (r:1 w:0)
+	/// Proof: Scheduler Lookup (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: Scheduler Agenda (r:1 w:0)
+	/// Proof: Scheduler Agenda (max_values: None, max_size: Some(107022), added: 109497, mode: MaxEncodedLen)
+	/// Storage: Scheduler Retries (r:0 w:1)
+	/// Proof: Scheduler Retries (max_values: None, max_size: Some(30), added: 2505, mode: MaxEncodedLen)
+	fn set_retry_named() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `91747`
+		//  Estimated: `110487`
+		// Minimum execution time: 98_126_000 picoseconds.
+		Weight::from_parts(101_232_000, 110487)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Scheduler Agenda (r:1 w:0)
+	/// Proof: Scheduler Agenda (max_values: None, max_size: Some(107022), added: 109497, mode: MaxEncodedLen)
+	/// Storage: Scheduler Retries (r:0 w:1)
+	/// Proof: Scheduler Retries (max_values: None, max_size: Some(30), added: 2505, mode: MaxEncodedLen)
+	fn cancel_retry() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `90717`
+		//  Estimated: `110487`
+		// Minimum execution time: 79_578_000 picoseconds.
+		Weight::from_parts(81_965_000, 110487)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Scheduler Lookup (r:1 w:0)
+	/// Proof: Scheduler Lookup (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: Scheduler Agenda (r:1 w:0)
+	/// Proof: Scheduler Agenda (max_values: None, max_size: Some(107022), added: 109497, mode: MaxEncodedLen)
+	/// Storage: Scheduler Retries (r:0 w:1)
+	/// Proof: Scheduler Retries (max_values: None, max_size: Some(30), added: 2505, mode: MaxEncodedLen)
+	fn cancel_retry_named() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `91759`
+		//  Estimated: `110487`
+		// Minimum execution time: 96_985_000 picoseconds.
+		Weight::from_parts(99_840_000, 110487)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -240,12 +325,16 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: Scheduler Retries (r:1 w:1)
+	/// Proof: Scheduler Retries (max_values: None, max_size: Some(30), added: 2505, mode: MaxEncodedLen)
 	fn service_task_base() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
-		//  Estimated: `0`
+		//  Estimated: `3495`
 		// Minimum execution time: 5_250_000 picoseconds.
-		Weight::from_parts(5_549_000, 0)
+		Weight::from_parts(5_549_000, 3495)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
 	/// Storage: Preimage PreimageFor (r:1 w:1)
 	/// Proof: Preimage PreimageFor (max_values: None, max_size: Some(4194344), added: 4196819, mode: Measured)
@@ -313,6 +402,8 @@ impl WeightInfo for () {
 	/// Proof: Scheduler Agenda (max_values: None, max_size: Some(107022), added: 109497, mode: MaxEncodedLen)
 	/// Storage: Scheduler Lookup (r:0 w:1)
 	/// Proof: Scheduler Lookup (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: Scheduler Retries (r:0 w:1)
+	/// Proof: Scheduler Retries (max_values: None, max_size: Some(30), added: 2505, mode: MaxEncodedLen)
 	/// The range of component `s` is `[1, 512]`.
 	fn cancel(s: u32, ) -> Weight {
 		// Proof Size summary in bytes:
@@ -323,7 +414,7 @@ impl WeightInfo for () {
 			// Standard Error: 1_344
 			.saturating_add(Weight::from_parts(545_863, 0).saturating_mul(s.into()))
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
-			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
 	}
 	/// Storage: Scheduler Lookup (r:1 w:1)
 	/// Proof: Scheduler Lookup (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
@@ -345,6 +436,8 @@ impl WeightInfo for () {
 	/// Proof: Scheduler Lookup (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
 	/// Storage: Scheduler Agenda (r:1 w:1)
 	/// Proof: Scheduler Agenda (max_values: None, max_size: Some(107022), added: 109497, mode: MaxEncodedLen)
+	/// Storage: Scheduler Retries (r:0 w:1)
+	/// Proof: Scheduler Retries (max_values: None, max_size: Some(30), added: 2505, mode: MaxEncodedLen)
 	/// The range of component `s` is `[1, 512]`.
 	fn cancel_named(s: u32, ) -> Weight {
 		// Proof Size summary in bytes:
@@ -355,6 +448,78 @@ impl WeightInfo for () {
 			// Standard Error: 1_084
 			.saturating_add(Weight::from_parts(557_691, 0).saturating_mul(s.into()))
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: Scheduler Agenda (r:1 w:1)
+	/// Proof: Scheduler Agenda (max_values: None, max_size: Some(107022), added: 109497, mode: MaxEncodedLen)
+	/// Storage: Scheduler Retries (r:0 w:1)
+	/// Proof: Scheduler Retries (max_values: None, max_size: Some(30), added: 2505, mode: MaxEncodedLen)
+	/// The range of component `s` is `[1, 512]`.
+	fn schedule_retry(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `159 + s * (177 ±0)`
+		//  Estimated: `110487`
+		// Minimum execution time: 13_092_000 picoseconds.
+		Weight::from_parts(15_328_457, 110487)
+			// Standard Error: 1_011
+			.saturating_add(Weight::from_parts(371_530, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
+	/// Storage: Scheduler Agenda (r:1 w:0)
+	/// Proof: Scheduler Agenda (max_values: None, max_size: Some(107022), added: 109497, mode: MaxEncodedLen)
+	/// Storage: Scheduler Retries (r:0 w:1)
+	/// Proof: Scheduler Retries (max_values: None, max_size: Some(30), added: 2505, mode: MaxEncodedLen)
+	fn set_retry() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `90705`
+		//  Estimated: `110487`
+		// Minimum execution time: 80_641_000 picoseconds.
+		Weight::from_parts(83_047_000, 110487)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Scheduler Lookup (r:1 w:0)
+	/// Proof: Scheduler Lookup (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: Scheduler Agenda (r:1 w:0)
+	/// Proof: Scheduler Agenda (max_values: None, max_size: Some(107022), added: 109497, mode: MaxEncodedLen)
+	/// Storage: Scheduler Retries (r:0 w:1)
+	/// Proof: Scheduler Retries (max_values: None, max_size: Some(30), added: 2505, mode: MaxEncodedLen)
+	fn set_retry_named() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `91747`
+		//  Estimated: `110487`
+		// Minimum execution time: 98_126_000 picoseconds.
+		Weight::from_parts(101_232_000, 110487)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Scheduler Agenda (r:1 w:0)
+	/// Proof: Scheduler Agenda (max_values: None, max_size: Some(107022), added: 109497, mode: MaxEncodedLen)
+	/// Storage: Scheduler Retries (r:0 w:1)
+	/// Proof: Scheduler Retries (max_values: None, max_size: Some(30), added: 2505, mode: MaxEncodedLen)
+	fn cancel_retry() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `90717`
+		//  Estimated: `110487`
+		// Minimum execution time: 79_578_000 picoseconds.
+		Weight::from_parts(81_965_000, 110487)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Scheduler Lookup (r:1 w:0)
+	/// Proof: Scheduler Lookup (max_values: None, max_size: Some(48), added: 2523, mode: MaxEncodedLen)
+	/// Storage: Scheduler Agenda (r:1 w:0)
+	/// Proof: Scheduler Agenda (max_values: None, max_size: Some(107022), added: 109497, mode: MaxEncodedLen)
+	/// Storage: Scheduler Retries (r:0 w:1)
+	/// Proof: Scheduler Retries (max_values: None, max_size: Some(30), added: 2505, mode: MaxEncodedLen)
+	fn cancel_retry_named() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `91759`
+		//  Estimated: `110487`
+		// Minimum execution time: 96_985_000 picoseconds.
+		Weight::from_parts(99_840_000, 110487)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
@@ -553,10 +553,10 @@ benchmarks! {
 	}
 
 	payout_stakers_dead_controller {
-		let n in 0 .. T::MaxNominatorRewardedPerValidator::get() as u32;
+		let n in 0 .. T::MaxExposurePageSize::get() as u32;
 		let (validator, nominators) = create_validator_with_nominators::<T>(
 			n,
-			T::MaxNominatorRewardedPerValidator::get() as u32,
+			T::MaxExposurePageSize::get() as u32,
 			true,
 			true,
 			RewardDestination::Controller,
@@ -587,10 +587,10 @@ benchmarks! {
 	}
 
 	payout_stakers_alive_staked {
-		let n in 0 .. T::MaxNominatorRewardedPerValidator::get() as u32;
+		let n in 0 .. T::MaxExposurePageSize::get() as u32;
 		let (validator, nominators) = create_validator_with_nominators::<T>(
 			n,
-			T::MaxNominatorRewardedPerValidator::get() as u32,
+			T::MaxExposurePageSize::get() as u32,
 			false,
 			true,
 			RewardDestination::Staked,
@@ -623,6 +623,44 @@ benchmarks! {
 		}
 	}
 
+	payout_stakers_by_page {
+		let n in 0 .. T::MaxExposurePageSize::get() as u32;
+		let (validator, nominators) = create_validator_with_nominators::<T>(
+			n,
+			T::MaxExposurePageSize::get() as u32,
+			false,
+			true,
+			RewardDestination::Staked,
+		)?;
+
+		let current_era = CurrentEra::<T>::get().unwrap();
+		// set the commission for this particular era as well.
+		<ErasValidatorPrefs<T>>::insert(current_era, validator.clone(), <Staking<T>>::validators(&validator));
+
+		let caller = whitelisted_caller();
+		let balance_before = T::Currency::free_balance(&validator);
+		let mut nominator_balances_before = Vec::new();
+		for (stash, _) in &nominators {
+			let balance = T::Currency::free_balance(stash);
+			nominator_balances_before.push(balance);
+		}
+		// the validator is paid along with the first page, which makes it the most expensive one.
+	}: _(RawOrigin::Signed(caller), validator.clone(), current_era, 0)
+	verify {
+		let balance_after = T::Currency::free_balance(&validator);
+		ensure!(
+			balance_before < balance_after,
+			"Balance of validator stash should have increased after payout.",
+		);
+		for ((stash, _), balance_before) in nominators.iter().zip(nominator_balances_before.iter()) {
+			let balance_after = T::Currency::free_balance(stash);
+			ensure!(
+				balance_before < &balance_after,
+				"Balance of nominator stash should have increased after payout.",
+			);
+		}
+	}
+
 	rebond {
 		let l in 1 .. T::MaxUnlockingChunks::get() as u32;
 
@@ -690,7 +728,7 @@ benchmarks! {
 			active: T::Currency::minimum_balance() - One::one(),
 			total: T::Currency::minimum_balance() - One::one(),
 			unlocking: Default::default(),
-			claimed_rewards: Default::default(),
+			legacy_claimed_rewards: Default::default(),
 		};
 		Ledger::<T>::insert(&controller, l);
 
@@ -985,7 +1023,7 @@ mod tests {
 
 			let (validator_stash, nominators) = create_validator_with_nominators::<Test>(
 				n,
-				<<Test as Config>::MaxNominatorRewardedPerValidator as Get<_>>::get(),
+				<<Test as Config>::MaxExposurePageSize as Get<_>>::get(),
 				false,
 				false,
 				RewardDestination::Staked,
@@ -1015,7 +1053,7 @@ mod tests {
 
 			let (validator_stash, _nominators) = create_validator_with_nominators::<Test>(
 				n,
-				<<Test as Config>::MaxNominatorRewardedPerValidator as Get<_>>::get(),
+				<<Test as Config>::MaxExposurePageSize as Get<_>>::get(),
 				false,
 				false,
 				RewardDestination::Staked,
@@ -113,10 +113,12 @@
 //! valid behavior_ while _punishing any misbehavior or lack of availability_.
 //!
 //! Rewards must be claimed for each era before it gets too old by `$HISTORY_DEPTH` using the
-//! `payout_stakers` call. Any account can call `payout_stakers`, which pays the reward to the
-//! validator as well as its nominators. Only the [`Config::MaxNominatorRewardedPerValidator`]
-//! biggest stakers can claim their reward. This is to limit the i/o cost to mutate storage for each
-//! nominator's account.
+//! `payout_stakers_by_page` call. The nominators backing a validator are split in pages of at most
+//! [`Config::MaxExposurePageSize`] nominators, and any account can call `payout_stakers_by_page`
+//! to pay the reward of a single page. The validator is paid along with the first page. This is to
+//! limit the i/o cost to mutate storage for each nominator's account in a single call. Only the
+//! biggest stakers fitting in [`Config::MaxExposurePageCount`] pages can claim their reward.
+//! `payout_stakers` pays the next page that has not been claimed yet.
 //!
 //! Slashing can occur at any point in time, once misbehavior is reported. Once slashing is
 //! determined, a value is deducted from the balance of the validator and all the nominators who
@@ -208,8 +210,8 @@
 //! Validators and nominators are rewarded at the end of each era. The total reward of an era is
 //! calculated using the era duration and the staking rate (the total amount of tokens staked by
 //! nominators and validators, divided by the total token supply). It aims to incentivize toward a
-//! defined staking rate. The full specification can be found
-//! [here](https://research.web3.foundation/en/latest/polkadot/Token%20Economics.html#inflation-model).
+//! defined staking rate. The full specification of the inflation model can be found at
+//! <https://research.web3.foundation/en/latest/polkadot/Token%20Economics.html#inflation-model>.
 //!
 //! Total reward is split among validators and their nominators depending on the number of points
 //! they received during the era. Points are added to a validator using
@@ -224,13 +226,11 @@
 //! The validator can declare an amount, named [`commission`](ValidatorPrefs::commission), that does
 //! not get shared with the nominators at each reward payout through its [`ValidatorPrefs`]. This
 //! value gets deducted from the total reward that is paid to the validator and its nominators. The
-//! remaining portion is split pro rata among the validator and the top
-//! [`Config::MaxNominatorRewardedPerValidator`] nominators that nominated the validator,
-//! proportional to the value staked behind the validator (_i.e._ dividing the
-//! [`own`](Exposure::own) or [`others`](Exposure::others) by [`total`](Exposure::total) in
-//! [`Exposure`]). Note that the pro rata division of rewards uses the total exposure behind the
-//! validator, *not* just the exposure of the validator and the top
-//! [`Config::MaxNominatorRewardedPerValidator`] nominators.
+//! remaining portion is split pro rata among the validator and the nominators that nominated the
+//! validator and fit in its exposure pages, proportional to the value staked behind the validator
+//! (_i.e._ dividing the [`own`](Exposure::own) or [`others`](Exposure::others) by
+//! [`total`](Exposure::total) in [`Exposure`]). Note that the pro rata division of rewards uses
+//! the total exposure behind the validator, *not* just the exposure of the page being paid out.
 //!
 //! All entities who receive a reward have the option to choose their reward destination through the
 //! [`Payee`] storage item (see
@@ -299,7 +299,7 @@ pub mod weights;
 
 mod pallet;
 
-use codec::{Decode, Encode, HasCompact, MaxEncodedLen};
+use codec::{Decode, Encode, EncodeLike, HasCompact, MaxEncodedLen};
 use frame_support::{
 	traits::{Currency, Defensive, Get},
 	weights::Weight,
@@ -341,6 +341,9 @@ pub type MaxWinnersOf<T> = <<T as Config>::ElectionProvider as frame_election_pr
 /// Counter for the number of "reward" points earned by a given validator.
 pub type RewardPoint = u32;
 
+/// Index of a page of the nominators backing a validator in a given era.
+pub type Page = u32;
+
 /// The balance type of this pallet.
 pub type BalanceOf<T> = <T as Config>::CurrencyBalance;
 
@@ -454,9 +457,11 @@ pub struct StakingLedger<T: Config> {
 	/// (assuming it doesn't get slashed first). It is assumed that this will be treated as a first
 	/// in, first out queue where the new (higher value) eras get pushed on the back.
 	pub unlocking: BoundedVec<UnlockChunk<BalanceOf<T>>, T::MaxUnlockingChunks>,
-	/// List of eras for which the stakers behind a validator have claimed rewards. Only updated
-	/// for validators.
-	pub claimed_rewards: BoundedVec<EraIndex, T::HistoryDepth>,
+	/// List of eras for which the stakers behind a validator have claimed rewards, for eras
+	/// stored before the paged exposures. Also holds the eras before this ledger was bonded.
+	///
+	/// The rewards claimed for paged exposures are tracked in [`ClaimedRewards`].
+	pub legacy_claimed_rewards: BoundedVec<EraIndex, T::HistoryDepth>,
 }
 
 impl<T: Config> StakingLedger<T> {
@@ -467,7 +472,7 @@ impl<T: Config> StakingLedger<T> {
 			total: Zero::zero(),
 			active: Zero::zero(),
 			unlocking: Default::default(),
-			claimed_rewards: Default::default(),
+			legacy_claimed_rewards: Default::default(),
 		}
 	}
 
@@ -497,7 +502,7 @@ impl<T: Config> StakingLedger<T> {
 			total,
 			active: self.active,
 			unlocking,
-			claimed_rewards: self.claimed_rewards,
+			legacy_claimed_rewards: self.legacy_claimed_rewards,
 		}
 	}
 
@@ -720,6 +725,74 @@ impl<AccountId, Balance: Default + HasCompact> Default for Exposure<AccountId, B
 	}
 }
 
+impl<AccountId: Clone, Balance: HasCompact + MaxEncodedLen + AtLeast32BitUnsigned + Copy>
+	Exposure<AccountId, Balance>
+{
+	/// Splits an `Exposure` into its [`PagedExposureMetadata`] and chunks of
+	/// [`IndividualExposure`], each chunk holding at most `page_size` nominators.
+	///
+	/// The returned metadata always reports at least one page, since the validator's own stake
+	/// is paid out with the first page even when there are no nominators.
+	pub fn into_pages(
+		self,
+		page_size: Page,
+	) -> (PagedExposureMetadata<Balance>, Vec<ExposurePage<AccountId, Balance>>) {
+		let pages = self
+			.others
+			.chunks(page_size.max(1) as usize)
+			.map(|chunk| ExposurePage {
+				page_total: chunk
+					.iter()
+					.fold(Zero::zero(), |acc: Balance, i| acc.saturating_add(i.value)),
+				others: chunk.to_vec(),
+			})
+			.collect::<Vec<_>>();
+
+		let metadata = PagedExposureMetadata {
+			total: self.total,
+			own: self.own,
+			nominator_count: self.others.len() as u32,
+			page_count: (pages.len() as Page).max(1),
+		};
+
+		(metadata, pages)
+	}
+}
+
+/// A page of the nominators backing a single validator in a given era.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct ExposurePage<AccountId, Balance: HasCompact> {
+	/// The total balance of the nominators in this page.
+	#[codec(compact)]
+	pub page_total: Balance,
+	/// The portions of nominators stashes that are exposed.
+	pub others: Vec<IndividualExposure<AccountId, Balance>>,
+}
+
+impl<AccountId, Balance: Default + HasCompact> Default for ExposurePage<AccountId, Balance> {
+	fn default() -> Self {
+		Self { page_total: Default::default(), others: vec![] }
+	}
+}
+
+/// Summary of the stake backing a single validator in a given era, whose nominators are stored
+/// separately in [`ExposurePage`]s.
+#[derive(
+	PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub struct PagedExposureMetadata<Balance: HasCompact + MaxEncodedLen> {
+	/// The total balance backing this validator.
+	#[codec(compact)]
+	pub total: Balance,
+	/// The validator's own stash that is exposed.
+	#[codec(compact)]
+	pub own: Balance,
+	/// Number of nominators backing this validator.
+	pub nominator_count: u32,
+	/// Number of pages of nominators.
+	pub page_count: Page,
+}
+
 /// A pending slash record. The value of the slash has been computed but not applied yet,
 /// rather deferred for several eras.
 #[derive(Encode, Decode, RuntimeDebug, TypeInfo)]
@@ -902,7 +975,126 @@ impl<T: Config> Convert<T::AccountId, Option<Exposure<T::AccountId, BalanceOf<T>
 {
 	fn convert(validator: T::AccountId) -> Option<Exposure<T::AccountId, BalanceOf<T>>> {
 		<Pallet<T>>::active_era()
-			.map(|active_era| <Pallet<T>>::eras_stakers(active_era.index, &validator))
+			.map(|active_era| EraInfo::<T>::get_full_exposure(active_era.index, &validator))
+	}
+}
+
+/// Accessors for the era exposures and claimed rewards.
+///
+/// Exposures are stored in pages since storage version 14. Eras recorded before that are still
+/// readable from the legacy `ErasStakers` and `ErasStakersClipped` storage items, which are
+/// treated as a single page until they fall out of `HistoryDepth`.
+pub struct EraInfo<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Config> EraInfo<T> {
+	/// Whether the rewards of the given `page` of `ledger.stash` for `era` have been claimed,
+	/// also looking at the legacy claimed rewards of the ledger.
+	pub(crate) fn is_rewards_claimed_with_legacy_fallback(
+		era: EraIndex,
+		ledger: &StakingLedger<T>,
+		page: Page,
+	) -> bool {
+		ledger.legacy_claimed_rewards.binary_search(&era).is_ok() ||
+			ClaimedRewards::<T>::get(era, &ledger.stash).contains(&page)
+	}
+
+	/// Get the exposure of `validator` restricted to the nominators of the given `page`.
+	///
+	/// The returned `total` and `own` are those of the full exposure. Returns `None` if the page
+	/// does not exist. Legacy exposures have a single page holding the clipped nominators.
+	pub fn get_paged_exposure(
+		era: EraIndex,
+		validator: &T::AccountId,
+		page: Page,
+	) -> Option<Exposure<T::AccountId, BalanceOf<T>>> {
+		match ErasStakersOverview::<T>::get(era, validator) {
+			Some(overview) => {
+				if page >= overview.page_count {
+					return None
+				}
+				let others = ErasStakersPaged::<T>::get((era, validator, page))
+					.map(|p| p.others)
+					.unwrap_or_default();
+				Some(Exposure { total: overview.total, own: overview.own, others })
+			},
+			None if page == 0 => Some(ErasStakersClipped::<T>::get(era, validator)),
+			None => None,
+		}
+	}
+
+	/// Get the full exposure of `validator` in `era`, collecting all of its pages.
+	pub fn get_full_exposure(
+		era: EraIndex,
+		validator: impl EncodeLike<T::AccountId>,
+	) -> Exposure<T::AccountId, BalanceOf<T>> {
+		match ErasStakersOverview::<T>::get(era, &validator) {
+			Some(overview) => {
+				let mut others = Vec::with_capacity(overview.nominator_count as usize);
+				for page in 0..overview.page_count {
+					if let Some(p) = ErasStakersPaged::<T>::get((era, &validator, page)) {
+						others.extend(p.others);
+					}
+				}
+				Exposure { total: overview.total, own: overview.own, others }
+			},
+			None => ErasStakers::<T>::get(era, validator),
+		}
+	}
+
+	/// Number of pages of the exposure of `validator` in `era`.
+	///
+	/// Always at least one, legacy exposures being a single page.
+	pub fn get_page_count(era: EraIndex, validator: &T::AccountId) -> Page {
+		ErasStakersOverview::<T>::get(era, validator).map_or(1, |o| o.page_count)
+	}
+
+	/// The first page of `ledger.stash` in `era` whose rewards have not been claimed yet, if any.
+	pub(crate) fn get_next_claimable_page(
+		era: EraIndex,
+		ledger: &StakingLedger<T>,
+	) -> Option<Page> {
+		if ledger.legacy_claimed_rewards.binary_search(&era).is_ok() {
+			return None
+		}
+
+		let claimed = ClaimedRewards::<T>::get(era, &ledger.stash);
+		(0..Self::get_page_count(era, &ledger.stash)).find(|page| !claimed.contains(page))
+	}
+
+	/// Record the rewards of `page` of `validator` in `era` as claimed.
+	pub(crate) fn set_rewards_as_claimed(era: EraIndex, validator: &T::AccountId, page: Page) {
+		ClaimedRewards::<T>::mutate(era, validator, |pages| {
+			if let Err(pos) = pages.binary_search(&page) {
+				// Since pages are unique and below the page count of the exposure, which is at
+				// most `MaxExposurePageCount`, following bound is always expected to be satisfied.
+				let _ = pages.try_insert(pos, page).defensive();
+			}
+		})
+	}
+
+	/// Store the exposure of `validator` in `era`, split in pages of at most
+	/// `T::MaxExposurePageSize` nominators.
+	///
+	/// Only the biggest stakers fitting in `T::MaxExposurePageCount` pages are kept. The `total`
+	/// and `own` of the exposure remain unchanged.
+	pub fn set_exposure(
+		era: EraIndex,
+		validator: &T::AccountId,
+		mut exposure: Exposure<T::AccountId, BalanceOf<T>>,
+	) {
+		let page_size = T::MaxExposurePageSize::get();
+		let max_nominators = T::MaxExposurePageCount::get().saturating_mul(page_size) as usize;
+		if exposure.others.len() > max_nominators {
+			exposure.others.sort_by(|a, b| a.value.cmp(&b.value).reverse());
+			exposure.others.truncate(max_nominators);
+		}
+
+		let (metadata, pages) = exposure.into_pages(page_size);
+
+		ErasStakersOverview::<T>::insert(era, validator, metadata);
+		for (page, exposure_page) in pages.into_iter().enumerate() {
+			ErasStakersPaged::<T>::insert((era, validator, page as Page), exposure_page);
+		}
 	}
 }
 
@@ -57,6 +57,103 @@ impl Default for ObsoleteReleases {
 #[storage_alias]
 type StorageVersion<T: Config> = StorageValue<Pallet<T>, ObsoleteReleases, ValueQuery>;
 
+pub mod v14 {
+	use super::*;
+
+	/// Migrate to paged exposures.
+	///
+	/// Exposures of new eras are stored in `ErasStakersOverview` and `ErasStakersPaged`, and their
+	/// claimed rewards in `ClaimedRewards`. The exposures of eras stored before this migration are
+	/// not translated: they are still read from `ErasStakers` and `ErasStakersClipped` as a single
+	/// page until they fall out of `HistoryDepth`.
+	///
+	/// The rewards validators claimed for these eras are moved from
+	/// `StakingLedger.legacy_claimed_rewards` to `ClaimedRewards`, as their single page.
+	pub struct MigrateToV14<T>(sp_std::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV14<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 13,
+				"Required v13 before upgrading to v14"
+			);
+
+			let claimed = ErasStakersClipped::<T>::iter_keys()
+				.filter(|(era, validator)| {
+					Bonded::<T>::get(validator)
+						.and_then(|controller| Ledger::<T>::get(controller))
+						.map_or(false, |l| l.legacy_claimed_rewards.binary_search(era).is_ok())
+				})
+				.count() as u32;
+
+			Ok(claimed.encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			let current = Pallet::<T>::current_storage_version();
+			let onchain = Pallet::<T>::on_chain_storage_version();
+
+			if current == 14 && onchain == 13 {
+				let mut reads = 1u64;
+				let mut writes = 1u64;
+
+				let mut exposed_eras = BTreeMap::<T::AccountId, Vec<EraIndex>>::new();
+				for (era, validator) in ErasStakersClipped::<T>::iter_keys() {
+					reads.saturating_inc();
+					exposed_eras.entry(validator).or_default().push(era);
+				}
+
+				for (validator, eras) in exposed_eras {
+					reads.saturating_accrue(2);
+					let Some(controller) = Bonded::<T>::get(&validator) else { continue };
+					let Some(mut ledger) = Ledger::<T>::get(&controller) else { continue };
+
+					let mut moved = false;
+					for era in eras {
+						if let Ok(pos) = ledger.legacy_claimed_rewards.binary_search(&era) {
+							ledger.legacy_claimed_rewards.remove(pos);
+							EraInfo::<T>::set_rewards_as_claimed(era, &validator, 0);
+							writes.saturating_inc();
+							moved = true;
+						}
+					}
+
+					if moved {
+						Ledger::<T>::insert(&controller, ledger);
+						writes.saturating_inc();
+					}
+				}
+
+				current.put::<Pallet<T>>();
+
+				log!(info, "v14 applied successfully");
+				T::DbWeight::get().reads_writes(reads, writes)
+			} else {
+				log!(warn, "Skipping v14, should be removed");
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 14,
+				"v14 not applied"
+			);
+
+			let claimed: u32 = Decode::decode(&mut &state[..]).expect(
+				"the state parameter should be something that was generated by pre_upgrade",
+			);
+			frame_support::ensure!(
+				ClaimedRewards::<T>::iter().count() as u32 == claimed,
+				"the claimed rewards of the validators should be moved to `ClaimedRewards`"
+			);
+
+			Ok(())
+		}
+	}
+}
+
 pub mod v13 {
 	use super::*;
 
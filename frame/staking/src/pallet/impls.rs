@@ -26,8 +26,8 @@ use frame_support::{
 	dispatch::WithPostDispatchInfo,
 	pallet_prelude::*,
 	traits::{
-		Currency, Defensive, DefensiveResult, EstimateNextNewSession, Get, Imbalance,
-		LockableCurrency, OnUnbalanced, TryCollect, UnixTime, WithdrawReasons,
+		Currency, Defensive, EstimateNextNewSession, Get, Imbalance, LockableCurrency,
+		OnUnbalanced, TryCollect, UnixTime, WithdrawReasons,
 	},
 	weights::Weight,
 };
@@ -45,9 +45,9 @@ use sp_staking::{
 use sp_std::prelude::*;
 
 use crate::{
-	log, slashing, weights::WeightInfo, ActiveEraInfo, BalanceOf, EraPayout, Exposure, ExposureOf,
-	Forcing, IndividualExposure, MaxWinnersOf, Nominations, PositiveImbalanceOf, RewardDestination,
-	SessionInterface, StakingLedger, ValidatorPrefs,
+	log, slashing, weights::WeightInfo, ActiveEraInfo, BalanceOf, EraInfo, EraPayout, Exposure,
+	ExposureOf, Forcing, IndividualExposure, MaxWinnersOf, Nominations, Page, PositiveImbalanceOf,
+	RewardDestination, SessionInterface, StakingLedger, ValidatorPrefs,
 };
 
 use super::{pallet::*, STAKING_ID};
@@ -138,55 +138,74 @@ impl<T: Config> Pallet<T> {
 		Ok(used_weight)
 	}
 
+	/// Get the full exposure of `account` in `era`. See [`EraInfo::get_full_exposure`].
+	#[deprecated(note = "Use `EraInfo::get_full_exposure` or `eras_stakers_paged` instead.")]
+	pub fn eras_stakers(
+		era: EraIndex,
+		account: impl codec::EncodeLike<T::AccountId>,
+	) -> Exposure<T::AccountId, BalanceOf<T>> {
+		EraInfo::<T>::get_full_exposure(era, account)
+	}
+
 	pub(super) fn do_payout_stakers(
 		validator_stash: T::AccountId,
 		era: EraIndex,
 	) -> DispatchResultWithPostInfo {
-		// Validate input data
-		let current_era = CurrentEra::<T>::get().ok_or_else(|| {
-			Error::<T>::InvalidEraToReward
-				.with_weight(T::WeightInfo::payout_stakers_alive_staked(0))
+		let controller = Self::bonded(&validator_stash).ok_or_else(|| {
+			Error::<T>::NotStash.with_weight(T::WeightInfo::payout_stakers_alive_staked(0))
+		})?;
+		let ledger = <Ledger<T>>::get(&controller).ok_or(Error::<T>::NotController)?;
+		let page = EraInfo::<T>::get_next_claimable_page(era, &ledger).ok_or_else(|| {
+			Error::<T>::AlreadyClaimed.with_weight(T::WeightInfo::payout_stakers_alive_staked(0))
 		})?;
+
+		Self::do_payout_stakers_by_page(
+			validator_stash,
+			era,
+			page,
+			T::WeightInfo::payout_stakers_alive_staked,
+		)
+	}
+
+	pub(super) fn do_payout_stakers_by_page(
+		validator_stash: T::AccountId,
+		era: EraIndex,
+		page: Page,
+		payout_weight: fn(u32) -> Weight,
+	) -> DispatchResultWithPostInfo {
+		// Validate input data
+		let current_era = CurrentEra::<T>::get()
+			.ok_or_else(|| Error::<T>::InvalidEraToReward.with_weight(payout_weight(0)))?;
 		let history_depth = T::HistoryDepth::get();
 		ensure!(
 			era <= current_era && era >= current_era.saturating_sub(history_depth),
-			Error::<T>::InvalidEraToReward
-				.with_weight(T::WeightInfo::payout_stakers_alive_staked(0))
+			Error::<T>::InvalidEraToReward.with_weight(payout_weight(0))
 		);
 
 		// Note: if era has no reward to be claimed, era may be future. better not to update
-		// `ledger.claimed_rewards` in this case.
-		let era_payout = <ErasValidatorReward<T>>::get(&era).ok_or_else(|| {
-			Error::<T>::InvalidEraToReward
-				.with_weight(T::WeightInfo::payout_stakers_alive_staked(0))
-		})?;
+		// `ClaimedRewards` in this case.
+		let era_payout = <ErasValidatorReward<T>>::get(&era)
+			.ok_or_else(|| Error::<T>::InvalidEraToReward.with_weight(payout_weight(0)))?;
 
-		let controller = Self::bonded(&validator_stash).ok_or_else(|| {
-			Error::<T>::NotStash.with_weight(T::WeightInfo::payout_stakers_alive_staked(0))
-		})?;
+		let controller = Self::bonded(&validator_stash)
+			.ok_or_else(|| Error::<T>::NotStash.with_weight(payout_weight(0)))?;
 		let mut ledger = <Ledger<T>>::get(&controller).ok_or(Error::<T>::NotController)?;
 
 		ledger
-			.claimed_rewards
+			.legacy_claimed_rewards
 			.retain(|&x| x >= current_era.saturating_sub(history_depth));
 
-		match ledger.claimed_rewards.binary_search(&era) {
-			Ok(_) =>
-				return Err(Error::<T>::AlreadyClaimed
-					.with_weight(T::WeightInfo::payout_stakers_alive_staked(0))),
-			Err(pos) => ledger
-				.claimed_rewards
-				.try_insert(pos, era)
-				// Since we retain era entries in `claimed_rewards` only upto
-				// `HistoryDepth`, following bound is always expected to be
-				// satisfied.
-				.defensive_map_err(|_| Error::<T>::BoundNotMet)?,
-		}
+		ensure!(
+			!EraInfo::<T>::is_rewards_claimed_with_legacy_fallback(era, &ledger, page),
+			Error::<T>::AlreadyClaimed.with_weight(payout_weight(0))
+		);
 
-		let exposure = <ErasStakersClipped<T>>::get(&era, &ledger.stash);
+		let exposure = EraInfo::<T>::get_paged_exposure(era, &ledger.stash, page)
+			.ok_or_else(|| Error::<T>::InvalidPage.with_weight(payout_weight(0)))?;
 
 		// Input data seems good, no errors allowed after this point
 
+		EraInfo::<T>::set_rewards_as_claimed(era, &ledger.stash, page);
 		<Ledger<T>>::insert(&controller, &ledger);
 
 		// Get Era reward points. It has TOTAL and INDIVIDUAL
@@ -206,7 +225,7 @@ impl<T: Config> Pallet<T> {
 
 		// Nothing to do if they have no reward points.
 		if validator_reward_points.is_zero() {
-			return Ok(Some(T::WeightInfo::payout_stakers_alive_staked(0)).into())
+			return Ok(Some(payout_weight(0)).into())
 		}
 
 		// This is the fraction of the total reward that the validator and the
@@ -223,9 +242,6 @@ impl<T: Config> Pallet<T> {
 		let validator_commission_payout = validator_commission * validator_total_payout;
 
 		let validator_leftover_payout = validator_total_payout - validator_commission_payout;
-		// Now let's calculate how this is split to the validator.
-		let validator_exposure_part = Perbill::from_rational(exposure.own, exposure.total);
-		let validator_staking_payout = validator_exposure_part * validator_leftover_payout;
 
 		Self::deposit_event(Event::<T>::PayoutStarted {
 			era_index: era,
@@ -233,15 +249,23 @@ impl<T: Config> Pallet<T> {
 		});
 
 		let mut total_imbalance = PositiveImbalanceOf::<T>::zero();
-		// We can now make total validator payout:
-		if let Some(imbalance) =
-			Self::make_payout(&ledger.stash, validator_staking_payout + validator_commission_payout)
-		{
-			Self::deposit_event(Event::<T>::Rewarded {
-				stash: ledger.stash,
-				amount: imbalance.peek(),
-			});
-			total_imbalance.subsume(imbalance);
+		// The validator is paid its commission and its own share with the first page only.
+		if page == 0 {
+			// Now let's calculate how this is split to the validator.
+			let validator_exposure_part = Perbill::from_rational(exposure.own, exposure.total);
+			let validator_staking_payout = validator_exposure_part * validator_leftover_payout;
+
+			// We can now make total validator payout:
+			if let Some(imbalance) = Self::make_payout(
+				&ledger.stash,
+				validator_staking_payout + validator_commission_payout,
+			) {
+				Self::deposit_event(Event::<T>::Rewarded {
+					stash: ledger.stash,
+					amount: imbalance.peek(),
+				});
+				total_imbalance.subsume(imbalance);
+			}
 		}
 
 		// Track the number of payout ops to nominators. Note:
@@ -249,8 +273,8 @@ impl<T: Config> Pallet<T> {
 		// out, so we do not need to count their payout op.
 		let mut nominator_payout_count: u32 = 0;
 
-		// Lets now calculate how this is split to the nominators.
-		// Reward only the clipped exposures. Note this is not necessarily sorted.
+		// Lets now calculate how this is split to the nominators of this page.
+		// Note this is not necessarily sorted.
 		for nominator in exposure.others.iter() {
 			let nominator_exposure_part = Perbill::from_rational(nominator.value, exposure.total);
 
@@ -268,8 +292,8 @@ impl<T: Config> Pallet<T> {
 		}
 
 		T::Reward::on_unbalanced(total_imbalance);
-		debug_assert!(nominator_payout_count <= T::MaxNominatorRewardedPerValidator::get());
-		Ok(Some(T::WeightInfo::payout_stakers_alive_staked(nominator_payout_count)).into())
+		debug_assert!(nominator_payout_count <= T::MaxExposurePageSize::get());
+		Ok(Some(payout_weight(nominator_payout_count)).into())
 	}
 
 	/// Update the ledger for a controller.
@@ -580,15 +604,7 @@ impl<T: Config> Pallet<T> {
 		let mut total_stake: BalanceOf<T> = Zero::zero();
 		exposures.into_iter().for_each(|(stash, exposure)| {
 			total_stake = total_stake.saturating_add(exposure.total);
-			<ErasStakers<T>>::insert(new_planned_era, &stash, &exposure);
-
-			let mut exposure_clipped = exposure;
-			let clipped_max_len = T::MaxNominatorRewardedPerValidator::get() as usize;
-			if exposure_clipped.others.len() > clipped_max_len {
-				exposure_clipped.others.sort_by(|a, b| a.value.cmp(&b.value).reverse());
-				exposure_clipped.others.truncate(clipped_max_len);
-			}
-			<ErasStakersClipped<T>>::insert(&new_planned_era, &stash, exposure_clipped);
+			EraInfo::<T>::set_exposure(new_planned_era, &stash, exposure);
 		});
 
 		// Insert current era staking information
@@ -679,6 +695,12 @@ impl<T: Config> Pallet<T> {
 		debug_assert!(cursor.maybe_cursor.is_none());
 		cursor = <ErasStakersClipped<T>>::clear_prefix(era_index, u32::MAX, None);
 		debug_assert!(cursor.maybe_cursor.is_none());
+		cursor = <ErasStakersOverview<T>>::clear_prefix(era_index, u32::MAX, None);
+		debug_assert!(cursor.maybe_cursor.is_none());
+		cursor = <ErasStakersPaged<T>>::clear_prefix((era_index,), u32::MAX, None);
+		debug_assert!(cursor.maybe_cursor.is_none());
+		cursor = <ClaimedRewards<T>>::clear_prefix(era_index, u32::MAX, None);
+		debug_assert!(cursor.maybe_cursor.is_none());
 		cursor = <ErasValidatorPrefs<T>>::clear_prefix(era_index, u32::MAX, None);
 		debug_assert!(cursor.maybe_cursor.is_none());
 		<ErasValidatorReward<T>>::remove(era_index);
@@ -745,7 +767,7 @@ impl<T: Config> Pallet<T> {
 		stash: T::AccountId,
 		exposure: Exposure<T::AccountId, BalanceOf<T>>,
 	) {
-		<ErasStakers<T>>::insert(&current_era, &stash, &exposure);
+		EraInfo::<T>::set_exposure(current_era, &stash, exposure);
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
@@ -1078,7 +1100,7 @@ impl<T: Config> ElectionDataProvider for Pallet<T> {
 				active: stake,
 				total: stake,
 				unlocking: Default::default(),
-				claimed_rewards: Default::default(),
+				legacy_claimed_rewards: Default::default(),
 			},
 		);
 
@@ -1096,7 +1118,7 @@ impl<T: Config> ElectionDataProvider for Pallet<T> {
 				active: stake,
 				total: stake,
 				unlocking: Default::default(),
-				claimed_rewards: Default::default(),
+				legacy_claimed_rewards: Default::default(),
 			},
 		);
 		Self::do_add_validator(
@@ -1137,7 +1159,7 @@ impl<T: Config> ElectionDataProvider for Pallet<T> {
 					active: stake,
 					total: stake,
 					unlocking: Default::default(),
-					claimed_rewards: Default::default(),
+					legacy_claimed_rewards: Default::default(),
 				},
 			);
 			Self::do_add_validator(
@@ -1158,7 +1180,7 @@ impl<T: Config> ElectionDataProvider for Pallet<T> {
 					active: stake,
 					total: stake,
 					unlocking: Default::default(),
-					claimed_rewards: Default::default(),
+					legacy_claimed_rewards: Default::default(),
 				},
 			);
 			Self::do_add_nominator(
@@ -1209,7 +1231,7 @@ impl<T: Config> historical::SessionManager<T::AccountId, Exposure<T::AccountId,
 			validators
 				.into_iter()
 				.map(|v| {
-					let exposure = Self::eras_stakers(current_era, &v);
+					let exposure = EraInfo::<T>::get_full_exposure(current_era, &v);
 					(v, exposure)
 				})
 				.collect()
@@ -1227,7 +1249,7 @@ impl<T: Config> historical::SessionManager<T::AccountId, Exposure<T::AccountId,
 				validators
 					.into_iter()
 					.map(|v| {
-						let exposure = Self::eras_stakers(current_era, &v);
+						let exposure = EraInfo::<T>::get_full_exposure(current_era, &v);
 						(v, exposure)
 					})
 					.collect()
@@ -1608,9 +1630,15 @@ impl<T: Config> StakingInterface for Pallet<T> {
 	}
 
 	fn is_exposed_in_era(who: &Self::AccountId, era: &EraIndex) -> bool {
+		// look in the legacy non paged exposures
 		ErasStakers::<T>::iter_prefix(era).any(|(validator, exposures)| {
 			validator == *who || exposures.others.iter().any(|i| i.who == *who)
-		})
+		}) ||
+			// look in the paged exposures, validators only have an overview without
+			// nominators.
+			ErasStakersOverview::<T>::contains_key(era, who) ||
+			ErasStakersPaged::<T>::iter_prefix_values((era,))
+				.any(|page| page.others.iter().any(|i| i.who == *who))
 	}
 
 	fn bonding_duration() -> EraIndex {
@@ -1714,7 +1742,7 @@ impl<T: Config> StakingInterface for Pallet<T> {
 				.map(|(who, value)| IndividualExposure { who: who.clone(), value: value.clone() })
 				.collect::<Vec<_>>();
 			let exposure = Exposure { total: Default::default(), own: Default::default(), others };
-			<ErasStakers<T>>::insert(&current_era, &stash, &exposure);
+			EraInfo::<T>::set_exposure(*current_era, stash, exposure);
 		}
 
 		fn set_current_era(era: EraIndex) {
@@ -1779,6 +1807,45 @@ impl<T: Config> Pallet<T> {
 				);
 				Ok(())
 			})
+			.collect::<Result<(), TryRuntimeError>>()?;
+
+		ErasStakersOverview::<T>::iter_prefix(era)
+			.map(|(validator, overview)| {
+				// the nominators that don't fit in `MaxExposurePageCount` pages are not stored, but
+				// still count in the total.
+				let expo = EraInfo::<T>::get_full_exposure(era, &validator);
+				ensure!(
+					expo.total >=
+						expo.own +
+							expo.others
+								.iter()
+								.map(|e| e.value)
+								.fold(Zero::zero(), |acc, x| acc + x),
+					"wrong total exposure.",
+				);
+				let pages = ErasStakersPaged::<T>::iter_prefix_values((era, validator))
+					.map(|page| {
+						ensure!(
+							page.page_total ==
+								page.others
+									.iter()
+									.map(|e| e.value)
+									.fold(Zero::zero(), |acc, x| acc + x),
+							"wrong page total exposure.",
+						);
+						ensure!(
+							page.others.len() as u32 <= T::MaxExposurePageSize::get(),
+							"exposure page too large.",
+						);
+						Ok(())
+					})
+					.collect::<Result<Vec<_>, TryRuntimeError>>()?;
+				ensure!(
+					(pages.len() as Page).max(1) == overview.page_count,
+					"wrong exposure page count.",
+				);
+				Ok(())
+			})
 			.collect::<Result<(), TryRuntimeError>>()
 	}
 
@@ -1802,7 +1869,7 @@ impl<T: Config> Pallet<T> {
 				let mut sum = BalanceOf::<T>::zero();
 				T::SessionInterface::validators()
 					.iter()
-					.map(|v| Self::eras_stakers(era, v))
+					.map(|v| EraInfo::<T>::get_full_exposure(era, v))
 					.map(|e| -> Result<(), TryRuntimeError> {
 						let individual =
 							e.others.iter().filter(|e| e.who == nominator).collect::<Vec<_>>();
@@ -45,9 +45,9 @@ pub use impls::*;
 
 use crate::{
 	slashing, weights::WeightInfo, AccountIdLookupOf, ActiveEraInfo, BalanceOf, EraPayout,
-	EraRewardPoints, Exposure, Forcing, NegativeImbalanceOf, Nominations, PositiveImbalanceOf,
-	RewardDestination, SessionInterface, StakingLedger, UnappliedSlash, UnlockChunk,
-	ValidatorPrefs,
+	EraRewardPoints, Exposure, ExposurePage, Forcing, NegativeImbalanceOf, Nominations, Page,
+	PagedExposureMetadata, PositiveImbalanceOf, RewardDestination, SessionInterface, StakingLedger,
+	UnappliedSlash, UnlockChunk, ValidatorPrefs,
 };
 
 const STAKING_ID: LockIdentifier = *b"staking ";
@@ -65,7 +65,7 @@ pub mod pallet {
 	use super::*;
 
 	/// The current storage version.
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(13);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(14);
 
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
@@ -137,9 +137,10 @@ pub mod pallet {
 		///
 		/// Following information is kept for eras in `[current_era -
 		/// HistoryDepth, current_era]`: `ErasStakers`, `ErasStakersClipped`,
-		/// `ErasValidatorPrefs`, `ErasValidatorReward`, `ErasRewardPoints`,
-		/// `ErasTotalStake`, `ErasStartSessionIndex`,
-		/// `StakingLedger.claimed_rewards`.
+		/// `ErasStakersOverview`, `ErasStakersPaged`, `ErasValidatorPrefs`,
+		/// `ErasValidatorReward`, `ErasRewardPoints`, `ErasTotalStake`,
+		/// `ErasStartSessionIndex`, `ClaimedRewards`,
+		/// `StakingLedger.legacy_claimed_rewards`.
 		///
 		/// Must be more than the number of eras delayed by session.
 		/// I.e. active era must always be in history. I.e. `active_era >
@@ -149,7 +150,7 @@ pub mod pallet {
 		/// this should be set to same value or greater as in storage.
 		///
 		/// Note: `HistoryDepth` is used as the upper bound for the `BoundedVec`
-		/// item `StakingLedger.claimed_rewards`. Setting this value lower than
+		/// item `StakingLedger.legacy_claimed_rewards`. Setting this value lower than
 		/// the existing value can lead to inconsistencies in the
 		/// `StakingLedger` and will need to be handled properly in a migration.
 		/// The test `reducing_history_depth_abrupt` shows this effect.
@@ -202,12 +203,25 @@ pub mod pallet {
 		/// guess.
 		type NextNewSession: EstimateNextNewSession<BlockNumberFor<Self>>;
 
-		/// The maximum number of nominators rewarded for each validator.
+		/// The maximum number of nominators in each page of an exposure.
 		///
-		/// For each validator only the `$MaxNominatorRewardedPerValidator` biggest stakers can
-		/// claim their reward. This used to limit the i/o cost for the nominator payout.
+		/// The nominators backing a validator are split in pages of this size, each paid out by a
+		/// separate call. This is used to limit the i/o cost of a single nominator payout.
+		///
+		/// For the legacy non-paged exposures, only the `$MaxExposurePageSize` biggest stakers of
+		/// each validator can claim their reward.
+		#[pallet::constant]
+		type MaxExposurePageSize: Get<u32>;
+
+		/// The maximum number of pages of an exposure.
+		///
+		/// Only the `$MaxExposurePageCount * $MaxExposurePageSize` biggest stakers of each
+		/// validator are exposed. The smaller ones are dropped from the exposure: their stake still
+		/// counts in its `total`, but they are neither rewarded nor slashed for the validator. To
+		/// keep all backers, the pages must fit the maximum number of electing voters. Must be at
+		/// least one.
 		#[pallet::constant]
-		type MaxNominatorRewardedPerValidator: Get<u32>;
+		type MaxExposurePageCount: Get<Page>;
 
 		/// The fraction of the validator set that is safe to be offending.
 		/// After the threshold is reached a new era will be forced.
@@ -403,8 +417,9 @@ pub mod pallet {
 	///
 	/// Is it removed after `HISTORY_DEPTH` eras.
 	/// If stakers hasn't been set or has been removed then empty exposure is returned.
+	///
+	/// Note: Deprecated since v14. Use [`EraInfo`](crate::EraInfo) instead to work with exposures.
 	#[pallet::storage]
-	#[pallet::getter(fn eras_stakers)]
 	#[pallet::unbounded]
 	pub type ErasStakers<T: Config> = StorageDoubleMap<
 		_,
@@ -419,7 +434,7 @@ pub mod pallet {
 	/// Clipped Exposure of validator at era.
 	///
 	/// This is similar to [`ErasStakers`] but number of nominators exposed is reduced to the
-	/// `T::MaxNominatorRewardedPerValidator` biggest stakers.
+	/// `T::MaxExposurePageSize` biggest stakers.
 	/// (Note: the field `total` and `own` of the exposure remains unchanged).
 	/// This is used to limit the i/o cost for the nominator payout.
 	///
@@ -427,6 +442,8 @@ pub mod pallet {
 	///
 	/// Is it removed after `HISTORY_DEPTH` eras.
 	/// If stakers hasn't been set or has been removed then empty exposure is returned.
+	///
+	/// Note: Deprecated since v14. Use [`EraInfo`](crate::EraInfo) instead to work with exposures.
 	#[pallet::storage]
 	#[pallet::unbounded]
 	#[pallet::getter(fn eras_stakers_clipped)]
@@ -440,6 +457,61 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// Summary of the exposure of validator at era, whose nominators are stored in
+	/// [`ErasStakersPaged`].
+	///
+	/// This is keyed first by the era index to allow bulk deletion and then the stash account.
+	///
+	/// Is it removed after `HISTORY_DEPTH` eras.
+	#[pallet::storage]
+	#[pallet::getter(fn eras_stakers_overview)]
+	pub type ErasStakersOverview<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		EraIndex,
+		Twox64Concat,
+		T::AccountId,
+		PagedExposureMetadata<BalanceOf<T>>,
+		OptionQuery,
+	>;
+
+	/// Paged nominators of the exposure of validator at era.
+	///
+	/// Each page holds at most `T::MaxExposurePageSize` nominators. This is keyed first by the era
+	/// index to allow bulk deletion, then the stash account and finally the page.
+	///
+	/// Is it removed after `HISTORY_DEPTH` eras.
+	#[pallet::storage]
+	#[pallet::unbounded]
+	#[pallet::getter(fn eras_stakers_paged)]
+	pub type ErasStakersPaged<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Twox64Concat, EraIndex>,
+			NMapKey<Twox64Concat, T::AccountId>,
+			NMapKey<Twox64Concat, Page>,
+		),
+		ExposurePage<T::AccountId, BalanceOf<T>>,
+		OptionQuery,
+	>;
+
+	/// Sorted pages of the exposure of validator at era whose rewards have been claimed.
+	///
+	/// This is keyed first by the era index to allow bulk deletion and then the stash account.
+	///
+	/// Is it removed after `HISTORY_DEPTH` eras.
+	#[pallet::storage]
+	#[pallet::getter(fn claimed_rewards)]
+	pub type ClaimedRewards<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		EraIndex,
+		Twox64Concat,
+		T::AccountId,
+		BoundedVec<Page, T::MaxExposurePageCount>,
+		ValueQuery,
+	>;
+
 	/// Similar to `ErasStakers`, this holds the preferences of validators.
 	///
 	/// This is keyed first by the era index to allow bulk deletion and then the stash account.
@@ -732,7 +804,7 @@ pub mod pallet {
 		InvalidNumberOfNominations,
 		/// Items are not sorted and unique.
 		NotSortedAndUnique,
-		/// Rewards for this era have already been claimed for this validator.
+		/// Rewards for this era or page have already been claimed for this validator.
 		AlreadyClaimed,
 		/// Incorrect previous history depth input provided.
 		IncorrectHistoryDepth,
@@ -756,6 +828,8 @@ pub mod pallet {
 		CommissionTooLow,
 		/// Some bound is not met.
 		BoundNotMet,
+		/// No nominators exist on this page.
+		InvalidPage,
 	}
 
 	#[pallet::hooks]
@@ -867,7 +941,9 @@ pub mod pallet {
 				total: value,
 				active: value,
 				unlocking: Default::default(),
-				claimed_rewards: (last_reward_era..current_era)
+				// Mark the eras before bonding as claimed, so that the rewards of a former ledger
+				// of this stash can't be claimed again.
+				legacy_claimed_rewards: (last_reward_era..current_era)
 					.try_collect()
 					// Since last_reward_era is calculated as `current_era -
 					// HistoryDepth`, following bound is always expected to be
@@ -1453,21 +1529,21 @@ pub mod pallet {
 			Ok(())
 		}
 
-		/// Pay out all the stakers behind a single validator for a single era.
+		/// Pay out the next page of the stakers behind a single validator for a single era.
 		///
-		/// - `validator_stash` is the stash account of the validator. Their nominators, up to
-		///   `T::MaxNominatorRewardedPerValidator`, will also receive their rewards.
+		/// - `validator_stash` is the stash account of the validator. The nominators of the next
+		///   unclaimed page, up to `T::MaxExposurePageSize`, will receive their rewards.
 		/// - `era` may be any era between `[current_era - history_depth; current_era]`.
 		///
 		/// The origin of this call must be _Signed_. Any account can call this function, even if
 		/// it is not one of the stakers.
 		///
+		/// See [`Call::payout_stakers_by_page`] to pay out a specific page.
+		///
 		/// ## Complexity
-		/// - At most O(MaxNominatorRewardedPerValidator).
+		/// - At most O(MaxExposurePageSize).
 		#[pallet::call_index(18)]
-		#[pallet::weight(T::WeightInfo::payout_stakers_alive_staked(
-			T::MaxNominatorRewardedPerValidator::get()
-		))]
+		#[pallet::weight(T::WeightInfo::payout_stakers_alive_staked(T::MaxExposurePageSize::get()))]
 		pub fn payout_stakers(
 			origin: OriginFor<T>,
 			validator_stash: T::AccountId,
@@ -1477,6 +1553,37 @@ pub mod pallet {
 			Self::do_payout_stakers(validator_stash, era)
 		}
 
+		/// Pay out a single page of the stakers behind a single validator for a single era.
+		///
+		/// - `validator_stash` is the stash account of the validator. The validator is paid out
+		///   with the first page.
+		/// - `era` may be any era between `[current_era - history_depth; current_era]`.
+		/// - `page` is the index of the page of nominators to pay out, in `[0, page_count)` as
+		///   given by [`EraInfo::get_page_count`](crate::EraInfo::get_page_count). Legacy exposures
+		///   have a single page.
+		///
+		/// The origin of this call must be _Signed_. Any account can call this function, even if
+		/// it is not one of the stakers.
+		///
+		/// ## Complexity
+		/// - At most O(MaxExposurePageSize).
+		#[pallet::call_index(26)]
+		#[pallet::weight(T::WeightInfo::payout_stakers_by_page(T::MaxExposurePageSize::get()))]
+		pub fn payout_stakers_by_page(
+			origin: OriginFor<T>,
+			validator_stash: T::AccountId,
+			era: EraIndex,
+			page: Page,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			Self::do_payout_stakers_by_page(
+				validator_stash,
+				era,
+				page,
+				T::WeightInfo::payout_stakers_by_page,
+			)
+		}
+
 		/// Rebond a portion of the stash scheduled to be unlocked.
 		///
 		/// The dispatch origin must be signed by the controller.
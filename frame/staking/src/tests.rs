@@ -154,7 +154,7 @@ fn basic_setup_works() {
 				total: 1000,
 				active: 1000,
 				unlocking: Default::default(),
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			}
 		);
 		// Account 21 controls its own stash, which is 200 * balance_factor units
@@ -165,7 +165,7 @@ fn basic_setup_works() {
 				total: 1000,
 				active: 1000,
 				unlocking: Default::default(),
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
 		// Account 1 does not control any stash
@@ -188,13 +188,13 @@ fn basic_setup_works() {
 				total: 500,
 				active: 500,
 				unlocking: Default::default(),
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
 		assert_eq!(Staking::nominators(101).unwrap().targets, vec![11, 21]);
 
 		assert_eq!(
-			Staking::eras_stakers(active_era(), 11),
+			EraInfo::<Test>::get_full_exposure(active_era(), &11),
 			Exposure {
 				total: 1125,
 				own: 1000,
@@ -202,7 +202,7 @@ fn basic_setup_works() {
 			},
 		);
 		assert_eq!(
-			Staking::eras_stakers(active_era(), 21),
+			EraInfo::<Test>::get_full_exposure(active_era(), &21),
 			Exposure {
 				total: 1375,
 				own: 1000,
@@ -463,7 +463,7 @@ fn staking_should_work() {
 				total: 1500,
 				active: 1500,
 				unlocking: Default::default(),
-				claimed_rewards: bounded_vec![0],
+				legacy_claimed_rewards: bounded_vec![0],
 			})
 		);
 		// e.g. it cannot reserve more than 500 that it has free from the total 2000
@@ -518,8 +518,8 @@ fn less_than_needed_candidates_works() {
 
 			// But the exposure is updated in a simple way. No external votes exists.
 			// This is purely self-vote.
-			assert!(ErasStakers::<Test>::iter_prefix_values(active_era())
-				.all(|exposure| exposure.others.is_empty()));
+			assert!(ErasStakersOverview::<Test>::iter_prefix_values(active_era())
+				.all(|exposure| exposure.nominator_count == 0));
 		});
 }
 
@@ -636,9 +636,9 @@ fn nominating_and_rewards_should_work() {
 			assert_eq!(Balances::total_balance(&21), initial_balance_21 + total_payout_0 / 2);
 			initial_balance_21 = Balances::total_balance(&21);
 
-			assert_eq!(ErasStakers::<Test>::iter_prefix_values(active_era()).count(), 2);
+			assert_eq!(ErasStakersOverview::<Test>::iter_prefix_values(active_era()).count(), 2);
 			assert_eq!(
-				Staking::eras_stakers(active_era(), 11),
+				EraInfo::<Test>::get_full_exposure(active_era(), &11),
 				Exposure {
 					total: 1000 + 800,
 					own: 1000,
@@ -649,7 +649,7 @@ fn nominating_and_rewards_should_work() {
 				},
 			);
 			assert_eq!(
-				Staking::eras_stakers(active_era(), 21),
+				EraInfo::<Test>::get_full_exposure(active_era(), &21),
 				Exposure {
 					total: 1000 + 1200,
 					own: 1000,
@@ -709,7 +709,7 @@ fn nominators_also_get_slashed_pro_rata() {
 	ExtBuilder::default().build_and_execute(|| {
 		mock::start_active_era(1);
 		let slash_percent = Perbill::from_percent(5);
-		let initial_exposure = Staking::eras_stakers(active_era(), 11);
+		let initial_exposure = EraInfo::<Test>::get_full_exposure(active_era(), &11);
 		// 101 is a nominator for 11
 		assert_eq!(initial_exposure.others.first().unwrap().who, 101);
 
@@ -977,7 +977,7 @@ fn cannot_transfer_staked_balance() {
 		// Confirm account 11 has some free balance
 		assert_eq!(Balances::free_balance(11), 1000);
 		// Confirm account 11 (via controller) is totally staked
-		assert_eq!(Staking::eras_stakers(active_era(), 11).total, 1000);
+		assert_eq!(EraInfo::<Test>::get_full_exposure(active_era(), &11).total, 1000);
 		// Confirm account 11 cannot transfer as a result
 		assert_noop!(
 			Balances::transfer_allow_death(RuntimeOrigin::signed(11), 21, 1),
@@ -1002,7 +1002,7 @@ fn cannot_transfer_staked_balance_2() {
 		// Confirm account 21 has some free balance
 		assert_eq!(Balances::free_balance(21), 2000);
 		// Confirm account 21 (via controller) is totally staked
-		assert_eq!(Staking::eras_stakers(active_era(), 21).total, 1000);
+		assert_eq!(EraInfo::<Test>::get_full_exposure(active_era(), &21).total, 1000);
 		// Confirm account 21 can transfer at most 1000
 		assert_noop!(
 			Balances::transfer_allow_death(RuntimeOrigin::signed(21), 21, 1001),
@@ -1021,7 +1021,7 @@ fn cannot_reserve_staked_balance() {
 		// Confirm account 11 has some free balance
 		assert_eq!(Balances::free_balance(11), 1000);
 		// Confirm account 11 (via controller 10) is totally staked
-		assert_eq!(Staking::eras_stakers(active_era(), 11).own, 1000);
+		assert_eq!(EraInfo::<Test>::get_full_exposure(active_era(), &11).own, 1000);
 		// Confirm account 11 cannot reserve as a result
 		assert_noop!(Balances::reserve(&11, 1), BalancesError::<Test, _>::LiquidityRestrictions);
 
@@ -1050,7 +1050,7 @@ fn reward_destination_works() {
 				total: 1000,
 				active: 1000,
 				unlocking: Default::default(),
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
 
@@ -1073,9 +1073,10 @@ fn reward_destination_works() {
 				total: 1000 + total_payout_0,
 				active: 1000 + total_payout_0,
 				unlocking: Default::default(),
-				claimed_rewards: bounded_vec![0],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
+		assert_eq!(Staking::claimed_rewards(0, &11), vec![0]);
 
 		// Change RewardDestination to Stash
 		<Payee<Test>>::insert(&11, RewardDestination::Stash);
@@ -1099,9 +1100,10 @@ fn reward_destination_works() {
 				total: 1000 + total_payout_0,
 				active: 1000 + total_payout_0,
 				unlocking: Default::default(),
-				claimed_rewards: bounded_vec![0, 1],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
+		assert_eq!(Staking::claimed_rewards(1, &11), vec![0]);
 
 		// Change RewardDestination to Controller
 		<Payee<Test>>::insert(&11, RewardDestination::Controller);
@@ -1128,9 +1130,10 @@ fn reward_destination_works() {
 				total: 1000 + total_payout_0,
 				active: 1000 + total_payout_0,
 				unlocking: Default::default(),
-				claimed_rewards: bounded_vec![0, 1, 2],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
+		assert_eq!(Staking::claimed_rewards(2, &11), vec![0]);
 	});
 }
 
@@ -1155,7 +1158,7 @@ fn validator_payment_prefs_work() {
 
 		// Compute total payout now for whole duration as other parameter won't change
 		let total_payout_1 = current_total_payout_for_duration(reward_time_per_era());
-		let exposure_1 = Staking::eras_stakers(active_era(), 11);
+		let exposure_1 = EraInfo::<Test>::get_full_exposure(active_era(), &11);
 		Pallet::<Test>::reward_by_ids(vec![(11, 1)]);
 
 		mock::start_active_era(2);
@@ -1188,7 +1191,7 @@ fn bond_extra_works() {
 				total: 1000,
 				active: 1000,
 				unlocking: Default::default(),
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
 
@@ -1205,7 +1208,7 @@ fn bond_extra_works() {
 				total: 1000 + 100,
 				active: 1000 + 100,
 				unlocking: Default::default(),
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
 
@@ -1219,7 +1222,7 @@ fn bond_extra_works() {
 				total: 1000000,
 				active: 1000000,
 				unlocking: Default::default(),
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
 	});
@@ -1257,11 +1260,11 @@ fn bond_extra_and_withdraw_unbonded_works() {
 				total: 1000,
 				active: 1000,
 				unlocking: Default::default(),
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
 		assert_eq!(
-			Staking::eras_stakers(active_era(), 11),
+			EraInfo::<Test>::get_full_exposure(active_era(), &11),
 			Exposure { total: 1000, own: 1000, others: vec![] }
 		);
 
@@ -1275,12 +1278,12 @@ fn bond_extra_and_withdraw_unbonded_works() {
 				total: 1000 + 100,
 				active: 1000 + 100,
 				unlocking: Default::default(),
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
 		// Exposure is a snapshot! only updated after the next era update.
 		assert_ne!(
-			Staking::eras_stakers(active_era(), 11),
+			EraInfo::<Test>::get_full_exposure(active_era(), &11),
 			Exposure { total: 1000 + 100, own: 1000 + 100, others: vec![] }
 		);
 
@@ -1296,12 +1299,12 @@ fn bond_extra_and_withdraw_unbonded_works() {
 				total: 1000 + 100,
 				active: 1000 + 100,
 				unlocking: Default::default(),
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
 		// Exposure is now updated.
 		assert_eq!(
-			Staking::eras_stakers(active_era(), 11),
+			EraInfo::<Test>::get_full_exposure(active_era(), &11),
 			Exposure { total: 1000 + 100, own: 1000 + 100, others: vec![] }
 		);
 
@@ -1314,7 +1317,7 @@ fn bond_extra_and_withdraw_unbonded_works() {
 				total: 1000 + 100,
 				active: 100,
 				unlocking: bounded_vec![UnlockChunk { value: 1000, era: 2 + 3 }],
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			}),
 		);
 
@@ -1327,7 +1330,7 @@ fn bond_extra_and_withdraw_unbonded_works() {
 				total: 1000 + 100,
 				active: 100,
 				unlocking: bounded_vec![UnlockChunk { value: 1000, era: 2 + 3 }],
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			}),
 		);
 
@@ -1343,7 +1346,7 @@ fn bond_extra_and_withdraw_unbonded_works() {
 				total: 1000 + 100,
 				active: 100,
 				unlocking: bounded_vec![UnlockChunk { value: 1000, era: 2 + 3 }],
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			}),
 		);
 
@@ -1359,7 +1362,7 @@ fn bond_extra_and_withdraw_unbonded_works() {
 				total: 100,
 				active: 100,
 				unlocking: Default::default(),
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			}),
 		);
 	})
@@ -1462,7 +1465,7 @@ fn rebond_works() {
 				total: 1000,
 				active: 1000,
 				unlocking: Default::default(),
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
 
@@ -1481,7 +1484,7 @@ fn rebond_works() {
 				total: 1000,
 				active: 100,
 				unlocking: bounded_vec![UnlockChunk { value: 900, era: 2 + 3 }],
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
 
@@ -1494,7 +1497,7 @@ fn rebond_works() {
 				total: 1000,
 				active: 1000,
 				unlocking: Default::default(),
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
 
@@ -1507,7 +1510,7 @@ fn rebond_works() {
 				total: 1000,
 				active: 100,
 				unlocking: bounded_vec![UnlockChunk { value: 900, era: 5 }],
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
 
@@ -1520,7 +1523,7 @@ fn rebond_works() {
 				total: 1000,
 				active: 600,
 				unlocking: bounded_vec![UnlockChunk { value: 400, era: 5 }],
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
 
@@ -1533,7 +1536,7 @@ fn rebond_works() {
 				total: 1000,
 				active: 1000,
 				unlocking: Default::default(),
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
 
@@ -1548,7 +1551,7 @@ fn rebond_works() {
 				total: 1000,
 				active: 100,
 				unlocking: bounded_vec![UnlockChunk { value: 900, era: 5 }],
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
 
@@ -1561,7 +1564,7 @@ fn rebond_works() {
 				total: 1000,
 				active: 600,
 				unlocking: bounded_vec![UnlockChunk { value: 400, era: 5 }],
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
 	})
@@ -1588,7 +1591,7 @@ fn rebond_is_fifo() {
 				total: 1000,
 				active: 1000,
 				unlocking: Default::default(),
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
 
@@ -1603,7 +1606,7 @@ fn rebond_is_fifo() {
 				total: 1000,
 				active: 600,
 				unlocking: bounded_vec![UnlockChunk { value: 400, era: 2 + 3 }],
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
 
@@ -1621,7 +1624,7 @@ fn rebond_is_fifo() {
 					UnlockChunk { value: 400, era: 2 + 3 },
 					UnlockChunk { value: 300, era: 3 + 3 },
 				],
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
 
@@ -1640,7 +1643,7 @@ fn rebond_is_fifo() {
 					UnlockChunk { value: 300, era: 3 + 3 },
 					UnlockChunk { value: 200, era: 4 + 3 },
 				],
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
 
@@ -1656,7 +1659,7 @@ fn rebond_is_fifo() {
 					UnlockChunk { value: 400, era: 2 + 3 },
 					UnlockChunk { value: 100, era: 3 + 3 },
 				],
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
 	})
@@ -1685,7 +1688,7 @@ fn rebond_emits_right_value_in_event() {
 				total: 1000,
 				active: 100,
 				unlocking: bounded_vec![UnlockChunk { value: 900, era: 1 + 3 }],
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
 
@@ -1698,7 +1701,7 @@ fn rebond_emits_right_value_in_event() {
 				total: 1000,
 				active: 200,
 				unlocking: bounded_vec![UnlockChunk { value: 800, era: 1 + 3 }],
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
 		// Event emitted should be correct
@@ -1713,7 +1716,7 @@ fn rebond_emits_right_value_in_event() {
 				total: 1000,
 				active: 1000,
 				unlocking: Default::default(),
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
 		// Event emitted should be correct, only 800
@@ -1733,15 +1736,15 @@ fn reward_to_stake_works() {
 			// Confirm account 10 and 20 are validators
 			assert!(<Validators<Test>>::contains_key(&11) && <Validators<Test>>::contains_key(&21));
 
-			assert_eq!(Staking::eras_stakers(active_era(), 11).total, 1000);
-			assert_eq!(Staking::eras_stakers(active_era(), 21).total, 2000);
+			assert_eq!(EraInfo::<Test>::get_full_exposure(active_era(), &11).total, 1000);
+			assert_eq!(EraInfo::<Test>::get_full_exposure(active_era(), &21).total, 2000);
 
 			// Give the man some money.
 			let _ = Balances::make_free_balance_be(&10, 1000);
 			let _ = Balances::make_free_balance_be(&20, 1000);
 
 			// Bypass logic and change current exposure
-			ErasStakers::<Test>::insert(0, 21, Exposure { total: 69, own: 69, others: vec![] });
+			EraInfo::<Test>::set_exposure(0, &21, Exposure { total: 69, own: 69, others: vec![] });
 			<Ledger<Test>>::insert(
 				&20,
 				StakingLedger {
@@ -1749,7 +1752,7 @@ fn reward_to_stake_works() {
 					total: 69,
 					active: 69,
 					unlocking: Default::default(),
-					claimed_rewards: bounded_vec![],
+					legacy_claimed_rewards: bounded_vec![],
 				},
 			);
 
@@ -1762,8 +1765,8 @@ fn reward_to_stake_works() {
 			mock::start_active_era(1);
 			mock::make_all_reward_payment(0);
 
-			assert_eq!(Staking::eras_stakers(active_era(), 11).total, 1000);
-			assert_eq!(Staking::eras_stakers(active_era(), 21).total, 2000);
+			assert_eq!(EraInfo::<Test>::get_full_exposure(active_era(), &11).total, 1000);
+			assert_eq!(EraInfo::<Test>::get_full_exposure(active_era(), &21).total, 2000);
 
 			let _11_balance = Balances::free_balance(&11);
 			let _21_balance = Balances::free_balance(&21);
@@ -1775,8 +1778,8 @@ fn reward_to_stake_works() {
 			mock::start_active_era(2);
 
 			// -- new infos
-			assert_eq!(Staking::eras_stakers(active_era(), 11).total, _11_balance);
-			assert_eq!(Staking::eras_stakers(active_era(), 21).total, _21_balance);
+			assert_eq!(EraInfo::<Test>::get_full_exposure(active_era(), &11).total, _11_balance);
+			assert_eq!(EraInfo::<Test>::get_full_exposure(active_era(), &21).total, _21_balance);
 		});
 }
 
@@ -1810,7 +1813,7 @@ fn reap_stash_works() {
 					total: 5,
 					active: 5,
 					unlocking: Default::default(),
-					claimed_rewards: bounded_vec![],
+					legacy_claimed_rewards: bounded_vec![],
 				},
 			);
 
@@ -1906,8 +1909,14 @@ fn wrong_vote_is_moot() {
 			assert_eq_uvec!(validator_controllers(), vec![21, 11]);
 
 			// our new voter is taken into account
-			assert!(Staking::eras_stakers(active_era(), 11).others.iter().any(|i| i.who == 61));
-			assert!(Staking::eras_stakers(active_era(), 21).others.iter().any(|i| i.who == 61));
+			assert!(EraInfo::<Test>::get_full_exposure(active_era(), &11)
+				.others
+				.iter()
+				.any(|i| i.who == 61));
+			assert!(EraInfo::<Test>::get_full_exposure(active_era(), &21)
+				.others
+				.iter()
+				.any(|i| i.who == 61));
 		});
 }
 
@@ -1940,7 +1949,7 @@ fn bond_with_no_staked_value() {
 					active: 0,
 					total: 5,
 					unlocking: bounded_vec![UnlockChunk { value: 5, era: 3 }],
-					claimed_rewards: bounded_vec![],
+					legacy_claimed_rewards: bounded_vec![],
 				})
 			);
 
@@ -1996,7 +2005,7 @@ fn bond_with_little_staked_value_bounded() {
 
 			// 2 is elected.
 			assert_eq_uvec!(validator_controllers(), vec![21, 11, 1]);
-			assert_eq!(Staking::eras_stakers(active_era(), 2).total, 0);
+			assert_eq!(EraInfo::<Test>::get_full_exposure(active_era(), &2).total, 0);
 
 			// Old ones are rewarded.
 			assert_eq_error_rate!(
@@ -2014,7 +2023,7 @@ fn bond_with_little_staked_value_bounded() {
 			mock::make_all_reward_payment(1);
 
 			assert_eq_uvec!(validator_controllers(), vec![21, 11, 1]);
-			assert_eq!(Staking::eras_stakers(active_era(), 2).total, 0);
+			assert_eq!(EraInfo::<Test>::get_full_exposure(active_era(), &2).total, 0);
 
 			// 2 is now rewarded.
 			assert_eq_error_rate!(
@@ -2165,8 +2174,14 @@ fn phragmen_should_not_overflow() {
 		assert_eq_uvec!(validator_controllers(), vec![3, 5]);
 
 		// We can safely convert back to values within [u64, u128].
-		assert!(Staking::eras_stakers(active_era(), 3).total > Votes::max_value() as Balance);
-		assert!(Staking::eras_stakers(active_era(), 5).total > Votes::max_value() as Balance);
+		assert!(
+			EraInfo::<Test>::get_full_exposure(active_era(), &3).total
+				> Votes::max_value() as Balance
+		);
+		assert!(
+			EraInfo::<Test>::get_full_exposure(active_era(), &5).total
+				> Votes::max_value() as Balance
+		);
 	})
 }
 
@@ -2190,8 +2205,7 @@ fn reward_validator_slashing_validator_does_not_overflow() {
 
 		// Check reward
 		ErasRewardPoints::<Test>::insert(0, reward);
-		ErasStakers::<Test>::insert(0, 11, &exposure);
-		ErasStakersClipped::<Test>::insert(0, 11, exposure);
+		EraInfo::<Test>::set_exposure(0, &11, exposure);
 		ErasValidatorReward::<Test>::insert(0, stake);
 		assert_ok!(Staking::payout_stakers(RuntimeOrigin::signed(1337), 11, 0));
 		assert_eq!(Balances::total_balance(&11), stake * 2);
@@ -2203,9 +2217,9 @@ fn reward_validator_slashing_validator_does_not_overflow() {
 		// only slashes out of bonded stake are applied. without this line, it is 0.
 		Staking::bond(RuntimeOrigin::signed(2), stake - 1, RewardDestination::default()).unwrap();
 		// Override exposure of 11
-		ErasStakers::<Test>::insert(
+		EraInfo::<Test>::set_exposure(
 			0,
-			11,
+			&11,
 			Exposure {
 				total: stake,
 				own: 1,
@@ -2216,7 +2230,7 @@ fn reward_validator_slashing_validator_does_not_overflow() {
 		// Check slashing
 		on_offence_now(
 			&[OffenceDetails {
-				offender: (11, Staking::eras_stakers(active_era(), 11)),
+				offender: (11, EraInfo::<Test>::get_full_exposure(active_era(), &11)),
 				reporters: vec![],
 			}],
 			&[Perbill::from_percent(100)],
@@ -2315,7 +2329,7 @@ fn offence_forces_new_era() {
 	ExtBuilder::default().build_and_execute(|| {
 		on_offence_now(
 			&[OffenceDetails {
-				offender: (11, Staking::eras_stakers(active_era(), 11)),
+				offender: (11, EraInfo::<Test>::get_full_exposure(active_era(), &11)),
 				reporters: vec![],
 			}],
 			&[Perbill::from_percent(5)],
@@ -2333,7 +2347,7 @@ fn offence_ensures_new_era_without_clobbering() {
 
 		on_offence_now(
 			&[OffenceDetails {
-				offender: (11, Staking::eras_stakers(active_era(), 11)),
+				offender: (11, EraInfo::<Test>::get_full_exposure(active_era(), &11)),
 				reporters: vec![],
 			}],
 			&[Perbill::from_percent(5)],
@@ -2351,7 +2365,7 @@ fn offence_deselects_validator_even_when_slash_is_zero() {
 
 		on_offence_now(
 			&[OffenceDetails {
-				offender: (11, Staking::eras_stakers(active_era(), 11)),
+				offender: (11, EraInfo::<Test>::get_full_exposure(active_era(), &11)),
 				reporters: vec![],
 			}],
 			&[Perbill::from_percent(0)],
@@ -2372,7 +2386,7 @@ fn slashing_performed_according_exposure() {
 	// This test checks that slashing is performed according the exposure (or more precisely,
 	// historical exposure), not the current balance.
 	ExtBuilder::default().build_and_execute(|| {
-		assert_eq!(Staking::eras_stakers(active_era(), 11).own, 1000);
+		assert_eq!(EraInfo::<Test>::get_full_exposure(active_era(), &11).own, 1000);
 
 		// Handle an offence with a historical exposure.
 		on_offence_now(
@@ -2398,7 +2412,7 @@ fn slash_in_old_span_does_not_deselect() {
 
 		on_offence_now(
 			&[OffenceDetails {
-				offender: (11, Staking::eras_stakers(active_era(), 11)),
+				offender: (11, EraInfo::<Test>::get_full_exposure(active_era(), &11)),
 				reporters: vec![],
 			}],
 			&[Perbill::from_percent(0)],
@@ -2421,7 +2435,7 @@ fn slash_in_old_span_does_not_deselect() {
 
 		on_offence_in_era(
 			&[OffenceDetails {
-				offender: (11, Staking::eras_stakers(active_era(), 11)),
+				offender: (11, EraInfo::<Test>::get_full_exposure(active_era(), &11)),
 				reporters: vec![],
 			}],
 			&[Perbill::from_percent(0)],
@@ -2437,7 +2451,7 @@ fn slash_in_old_span_does_not_deselect() {
 
 		on_offence_in_era(
 			&[OffenceDetails {
-				offender: (11, Staking::eras_stakers(active_era(), 11)),
+				offender: (11, EraInfo::<Test>::get_full_exposure(active_era(), &11)),
 				reporters: vec![],
 			}],
 			// NOTE: A 100% slash here would clean up the account, causing de-registration.
@@ -2464,11 +2478,11 @@ fn reporters_receive_their_slice() {
 		// The reporters' reward is calculated from the total exposure.
 		let initial_balance = 1125;
 
-		assert_eq!(Staking::eras_stakers(active_era(), 11).total, initial_balance);
+		assert_eq!(EraInfo::<Test>::get_full_exposure(active_era(), &11).total, initial_balance);
 
 		on_offence_now(
 			&[OffenceDetails {
-				offender: (11, Staking::eras_stakers(active_era(), 11)),
+				offender: (11, EraInfo::<Test>::get_full_exposure(active_era(), &11)),
 				reporters: vec![1, 2],
 			}],
 			&[Perbill::from_percent(50)],
@@ -2491,11 +2505,11 @@ fn subsequent_reports_in_same_span_pay_out_less() {
 		// The reporters' reward is calculated from the total exposure.
 		let initial_balance = 1125;
 
-		assert_eq!(Staking::eras_stakers(active_era(), 11).total, initial_balance);
+		assert_eq!(EraInfo::<Test>::get_full_exposure(active_era(), &11).total, initial_balance);
 
 		on_offence_now(
 			&[OffenceDetails {
-				offender: (11, Staking::eras_stakers(active_era(), 11)),
+				offender: (11, EraInfo::<Test>::get_full_exposure(active_era(), &11)),
 				reporters: vec![1],
 			}],
 			&[Perbill::from_percent(20)],
@@ -2508,7 +2522,7 @@ fn subsequent_reports_in_same_span_pay_out_less() {
 
 		on_offence_now(
 			&[OffenceDetails {
-				offender: (11, Staking::eras_stakers(active_era(), 11)),
+				offender: (11, EraInfo::<Test>::get_full_exposure(active_era(), &11)),
 				reporters: vec![1],
 			}],
 			&[Perbill::from_percent(50)],
@@ -2530,7 +2544,7 @@ fn invulnerables_are_not_slashed() {
 		assert_eq!(Balances::free_balance(11), 1000);
 		assert_eq!(Balances::free_balance(21), 2000);
 
-		let exposure = Staking::eras_stakers(active_era(), 21);
+		let exposure = EraInfo::<Test>::get_full_exposure(active_era(), &21);
 		let initial_balance = Staking::slashable_balance_of(&21);
 
 		let nominator_balances: Vec<_> =
@@ -2539,11 +2553,11 @@ fn invulnerables_are_not_slashed() {
 		on_offence_now(
 			&[
 				OffenceDetails {
-					offender: (11, Staking::eras_stakers(active_era(), 11)),
+					offender: (11, EraInfo::<Test>::get_full_exposure(active_era(), &11)),
 					reporters: vec![],
 				},
 				OffenceDetails {
-					offender: (21, Staking::eras_stakers(active_era(), 21)),
+					offender: (21, EraInfo::<Test>::get_full_exposure(active_era(), &21)),
 					reporters: vec![],
 				},
 			],
@@ -2573,7 +2587,7 @@ fn dont_slash_if_fraction_is_zero() {
 
 		on_offence_now(
 			&[OffenceDetails {
-				offender: (11, Staking::eras_stakers(active_era(), 11)),
+				offender: (11, EraInfo::<Test>::get_full_exposure(active_era(), &11)),
 				reporters: vec![],
 			}],
 			&[Perbill::from_percent(0)],
@@ -2594,7 +2608,7 @@ fn only_slash_for_max_in_era() {
 
 		on_offence_now(
 			&[OffenceDetails {
-				offender: (11, Staking::eras_stakers(active_era(), 11)),
+				offender: (11, EraInfo::<Test>::get_full_exposure(active_era(), &11)),
 				reporters: vec![],
 			}],
 			&[Perbill::from_percent(50)],
@@ -2606,7 +2620,7 @@ fn only_slash_for_max_in_era() {
 
 		on_offence_now(
 			&[OffenceDetails {
-				offender: (11, Staking::eras_stakers(active_era(), 11)),
+				offender: (11, EraInfo::<Test>::get_full_exposure(active_era(), &11)),
 				reporters: vec![],
 			}],
 			&[Perbill::from_percent(25)],
@@ -2617,7 +2631,7 @@ fn only_slash_for_max_in_era() {
 
 		on_offence_now(
 			&[OffenceDetails {
-				offender: (11, Staking::eras_stakers(active_era(), 11)),
+				offender: (11, EraInfo::<Test>::get_full_exposure(active_era(), &11)),
 				reporters: vec![],
 			}],
 			&[Perbill::from_percent(60)],
@@ -2639,7 +2653,7 @@ fn garbage_collection_after_slashing() {
 
 			on_offence_now(
 				&[OffenceDetails {
-					offender: (11, Staking::eras_stakers(active_era(), 11)),
+					offender: (11, EraInfo::<Test>::get_full_exposure(active_era(), &11)),
 					reporters: vec![],
 				}],
 				&[Perbill::from_percent(10)],
@@ -2651,7 +2665,7 @@ fn garbage_collection_after_slashing() {
 
 			on_offence_now(
 				&[OffenceDetails {
-					offender: (11, Staking::eras_stakers(active_era(), 11)),
+					offender: (11, EraInfo::<Test>::get_full_exposure(active_era(), &11)),
 					reporters: vec![],
 				}],
 				&[Perbill::from_percent(100)],
@@ -2688,12 +2702,15 @@ fn garbage_collection_on_window_pruning() {
 		assert_eq!(Balances::free_balance(11), 1000);
 		let now = active_era();
 
-		let exposure = Staking::eras_stakers(now, 11);
+		let exposure = EraInfo::<Test>::get_full_exposure(now, &11);
 		assert_eq!(Balances::free_balance(101), 2000);
 		let nominated_value = exposure.others.iter().find(|o| o.who == 101).unwrap().value;
 
 		on_offence_now(
-			&[OffenceDetails { offender: (11, Staking::eras_stakers(now, 11)), reporters: vec![] }],
+			&[OffenceDetails {
+				offender: (11, EraInfo::<Test>::get_full_exposure(now, &11)),
+				reporters: vec![],
+			}],
 			&[Perbill::from_percent(10)],
 		);
 
@@ -2728,14 +2745,14 @@ fn slashing_nominators_by_span_max() {
 		assert_eq!(Balances::free_balance(101), 2000);
 		assert_eq!(Staking::slashable_balance_of(&21), 1000);
 
-		let exposure_11 = Staking::eras_stakers(active_era(), 11);
-		let exposure_21 = Staking::eras_stakers(active_era(), 21);
+		let exposure_11 = EraInfo::<Test>::get_full_exposure(active_era(), &11);
+		let exposure_21 = EraInfo::<Test>::get_full_exposure(active_era(), &21);
 		let nominated_value_11 = exposure_11.others.iter().find(|o| o.who == 101).unwrap().value;
 		let nominated_value_21 = exposure_21.others.iter().find(|o| o.who == 101).unwrap().value;
 
 		on_offence_in_era(
 			&[OffenceDetails {
-				offender: (11, Staking::eras_stakers(active_era(), 11)),
+				offender: (11, EraInfo::<Test>::get_full_exposure(active_era(), &11)),
 				reporters: vec![],
 			}],
 			&[Perbill::from_percent(10)],
@@ -2762,7 +2779,7 @@ fn slashing_nominators_by_span_max() {
 		// second slash: higher era, higher value, same span.
 		on_offence_in_era(
 			&[OffenceDetails {
-				offender: (21, Staking::eras_stakers(active_era(), 21)),
+				offender: (21, EraInfo::<Test>::get_full_exposure(active_era(), &21)),
 				reporters: vec![],
 			}],
 			&[Perbill::from_percent(30)],
@@ -2784,7 +2801,7 @@ fn slashing_nominators_by_span_max() {
 		// in-era value, but lower slash value than slash 2.
 		on_offence_in_era(
 			&[OffenceDetails {
-				offender: (11, Staking::eras_stakers(active_era(), 11)),
+				offender: (11, EraInfo::<Test>::get_full_exposure(active_era(), &11)),
 				reporters: vec![],
 			}],
 			&[Perbill::from_percent(20)],
@@ -2819,7 +2836,7 @@ fn slashes_are_summed_across_spans() {
 
 		on_offence_now(
 			&[OffenceDetails {
-				offender: (21, Staking::eras_stakers(active_era(), 21)),
+				offender: (21, EraInfo::<Test>::get_full_exposure(active_era(), &21)),
 				reporters: vec![],
 			}],
 			&[Perbill::from_percent(10)],
@@ -2842,7 +2859,7 @@ fn slashes_are_summed_across_spans() {
 
 		on_offence_now(
 			&[OffenceDetails {
-				offender: (21, Staking::eras_stakers(active_era(), 21)),
+				offender: (21, EraInfo::<Test>::get_full_exposure(active_era(), &21)),
 				reporters: vec![],
 			}],
 			&[Perbill::from_percent(10)],
@@ -2866,7 +2883,7 @@ fn deferred_slashes_are_deferred() {
 
 		assert_eq!(Balances::free_balance(11), 1000);
 
-		let exposure = Staking::eras_stakers(active_era(), 11);
+		let exposure = EraInfo::<Test>::get_full_exposure(active_era(), &11);
 		assert_eq!(Balances::free_balance(101), 2000);
 		let nominated_value = exposure.others.iter().find(|o| o.who == 101).unwrap().value;
 
@@ -2874,7 +2891,7 @@ fn deferred_slashes_are_deferred() {
 
 		on_offence_now(
 			&[OffenceDetails {
-				offender: (11, Staking::eras_stakers(active_era(), 11)),
+				offender: (11, EraInfo::<Test>::get_full_exposure(active_era(), &11)),
 				reporters: vec![],
 			}],
 			&[Perbill::from_percent(10)],
@@ -2925,7 +2942,7 @@ fn retroactive_deferred_slashes_two_eras_before() {
 		assert_eq!(BondingDuration::get(), 3);
 
 		mock::start_active_era(1);
-		let exposure_11_at_era1 = Staking::eras_stakers(active_era(), 11);
+		let exposure_11_at_era1 = EraInfo::<Test>::get_full_exposure(active_era(), &11);
 
 		mock::start_active_era(3);
 
@@ -2961,7 +2978,7 @@ fn retroactive_deferred_slashes_one_before() {
 		assert_eq!(BondingDuration::get(), 3);
 
 		mock::start_active_era(1);
-		let exposure_11_at_era1 = Staking::eras_stakers(active_era(), 11);
+		let exposure_11_at_era1 = EraInfo::<Test>::get_full_exposure(active_era(), &11);
 
 		// unbond at slash era.
 		mock::start_active_era(2);
@@ -3009,12 +3026,12 @@ fn staker_cannot_bail_deferred_slash() {
 		assert_eq!(Balances::free_balance(11), 1000);
 		assert_eq!(Balances::free_balance(101), 2000);
 
-		let exposure = Staking::eras_stakers(active_era(), 11);
+		let exposure = EraInfo::<Test>::get_full_exposure(active_era(), &11);
 		let nominated_value = exposure.others.iter().find(|o| o.who == 101).unwrap().value;
 
 		on_offence_now(
 			&[OffenceDetails {
-				offender: (11, Staking::eras_stakers(active_era(), 11)),
+				offender: (11, EraInfo::<Test>::get_full_exposure(active_era(), &11)),
 				reporters: vec![],
 			}],
 			&[Perbill::from_percent(10)],
@@ -3033,7 +3050,7 @@ fn staker_cannot_bail_deferred_slash() {
 				active: 0,
 				total: 500,
 				stash: 101,
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 				unlocking: bounded_vec![UnlockChunk { era: 4u32, value: 500 }],
 			}
 		);
@@ -3083,7 +3100,7 @@ fn remove_deferred() {
 
 		assert_eq!(Balances::free_balance(11), 1000);
 
-		let exposure = Staking::eras_stakers(active_era(), 11);
+		let exposure = EraInfo::<Test>::get_full_exposure(active_era(), &11);
 		assert_eq!(Balances::free_balance(101), 2000);
 		let nominated_value = exposure.others.iter().find(|o| o.who == 101).unwrap().value;
 
@@ -3159,7 +3176,7 @@ fn remove_multi_deferred() {
 
 		assert_eq!(Balances::free_balance(11), 1000);
 
-		let exposure = Staking::eras_stakers(active_era(), 11);
+		let exposure = EraInfo::<Test>::get_full_exposure(active_era(), &11);
 		assert_eq!(Balances::free_balance(101), 2000);
 
 		on_offence_now(
@@ -3169,7 +3186,7 @@ fn remove_multi_deferred() {
 
 		on_offence_now(
 			&[OffenceDetails {
-				offender: (21, Staking::eras_stakers(active_era(), 21)),
+				offender: (21, EraInfo::<Test>::get_full_exposure(active_era(), &21)),
 				reporters: vec![],
 			}],
 			&[Perbill::from_percent(10)],
@@ -3231,8 +3248,8 @@ fn slash_kicks_validators_not_nominators_and_disables_nominator_for_kicked_valid
 		assert!(Staking::nominators(101).unwrap().targets.contains(&11));
 
 		// 11 and 21 both have the support of 100
-		let exposure_11 = Staking::eras_stakers(active_era(), &11);
-		let exposure_21 = Staking::eras_stakers(active_era(), &21);
+		let exposure_11 = EraInfo::<Test>::get_full_exposure(active_era(), &11);
+		let exposure_21 = EraInfo::<Test>::get_full_exposure(active_era(), &21);
 
 		assert_eq!(exposure_11.total, 1000 + 125);
 		assert_eq!(exposure_21.total, 1000 + 375);
@@ -3271,8 +3288,8 @@ fn slash_kicks_validators_not_nominators_and_disables_nominator_for_kicked_valid
 		assert_ok!(Staking::validate(RuntimeOrigin::signed(11), Default::default()));
 
 		mock::start_active_era(2);
-		let exposure_11 = Staking::eras_stakers(active_era(), &11);
-		let exposure_21 = Staking::eras_stakers(active_era(), &21);
+		let exposure_11 = EraInfo::<Test>::get_full_exposure(active_era(), &11);
+		let exposure_21 = EraInfo::<Test>::get_full_exposure(active_era(), &21);
 
 		// 11's own expo is reduced. sum of support from 11 is less (448), which is 500
 		// 900 + 146
@@ -3289,8 +3306,10 @@ fn non_slashable_offence_doesnt_disable_validator() {
 		mock::start_active_era(1);
 		assert_eq_uvec!(Session::validators(), vec![11, 21]);
 
-		let exposure_11 = Staking::eras_stakers(Staking::active_era().unwrap().index, &11);
-		let exposure_21 = Staking::eras_stakers(Staking::active_era().unwrap().index, &21);
+		let exposure_11 =
+			EraInfo::<Test>::get_full_exposure(Staking::active_era().unwrap().index, &11);
+		let exposure_21 =
+			EraInfo::<Test>::get_full_exposure(Staking::active_era().unwrap().index, &21);
 
 		// offence with no slash associated
 		on_offence_now(
@@ -3346,8 +3365,10 @@ fn slashing_independent_of_disabling_validator() {
 		mock::start_active_era(1);
 		assert_eq_uvec!(Session::validators(), vec![11, 21]);
 
-		let exposure_11 = Staking::eras_stakers(Staking::active_era().unwrap().index, &11);
-		let exposure_21 = Staking::eras_stakers(Staking::active_era().unwrap().index, &21);
+		let exposure_11 =
+			EraInfo::<Test>::get_full_exposure(Staking::active_era().unwrap().index, &11);
+		let exposure_21 =
+			EraInfo::<Test>::get_full_exposure(Staking::active_era().unwrap().index, &21);
 
 		let now = Staking::active_era().unwrap().index;
 
@@ -3420,9 +3441,12 @@ fn offence_threshold_triggers_new_era() {
 			// we have 4 validators and an offending validator threshold of 75%,
 			// once the third validator commits an offence a new era should be forced
 
-			let exposure_11 = Staking::eras_stakers(Staking::active_era().unwrap().index, &11);
-			let exposure_21 = Staking::eras_stakers(Staking::active_era().unwrap().index, &21);
-			let exposure_31 = Staking::eras_stakers(Staking::active_era().unwrap().index, &31);
+			let exposure_11 =
+				EraInfo::<Test>::get_full_exposure(Staking::active_era().unwrap().index, &11);
+			let exposure_21 =
+				EraInfo::<Test>::get_full_exposure(Staking::active_era().unwrap().index, &21);
+			let exposure_31 =
+				EraInfo::<Test>::get_full_exposure(Staking::active_era().unwrap().index, &31);
 
 			on_offence_now(
 				&[OffenceDetails { offender: (11, exposure_11.clone()), reporters: vec![] }],
@@ -3457,8 +3481,10 @@ fn disabled_validators_are_kept_disabled_for_whole_era() {
 			assert_eq_uvec!(Session::validators(), vec![11, 21, 31, 41]);
 			assert_eq!(<Test as Config>::SessionsPerEra::get(), 3);
 
-			let exposure_11 = Staking::eras_stakers(Staking::active_era().unwrap().index, &11);
-			let exposure_21 = Staking::eras_stakers(Staking::active_era().unwrap().index, &21);
+			let exposure_11 =
+				EraInfo::<Test>::get_full_exposure(Staking::active_era().unwrap().index, &11);
+			let exposure_21 =
+				EraInfo::<Test>::get_full_exposure(Staking::active_era().unwrap().index, &21);
 
 			on_offence_now(
 				&[OffenceDetails { offender: (11, exposure_11.clone()), reporters: vec![] }],
@@ -3598,7 +3624,7 @@ fn zero_slash_keeps_nominators() {
 
 		assert_eq!(Balances::free_balance(11), 1000);
 
-		let exposure = Staking::eras_stakers(active_era(), 11);
+		let exposure = EraInfo::<Test>::get_full_exposure(active_era(), &11);
 		assert_eq!(Balances::free_balance(101), 2000);
 
 		on_offence_now(
@@ -3677,9 +3703,9 @@ fn six_session_delay() {
 }
 
 #[test]
-fn test_max_nominator_rewarded_per_validator_and_cant_steal_someone_else_reward() {
+fn test_nominators_over_max_exposure_page_size_are_rewarded() {
 	ExtBuilder::default().build_and_execute(|| {
-		for i in 0..=<<Test as Config>::MaxNominatorRewardedPerValidator as Get<_>>::get() {
+		for i in 0..=MaxExposurePageSize::get() {
 			let stash = 10_000 + i as AccountId;
 			let balance = 10_000 + i as Balance;
 			Balances::make_free_balance_be(&stash, balance);
@@ -3697,77 +3723,76 @@ fn test_max_nominator_rewarded_per_validator_and_cant_steal_someone_else_reward(
 		let _ = current_total_payout_for_duration(reward_time_per_era());
 
 		mock::start_active_era(2);
+		// the nominators of 11 don't fit in a single page.
+		assert_eq!(EraInfo::<Test>::get_page_count(1, &11), 2);
 		mock::make_all_reward_payment(1);
 
-		// Assert only nominators from 1 to Max are rewarded
-		for i in 0..=<<Test as Config>::MaxNominatorRewardedPerValidator as Get<_>>::get() {
+		// Assert all nominators are rewarded
+		for i in 0..=MaxExposurePageSize::get() {
 			let stash = 10_000 + i as AccountId;
 			let balance = 10_000 + i as Balance;
-			if stash == 10_000 {
-				assert!(Balances::free_balance(&stash) == balance);
-			} else {
-				assert!(Balances::free_balance(&stash) > balance);
-			}
+			assert!(Balances::free_balance(&stash) > balance);
 		}
 	});
 }
 
 #[test]
 fn test_payout_stakers() {
-	// Test that payout_stakers work in general, including that only the top
-	// `T::MaxNominatorRewardedPerValidator` nominators are rewarded.
+	// Test that payout_stakers work in general, including that all the nominators are rewarded
+	// page by page.
 	ExtBuilder::default().has_stakers(false).build_and_execute(|| {
 		let balance = 1000;
-		// Track the exposure of the validator and all nominators.
-		let mut total_exposure = balance;
-		// Track the exposure of the validator and the nominators that will get paid out.
-		let mut payout_exposure = balance;
 		// Create a validator:
 		bond_validator(11, balance); // Default(64)
 		assert_eq!(Validators::<Test>::count(), 1);
 
 		// Create nominators, targeting stash of validators
 		for i in 0..100 {
-			let bond_amount = balance + i as Balance;
-			bond_nominator(1000 + i, bond_amount, vec![11]);
-			total_exposure += bond_amount;
-			if i >= 36 {
-				payout_exposure += bond_amount;
-			};
+			bond_nominator(1000 + i, balance + i as Balance, vec![11]);
 		}
-		let payout_exposure_part = Perbill::from_rational(payout_exposure, total_exposure);
 
 		mock::start_active_era(1);
 		Staking::reward_by_ids(vec![(11, 1)]);
 
 		// compute and ensure the reward amount is greater than zero.
 		let payout = current_total_payout_for_duration(reward_time_per_era());
-		let actual_paid_out = payout_exposure_part * payout;
 
 		mock::start_active_era(2);
 
+		// 100 nominators are split in a page of 64 and a page of 36.
+		assert_eq!(EraInfo::<Test>::get_page_count(1, &11), 2);
+		assert_eq!(ErasStakersPaged::<Test>::get((1, 11, 0)).unwrap().others.len(), 64);
+		assert_eq!(ErasStakersPaged::<Test>::get((1, 11, 1)).unwrap().others.len(), 36);
+
 		let pre_payout_total_issuance = Balances::total_issuance();
 		RewardOnUnbalanceWasCalled::set(false);
 		assert_ok!(Staking::payout_stakers(RuntimeOrigin::signed(1337), 11, 1));
-		assert_eq_error_rate!(
-			Balances::total_issuance(),
-			pre_payout_total_issuance + actual_paid_out,
-			1
-		);
 		assert!(RewardOnUnbalanceWasCalled::get());
 
-		// Top 64 nominators of validator 11 automatically paid out, including the validator
-		// Validator payout goes to controller.
+		// The validator and the nominators of the first page are paid out.
 		assert!(Balances::free_balance(&11) > balance);
-		for i in 36..100 {
-			assert!(Balances::free_balance(&(1000 + i)) > balance + i as Balance);
+		let first_page = ErasStakersPaged::<Test>::get((1, 11, 0)).unwrap().others;
+		for i in first_page.iter() {
+			assert!(Balances::free_balance(&i.who) > i.value);
 		}
-		// The bottom 36 do not
-		for i in 0..36 {
-			assert_eq!(Balances::free_balance(&(1000 + i)), balance + i as Balance);
+		let second_page = ErasStakersPaged::<Test>::get((1, 11, 1)).unwrap().others;
+		for i in second_page.iter() {
+			assert_eq!(Balances::free_balance(&i.who), i.value);
 		}
+		assert_eq!(Staking::claimed_rewards(1, &11), vec![0]);
 
-		// We track rewards in `claimed_rewards` vec
+		// The second call pays out the next page.
+		let validator_balance = Balances::free_balance(&11);
+		assert_ok!(Staking::payout_stakers(RuntimeOrigin::signed(1337), 11, 1));
+		// The validator is only paid with the first page.
+		assert_eq!(Balances::free_balance(&11), validator_balance);
+		for i in second_page.iter() {
+			assert!(Balances::free_balance(&i.who) > i.value);
+		}
+		assert_eq_error_rate!(Balances::total_issuance(), pre_payout_total_issuance + payout, 2);
+
+		// We track claimed pages in `ClaimedRewards`, not in the ledger.
+		assert_eq!(Staking::claimed_rewards(1, &11), vec![0, 1]);
 		assert_eq!(
 			Staking::ledger(&11),
 			Some(StakingLedger {
@@ -3775,7 +3800,7 @@ fn test_payout_stakers() {
 				total: 1000,
 				active: 1000,
 				unlocking: Default::default(),
-				claimed_rewards: bounded_vec![1]
+				legacy_claimed_rewards: bounded_vec![]
 			})
 		);
 
@@ -3784,32 +3809,21 @@ fn test_payout_stakers() {
 
 			// compute and ensure the reward amount is greater than zero.
 			let payout = current_total_payout_for_duration(reward_time_per_era());
-			let actual_paid_out = payout_exposure_part * payout;
 			let pre_payout_total_issuance = Balances::total_issuance();
 
 			mock::start_active_era(i);
 			RewardOnUnbalanceWasCalled::set(false);
 			assert_ok!(Staking::payout_stakers(RuntimeOrigin::signed(1337), 11, i - 1));
+			assert_ok!(Staking::payout_stakers(RuntimeOrigin::signed(1337), 11, i - 1));
 			assert_eq_error_rate!(
 				Balances::total_issuance(),
-				pre_payout_total_issuance + actual_paid_out,
-				1
+				pre_payout_total_issuance + payout,
+				2
 			);
 			assert!(RewardOnUnbalanceWasCalled::get());
+			assert_eq!(Staking::claimed_rewards(i - 1, &11), vec![0, 1]);
 		}
 
-		// We track rewards in `claimed_rewards` vec
-		assert_eq!(
-			Staking::ledger(&11),
-			Some(StakingLedger {
-				stash: 11,
-				total: 1000,
-				active: 1000,
-				unlocking: Default::default(),
-				claimed_rewards: (1..=14).collect::<Vec<_>>().try_into().unwrap()
-			})
-		);
-
 		let last_era = 99;
 		let history_depth = HistoryDepth::get();
 		let expected_last_reward_era = last_era - 1;
@@ -3822,6 +3836,12 @@ fn test_payout_stakers() {
 		}
 
 		// We clean it up as history passes
+		for era in 1..expected_start_reward_era {
+			assert!(Staking::claimed_rewards(era, &11).is_empty());
+			assert!(ErasStakersOverview::<Test>::get(era, &11).is_none());
+			assert!(ErasStakersPaged::<Test>::iter_prefix_values((era,)).next().is_none());
+		}
+
 		assert_ok!(Staking::payout_stakers(
 			RuntimeOrigin::signed(1337),
 			11,
@@ -3832,37 +3852,21 @@ fn test_payout_stakers() {
 			11,
 			expected_last_reward_era
 		));
-		assert_eq!(
-			Staking::ledger(&11),
-			Some(StakingLedger {
-				stash: 11,
-				total: 1000,
-				active: 1000,
-				unlocking: Default::default(),
-				claimed_rewards: bounded_vec![expected_start_reward_era, expected_last_reward_era]
-			})
-		);
+		assert_eq!(Staking::claimed_rewards(expected_start_reward_era, &11), vec![0]);
+		assert_eq!(Staking::claimed_rewards(expected_last_reward_era, &11), vec![0]);
 
 		// Out of order claims works.
+		assert_ok!(Staking::payout_stakers_by_page(RuntimeOrigin::signed(1337), 11, 69, 1));
+		assert_ok!(Staking::payout_stakers_by_page(RuntimeOrigin::signed(1337), 11, 23, 1));
+		assert_ok!(Staking::payout_stakers_by_page(RuntimeOrigin::signed(1337), 11, 42, 0));
+		assert_ok!(Staking::payout_stakers_by_page(RuntimeOrigin::signed(1337), 11, 23, 0));
+		assert_eq!(Staking::claimed_rewards(69, &11), vec![1]);
+		assert_eq!(Staking::claimed_rewards(23, &11), vec![0, 1]);
+		assert_eq!(Staking::claimed_rewards(42, &11), vec![0]);
+
+		// `payout_stakers` pays the next unclaimed page.
 		assert_ok!(Staking::payout_stakers(RuntimeOrigin::signed(1337), 11, 69));
-		assert_ok!(Staking::payout_stakers(RuntimeOrigin::signed(1337), 11, 23));
-		assert_ok!(Staking::payout_stakers(RuntimeOrigin::signed(1337), 11, 42));
-		assert_eq!(
-			Staking::ledger(&11),
-			Some(StakingLedger {
-				stash: 11,
-				total: 1000,
-				active: 1000,
-				unlocking: Default::default(),
-				claimed_rewards: bounded_vec![
-					expected_start_reward_era,
-					23,
-					42,
-					69,
-					expected_last_reward_era
-				]
-			})
-		);
+		assert_eq!(Staking::claimed_rewards(69, &11), vec![0, 1]);
 	});
 }
 
@@ -3900,6 +3904,11 @@ fn payout_stakers_handles_basic_errors() {
 			Staking::payout_stakers(RuntimeOrigin::signed(1337), 10, 1),
 			Error::<Test>::NotStash.with_weight(err_weight)
 		);
+		// Wrong Page
+		assert_noop!(
+			Staking::payout_stakers_by_page(RuntimeOrigin::signed(1337), 11, 1, 2),
+			Error::<Test>::InvalidPage.with_weight(err_weight)
+		);
 
 		let last_era = 99;
 		for i in 3..=last_era {
@@ -3924,6 +3933,31 @@ fn payout_stakers_handles_basic_errors() {
 			Staking::payout_stakers(RuntimeOrigin::signed(1337), 11, expected_last_reward_era + 1),
 			Error::<Test>::InvalidEraToReward.with_weight(err_weight)
 		);
+		assert_ok!(Staking::payout_stakers_by_page(
+			RuntimeOrigin::signed(1337),
+			11,
+			expected_start_reward_era,
+			0
+		));
+		assert_ok!(Staking::payout_stakers_by_page(
+			RuntimeOrigin::signed(1337),
+			11,
+			expected_last_reward_era,
+			0
+		));
+
+		// Can't claim the same page again
+		assert_noop!(
+			Staking::payout_stakers_by_page(
+				RuntimeOrigin::signed(1337),
+				11,
+				expected_start_reward_era,
+				0
+			),
+			Error::<Test>::AlreadyClaimed.with_weight(err_weight)
+		);
+
+		// Claim the remaining pages
 		assert_ok!(Staking::payout_stakers(
 			RuntimeOrigin::signed(1337),
 			11,
@@ -3944,6 +3978,164 @@ fn payout_stakers_handles_basic_errors() {
 			Staking::payout_stakers(RuntimeOrigin::signed(1337), 11, expected_last_reward_era),
 			Error::<Test>::AlreadyClaimed.with_weight(err_weight)
 		);
+		assert_noop!(
+			Staking::payout_stakers_by_page(
+				RuntimeOrigin::signed(1337),
+				11,
+				expected_last_reward_era,
+				1
+			),
+			Error::<Test>::AlreadyClaimed.with_weight(err_weight)
+		);
+	});
+}
+
+#[test]
+fn payout_stakers_handles_legacy_exposures() {
+	// Eras stored before the paged exposures are paid out as a single page of the clipped
+	// exposure, and the legacy claimed rewards of the ledger are still honoured.
+	ExtBuilder::default().has_stakers(false).build_and_execute(|| {
+		let err_weight = <Test as Config>::WeightInfo::payout_stakers_alive_staked(0);
+		let balance = 1000;
+		bond_validator(11, balance);
+		bond_nominator(101, balance, vec![11]);
+		bond_nominator(102, balance, vec![11]);
+
+		mock::start_active_era(1);
+		Staking::reward_by_ids(vec![(11, 1)]);
+		let _ = current_total_payout_for_duration(reward_time_per_era());
+		mock::start_active_era(2);
+		Staking::reward_by_ids(vec![(11, 1)]);
+		let _ = current_total_payout_for_duration(reward_time_per_era());
+		mock::start_active_era(3);
+
+		// turn the exposures of era 1 and 2 into legacy exposures, only 101 being in the clipped
+		// exposure.
+		let exposures = (1..=2)
+			.map(|era| (era, EraInfo::<Test>::get_full_exposure(era, &11)))
+			.collect::<Vec<_>>();
+		let _ = ErasStakersOverview::<Test>::clear(u32::MAX, None);
+		let _ = ErasStakersPaged::<Test>::clear(u32::MAX, None);
+		for (era, exposure) in exposures {
+			let mut clipped = exposure.clone();
+			clipped.others.retain(|i| i.who == 101);
+			ErasStakers::<Test>::insert(era, 11, exposure.clone());
+			ErasStakersClipped::<Test>::insert(era, 11, clipped);
+			assert_eq!(EraInfo::<Test>::get_full_exposure(era, &11), exposure);
+			assert_eq!(EraInfo::<Test>::get_page_count(era, &11), 1);
+		}
+		assert!(<Staking as sp_staking::StakingInterface>::is_exposed_in_era(&102, &1));
+
+		// era 2 has been claimed before the upgrade.
+		Ledger::<Test>::mutate(&11, |ledger| {
+			ledger.as_mut().unwrap().legacy_claimed_rewards = bounded_vec![2];
+		});
+		assert_noop!(
+			Staking::payout_stakers(RuntimeOrigin::signed(1337), 11, 2),
+			Error::<Test>::AlreadyClaimed.with_weight(err_weight)
+		);
+
+		// era 1 pays out the clipped exposure as a single page.
+		assert_noop!(
+			Staking::payout_stakers_by_page(RuntimeOrigin::signed(1337), 11, 1, 1),
+			Error::<Test>::InvalidPage.with_weight(err_weight)
+		);
+		assert_ok!(Staking::payout_stakers(RuntimeOrigin::signed(1337), 11, 1));
+		assert!(Balances::free_balance(&11) > balance);
+		assert!(Balances::free_balance(&101) > balance);
+		assert_eq!(Balances::free_balance(&102), balance);
+		assert_eq!(Staking::claimed_rewards(1, &11), vec![0]);
+		assert_noop!(
+			Staking::payout_stakers(RuntimeOrigin::signed(1337), 11, 1),
+			Error::<Test>::AlreadyClaimed.with_weight(err_weight)
+		);
+	});
+}
+
+#[test]
+fn exposures_are_clipped_to_max_exposure_page_count() {
+	ExtBuilder::default().has_stakers(false).build_and_execute(|| {
+		MaxExposurePageSize::set(2);
+		MaxExposurePageCount::set(2);
+
+		let balance = 1000;
+		bond_validator(11, balance);
+		for i in 0..5 {
+			bond_nominator(100 + i, balance + i as Balance, vec![11]);
+		}
+
+		mock::start_active_era(1);
+		Staking::reward_by_ids(vec![(11, 1)]);
+		let _ = current_total_payout_for_duration(reward_time_per_era());
+		mock::start_active_era(2);
+
+		// only the 4 biggest nominators fit in 2 pages of 2, the total remains unchanged.
+		assert_eq!(EraInfo::<Test>::get_page_count(1, &11), 2);
+		let exposure = EraInfo::<Test>::get_full_exposure(1, &11);
+		assert_eq!(exposure.total, 6 * balance + 10);
+		assert_eq_uvec!(
+			exposure.others.iter().map(|i| i.who).collect::<Vec<_>>(),
+			vec![101, 102, 103, 104]
+		);
+
+		assert_ok!(Staking::payout_stakers(RuntimeOrigin::signed(1337), 11, 1));
+		assert_ok!(Staking::payout_stakers(RuntimeOrigin::signed(1337), 11, 1));
+		assert_eq!(Staking::claimed_rewards(1, &11), vec![0, 1]);
+		for i in 1..5 {
+			assert!(Balances::free_balance(&(100 + i)) > balance + i as Balance);
+		}
+		assert_eq!(Balances::free_balance(&100), balance);
+	});
+}
+
+#[test]
+fn migrate_to_v14_moves_legacy_claimed_rewards() {
+	use frame_support::traits::OnRuntimeUpgrade;
+
+	ExtBuilder::default().has_stakers(false).build_and_execute(|| {
+		let err_weight = <Test as Config>::WeightInfo::payout_stakers_alive_staked(0);
+		let balance = 1000;
+		bond_validator(11, balance);
+		bond_nominator(101, balance, vec![11]);
+
+		for era in 1..=3 {
+			mock::start_active_era(era);
+			Staking::reward_by_ids(vec![(11, 1)]);
+			let _ = current_total_payout_for_duration(reward_time_per_era());
+		}
+		mock::start_active_era(4);
+
+		// turn the exposures of era 1 to 3 into legacy exposures.
+		let exposures = (1..=3)
+			.map(|era| (era, EraInfo::<Test>::get_full_exposure(era, &11)))
+			.collect::<Vec<_>>();
+		let _ = ErasStakersOverview::<Test>::clear(u32::MAX, None);
+		let _ = ErasStakersPaged::<Test>::clear(u32::MAX, None);
+		for (era, exposure) in exposures {
+			ErasStakers::<Test>::insert(era, 11, exposure.clone());
+			ErasStakersClipped::<Test>::insert(era, 11, exposure);
+		}
+
+		// era 1 and 2 have been claimed before the upgrade.
+		Ledger::<Test>::mutate(&11, |ledger| {
+			ledger.as_mut().unwrap().legacy_claimed_rewards = bounded_vec![1, 2];
+		});
+		StorageVersion::new(13).put::<Pallet<Test>>();
+
+		migrations::v14::MigrateToV14::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Pallet::<Test>::on_chain_storage_version(), 14);
+		assert_eq!(Staking::ledger(&11).unwrap().legacy_claimed_rewards, bounded_vec![]);
+		assert_eq!(Staking::claimed_rewards(1, &11), vec![0]);
+		assert_eq!(Staking::claimed_rewards(2, &11), vec![0]);
+		assert!(Staking::claimed_rewards(3, &11).is_empty());
+
+		assert_noop!(
+			Staking::payout_stakers(RuntimeOrigin::signed(1337), 11, 2),
+			Error::<Test>::AlreadyClaimed.with_weight(err_weight)
+		);
+		assert_ok!(Staking::payout_stakers(RuntimeOrigin::signed(1337), 11, 3));
+		assert_eq!(Staking::claimed_rewards(3, &11), vec![0]);
 	});
 }
 
@@ -3952,8 +4144,7 @@ fn payout_stakers_handles_weight_refund() {
 	// Note: this test relies on the assumption that `payout_stakers_alive_staked` is solely used by
 	// `payout_stakers` to calculate the weight of each payout op.
 	ExtBuilder::default().has_stakers(false).build_and_execute(|| {
-		let max_nom_rewarded =
-			<<Test as Config>::MaxNominatorRewardedPerValidator as Get<_>>::get();
+		let max_nom_rewarded = <<Test as Config>::MaxExposurePageSize as Get<_>>::get();
 		// Make sure the configured value is meaningful for our use.
 		assert!(max_nom_rewarded >= 4);
 		let half_max_nom_rewarded = max_nom_rewarded / 2;
@@ -4066,7 +4257,7 @@ fn bond_during_era_correctly_populates_claimed_rewards() {
 				total: 1000,
 				active: 1000,
 				unlocking: Default::default(),
-				claimed_rewards: bounded_vec![],
+				legacy_claimed_rewards: bounded_vec![],
 			})
 		);
 		mock::start_active_era(5);
@@ -4078,7 +4269,7 @@ fn bond_during_era_correctly_populates_claimed_rewards() {
 				total: 1000,
 				active: 1000,
 				unlocking: Default::default(),
-				claimed_rewards: (0..5).collect::<Vec<_>>().try_into().unwrap(),
+				legacy_claimed_rewards: (0..5).collect::<Vec<_>>().try_into().unwrap(),
 			})
 		);
 
@@ -4094,7 +4285,7 @@ fn bond_during_era_correctly_populates_claimed_rewards() {
 				total: 1000,
 				active: 1000,
 				unlocking: Default::default(),
-				claimed_rewards: (last_reward_era..current_era)
+				legacy_claimed_rewards: (last_reward_era..current_era)
 					.collect::<Vec<_>>()
 					.try_into()
 					.unwrap(),
@@ -4126,7 +4317,7 @@ fn offences_weight_calculated_correctly() {
 			>,
 		> = (1..10)
 			.map(|i| OffenceDetails {
-				offender: (i, Staking::eras_stakers(active_era(), i)),
+				offender: (i, EraInfo::<Test>::get_full_exposure(active_era(), &i)),
 				reporters: vec![],
 			})
 			.collect();
@@ -4142,7 +4333,7 @@ fn offences_weight_calculated_correctly() {
 
 		// On Offence with one offenders, Applied
 		let one_offender = [OffenceDetails {
-			offender: (11, Staking::eras_stakers(active_era(), 11)),
+			offender: (11, EraInfo::<Test>::get_full_exposure(active_era(), &11)),
 			reporters: vec![1],
 		}];
 
@@ -4349,7 +4540,7 @@ fn cannot_rebond_to_lower_than_ed() {
 					total: 11 * 1000,
 					active: 11 * 1000,
 					unlocking: Default::default(),
-					claimed_rewards: bounded_vec![],
+					legacy_claimed_rewards: bounded_vec![],
 				}
 			);
 
@@ -4363,7 +4554,7 @@ fn cannot_rebond_to_lower_than_ed() {
 					total: 11 * 1000,
 					active: 0,
 					unlocking: bounded_vec![UnlockChunk { value: 11 * 1000, era: 3 }],
-					claimed_rewards: bounded_vec![],
+					legacy_claimed_rewards: bounded_vec![],
 				}
 			);
 
@@ -4389,7 +4580,7 @@ fn cannot_bond_extra_to_lower_than_ed() {
 					total: 11 * 1000,
 					active: 11 * 1000,
 					unlocking: Default::default(),
-					claimed_rewards: bounded_vec![],
+					legacy_claimed_rewards: bounded_vec![],
 				}
 			);
 
@@ -4403,7 +4594,7 @@ fn cannot_bond_extra_to_lower_than_ed() {
 					total: 11 * 1000,
 					active: 0,
 					unlocking: bounded_vec![UnlockChunk { value: 11 * 1000, era: 3 }],
-					claimed_rewards: bounded_vec![],
+					legacy_claimed_rewards: bounded_vec![],
 				}
 			);
 
@@ -4430,7 +4621,7 @@ fn do_not_die_when_active_is_ed() {
 					total: 1000 * ed,
 					active: 1000 * ed,
 					unlocking: Default::default(),
-					claimed_rewards: bounded_vec![],
+					legacy_claimed_rewards: bounded_vec![],
 				}
 			);
 
@@ -4447,7 +4638,7 @@ fn do_not_die_when_active_is_ed() {
 					total: ed,
 					active: ed,
 					unlocking: Default::default(),
-					claimed_rewards: bounded_vec![],
+					legacy_claimed_rewards: bounded_vec![],
 				}
 			);
 		})
@@ -5309,7 +5500,7 @@ fn proportional_slash_stop_slashing_if_remaining_zero() {
 		active: 20,
 		// we have some chunks, but they are not affected.
 		unlocking: bounded_vec![c(1, 10), c(2, 10)],
-		claimed_rewards: bounded_vec![],
+		legacy_claimed_rewards: bounded_vec![],
 	};
 
 	assert_eq!(BondingDuration::get(), 3);
@@ -5327,7 +5518,7 @@ fn proportional_ledger_slash_works() {
 		total: 10,
 		active: 10,
 		unlocking: bounded_vec![],
-		claimed_rewards: bounded_vec![],
+		legacy_claimed_rewards: bounded_vec![],
 	};
 	assert_eq!(BondingDuration::get(), 3);
 
@@ -5564,7 +5755,7 @@ fn pre_bonding_era_cannot_be_claimed() {
 		// add a new candidate for being a validator. account 3 controlled by 4.
 		assert_ok!(Staking::bond(RuntimeOrigin::signed(3), 1500, RewardDestination::Controller));
 
-		let claimed_rewards: BoundedVec<_, _> =
+		let legacy_claimed_rewards: BoundedVec<_, _> =
 			(start_reward_era..=last_reward_era).collect::<Vec<_>>().try_into().unwrap();
 		assert_eq!(
 			Staking::ledger(&3).unwrap(),
@@ -5573,7 +5764,7 @@ fn pre_bonding_era_cannot_be_claimed() {
 				total: 1500,
 				active: 1500,
 				unlocking: Default::default(),
-				claimed_rewards,
+				legacy_claimed_rewards,
 			}
 		);
 
@@ -5593,17 +5784,18 @@ fn pre_bonding_era_cannot_be_claimed() {
 			Error::<Test>::AlreadyClaimed.with_weight(err_weight)
 		);
 
-		// decoding will fail now since Staking Ledger is in corrupt state
+		// the ledger was pruned by the last claim, and claimed pages are not tracked in the
+		// ledger anymore, so it still fits a reduced history depth.
 		HistoryDepth::set(history_depth - 1);
-		assert_eq!(Staking::ledger(&4), None);
+		assert!(Staking::ledger(&3).is_some());
 
 		// make sure stakers still cannot claim rewards that they are not meant to
 		assert_noop!(
 			Staking::payout_stakers(RuntimeOrigin::signed(3), 3, current_era - 2),
-			Error::<Test>::NotController
+			Error::<Test>::AlreadyClaimed.with_weight(err_weight)
 		);
 
-		// fix the corrupted state for post conditions check
+		// restore the history depth for post conditions check
 		HistoryDepth::set(history_depth);
 	});
 }
@@ -5630,7 +5822,7 @@ fn reducing_history_depth_abrupt() {
 
 		// all previous era before the bonding action should be marked as
 		// claimed.
-		let claimed_rewards: BoundedVec<_, _> =
+		let legacy_claimed_rewards: BoundedVec<_, _> =
 			(start_reward_era..=last_reward_era).collect::<Vec<_>>().try_into().unwrap();
 		assert_eq!(
 			Staking::ledger(&3).unwrap(),
@@ -5639,7 +5831,7 @@ fn reducing_history_depth_abrupt() {
 				total: 1500,
 				active: 1500,
 				unlocking: Default::default(),
-				claimed_rewards,
+				legacy_claimed_rewards,
 			}
 		);
 
@@ -5657,11 +5849,9 @@ fn reducing_history_depth_abrupt() {
 		// history_depth reduced without migration
 		let history_depth = original_history_depth - 1;
 		HistoryDepth::set(history_depth);
-		// claiming reward does not work anymore
-		assert_noop!(
-			Staking::payout_stakers(RuntimeOrigin::signed(3), 3, current_era - 1),
-			Error::<Test>::NotController
-		);
+		// claiming reward still works, as the ledger was pruned by the last claim and claimed
+		// pages are not tracked in the ledger anymore.
+		assert_ok!(Staking::payout_stakers(RuntimeOrigin::signed(3), 3, current_era - 1));
 
 		// new stakers can still bond
 		assert_ok!(Staking::bond(RuntimeOrigin::signed(5), 1200, RewardDestination::Controller));
@@ -5669,7 +5859,7 @@ fn reducing_history_depth_abrupt() {
 		// new staking ledgers created will be bounded by the current history depth
 		let last_reward_era = current_era - 1;
 		let start_reward_era = current_era - history_depth;
-		let claimed_rewards: BoundedVec<_, _> =
+		let legacy_claimed_rewards: BoundedVec<_, _> =
 			(start_reward_era..=last_reward_era).collect::<Vec<_>>().try_into().unwrap();
 		assert_eq!(
 			Staking::ledger(&5).unwrap(),
@@ -5678,7 +5868,7 @@ fn reducing_history_depth_abrupt() {
 				total: 1200,
 				active: 1200,
 				unlocking: Default::default(),
-				claimed_rewards,
+				legacy_claimed_rewards,
 			}
 		);
 
@@ -5862,7 +6052,7 @@ mod staking_interface {
 		ExtBuilder::default().build_and_execute(|| {
 			on_offence_now(
 				&[OffenceDetails {
-					offender: (11, Staking::eras_stakers(active_era(), 11)),
+					offender: (11, EraInfo::<Test>::get_full_exposure(active_era(), &11)),
 					reporters: vec![],
 				}],
 				&[Perbill::from_percent(100)],
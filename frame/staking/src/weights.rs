@@ -72,6 +72,7 @@ pub trait WeightInfo {
 	fn cancel_deferred_slash(s: u32, ) -> Weight;
 	fn payout_stakers_dead_controller(n: u32, ) -> Weight;
 	fn payout_stakers_alive_staked(n: u32, ) -> Weight;
+	fn payout_stakers_by_page(n: u32, ) -> Weight;
 	fn rebond(l: u32, ) -> Weight;
 	fn reap_stash(s: u32, ) -> Weight;
 	fn new_era(v: u32, n: u32, ) -> Weight;
@@ -469,8 +470,12 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: Staking Bonded (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
 	/// Storage: Staking Ledger (r:1 w:1)
 	/// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
-	/// Storage: Staking ErasStakersClipped (r:1 w:0)
-	/// Proof Skipped: Staking ErasStakersClipped (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Staking ClaimedRewards (r:1 w:1)
+	/// Proof: Staking ClaimedRewards (max_values: None, max_size: Some(682), added: 3157, mode: MaxEncodedLen)
+	/// Storage: Staking ErasStakersOverview (r:1 w:0)
+	/// Proof: Staking ErasStakersOverview (max_values: None, max_size: Some(94), added: 2569, mode: MaxEncodedLen)
+	/// Storage: Staking ErasStakersPaged (r:1 w:0)
+	/// Proof Skipped: Staking ErasStakersPaged (max_values: None, max_size: None, mode: Measured)
 	/// Storage: Staking ErasRewardPoints (r:1 w:0)
 	/// Proof Skipped: Staking ErasRewardPoints (max_values: None, max_size: None, mode: Measured)
 	/// Storage: Staking ErasValidatorPrefs (r:1 w:0)
@@ -488,9 +493,9 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_parts(146_781_264, 19844)
 			// Standard Error: 31_341
 			.saturating_add(Weight::from_parts(30_553_008, 0).saturating_mul(n.into()))
-			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().reads(11_u64))
 			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
-			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
 			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
 			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(n.into()))
 	}
@@ -502,8 +507,12 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: Staking Bonded (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
 	/// Storage: Staking Ledger (r:257 w:257)
 	/// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
-	/// Storage: Staking ErasStakersClipped (r:1 w:0)
-	/// Proof Skipped: Staking ErasStakersClipped (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Staking ClaimedRewards (r:1 w:1)
+	/// Proof: Staking ClaimedRewards (max_values: None, max_size: Some(682), added: 3157, mode: MaxEncodedLen)
+	/// Storage: Staking ErasStakersOverview (r:1 w:0)
+	/// Proof: Staking ErasStakersOverview (max_values: None, max_size: Some(94), added: 2569, mode: MaxEncodedLen)
+	/// Storage: Staking ErasStakersPaged (r:1 w:0)
+	/// Proof Skipped: Staking ErasStakersPaged (max_values: None, max_size: None, mode: Measured)
 	/// Storage: Staking ErasRewardPoints (r:1 w:0)
 	/// Proof Skipped: Staking ErasRewardPoints (max_values: None, max_size: None, mode: Measured)
 	/// Storage: Staking ErasValidatorPrefs (r:1 w:0)
@@ -525,9 +534,50 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_parts(151_046_907, 30845)
 			// Standard Error: 41_899
 			.saturating_add(Weight::from_parts(49_837_804, 0).saturating_mul(n.into()))
-			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().reads(13_u64))
 			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(n.into())))
-			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 3774).saturating_mul(n.into()))
+	}
+	/// Storage: Staking CurrentEra (r:1 w:0)
+	/// Proof: Staking CurrentEra (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Staking ErasValidatorReward (r:1 w:0)
+	/// Proof: Staking ErasValidatorReward (max_values: None, max_size: Some(28), added: 2503, mode: MaxEncodedLen)
+	/// Storage: Staking Bonded (r:257 w:0)
+	/// Proof: Staking Bonded (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+	/// Storage: Staking Ledger (r:257 w:257)
+	/// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
+	/// Storage: Staking ClaimedRewards (r:1 w:1)
+	/// Proof: Staking ClaimedRewards (max_values: None, max_size: Some(682), added: 3157, mode: MaxEncodedLen)
+	/// Storage: Staking ErasStakersOverview (r:1 w:0)
+	/// Proof: Staking ErasStakersOverview (max_values: None, max_size: Some(94), added: 2569, mode: MaxEncodedLen)
+	/// Storage: Staking ErasStakersPaged (r:1 w:0)
+	/// Proof Skipped: Staking ErasStakersPaged (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Staking ErasRewardPoints (r:1 w:0)
+	/// Proof Skipped: Staking ErasRewardPoints (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Staking ErasValidatorPrefs (r:1 w:0)
+	/// Proof: Staking ErasValidatorPrefs (max_values: None, max_size: Some(57), added: 2532, mode: MaxEncodedLen)
+	/// Storage: Staking Payee (r:257 w:0)
+	/// Proof: Staking Payee (max_values: None, max_size: Some(73), added: 2548, mode: MaxEncodedLen)
+	/// Storage: System Account (r:257 w:257)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Balances Locks (r:257 w:257)
+	/// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+	/// Storage: Balances Freezes (r:257 w:0)
+	/// Proof: Balances Freezes (max_values: None, max_size: Some(49), added: 2524, mode: MaxEncodedLen)
+	/// The range of component `n` is `[0, 256]`.
+	fn payout_stakers_by_page(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `33190 + n * (377 ±0)`
+		//  Estimated: `30845 + n * (3774 ±0)`
+		// Minimum execution time: 121_303_000 picoseconds.
+		Weight::from_parts(151_046_907, 30845)
+			// Standard Error: 41_899
+			.saturating_add(Weight::from_parts(49_837_804, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(13_u64))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
 			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
 			.saturating_add(Weight::from_parts(0, 3774).saturating_mul(n.into()))
 	}
@@ -1179,8 +1229,12 @@ impl WeightInfo for () {
 	/// Proof: Staking Bonded (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
 	/// Storage: Staking Ledger (r:1 w:1)
 	/// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
-	/// Storage: Staking ErasStakersClipped (r:1 w:0)
-	/// Proof Skipped: Staking ErasStakersClipped (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Staking ClaimedRewards (r:1 w:1)
+	/// Proof: Staking ClaimedRewards (max_values: None, max_size: Some(682), added: 3157, mode: MaxEncodedLen)
+	/// Storage: Staking ErasStakersOverview (r:1 w:0)
+	/// Proof: Staking ErasStakersOverview (max_values: None, max_size: Some(94), added: 2569, mode: MaxEncodedLen)
+	/// Storage: Staking ErasStakersPaged (r:1 w:0)
+	/// Proof Skipped: Staking ErasStakersPaged (max_values: None, max_size: None, mode: Measured)
 	/// Storage: Staking ErasRewardPoints (r:1 w:0)
 	/// Proof Skipped: Staking ErasRewardPoints (max_values: None, max_size: None, mode: Measured)
 	/// Storage: Staking ErasValidatorPrefs (r:1 w:0)
@@ -1198,9 +1252,9 @@ impl WeightInfo for () {
 		Weight::from_parts(146_781_264, 19844)
 			// Standard Error: 31_341
 			.saturating_add(Weight::from_parts(30_553_008, 0).saturating_mul(n.into()))
-			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
 			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
-			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
 			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
 			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(n.into()))
 	}
@@ -1212,8 +1266,12 @@ impl WeightInfo for () {
 	/// Proof: Staking Bonded (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
 	/// Storage: Staking Ledger (r:257 w:257)
 	/// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
-	/// Storage: Staking ErasStakersClipped (r:1 w:0)
-	/// Proof Skipped: Staking ErasStakersClipped (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Staking ClaimedRewards (r:1 w:1)
+	/// Proof: Staking ClaimedRewards (max_values: None, max_size: Some(682), added: 3157, mode: MaxEncodedLen)
+	/// Storage: Staking ErasStakersOverview (r:1 w:0)
+	/// Proof: Staking ErasStakersOverview (max_values: None, max_size: Some(94), added: 2569, mode: MaxEncodedLen)
+	/// Storage: Staking ErasStakersPaged (r:1 w:0)
+	/// Proof Skipped: Staking ErasStakersPaged (max_values: None, max_size: None, mode: Measured)
 	/// Storage: Staking ErasRewardPoints (r:1 w:0)
 	/// Proof Skipped: Staking ErasRewardPoints (max_values: None, max_size: None, mode: Measured)
 	/// Storage: Staking ErasValidatorPrefs (r:1 w:0)
@@ -1235,9 +1293,50 @@ impl WeightInfo for () {
 		Weight::from_parts(151_046_907, 30845)
 			// Standard Error: 41_899
 			.saturating_add(Weight::from_parts(49_837_804, 0).saturating_mul(n.into()))
-			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().reads(13_u64))
 			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(n.into())))
-			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 3774).saturating_mul(n.into()))
+	}
+	/// Storage: Staking CurrentEra (r:1 w:0)
+	/// Proof: Staking CurrentEra (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Staking ErasValidatorReward (r:1 w:0)
+	/// Proof: Staking ErasValidatorReward (max_values: None, max_size: Some(28), added: 2503, mode: MaxEncodedLen)
+	/// Storage: Staking Bonded (r:257 w:0)
+	/// Proof: Staking Bonded (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+	/// Storage: Staking Ledger (r:257 w:257)
+	/// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
+	/// Storage: Staking ClaimedRewards (r:1 w:1)
+	/// Proof: Staking ClaimedRewards (max_values: None, max_size: Some(682), added: 3157, mode: MaxEncodedLen)
+	/// Storage: Staking ErasStakersOverview (r:1 w:0)
+	/// Proof: Staking ErasStakersOverview (max_values: None, max_size: Some(94), added: 2569, mode: MaxEncodedLen)
+	/// Storage: Staking ErasStakersPaged (r:1 w:0)
+	/// Proof Skipped: Staking ErasStakersPaged (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Staking ErasRewardPoints (r:1 w:0)
+	/// Proof Skipped: Staking ErasRewardPoints (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Staking ErasValidatorPrefs (r:1 w:0)
+	/// Proof: Staking ErasValidatorPrefs (max_values: None, max_size: Some(57), added: 2532, mode: MaxEncodedLen)
+	/// Storage: Staking Payee (r:257 w:0)
+	/// Proof: Staking Payee (max_values: None, max_size: Some(73), added: 2548, mode: MaxEncodedLen)
+	/// Storage: System Account (r:257 w:257)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Balances Locks (r:257 w:257)
+	/// Proof: Balances Locks (max_values: None, max_size: Some(1299), added: 3774, mode: MaxEncodedLen)
+	/// Storage: Balances Freezes (r:257 w:0)
+	/// Proof: Balances Freezes (max_values: None, max_size: Some(49), added: 2524, mode: MaxEncodedLen)
+	/// The range of component `n` is `[0, 256]`.
+	fn payout_stakers_by_page(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `33190 + n * (377 ±0)`
+		//  Estimated: `30845 + n * (3774 ±0)`
+		// Minimum execution time: 121_303_000 picoseconds.
+		Weight::from_parts(151_046_907, 30845)
+			// Standard Error: 41_899
+			.saturating_add(Weight::from_parts(49_837_804, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(13_u64))
+			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
 			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
 			.saturating_add(Weight::from_parts(0, 3774).saturating_mul(n.into()))
 	}
@@ -0,0 +1,154 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License
+
+use crate::construct_runtime::Pallet;
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use std::str::FromStr;
+
+/// Expands aggregate `RuntimeTask` enum.
+pub fn expand_outer_task(
+	runtime: &Ident,
+	pallet_decls: &[Pallet],
+	scrate: &TokenStream,
+) -> TokenStream {
+	let mut from_impls = Vec::new();
+	let mut task_variants = Vec::new();
+	let mut variant_names = Vec::new();
+	let mut task_paths = Vec::new();
+	let mut attrs = Vec::new();
+	for decl in pallet_decls {
+		if decl.find_part("Task").is_none() {
+			continue
+		}
+
+		let variant_name = &decl.name;
+		let path = &decl.path;
+		let index = decl.index;
+		let attr = decl.cfg_pattern.iter().fold(TokenStream::new(), |acc, pattern| {
+			let attr = TokenStream::from_str(&format!("#[cfg({})]", pattern.original()))
+				.expect("was successfully parsed before; qed");
+			quote! {
+				#acc
+				#attr
+			}
+		});
+		let task_path = match decl.instance.as_ref() {
+			Some(inst) => quote!(#path::Task<#runtime, #path::#inst>),
+			None => quote!(#path::Task<#runtime>),
+		};
+
+		from_impls.push(quote! {
+			#attr
+			impl From<#task_path> for RuntimeTask {
+				fn from(task: #task_path) -> Self {
+					RuntimeTask::#variant_name(task)
+				}
+			}
+		});
+
+		task_variants.push(quote! {
+			#attr
+			#[codec(index = #index)]
+			#variant_name(#task_path),
+		});
+
+		variant_names.push(variant_name);
+		task_paths.push(task_path);
+		attrs.push(attr);
+	}
+
+	quote! {
+		/// An aggregation of all `Task` enums across all pallets included in the current runtime.
+		#[derive(
+			Clone, Eq, PartialEq,
+			#scrate::codec::Encode,
+			#scrate::codec::Decode,
+			#scrate::scale_info::TypeInfo,
+			#scrate::RuntimeDebug,
+		)]
+		pub enum RuntimeTask {
+			#( #task_variants )*
+		}
+
+		impl #scrate::traits::Task for RuntimeTask {
+			type Enumeration = #scrate::sp_std::vec::IntoIter<RuntimeTask>;
+
+			fn iter() -> Self::Enumeration {
+				let mut all_tasks = #scrate::sp_std::vec::Vec::<RuntimeTask>::new();
+				#(
+					#attrs
+					all_tasks.extend(
+						<#task_paths as #scrate::traits::Task>::iter().map(RuntimeTask::from),
+					);
+				)*
+				all_tasks.into_iter()
+			}
+
+			fn is_valid(&self) -> bool {
+				match *self {
+					#(
+						#attrs
+						RuntimeTask::#variant_names(ref task) =>
+							#scrate::traits::Task::is_valid(task),
+					)*
+				}
+			}
+
+			fn is_valid_weight(&self) -> #scrate::weights::Weight {
+				match *self {
+					#(
+						#attrs
+						RuntimeTask::#variant_names(ref task) =>
+							#scrate::traits::Task::is_valid_weight(task),
+					)*
+				}
+			}
+
+			fn run(&self) -> Result<(), #scrate::sp_runtime::DispatchError> {
+				match *self {
+					#(
+						#attrs
+						RuntimeTask::#variant_names(ref task) => #scrate::traits::Task::run(task),
+					)*
+				}
+			}
+
+			fn weight(&self) -> #scrate::weights::Weight {
+				match *self {
+					#(
+						#attrs
+						RuntimeTask::#variant_names(ref task) =>
+							#scrate::traits::Task::weight(task),
+					)*
+				}
+			}
+
+			fn task_index(&self) -> u32 {
+				match *self {
+					#(
+						#attrs
+						RuntimeTask::#variant_names(ref task) =>
+							#scrate::traits::Task::task_index(task),
+					)*
+				}
+			}
+		}
+
+		#( #from_impls )*
+	}
+}
@@ -15,7 +15,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License
 
-use crate::construct_runtime::Pallet;
+use crate::construct_runtime::{Pallet, SYSTEM_PALLET_NAME};
 use proc_macro2::TokenStream;
 use quote::quote;
 use std::str::FromStr;
@@ -30,7 +30,15 @@ pub fn expand_outer_validate_unsigned(
 	let mut pallet_attrs = Vec::new();
 	let mut query_validate_unsigned_part_macros = Vec::new();
 
+	// The system pallet only validates unsigned tasks, which don't exist unless a pallet
+	// declares some.
+	let has_tasks = pallet_decls.iter().any(|decl| decl.exists_part("Task"));
+
 	for pallet_decl in pallet_decls {
+		if pallet_decl.name == SYSTEM_PALLET_NAME && !has_tasks {
+			continue
+		}
+
 		if pallet_decl.exists_part("ValidateUnsigned") {
 			let name = &pallet_decl.name;
 			let path = &pallet_decl.path;
@@ -400,6 +400,7 @@ fn construct_runtime_final_expansion(
 	let hold_reason = expand::expand_outer_hold_reason(&pallets, &scrate);
 	let lock_id = expand::expand_outer_lock_id(&pallets, &scrate);
 	let slash_reason = expand::expand_outer_slash_reason(&pallets, &scrate);
+	let task = expand::expand_outer_task(&name, &pallets, &scrate);
 	let integrity_test = decl_integrity_test(&scrate);
 	let static_assertions = decl_static_assertions(&name, &pallets, &scrate);
 
@@ -488,6 +489,8 @@ fn construct_runtime_final_expansion(
 
 		#slash_reason
 
+		#task
+
 		#integrity_test
 
 		#static_assertions
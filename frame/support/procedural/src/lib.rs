@@ -362,6 +362,7 @@ pub fn decl_storage(input: TokenStream) -> TokenStream {
 ///
 ///   - `Pallet` - Required for all pallets
 ///   - `Call` - If the pallet has callable functions
+///   - `Task<T>` - If the pallet declares tasks with `#[pallet::tasks]`
 ///   - `Storage` - If the pallet uses storage
 ///   - `Event` or `Event<T>` (if the event is generic) - If the pallet emits events
 ///   - `Origin` or `Origin<T>` (if the origin is generic) - If the pallet has instanciable origins
@@ -476,6 +477,9 @@ pub fn construct_runtime(input: TokenStream) -> TokenStream {
 /// * Storage hashers no longer need to be specified and can be replaced by `_`. In dev mode, these
 ///   will be replaced by `Blake2_128Concat`. In case of explicit key-binding, `Hasher` can simply
 ///   be ignored when in `dev_mode`.
+/// * `#[pallet::task_weight]` and `#[pallet::task_condition_weight]` no longer need to be specified
+///   on every task of a `#[pallet::tasks]` block. By default, dev mode pallets will assume a weight
+///   of zero (`0`), and such tasks can still be run through `frame_system::Call::do_task`.
 ///
 /// Note that the `dev_mode` argument can only be supplied to the `#[pallet]` or
 /// `#[frame_support::pallet]` attribute macro that encloses your pallet module. This argument
@@ -1763,6 +1767,88 @@ pub fn composite_enum(_: TokenStream, _: TokenStream) -> TokenStream {
 	pallet_macro_stub()
 }
 
+/// The `#[pallet::tasks]` attribute allows you to define permissionless background work, called
+/// tasks, that anyone can submit through `frame_system::Call::do_task`.
+///
+/// Item must be defined as:
+///
+/// ```ignore
+/// #[pallet::tasks]
+/// impl<T: Config> Pallet<T> {
+/// 	#[pallet::task_index(0)]
+/// 	#[pallet::task_condition(|i| Numbers::<T>::contains_key(i))]
+/// 	#[pallet::task_condition_weight(T::DbWeight::get().reads(1))]
+/// 	#[pallet::task_list(Numbers::<T>::iter_keys())]
+/// 	#[pallet::task_weight(T::WeightInfo::add_number_into_total())]
+/// 	pub fn add_number_into_total(i: u32) -> DispatchResult {
+/// 		// ...
+/// 	}
+/// }
+/// ```
+///
+/// Each function must be annotated with:
+/// * `#[pallet::task_index($idx)]`: the index of the task within the pallet, used as its codec
+///   index.
+/// * `#[pallet::task_condition($closure)]`: a non-capturing closure receiving references to the
+///   task arguments and returning whether this instance of the task is currently valid.
+/// * `#[pallet::task_condition_weight($expr)]`: the weight of evaluating the condition, which is
+///   charged on top of the weight of the task. Optional in dev mode, where it defaults to zero.
+/// * `#[pallet::task_list($expr)]`: an iterator over the arguments of all candidate tasks; a tuple
+///   of arguments is expected when the task takes more than one argument.
+/// * `#[pallet::task_weight($expr)]`: the weight of the task. Optional in dev mode, where it
+///   defaults to zero.
+///
+/// ## Macro expansion
+///
+/// The macro creates an enum `Task` with one variant per task function, which implements
+/// `frame_support::traits::Task`. `construct_runtime` aggregates these enums into the
+/// `RuntimeTask` enum.
+#[proc_macro_attribute]
+pub fn tasks(_: TokenStream, _: TokenStream) -> TokenStream {
+	pallet_macro_stub()
+}
+
+/// Declares the index of a task inside a `#[pallet::tasks]` block.
+///
+/// See [`macro@tasks`] for more information.
+#[proc_macro_attribute]
+pub fn task_index(_: TokenStream, _: TokenStream) -> TokenStream {
+	pallet_macro_stub()
+}
+
+/// Declares the validity condition of a task inside a `#[pallet::tasks]` block.
+///
+/// See [`macro@tasks`] for more information.
+#[proc_macro_attribute]
+pub fn task_condition(_: TokenStream, _: TokenStream) -> TokenStream {
+	pallet_macro_stub()
+}
+
+/// Declares the weight of evaluating the validity condition of a task inside a
+/// `#[pallet::tasks]` block.
+///
+/// See [`macro@tasks`] for more information.
+#[proc_macro_attribute]
+pub fn task_condition_weight(_: TokenStream, _: TokenStream) -> TokenStream {
+	pallet_macro_stub()
+}
+
+/// Declares how candidate instances of a task are enumerated inside a `#[pallet::tasks]` block.
+///
+/// See [`macro@tasks`] for more information.
+#[proc_macro_attribute]
+pub fn task_list(_: TokenStream, _: TokenStream) -> TokenStream {
+	pallet_macro_stub()
+}
+
+/// Declares the weight of a task inside a `#[pallet::tasks]` block.
+///
+/// See [`macro@tasks`] for more information.
+#[proc_macro_attribute]
+pub fn task_weight(_: TokenStream, _: TokenStream) -> TokenStream {
+	pallet_macro_stub()
+}
+
 /// Can be attached to a module. Doing so will declare that module as importable into a pallet
 /// via [`#[import_section]`](`macro@import_section`).
 ///
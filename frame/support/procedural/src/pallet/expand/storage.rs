@@ -63,7 +63,7 @@ fn check_prefix_duplicates(
 		return Err(err)
 	}
 
-	if let Metadata::CountedMap { .. } = storage_def.metadata {
+	if let Metadata::CountedMap { .. } | Metadata::CountedNMap { .. } = storage_def.metadata {
 		let counter_prefix = counter_prefix(&prefix);
 		let counter_dup_err = syn::Error::new(
 			storage_def.prefix_span(),
@@ -248,7 +248,14 @@ pub fn process_generics(def: &mut Def) -> syn::Result<Vec<ResultOnEmptyStructMet
 					let max_values = max_values.unwrap_or_else(|| default_max_values.clone());
 					args.args.push(syn::GenericArgument::Type(max_values));
 				},
-				StorageGenerics::NMap { keygen, value, query_kind, on_empty, max_values } => {
+				StorageGenerics::NMap { keygen, value, query_kind, on_empty, max_values } |
+				StorageGenerics::CountedNMap {
+					keygen,
+					value,
+					query_kind,
+					on_empty,
+					max_values,
+				} => {
 					args.args.push(syn::GenericArgument::Type(keygen));
 					args.args.push(syn::GenericArgument::Type(value.clone()));
 					let mut query_kind = query_kind.unwrap_or_else(|| default_query_kind.clone());
@@ -265,7 +272,7 @@ pub fn process_generics(def: &mut Def) -> syn::Result<Vec<ResultOnEmptyStructMet
 
 			let (value_idx, query_idx, on_empty_idx) = match storage_def.metadata {
 				Metadata::Value { .. } => (1, 2, 3),
-				Metadata::NMap { .. } => (2, 3, 4),
+				Metadata::NMap { .. } | Metadata::CountedNMap { .. } => (2, 3, 4),
 				Metadata::Map { .. } | Metadata::CountedMap { .. } => (3, 4, 5),
 				Metadata::DoubleMap { .. } => (5, 6, 7),
 			};
@@ -367,6 +374,17 @@ fn augment_final_docs(def: &mut Def) {
 			);
 			push_string_literal(&doc_line, storage);
 		},
+		Metadata::CountedNMap { keys, value, .. } => {
+			let doc_line = format!(
+				"Storage type is [`CountedStorageNMap`] with keys type ({}) and value type {}.",
+				keys.iter()
+					.map(|k| k.to_token_stream().to_string())
+					.collect::<Vec<_>>()
+					.join(", "),
+				value.to_token_stream()
+			);
+			push_string_literal(&doc_line, storage);
+		},
 	});
 }
 
@@ -579,6 +597,36 @@ pub fn expand_storages(def: &mut Def) -> proc_macro2::TokenStream {
 						}
 					)
 				},
+				Metadata::CountedNMap { keygen, value, .. } => {
+					let query = match storage.query_kind.as_ref().expect("Checked by def") {
+						QueryKind::OptionQuery => quote::quote_spanned!(storage.attr_span =>
+							Option<#value>
+						),
+						QueryKind::ResultQuery(error_path, _) => {
+							quote::quote_spanned!(storage.attr_span =>
+								Result<#value, #error_path>
+							)
+						},
+						QueryKind::ValueQuery => quote::quote!(#value),
+					};
+					quote::quote_spanned!(storage.attr_span =>
+						#(#cfg_attrs)*
+						impl<#type_impl_gen> #pallet_ident<#type_use_gen> #completed_where_clause {
+							#[doc = #getter_doc_line]
+							pub fn #getter<KArg>(key: KArg) -> #query
+							where
+								KArg: #frame_support::storage::types::EncodeLikeTuple<
+									<#keygen as #frame_support::storage::types::KeyGenerator>::KArg
+								>
+									+ #frame_support::storage::types::TupleToEncodedIter,
+							{
+								// NOTE: we can't use any trait here because CountedStorageNMap
+								// doesn't implement any.
+								<#full_ident>::get(key)
+							}
+						}
+					)
+				},
 			}
 		} else {
 			Default::default()
@@ -595,7 +643,13 @@ pub fn expand_storages(def: &mut Def) -> proc_macro2::TokenStream {
 
 		let cfg_attrs = &storage_def.cfg_attrs;
 
-		let maybe_counter = if let Metadata::CountedMap { .. } = storage_def.metadata {
+		let counter_instance = match storage_def.metadata {
+			Metadata::CountedMap { .. } => Some(quote::quote!(CountedStorageMapInstance)),
+			Metadata::CountedNMap { .. } => Some(quote::quote!(CountedStorageNMapInstance)),
+			_ => None,
+		};
+
+		let maybe_counter = if let Some(counter_instance) = counter_instance {
 			let counter_prefix_struct_ident = counter_prefix_ident(&storage_def.ident);
 			let counter_prefix_struct_const = counter_prefix(&prefix_struct_const);
 
@@ -620,7 +674,7 @@ pub fn expand_storages(def: &mut Def) -> proc_macro2::TokenStream {
 					const STORAGE_PREFIX: &'static str = #counter_prefix_struct_const;
 				}
 				#(#cfg_attrs)*
-				impl<#type_impl_gen> #frame_support::storage::types::CountedStorageMapInstance
+				impl<#type_impl_gen> #frame_support::storage::types::#counter_instance
 					for #prefix_struct_ident<#type_use_gen>
 					#config_where_clause
 				{
@@ -0,0 +1,177 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::pallet::Def;
+use quote::quote;
+
+/// * Generate enum `Task` with a variant for each task function.
+/// * Implement `frame_support::traits::Task` on it.
+pub fn expand_tasks(def: &mut Def) -> proc_macro2::TokenStream {
+	let Some(tasks_def) = def.tasks.as_ref() else { return Default::default() };
+
+	let span = tasks_def.attr_span;
+	let frame_support = &def.frame_support;
+	let type_impl_gen = &def.type_impl_generics(span);
+	let type_decl_bounded_gen = &def.type_decl_bounded_generics(span);
+	let type_use_gen = &def.type_use_generics(span);
+	let pallet_ident = &def.pallet_struct.pallet;
+	let where_clause = &tasks_def.where_clause;
+	let docs = &tasks_def.docs;
+	let tasks = &tasks_def.tasks;
+
+	let capture_docs = if cfg!(feature = "no-metadata-docs") { "never" } else { "always" };
+
+	let fn_name = tasks.iter().map(|task| &task.name).collect::<Vec<_>>();
+	let fn_doc = tasks.iter().map(|task| &task.docs).collect::<Vec<_>>();
+	let task_index = tasks.iter().map(|task| task.task_index).collect::<Vec<_>>();
+	let task_index_u32 = task_index.iter().map(|i| *i as u32).collect::<Vec<_>>();
+	let args_name = tasks
+		.iter()
+		.map(|task| task.args.iter().map(|(name, _)| name).collect::<Vec<_>>())
+		.collect::<Vec<_>>();
+	let args_type = tasks
+		.iter()
+		.map(|task| task.args.iter().map(|(_, ty)| ty).collect::<Vec<_>>())
+		.collect::<Vec<_>>();
+
+	let condition = tasks.iter().map(|task| {
+		let condition = &task.condition;
+		let args_name = task.args.iter().map(|(name, _)| name);
+		let args_type = task.args.iter().map(|(_, ty)| ty);
+		// Coercing to a function pointer lets the closure arguments be inferred from the task
+		// arguments.
+		quote::quote_spanned!(task.span =>
+			let __task_condition: fn( #( &#args_type ),* ) -> bool = #condition;
+			__task_condition( #( #args_name ),* )
+		)
+	});
+
+	let list = tasks.iter().map(|task| {
+		let list = &task.list;
+		let name = &task.name;
+		let args_name = task.args.iter().map(|(name, _)| name).collect::<Vec<_>>();
+		let pattern = match args_name.len() {
+			1 => quote!( #( #args_name )* ),
+			_ => quote!( ( #( #args_name ),* ) ),
+		};
+		quote::quote_spanned!(task.span =>
+			__tasks.extend((#list).map(|#pattern| Self::#name { #( #args_name ),* }));
+		)
+	});
+
+	let weight = tasks.iter().map(|task| match &task.weight {
+		Some(weight) => quote!(#weight),
+		None => quote!(#frame_support::weights::Weight::zero()),
+	});
+
+	let condition_weight = tasks.iter().map(|task| match &task.condition_weight {
+		Some(weight) => quote!(#weight),
+		None => quote!(#frame_support::weights::Weight::zero()),
+	});
+
+	quote::quote_spanned!(span =>
+		#( #[doc = #docs] )*
+		#[derive(
+			#frame_support::RuntimeDebugNoBound,
+			#frame_support::CloneNoBound,
+			#frame_support::EqNoBound,
+			#frame_support::PartialEqNoBound,
+			#frame_support::codec::Encode,
+			#frame_support::codec::Decode,
+			#frame_support::scale_info::TypeInfo,
+		)]
+		#[codec(encode_bound())]
+		#[codec(decode_bound())]
+		#[scale_info(skip_type_params(#type_use_gen), capture_docs = #capture_docs)]
+		#[allow(non_camel_case_types)]
+		pub enum Task<#type_decl_bounded_gen> #where_clause {
+			#[doc(hidden)]
+			#[codec(skip)]
+			__Ignore(
+				#frame_support::sp_std::marker::PhantomData<(#type_use_gen,)>,
+				#frame_support::Never,
+			),
+			#(
+				#( #[doc = #fn_doc] )*
+				#[codec(index = #task_index)]
+				#fn_name {
+					#(
+						#[allow(missing_docs)]
+						#args_name: #args_type
+					),*
+				},
+			)*
+		}
+
+		impl<#type_impl_gen> #frame_support::traits::Task for Task<#type_use_gen> #where_clause {
+			type Enumeration = #frame_support::sp_std::vec::IntoIter<Self>;
+
+			fn iter() -> Self::Enumeration {
+				let mut __tasks = #frame_support::sp_std::vec::Vec::new();
+				#( #list )*
+				__tasks.into_iter()
+			}
+
+			fn is_valid(&self) -> bool {
+				match self {
+					#(
+						Self::#fn_name { #( #args_name ),* } => { #condition },
+					)*
+					Self::__Ignore(_, _) => unreachable!("__Ignore cannot be used"),
+				}
+			}
+
+			fn is_valid_weight(&self) -> #frame_support::weights::Weight {
+				match self {
+					#(
+						#[allow(unused_variables)]
+						Self::#fn_name { #( #args_name ),* } => #condition_weight,
+					)*
+					Self::__Ignore(_, _) => unreachable!("__Ignore cannot be used"),
+				}
+			}
+
+			fn run(&self) -> Result<(), #frame_support::sp_runtime::DispatchError> {
+				match self.clone() {
+					#(
+						Self::#fn_name { #( #args_name ),* } =>
+							<#pallet_ident<#type_use_gen>>::#fn_name( #( #args_name ),* )
+								.map_err(Into::into),
+					)*
+					Self::__Ignore(_, _) => unreachable!("__Ignore cannot be used"),
+				}
+			}
+
+			fn weight(&self) -> #frame_support::weights::Weight {
+				match self {
+					#(
+						#[allow(unused_variables)]
+						Self::#fn_name { #( #args_name ),* } => #weight,
+					)*
+					Self::__Ignore(_, _) => unreachable!("__Ignore cannot be used"),
+				}
+			}
+
+			fn task_index(&self) -> u32 {
+				match self {
+					#( Self::#fn_name { .. } => #task_index_u32, )*
+					Self::__Ignore(_, _) => unreachable!("__Ignore cannot be used"),
+				}
+			}
+		}
+	)
+}
@@ -0,0 +1,261 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::helper;
+use frame_support_procedural_tools::get_doc_literals;
+use std::collections::HashMap;
+use syn::spanned::Spanned;
+
+/// List of additional token to be used for parsing.
+mod keyword {
+	syn::custom_keyword!(pallet);
+	syn::custom_keyword!(task_index);
+	syn::custom_keyword!(task_condition);
+	syn::custom_keyword!(task_condition_weight);
+	syn::custom_keyword!(task_list);
+	syn::custom_keyword!(task_weight);
+}
+
+/// Definition of tasks typically `impl<T: Config> Pallet<T> { ... }` annotated with
+/// `#[pallet::tasks]`.
+pub struct TasksDef {
+	/// The where_clause used.
+	pub where_clause: Option<syn::WhereClause>,
+	/// A set of usage of instance, must be check for consistency with trait.
+	pub instances: Vec<helper::InstanceUsage>,
+	/// The index of the tasks item in pallet module.
+	pub index: usize,
+	/// Information on the task functions (used for expansion).
+	pub tasks: Vec<TaskDef>,
+	/// The span of the pallet::tasks attribute.
+	pub attr_span: proc_macro2::Span,
+	/// Docs, specified on the impl Block.
+	pub docs: Vec<syn::Expr>,
+}
+
+/// Definition of a single task, typically:
+/// `#[pallet::task_index(..)] #[pallet::task_condition(..)] ... fn foo(param1: ...) -> ..`
+#[derive(Clone)]
+pub struct TaskDef {
+	/// Function name.
+	pub name: syn::Ident,
+	/// Information on args: `(name, type)`.
+	pub args: Vec<(syn::Ident, Box<syn::Type>)>,
+	/// Index of the task within the pallet, also used as its codec index.
+	pub task_index: u8,
+	/// Closure deciding whether a given instance of the task is valid.
+	pub condition: syn::Expr,
+	/// Weight of evaluating the condition, `None` only in dev mode when no weight was given.
+	pub condition_weight: Option<syn::Expr>,
+	/// Expression evaluating to an iterator over the arguments of all candidate tasks.
+	pub list: syn::Expr,
+	/// Weight of the task, `None` only in dev mode when no weight was given.
+	pub weight: Option<syn::Expr>,
+	/// Docs, used for metadata.
+	pub docs: Vec<syn::Expr>,
+	/// Span of the function, used for error reporting.
+	pub span: proc_macro2::Span,
+}
+
+/// Attributes for functions in tasks impl block.
+/// Parse for `#[pallet::task_index(expr)]`, `#[pallet::task_condition(expr)]`,
+/// `#[pallet::task_condition_weight(expr)]`, `#[pallet::task_list(expr)]` or
+/// `#[pallet::task_weight(expr)]`.
+pub enum TaskAttr {
+	Index(u8),
+	Condition(syn::Expr),
+	ConditionWeight(syn::Expr),
+	List(syn::Expr),
+	Weight(syn::Expr),
+}
+
+impl syn::parse::Parse for TaskAttr {
+	fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+		input.parse::<syn::Token![#]>()?;
+		let content;
+		syn::bracketed!(content in input);
+		content.parse::<keyword::pallet>()?;
+		content.parse::<syn::Token![::]>()?;
+
+		let lookahead = content.lookahead1();
+		if lookahead.peek(keyword::task_index) {
+			content.parse::<keyword::task_index>()?;
+			let index_content;
+			syn::parenthesized!(index_content in content);
+			let index = index_content.parse::<syn::LitInt>()?;
+			if !index.suffix().is_empty() {
+				let msg = "Number literal must not have a suffix";
+				return Err(syn::Error::new(index.span(), msg))
+			}
+			Ok(TaskAttr::Index(index.base10_parse()?))
+		} else if lookahead.peek(keyword::task_condition_weight) {
+			content.parse::<keyword::task_condition_weight>()?;
+			let weight_content;
+			syn::parenthesized!(weight_content in content);
+			Ok(TaskAttr::ConditionWeight(weight_content.parse::<syn::Expr>()?))
+		} else if lookahead.peek(keyword::task_condition) {
+			content.parse::<keyword::task_condition>()?;
+			let condition_content;
+			syn::parenthesized!(condition_content in content);
+			Ok(TaskAttr::Condition(condition_content.parse::<syn::Expr>()?))
+		} else if lookahead.peek(keyword::task_list) {
+			content.parse::<keyword::task_list>()?;
+			let list_content;
+			syn::parenthesized!(list_content in content);
+			Ok(TaskAttr::List(list_content.parse::<syn::Expr>()?))
+		} else if lookahead.peek(keyword::task_weight) {
+			content.parse::<keyword::task_weight>()?;
+			let weight_content;
+			syn::parenthesized!(weight_content in content);
+			Ok(TaskAttr::Weight(weight_content.parse::<syn::Expr>()?))
+		} else {
+			Err(lookahead.error())
+		}
+	}
+}
+
+impl TasksDef {
+	pub fn try_from(
+		attr_span: proc_macro2::Span,
+		index: usize,
+		item: &mut syn::Item,
+		dev_mode: bool,
+	) -> syn::Result<Self> {
+		let item_impl = if let syn::Item::Impl(item) = item {
+			item
+		} else {
+			return Err(syn::Error::new(item.span(), "Invalid pallet::tasks, expected item impl"))
+		};
+
+		let instances = vec![
+			helper::check_impl_gen(&item_impl.generics, item_impl.impl_token.span())?,
+			helper::check_pallet_struct_usage(&item_impl.self_ty)?,
+		];
+
+		if let Some((_, _, for_)) = item_impl.trait_ {
+			let msg = "Invalid pallet::tasks, expected no trait ident as in \
+				`impl<..> Pallet<..> { .. }`";
+			return Err(syn::Error::new(for_.span(), msg))
+		}
+
+		let mut tasks = vec![];
+		let mut indices = HashMap::new();
+		for item in &mut item_impl.items {
+			let syn::ImplItem::Fn(method) = item else {
+				let msg = "Invalid pallet::tasks, only method accepted";
+				return Err(syn::Error::new(item.span(), msg))
+			};
+
+			if let Some(syn::FnArg::Receiver(receiver)) = method.sig.inputs.first() {
+				let msg = "Invalid pallet::tasks, task function must not take `self`";
+				return Err(syn::Error::new(receiver.span(), msg))
+			}
+
+			if let syn::ReturnType::Default = method.sig.output {
+				let msg = "Invalid pallet::tasks, require return type DispatchResult";
+				return Err(syn::Error::new(method.sig.span(), msg))
+			}
+
+			let mut task_index = None;
+			let mut condition = None;
+			let mut condition_weight = None;
+			let mut list = None;
+			let mut weight = None;
+			for attr in helper::take_item_pallet_attrs::<TaskAttr>(&mut method.attrs)? {
+				let (duplicate, name) = match attr {
+					TaskAttr::Index(i) => (task_index.replace(i).is_some(), "task_index"),
+					TaskAttr::Condition(c) => (condition.replace(c).is_some(), "task_condition"),
+					TaskAttr::ConditionWeight(w) =>
+						(condition_weight.replace(w).is_some(), "task_condition_weight"),
+					TaskAttr::List(l) => (list.replace(l).is_some(), "task_list"),
+					TaskAttr::Weight(w) => (weight.replace(w).is_some(), "task_weight"),
+				};
+				if duplicate {
+					let msg = format!("Invalid pallet::tasks, too many {} attributes given", name);
+					return Err(syn::Error::new(method.sig.span(), msg))
+				}
+			}
+
+			let missing = |name: &str| {
+				let msg = format!(
+					"Invalid pallet::tasks, missing `#[pallet::{}(..)]` attribute on task `{}`",
+					name, method.sig.ident,
+				);
+				syn::Error::new(method.sig.span(), msg)
+			};
+
+			let task_index = task_index.ok_or_else(|| missing("task_index"))?;
+			let condition = condition.ok_or_else(|| missing("task_condition"))?;
+			let list = list.ok_or_else(|| missing("task_list"))?;
+			// a zero weight is injected during expansion when dev mode is enabled and no weight
+			// has been specified on the task
+			if weight.is_none() && !dev_mode {
+				return Err(missing("task_weight"))
+			}
+			if condition_weight.is_none() && !dev_mode {
+				return Err(missing("task_condition_weight"))
+			}
+
+			if let Some(used_fn) = indices.insert(task_index, method.sig.ident.clone()) {
+				let msg = format!(
+					"Task indices are conflicting: Both functions {} and {} are at index {}",
+					used_fn, method.sig.ident, task_index,
+				);
+				let mut err = syn::Error::new(used_fn.span(), &msg);
+				err.combine(syn::Error::new(method.sig.ident.span(), msg));
+				return Err(err)
+			}
+
+			let mut args = vec![];
+			for arg in method.sig.inputs.iter() {
+				let syn::FnArg::Typed(arg) = arg else {
+					unreachable!("Receiver is rejected above; qed");
+				};
+
+				let arg_ident = if let syn::Pat::Ident(pat) = &*arg.pat {
+					pat.ident.clone()
+				} else {
+					let msg = "Invalid pallet::tasks, argument must be ident";
+					return Err(syn::Error::new(arg.pat.span(), msg))
+				};
+
+				args.push((arg_ident, arg.ty.clone()));
+			}
+
+			tasks.push(TaskDef {
+				name: method.sig.ident.clone(),
+				args,
+				task_index,
+				condition,
+				condition_weight,
+				list,
+				weight,
+				docs: get_doc_literals(&method.attrs),
+				span: method.sig.span(),
+			});
+		}
+
+		Ok(Self {
+			index,
+			attr_span,
+			instances,
+			tasks,
+			where_clause: item_impl.generics.where_clause.clone(),
+			docs: get_doc_literals(&item_impl.attrs),
+		})
+	}
+}
@@ -140,6 +140,7 @@ mod storage_types {
 	syn::custom_keyword!(CountedStorageMap);
 	syn::custom_keyword!(StorageDoubleMap);
 	syn::custom_keyword!(StorageNMap);
+	syn::custom_keyword!(CountedStorageNMap);
 }
 
 /// The supported storage types
@@ -218,6 +219,20 @@ enum StorageType {
 		_trailing_comma: Option<Token![,]>,
 		_gt_token: Token![>],
 	},
+	CountedNMap {
+		_kw: storage_types::CountedStorageNMap,
+		_lt_token: Token![<],
+		prefix: SimplePath,
+		prefix_generics: Option<TypeGenerics>,
+		_paren_comma: Token![,],
+		_paren_token: token::Paren,
+		key_types: Punctuated<Type, Token![,]>,
+		_value_comma: Token![,],
+		value_ty: Type,
+		query_type: Option<(Token![,], Type)>,
+		_trailing_comma: Option<Token![,]>,
+		_gt_token: Token![>],
+	},
 }
 
 impl StorageType {
@@ -301,13 +316,21 @@ impl StorageType {
 					>;
 				}
 			},
+			Self::CountedNMap { value_ty, query_type, key_types, prefix_generics, .. } |
 			Self::NMap { value_ty, query_type, key_types, prefix_generics, .. } => {
 				let query_type = query_type.as_ref().map(|(c, t)| quote!(#c #t));
 				let key_types = key_types.iter();
+				let map_type = Ident::new(
+					match self {
+						Self::NMap { .. } => "StorageNMap",
+						_ => "CountedStorageNMap",
+					},
+					Span::call_site(),
+				);
 
 				quote! {
 					#( #attributes )*
-					#visibility type #storage_name #storage_generics = #crate_::storage::types::StorageNMap<
+					#visibility type #storage_name #storage_generics = #crate_::storage::types::#map_type<
 						#storage_instance #prefix_generics,
 						( #( #key_types ),* ),
 						#value_ty
@@ -325,6 +348,7 @@ impl StorageType {
 			Self::Map { prefix, .. } |
 			Self::CountedMap { prefix, .. } |
 			Self::NMap { prefix, .. } |
+			Self::CountedNMap { prefix, .. } |
 			Self::DoubleMap { prefix, .. } => prefix,
 		}
 	}
@@ -336,6 +360,7 @@ impl StorageType {
 			Self::Map { prefix_generics, .. } |
 			Self::CountedMap { prefix_generics, .. } |
 			Self::NMap { prefix_generics, .. } |
+			Self::CountedNMap { prefix_generics, .. } |
 			Self::DoubleMap { prefix_generics, .. } => prefix_generics.as_ref(),
 		}
 	}
@@ -444,6 +469,22 @@ impl Parse for StorageType {
 				_trailing_comma: input.peek(Token![,]).then(|| input.parse()).transpose()?,
 				_gt_token: input.parse()?,
 			})
+		} else if lookahead.peek(storage_types::CountedStorageNMap) {
+			let content;
+			Ok(Self::CountedNMap {
+				_kw: input.parse()?,
+				_lt_token: input.parse()?,
+				prefix: input.parse()?,
+				prefix_generics: parse_pallet_generics(input)?,
+				_paren_comma: input.parse()?,
+				_paren_token: parenthesized!(content in input),
+				key_types: Punctuated::parse_terminated(&content)?,
+				_value_comma: input.parse()?,
+				value_ty: input.parse()?,
+				query_type: parse_query_type(input)?,
+				_trailing_comma: input.peek(Token![,]).then(|| input.parse()).transpose()?,
+				_gt_token: input.parse()?,
+			})
 		} else {
 			Err(lookahead.error())
 		}
@@ -521,7 +562,11 @@ pub fn storage_alias(input: TokenStream) -> Result<TokenStream> {
 		input.storage_type.prefix(),
 		input.storage_type.prefix_generics(),
 		&input.visibility,
-		matches!(input.storage_type, StorageType::CountedMap { .. }),
+		match input.storage_type {
+			StorageType::CountedMap { .. } => Some(CounterType::CountedMap),
+			StorageType::CountedNMap { .. } => Some(CounterType::CountedNMap),
+			_ => None,
+		},
 	)?;
 
 	let definition = input.storage_type.generate_type_declaration(
@@ -542,6 +587,12 @@ pub fn storage_alias(input: TokenStream) -> Result<TokenStream> {
 	})
 }
 
+/// The type of the counter a counted storage needs an instance for.
+enum CounterType {
+	CountedMap,
+	CountedNMap,
+}
+
 /// The storage instance to use for the storage alias.
 struct StorageInstance {
 	name: Ident,
@@ -557,7 +608,7 @@ fn generate_storage_instance(
 	prefix: &SimplePath,
 	prefix_generics: Option<&TypeGenerics>,
 	visibility: &Visibility,
-	is_counted_map: bool,
+	counter_type: Option<CounterType>,
 ) -> Result<StorageInstance> {
 	if let Some(ident) = prefix.get_ident().filter(|i| *i == "_") {
 		return Err(Error::new(ident.span(), "`_` is not allowed as prefix by `storage_alias`."))
@@ -597,9 +648,16 @@ fn generate_storage_instance(
 	let name = Ident::new(&name_str, Span::call_site());
 	let storage_name_str = storage_name.to_string();
 
-	let counter_code = is_counted_map.then(|| {
+	let counter_code = counter_type.map(|counter_type| {
 		let counter_name = Ident::new(&counter_prefix(&name_str), Span::call_site());
 		let counter_storage_name_str = counter_prefix(&storage_name_str);
+		let counter_instance = Ident::new(
+			match counter_type {
+				CounterType::CountedMap => "CountedStorageMapInstance",
+				CounterType::CountedNMap => "CountedStorageNMapInstance",
+			},
+			Span::call_site(),
+		);
 
 		quote! {
 			#visibility struct #counter_name< #impl_generics >(
@@ -616,7 +674,7 @@ fn generate_storage_instance(
 				const STORAGE_PREFIX: &'static str = #counter_storage_name_str;
 			}
 
-			impl<#impl_generics> #crate_::storage::types::CountedStorageMapInstance
+			impl<#impl_generics> #crate_::storage::types::#counter_instance
 				for #name< #type_generics > #where_clause
 			{
 				type CounterPrefix = #counter_name < #type_generics >;
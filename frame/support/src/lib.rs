@@ -132,8 +132,9 @@ impl TypeId for PalletId {
 }
 
 /// Generate a new type alias for [`storage::types::StorageValue`],
-/// [`storage::types::StorageMap`], [`storage::types::StorageDoubleMap`]
-/// and [`storage::types::StorageNMap`].
+/// [`storage::types::StorageMap`], [`storage::types::CountedStorageMap`],
+/// [`storage::types::StorageDoubleMap`], [`storage::types::StorageNMap`]
+/// and [`storage::types::CountedStorageNMap`].
 ///
 /// Useful for creating a *storage-like* struct for test and migrations.
 ///
@@ -181,6 +182,11 @@ impl TypeId for PalletId {
 /// #[storage_alias]
 /// type SomeNMap = StorageNMap<Prefix, (NMapKey<Twox64Concat, u32>, NMapKey<Twox64Concat, u64>), Vec<u8>>;
 ///
+/// // and the counted variant of NMap
+/// #[storage_alias]
+/// type SomeCountedNMap =
+/// 	CountedStorageNMap<Prefix, (NMapKey<Twox64Concat, u32>, NMapKey<Twox64Concat, u64>), Vec<u8>>;
+///
 /// // Using pallet name as prefix.
 /// //
 /// // When the first generic argument is taking generic arguments it is expected to be a pallet.
@@ -1550,8 +1556,8 @@ pub mod pallet_prelude {
 		storage::{
 			bounded_vec::BoundedVec,
 			types::{
-				CountedStorageMap, Key as NMapKey, OptionQuery, ResultQuery, StorageDoubleMap,
-				StorageMap, StorageNMap, StorageValue, ValueQuery,
+				CountedStorageMap, CountedStorageNMap, Key as NMapKey, OptionQuery, ResultQuery,
+				StorageDoubleMap, StorageMap, StorageNMap, StorageValue, ValueQuery,
 			},
 			StorageList,
 		},
@@ -2060,7 +2066,10 @@ pub mod pallet_prelude {
 /// * [`CountedStorageMap`](`pallet_prelude::CountedStorageMap`) expects `Hasher`, `Key`,
 ///   `Value` and optionally `QueryKind` and `OnEmpty`,
 /// * [`StorageDoubleMap`](`pallet_prelude::StorageDoubleMap`) expects `Hasher1`, `Key1`,
-///   `Hasher2`, `Key2`, `Value` and optionally `QueryKind` and `OnEmpty`.
+///   `Hasher2`, `Key2`, `Value` and optionally `QueryKind` and `OnEmpty`,
+/// * [`StorageNMap`](`pallet_prelude::StorageNMap`) and
+///   [`CountedStorageNMap`](`pallet_prelude::CountedStorageNMap`) expect `Key`, `Value` and
+///   optionally `QueryKind` and `OnEmpty`.
 ///
 /// For unnamed generic arguments: Their first generic must be `_` as it is replaced by the
 /// macro and other generic must declared as a normal generic type declaration.
@@ -2073,6 +2082,9 @@ pub mod pallet_prelude {
 /// For the [`CountedStorageMap`](`pallet_prelude::CountedStorageMap`) variant, the `Prefix`
 /// also implements
 /// [`CountedStorageMapInstance`](`frame_support::storage::types::CountedStorageMapInstance`).
+/// Likewise, for the [`CountedStorageNMap`](`pallet_prelude::CountedStorageNMap`) variant it
+/// implements
+/// [`CountedStorageNMapInstance`](`frame_support::storage::types::CountedStorageNMapInstance`).
 /// It also associates a [`CounterPrefix`](`pallet_prelude::CounterPrefix'), which is
 /// implemented the same as above, but the storage prefix is prepend with `"CounterFor"`. E.g.
 /// if runtime names the pallet "MyExample" then the storage `type Foo<T> =
@@ -2187,8 +2199,11 @@ pub mod pallet_prelude {
 /// `_GeneratedPrefixForStorage$NameOfStorage`, and implements
 /// [`StorageInstance`](traits::StorageInstance) on it using the pallet and storage name. It
 /// then uses it as the first generic of the aliased type. For
-/// [`CountedStorageMap`](`pallet_prelude::CountedStorageMap`),
+/// [`CountedStorageMap`](`pallet_prelude::CountedStorageMap`) and
+/// [`CountedStorageNMap`](`pallet_prelude::CountedStorageNMap`),
 /// [`CountedStorageMapInstance`](`frame_support::storage::types::CountedStorageMapInstance`)
+/// (resp.
+/// [`CountedStorageNMapInstance`](`frame_support::storage::types::CountedStorageNMapInstance`))
 /// is implemented, and another similar struct is generated.
 ///
 /// For a named generic, the macro will reorder the generics, and remove the names.
@@ -2905,8 +2920,9 @@ pub mod pallet_macros {
 		call_index, compact, composite_enum, config, constant,
 		disable_frame_system_supertrait_check, error, event, extra_constants, generate_deposit,
 		generate_store, genesis_build, genesis_config, getter, hooks, import_section, inherent,
-		no_default, origin, pallet_section, storage, storage_prefix, storage_version, type_value,
-		unbounded, validate_unsigned, weight, whitelist_storage,
+		no_default, origin, pallet_section, storage, storage_prefix, storage_version,
+		task_condition, task_condition_weight, task_index, task_list, task_weight, tasks,
+		type_value, unbounded, validate_unsigned, weight, whitelist_storage,
 	};
 }
 
@@ -23,6 +23,7 @@ use codec::FullCodec;
 use sp_std::prelude::*;
 
 mod counted_map;
+mod counted_nmap;
 mod double_map;
 mod key;
 mod map;
@@ -30,6 +31,7 @@ mod nmap;
 mod value;
 
 pub use counted_map::{CountedStorageMap, CountedStorageMapInstance};
+pub use counted_nmap::{CountedStorageNMap, CountedStorageNMapInstance};
 pub use double_map::StorageDoubleMap;
 pub use key::{
 	EncodeLikeTuple, HasKeyPrefix, HasReversibleKeyPrefix, Key, KeyGenerator,
@@ -133,7 +135,8 @@ where
 
 /// Build the metadata of a storage.
 ///
-/// Implemented by each of the storage types: value, map, countedmap, doublemap and nmap.
+/// Implemented by each of the storage types: value, map, countedmap, doublemap, nmap and
+/// countednmap.
 pub trait StorageEntryMetadataBuilder {
 	/// Build into `entries` the storage metadata entries of a storage given some `docs`.
 	fn build_metadata(doc: Vec<&'static str>, entries: &mut Vec<StorageEntryMetadataIR>);
@@ -103,6 +103,15 @@ pub use dispatch::{
 	OriginTrait, TryMapSuccess, TryWithMorphedArg, UnfilteredDispatchable,
 };
 
+pub mod safe_mode;
+pub use safe_mode::{SafeMode, SafeModeError, SafeModeNotify};
+
+mod tasks;
+pub use tasks::Task;
+
+pub mod tx_pause;
+pub use tx_pause::{TransactionPause, TransactionPauseError};
+
 mod voting;
 pub use voting::{ClassCountOf, PollStatus, Polling, VoteTally};
 
@@ -0,0 +1,432 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::traits::{
+	fungible::{Balanced, Inspect},
+	tokens::{Fortitude, Precision, Preservation},
+	Imbalance,
+};
+use core::fmt::Debug;
+use sp_arithmetic::traits::AtLeast8BitUnsigned;
+use sp_runtime::traits::Zero;
+
+/// Test the `issue` and `resolve` functions for successfully minting into an account.
+///
+/// This test checks that `issue` increases the total issuance and returns a credit of the issued
+/// amount, and that resolving that credit into an account credits the account while keeping the
+/// total issuance in line with the account balances.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `Balanced<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn issue_and_resolve_credit<T, AccountId>(_dust_trap: Option<AccountId>)
+where
+	T: Balanced<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	let initial_total_issuance = T::total_issuance();
+	let account = AccountId::from(10);
+
+	// Test: Issue some funds
+	let amount = T::minimum_balance() + 10.into();
+	let credit = T::issue(amount);
+
+	// Verify: The credit matches the issued amount and the total issuance grew accordingly
+	assert_eq!(credit.peek(), amount);
+	assert_eq!(T::total_issuance(), initial_total_issuance + amount);
+
+	// Test: Resolve the credit into an account
+	assert!(T::resolve(&account, credit).is_ok());
+
+	// Verify: The account was credited and the total issuance is unchanged
+	assert_eq!(T::balance(&account), amount);
+	assert_eq!(T::total_balance(&account), amount);
+	assert_eq!(T::total_issuance(), initial_total_issuance + amount);
+}
+
+/// Test the `resolve` function for a credit below the minimum balance.
+///
+/// This test verifies that resolving a credit smaller than the minimum balance into a new account
+/// fails and hands back the original credit, which then reduces the total issuance when dropped.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `Balanced<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn issue_and_resolve_credit_below_minimum<T, AccountId>(_dust_trap: Option<AccountId>)
+where
+	T: Balanced<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	// This test means nothing if there is no minimum balance
+	if T::minimum_balance() <= T::Balance::from(1) {
+		return
+	}
+
+	let initial_total_issuance = T::total_issuance();
+	let account = AccountId::from(10);
+
+	// Test: Resolve a credit below the minimum balance into a new account
+	let amount = T::minimum_balance() - 1.into();
+	let credit = T::issue(amount);
+	let credit = match T::resolve(&account, credit) {
+		Ok(()) => panic!("resolving below the minimum balance must fail"),
+		Err(credit) => credit,
+	};
+
+	// Verify: The credit was handed back in full and the account was not created
+	assert_eq!(credit.peek(), amount);
+	assert_eq!(T::balance(&account), T::Balance::zero());
+
+	// Verify: Dropping the credit reverts the issuance
+	drop(credit);
+	assert_eq!(T::total_issuance(), initial_total_issuance);
+}
+
+/// Test the `rescind` and `settle` functions for successfully burning from an account.
+///
+/// This test checks that `rescind` reduces the total issuance and returns a debt of the rescinded
+/// amount, and that settling that debt against an account debits the account.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `Balanced<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn rescind_and_settle_debt<T, AccountId>(_dust_trap: Option<AccountId>)
+where
+	T: Balanced<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	let account = AccountId::from(10);
+	let initial_balance = T::minimum_balance() + 10.into();
+	assert!(T::resolve(&account, T::issue(initial_balance)).is_ok());
+	let initial_total_issuance = T::total_issuance();
+
+	// Test: Rescind some funds
+	let amount = T::Balance::from(5);
+	let debt = T::rescind(amount);
+
+	// Verify: The debt matches the rescinded amount and the total issuance shrank accordingly
+	assert_eq!(debt.peek(), amount);
+	assert_eq!(T::total_issuance(), initial_total_issuance - amount);
+
+	// Test: Settle the debt against the account
+	let credit = match T::settle(&account, debt, Preservation::Expendable) {
+		Ok(credit) => credit,
+		Err(_) => panic!("settling a debt covered by the balance must succeed"),
+	};
+
+	// Verify: Nothing is left over, the account was debited and the total issuance is unchanged
+	assert_eq!(credit.peek(), T::Balance::zero());
+	assert_eq!(T::balance(&account), initial_balance - amount);
+	assert_eq!(T::total_issuance(), initial_total_issuance - amount);
+}
+
+/// Test the `settle` function for handling insufficient funds.
+///
+/// This test verifies that settling a debt larger than the account balance fails and hands back
+/// the original debt, which then restores the total issuance when dropped.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `Balanced<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn rescind_and_settle_debt_insufficient_funds<T, AccountId>(_dust_trap: Option<AccountId>)
+where
+	T: Balanced<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	let account_0 = AccountId::from(10);
+	let account_1 = AccountId::from(20);
+	let initial_balance = T::minimum_balance() + 10.into();
+	assert!(T::resolve(&account_0, T::issue(initial_balance)).is_ok());
+	assert!(T::resolve(&account_1, T::issue(initial_balance)).is_ok());
+	let initial_total_issuance = T::total_issuance();
+
+	// Test: Settle a debt larger than the account balance
+	let amount = initial_balance + 1.into();
+	let debt = T::rescind(amount);
+	let debt = match T::settle(&account_0, debt, Preservation::Expendable) {
+		Ok(_) => panic!("settling a debt larger than the balance must fail"),
+		Err(debt) => debt,
+	};
+
+	// Verify: The debt was handed back in full and the account is untouched
+	assert_eq!(debt.peek(), amount);
+	assert_eq!(T::balance(&account_0), initial_balance);
+
+	// Verify: Dropping the debt reverts the rescinded issuance
+	drop(debt);
+	assert_eq!(T::total_issuance(), initial_total_issuance);
+}
+
+/// Test the `pair` function.
+///
+/// This test checks that `pair` produces a debt and a credit of equal value which, once dropped,
+/// leave the total issuance unchanged.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `Balanced<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn pair_success<T, AccountId>(_dust_trap: Option<AccountId>)
+where
+	T: Balanced<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	let account = AccountId::from(10);
+	assert!(T::resolve(&account, T::issue(T::minimum_balance() + 10.into())).is_ok());
+	let initial_total_issuance = T::total_issuance();
+
+	// Test: Create a pair of imbalances
+	let amount = T::Balance::from(5);
+	let (debt, credit) = T::pair(amount);
+
+	// Verify: Both imbalances have the requested value
+	assert_eq!(debt.peek(), amount);
+	assert_eq!(credit.peek(), amount);
+
+	// Verify: Dropping both leaves the total issuance unchanged
+	drop(debt);
+	drop(credit);
+	assert_eq!(T::total_issuance(), initial_total_issuance);
+}
+
+/// Test the `deposit` function for successfully depositing into an account.
+///
+/// This test checks that `deposit` credits the account and returns a debt of the deposited amount
+/// which, once dropped, increases the total issuance by the same amount.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `Balanced<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn deposit_success<T, AccountId>(_dust_trap: Option<AccountId>)
+where
+	T: Balanced<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	let initial_total_issuance = T::total_issuance();
+	let account = AccountId::from(10);
+
+	// Test: Deposit into a new account
+	let amount = T::minimum_balance() + 10.into();
+	let debt = T::deposit(&account, amount, Precision::Exact).unwrap();
+
+	// Verify: The debt matches the deposit and the account was credited
+	assert_eq!(debt.peek(), amount);
+	assert_eq!(T::balance(&account), amount);
+	assert_eq!(T::total_balance(&account), amount);
+
+	// Verify: Dropping the debt increases the total issuance
+	drop(debt);
+	assert_eq!(T::total_issuance(), initial_total_issuance + amount);
+}
+
+/// Test the `deposit` function for handling amounts below the minimum balance.
+///
+/// This test verifies that depositing less than the minimum balance into a new account with
+/// `Precision::Exact` returns an error and leaves the total issuance unchanged.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `Balanced<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn deposit_below_minimum<T, AccountId>(_dust_trap: Option<AccountId>)
+where
+	T: Balanced<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	// This test means nothing if there is no minimum balance
+	if T::minimum_balance() <= T::Balance::from(1) {
+		return
+	}
+
+	let initial_total_issuance = T::total_issuance();
+	let account = AccountId::from(10);
+
+	// Verify: Depositing below the minimum balance into a new account returns an Err
+	let amount = T::minimum_balance() - 1.into();
+	assert!(T::deposit(&account, amount, Precision::Exact).is_err());
+
+	// Verify: Noop
+	assert_eq!(T::balance(&account), T::Balance::zero());
+	assert_eq!(T::total_issuance(), initial_total_issuance);
+}
+
+/// Test the `withdraw` function for successfully withdrawing from an account.
+///
+/// This test checks that `withdraw` debits the account and returns a credit of the withdrawn
+/// amount which, once dropped, reduces the total issuance by the same amount.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `Balanced<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn withdraw_success<T, AccountId>(_dust_trap: Option<AccountId>)
+where
+	T: Balanced<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	let account = AccountId::from(10);
+	let initial_balance = T::minimum_balance() + 10.into();
+	assert!(T::resolve(&account, T::issue(initial_balance)).is_ok());
+	let initial_total_issuance = T::total_issuance();
+
+	// Test: Withdraw from the account
+	let amount = T::Balance::from(5);
+	let credit = T::withdraw(
+		&account,
+		amount,
+		Precision::Exact,
+		Preservation::Expendable,
+		Fortitude::Polite,
+	)
+	.unwrap();
+
+	// Verify: The credit matches the withdrawal and the account was debited
+	assert_eq!(credit.peek(), amount);
+	assert_eq!(T::balance(&account), initial_balance - amount);
+
+	// Verify: Dropping the credit reduces the total issuance
+	drop(credit);
+	assert_eq!(T::total_issuance(), initial_total_issuance - amount);
+}
+
+/// Test the `withdraw` function for handling insufficient funds.
+///
+/// This test verifies that withdrawing more than the account balance with `Precision::Exact`
+/// returns an error and does not change the account balance or total issuance.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `Balanced<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn withdraw_insufficient_funds<T, AccountId>(_dust_trap: Option<AccountId>)
+where
+	T: Balanced<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	let account = AccountId::from(10);
+	let initial_balance = T::minimum_balance() + 10.into();
+	assert!(T::resolve(&account, T::issue(initial_balance)).is_ok());
+	let initial_total_issuance = T::total_issuance();
+
+	// Verify: Withdrawing more than the balance returns an Err
+	assert!(T::withdraw(
+		&account,
+		initial_balance + 1.into(),
+		Precision::Exact,
+		Preservation::Expendable,
+		Fortitude::Polite,
+	)
+	.is_err());
+
+	// Verify: Noop
+	assert_eq!(T::balance(&account), initial_balance);
+	assert_eq!(T::total_issuance(), initial_total_issuance);
+}
+
+/// Test the `withdraw` function for leaving dust behind with `Preservation::Expendable`.
+///
+/// This test verifies that withdrawing such that less than the minimum balance would remain reaps
+/// the account, and that every unit removed from it is accounted for: either as part of the
+/// returned credit, in the dust trap, or as a reduction of the total issuance.
+///
+/// # Parameters
+///
+/// - dust_trap: An optional account identifier to which dust will be collected. If None, dust will
+///   be removed from the total issuance.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `Balanced<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn withdraw_expendable_dust<T, AccountId>(dust_trap: Option<AccountId>)
+where
+	T: Balanced<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	// This test means nothing if there is no minimum balance
+	if T::minimum_balance() <= T::Balance::from(1) {
+		return
+	}
+
+	let account = AccountId::from(10);
+	let initial_balance = T::minimum_balance() + 10.into();
+	assert!(T::resolve(&account, T::issue(initial_balance)).is_ok());
+	let initial_total_issuance = T::total_issuance();
+	let initial_dust_trap_balance = match dust_trap.clone() {
+		Some(dust_trap) => T::total_balance(&dust_trap),
+		None => T::Balance::zero(),
+	};
+
+	// Test: Withdraw such that only dust would remain
+	let amount = T::Balance::from(11);
+	let credit = T::withdraw(
+		&account,
+		amount,
+		Precision::Exact,
+		Preservation::Expendable,
+		Fortitude::Polite,
+	)
+	.unwrap();
+
+	// Verify: At least the requested amount was withdrawn and the account was reaped
+	assert!(credit.peek() >= amount);
+	assert_eq!(T::balance(&account), T::Balance::zero());
+	assert_eq!(T::total_balance(&account), T::Balance::zero());
+
+	// Verify: Once the credit is dropped, the entire balance is accounted for
+	drop(credit);
+	let dust_trap_gain = match dust_trap {
+		Some(dust_trap) => T::total_balance(&dust_trap) - initial_dust_trap_balance,
+		None => T::Balance::zero(),
+	};
+	assert_eq!(T::total_issuance(), initial_total_issuance - initial_balance + dust_trap_gain);
+}
@@ -1 +1,5 @@
+pub mod balanced;
 pub mod inspect_mutate;
+pub mod mutate_freeze;
+pub mod mutate_hold;
+pub mod unbalanced;
@@ -0,0 +1,194 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::traits::{
+	fungible::{Inspect, Mutate, MutateFreeze},
+	tokens::{Fortitude, Preservation, WithdrawConsequence},
+};
+use core::fmt::Debug;
+use sp_arithmetic::traits::AtLeast8BitUnsigned;
+use sp_runtime::traits::Zero;
+
+/// Test the `set_freeze` function for successfully freezing funds.
+///
+/// This test checks that `set_freeze` sets the frozen balance of the account for the given
+/// identifier, replacing any previous value even if it is lower, and that it changes neither the
+/// balance of the account nor the total issuance.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `MutateFreeze<AccountId>` and `Mutate<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn set_freeze_success<T, AccountId>(id: T::Id, _dust_trap: Option<AccountId>)
+where
+	T: MutateFreeze<AccountId> + Mutate<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	let account = AccountId::from(10);
+	let initial_balance = T::minimum_balance() + 10.into();
+	T::set_balance(&account, initial_balance);
+	let initial_total_issuance = T::total_issuance();
+
+	// Test: Freeze some funds
+	assert!(T::can_freeze(&id, &account));
+	T::set_freeze(&id, &account, 5.into()).unwrap();
+
+	// Verify: The frozen balance was set and no funds moved
+	assert_eq!(T::balance_frozen(&id, &account), 5.into());
+	assert_eq!(T::balance(&account), initial_balance);
+	assert_eq!(T::total_balance(&account), initial_balance);
+
+	// Test: Set a lower freeze for the same identifier
+	T::set_freeze(&id, &account, 3.into()).unwrap();
+
+	// Verify: The frozen balance was replaced
+	assert_eq!(T::balance_frozen(&id, &account), 3.into());
+
+	// Verify: The total issuance is untouched
+	assert_eq!(T::total_issuance(), initial_total_issuance);
+}
+
+/// Test the `extend_freeze` function.
+///
+/// This test checks that `extend_freeze` only ever raises the frozen balance of the account for
+/// the given identifier, and is a no-op when the amount is not greater than the existing freeze.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `MutateFreeze<AccountId>` and `Mutate<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn extend_freeze_success<T, AccountId>(id: T::Id, _dust_trap: Option<AccountId>)
+where
+	T: MutateFreeze<AccountId> + Mutate<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	let account = AccountId::from(10);
+	T::set_balance(&account, T::minimum_balance() + 10.into());
+
+	// Test: Extend a freeze that does not exist yet
+	T::extend_freeze(&id, &account, 5.into()).unwrap();
+
+	// Verify: The freeze was created
+	assert_eq!(T::balance_frozen(&id, &account), 5.into());
+
+	// Test: Extend the freeze by a lower amount and by nothing
+	T::extend_freeze(&id, &account, 3.into()).unwrap();
+	T::extend_freeze(&id, &account, T::Balance::zero()).unwrap();
+
+	// Verify: Noop
+	assert_eq!(T::balance_frozen(&id, &account), 5.into());
+
+	// Test: Extend the freeze by a higher amount
+	T::extend_freeze(&id, &account, 8.into()).unwrap();
+
+	// Verify: The freeze was raised
+	assert_eq!(T::balance_frozen(&id, &account), 8.into());
+}
+
+/// Test the `thaw` function.
+///
+/// This test checks that both `thaw` and setting a freeze of zero remove the freeze for the given
+/// identifier.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `MutateFreeze<AccountId>` and `Mutate<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn thaw_success<T, AccountId>(id: T::Id, _dust_trap: Option<AccountId>)
+where
+	T: MutateFreeze<AccountId> + Mutate<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	let account = AccountId::from(10);
+	let initial_balance = T::minimum_balance() + 10.into();
+	T::set_balance(&account, initial_balance);
+
+	// Test: Thaw an existing freeze
+	T::set_freeze(&id, &account, 5.into()).unwrap();
+	T::thaw(&id, &account).unwrap();
+
+	// Verify: The freeze is gone
+	assert_eq!(T::balance_frozen(&id, &account), T::Balance::zero());
+
+	// Test: Set an existing freeze to zero
+	T::set_freeze(&id, &account, 5.into()).unwrap();
+	T::set_freeze(&id, &account, T::Balance::zero()).unwrap();
+
+	// Verify: The freeze is gone and the funds are untouched
+	assert_eq!(T::balance_frozen(&id, &account), T::Balance::zero());
+	assert_eq!(T::balance(&account), initial_balance);
+}
+
+/// Test that frozen funds cannot be withdrawn.
+///
+/// This test verifies that a freeze reduces the reducible balance of the account, that withdrawals
+/// and transfers eating into the frozen funds fail, and that a freeze exceeding the balance of the
+/// account leaves nothing reducible.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `MutateFreeze<AccountId>` and `Mutate<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn freeze_restricts_withdrawals<T, AccountId>(id: T::Id, _dust_trap: Option<AccountId>)
+where
+	T: MutateFreeze<AccountId> + Mutate<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	let account_0 = AccountId::from(10);
+	let account_1 = AccountId::from(20);
+	let initial_balance = T::minimum_balance() + 10.into();
+	T::set_balance(&account_0, initial_balance);
+	T::set_balance(&account_1, initial_balance);
+
+	// Test: Freeze all but 5 units of the balance
+	T::set_freeze(&id, &account_0, T::minimum_balance() + 5.into()).unwrap();
+
+	// Verify: Only the unfrozen funds are reducible
+	let reducible = T::reducible_balance(&account_0, Preservation::Expendable, Fortitude::Polite);
+	assert_eq!(reducible, 5.into());
+	assert_eq!(T::can_withdraw(&account_0, 5.into()), WithdrawConsequence::Success);
+	assert_ne!(T::can_withdraw(&account_0, 6.into()), WithdrawConsequence::Success);
+
+	// Verify: Transferring into the frozen funds returns an Err
+	T::transfer(&account_0, &account_1, 6.into(), Preservation::Expendable).unwrap_err();
+	assert_eq!(T::balance(&account_0), initial_balance);
+
+	// Verify: Transferring the unfrozen funds is fine
+	T::transfer(&account_0, &account_1, 5.into(), Preservation::Expendable).unwrap();
+	assert_eq!(T::balance(&account_0), initial_balance - 5.into());
+	assert_eq!(T::balance(&account_1), initial_balance + 5.into());
+
+	// Test: Freeze more than the balance of the account
+	T::set_freeze(&id, &account_0, initial_balance * 2.into()).unwrap();
+
+	// Verify: Nothing is reducible
+	let reducible = T::reducible_balance(&account_0, Preservation::Expendable, Fortitude::Polite);
+	assert_eq!(reducible, T::Balance::zero());
+	T::transfer(&account_0, &account_1, 1.into(), Preservation::Expendable).unwrap_err();
+}
@@ -0,0 +1,450 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::traits::{
+	fungible::{Inspect, Unbalanced},
+	tokens::{Fortitude, Precision, Preservation},
+};
+use core::fmt::Debug;
+use sp_arithmetic::traits::AtLeast8BitUnsigned;
+use sp_runtime::traits::{Bounded, Zero};
+
+/// Test the `write_balance` function for successfully setting an account balance.
+///
+/// This test checks that `write_balance` sets the balance of an account to the given value and
+/// reports no dust. Being a low-level function, it must not touch the total issuance.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `Unbalanced<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn write_balance_success<T, AccountId>(_dust_trap: Option<AccountId>)
+where
+	T: Unbalanced<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	let initial_total_issuance = T::total_issuance();
+	let account = AccountId::from(10);
+
+	// Test: Write a balance above the minimum
+	let amount = T::minimum_balance() + 10.into();
+	let dust = T::write_balance(&account, amount).unwrap();
+
+	// Verify: No dust was produced and the balance was written
+	assert_eq!(dust, None);
+	assert_eq!(T::balance(&account), amount);
+	assert_eq!(T::total_balance(&account), amount);
+
+	// Test: Write a lower balance that is still above the minimum
+	let amount = T::minimum_balance() + 5.into();
+	let dust = T::write_balance(&account, amount).unwrap();
+
+	// Verify: No dust was produced and the balance was written
+	assert_eq!(dust, None);
+	assert_eq!(T::balance(&account), amount);
+
+	// Verify: The total issuance is untouched
+	assert_eq!(T::total_issuance(), initial_total_issuance);
+}
+
+/// Test the `write_balance` function for writing a balance below the minimum.
+///
+/// This test verifies that writing a non-zero balance below the minimum balance reaps the account
+/// and reports the written amount back as dust, leaving it to the caller to account for it.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `Unbalanced<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn write_balance_below_minimum<T, AccountId>(_dust_trap: Option<AccountId>)
+where
+	T: Unbalanced<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	// This test means nothing if there is no minimum balance
+	if T::minimum_balance() <= T::Balance::from(1) {
+		return
+	}
+
+	let initial_total_issuance = T::total_issuance();
+	let account = AccountId::from(10);
+	T::write_balance(&account, T::minimum_balance() + 10.into()).unwrap();
+
+	// Test: Write a balance below the minimum
+	let amount = T::minimum_balance() - 1.into();
+	let dust = T::write_balance(&account, amount).unwrap();
+
+	// Verify: The account was reaped and the written amount is reported as dust
+	assert_eq!(dust, Some(amount));
+	assert_eq!(T::balance(&account), T::Balance::zero());
+	assert_eq!(T::total_balance(&account), T::Balance::zero());
+
+	// Verify: The total issuance is untouched
+	assert_eq!(T::total_issuance(), initial_total_issuance);
+}
+
+/// Test the `set_total_issuance` function.
+///
+/// This test checks that `set_total_issuance` overwrites the total issuance without affecting any
+/// account balance.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `Unbalanced<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn set_total_issuance_success<T, AccountId>(_dust_trap: Option<AccountId>)
+where
+	T: Unbalanced<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	let account = AccountId::from(10);
+	let balance = T::minimum_balance() + 10.into();
+	T::increase_balance(&account, balance, Precision::Exact).unwrap();
+
+	// Test: Set the total issuance to an arbitrary value
+	let total_issuance = T::total_issuance() + 100.into();
+	T::set_total_issuance(total_issuance);
+
+	// Verify: The total issuance was updated and the account balance is unchanged
+	assert_eq!(T::total_issuance(), total_issuance);
+	assert_eq!(T::balance(&account), balance);
+}
+
+/// Test the `increase_balance` function for a successful increase with `Precision::Exact`.
+///
+/// This test checks that the account balance is increased by exactly the requested amount and that
+/// the total issuance is left unchanged.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `Unbalanced<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn increase_balance_exact_success<T, AccountId>(_dust_trap: Option<AccountId>)
+where
+	T: Unbalanced<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	let initial_total_issuance = T::total_issuance();
+	let account = AccountId::from(10);
+
+	// Test: Increase the balance of a new account
+	let amount = T::minimum_balance() + 10.into();
+	let increased = T::increase_balance(&account, amount, Precision::Exact).unwrap();
+
+	// Verify: The balance was increased by the exact amount
+	assert_eq!(increased, amount);
+	assert_eq!(T::balance(&account), amount);
+
+	// Test: Increase the balance of the now existing account
+	let increased = T::increase_balance(&account, 5.into(), Precision::Exact).unwrap();
+
+	// Verify: The balance was increased by the exact amount
+	assert_eq!(increased, 5.into());
+	assert_eq!(T::balance(&account), amount + 5.into());
+	assert_eq!(T::total_balance(&account), amount + 5.into());
+
+	// Verify: The total issuance is untouched
+	assert_eq!(T::total_issuance(), initial_total_issuance);
+}
+
+/// Test the `increase_balance` function for overflow prevention with `Precision::Exact`.
+///
+/// This test ensures that increasing a balance beyond the maximum balance value returns an error
+/// and leaves the account balance unchanged.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `Unbalanced<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn increase_balance_overflow<T, AccountId>(_dust_trap: Option<AccountId>)
+where
+	T: Unbalanced<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	let account = AccountId::from(10);
+	let amount = T::Balance::max_value() - 5.into();
+	T::increase_balance(&account, amount, Precision::Exact).unwrap();
+
+	// Verify: Increasing beyond the maximum balance value returns an Err
+	T::increase_balance(&account, 10.into(), Precision::Exact).unwrap_err();
+
+	// Verify: The balance did not change
+	assert_eq!(T::balance(&account), amount);
+}
+
+/// Test the `increase_balance` function for handling amounts below the minimum balance.
+///
+/// This test verifies that creating an account with less than the minimum balance using
+/// `Precision::Exact` returns an error and does not create the account.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `Unbalanced<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn increase_balance_below_minimum<T, AccountId>(_dust_trap: Option<AccountId>)
+where
+	T: Unbalanced<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	// This test means nothing if there is no minimum balance
+	if T::minimum_balance() <= T::Balance::from(1) {
+		return
+	}
+
+	let account = AccountId::from(10);
+
+	// Verify: Increasing a new account by less than the minimum balance returns an Err
+	let amount = T::minimum_balance() - 1.into();
+	T::increase_balance(&account, amount, Precision::Exact).unwrap_err();
+
+	// Verify: The account was not created
+	assert_eq!(T::balance(&account), T::Balance::zero());
+	assert_eq!(T::total_balance(&account), T::Balance::zero());
+}
+
+/// Test the `decrease_balance` function for a successful decrease with `Precision::Exact`.
+///
+/// This test checks that the account balance is reduced by exactly the requested amount and that
+/// the total issuance is left unchanged.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `Unbalanced<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn decrease_balance_exact_success<T, AccountId>(_dust_trap: Option<AccountId>)
+where
+	T: Unbalanced<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	let account = AccountId::from(10);
+	let initial_balance = T::minimum_balance() + 10.into();
+	T::increase_balance(&account, initial_balance, Precision::Exact).unwrap();
+	T::set_total_issuance(T::total_issuance() + initial_balance);
+	let initial_total_issuance = T::total_issuance();
+
+	// Test: Decrease the balance by an exact amount
+	let amount = T::Balance::from(5);
+	let decreased = T::decrease_balance(
+		&account,
+		amount,
+		Precision::Exact,
+		Preservation::Expendable,
+		Fortitude::Polite,
+	)
+	.unwrap();
+
+	// Verify: The balance was reduced by the exact amount
+	assert_eq!(decreased, amount);
+	assert_eq!(T::balance(&account), initial_balance - amount);
+	assert_eq!(T::total_balance(&account), initial_balance - amount);
+
+	// Verify: The total issuance is untouched
+	assert_eq!(T::total_issuance(), initial_total_issuance);
+}
+
+/// Test the `decrease_balance` function for handling insufficient funds.
+///
+/// This test verifies that decreasing a balance by more than it holds with `Precision::Exact`
+/// returns an error and leaves the account balance unchanged.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `Unbalanced<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn decrease_balance_insufficient_funds<T, AccountId>(_dust_trap: Option<AccountId>)
+where
+	T: Unbalanced<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	let account = AccountId::from(10);
+	let initial_balance = T::minimum_balance() + 10.into();
+	T::increase_balance(&account, initial_balance, Precision::Exact).unwrap();
+	T::set_total_issuance(T::total_issuance() + initial_balance);
+
+	// Verify: Decreasing by more than the balance returns an Err
+	T::decrease_balance(
+		&account,
+		initial_balance + 1.into(),
+		Precision::Exact,
+		Preservation::Expendable,
+		Fortitude::Polite,
+	)
+	.unwrap_err();
+
+	// Verify: The balance did not change
+	assert_eq!(T::balance(&account), initial_balance);
+}
+
+/// Test the `decrease_balance` function with `Preservation::Preserve`.
+///
+/// This test verifies that an exact decrease which would take the account below the minimum
+/// balance fails, while a best-effort decrease only reduces the balance down to the minimum.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `Unbalanced<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn decrease_balance_preserve<T, AccountId>(_dust_trap: Option<AccountId>)
+where
+	T: Unbalanced<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	// This test means nothing if there is no minimum balance
+	if T::minimum_balance() == T::Balance::zero() {
+		return
+	}
+
+	let account = AccountId::from(10);
+	let initial_balance = T::minimum_balance() + 10.into();
+	T::increase_balance(&account, initial_balance, Precision::Exact).unwrap();
+	T::set_total_issuance(T::total_issuance() + initial_balance);
+
+	// Verify: Decreasing the entire balance exactly while preserving the account returns an Err
+	T::decrease_balance(
+		&account,
+		initial_balance,
+		Precision::Exact,
+		Preservation::Preserve,
+		Fortitude::Polite,
+	)
+	.unwrap_err();
+	assert_eq!(T::balance(&account), initial_balance);
+
+	// Test: Decrease the entire balance with a best-effort while preserving the account
+	let decreased = T::decrease_balance(
+		&account,
+		initial_balance,
+		Precision::BestEffort,
+		Preservation::Preserve,
+		Fortitude::Polite,
+	)
+	.unwrap();
+
+	// Verify: Only the balance above the minimum was removed
+	assert_eq!(decreased, 10.into());
+	assert_eq!(T::balance(&account), T::minimum_balance());
+}
+
+/// Test the `decrease_balance` function for leaving dust behind with `Preservation::Expendable`.
+///
+/// This test verifies that decreasing a balance such that less than the minimum balance would
+/// remain reaps the account entirely, rather than leaving the dust in place.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `Unbalanced<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn decrease_balance_expendable_dust<T, AccountId>(_dust_trap: Option<AccountId>)
+where
+	T: Unbalanced<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	// This test means nothing if there is no minimum balance
+	if T::minimum_balance() <= T::Balance::from(1) {
+		return
+	}
+
+	let account = AccountId::from(10);
+	let initial_balance = T::minimum_balance() + 10.into();
+	T::increase_balance(&account, initial_balance, Precision::Exact).unwrap();
+	T::set_total_issuance(T::total_issuance() + initial_balance);
+
+	// Test: Decrease the balance such that only dust would remain
+	let amount = T::Balance::from(11);
+	let decreased = T::decrease_balance(
+		&account,
+		amount,
+		Precision::Exact,
+		Preservation::Expendable,
+		Fortitude::Polite,
+	)
+	.unwrap();
+
+	// Verify: At least the requested amount was removed and no dust remains in the account
+	assert!(decreased >= amount);
+	assert!(decreased <= initial_balance);
+	assert_eq!(T::balance(&account), T::Balance::zero());
+	assert_eq!(T::total_balance(&account), T::Balance::zero());
+}
+
+/// Test the `deactivate` and `reactivate` functions.
+///
+/// This test checks that deactivating and then reactivating the same amount leaves the active
+/// issuance where it started, and that the active issuance never exceeds the total issuance.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `Unbalanced<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn deactivate_and_reactivate_success<T, AccountId>(_dust_trap: Option<AccountId>)
+where
+	T: Unbalanced<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	T::set_total_issuance(T::total_issuance() + 100.into());
+	let initial_total_issuance = T::total_issuance();
+	let initial_active_issuance = T::active_issuance();
+
+	// Test: Deactivate some funds
+	let amount = T::Balance::from(10);
+	T::deactivate(amount);
+
+	// Verify: The active issuance did not grow and the total issuance is untouched
+	assert!(T::active_issuance() <= initial_active_issuance);
+	assert!(T::active_issuance() >= initial_active_issuance - amount);
+	assert_eq!(T::total_issuance(), initial_total_issuance);
+
+	// Test: Reactivate the same funds
+	T::reactivate(amount);
+
+	// Verify: The active issuance is back where it started
+	assert_eq!(T::active_issuance(), initial_active_issuance);
+	assert_eq!(T::total_issuance(), initial_total_issuance);
+}
@@ -0,0 +1,10 @@
+//! Conformance tests for the `fungibles` traits.
+//!
+//! These mirror the suites of `fungible::conformance_tests`, with every test additionally taking
+//! the `asset_id` of an existing asset to run against.
+
+pub mod balanced;
+pub mod inspect_mutate;
+pub mod mutate_freeze;
+pub mod mutate_hold;
+pub mod unbalanced;
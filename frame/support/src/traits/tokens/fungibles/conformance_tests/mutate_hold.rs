@@ -0,0 +1,539 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::traits::{
+	fungibles::{Inspect, Mutate, MutateHold},
+	tokens::{Fortitude, Precision, Preservation, Restriction},
+};
+use core::fmt::Debug;
+use sp_arithmetic::traits::AtLeast8BitUnsigned;
+use sp_runtime::traits::Zero;
+
+/// Test the `hold` function for successfully placing funds on hold.
+///
+/// This test checks that holding funds moves them from the main balance of the account to the
+/// balance on hold for the given reason, without changing the total balance of the account or the
+/// total issuance.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `MutateHold<AccountId>` and `Mutate<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn hold_success<T, AccountId>(
+	asset_id: T::AssetId,
+	reason: T::Reason,
+	_dust_trap: Option<AccountId>,
+) where
+	T: MutateHold<AccountId> + Mutate<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	let account = AccountId::from(10);
+	let initial_balance = T::minimum_balance(asset_id.clone()) + 10.into();
+	T::set_balance(asset_id.clone(), &account, initial_balance);
+	let initial_total_issuance = T::total_issuance(asset_id.clone());
+
+	// Test: Place some funds on hold
+	let amount = T::Balance::from(5);
+	T::hold(asset_id.clone(), &reason, &account, amount).unwrap();
+
+	// Verify: The funds moved from the main balance to the balance on hold
+	assert_eq!(T::balance_on_hold(asset_id.clone(), &reason, &account), amount);
+	assert_eq!(T::total_balance_on_hold(asset_id.clone(), &account), amount);
+	assert_eq!(T::balance(asset_id.clone(), &account), initial_balance - amount);
+	assert_eq!(T::total_balance(asset_id.clone(), &account), initial_balance);
+
+	// Test: Place more funds on hold for the same reason
+	T::hold(asset_id.clone(), &reason, &account, amount).unwrap();
+
+	// Verify: The holds accumulate
+	assert_eq!(T::balance_on_hold(asset_id.clone(), &reason, &account), amount * 2.into());
+	assert_eq!(T::balance(asset_id.clone(), &account), initial_balance - amount * 2.into());
+	assert_eq!(T::total_balance(asset_id.clone(), &account), initial_balance);
+
+	// Verify: The total issuance is untouched
+	assert_eq!(T::total_issuance(asset_id.clone()), initial_total_issuance);
+}
+
+/// Test the `hold` function for handling insufficient funds.
+///
+/// This test verifies that holding more than the main balance of the account returns an error
+/// and does not change any balance.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `MutateHold<AccountId>` and `Mutate<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn hold_insufficient_funds<T, AccountId>(
+	asset_id: T::AssetId,
+	reason: T::Reason,
+	_dust_trap: Option<AccountId>,
+) where
+	T: MutateHold<AccountId> + Mutate<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	let account = AccountId::from(10);
+	let initial_balance = T::minimum_balance(asset_id.clone()) + 10.into();
+	T::set_balance(asset_id.clone(), &account, initial_balance);
+
+	// Verify: Holding more than the balance returns an Err
+	assert!(!T::can_hold(asset_id.clone(), &reason, &account, initial_balance + 1.into()));
+	T::hold(asset_id.clone(), &reason, &account, initial_balance + 1.into()).unwrap_err();
+
+	// Verify: Noop
+	assert_eq!(T::balance_on_hold(asset_id.clone(), &reason, &account), T::Balance::zero());
+	assert_eq!(T::balance(asset_id.clone(), &account), initial_balance);
+	assert_eq!(T::total_balance(asset_id.clone(), &account), initial_balance);
+}
+
+/// Test the `hold` function for leaving dust in the main balance.
+///
+/// This test verifies that placing funds on hold such that less than the minimum balance would
+/// remain in the main balance of the account returns an error, since holds must not reap the
+/// account.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `MutateHold<AccountId>` and `Mutate<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn hold_dust<T, AccountId>(
+	asset_id: T::AssetId,
+	reason: T::Reason,
+	_dust_trap: Option<AccountId>,
+) where
+	T: MutateHold<AccountId> + Mutate<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	// This test means nothing if there is no minimum balance
+	if T::minimum_balance(asset_id.clone()) == T::Balance::zero() {
+		return
+	}
+
+	let account = AccountId::from(10);
+	let initial_balance = T::minimum_balance(asset_id.clone()) + 10.into();
+	T::set_balance(asset_id.clone(), &account, initial_balance);
+
+	// Verify: Holding such that only dust would remain returns an Err
+	T::hold(asset_id.clone(), &reason, &account, 11.into()).unwrap_err();
+
+	// Verify: Noop
+	assert_eq!(T::balance_on_hold(asset_id.clone(), &reason, &account), T::Balance::zero());
+	assert_eq!(T::balance(asset_id.clone(), &account), initial_balance);
+
+	// Verify: Holding down to exactly the minimum balance is fine
+	T::hold(asset_id.clone(), &reason, &account, 10.into()).unwrap();
+	assert_eq!(T::balance_on_hold(asset_id.clone(), &reason, &account), 10.into());
+	assert_eq!(T::balance(asset_id.clone(), &account), T::minimum_balance(asset_id.clone()));
+}
+
+/// Test the `release` function for successfully releasing an exact amount.
+///
+/// This test checks that releasing held funds with `Precision::Exact` moves them back to the main
+/// balance of the account.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `MutateHold<AccountId>` and `Mutate<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn release_exact_success<T, AccountId>(
+	asset_id: T::AssetId,
+	reason: T::Reason,
+	_dust_trap: Option<AccountId>,
+) where
+	T: MutateHold<AccountId> + Mutate<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	let account = AccountId::from(10);
+	let initial_balance = T::minimum_balance(asset_id.clone()) + 10.into();
+	T::set_balance(asset_id.clone(), &account, initial_balance);
+	T::hold(asset_id.clone(), &reason, &account, 10.into()).unwrap();
+	let initial_total_issuance = T::total_issuance(asset_id.clone());
+
+	// Test: Release part of the held funds
+	let amount = T::Balance::from(4);
+	let released =
+		T::release(asset_id.clone(), &reason, &account, amount, Precision::Exact).unwrap();
+
+	// Verify: The funds moved back to the main balance
+	assert_eq!(released, amount);
+	assert_eq!(
+		T::balance_on_hold(asset_id.clone(), &reason, &account),
+		T::Balance::from(10) - amount
+	);
+	assert_eq!(
+		T::balance(asset_id.clone(), &account),
+		T::minimum_balance(asset_id.clone()) + amount
+	);
+	assert_eq!(T::total_balance(asset_id.clone(), &account), initial_balance);
+
+	// Verify: The total issuance is untouched
+	assert_eq!(T::total_issuance(asset_id.clone()), initial_total_issuance);
+}
+
+/// Test the `release` function for releasing more than is held.
+///
+/// This test verifies that releasing more than the balance on hold fails with `Precision::Exact`,
+/// while with `Precision::BestEffort` it releases everything that is held.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `MutateHold<AccountId>` and `Mutate<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn release_more_than_held<T, AccountId>(
+	asset_id: T::AssetId,
+	reason: T::Reason,
+	_dust_trap: Option<AccountId>,
+) where
+	T: MutateHold<AccountId> + Mutate<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	let account = AccountId::from(10);
+	let initial_balance = T::minimum_balance(asset_id.clone()) + 10.into();
+	T::set_balance(asset_id.clone(), &account, initial_balance);
+	let held = T::Balance::from(5);
+	T::hold(asset_id.clone(), &reason, &account, held).unwrap();
+
+	// Verify: Releasing more than is held with `Precision::Exact` returns an Err
+	T::release(asset_id.clone(), &reason, &account, held + 1.into(), Precision::Exact).unwrap_err();
+	assert_eq!(T::balance_on_hold(asset_id.clone(), &reason, &account), held);
+	assert_eq!(T::balance(asset_id.clone(), &account), initial_balance - held);
+
+	// Test: Release more than is held with `Precision::BestEffort`
+	let released =
+		T::release(asset_id.clone(), &reason, &account, held + 1.into(), Precision::BestEffort)
+			.unwrap();
+
+	// Verify: Everything that was held got released
+	assert_eq!(released, held);
+	assert_eq!(T::balance_on_hold(asset_id.clone(), &reason, &account), T::Balance::zero());
+	assert_eq!(T::total_balance_on_hold(asset_id.clone(), &account), T::Balance::zero());
+	assert_eq!(T::balance(asset_id.clone(), &account), initial_balance);
+}
+
+/// Test the `burn_held` function for successfully burning held funds.
+///
+/// This test checks that burning held funds removes them from the balance on hold and reduces the
+/// total issuance accordingly, leaving the main balance untouched.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `MutateHold<AccountId>` and `Mutate<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn burn_held_success<T, AccountId>(
+	asset_id: T::AssetId,
+	reason: T::Reason,
+	_dust_trap: Option<AccountId>,
+) where
+	T: MutateHold<AccountId> + Mutate<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	let account = AccountId::from(10);
+	let initial_balance = T::minimum_balance(asset_id.clone()) + 10.into();
+	T::set_balance(asset_id.clone(), &account, initial_balance);
+	T::hold(asset_id.clone(), &reason, &account, 10.into()).unwrap();
+	let initial_total_issuance = T::total_issuance(asset_id.clone());
+
+	// Test: Burn part of the held funds
+	let amount = T::Balance::from(4);
+	let burned = T::burn_held(
+		asset_id.clone(),
+		&reason,
+		&account,
+		amount,
+		Precision::Exact,
+		Fortitude::Polite,
+	)
+	.unwrap();
+
+	// Verify: The held funds and the total issuance were reduced by the burned amount
+	assert_eq!(burned, amount);
+	assert_eq!(
+		T::balance_on_hold(asset_id.clone(), &reason, &account),
+		T::Balance::from(10) - amount
+	);
+	assert_eq!(T::balance(asset_id.clone(), &account), T::minimum_balance(asset_id.clone()));
+	assert_eq!(T::total_balance(asset_id.clone(), &account), initial_balance - amount);
+	assert_eq!(T::total_issuance(asset_id.clone()), initial_total_issuance - amount);
+
+	// Verify: Burning more than is held with `Precision::Exact` returns an Err
+	T::burn_held(
+		asset_id.clone(),
+		&reason,
+		&account,
+		10.into(),
+		Precision::Exact,
+		Fortitude::Polite,
+	)
+	.unwrap_err();
+	assert_eq!(
+		T::balance_on_hold(asset_id.clone(), &reason, &account),
+		T::Balance::from(10) - amount
+	);
+	assert_eq!(T::total_issuance(asset_id.clone()), initial_total_issuance - amount);
+}
+
+/// Test the `transfer_on_hold` function with `Restriction::OnHold`.
+///
+/// This test checks that held funds can be transferred to another account such that they remain
+/// on hold for the same reason in the destination account.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `MutateHold<AccountId>` and `Mutate<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn transfer_on_hold_to_hold<T, AccountId>(
+	asset_id: T::AssetId,
+	reason: T::Reason,
+	_dust_trap: Option<AccountId>,
+) where
+	T: MutateHold<AccountId> + Mutate<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	let account_0 = AccountId::from(10);
+	let account_1 = AccountId::from(20);
+	let initial_balance = T::minimum_balance(asset_id.clone()) + 10.into();
+	T::set_balance(asset_id.clone(), &account_0, initial_balance);
+	T::set_balance(asset_id.clone(), &account_1, initial_balance);
+	T::hold(asset_id.clone(), &reason, &account_0, 10.into()).unwrap();
+	let initial_total_issuance = T::total_issuance(asset_id.clone());
+
+	// Test: Transfer part of the held funds, keeping them on hold
+	let amount = T::Balance::from(4);
+	let transferred = T::transfer_on_hold(
+		asset_id.clone(),
+		&reason,
+		&account_0,
+		&account_1,
+		amount,
+		Precision::Exact,
+		Restriction::OnHold,
+		Fortitude::Polite,
+	)
+	.unwrap();
+
+	// Verify: The held funds moved to the balance on hold of the destination
+	assert_eq!(transferred, amount);
+	assert_eq!(
+		T::balance_on_hold(asset_id.clone(), &reason, &account_0),
+		T::Balance::from(10) - amount
+	);
+	assert_eq!(T::balance_on_hold(asset_id.clone(), &reason, &account_1), amount);
+	assert_eq!(T::balance(asset_id.clone(), &account_0), T::minimum_balance(asset_id.clone()));
+	assert_eq!(T::balance(asset_id.clone(), &account_1), initial_balance);
+	assert_eq!(T::total_balance(asset_id.clone(), &account_0), initial_balance - amount);
+	assert_eq!(T::total_balance(asset_id.clone(), &account_1), initial_balance + amount);
+
+	// Verify: The total issuance is untouched
+	assert_eq!(T::total_issuance(asset_id.clone()), initial_total_issuance);
+}
+
+/// Test the `transfer_on_hold` function with `Restriction::Free`.
+///
+/// This test checks that held funds can be transferred into the main balance of another account.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `MutateHold<AccountId>` and `Mutate<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn transfer_on_hold_to_free<T, AccountId>(
+	asset_id: T::AssetId,
+	reason: T::Reason,
+	_dust_trap: Option<AccountId>,
+) where
+	T: MutateHold<AccountId> + Mutate<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	let account_0 = AccountId::from(10);
+	let account_1 = AccountId::from(20);
+	let initial_balance = T::minimum_balance(asset_id.clone()) + 10.into();
+	T::set_balance(asset_id.clone(), &account_0, initial_balance);
+	T::set_balance(asset_id.clone(), &account_1, initial_balance);
+	T::hold(asset_id.clone(), &reason, &account_0, 10.into()).unwrap();
+	let initial_total_issuance = T::total_issuance(asset_id.clone());
+
+	// Test: Transfer all of the held funds into the main balance of the destination
+	let amount = T::Balance::from(10);
+	let transferred = T::transfer_on_hold(
+		asset_id.clone(),
+		&reason,
+		&account_0,
+		&account_1,
+		amount,
+		Precision::Exact,
+		Restriction::Free,
+		Fortitude::Polite,
+	)
+	.unwrap();
+
+	// Verify: The held funds moved to the main balance of the destination
+	assert_eq!(transferred, amount);
+	assert_eq!(T::balance_on_hold(asset_id.clone(), &reason, &account_0), T::Balance::zero());
+	assert_eq!(T::balance_on_hold(asset_id.clone(), &reason, &account_1), T::Balance::zero());
+	assert_eq!(
+		T::total_balance(asset_id.clone(), &account_0),
+		T::minimum_balance(asset_id.clone())
+	);
+	assert_eq!(T::balance(asset_id.clone(), &account_1), initial_balance + amount);
+
+	// Verify: The total issuance is untouched
+	assert_eq!(T::total_issuance(asset_id.clone()), initial_total_issuance);
+}
+
+/// Test the `transfer_on_hold` function for creating an account below the minimum balance.
+///
+/// This test verifies that transferring held funds into the main balance of a new account fails
+/// if the amount is below the minimum balance, rather than creating a dust account.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `MutateHold<AccountId>` and `Mutate<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn transfer_on_hold_below_minimum<T, AccountId>(
+	asset_id: T::AssetId,
+	reason: T::Reason,
+	_dust_trap: Option<AccountId>,
+) where
+	T: MutateHold<AccountId> + Mutate<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	// This test means nothing if there is no minimum balance
+	if T::minimum_balance(asset_id.clone()) <= T::Balance::from(1) {
+		return
+	}
+
+	let account_0 = AccountId::from(10);
+	let account_1 = AccountId::from(20);
+	let initial_balance =
+		T::minimum_balance(asset_id.clone()) + T::minimum_balance(asset_id.clone());
+	T::set_balance(asset_id.clone(), &account_0, initial_balance);
+	T::hold(asset_id.clone(), &reason, &account_0, T::minimum_balance(asset_id.clone())).unwrap();
+
+	// Verify: Transferring less than the minimum balance into a new account returns an Err
+	let amount = T::minimum_balance(asset_id.clone()) - 1.into();
+	T::transfer_on_hold(
+		asset_id.clone(),
+		&reason,
+		&account_0,
+		&account_1,
+		amount,
+		Precision::Exact,
+		Restriction::Free,
+		Fortitude::Polite,
+	)
+	.unwrap_err();
+
+	// Verify: Noop
+	assert_eq!(
+		T::balance_on_hold(asset_id.clone(), &reason, &account_0),
+		T::minimum_balance(asset_id.clone())
+	);
+	assert_eq!(T::total_balance(asset_id.clone(), &account_1), T::Balance::zero());
+}
+
+/// Test the `transfer_and_hold` function.
+///
+/// This test checks that funds can be transferred from the main balance of one account straight
+/// onto hold in another account.
+///
+/// # Type Parameters
+///
+/// ```text
+/// - `T`: Implements `MutateHold<AccountId>` and `Mutate<AccountId>`.
+/// - `AccountId`: Account identifier implementing `AtLeast8BitUnsigned`.
+/// ```
+pub fn transfer_and_hold_success<T, AccountId>(
+	asset_id: T::AssetId,
+	reason: T::Reason,
+	_dust_trap: Option<AccountId>,
+) where
+	T: MutateHold<AccountId> + Mutate<AccountId>,
+	<T as Inspect<AccountId>>::Balance: AtLeast8BitUnsigned + Debug,
+	AccountId: AtLeast8BitUnsigned,
+{
+	let account_0 = AccountId::from(10);
+	let account_1 = AccountId::from(20);
+	let initial_balance = T::minimum_balance(asset_id.clone()) + 10.into();
+	T::set_balance(asset_id.clone(), &account_0, initial_balance);
+	T::set_balance(asset_id.clone(), &account_1, initial_balance);
+	let initial_total_issuance = T::total_issuance(asset_id.clone());
+
+	// Test: Transfer funds onto hold in another account
+	let amount = T::Balance::from(5);
+	let transferred = T::transfer_and_hold(
+		asset_id.clone(),
+		&reason,
+		&account_0,
+		&account_1,
+		amount,
+		Precision::Exact,
+		Preservation::Preserve,
+		Fortitude::Polite,
+	)
+	.unwrap();
+
+	// Verify: The funds left the main balance of the source and are on hold in the destination
+	assert_eq!(transferred, amount);
+	assert_eq!(T::balance(asset_id.clone(), &account_0), initial_balance - amount);
+	assert_eq!(T::balance_on_hold(asset_id.clone(), &reason, &account_0), T::Balance::zero());
+	assert_eq!(T::balance(asset_id.clone(), &account_1), initial_balance);
+	assert_eq!(T::balance_on_hold(asset_id.clone(), &reason, &account_1), amount);
+
+	// Verify: Transferring the remainder while preserving the source returns an Err
+	if T::minimum_balance(asset_id.clone()) > T::Balance::zero() {
+		T::transfer_and_hold(
+			asset_id.clone(),
+			&reason,
+			&account_0,
+			&account_1,
+			initial_balance - amount,
+			Precision::Exact,
+			Preservation::Preserve,
+			Fortitude::Polite,
+		)
+		.unwrap_err();
+		assert_eq!(T::balance(asset_id.clone(), &account_0), initial_balance - amount);
+	}
+
+	// Verify: The total issuance is untouched
+	assert_eq!(T::total_issuance(asset_id.clone()), initial_total_issuance);
+}
@@ -195,9 +195,10 @@ pub trait Unbalanced<AccountId>: Inspect<AccountId> {
 		force: Fortitude,
 	) -> Result<Self::Balance, DispatchError> {
 		let old_balance = Self::balance(asset.clone(), who);
-		let free = Self::reducible_balance(asset.clone(), who, preservation, force);
-		if let BestEffort = precision {
-			amount = amount.min(free);
+		let reducible = Self::reducible_balance(asset.clone(), who, preservation, force);
+		match precision {
+			BestEffort => amount = amount.min(reducible),
+			Exact => ensure!(reducible >= amount, TokenError::FundsUnavailable),
 		}
 		let new_balance = old_balance.checked_sub(&amount).ok_or(TokenError::FundsUnavailable)?;
 		if let Some(dust) = Self::write_balance(asset.clone(), who, new_balance)? {
@@ -30,7 +30,7 @@ use crate::{
 	traits::Get,
 };
 use codec::{Decode, Encode};
-use sp_runtime::TokenError;
+use sp_runtime::{Permill, TokenError};
 use sp_std::prelude::*;
 
 /// Trait for providing an interface to a read-only NFT-like item.
@@ -118,6 +118,13 @@ pub trait InspectEnumerable<AccountId>: Inspect<AccountId> {
 	fn owned(who: &AccountId) -> Self::OwnedIterator;
 }
 
+/// Trait for providing an interface to inspect the royalties owed on sales of an NFT-like item.
+pub trait InspectRoyalty<AccountId>: Inspect<AccountId> {
+	/// Returns the account entitled to a royalty on every sale of `item`, together with its share
+	/// of the sale price, or `None` if no royalty applies.
+	fn royalty(item: &Self::ItemId) -> Option<(AccountId, Permill)>;
+}
+
 /// Trait for providing an interface for NFT-like items which may be minted, burned and/or have
 /// attributes set on them.
 pub trait Mutate<AccountId, ItemConfig>: Inspect<AccountId> {
@@ -255,6 +262,17 @@ impl<
 	}
 }
 
+impl<
+		F: nonfungibles::InspectRoyalty<AccountId>,
+		A: Get<<F as nonfungibles::Inspect<AccountId>>::CollectionId>,
+		AccountId,
+	> InspectRoyalty<AccountId> for ItemOf<F, A, AccountId>
+{
+	fn royalty(item: &Self::ItemId) -> Option<(AccountId, Permill)> {
+		<F as nonfungibles::InspectRoyalty<AccountId>>::royalty(&A::get(), item)
+	}
+}
+
 impl<
 		F: nonfungibles::Mutate<AccountId, ItemConfig>,
 		A: Get<<F as nonfungibles::Inspect<AccountId>>::CollectionId>,
@@ -29,7 +29,7 @@
 
 use crate::dispatch::{DispatchError, DispatchResult, Parameter};
 use codec::{Decode, Encode};
-use sp_runtime::TokenError;
+use sp_runtime::{traits::AtLeast32BitUnsigned, Permill, TokenError};
 use sp_std::prelude::*;
 
 /// Trait for providing an interface to many read-only NFT-like sets of items.
@@ -180,6 +180,29 @@ pub trait InspectEnumerable<AccountId>: Inspect<AccountId> {
 	) -> Self::OwnedInCollectionIterator;
 }
 
+/// Trait for providing an interface to inspect the royalties owed on sales of items.
+pub trait InspectRoyalty<AccountId>: Inspect<AccountId> {
+	/// Returns the account entitled to a royalty on every sale of `item` of `collection`,
+	/// together with its share of the sale price, or `None` if no royalty applies.
+	fn royalty(
+		collection: &Self::CollectionId,
+		item: &Self::ItemId,
+	) -> Option<(AccountId, Permill)>;
+
+	/// Returns the royalty owed out of a sale of `item` of `collection` for `price`, together
+	/// with the account it should be paid to, or `None` if no royalty applies.
+	///
+	/// The royalty is rounded down.
+	fn royalty_for<Balance: AtLeast32BitUnsigned + Copy>(
+		collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		price: Balance,
+	) -> Option<(AccountId, Balance)> {
+		Self::royalty(collection, item)
+			.map(|(recipient, share)| (recipient, share.mul_floor(price)))
+	}
+}
+
 /// Trait for providing an interface to check the account's role within the collection.
 pub trait InspectRole<AccountId>: Inspect<AccountId> {
 	/// Returns `true` if `who` is the issuer of the `collection`.
@@ -566,6 +566,12 @@ pub mod pallet2 {
 	pub type SomeCountedStorageMap<T> =
 		CountedStorageMap<Hasher = Twox64Concat, Key = u8, Value = u32>;
 
+	#[pallet::storage]
+	pub type SomeCountedNMap<T> = CountedStorageNMap<
+		Key = (NMapKey<Twox64Concat, u8>, NMapKey<Twox64Concat, u16>),
+		Value = u32,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(fn deposit_event)]
 	pub enum Event {
@@ -658,6 +664,7 @@ impl frame_system::Config for Runtime {
 	type RuntimeOrigin = RuntimeOrigin;
 	type Nonce = u64;
 	type RuntimeCall = RuntimeCall;
+	type RuntimeTask = RuntimeTask;
 	type Hash = sp_runtime::testing::H256;
 	type Hashing = sp_runtime::traits::BlakeTwo256;
 	type AccountId = u64;
@@ -1608,6 +1615,24 @@ fn metadata() {
 						default: vec![0, 0, 0, 0],
 						docs: maybe_docs(vec!["Counter for the related counted storage map"]),
 					},
+					StorageEntryMetadata {
+						name: "SomeCountedNMap",
+						modifier: StorageEntryModifier::Optional,
+						ty: StorageEntryType::Map {
+							hashers: vec![StorageHasher::Twox64Concat, StorageHasher::Twox64Concat],
+							key: meta_type::<(u8, u16)>(),
+							value: meta_type::<u32>(),
+						},
+						default: vec![0],
+						docs: vec![],
+					},
+					StorageEntryMetadata {
+						name: "CounterForSomeCountedNMap",
+						modifier: StorageEntryModifier::Default,
+						ty: StorageEntryType::Plain(meta_type::<u32>()),
+						default: vec![0, 0, 0, 0],
+						docs: maybe_docs(vec!["Counter for the related counted storage map"]),
+					},
 				],
 			}),
 			calls: None,
@@ -1961,6 +1986,20 @@ fn test_storage_info() {
 				max_values: Some(1),
 				max_size: Some(4),
 			},
+			StorageInfo {
+				pallet_name: b"Example2".to_vec(),
+				storage_name: b"SomeCountedNMap".to_vec(),
+				prefix: prefix(b"Example2", b"SomeCountedNMap").to_vec(),
+				max_values: None,
+				max_size: None,
+			},
+			StorageInfo {
+				pallet_name: b"Example2".to_vec(),
+				storage_name: b"CounterForSomeCountedNMap".to_vec(),
+				prefix: prefix(b"Example2", b"CounterForSomeCountedNMap").to_vec(),
+				max_values: Some(1),
+				max_size: Some(4),
+			},
 		],
 	);
 }
@@ -2082,7 +2121,7 @@ fn assert_type_all_pallets_without_system_reversed_is_correct() {
 
 #[test]
 fn test_storage_alias() {
-	use frame_support::Twox64Concat;
+	use frame_support::{storage::types::Key as NMapKey, Twox64Concat};
 
 	#[frame_support::storage_alias]
 	type Value<T: pallet::Config>
@@ -2096,6 +2135,16 @@ fn test_storage_alias() {
 		<T as frame_system::Config>::AccountId: From<SomeType1> + SomeAssociation1,
 	= CountedStorageMap<pallet2::Pallet<T>, Twox64Concat, u8, u32>;
 
+	#[frame_support::storage_alias]
+	type SomeCountedNMap<T: pallet2::Config>
+	where
+		<T as frame_system::Config>::AccountId: From<SomeType1> + SomeAssociation1,
+	= CountedStorageNMap<
+		pallet2::Pallet<T>,
+		(NMapKey<Twox64Concat, u8>, NMapKey<Twox64Concat, u16>),
+		u32,
+	>;
+
 	TestExternalities::default().execute_with(|| {
 		pallet::Value::<Runtime>::put(10);
 		assert_eq!(10, Value::<Runtime>::get());
@@ -2107,6 +2156,14 @@ fn test_storage_alias() {
 			SomeCountedStorageMap::<Runtime>::storage_info(),
 			pallet2::SomeCountedStorageMap::<Runtime>::storage_info()
 		);
+
+		pallet2::SomeCountedNMap::<Runtime>::insert((10, 20), 100);
+		assert_eq!(Some(100), SomeCountedNMap::<Runtime>::get((10, 20)));
+		assert_eq!(1, SomeCountedNMap::<Runtime>::count());
+		assert_eq!(
+			SomeCountedNMap::<Runtime>::storage_info(),
+			pallet2::SomeCountedNMap::<Runtime>::storage_info()
+		);
 	})
 }
 
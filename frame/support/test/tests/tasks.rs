@@ -0,0 +1,172 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for `#[pallet::tasks]` and the aggregated `RuntimeTask` of `construct_runtime!`.
+
+use frame_support::{derive_impl, traits::Task};
+
+pub type Header = sp_runtime::generic::Header<u32, sp_runtime::traits::BlakeTwo256>;
+pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
+pub type UncheckedExtrinsic = sp_runtime::generic::UncheckedExtrinsic<u32, RuntimeCall, (), ()>;
+
+#[frame_support::pallet(dev_mode)]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T, I = ()>(_);
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config {}
+
+	#[pallet::storage]
+	pub type Pairs<T: Config<I>, I: 'static = ()> = StorageMap<_, Twox64Concat, u32, u64>;
+
+	#[pallet::storage]
+	pub type Flag<T: Config<I>, I: 'static = ()> = StorageValue<_, bool, ValueQuery>;
+
+	#[pallet::storage]
+	pub type Sum<T: Config<I>, I: 'static = ()> = StorageValue<_, u64, ValueQuery>;
+
+	#[pallet::tasks]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		#[pallet::task_index(0)]
+		#[pallet::task_condition(|key, value| Pairs::<T, I>::get(key) == Some(*value))]
+		#[pallet::task_list(Pairs::<T, I>::iter())]
+		#[pallet::task_weight(Weight::from_parts(*value, 0))]
+		fn sum_pair(key: u32, value: u64) -> DispatchResult {
+			Pairs::<T, I>::remove(key);
+			Sum::<T, I>::mutate(|sum| *sum += value);
+			Ok(())
+		}
+
+		#[pallet::task_index(5)]
+		#[pallet::task_condition(|| Flag::<T, I>::get())]
+		#[pallet::task_list(Flag::<T, I>::get().then_some(()).into_iter())]
+		fn clear_flag() -> DispatchResult {
+			Flag::<T, I>::kill();
+			Err(DispatchError::Other("always fails after clearing"))
+		}
+	}
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Runtime {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type Block = Block;
+	type BlockHashCount = frame_support::traits::ConstU32<10>;
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeTask = RuntimeTask;
+	type RuntimeEvent = RuntimeEvent;
+	type PalletInfo = PalletInfo;
+	type OnSetCode = ();
+}
+
+impl pallet::Config for Runtime {}
+impl pallet::Config<pallet::Instance1> for Runtime {}
+
+frame_support::construct_runtime!(
+	pub enum Runtime
+	{
+		System: frame_system,
+		Example: pallet,
+		Instance1Example: pallet::<Instance1>,
+	}
+);
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	use sp_runtime::BuildStorage;
+
+	let mut ext: sp_io::TestExternalities =
+		RuntimeGenesisConfig::default().build_storage().unwrap().into();
+	ext.execute_with(|| frame_system::Pallet::<Runtime>::set_block_number(1));
+	ext
+}
+
+#[test]
+fn runtime_task_aggregates_pallet_tasks() {
+	new_test_ext().execute_with(|| {
+		assert!(<RuntimeTask as Task>::iter().next().is_none());
+
+		pallet::Pairs::<Runtime>::insert(1, 10);
+		pallet::Flag::<Runtime, pallet::Instance1>::put(true);
+
+		let tasks = <RuntimeTask as Task>::iter().collect::<Vec<_>>();
+		assert_eq!(
+			tasks,
+			vec![
+				RuntimeTask::Example(pallet::Task::sum_pair { key: 1, value: 10 }),
+				RuntimeTask::Instance1Example(pallet::Task::clear_flag {}),
+			],
+		);
+		assert_eq!(tasks.iter().map(Task::task_index).collect::<Vec<_>>(), vec![0, 5]);
+		assert_eq!(tasks[0].weight(), frame_support::weights::Weight::from_parts(10, 0));
+		assert!(tasks.iter().all(Task::is_valid));
+
+		let wrong_value: RuntimeTask =
+			pallet::Task::<Runtime>::sum_pair { key: 1, value: 11 }.into();
+		assert!(!wrong_value.is_valid());
+	});
+}
+
+#[test]
+fn runtime_task_runs_pallet_task() {
+	new_test_ext().execute_with(|| {
+		pallet::Pairs::<Runtime>::insert(1, 10);
+		pallet::Flag::<Runtime, pallet::Instance1>::put(true);
+
+		let sum_pair: RuntimeTask = pallet::Task::<Runtime>::sum_pair { key: 1, value: 10 }.into();
+		assert_eq!(sum_pair.run(), Ok(()));
+		assert_eq!(pallet::Sum::<Runtime>::get(), 10);
+		assert!(!sum_pair.is_valid());
+
+		let clear_flag = RuntimeTask::Instance1Example(pallet::Task::clear_flag {});
+		assert_eq!(clear_flag.run(), Err("always fails after clearing".into()));
+		assert!(<RuntimeTask as Task>::iter().next().is_none());
+	});
+}
+
+#[test]
+fn task_encoding_uses_task_index() {
+	use codec::Encode;
+
+	let task = RuntimeTask::Instance1Example(pallet::Task::clear_flag {});
+	assert_eq!(task.encode(), vec![2, 5]);
+}
+
+#[test]
+fn dev_mode_task_without_weight_runs_through_do_task() {
+	use frame_support::{assert_ok, dispatch::GetDispatchInfo, weights::Weight};
+	use sp_runtime::traits::Dispatchable;
+
+	new_test_ext().execute_with(|| {
+		pallet::Flag::<Runtime, pallet::Instance1>::put(true);
+
+		let task = RuntimeTask::Instance1Example(pallet::Task::clear_flag {});
+		let call = frame_system::Call::<Runtime>::do_task { task: task.clone() };
+		assert_eq!(call.get_dispatch_info().weight, Weight::zero());
+
+		assert_ok!(RuntimeCall::from(call).dispatch(RuntimeOrigin::none()));
+		// the failed task is reverted
+		assert!(pallet::Flag::<Runtime, pallet::Instance1>::get());
+		frame_system::Pallet::<Runtime>::assert_last_event(
+			frame_system::Event::TaskFailed { task, err: "always fails after clearing".into() }
+				.into(),
+		);
+	});
+}
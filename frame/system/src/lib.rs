@@ -96,7 +96,8 @@ use frame_support::{
 	storage::{self, StorageStreamIter},
 	traits::{
 		ConstU32, Contains, EnsureOrigin, EnsureOriginWithArg, Get, HandleLifetime,
-		OnKilledAccount, OnNewAccount, OriginTrait, PalletInfo, SortedMembers, StoredMap, TypedGet,
+		OnKilledAccount, OnNewAccount, OriginTrait, PalletInfo, SortedMembers, StoredMap, Task,
+		TypedGet,
 	},
 	Parameter,
 };
@@ -273,6 +274,10 @@ pub mod pallet {
 			+ Debug
 			+ From<Call<Self>>;
 
+		/// The aggregated `RuntimeTask` type.
+		#[pallet::no_default]
+		type RuntimeTask: Task;
+
 		/// This stores the number of previous transactions associated with a sender account.
 		type Nonce: Parameter
 			+ Member
@@ -500,6 +505,23 @@ pub mod pallet {
 			Self::deposit_event(Event::Remarked { sender: who, hash });
 			Ok(().into())
 		}
+
+		/// Run a valid task of the runtime.
+		///
+		/// Can be executed by every `origin`, including unsigned transactions submitted by
+		/// offchain workers. The task is run in its own storage layer: if it fails, its changes
+		/// are reverted and `TaskFailed` is emitted.
+		#[pallet::call_index(8)]
+		#[pallet::weight(task.is_valid_weight().saturating_add(task.weight()))]
+		pub fn do_task(_origin: OriginFor<T>, task: T::RuntimeTask) -> DispatchResultWithPostInfo {
+			ensure!(task.is_valid(), Error::<T>::InvalidTask);
+
+			match storage::with_storage_layer(|| task.run()) {
+				Ok(()) => Self::deposit_event(Event::TaskCompleted { task }),
+				Err(err) => Self::deposit_event(Event::TaskFailed { task, err }),
+			}
+			Ok(().into())
+		}
 	}
 
 	/// Event for the System pallet.
@@ -517,6 +539,10 @@ pub mod pallet {
 		KilledAccount { account: T::AccountId },
 		/// On on-chain remark happened.
 		Remarked { sender: T::AccountId, hash: T::Hash },
+		/// A task has been run successfully.
+		TaskCompleted { task: T::RuntimeTask },
+		/// A task has been run but failed, its changes have been reverted.
+		TaskFailed { task: T::RuntimeTask, err: DispatchError },
 	}
 
 	/// Error for the System pallet
@@ -538,6 +564,27 @@ pub mod pallet {
 		NonZeroRefCount,
 		/// The origin filter prevent the call to be dispatched.
 		CallFiltered,
+		/// The given task is not valid at the moment.
+		InvalidTask,
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let Call::do_task { task } = call else { return InvalidTransaction::Call.into() };
+
+			if !task.is_valid() {
+				return InvalidTransaction::Stale.into()
+			}
+
+			ValidTransaction::with_tag_prefix("SystemTask")
+				.priority(TransactionPriority::min_value())
+				.and_provides(task.encode())
+				.propagate(true)
+				.build()
+		}
 	}
 
 	/// Exposed trait-generic origin type.
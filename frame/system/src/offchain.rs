@@ -53,11 +53,16 @@
 //! #### Submit a signed transaction
 //!
 //! [`Signer`](./struct.Signer.html) can be used to sign/verify payloads
+//!
+//! ### Submit valid tasks
+//!
+//! [`submit_valid_tasks`] discovers all tasks of the runtime which are currently valid and
+//! submits an unsigned `do_task` transaction for each of them.
 
 #![warn(missing_docs)]
 
 use codec::Encode;
-use frame_support::RuntimeDebug;
+use frame_support::{traits::Task, RuntimeDebug};
 use sp_runtime::{
 	app_crypto::RuntimeAppPublic,
 	traits::{Extrinsic as ExtrinsicT, IdentifyAccount, One},
@@ -100,6 +105,31 @@ where
 	}
 }
 
+/// Submit an unsigned `do_task` transaction for each task of the runtime that is currently valid.
+///
+/// Meant to be called from an offchain worker. Since the tasks of all pallets are submitted, a
+/// runtime should call this from a single pallet only. Returns the number of submitted
+/// transactions.
+pub fn submit_valid_tasks<T>() -> usize
+where
+	T: crate::Config + SendTransactionTypes<crate::Call<T>>,
+{
+	let mut submitted = 0;
+	for task in <T::RuntimeTask as Task>::iter().filter(|task| task.is_valid()) {
+		let task_index = task.task_index();
+		let call = crate::Call::<T>::do_task { task };
+		match SubmitTransaction::<T, crate::Call<T>>::submit_unsigned_transaction(call.into()) {
+			Ok(()) => submitted += 1,
+			Err(()) => log::error!(
+				target: crate::LOG_TARGET,
+				"Failed to submit task with index {}",
+				task_index,
+			),
+		}
+	}
+	submitted
+}
+
 /// Provides an implementation for signing transaction payloads.
 ///
 /// Keys used for signing are defined when instantiating the signer object.
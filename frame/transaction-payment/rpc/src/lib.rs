@@ -26,9 +26,12 @@ use jsonrpsee::{
 	types::error::{CallError, ErrorCode, ErrorObject},
 };
 use pallet_transaction_payment_rpc_runtime_api::{FeeDetails, InclusionFee, RuntimeDispatchInfo};
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
-use sp_core::Bytes;
+use sp_core::{
+	traits::{RpcCallExt, RpcCallKind},
+	Bytes,
+};
 use sp_rpc::number::NumberOrHex;
 use sp_runtime::traits::{Block as BlockT, MaybeDisplay};
 
@@ -94,7 +97,8 @@ where
 		encoded_xt: Bytes,
 		at: Option<Block::Hash>,
 	) -> RpcResult<RuntimeDispatchInfo<Balance, sp_weights::Weight>> {
-		let api = self.client.runtime_api();
+		let mut api = self.client.runtime_api();
+		api.register_extension(RpcCallExt(RpcCallKind::RuntimeApi));
 		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
 
 		let encoded_len = encoded_xt.len() as u32;
@@ -131,7 +135,8 @@ where
 		encoded_xt: Bytes,
 		at: Option<Block::Hash>,
 	) -> RpcResult<FeeDetails<NumberOrHex>> {
-		let api = self.client.runtime_api();
+		let mut api = self.client.runtime_api();
+		api.register_extension(RpcCallExt(RpcCallKind::RuntimeApi));
 		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
 
 		let encoded_len = encoded_xt.len() as u32;
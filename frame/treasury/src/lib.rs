@@ -41,6 +41,8 @@
 //! - **Deposit:** Funds that a proposer must lock when making a proposal. The deposit will be
 //!   returned or slashed if the proposal is approved or rejected respectively.
 //! - **Pot:** Unspent funds accumulated by the treasury pallet.
+//! - **Spend:** An approved proposal for transferring a specific amount of funds to a designated
+//!   beneficiary.
 //!
 //! ## Interface
 //!
@@ -51,6 +53,13 @@
 //! - `reject_proposal` - Reject a proposal, slashing the deposit.
 //! - `approve_proposal` - Accept the proposal, returning the deposit.
 //! - `remove_approval` - Remove an approval, the deposit will no longer be returned.
+//! - `spend_local` - Propose and approve a spend of the native currency from the pot.
+//!
+//! Spends of arbitrary asset kinds, paid through [`Config::Paymaster`]:
+//! - `spend` - Propose and approve a spend of some asset kind.
+//! - `payout` - Claim an approved spend within its validity window.
+//! - `check_status` - Update the status of an attempted payout; allows retrying failed payouts.
+//! - `void_spend` - Void an approved spend whose payout has not been attempted yet.
 //!
 //! ## GenesisConfig
 //!
@@ -62,6 +71,8 @@ mod benchmarking;
 #[cfg(test)]
 mod tests;
 pub mod weights;
+#[cfg(feature = "runtime-benchmarks")]
+pub use benchmarking::ArgumentsFactory;
 
 use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
@@ -75,8 +86,10 @@ use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 use frame_support::{
 	print,
 	traits::{
-		Currency, ExistenceRequirement::KeepAlive, Get, Imbalance, OnUnbalanced,
-		ReservableCurrency, WithdrawReasons,
+		tokens::{ConversionFromAssetBalance, Pay, PaymentStatus},
+		Currency,
+		ExistenceRequirement::KeepAlive,
+		Get, Imbalance, OnUnbalanced, ReservableCurrency, WithdrawReasons,
 	},
 	weights::Weight,
 	PalletId,
@@ -94,6 +107,9 @@ pub type NegativeImbalanceOf<T, I = ()> = <<T as Config<I>>::Currency as Currenc
 	<T as frame_system::Config>::AccountId,
 >>::NegativeImbalance;
 type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
+pub type AssetBalanceOf<T, I = ()> = <<T as Config<I>>::Paymaster as Pay>::Balance;
+pub type PaymentIdOf<T, I = ()> = <<T as Config<I>>::Paymaster as Pay>::Id;
+type BeneficiaryLookupOf<T, I> = <<T as Config<I>>::BeneficiaryLookup as StaticLookup>::Source;
 
 /// A trait to allow the Treasury Pallet to spend it's funds for other purposes.
 /// There is an expectation that the implementer of this trait will correctly manage
@@ -133,10 +149,43 @@ pub struct Proposal<AccountId, Balance> {
 	bond: Balance,
 }
 
+/// An index of an approved spend of some asset kind. Just a `u32`.
+pub type SpendIndex = u32;
+
+/// The state of the payout of a spend.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub enum PaymentState<Id> {
+	/// The payout has not been attempted yet.
+	Pending,
+	/// The payout has been attempted, `id` identifies the payment.
+	Attempted { id: Id },
+	/// The payout has failed and may be retried.
+	Failed,
+}
+
+/// An approved spend of some asset kind.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct SpendStatus<AssetKind, AssetBalance, Beneficiary, BlockNumber, PaymentId> {
+	/// The kind of asset to be spent.
+	asset_kind: AssetKind,
+	/// The asset amount of the spend.
+	amount: AssetBalance,
+	/// The beneficiary of the spend.
+	beneficiary: Beneficiary,
+	/// The block number from which the spend can be claimed.
+	valid_from: BlockNumber,
+	/// The block number from which the spend can no longer be claimed.
+	expire_at: BlockNumber,
+	/// The state of the payout.
+	status: PaymentState<PaymentId>,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
-	use frame_support::{dispatch_context::with_context, pallet_prelude::*};
+	use frame_support::{
+		dispatch::DispatchResultWithPostInfo, dispatch_context::with_context, pallet_prelude::*,
+	};
 	use frame_system::pallet_prelude::*;
 
 	#[pallet::pallet]
@@ -201,9 +250,37 @@ pub mod pallet {
 		type MaxApprovals: Get<u32>;
 
 		/// The origin required for approving spends from the treasury outside of the proposal
-		/// process. The `Success` value is the maximum amount that this origin is allowed to
-		/// spend at a time.
+		/// process. The `Success` value is the maximum amount in the native currency that this
+		/// origin is allowed to spend at a time.
 		type SpendOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = BalanceOf<Self, I>>;
+
+		/// The kinds of asset that can be spent from the treasury.
+		type AssetKind: Parameter + MaxEncodedLen;
+
+		/// The beneficiaries of the spends of [`Self::AssetKind`].
+		type Beneficiary: Parameter + MaxEncodedLen;
+
+		/// Converting trait to take a source type and convert to [`Self::Beneficiary`].
+		type BeneficiaryLookup: StaticLookup<Target = Self::Beneficiary>;
+
+		/// Makes the payouts of the spends of [`Self::AssetKind`].
+		type Paymaster: Pay<Beneficiary = Self::Beneficiary, AssetKind = Self::AssetKind>;
+
+		/// Converts an amount of [`Self::AssetKind`] into the native currency, solely to check it
+		/// against the maximum amount the [`Self::SpendOrigin`] is allowed to spend.
+		type BalanceConverter: ConversionFromAssetBalance<
+			AssetBalanceOf<Self, I>,
+			Self::AssetKind,
+			BalanceOf<Self, I>,
+		>;
+
+		/// The period during which an approved spend has to be claimed.
+		#[pallet::constant]
+		type PayoutPeriod: Get<BlockNumberFor<Self>>;
+
+		/// Helper type for benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: ArgumentsFactory<Self::AssetKind, Self::Beneficiary>;
 	}
 
 	/// Number of proposals that have been made.
@@ -233,6 +310,26 @@ pub mod pallet {
 	pub type Approvals<T: Config<I>, I: 'static = ()> =
 		StorageValue<_, BoundedVec<ProposalIndex, T::MaxApprovals>, ValueQuery>;
 
+	/// The count of spends of some asset kind that have been made.
+	#[pallet::storage]
+	pub(crate) type SpendCount<T, I = ()> = StorageValue<_, SpendIndex, ValueQuery>;
+
+	/// Approved spends of some asset kind that have not been processed yet.
+	#[pallet::storage]
+	pub type Spends<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		SpendIndex,
+		SpendStatus<
+			T::AssetKind,
+			AssetBalanceOf<T, I>,
+			T::Beneficiary,
+			BlockNumberFor<T>,
+			PaymentIdOf<T, I>,
+		>,
+		OptionQuery,
+	>;
+
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
@@ -277,6 +374,24 @@ pub mod pallet {
 		},
 		/// The inactive funds of the pallet have been updated.
 		UpdatedInactive { reactivated: BalanceOf<T, I>, deactivated: BalanceOf<T, I> },
+		/// A new spend of some asset kind has been approved.
+		AssetSpendApproved {
+			index: SpendIndex,
+			asset_kind: T::AssetKind,
+			amount: AssetBalanceOf<T, I>,
+			beneficiary: T::Beneficiary,
+			valid_from: BlockNumberFor<T>,
+			expire_at: BlockNumberFor<T>,
+		},
+		/// An approved spend was voided.
+		AssetSpendVoided { index: SpendIndex },
+		/// A payment happened.
+		Paid { index: SpendIndex, payment_id: PaymentIdOf<T, I> },
+		/// A payment failed and can be retried.
+		PaymentFailed { index: SpendIndex, payment_id: PaymentIdOf<T, I> },
+		/// A spend was processed and removed from the storage. It might have been successfully
+		/// paid or it might have expired.
+		SpendProcessed { index: SpendIndex },
 	}
 
 	/// Error for the treasury pallet.
@@ -293,6 +408,20 @@ pub mod pallet {
 		InsufficientPermission,
 		/// Proposal has not been approved.
 		ProposalNotApproved,
+		/// The balance of the asset kind is not convertible to the balance of the native asset.
+		FailedToConvertBalance,
+		/// The spend has expired and cannot be claimed.
+		SpendExpired,
+		/// The spend is not yet eligible for payout.
+		EarlyPayout,
+		/// The payment has already been attempted.
+		AlreadyAttempted,
+		/// There was some issue with the mechanism of payment.
+		PayoutError,
+		/// The payout was not yet attempted/claimed.
+		NotAttempted,
+		/// The payment has neither failed nor succeeded yet.
+		Inconclusive,
 	}
 
 	#[pallet::hooks]
@@ -404,7 +533,7 @@ pub mod pallet {
 			Ok(())
 		}
 
-		/// Propose and approve a spend of treasury funds.
+		/// Propose and approve a spend of treasury funds in the native currency.
 		///
 		/// - `origin`: Must be `SpendOrigin` with the `Success` value being at least `amount`.
 		/// - `amount`: The amount to be transferred from the treasury to the `beneficiary`.
@@ -413,8 +542,8 @@ pub mod pallet {
 		/// NOTE: For record-keeping purposes, the proposer is deemed to be equivalent to the
 		/// beneficiary.
 		#[pallet::call_index(3)]
-		#[pallet::weight(T::WeightInfo::spend())]
-		pub fn spend(
+		#[pallet::weight(T::WeightInfo::spend_local())]
+		pub fn spend_local(
 			origin: OriginFor<T>,
 			#[pallet::compact] amount: BalanceOf<T, I>,
 			beneficiary: AccountIdLookupOf<T>,
@@ -491,6 +620,185 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Propose and approve a spend of some asset kind, paid through `T::Paymaster`.
+		///
+		/// - `origin`: Must be `SpendOrigin` with the `Success` value being at least `amount` of
+		///   `asset_kind` converted to the native currency by `T::BalanceConverter`.
+		/// - `asset_kind`: The kind of asset to be spent.
+		/// - `amount`: The amount of `asset_kind` to be paid to the `beneficiary`.
+		/// - `beneficiary`: The beneficiary of the spend.
+		/// - `valid_from`: The block number from which the spend can be claimed with `payout`. It
+		///   may lie in the past as long as the spend has not expired yet. If `None`, the spend can
+		///   be claimed right away.
+		///
+		/// The spend has to be claimed within `T::PayoutPeriod` blocks from `valid_from`.
+		///
+		/// Emits [`Event::AssetSpendApproved`] if successful.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::spend())]
+		pub fn spend(
+			origin: OriginFor<T>,
+			asset_kind: Box<T::AssetKind>,
+			#[pallet::compact] amount: AssetBalanceOf<T, I>,
+			beneficiary: Box<BeneficiaryLookupOf<T, I>>,
+			valid_from: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			let max_amount = T::SpendOrigin::ensure_origin(origin)?;
+			let beneficiary = T::BeneficiaryLookup::lookup(*beneficiary)?;
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let valid_from = valid_from.unwrap_or(now);
+			let expire_at = valid_from.saturating_add(T::PayoutPeriod::get());
+			ensure!(expire_at > now, Error::<T, I>::SpendExpired);
+
+			let native_amount =
+				T::BalanceConverter::from_asset_balance(amount, (*asset_kind).clone())
+					.map_err(|_| Error::<T, I>::FailedToConvertBalance)?;
+
+			ensure!(native_amount <= max_amount, Error::<T, I>::InsufficientPermission);
+
+			with_context::<SpendContext<BalanceOf<T, I>>, _>(|v| {
+				let context = v.or_default();
+				// We group based on `max_amount`, to distinguish between different kind of
+				// origins. (assumes that all origins have different `max_amount`)
+				//
+				// Worst case is that we reject some "valid" request.
+				let spend = context.spend_in_context.entry(max_amount).or_default();
+
+				// Ensure that we don't overflow nor use more than `max_amount`
+				if spend.checked_add(&native_amount).map(|s| s > max_amount).unwrap_or(true) {
+					Err(Error::<T, I>::InsufficientPermission)
+				} else {
+					*spend = spend.saturating_add(native_amount);
+					Ok(())
+				}
+			})
+			.unwrap_or(Ok(()))?;
+
+			let index = SpendCount::<T, I>::get();
+			Spends::<T, I>::insert(
+				index,
+				SpendStatus {
+					asset_kind: (*asset_kind).clone(),
+					amount,
+					beneficiary: beneficiary.clone(),
+					valid_from,
+					expire_at,
+					status: PaymentState::Pending,
+				},
+			);
+			SpendCount::<T, I>::put(index + 1);
+
+			Self::deposit_event(Event::AssetSpendApproved {
+				index,
+				asset_kind: *asset_kind,
+				amount,
+				beneficiary,
+				valid_from,
+				expire_at,
+			});
+			Ok(())
+		}
+
+		/// Claim an approved spend, paying it out to its beneficiary.
+		///
+		/// - `origin`: Must be signed.
+		/// - `index`: The index of the spend.
+		///
+		/// The spend must be claimed between its `valid_from` and `expire_at` blocks. A failed
+		/// payout can be retried once `check_status` has recorded the failure.
+		///
+		/// Emits [`Event::Paid`] if successful.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::payout())]
+		pub fn payout(origin: OriginFor<T>, index: SpendIndex) -> DispatchResult {
+			ensure_signed(origin)?;
+			let mut spend = Spends::<T, I>::get(index).ok_or(Error::<T, I>::InvalidIndex)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(now >= spend.valid_from, Error::<T, I>::EarlyPayout);
+			ensure!(spend.expire_at > now, Error::<T, I>::SpendExpired);
+			ensure!(
+				matches!(spend.status, PaymentState::Pending | PaymentState::Failed),
+				Error::<T, I>::AlreadyAttempted
+			);
+
+			let id = T::Paymaster::pay(&spend.beneficiary, spend.asset_kind.clone(), spend.amount)
+				.map_err(|_| Error::<T, I>::PayoutError)?;
+
+			spend.status = PaymentState::Attempted { id };
+			Spends::<T, I>::insert(index, spend);
+
+			Self::deposit_event(Event::<T, I>::Paid { index, payment_id: id });
+			Ok(())
+		}
+
+		/// Check the status of the payout of a spend and remove the spend if it is processed.
+		///
+		/// - `origin`: Must be signed.
+		/// - `index`: The index of the spend.
+		///
+		/// A spend is processed once its payout succeeded, or once it expired without a payout
+		/// in progress. The transaction fee is refunded in that case. A failed payout is recorded
+		/// so that it can be retried with `payout`.
+		///
+		/// Emits [`Event::PaymentFailed`] if the payout failed and [`Event::SpendProcessed`] if the
+		/// spend was processed.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::check_status())]
+		pub fn check_status(origin: OriginFor<T>, index: SpendIndex) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let mut spend = Spends::<T, I>::get(index).ok_or(Error::<T, I>::InvalidIndex)?;
+			let now = frame_system::Pallet::<T>::block_number();
+
+			if now >= spend.expire_at && !matches!(spend.status, PaymentState::Attempted { .. }) {
+				// The spend has expired and no further status update is expected.
+				Spends::<T, I>::remove(index);
+				Self::deposit_event(Event::<T, I>::SpendProcessed { index });
+				return Ok(Pays::No.into())
+			}
+
+			let payment_id = match spend.status {
+				PaymentState::Attempted { id } => id,
+				_ => return Err(Error::<T, I>::NotAttempted.into()),
+			};
+
+			match T::Paymaster::check_payment(payment_id) {
+				PaymentStatus::Failure => {
+					spend.status = PaymentState::Failed;
+					Spends::<T, I>::insert(index, spend);
+					Self::deposit_event(Event::<T, I>::PaymentFailed { index, payment_id });
+					Ok(Pays::Yes.into())
+				},
+				PaymentStatus::Success | PaymentStatus::Unknown => {
+					Spends::<T, I>::remove(index);
+					Self::deposit_event(Event::<T, I>::SpendProcessed { index });
+					Ok(Pays::No.into())
+				},
+				PaymentStatus::InProgress => Err(Error::<T, I>::Inconclusive.into()),
+			}
+		}
+
+		/// Void an approved spend whose payout has not been attempted yet.
+		///
+		/// May only be called from `T::RejectOrigin`.
+		/// - `index`: The index of the spend.
+		///
+		/// Emits [`Event::AssetSpendVoided`] if successful.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::void_spend())]
+		pub fn void_spend(origin: OriginFor<T>, index: SpendIndex) -> DispatchResult {
+			T::RejectOrigin::ensure_origin(origin)?;
+			let spend = Spends::<T, I>::get(index).ok_or(Error::<T, I>::InvalidIndex)?;
+			ensure!(
+				matches!(spend.status, PaymentState::Pending | PaymentState::Failed),
+				Error::<T, I>::AlreadyAttempted
+			);
+
+			Spends::<T, I>::remove(index);
+			Self::deposit_event(Event::<T, I>::AssetSpendVoided { index });
+			Ok(())
+		}
 	}
 }
 
@@ -19,6 +19,7 @@
 
 #![cfg(test)]
 
+use core::{cell::RefCell, marker::PhantomData};
 use sp_core::H256;
 use sp_runtime::{
 	traits::{BadOrigin, BlakeTwo256, Dispatchable, IdentityLookup},
@@ -26,8 +27,13 @@ use sp_runtime::{
 };
 
 use frame_support::{
-	assert_err_ignore_postinfo, assert_noop, assert_ok, parameter_types,
-	traits::{ConstU32, ConstU64, OnInitialize},
+	assert_err_ignore_postinfo, assert_noop, assert_ok,
+	pallet_prelude::Pays,
+	parameter_types,
+	traits::{
+		tokens::{ConversionFromAssetBalance, PaymentStatus},
+		ConstU32, ConstU64, OnInitialize,
+	},
 	PalletId,
 };
 
@@ -56,6 +62,7 @@ impl frame_system::Config for Test {
 	type RuntimeOrigin = RuntimeOrigin;
 	type Nonce = u64;
 	type RuntimeCall = RuntimeCall;
+	type RuntimeTask = RuntimeTask;
 	type Hash = H256;
 	type Hashing = BlakeTwo256;
 	type AccountId = u128; // u64 is not enough to hold bytes used to generate bounty account
@@ -96,10 +103,72 @@ impl pallet_utility::Config for Test {
 	type WeightInfo = ();
 }
 
+thread_local! {
+	pub static PAID: RefCell<BTreeMap<(u128, u32), u64>> = RefCell::new(BTreeMap::new());
+	pub static STATUS: RefCell<BTreeMap<u64, PaymentStatus>> = RefCell::new(BTreeMap::new());
+	pub static LAST_ID: RefCell<u64> = RefCell::new(0u64);
+}
+
+/// The amount of an asset kind paid to an account.
+fn paid(who: u128, asset_kind: u32) -> u64 {
+	PAID.with(|p| p.borrow().get(&(who, asset_kind)).cloned().unwrap_or(0))
+}
+
+/// Reduce the amount of an asset kind paid to an account.
+fn unpay(who: u128, asset_kind: u32, amount: u64) {
+	PAID.with(|p| p.borrow_mut().entry((who, asset_kind)).or_default().saturating_reduce(amount))
+}
+
+/// Set the status of a payment.
+fn set_status(id: u64, s: PaymentStatus) {
+	STATUS.with(|m| m.borrow_mut().insert(id, s));
+}
+
+pub struct TestPay;
+impl Pay for TestPay {
+	type Beneficiary = u128;
+	type Balance = u64;
+	type Id = u64;
+	type AssetKind = u32;
+	type Error = ();
+
+	fn pay(
+		who: &Self::Beneficiary,
+		asset_kind: Self::AssetKind,
+		amount: Self::Balance,
+	) -> Result<Self::Id, Self::Error> {
+		PAID.with(|paid| *paid.borrow_mut().entry((*who, asset_kind)).or_default() += amount);
+		Ok(LAST_ID.with(|lid| {
+			let x = *lid.borrow();
+			lid.replace(x + 1);
+			x
+		}))
+	}
+	fn check_payment(id: Self::Id) -> PaymentStatus {
+		STATUS.with(|s| s.borrow().get(&id).cloned().unwrap_or(PaymentStatus::Unknown))
+	}
+	#[cfg(feature = "runtime-benchmarks")]
+	fn ensure_successful(_: &Self::Beneficiary, _: Self::AssetKind, _: Self::Balance) {}
+	#[cfg(feature = "runtime-benchmarks")]
+	fn ensure_concluded(id: Self::Id) {
+		set_status(id, PaymentStatus::Failure)
+	}
+}
+
+/// Converts an asset balance to the native balance by multiplying it with `N`.
+pub struct MulBy<N>(PhantomData<N>);
+impl<N: Get<u64>> ConversionFromAssetBalance<u64, u32, u64> for MulBy<N> {
+	type Error = ();
+	fn from_asset_balance(balance: u64, _asset_kind: u32) -> Result<u64, Self::Error> {
+		balance.checked_mul(N::get()).ok_or(())
+	}
+}
+
 parameter_types! {
 	pub const ProposalBond: Permill = Permill::from_percent(5);
 	pub const Burn: Permill = Permill::from_percent(50);
 	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub const SpendPayoutPeriod: u64 = 5;
 }
 pub struct TestSpendOrigin;
 impl frame_support::traits::EnsureOrigin<RuntimeOrigin> for TestSpendOrigin {
@@ -137,6 +206,14 @@ impl Config for Test {
 	type SpendFunds = ();
 	type MaxApprovals = ConstU32<100>;
 	type SpendOrigin = TestSpendOrigin;
+	type AssetKind = u32;
+	type Beneficiary = u128;
+	type BeneficiaryLookup = IdentityLookup<Self::Beneficiary>;
+	type Paymaster = TestPay;
+	type BalanceConverter = MulBy<ConstU64<2>>;
+	type PayoutPeriod = SpendPayoutPeriod;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -160,40 +237,40 @@ fn genesis_config_works() {
 }
 
 #[test]
-fn spend_origin_permissioning_works() {
+fn spend_local_origin_permissioning_works() {
 	new_test_ext().execute_with(|| {
-		assert_noop!(Treasury::spend(RuntimeOrigin::signed(1), 1, 1), BadOrigin);
+		assert_noop!(Treasury::spend_local(RuntimeOrigin::signed(1), 1, 1), BadOrigin);
 		assert_noop!(
-			Treasury::spend(RuntimeOrigin::signed(10), 6, 1),
+			Treasury::spend_local(RuntimeOrigin::signed(10), 6, 1),
 			Error::<Test>::InsufficientPermission
 		);
 		assert_noop!(
-			Treasury::spend(RuntimeOrigin::signed(11), 11, 1),
+			Treasury::spend_local(RuntimeOrigin::signed(11), 11, 1),
 			Error::<Test>::InsufficientPermission
 		);
 		assert_noop!(
-			Treasury::spend(RuntimeOrigin::signed(12), 21, 1),
+			Treasury::spend_local(RuntimeOrigin::signed(12), 21, 1),
 			Error::<Test>::InsufficientPermission
 		);
 		assert_noop!(
-			Treasury::spend(RuntimeOrigin::signed(13), 51, 1),
+			Treasury::spend_local(RuntimeOrigin::signed(13), 51, 1),
 			Error::<Test>::InsufficientPermission
 		);
 	});
 }
 
 #[test]
-fn spend_origin_works() {
+fn spend_local_origin_works() {
 	new_test_ext().execute_with(|| {
 		// Check that accumulate works when we have Some value in Dummy already.
 		Balances::make_free_balance_be(&Treasury::account_id(), 101);
-		assert_ok!(Treasury::spend(RuntimeOrigin::signed(10), 5, 6));
-		assert_ok!(Treasury::spend(RuntimeOrigin::signed(10), 5, 6));
-		assert_ok!(Treasury::spend(RuntimeOrigin::signed(10), 5, 6));
-		assert_ok!(Treasury::spend(RuntimeOrigin::signed(10), 5, 6));
-		assert_ok!(Treasury::spend(RuntimeOrigin::signed(11), 10, 6));
-		assert_ok!(Treasury::spend(RuntimeOrigin::signed(12), 20, 6));
-		assert_ok!(Treasury::spend(RuntimeOrigin::signed(13), 50, 6));
+		assert_ok!(Treasury::spend_local(RuntimeOrigin::signed(10), 5, 6));
+		assert_ok!(Treasury::spend_local(RuntimeOrigin::signed(10), 5, 6));
+		assert_ok!(Treasury::spend_local(RuntimeOrigin::signed(10), 5, 6));
+		assert_ok!(Treasury::spend_local(RuntimeOrigin::signed(10), 5, 6));
+		assert_ok!(Treasury::spend_local(RuntimeOrigin::signed(11), 10, 6));
+		assert_ok!(Treasury::spend_local(RuntimeOrigin::signed(12), 20, 6));
+		assert_ok!(Treasury::spend_local(RuntimeOrigin::signed(13), 50, 6));
 
 		<Treasury as OnInitialize<u64>>::on_initialize(1);
 		assert_eq!(Balances::free_balance(6), 0);
@@ -485,8 +562,8 @@ fn spending_in_batch_respects_max_total() {
 		// Respect the `max_total` for the given origin.
 		assert_ok!(RuntimeCall::from(UtilityCall::batch_all {
 			calls: vec![
-				RuntimeCall::from(TreasuryCall::spend { amount: 2, beneficiary: 100 }),
-				RuntimeCall::from(TreasuryCall::spend { amount: 2, beneficiary: 101 })
+				RuntimeCall::from(TreasuryCall::spend_local { amount: 2, beneficiary: 100 }),
+				RuntimeCall::from(TreasuryCall::spend_local { amount: 2, beneficiary: 101 })
 			]
 		})
 		.dispatch(RuntimeOrigin::signed(10)));
@@ -494,8 +571,8 @@ fn spending_in_batch_respects_max_total() {
 		assert_err_ignore_postinfo!(
 			RuntimeCall::from(UtilityCall::batch_all {
 				calls: vec![
-					RuntimeCall::from(TreasuryCall::spend { amount: 2, beneficiary: 100 }),
-					RuntimeCall::from(TreasuryCall::spend { amount: 4, beneficiary: 101 })
+					RuntimeCall::from(TreasuryCall::spend_local { amount: 2, beneficiary: 100 }),
+					RuntimeCall::from(TreasuryCall::spend_local { amount: 4, beneficiary: 101 })
 				]
 			})
 			.dispatch(RuntimeOrigin::signed(10)),
@@ -503,3 +580,195 @@ fn spending_in_batch_respects_max_total() {
 		);
 	})
 }
+
+#[test]
+fn spend_origin_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Treasury::spend(RuntimeOrigin::signed(10), Box::new(1), 1, Box::new(6), None));
+		assert_ok!(Treasury::spend(RuntimeOrigin::signed(10), Box::new(1), 2, Box::new(6), None));
+		assert_noop!(
+			Treasury::spend(RuntimeOrigin::signed(10), Box::new(1), 3, Box::new(6), None),
+			Error::<Test, _>::InsufficientPermission
+		);
+		assert_ok!(Treasury::spend(RuntimeOrigin::signed(11), Box::new(1), 5, Box::new(6), None));
+		assert_noop!(
+			Treasury::spend(RuntimeOrigin::signed(11), Box::new(1), 6, Box::new(6), None),
+			Error::<Test, _>::InsufficientPermission
+		);
+		assert_ok!(Treasury::spend(RuntimeOrigin::signed(12), Box::new(1), 10, Box::new(6), None));
+		assert_noop!(
+			Treasury::spend(RuntimeOrigin::signed(12), Box::new(1), 11, Box::new(6), None),
+			Error::<Test, _>::InsufficientPermission
+		);
+		assert_ok!(Treasury::spend(RuntimeOrigin::signed(13), Box::new(1), 25, Box::new(6), None));
+		assert_noop!(
+			Treasury::spend(RuntimeOrigin::signed(13), Box::new(1), 26, Box::new(6), None),
+			Error::<Test, _>::InsufficientPermission
+		);
+		assert_noop!(
+			Treasury::spend(RuntimeOrigin::signed(1), Box::new(1), 1, Box::new(6), None),
+			BadOrigin
+		);
+	});
+}
+
+#[test]
+fn spend_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Treasury::spend(RuntimeOrigin::signed(10), Box::new(1), 2, Box::new(6), None));
+
+		assert_eq!(SpendCount::<Test, _>::get(), 1);
+		assert_eq!(
+			Spends::<Test, _>::get(0).unwrap(),
+			SpendStatus {
+				asset_kind: 1,
+				amount: 2,
+				beneficiary: 6,
+				valid_from: 1,
+				expire_at: 6,
+				status: PaymentState::Pending,
+			}
+		);
+		System::assert_last_event(
+			Event::<Test, _>::AssetSpendApproved {
+				index: 0,
+				asset_kind: 1,
+				amount: 2,
+				beneficiary: 6,
+				valid_from: 1,
+				expire_at: 6,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn spend_expires() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(10);
+		// The validity window has already passed.
+		assert_noop!(
+			Treasury::spend(RuntimeOrigin::signed(10), Box::new(1), 2, Box::new(6), Some(5)),
+			Error::<Test, _>::SpendExpired
+		);
+		// A validity window in the past is fine as long as it has not passed yet.
+		assert_ok!(Treasury::spend(
+			RuntimeOrigin::signed(10),
+			Box::new(1),
+			2,
+			Box::new(6),
+			Some(6)
+		));
+
+		System::set_block_number(11);
+		assert_noop!(Treasury::payout(RuntimeOrigin::signed(1), 0), Error::<Test, _>::SpendExpired);
+		// The expired spend is removed without fees.
+		assert_eq!(Treasury::check_status(RuntimeOrigin::signed(1), 0), Ok(Pays::No.into()));
+		assert!(Spends::<Test, _>::get(0).is_none());
+		System::assert_last_event(Event::<Test, _>::SpendProcessed { index: 0 }.into());
+	});
+}
+
+#[test]
+fn spend_payout_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Treasury::spend(
+			RuntimeOrigin::signed(10),
+			Box::new(1),
+			2,
+			Box::new(6),
+			Some(2)
+		));
+		assert_noop!(Treasury::payout(RuntimeOrigin::signed(1), 0), Error::<Test, _>::EarlyPayout);
+		assert_noop!(Treasury::payout(RuntimeOrigin::signed(1), 1), Error::<Test, _>::InvalidIndex);
+
+		System::set_block_number(2);
+		assert_ok!(Treasury::payout(RuntimeOrigin::signed(1), 0));
+		assert_eq!(paid(6, 1), 2);
+		let payment_id = get_payment_id(0).expect("no payment attempt");
+		System::assert_last_event(Event::<Test, _>::Paid { index: 0, payment_id }.into());
+		assert_noop!(
+			Treasury::payout(RuntimeOrigin::signed(1), 0),
+			Error::<Test, _>::AlreadyAttempted
+		);
+
+		// The payment is in progress.
+		set_status(payment_id, PaymentStatus::InProgress);
+		assert_noop!(
+			Treasury::check_status(RuntimeOrigin::signed(1), 0),
+			Error::<Test, _>::Inconclusive
+		);
+
+		// The payment succeeded and the spend is removed without fees.
+		set_status(payment_id, PaymentStatus::Success);
+		assert_eq!(Treasury::check_status(RuntimeOrigin::signed(1), 0), Ok(Pays::No.into()));
+		assert!(Spends::<Test, _>::get(0).is_none());
+		System::assert_last_event(Event::<Test, _>::SpendProcessed { index: 0 }.into());
+	});
+}
+
+#[test]
+fn failed_payout_can_be_retried() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Treasury::spend(RuntimeOrigin::signed(10), Box::new(1), 2, Box::new(6), None));
+		assert_noop!(
+			Treasury::check_status(RuntimeOrigin::signed(1), 0),
+			Error::<Test, _>::NotAttempted
+		);
+		assert_ok!(Treasury::payout(RuntimeOrigin::signed(1), 0));
+		let payment_id = get_payment_id(0).expect("no payment attempt");
+
+		// The payment failed.
+		unpay(6, 1, 2);
+		set_status(payment_id, PaymentStatus::Failure);
+		assert_eq!(Treasury::check_status(RuntimeOrigin::signed(1), 0), Ok(Pays::Yes.into()));
+		assert_eq!(Spends::<Test, _>::get(0).unwrap().status, PaymentState::Failed);
+		System::assert_last_event(Event::<Test, _>::PaymentFailed { index: 0, payment_id }.into());
+
+		// The payout is retried.
+		assert_ok!(Treasury::payout(RuntimeOrigin::signed(1), 0));
+		assert_eq!(paid(6, 1), 2);
+		assert_ne!(get_payment_id(0).expect("no payment attempt"), payment_id);
+	});
+}
+
+#[test]
+fn void_spend_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Treasury::spend(RuntimeOrigin::signed(10), Box::new(1), 2, Box::new(6), None));
+		assert_ok!(Treasury::spend(RuntimeOrigin::signed(10), Box::new(1), 2, Box::new(6), None));
+		assert_ok!(Treasury::payout(RuntimeOrigin::signed(1), 1));
+
+		assert_noop!(Treasury::void_spend(RuntimeOrigin::signed(1), 0), BadOrigin);
+		assert_noop!(
+			Treasury::void_spend(RuntimeOrigin::root(), 1),
+			Error::<Test, _>::AlreadyAttempted
+		);
+		assert_ok!(Treasury::void_spend(RuntimeOrigin::root(), 0));
+		assert!(Spends::<Test, _>::get(0).is_none());
+		System::assert_last_event(Event::<Test, _>::AssetSpendVoided { index: 0 }.into());
+		assert_noop!(Treasury::payout(RuntimeOrigin::signed(1), 0), Error::<Test, _>::InvalidIndex);
+	});
+}
+
+#[test]
+fn spend_fails_on_failed_balance_conversion() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Treasury::spend(RuntimeOrigin::root(), Box::new(1), u64::MAX, Box::new(6), None),
+			Error::<Test, _>::FailedToConvertBalance
+		);
+	});
+}
+
+fn get_payment_id(index: SpendIndex) -> Option<u64> {
+	match Spends::<Test, _>::get(index)?.status {
+		PaymentState::Attempted { id } => Some(id),
+		_ => None,
+	}
+}
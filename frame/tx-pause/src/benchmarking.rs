@@ -0,0 +1,83 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for the transaction pause pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::{Pallet as TxPause, *};
+
+use frame_benchmarking::v2::*;
+
+/// Find the first call of the runtime that can be paused.
+fn pausable_call<T: Config>() -> Option<RuntimeCallNameOf<T>> {
+	let runtime_call = <T as Config>::RuntimeCall::get_module_names().iter().flat_map(|pallet| {
+		<T as Config>::RuntimeCall::get_call_names(pallet)
+			.iter()
+			.map(move |call| (pallet, call))
+	});
+
+	for (pallet, call) in runtime_call {
+		let (Ok(pallet), Ok(call)) = (
+			PalletNameOf::<T>::try_from(pallet.as_bytes().to_vec()),
+			PalletCallNameOf::<T>::try_from(call.as_bytes().to_vec()),
+		) else {
+			continue
+		};
+
+		let full_name = (pallet, call);
+		if TxPause::<T>::ensure_can_pause(&full_name).is_ok() {
+			return Some(full_name)
+		}
+	}
+
+	None
+}
+
+#[benchmarks]
+mod benches {
+	use super::*;
+
+	#[benchmark]
+	fn pause() -> Result<(), BenchmarkError> {
+		let origin =
+			T::PauseOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let full_name = pausable_call::<T>().ok_or(BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, full_name.clone());
+
+		assert!(PausedCalls::<T>::get(full_name).is_some());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn unpause() -> Result<(), BenchmarkError> {
+		let unpause_origin =
+			T::UnpauseOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let full_name = pausable_call::<T>().ok_or(BenchmarkError::Weightless)?;
+		TxPause::<T>::do_pause(full_name.clone()).unwrap();
+
+		#[extrinsic_call]
+		_(unpause_origin as T::RuntimeOrigin, full_name.clone());
+
+		assert!(PausedCalls::<T>::get(full_name).is_none());
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(TxPause, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,279 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test suite for the transaction pause pallet.
+
+#![cfg(test)]
+
+use super::*;
+use crate as pallet_tx_pause;
+use crate::mock::{RuntimeCall, *};
+
+use frame_support::{assert_err, assert_noop, assert_ok, dispatch::Dispatchable};
+use sp_runtime::{BuildStorage, DispatchError};
+
+fn call_transfer(dest: u64, value: u64) -> RuntimeCall {
+	RuntimeCall::Balances(pallet_balances::Call::transfer { dest, value })
+}
+
+fn call_transfer_keep_alive(dest: u64, value: u64) -> RuntimeCall {
+	RuntimeCall::Balances(pallet_balances::Call::transfer_keep_alive { dest, value })
+}
+
+fn full_name<T: Config>(pallet_name: &[u8], call_name: &[u8]) -> RuntimeCallNameOf<T> {
+	<RuntimeCallNameOf<T>>::from((
+		pallet_name.to_vec().try_into().unwrap(),
+		call_name.to_vec().try_into().unwrap(),
+	))
+}
+
+#[test]
+fn can_pause_specific_call() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(call_transfer(BOB, 1).dispatch(RuntimeOrigin::signed(ALICE)));
+
+		assert_ok!(TxPause::pause(
+			RuntimeOrigin::root(),
+			full_name::<Test>(b"Balances", b"transfer")
+		));
+		System::assert_last_event(
+			Event::CallPaused { full_name: full_name::<Test>(b"Balances", b"transfer") }.into(),
+		);
+
+		assert_err!(
+			call_transfer(BOB, 1).dispatch(RuntimeOrigin::signed(ALICE)),
+			frame_system::Error::<Test>::CallFiltered
+		);
+		assert_ok!(call_transfer_keep_alive(BOB, 1).dispatch(RuntimeOrigin::signed(ALICE)));
+	});
+}
+
+#[test]
+fn can_unpause_specific_call() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TxPause::pause(
+			RuntimeOrigin::root(),
+			full_name::<Test>(b"Balances", b"transfer"),
+		));
+		assert_err!(
+			call_transfer(BOB, 1).dispatch(RuntimeOrigin::signed(ALICE)),
+			frame_system::Error::<Test>::CallFiltered
+		);
+
+		assert_ok!(TxPause::unpause(
+			RuntimeOrigin::root(),
+			full_name::<Test>(b"Balances", b"transfer"),
+		));
+		System::assert_last_event(
+			Event::CallUnpaused { full_name: full_name::<Test>(b"Balances", b"transfer") }.into(),
+		);
+		assert_ok!(call_transfer(BOB, 1).dispatch(RuntimeOrigin::signed(ALICE)));
+	});
+}
+
+#[test]
+fn can_filter_balance_in_batch_when_paused() {
+	new_test_ext().execute_with(|| {
+		let batch_call =
+			RuntimeCall::Utility(pallet_utility::Call::batch { calls: vec![call_transfer(1, 1)] });
+
+		assert_ok!(TxPause::pause(
+			RuntimeOrigin::root(),
+			full_name::<Test>(b"Balances", b"transfer"),
+		));
+
+		assert_ok!(batch_call.dispatch(RuntimeOrigin::signed(ALICE)));
+		System::assert_last_event(
+			pallet_utility::Event::BatchInterrupted {
+				index: 0,
+				error: frame_system::Error::<Test>::CallFiltered.into(),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn root_bypasses_the_filter() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TxPause::pause(
+			RuntimeOrigin::root(),
+			full_name::<Test>(b"Balances", b"force_transfer"),
+		));
+
+		assert_ok!(RuntimeCall::Balances(pallet_balances::Call::force_transfer {
+			source: ALICE,
+			dest: BOB,
+			value: 1
+		})
+		.dispatch(RuntimeOrigin::root()));
+	});
+}
+
+#[test]
+fn fails_to_pause_self() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TxPause::pause(RuntimeOrigin::root(), full_name::<Test>(b"TxPause", b"pause")),
+			Error::<Test>::Unpausable
+		);
+	});
+}
+
+#[test]
+fn fails_to_pause_unpausable_call() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TxPause::pause(
+				RuntimeOrigin::root(),
+				full_name::<Test>(b"Balances", b"transfer_keep_alive"),
+			),
+			Error::<Test>::Unpausable
+		);
+	});
+}
+
+#[test]
+fn fails_to_pause_unknown_call() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TxPause::pause(RuntimeOrigin::root(), full_name::<Test>(b"Balances", b"nonexistent")),
+			Error::<Test>::NotFound
+		);
+		assert_noop!(
+			TxPause::pause(RuntimeOrigin::root(), full_name::<Test>(b"Unknown", b"transfer")),
+			Error::<Test>::NotFound
+		);
+	});
+}
+
+#[test]
+fn fails_to_pause_already_paused_call() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TxPause::pause(
+			RuntimeOrigin::root(),
+			full_name::<Test>(b"Balances", b"transfer"),
+		));
+
+		assert_noop!(
+			TxPause::pause(RuntimeOrigin::root(), full_name::<Test>(b"Balances", b"transfer")),
+			Error::<Test>::IsPaused
+		);
+	});
+}
+
+#[test]
+fn fails_to_unpause_not_paused_call() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TxPause::unpause(RuntimeOrigin::root(), full_name::<Test>(b"Balances", b"transfer")),
+			Error::<Test>::IsUnpaused
+		);
+	});
+}
+
+#[test]
+fn pause_and_unpause_require_the_configured_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TxPause::pause(
+				RuntimeOrigin::signed(ALICE),
+				full_name::<Test>(b"Balances", b"transfer")
+			),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(TxPause::pause(
+			RuntimeOrigin::root(),
+			full_name::<Test>(b"Balances", b"transfer"),
+		));
+		assert_noop!(
+			TxPause::unpause(
+				RuntimeOrigin::signed(ALICE),
+				full_name::<Test>(b"Balances", b"transfer")
+			),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn too_long_names_are_never_paused() {
+	new_test_ext().execute_with(|| {
+		let long_name = vec![b'a'; MaxNameLen::get() as usize + 1];
+
+		assert!(!TxPause::is_paused_unbound(long_name.clone(), b"transfer".to_vec()));
+		assert!(!TxPause::is_paused_unbound(b"Balances".to_vec(), long_name));
+	});
+}
+
+#[test]
+fn transaction_pause_trait_works() {
+	use frame_support::traits::{TransactionPause, TransactionPauseError};
+
+	new_test_ext().execute_with(|| {
+		let transfer = full_name::<Test>(b"Balances", b"transfer");
+		let keep_alive = full_name::<Test>(b"Balances", b"transfer_keep_alive");
+
+		assert!(<TxPause as TransactionPause>::can_pause(transfer.clone()));
+		assert!(!<TxPause as TransactionPause>::can_pause(keep_alive.clone()));
+		assert_eq!(
+			<TxPause as TransactionPause>::pause(keep_alive),
+			Err(TransactionPauseError::Unpausable)
+		);
+		assert_eq!(
+			<TxPause as TransactionPause>::unpause(transfer.clone()),
+			Err(TransactionPauseError::AlreadyUnpaused)
+		);
+
+		assert_ok!(<TxPause as TransactionPause>::pause(transfer.clone()));
+		assert!(<TxPause as TransactionPause>::is_paused(transfer.clone()));
+		assert_eq!(
+			<TxPause as TransactionPause>::pause(transfer.clone()),
+			Err(TransactionPauseError::AlreadyPaused)
+		);
+
+		assert_ok!(<TxPause as TransactionPause>::unpause(transfer.clone()));
+		assert!(!<TxPause as TransactionPause>::is_paused(transfer));
+	});
+}
+
+#[test]
+fn genesis_config_can_pause_calls() {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_tx_pause::GenesisConfig::<Test> {
+		paused: vec![full_name::<Test>(b"Balances", b"transfer")],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	sp_io::TestExternalities::new(t).execute_with(|| {
+		assert!(TxPause::is_paused(&full_name::<Test>(b"Balances", b"transfer")));
+		assert!(!<TxPause as Contains<RuntimeCall>>::contains(&call_transfer(BOB, 1)));
+	});
+}
+
+#[test]
+#[should_panic(expected = "Genesis data is known good; qed")]
+fn genesis_config_cannot_pause_unpausable_calls() {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_tx_pause::GenesisConfig::<Test> {
+		paused: vec![full_name::<Test>(b"Balances", b"transfer_keep_alive")],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+}
@@ -513,6 +513,12 @@ impl ByteArray for AccountId32 {
 	const LEN: usize = 32;
 }
 
+impl FromEntropy for AccountId32 {
+	fn from_entropy(input: &mut impl codec::Input) -> Result<Self, codec::Error> {
+		Ok(AccountId32::new(FromEntropy::from_entropy(input)?))
+	}
+}
+
 #[cfg(feature = "serde")]
 impl Ss58Codec for AccountId32 {}
 
@@ -1169,6 +1175,12 @@ impl FromEntropy for bool {
 	}
 }
 
+impl FromEntropy for () {
+	fn from_entropy(_: &mut impl codec::Input) -> Result<Self, codec::Error> {
+		Ok(())
+	}
+}
+
 macro_rules! impl_from_entropy {
 	($type:ty , $( $others:tt )*) => {
 		impl_from_entropy!($type);
@@ -179,6 +179,22 @@ impl ReadRuntimeVersionExt {
 	}
 }
 
+/// The kind of a runtime call that serves an RPC request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RpcCallKind {
+	/// A runtime function called directly by the RPC client, e.g. through `state_call`.
+	Call,
+	/// A runtime API called by an RPC method, e.g. `payment_queryInfo` or `system_dryRun`.
+	RuntimeApi,
+}
+
+sp_externalities::decl_extension! {
+	/// Marks a runtime call as serving an RPC request of the given kind.
+	///
+	/// These calls come from untrusted sources, so the executor may limit their resources.
+	pub struct RpcCallExt(RpcCallKind);
+}
+
 /// Something that can spawn tasks (blocking and non-blocking) with an assigned name
 /// and optional group.
 #[dyn_clonable::clonable]
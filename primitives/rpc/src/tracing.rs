@@ -99,3 +99,41 @@ pub enum TraceBlockResponse {
 	/// Successful block tracing response
 	BlockTrace(BlockTrace),
 }
+
+/// Response for the `state_profileBlockHostCalls` RPC.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockHostCalls {
+	/// Hash of the block being profiled
+	pub block_hash: String,
+	/// Parent hash
+	pub parent_hash: String,
+	/// Runtime calls made while executing the block, in the order they were made
+	pub runtime_calls: Vec<RuntimeCallHostCalls>,
+}
+
+/// Host functions called by a single runtime call.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeCallHostCalls {
+	/// The runtime method which was called
+	pub method: String,
+	/// Statistics of the called host functions, sorted by name
+	pub host_functions: Vec<HostFunctionCalls>,
+}
+
+/// Statistics of the calls to a single host function.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct HostFunctionCalls {
+	/// Name of the host function
+	pub name: String,
+	/// Number of calls
+	pub calls: u64,
+	/// Number of bytes read from the runtime memory
+	pub bytes_read: u64,
+	/// Number of bytes written to the runtime memory
+	pub bytes_written: u64,
+	/// Wall time spent in the host function, in nanoseconds
+	pub time_ns: u64,
+}
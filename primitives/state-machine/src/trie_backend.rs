@@ -30,6 +30,7 @@ use codec::Codec;
 use hash_db::HashDB;
 use hash_db::Hasher;
 use sp_core::storage::{ChildInfo, StateVersion};
+use sp_trie::MerkleValue;
 #[cfg(feature = "std")]
 use sp_trie::{
 	cache::{LocalTrieCache, TrieCache},
@@ -405,6 +406,18 @@ where
 		self.essence.child_storage(child_info, key)
 	}
 
+	fn closest_merkle_value(&self, key: &[u8]) -> Result<Option<MerkleValue<H::Out>>, Self::Error> {
+		self.essence.closest_merkle_value(key)
+	}
+
+	fn child_closest_merkle_value(
+		&self,
+		child_info: &ChildInfo,
+		key: &[u8],
+	) -> Result<Option<MerkleValue<H::Out>>, Self::Error> {
+		self.essence.child_closest_merkle_value(child_info, key)
+	}
+
 	fn next_storage_key(&self, key: &[u8]) -> Result<Option<StorageKey>, Self::Error> {
 		let (is_cached, mut cache) = access_cache(&self.next_storage_key_cache, Option::take)
 			.map(|cache| (cache.last_key == key, cache))
@@ -741,6 +754,34 @@ pub mod tests {
 		);
 	}
 
+	parameterized_test!(closest_merkle_value_works, closest_merkle_value_works_inner);
+	fn closest_merkle_value_works_inner(
+		state_version: StateVersion,
+		cache: Option<Cache>,
+		recorder: Option<Recorder>,
+	) {
+		let test_trie = test_trie(state_version, cache, recorder);
+
+		assert!(test_trie.closest_merkle_value(b"value1").unwrap().is_some());
+		assert_eq!(test_trie.closest_merkle_value(b"non-existing-key").unwrap(), None);
+		// Both prefixes resolve to the same closest descendant node.
+		assert_eq!(
+			test_trie.closest_merkle_value(b"valu").unwrap(),
+			test_trie.closest_merkle_value(b"value").unwrap(),
+		);
+		assert_ne!(
+			test_trie.closest_merkle_value(b"value").unwrap(),
+			test_trie.closest_merkle_value(b"value1").unwrap(),
+		);
+
+		let child_info = ChildInfo::new_default(CHILD_KEY_1);
+		assert!(test_trie.child_closest_merkle_value(&child_info, b"value3").unwrap().is_some());
+		assert_eq!(
+			test_trie.child_closest_merkle_value(&child_info, b"non-existing-key").unwrap(),
+			None
+		);
+	}
+
 	parameterized_test!(
 		pairs_are_not_empty_on_non_empty_storage,
 		pairs_are_not_empty_on_non_empty_storage_inner
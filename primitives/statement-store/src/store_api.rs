@@ -16,7 +16,7 @@
 // limitations under the License.
 
 pub use crate::runtime_api::StatementSource;
-use crate::{Hash, Statement, Topic};
+use crate::{DecryptionKey, Hash, Statement, Topic};
 
 /// Statement store error.
 #[derive(Debug, Eq, PartialEq, thiserror::Error)]
@@ -30,6 +30,9 @@ pub enum Error {
 	/// Error making runtime call.
 	#[error("Error calling into the runtime")]
 	Runtime,
+	/// The maximum number of subscribers is reached.
+	#[error("Too many subscribers")]
+	TooManySubscribers,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -61,6 +64,36 @@ pub enum SubmitResult {
 /// Result type for `Error`
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Filter over the topics of statements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TopicFilter {
+	/// Matches statements that include all of the given topics.
+	///
+	/// An empty list matches all statements.
+	MatchAll(Vec<Topic>),
+	/// Matches statements that include at least one of the given topics.
+	///
+	/// An empty list does not match any statement.
+	MatchAny(Vec<Topic>),
+}
+
+impl TopicFilter {
+	/// Returns `true` if the topics of `statement` pass this filter.
+	pub fn matches(&self, statement: &Statement) -> bool {
+		let has_topic = |topic: &Topic| {
+			(0..crate::MAX_TOPICS).any(|index| statement.topic(index).as_ref() == Some(topic))
+		};
+
+		match self {
+			TopicFilter::MatchAll(topics) => topics.iter().all(has_topic),
+			TopicFilter::MatchAny(topics) => topics.iter().any(has_topic),
+		}
+	}
+}
+
+/// Stream of statements accepted by the store.
+pub type StatementStream = std::pin::Pin<Box<dyn futures::Stream<Item = Statement> + Send>>;
+
 /// Statement store API.
 pub trait StatementStore: Send + Sync {
 	/// Return all statements.
@@ -87,4 +120,15 @@ pub trait StatementStore: Send + Sync {
 
 	/// Remove a statement from the store.
 	fn remove(&self, hash: &Hash) -> Result<()>;
+
+	/// Subscribe to statements that are accepted by the store after this call.
+	///
+	/// Only statements whose topics pass `topic_filter` and whose decryption key is
+	/// `dest` are reported. Statements without a `DecryptionKey` field are matched by
+	/// passing `None`.
+	fn subscribe(
+		&self,
+		topic_filter: TopicFilter,
+		dest: Option<DecryptionKey>,
+	) -> Result<StatementStream>;
 }
@@ -367,6 +367,17 @@ impl<H: Hasher, I: DerefMut<Target = RecorderInner<H::Out>>> trie_db::TrieRecord
 				// accounted for by the recorded node that holds the hash.
 				self.update_recorded_keys(full_key, RecordedForKey::Hash);
 			},
+			TrieAccess::InlineValue { full_key } => {
+				tracing::trace!(
+					target: LOG_TARGET,
+					key = ?sp_core::hexdisplay::HexDisplay::from(&full_key),
+					"Recorded inline value access for key",
+				);
+
+				// A value was accessed that is stored inline a node and we recorded all trie nodes
+				// to access this value.
+				self.update_recorded_keys(full_key, RecordedForKey::Value);
+			},
 			TrieAccess::NonExisting { full_key } => {
 				tracing::trace!(
 					target: LOG_TARGET,
@@ -346,6 +346,19 @@ if_wasmtime_is_enabled! {
 			callback: impl FnOnce(&mut dyn FunctionContext) -> R,
 		) -> R;
 
+		/// The same as [`Self::with_function_context`], but also provides the name of the host
+		/// function which is being called.
+		///
+		/// By default the name is ignored.
+		fn with_named_function_context<R>(
+			caller: wasmtime::Caller<Self::State>,
+			fn_name: &'static str,
+			callback: impl FnOnce(&mut dyn FunctionContext) -> R,
+		) -> R {
+			let _ = fn_name;
+			Self::with_function_context(caller, callback)
+		}
+
 		/// Registers a given host function with the WASM executor.
 		///
 		/// The function has to be statically callable, and all of its arguments
@@ -442,6 +455,14 @@ where
 					T::with_function_context(caller, callback)
 				}
 
+				fn with_named_function_context<R>(
+					caller: wasmtime::Caller<Self::State>,
+					fn_name: &'static str,
+					callback: impl FnOnce(&mut dyn FunctionContext) -> R,
+				) -> R {
+					T::with_named_function_context(caller, fn_name, callback)
+				}
+
 				fn register_static<Params, Results>(
 					&mut self,
 					fn_name: &str,
@@ -31,7 +31,11 @@ use sc_transaction_pool_api::{InPoolTransaction, TransactionPool};
 use sp_api::ApiExt;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::HeaderBackend;
-use sp_core::{hexdisplay::HexDisplay, Bytes};
+use sp_core::{
+	hexdisplay::HexDisplay,
+	traits::{RpcCallExt, RpcCallKind},
+	Bytes,
+};
 use sp_runtime::{legacy, traits};
 
 pub use frame_system_rpc_runtime_api::AccountNonceApi;
@@ -99,7 +103,8 @@ where
 	Nonce: Clone + Display + Codec + Send + traits::AtLeast32Bit + 'static,
 {
 	async fn nonce(&self, account: AccountId) -> RpcResult<Nonce> {
-		let api = self.client.runtime_api();
+		let mut api = self.client.runtime_api();
+		api.register_extension(RpcCallExt(RpcCallKind::RuntimeApi));
 		let best = self.client.info().best_hash;
 
 		let nonce = api.account_nonce(best, account.clone()).map_err(|e| {
@@ -118,7 +123,8 @@ where
 		at: Option<<Block as traits::Block>::Hash>,
 	) -> RpcResult<Bytes> {
 		self.deny_unsafe.check_if_safe()?;
-		let api = self.client.runtime_api();
+		let mut api = self.client.runtime_api();
+		api.register_extension(RpcCallExt(RpcCallKind::RuntimeApi));
 		let best_hash = at.unwrap_or_else(||
 			// If the block hash is not supplied assume the best block.
 			self.client.info().best_hash);